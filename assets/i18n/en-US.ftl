@@ -0,0 +1,552 @@
+## Window titles
+
+title = Arkiv Katalog
+title-window = Arkiv Katalog: { $name }
+title-settings = Settings
+title-home = Home
+title-about = About
+title-shortcuts = Keyboard Shortcuts
+title-undo-history = Undo History
+title-statistics = Statistics
+title-calendar = Calendar
+title-recently-deleted = Recently Deleted
+title-edit-metadata = Edit Metadata
+
+## Common buttons
+
+button-back = Back
+button-ok = OK
+button-cancel = Cancel
+button-apply = Apply
+button-revert = Revert
+button-save = Save
+button-load = Load
+button-discard = Discard
+button-add = Add
+button-remove = Remove
+button-keep-mine = Keep Mine
+button-browse = Browse...
+button-rename = Rename
+button-move = Move
+button-copy = Copy
+button-extract = Extract
+
+## Settings window
+
+settings-language = Language
+settings-language-system = System
+settings-theme = Theme
+settings-reader-theme = Reader Theme
+settings-activation = Activation
+settings-card-size = Card Size
+settings-swipe-distance = Swipe Distance
+settings-pinch-distance = Pinch Distance
+settings-slideshow-interval = Slideshow Interval
+settings-slideshow-loop = Loop Slideshows
+settings-off = Off
+settings-thumbnail-cache = Thumbnail Cache
+settings-hover-preview = Hover Preview
+settings-file-sizes = File Sizes
+settings-scale = Scale
+settings-font-size = Font Size
+settings-font-size-restart = { $size }px, after restart
+settings-convert-quality = Convert Quality
+settings-keep-pages = Keep Pages
+settings-battery-saver = Battery Saver
+settings-animate-covers = Animate Covers
+settings-cover-accents = Tint Cards with Cover Colors
+settings-section-headers = Group Grids by First Letter
+settings-freedesktop-thumbnails = Share Covers with File Managers
+settings-task-notifications = Notify When Tasks Finish in the Background
+settings-skip-hidden = Skip Hidden Files
+settings-follow-symlinks = Follow Symlinks
+settings-remember-window-size = Remember Window Size
+settings-remember-window-position = Remember Window Position
+settings-continue-reading-shelf = Continue Reading Shelf
+settings-sidebar = Sidebar
+settings-library-roots = Library Roots
+settings-directory = Directory
+settings-card-badges = Card Badges
+settings-home = Home
+settings-back-up = Back Up
+settings-backups = Backups
+settings-restore = Restore
+settings-unapplied = Unapplied changes
+settings-conflict = Config file changed on disk
+activation-single-click = Single Click
+activation-double-click = Double Click
+card-size-compact = Compact
+card-size-comfortable = Comfortable
+card-size-spacious = Spacious
+card-size-custom = Custom ({ $mm } mm)
+theme-auto = Auto
+theme-same-as-theme = Same as Theme
+
+## Status bar
+
+status-new-window = New Window
+status-tasks = Tasks
+status-about = About
+status-copy-launch-command = Copy Launch Command
+status-slideshow = Slideshow
+status-stop-slideshow = Stop Slideshow
+status-items =
+    { $count ->
+        [one] { $count } item
+       *[other] { $count } items
+    }
+status-filtered-items = { $shown } of { $count } items
+status-selected = { $count } selected
+status-pages =
+    { $count ->
+        [one] { $count } page
+       *[other] { $count } pages
+    }
+status-pages-left =
+    { $count ->
+        [one] { $count } page
+       *[other] { $count } pages
+    }, { $left } left
+status-modified = modified { $time }
+status-selected-size = { $count } selected, { $size }
+status-selected-size-left = { $count } selected, { $size }, { $left } left
+status-scanning = scanning
+status-loading = loading { $count }
+status-active-tasks =
+    { $count ->
+        [one] { $count } task
+       *[other] { $count } tasks
+    }
+status-profile = profile:
+status-problems-found =
+    { $count ->
+        [one] Found { $count } problem on startup, run `arkiv-katalog doctor` for fixes
+       *[other] Found { $count } problems on startup, run `arkiv-katalog doctor` for fixes
+    }
+
+## Sidebar
+
+sidebar-library = Library
+sidebar-reading-lists = Reading Lists
+sidebar-tags = Tags
+sidebar-recent = Recent
+
+## Context menu of items
+
+menu-mark-read = Mark as Read
+menu-mark-unread = Mark as Unread
+menu-copy-path = Copy Path
+menu-copy-name = Copy Name
+menu-copy-cover = Copy Cover
+menu-move-earlier = Move Earlier
+menu-move-later = Move Later
+menu-rename = Rename...
+menu-move-to = Move to...
+menu-extract-here = Extract Here
+menu-extract-to = Extract to...
+menu-pack = Pack as CBZ...
+menu-edit-metadata = Edit Metadata...
+menu-move-to-trash = Move to Trash
+menu-convert = Convert to CBZ
+menu-verify = Verify
+menu-write-thumbnails = Write Thumbnails
+menu-add-to-list = Add to { $list }
+menu-remove-from-list = Remove from List
+menu-choose-cover = Choose Cover Image...
+menu-reset-cover = Reset Cover
+menu-open-with = Open With...
+menu-open-with-application = Open with { $application }
+
+## About window
+
+about-version = Version
+about-revision = Revision
+about-built = Built
+about-license = License
+about-dependencies = Dependencies
+about-no-license = No license text was found for this dependency.
+
+## Panes
+
+pane-new = New
+pane-drop-hint = Release to move here, hold Ctrl to copy
+pane-badge-format = Format
+pane-badge-page-count = Page Count
+pane-badge-unread = Unread
+pane-offline = OFFLINE
+pane-new-badge = NEW
+pane-page-count = { $count }p
+pane-new-list = New List...
+pane-filter = Filter
+pane-shelf = Shelf
+pane-reading-order = Reading Order
+pane-reading-lists = Reading Lists
+pane-opds-feeds = OPDS Feeds
+pane-new-volumes = { $count } New
+pane-follow = Follow
+pane-following = Following
+pane-wishlist = Wishlist:
+pane-wish-missing = Wish for { $count } Missing
+pane-series = { $name } ({ $count })
+pane-series-left = { $name } ({ $count }), { $left } left
+pane-open = Open...
+pane-recent = Recent
+pane-quick-look-title =
+    { $name }, { $count ->
+        [one] { $count } page
+       *[other] { $count } pages
+    }
+pane-read = Read
+pane-close = Close
+pane-grid = Grid
+pane-list = List
+pane-detail = Detail
+pane-column-name = Name
+pane-column-series = Series
+pane-column-pages = Pages
+pane-column-size = Size
+pane-column-modified = Modified
+pane-offline-name = { $name } (Offline)
+pane-nothing-selected = Nothing selected
+pane-reading-page = Reading, page { $page }
+pane-author = Author
+pane-isbn = ISBN
+pane-progress = Progress
+pane-file = File
+opds-untitled = Untitled
+opds-next-page = Next Page
+
+## Dialogs
+
+dialog-restore-backup = Restore Backup
+dialog-restore-backup-body = Replace settings and catalogue of profile with those of { $name }?
+dialog-restoring-backup = Restoring Backup
+dialog-new-reading-list = New Reading List
+dialog-name = Name
+dialog-apply-to-all = Apply to All
+dialog-file-exists = { $name } Already Exists
+dialog-file-exists-body = A file of the same name is in { $dir }.
+dialog-conflict-skip = Skip
+dialog-conflict-overwrite = Overwrite
+dialog-conflict-rename = Keep Both
+dialog-directory = Directory
+dialog-rename = Rename { $name }
+dialog-move = Move { $name }
+dialog-extract = Extract { $name }
+dialog-extract-archives = Extract { $count } archives
+dialog-archives = { $count } archives
+dialog-transfer-move = Move { $name } to { $dir }?
+dialog-transfer-copy = Copy { $name } to { $dir }?
+dialog-moving = Moving Archives
+dialog-copying = Copying Archives
+dialog-transfer-progress = { $done } of { $count } archives
+dialog-open-with = Open { $name } with
+dialog-remember-once = Just This Time
+dialog-remember-item = Always for This Archive
+dialog-remember-kind = Always for { $kind } Archives
+dialog-pack = Pack { $name } as CBZ
+dialog-pack-write-info = Write ComicInfo.xml
+dialog-pack-trash = Move Folder to Trash
+button-open = Open
+button-pack = Pack
+default-reading-list = Reading List { $number }
+dialog-extract-to = Extract to
+dialog-move-to = Move to
+dialog-choose-cover = Choose Cover
+dialog-filter-images = Images
+
+## Common text
+
+common-loading = Loading...
+common-page = Page { $page }
+common-page-of = { $page } / { $count }
+
+## Reader
+
+reader-brightness = Brightness
+reader-contrast = Contrast
+reader-gamma = Gamma
+reader-invert = Invert
+reader-remember-adjustments = Remember for Archive
+reader-reset = Reset
+reader-bookmarks = Bookmarks
+reader-bookmark = Bookmark
+reader-remove-bookmark = Remove Bookmark
+reader-adjust = Adjust
+reader-slideshow = Slideshow
+reader-stop-slideshow = Stop Slideshow
+reader-scroll = Scroll
+reader-pages = Pages
+reader-use-as-cover = Use as Cover
+
+## Tasks
+
+task-title =
+    { $kind ->
+        [convert] Convert { $name }
+        [extract] Extract { $name }
+        [verify] Verify { $name }
+       *[thumbnails] Thumbnails of { $name }
+    }
+task-queued = queued
+task-done = done
+task-cancelled = cancelled
+task-finished = { $title } finished
+task-all-finished =
+    { $count ->
+        [one] One background task finished
+       *[other] { $count } background tasks finished
+    }
+task-summary = { $done } done, { $failed } failed, { $cancelled } cancelled
+task-panel = Tasks
+task-cancel-all = Cancel All
+task-clear-finished = Clear Finished
+task-hide = Hide
+task-none = No tasks
+
+## Read states
+
+state-unread = Unread
+state-reading = Reading
+state-finished = Finished
+
+## Statistics window
+
+stats-archives = Archives
+stats-pages = Pages
+stats-starred = Starred
+stats-weeks-ago =
+    { $weeks ->
+        [0] This week
+        [1] Last week
+       *[other] { $weeks } weeks ago
+    }
+stats-computing = Computing...
+stats-no-archives = No archives found
+stats-pages-read = Pages Read
+stats-disk-usage = Disk Usage
+
+## Home window
+
+home-continue-reading = Continue Reading
+home-recently-added = Recently Added
+home-random-picks = Random Picks
+home-library = Library
+home-reading-at = { $name }, page { $page }
+home-stats = { $seen } archives, { $reading } reading, { $finished } finished, { $starred } starred, { $pages } pages
+home-shuffle = Shuffle
+home-empty = Nothing here yet
+
+## Undo history window
+
+history-marked =
+    Marked { $count ->
+        [one] { $count } item
+       *[other] { $count } items
+    } as { $state }
+history-was = was { $state }
+history-more = and { $count } more
+history-empty = Nothing to undo
+
+## Metadata editor
+
+metadata-archives =
+    { $count ->
+        [one] { $count } archive
+       *[other] { $count } archives
+    }
+metadata-reading = Reading metadata of { $title }...
+metadata-edit = Edit { $title }
+metadata-field =
+    { $field ->
+        [Title] Title
+        [Series] Series
+        [Number] Number
+        [Writer] Writer
+        [Year] Year
+       *[Tags] Tags
+    }
+metadata-multiple-values = Multiple values
+metadata-review = Review Changes
+metadata-will-be-written =
+    Metadata of { $count ->
+        [one] { $count } archive
+       *[other] { $count } archives
+    } will be written
+metadata-write = Write
+metadata-writing = Writing...
+
+## Calendar window
+
+calendar-weekday-short =
+    { $weekday ->
+        [1] Mon
+        [2] Tue
+        [3] Wed
+        [4] Thu
+        [5] Fri
+        [6] Sat
+       *[7] Sun
+    }
+calendar-weekday =
+    { $weekday ->
+        [1] Monday
+        [2] Tuesday
+        [3] Wednesday
+        [4] Thursday
+        [5] Friday
+        [6] Saturday
+       *[7] Sunday
+    }
+calendar-month-name =
+    { $month ->
+        [1] January
+        [2] February
+        [3] March
+        [4] April
+        [5] May
+        [6] June
+        [7] July
+        [8] August
+        [9] September
+        [10] October
+        [11] November
+       *[12] December
+    }
+calendar-month = { $name } { $year }
+calendar-day = { $weekday } { $day } { $month }
+calendar-added = Added
+calendar-finished = Finished
+calendar-empty = Nothing added or finished
+
+## Recently deleted window
+
+trash-empty = Nothing has been moved to trash
+trash-deleted = { $dir }, deleted { $date }
+trash-restore = Restore
+trash-forget = Forget
+
+## Formatting
+
+format-binary = Binary (KiB)
+format-decimal = Decimal (kB)
+format-just-now = just now
+format-yesterday = yesterday
+format-minutes-ago =
+    { $count ->
+        [one] { $count } minute ago
+       *[other] { $count } minutes ago
+    }
+format-hours-ago =
+    { $count ->
+        [one] { $count } hour ago
+       *[other] { $count } hours ago
+    }
+format-days-ago =
+    { $count ->
+        [one] { $count } day ago
+       *[other] { $count } days ago
+    }
+format-weeks-ago =
+    { $count ->
+        [one] { $count } week ago
+       *[other] { $count } weeks ago
+    }
+format-months-ago =
+    { $count ->
+        [one] { $count } month ago
+       *[other] { $count } months ago
+    }
+format-years-ago =
+    { $count ->
+        [one] { $count } year ago
+       *[other] { $count } years ago
+    }
+format-under-a-minute = < 1 min
+format-minutes = { $minutes } min
+format-hours = { $hours } h
+format-hours-minutes = { $hours } h { $minutes } min
+
+## Keyboard shortcuts window
+
+action-none = None
+action-back = Back
+action-forward = Forward
+action-new-pane = New Pane
+action-close-pane = Close Pane
+action-toggle-settings = Toggle Settings
+action-toggle-undo-history = Toggle Undo History
+action-toggle-statistics = Toggle Statistics
+action-toggle-calendar = Toggle Calendar
+action-toggle-trash = Toggle Recently Deleted
+action-toggle-debug-overlay = Toggle Debug Overlay
+action-copy-launch-command = Copy Launch Command
+action-mark-read = Mark as Read
+action-mark-unread = Mark as Unread
+action-rename = Rename Archive Under Cursor
+action-undo = Undo
+action-back-up-profile = Back Up Profile
+action-toggle-sidebar = Toggle Sidebar
+action-toggle-shortcuts = Toggle Keyboard Shortcuts
+action-toggle-about = Toggle About
+mouse-middle = Middle Button
+mouse-back = Back Button
+mouse-forward = Forward Button
+context-global = All Windows
+context-main = Main Windows
+context-reader = Reader
+shortcut-zoom-in = Scale Up
+shortcut-zoom-out = Scale Down
+shortcut-reset-zoom = Reset Scale
+shortcut-undo = Undo
+shortcut-move-cursor = Move Cursor, Shift Selects
+shortcut-mark-read = Mark as Read
+shortcut-mark-unread = Mark as Unread
+shortcut-filter = Filter
+shortcut-cycle-sort = Change Sort Order
+shortcut-close-pane = Close Pane
+shortcut-copy-paths = Copy Paths
+shortcut-copy-names = Copy Names
+shortcut-paste = Open Pasted Paths
+shortcut-rescan = Scan Again
+shortcut-type-ahead = Jump to Typed Name
+shortcut-activate = Open
+shortcut-quick-look = Quick Look
+shortcut-move-to-trash = Move to Trash
+shortcut-cancel = Close Menus and Dialogs
+shortcut-fullscreen = Toggle Fullscreen
+shortcut-leave-fullscreen = Leave Fullscreen
+shortcut-bookmark = Toggle Bookmark
+shortcut-next-page = Next Page
+shortcut-previous-page = Previous Page
+shortcut-page-strip = Toggle Page Thumbnails
+shortcut-continuous = Toggle Continuous Scrolling
+shortcut-slideshow = Start or Stop Slideshow
+help-function-keys = Function Keys
+help-mouse-buttons = Mouse Buttons
+
+## Notifications
+
+notify-new-volume = New volume of { $series }
+notify-new-volumes = { $count } new volumes of followed series
+notify-profile-backed-up = Profile backed up
+notify-scanned = Scanned { $name }
+notify-items =
+    { $count ->
+        [one] { $count } item
+       *[other] { $count } items
+    }
+
+## Errors
+
+error-folder-missing = Folder { $name } does not exist
+error-folder-denied = Not allowed to open folder { $name }
+error-folder-unreadable = Could not open folder { $name }
+error-archive = Could not read { $name }, { $reason }
+error-config =
+    { $action ->
+        [save] Could not save { $name }, { $reason }
+       *[load] Could not load { $name }, { $reason }
+    }
+error-catalogue = Could not update catalogue, { $reason }
+error-feed = Could not load feed, { $reason }
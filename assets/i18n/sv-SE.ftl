@@ -0,0 +1,552 @@
+## Window titles
+
+title = Arkiv Katalog
+title-window = Arkiv Katalog: { $name }
+title-settings = Inställningar
+title-home = Hem
+title-about = Om
+title-shortcuts = Kortkommandon
+title-undo-history = Ångrahistorik
+title-statistics = Statistik
+title-calendar = Kalender
+title-recently-deleted = Nyligen borttagna
+title-edit-metadata = Redigera metadata
+
+## Common buttons
+
+button-back = Tillbaka
+button-ok = OK
+button-cancel = Avbryt
+button-apply = Verkställ
+button-revert = Återställ
+button-save = Spara
+button-load = Läs in
+button-discard = Kasta
+button-add = Lägg till
+button-remove = Ta bort
+button-keep-mine = Behåll mina
+button-browse = Bläddra...
+button-rename = Byt namn
+button-move = Flytta
+button-copy = Kopiera
+button-extract = Packa upp
+
+## Settings window
+
+settings-language = Språk
+settings-language-system = System
+settings-theme = Tema
+settings-reader-theme = Läsartema
+settings-activation = Aktivering
+settings-card-size = Kortstorlek
+settings-swipe-distance = Svepavstånd
+settings-pinch-distance = Nypavstånd
+settings-slideshow-interval = Bildspelsintervall
+settings-slideshow-loop = Upprepa bildspel
+settings-off = Av
+settings-thumbnail-cache = Miniatyrcache
+settings-hover-preview = Förhandsvisning
+settings-file-sizes = Filstorlekar
+settings-scale = Skala
+settings-font-size = Textstorlek
+settings-font-size-restart = { $size }px, efter omstart
+settings-convert-quality = Konverteringskvalitet
+settings-keep-pages = Behåll sidor
+settings-battery-saver = Batterisparläge
+settings-animate-covers = Animera omslag
+settings-cover-accents = Färga kort efter omslag
+settings-section-headers = Gruppera rutnät efter begynnelsebokstav
+settings-freedesktop-thumbnails = Dela omslag med filhanterare
+settings-task-notifications = Meddela när uppgifter blir klara i bakgrunden
+settings-skip-hidden = Hoppa över dolda filer
+settings-follow-symlinks = Följ symboliska länkar
+settings-remember-window-size = Kom ihåg fönsterstorlek
+settings-remember-window-position = Kom ihåg fönsterposition
+settings-continue-reading-shelf = Fortsätt läsa-hylla
+settings-sidebar = Sidopanel
+settings-library-roots = Biblioteksrötter
+settings-directory = Katalog
+settings-card-badges = Kortmärken
+settings-home = Hem
+settings-back-up = Säkerhetskopiera
+settings-backups = Säkerhetskopior
+settings-restore = Återställ
+settings-unapplied = Ändringar som inte verkställts
+settings-conflict = Inställningsfilen ändrades på disk
+activation-single-click = Enkelklick
+activation-double-click = Dubbelklick
+card-size-compact = Kompakt
+card-size-comfortable = Bekväm
+card-size-spacious = Rymlig
+card-size-custom = Anpassad ({ $mm } mm)
+theme-auto = Automatiskt
+theme-same-as-theme = Samma som tema
+
+## Status bar
+
+status-new-window = Nytt fönster
+status-tasks = Uppgifter
+status-about = Om
+status-copy-launch-command = Kopiera startkommando
+status-slideshow = Bildspel
+status-stop-slideshow = Stoppa bildspel
+status-items =
+    { $count ->
+        [one] { $count } objekt
+       *[other] { $count } objekt
+    }
+status-filtered-items = { $shown } av { $count } objekt
+status-selected = { $count } markerade
+status-pages =
+    { $count ->
+        [one] { $count } sida
+       *[other] { $count } sidor
+    }
+status-pages-left =
+    { $count ->
+        [one] { $count } sida
+       *[other] { $count } sidor
+    }, { $left } kvar
+status-modified = ändrad { $time }
+status-selected-size = { $count } markerade, { $size }
+status-selected-size-left = { $count } markerade, { $size }, { $left } kvar
+status-scanning = söker igenom
+status-loading = läser in { $count }
+status-active-tasks =
+    { $count ->
+        [one] { $count } uppgift
+       *[other] { $count } uppgifter
+    }
+status-profile = profil:
+status-problems-found =
+    { $count ->
+        [one] Hittade { $count } problem vid start, kör `arkiv-katalog doctor` för åtgärder
+       *[other] Hittade { $count } problem vid start, kör `arkiv-katalog doctor` för åtgärder
+    }
+
+## Sidebar
+
+sidebar-library = Bibliotek
+sidebar-reading-lists = Läslistor
+sidebar-tags = Taggar
+sidebar-recent = Senaste
+
+## Context menu of items
+
+menu-mark-read = Markera som läst
+menu-mark-unread = Markera som oläst
+menu-copy-path = Kopiera sökväg
+menu-copy-name = Kopiera namn
+menu-copy-cover = Kopiera omslag
+menu-move-earlier = Flytta tidigare
+menu-move-later = Flytta senare
+menu-rename = Byt namn...
+menu-move-to = Flytta till...
+menu-extract-here = Packa upp här
+menu-extract-to = Packa upp till...
+menu-pack = Packa som CBZ...
+menu-edit-metadata = Redigera metadata...
+menu-move-to-trash = Flytta till papperskorgen
+menu-convert = Konvertera till CBZ
+menu-verify = Verifiera
+menu-write-thumbnails = Skriv miniatyrer
+menu-add-to-list = Lägg till i { $list }
+menu-remove-from-list = Ta bort från listan
+menu-choose-cover = Välj omslagsbild...
+menu-reset-cover = Återställ omslag
+menu-open-with = Öppna med...
+menu-open-with-application = Öppna med { $application }
+
+## About window
+
+about-version = Version
+about-revision = Revision
+about-built = Byggd
+about-license = Licens
+about-dependencies = Beroenden
+about-no-license = Ingen licenstext hittades för det här beroendet.
+
+## Panes
+
+pane-new = Ny
+pane-drop-hint = Släpp för att flytta hit, håll ned Ctrl för att kopiera
+pane-badge-format = Format
+pane-badge-page-count = Sidantal
+pane-badge-unread = Oläst
+pane-offline = OFFLINE
+pane-new-badge = NY
+pane-page-count = { $count } s
+pane-new-list = Ny lista...
+pane-filter = Filtrera
+pane-shelf = Hylla
+pane-reading-order = Läsordning
+pane-reading-lists = Läslistor
+pane-opds-feeds = OPDS-flöden
+pane-new-volumes = { $count } nya
+pane-follow = Följ
+pane-following = Följer
+pane-wishlist = Önskelista:
+pane-wish-missing = Önska { $count } saknade
+pane-series = { $name } ({ $count })
+pane-series-left = { $name } ({ $count }), { $left } kvar
+pane-open = Öppna...
+pane-recent = Senaste
+pane-quick-look-title =
+    { $name }, { $count ->
+        [one] { $count } sida
+       *[other] { $count } sidor
+    }
+pane-read = Läs
+pane-close = Stäng
+pane-grid = Rutnät
+pane-list = Lista
+pane-detail = Detaljer
+pane-column-name = Namn
+pane-column-series = Serie
+pane-column-pages = Sidor
+pane-column-size = Storlek
+pane-column-modified = Ändrad
+pane-offline-name = { $name } (offline)
+pane-nothing-selected = Inget markerat
+pane-reading-page = Läser, sida { $page }
+pane-author = Författare
+pane-isbn = ISBN
+pane-progress = Framsteg
+pane-file = Fil
+opds-untitled = Namnlös
+opds-next-page = Nästa sida
+
+## Dialogs
+
+dialog-restore-backup = Återställ säkerhetskopia
+dialog-restore-backup-body = Ersätt profilens inställningar och katalog med de i { $name }?
+dialog-restoring-backup = Återställer säkerhetskopia
+dialog-new-reading-list = Ny läslista
+dialog-name = Namn
+dialog-apply-to-all = Använd för alla
+dialog-file-exists = { $name } finns redan
+dialog-file-exists-body = En fil med samma namn finns i { $dir }.
+dialog-conflict-skip = Hoppa över
+dialog-conflict-overwrite = Skriv över
+dialog-conflict-rename = Behåll båda
+dialog-directory = Mapp
+dialog-rename = Byt namn på { $name }
+dialog-move = Flytta { $name }
+dialog-extract = Packa upp { $name }
+dialog-extract-archives = Packa upp { $count } arkiv
+dialog-archives = { $count } arkiv
+dialog-transfer-move = Flytta { $name } till { $dir }?
+dialog-transfer-copy = Kopiera { $name } till { $dir }?
+dialog-moving = Flyttar arkiv
+dialog-copying = Kopierar arkiv
+dialog-transfer-progress = { $done } av { $count } arkiv
+dialog-open-with = Öppna { $name } med
+dialog-remember-once = Bara den här gången
+dialog-remember-item = Alltid för det här arkivet
+dialog-remember-kind = Alltid för { $kind }-arkiv
+dialog-pack = Packa { $name } som CBZ
+dialog-pack-write-info = Skriv ComicInfo.xml
+dialog-pack-trash = Flytta mappen till papperskorgen
+button-open = Öppna
+button-pack = Packa
+default-reading-list = Läslista { $number }
+dialog-extract-to = Packa upp till
+dialog-move-to = Flytta till
+dialog-choose-cover = Välj omslag
+dialog-filter-images = Bilder
+
+## Common text
+
+common-loading = Läser in...
+common-page = Sida { $page }
+common-page-of = { $page } / { $count }
+
+## Reader
+
+reader-brightness = Ljusstyrka
+reader-contrast = Kontrast
+reader-gamma = Gamma
+reader-invert = Invertera
+reader-remember-adjustments = Kom ihåg för arkivet
+reader-reset = Återställ
+reader-bookmarks = Bokmärken
+reader-bookmark = Bokmärk
+reader-remove-bookmark = Ta bort bokmärke
+reader-adjust = Justera
+reader-slideshow = Bildspel
+reader-stop-slideshow = Stoppa bildspel
+reader-scroll = Rulla
+reader-pages = Sidor
+reader-use-as-cover = Använd som omslag
+
+## Tasks
+
+task-title =
+    { $kind ->
+        [convert] Konvertera { $name }
+        [extract] Packa upp { $name }
+        [verify] Kontrollera { $name }
+       *[thumbnails] Miniatyrer av { $name }
+    }
+task-queued = i kö
+task-done = klar
+task-cancelled = avbruten
+task-finished = { $title } är klar
+task-all-finished =
+    { $count ->
+        [one] En bakgrundsuppgift är klar
+       *[other] { $count } bakgrundsuppgifter är klara
+    }
+task-summary = { $done } klara, { $failed } misslyckade, { $cancelled } avbrutna
+task-panel = Uppgifter
+task-cancel-all = Avbryt alla
+task-clear-finished = Rensa klara
+task-hide = Dölj
+task-none = Inga uppgifter
+
+## Read states
+
+state-unread = Oläst
+state-reading = Läser
+state-finished = Utläst
+
+## Statistics window
+
+stats-archives = Arkiv
+stats-pages = Sidor
+stats-starred = Stjärnmärkta
+stats-weeks-ago =
+    { $weeks ->
+        [0] Den här veckan
+        [1] Förra veckan
+       *[other] { $weeks } veckor sedan
+    }
+stats-computing = Beräknar...
+stats-no-archives = Inga arkiv hittades
+stats-pages-read = Lästa sidor
+stats-disk-usage = Diskanvändning
+
+## Home window
+
+home-continue-reading = Fortsätt läsa
+home-recently-added = Nyligen tillagda
+home-random-picks = Slumpade val
+home-library = Bibliotek
+home-reading-at = { $name }, sida { $page }
+home-stats = { $seen } arkiv, { $reading } påbörjade, { $finished } utlästa, { $starred } stjärnmärkta, { $pages } sidor
+home-shuffle = Blanda
+home-empty = Inget här än
+
+## Undo history window
+
+history-marked =
+    Markerade { $count ->
+        [one] { $count } objekt
+       *[other] { $count } objekt
+    } som { $state }
+history-was = var { $state }
+history-more = och { $count } till
+history-empty = Inget att ångra
+
+## Metadata editor
+
+metadata-archives =
+    { $count ->
+        [one] { $count } arkiv
+       *[other] { $count } arkiv
+    }
+metadata-reading = Läser metadata för { $title }...
+metadata-edit = Redigera { $title }
+metadata-field =
+    { $field ->
+        [Title] Titel
+        [Series] Serie
+        [Number] Nummer
+        [Writer] Författare
+        [Year] År
+       *[Tags] Taggar
+    }
+metadata-multiple-values = Flera värden
+metadata-review = Granska ändringar
+metadata-will-be-written =
+    Metadata för { $count ->
+        [one] { $count } arkiv
+       *[other] { $count } arkiv
+    } kommer att skrivas
+metadata-write = Skriv
+metadata-writing = Skriver...
+
+## Calendar window
+
+calendar-weekday-short =
+    { $weekday ->
+        [1] mån
+        [2] tis
+        [3] ons
+        [4] tor
+        [5] fre
+        [6] lör
+       *[7] sön
+    }
+calendar-weekday =
+    { $weekday ->
+        [1] måndag
+        [2] tisdag
+        [3] onsdag
+        [4] torsdag
+        [5] fredag
+        [6] lördag
+       *[7] söndag
+    }
+calendar-month-name =
+    { $month ->
+        [1] januari
+        [2] februari
+        [3] mars
+        [4] april
+        [5] maj
+        [6] juni
+        [7] juli
+        [8] augusti
+        [9] september
+        [10] oktober
+        [11] november
+       *[12] december
+    }
+calendar-month = { $name } { $year }
+calendar-day = { $weekday } { $day } { $month }
+calendar-added = Tillagd
+calendar-finished = Utläst
+calendar-empty = Inget tillagt eller utläst
+
+## Recently deleted window
+
+trash-empty = Inget har flyttats till papperskorgen
+trash-deleted = { $dir }, raderad { $date }
+trash-restore = Återställ
+trash-forget = Glöm
+
+## Formatting
+
+format-binary = Binär (KiB)
+format-decimal = Decimal (kB)
+format-just-now = nyss
+format-yesterday = i går
+format-minutes-ago =
+    { $count ->
+        [one] { $count } minut sedan
+       *[other] { $count } minuter sedan
+    }
+format-hours-ago =
+    { $count ->
+        [one] { $count } timme sedan
+       *[other] { $count } timmar sedan
+    }
+format-days-ago =
+    { $count ->
+        [one] { $count } dag sedan
+       *[other] { $count } dagar sedan
+    }
+format-weeks-ago =
+    { $count ->
+        [one] { $count } vecka sedan
+       *[other] { $count } veckor sedan
+    }
+format-months-ago =
+    { $count ->
+        [one] { $count } månad sedan
+       *[other] { $count } månader sedan
+    }
+format-years-ago =
+    { $count ->
+        [one] { $count } år sedan
+       *[other] { $count } år sedan
+    }
+format-under-a-minute = < 1 min
+format-minutes = { $minutes } min
+format-hours = { $hours } h
+format-hours-minutes = { $hours } h { $minutes } min
+
+## Keyboard shortcuts window
+
+action-none = Ingen
+action-back = Bakåt
+action-forward = Framåt
+action-new-pane = Ny panel
+action-close-pane = Stäng panel
+action-toggle-settings = Visa/dölj inställningar
+action-toggle-undo-history = Visa/dölj ångrahistorik
+action-toggle-statistics = Visa/dölj statistik
+action-toggle-calendar = Visa/dölj kalender
+action-toggle-trash = Visa/dölj nyligen raderade
+action-toggle-debug-overlay = Visa/dölj felsökningsöverlägg
+action-copy-launch-command = Kopiera startkommando
+action-mark-read = Markera som läst
+action-mark-unread = Markera som oläst
+action-rename = Byt namn på arkivet under markören
+action-undo = Ångra
+action-back-up-profile = Säkerhetskopiera profil
+action-toggle-sidebar = Visa/dölj sidofält
+action-toggle-shortcuts = Visa/dölj kortkommandon
+action-toggle-about = Visa/dölj om
+mouse-middle = Mittenknapp
+mouse-back = Bakåtknapp
+mouse-forward = Framåtknapp
+context-global = Alla fönster
+context-main = Huvudfönster
+context-reader = Läsare
+shortcut-zoom-in = Förstora
+shortcut-zoom-out = Förminska
+shortcut-reset-zoom = Återställ skala
+shortcut-undo = Ångra
+shortcut-move-cursor = Flytta markör, Skift markerar
+shortcut-mark-read = Markera som läst
+shortcut-mark-unread = Markera som oläst
+shortcut-filter = Filtrera
+shortcut-cycle-sort = Ändra sortering
+shortcut-close-pane = Stäng panel
+shortcut-copy-paths = Kopiera sökvägar
+shortcut-copy-names = Kopiera namn
+shortcut-paste = Öppna inklistrade sökvägar
+shortcut-rescan = Sök igenom igen
+shortcut-type-ahead = Hoppa till skrivet namn
+shortcut-activate = Öppna
+shortcut-quick-look = Snabbtitt
+shortcut-move-to-trash = Flytta till papperskorgen
+shortcut-cancel = Stäng menyer och dialoger
+shortcut-fullscreen = Växla helskärm
+shortcut-leave-fullscreen = Lämna helskärm
+shortcut-bookmark = Växla bokmärke
+shortcut-next-page = Nästa sida
+shortcut-previous-page = Föregående sida
+shortcut-page-strip = Visa/dölj sidminiatyrer
+shortcut-continuous = Växla kontinuerlig rullning
+shortcut-slideshow = Starta eller stoppa bildspel
+help-function-keys = Funktionstangenter
+help-mouse-buttons = Musknappar
+
+## Notifications
+
+notify-new-volume = Ny volym av { $series }
+notify-new-volumes = { $count } nya volymer av följda serier
+notify-profile-backed-up = Profil säkerhetskopierad
+notify-scanned = Genomsökte { $name }
+notify-items =
+    { $count ->
+        [one] { $count } objekt
+       *[other] { $count } objekt
+    }
+
+## Errors
+
+error-folder-missing = Mappen { $name } finns inte
+error-folder-denied = Saknar behörighet att öppna mappen { $name }
+error-folder-unreadable = Kunde inte öppna mappen { $name }
+error-archive = Kunde inte läsa { $name }, { $reason }
+error-config =
+    { $action ->
+        [save] Kunde inte spara { $name }, { $reason }
+       *[load] Kunde inte läsa in { $name }, { $reason }
+    }
+error-catalogue = Kunde inte uppdatera katalogen, { $reason }
+error-feed = Kunde inte läsa in flödet, { $reason }
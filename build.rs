@@ -0,0 +1,191 @@
+//! Build script recording the git revision and time of the build, and collecting license
+//! texts of dependencies from the cargo registry for the about window.
+
+use ::std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::SystemTime,
+};
+
+/// Print an instruction to cargo.
+///
+/// # Errors
+/// If stdout cannot be written to.
+fn instruct(instruction: &str) -> io::Result<()> {
+    writeln!(io::stdout().lock(), "cargo::{instruction}")
+}
+
+/// Get short hash of the checked out git revision, if built from a git repository.
+fn git_hash(manifest_dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(manifest_dir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        .filter(|hash| !hash.is_empty())
+}
+
+/// Get time of build in seconds since the unix epoch, `SOURCE_DATE_EPOCH` is used if set
+/// such that builds may be reproduced.
+fn build_time() -> u64 {
+    env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs())
+        })
+}
+
+/// Get names and versions of packages from crates.io in lock file.
+fn locked_packages(lock: &str) -> Vec<(String, String)> {
+    let mut packages = Vec::new();
+    for package in lock.split("[[package]]").skip(1) {
+        let mut name = None;
+        let mut version = None;
+        let mut registry = false;
+        for line in package.lines() {
+            let Some((key, value)) = line.split_once(" = ") else {
+                continue;
+            };
+            let value = value.trim_matches('"');
+            match key {
+                "name" => name = Some(value.to_owned()),
+                "version" => version = Some(value.to_owned()),
+                "source" => registry = value.starts_with("registry+"),
+                _ => {}
+            }
+        }
+        if let (Some(name), Some(version), true) = (name, version, registry) {
+            packages.push((name, version));
+        }
+    }
+    packages
+}
+
+/// Get license texts of a package extracted in a directory, sorted by file name.
+fn license_texts(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path.file_name().is_some_and(|name| {
+                    let name = name.to_string_lossy().to_ascii_lowercase();
+                    ["license", "licence", "copying", "unlicense"]
+                        .iter()
+                        .any(|prefix| name.starts_with(prefix))
+                })
+        })
+        .collect::<Vec<_>>();
+    files.sort_unstable();
+    files
+        .into_iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect()
+}
+
+/// Write rust source of dependencies and their deduplicated license texts to path.
+/// Packages missing from the registry, such as those of other platforms, are skipped.
+///
+/// # Errors
+/// If the source cannot be written.
+fn write_licenses(lock: &str, registry: &[PathBuf], path: &Path) -> io::Result<()> {
+    let mut texts = BTreeMap::<String, usize>::new();
+    let mut dependencies = Vec::new();
+    for (name, version) in locked_packages(lock) {
+        let Some(dir) = registry
+            .iter()
+            .map(|index| index.join(format!("{name}-{version}")))
+            .find(|dir| dir.is_dir())
+        else {
+            continue;
+        };
+        let indices = license_texts(&dir)
+            .into_iter()
+            .map(|text| {
+                let next = texts.len();
+                *texts.entry(text).or_insert(next)
+            })
+            .collect::<Vec<_>>();
+        dependencies.push((name, version, indices));
+    }
+    let mut ordered = texts.into_iter().collect::<Vec<_>>();
+    ordered.sort_unstable_by_key(|(_, index)| *index);
+
+    let mut source = String::new();
+    source.push_str(
+        "/// License texts of dependencies, shared by dependencies using the same text.\n",
+    );
+    source.push_str("pub static LICENSE_TEXTS: &[&str] = &[\n");
+    for (text, _) in ordered {
+        source.push_str(&format!("    {text:?},\n"));
+    }
+    source.push_str("];\n\n");
+    source.push_str(
+        "/// Name, version and indices into [LICENSE_TEXTS] of license texts of dependencies.\n",
+    );
+    source.push_str("pub static DEPENDENCIES: &[(&str, &str, &[usize])] = &[\n");
+    for (name, version, indices) in dependencies {
+        source.push_str(&format!("    ({name:?}, {version:?}, &{indices:?}),\n"));
+    }
+    source.push_str("];\n");
+    fs::write(path, source)
+}
+
+/// Run build script.
+///
+/// # Errors
+/// If instructions or license texts cannot be written.
+fn main() -> io::Result<()> {
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap_or_default());
+
+    instruct("rerun-if-changed=build.rs")?;
+    instruct("rerun-if-changed=Cargo.lock")?;
+    instruct("rerun-if-env-changed=SOURCE_DATE_EPOCH")?;
+    let git_dir = manifest_dir.join(".git");
+    if git_dir.join("HEAD").exists() {
+        instruct(&format!(
+            "rerun-if-changed={}",
+            git_dir.join("HEAD").display()
+        ))?;
+        instruct(&format!(
+            "rerun-if-changed={}",
+            git_dir.join("refs").display()
+        ))?;
+    }
+
+    let hash = git_hash(&manifest_dir).unwrap_or_else(|| "unknown".to_owned());
+    instruct(&format!("rustc-env=ARKIV_KATALOG_GIT_HASH={hash}"))?;
+    instruct(&format!(
+        "rustc-env=ARKIV_KATALOG_BUILD_TIME={}",
+        build_time()
+    ))?;
+
+    let cargo_home = env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")))
+        .unwrap_or_default();
+    let registry = fs::read_dir(cargo_home.join("registry").join("src"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let lock = fs::read_to_string(manifest_dir.join("Cargo.lock")).unwrap_or_default();
+    write_licenses(&lock, &registry, &out_dir.join("licenses.rs"))
+}
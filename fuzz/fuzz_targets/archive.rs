@@ -0,0 +1,29 @@
+//! Fuzz opening and reading archives, the first byte selects the archive format.
+
+#![no_main]
+
+use ::arkiv_katalog::archive::{Archive, preview};
+use ::libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&kind, content)) = data.split_first() else {
+        return;
+    };
+    let ext = match kind % 3 {
+        0 => "cbz",
+        1 => "zip",
+        _ => "epub",
+    };
+
+    let dir = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    let path = dir.path().join(format!("archive.{ext}"));
+    ::std::fs::write(&path, content).expect("temporary file should be writable");
+
+    if let Ok(mut archive) = Archive::open(&path) {
+        _ = archive.metadata();
+        for index in 0..=archive.page_count() {
+            _ = archive.page(index);
+        }
+    }
+    _ = preview(&path, 64);
+});
@@ -0,0 +1,11 @@
+//! Fuzz decoding and downscaling page images.
+
+#![no_main]
+
+use ::arkiv_katalog::archive::Page;
+use ::bytes::Bytes;
+use ::libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    _ = Page::Encoded(Bytes::copy_from_slice(data)).thumbnail(64);
+});
@@ -0,0 +1,122 @@
+//! [About] impl, window showing version and build of application along with licenses of
+//! its dependencies.
+
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+    window,
+};
+use ::tap::Pipe;
+
+use crate::{Message, i18n::tr};
+
+/// Dependencies and their license texts, collected by the build script.
+mod licenses {
+    include!(concat!(env!("OUT_DIR"), "/licenses.rs"));
+}
+
+/// Version of application.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// License of application.
+const LICENSE: &str = env!("CARGO_PKG_LICENSE");
+
+/// Short hash of git revision application was built from, `unknown` if not built from a
+/// git repository.
+const GIT_HASH: &str = env!("ARKIV_KATALOG_GIT_HASH");
+
+/// Time of build in seconds since the unix epoch.
+const BUILD_TIME: &str = env!("ARKIV_KATALOG_BUILD_TIME");
+
+/// State of about window.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct About {
+    /// Index of dependency license texts are shown for.
+    dependency: Option<usize>,
+}
+
+impl About {
+    /// Show license texts of dependency, or the list of dependencies if [None].
+    pub const fn show_dependency(&mut self, dependency: Option<usize>) {
+        self.dependency = dependency;
+    }
+
+    /// View about window.
+    pub fn view(&self, window_id: window::Id) -> Element<'static, Message> {
+        let build_date = BUILD_TIME
+            .parse()
+            .ok()
+            .and_then(|second| ::jiff::Timestamp::from_second(second).ok())
+            .map_or_else(
+                || BUILD_TIME.to_owned(),
+                |timestamp| timestamp.strftime("%Y-%m-%d %H:%M UTC").to_string(),
+            );
+        let header = widget::Column::new()
+            .spacing(3)
+            .push(widget::text(tr("title")).size(20))
+            .push(widget::text(format!("{} {VERSION}", tr("about-version"))))
+            .push(widget::text(format!("{} {GIT_HASH}", tr("about-revision"))))
+            .push(widget::text(format!("{} {build_date}", tr("about-built"))))
+            .push(widget::text(format!("{} {LICENSE}", tr("about-license"))));
+
+        let content: Element<'static, Message> = match self
+            .dependency
+            .and_then(|index| licenses::DEPENDENCIES.get(index))
+        {
+            Some((name, version, texts)) => widget::Column::new()
+                .spacing(5)
+                .push(
+                    widget::Row::new()
+                        .align_y(Center)
+                        .spacing(5)
+                        .push(
+                            widget::button(widget::text(tr("button-back")))
+                                .padding(3)
+                                .style(widget::button::secondary)
+                                .on_press(Message::ShowDependencyLicense(window_id, None)),
+                        )
+                        .push(widget::text(format!("{name} {version}")).size(16)),
+                )
+                .push(
+                    texts
+                        .is_empty()
+                        .then(|| widget::text(tr("about-no-license"))),
+                )
+                .extend(texts.iter().filter_map(|&text| {
+                    licenses::LICENSE_TEXTS.get(text).map(|text| {
+                        widget::text(*text)
+                            .size(12)
+                            .wrapping(Wrapping::WordOrGlyph)
+                            .pipe(widget::container)
+                            .style(widget::container::bordered_box)
+                            .padding(5)
+                            .width(Fill)
+                            .into()
+                    })
+                }))
+                .into(),
+            None => widget::Column::new()
+                .push(widget::text(tr("about-dependencies")).size(16))
+                .extend(licenses::DEPENDENCIES.iter().enumerate().map(
+                    |(index, (name, version, _))| {
+                        widget::button(widget::text(format!("{name} {version}")).size(12))
+                            .padding([0, 4])
+                            .style(widget::button::text)
+                            .on_press(Message::ShowDependencyLicense(window_id, Some(index)))
+                            .into()
+                    },
+                ))
+                .into(),
+        };
+
+        widget::Column::new()
+            .padding(5)
+            .spacing(10)
+            .push(header)
+            .push(content)
+            .pipe(widget::scrollable)
+            .into()
+    }
+}
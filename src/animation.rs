@@ -0,0 +1,101 @@
+//! [Animation] impl, playback of animated covers and pages.
+
+use ::core::time::Duration;
+use ::std::{sync::Arc, time::Instant};
+
+use ::iced::widget::image::Handle;
+
+use crate::{
+    archive::{Adjustments, Frame, Page},
+    cache,
+};
+
+/// Pauses in playback longer than this, such as while the application is suspended,
+/// restart the current frame instead of skipping ahead.
+const MAX_LAG: Duration = Duration::from_secs(1);
+
+/// Interval animations are advanced at.
+pub const TICK: Duration = Duration::from_millis(20);
+
+/// Frames of an animation along with the frame currently shown.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    /// Images of frames and the time they are shown for.
+    frames: Arc<[(Handle, Duration)]>,
+    /// Index of frame currently shown.
+    current: usize,
+    /// Time current frame was shown at.
+    shown_at: Instant,
+}
+
+impl Animation {
+    /// Create an animation from decoded frames, [None] if there are fewer than two.
+    pub fn new(frames: Vec<Frame>, now: Instant) -> Option<Self> {
+        (frames.len() > 1).then(|| Self {
+            frames: frames
+                .into_iter()
+                .map(|Frame { page, delay }| (cache::handle(page), delay))
+                .collect(),
+            current: 0,
+            shown_at: now,
+        })
+    }
+
+    /// Decode frames of a page at full size if it is animated, with adjustments applied.
+    /// Pages which cannot be animated give [None], such that they are shown as still
+    /// images.
+    pub fn of_page(page: &Page, adjustments: &Adjustments) -> Option<Self> {
+        page.animation(None)
+            .and_then(|frames| {
+                frames
+                    .map(|frames| {
+                        frames
+                            .into_iter()
+                            .map(|Frame { page, delay }| {
+                                Ok(Frame {
+                                    page: page.adjusted(adjustments)?,
+                                    delay,
+                                })
+                            })
+                            .collect()
+                    })
+                    .transpose()
+            })
+            .inspect_err(|err| ::log::warn!("could not decode animation\n{err}"))
+            .ok()
+            .flatten()
+            .and_then(|frames| Self::new(frames, Instant::now()))
+    }
+
+    /// Get image of frame currently shown.
+    pub fn frame(&self) -> &Handle {
+        &self.frames[self.current].0
+    }
+
+    /// Get approximate memory used by frames in bytes.
+    pub fn size(&self) -> usize {
+        self.frames
+            .iter()
+            .map(|(handle, _)| cache::handle_size(handle))
+            .sum()
+    }
+
+    /// Advance to the frame which should be shown at the given time, returns true if the
+    /// shown frame changed.
+    pub fn advance(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.shown_at);
+        if elapsed > MAX_LAG {
+            self.shown_at = now;
+            return false;
+        }
+        let mut changed = false;
+        while let Some((_, delay)) = self.frames.get(self.current)
+            && now.saturating_duration_since(self.shown_at) >= *delay
+        {
+            self.shown_at += *delay;
+            self.current = (self.current + 1) % self.frames.len();
+            changed = true;
+        }
+        changed
+    }
+}
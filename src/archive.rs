@@ -0,0 +1,443 @@
+//! Archive reading.
+
+use ::std::{
+    fs,
+    io::Cursor,
+    path::{Path, PathBuf},
+};
+
+use ::bytes::Bytes;
+use ::derive_more::IsVariant;
+
+use crate::{error::ArchiveError, isbn::Isbn, worker::CancelToken};
+
+mod adjust;
+mod animation;
+mod cbz;
+mod comic_info;
+mod convert;
+mod epub;
+mod extract;
+#[cfg(feature = "jxl")]
+mod jxl;
+mod pack;
+#[cfg(feature = "pdf")]
+mod pdf;
+
+#[cfg(feature = "pdf")]
+pub use self::pdf::is_available as has_pdfium;
+pub use self::{
+    adjust::Adjustments,
+    animation::Frame,
+    cbz::{natural_page_indices, read_comic_info, write_comic_info},
+    comic_info::{COMIC_INFO, ComicField, ComicInfo},
+    convert::{convert_to_cbz, has_extractor, is_convertible},
+    extract::{extract_into, extract_to, is_extractable},
+    pack::pack_dir,
+};
+
+/// Result of reading archives.
+pub type Result<T, E = ArchiveError> = ::core::result::Result<T, E>;
+
+/// Max amount of bytes preallocated for an archive entry, sizes in archive headers
+/// may not be trusted.
+const MAX_PREALLOCATION: u64 = 64 * 1024 * 1024;
+
+/// Get capacity to preallocate for an archive entry of given size.
+fn preallocation(size: u64) -> usize {
+    usize::try_from(size.min(MAX_PREALLOCATION)).unwrap_or_default()
+}
+
+/// Bits kept of each color channel when finding the dominant color of a page.
+const COLOR_BITS: u32 = 3;
+
+/// Pixels with an alpha below this are ignored when finding the dominant color of a page.
+const MIN_ALPHA: u8 = 128;
+
+/// Extensions of page images in archives.
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "bmp", "webp", "avif", "jxl"];
+
+/// Check if the extension of a path is equal to one of the given extensions, ignoring case.
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+}
+
+/// Supported archive formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, IsVariant)]
+pub enum ArchiveKind {
+    /// Zip archive of images.
+    Cbz,
+    /// Epub document.
+    Epub,
+    /// Pdf document.
+    #[cfg(feature = "pdf")]
+    Pdf,
+}
+
+impl ArchiveKind {
+    /// Get archive kind of a path from its extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        if has_extension(path, &["cbz", "zip"]) {
+            return Some(Self::Cbz);
+        }
+        if has_extension(path, &["epub"]) {
+            return Some(Self::Epub);
+        }
+        #[cfg(feature = "pdf")]
+        if has_extension(path, &["pdf"]) {
+            return Some(Self::Pdf);
+        }
+        None
+    }
+}
+
+impl ::core::fmt::Display for ArchiveKind {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(match self {
+            ArchiveKind::Cbz => "CBZ",
+            ArchiveKind::Epub => "EPUB",
+            #[cfg(feature = "pdf")]
+            ArchiveKind::Pdf => "PDF",
+        })
+    }
+}
+
+/// Image shown as cover of an archive instead of its first page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cover {
+    /// Page of archive at index.
+    Page(usize),
+    /// Image file outside of archive.
+    Image(PathBuf),
+}
+
+/// Metadata of an archive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    /// Title of archive.
+    pub title: Option<String>,
+    /// Author of archive.
+    pub author: Option<String>,
+    /// Book number of archive.
+    pub isbn: Option<Isbn>,
+}
+
+/// Image of a single page.
+#[derive(Debug, Clone)]
+pub enum Page {
+    /// Page is an encoded image, such as a png or jpeg.
+    Encoded(Bytes),
+    /// Page has been rendered to rgba pixels.
+    Rgba {
+        /// Width of image.
+        width: u32,
+        /// Height of image.
+        height: u32,
+        /// Rgba pixel data of image.
+        pixels: Bytes,
+    },
+}
+
+impl Page {
+    /// Decode page if it is in a format the image widget cannot decode by itself.
+    ///
+    /// # Errors
+    /// If the page needs decoding and decoding fails.
+    #[cfg_attr(not(feature = "jxl"), expect(clippy::missing_const_for_fn))]
+    pub fn decoded(self) -> Result<Self> {
+        match self {
+            #[cfg(feature = "jxl")]
+            Page::Encoded(bytes) if jxl::is_jxl(&bytes) => jxl::decode(&bytes),
+            page => Ok(page),
+        }
+    }
+
+    /// Decode frames of page if it is an animated gif or webp image, downscaled to fit
+    /// within a square of the given size if any. Other pages give [None].
+    ///
+    /// # Errors
+    /// If the page is an animation which cannot be decoded.
+    pub fn animation(&self, size: Option<u32>) -> Result<Option<Vec<Frame>>> {
+        match self {
+            Page::Encoded(bytes) => animation::decode(bytes, size),
+            Page::Rgba { .. } => Ok(None),
+        }
+    }
+
+    /// Find the dominant color of a decoded page, colors are grouped coarsely and
+    /// saturated colors weigh more than grays. [None] for encoded and transparent pages.
+    pub fn dominant_color(&self) -> Option<[u8; 3]> {
+        let Page::Rgba { pixels, .. } = self else {
+            return None;
+        };
+        let shift = 8 - COLOR_BITS;
+        // Weight of group along with sums of its channels weighted the same.
+        let mut groups = vec![[0u64; 4]; 1 << (3 * COLOR_BITS)];
+        for pixel in pixels.chunks_exact(4) {
+            let &[r, g, b, a] = pixel else {
+                continue;
+            };
+            if a < MIN_ALPHA {
+                continue;
+            }
+            let index = (usize::from(r >> shift) << (2 * COLOR_BITS))
+                | (usize::from(g >> shift) << COLOR_BITS)
+                | usize::from(b >> shift);
+            let saturation = r.max(g).max(b) - r.min(g).min(b);
+            let weight = 1 + u64::from(saturation / 32);
+            let group = &mut groups[index];
+            group[0] += weight;
+            for (sum, channel) in group[1..].iter_mut().zip([r, g, b]) {
+                *sum += weight * u64::from(channel);
+            }
+        }
+        let [weight, r, g, b] = groups.into_iter().max_by_key(|group| group[0])?;
+        let mean = |sum: u64| u8::try_from(sum / weight).unwrap_or(u8::MAX);
+        (weight > 0).then(|| [mean(r), mean(g), mean(b)])
+    }
+
+    /// Get width and height of page in pixels, only the header of encoded pages is read.
+    /// [None] if the size of an encoded page cannot be read.
+    pub fn dimensions(&self) -> Option<(u32, u32)> {
+        match self {
+            Page::Encoded(bytes) => ::image::ImageReader::new(Cursor::new(bytes))
+                .with_guessed_format()
+                .ok()?
+                .into_dimensions()
+                .ok(),
+            Page::Rgba { width, height, .. } => Some((*width, *height)),
+        }
+    }
+
+    /// Decode page into an image.
+    ///
+    /// # Errors
+    /// If the page cannot be decoded.
+    fn image(self) -> Result<::image::DynamicImage> {
+        Ok(match self.decoded()? {
+            Page::Encoded(bytes) => ::image::load_from_memory(&bytes)
+                .map_err(|err| ArchiveError::Decode(Box::new(err)))?,
+            Page::Rgba {
+                width,
+                height,
+                pixels,
+            } => ::image::RgbaImage::from_raw(width, height, pixels.to_vec())
+                .ok_or(ArchiveError::PixelSize { width, height })?
+                .into(),
+        })
+    }
+
+    /// Decode page into rgba pixels, as the image widget does before showing it.
+    ///
+    /// # Errors
+    /// If the page cannot be decoded.
+    pub fn rgba(self) -> Result<Self> {
+        let image = self.image()?.into_rgba8();
+        Ok(Page::Rgba {
+            width: image.width(),
+            height: image.height(),
+            pixels: Bytes::from(image.into_raw()),
+        })
+    }
+
+    /// Decode page and apply adjustments to it, pages are kept as is if adjustments are
+    /// neutral.
+    ///
+    /// # Errors
+    /// If the page needs decoding and decoding fails.
+    pub fn adjusted(self, adjustments: &Adjustments) -> Result<Self> {
+        if adjustments.is_neutral() {
+            return self.decoded();
+        }
+        let mut image = self.image()?.into_rgba8();
+        adjustments.apply(&mut image);
+        Ok(Page::Rgba {
+            width: image.width(),
+            height: image.height(),
+            pixels: Bytes::from(image.into_raw()),
+        })
+    }
+
+    /// Decode page and downscale it to fit within a square of the given size, decoded
+    /// pages already fitting are kept as is.
+    ///
+    /// # Errors
+    /// If the page cannot be decoded.
+    pub fn thumbnail(self, size: u32) -> Result<Self> {
+        if let Page::Rgba { width, height, .. } = self
+            && width <= size
+            && height <= size
+        {
+            return Ok(self);
+        }
+        let image = self.image()?;
+        let image = if image.width() > size || image.height() > size {
+            image.thumbnail(size, size)
+        } else {
+            image
+        }
+        .into_rgba8();
+
+        Ok(Page::Rgba {
+            width: image.width(),
+            height: image.height(),
+            pixels: Bytes::from(image.into_raw()),
+        })
+    }
+}
+
+/// An opened archive.
+#[derive(Debug)]
+pub enum Archive {
+    /// Opened zip archive.
+    Cbz(cbz::Cbz),
+    /// Opened epub document.
+    Epub(epub::Epub),
+    /// Opened pdf document.
+    #[cfg(feature = "pdf")]
+    Pdf(Box<pdf::Pdf>),
+}
+
+impl Archive {
+    /// Open archive at path.
+    ///
+    /// # Errors
+    /// If the path is not a supported archive, or if it cannot be opened.
+    pub fn open(path: &Path) -> Result<Self> {
+        match ArchiveKind::from_path(path) {
+            Some(ArchiveKind::Cbz) => cbz::Cbz::open(path).map(Self::Cbz),
+            Some(ArchiveKind::Epub) => epub::Epub::open(path).map(Self::Epub),
+            #[cfg(feature = "pdf")]
+            Some(ArchiveKind::Pdf) => pdf::Pdf::open(path).map(Box::new).map(Self::Pdf),
+            None => Err(ArchiveError::Unsupported),
+        }
+    }
+
+    /// Get metadata of archive.
+    pub fn metadata(&self) -> Metadata {
+        match self {
+            Archive::Epub(epub) => epub.metadata().clone(),
+            Archive::Cbz(..) => Metadata::default(),
+            #[cfg(feature = "pdf")]
+            Archive::Pdf(..) => Metadata::default(),
+        }
+    }
+
+    /// Get amount of pages in archive.
+    pub fn page_count(&self) -> usize {
+        match self {
+            Archive::Cbz(cbz) => cbz.page_count(),
+            Archive::Epub(epub) => epub.page_count(),
+            #[cfg(feature = "pdf")]
+            Archive::Pdf(pdf) => pdf.page_count(),
+        }
+    }
+
+    /// Read page at index.
+    ///
+    /// # Errors
+    /// If the page does not exist or cannot be read.
+    pub fn page(&mut self, index: usize) -> Result<Page> {
+        match self {
+            Archive::Cbz(cbz) => cbz.page(index),
+            Archive::Epub(epub) => epub.page(index),
+            #[cfg(feature = "pdf")]
+            Archive::Pdf(pdf) => pdf.page(index),
+        }
+    }
+}
+
+/// Remove pages identical to an earlier page from archive at path, returning the amount
+/// of pages removed. Only zip archives are rewritten, other formats are left as is.
+///
+/// # Errors
+/// If the archive cannot be read or written.
+pub fn strip_duplicate_pages(path: &Path, temp_dir: &Path) -> Result<usize> {
+    match ArchiveKind::from_path(path) {
+        Some(ArchiveKind::Cbz) => cbz::strip_duplicate_pages(path, temp_dir),
+        Some(_) => Ok(0),
+        None => Err(ArchiveError::Unsupported),
+    }
+}
+
+/// Open archive at path and decode all of its pages, reporting the amount of pages
+/// decoded and the amount of pages in it, returning the amount of pages.
+///
+/// # Errors
+/// If the archive cannot be opened, any page cannot be read or decoded, or verification
+/// is cancelled.
+pub fn verify(
+    path: &Path,
+    cancel: &CancelToken,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<usize> {
+    let mut archive = Archive::open(path)?;
+    let page_count = archive.page_count();
+    for index in 0..page_count {
+        if cancel.is_cancelled() {
+            return Err(ArchiveError::Cancelled);
+        }
+        archive.page(index)?.decoded()?;
+        progress(index + 1, page_count);
+    }
+    Ok(page_count)
+}
+
+/// Read cover, downscaled to fit within a square of the given size, and metadata of
+/// archive at path.
+///
+/// # Errors
+/// If the archive cannot be opened, has no pages or the cover cannot be decoded.
+pub fn preview(path: &Path, size: u32) -> Result<(Page, Metadata)> {
+    preview_cover(path, size, &Cover::Page(0))
+}
+
+/// Read given cover, downscaled to fit within a square of the given size, and metadata
+/// of archive at path. Pages past the end of the archive and images which cannot be read
+/// fall back to the first page.
+///
+/// # Errors
+/// If the archive cannot be opened, has no pages or the cover cannot be decoded.
+pub fn preview_cover(path: &Path, size: u32, cover: &Cover) -> Result<(Page, Metadata)> {
+    let mut archive = Archive::open(path)?;
+    let page = read_cover(&mut archive, path, cover)?;
+    Ok((page.thumbnail(size)?, archive.metadata()))
+}
+
+/// Read frames of given cover, downscaled to fit within a square of the given size, of
+/// archive at path, [None] if the cover is not animated.
+///
+/// # Errors
+/// If the archive cannot be opened, has no pages or the cover cannot be decoded.
+pub fn cover_animation(path: &Path, size: u32, cover: &Cover) -> Result<Option<Vec<Frame>>> {
+    read_cover(&mut Archive::open(path)?, path, cover)?.animation(Some(size))
+}
+
+/// Read given cover of archive at path at full size, falling back to the first page as
+/// [preview_cover] does.
+///
+/// # Errors
+/// If the archive cannot be opened or has no pages.
+pub fn cover_page(path: &Path, cover: &Cover) -> Result<Page> {
+    read_cover(&mut Archive::open(path)?, path, cover)
+}
+
+/// Read given cover of an opened archive at path.
+///
+/// # Errors
+/// If the archive has no pages.
+fn read_cover(archive: &mut Archive, path: &Path, cover: &Cover) -> Result<Page> {
+    Ok(match cover {
+        &Cover::Page(index) if index < archive.page_count() => archive.page(index)?,
+        Cover::Page(..) => archive.page(0)?,
+        Cover::Image(image) => match fs::read(image) {
+            Ok(content) => Page::Encoded(Bytes::from(content)),
+            Err(err) => {
+                ::log::warn!("could not read cover {image:?} of {path:?}\n{err}");
+                archive.page(0)?
+            }
+        },
+    })
+}
@@ -0,0 +1,66 @@
+//! Brightness, contrast and gamma adjustments of pages, for scans with poor contrast and
+//! reading at night.
+
+use ::core::ops::RangeInclusive;
+
+/// Adjustments of pages shown in a reader, neutral adjustments keep pages unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Adjustments {
+    /// Brightness added to each channel, as a fraction of full intensity.
+    pub brightness: f32,
+    /// Contrast change, negative values flatten and positive values steepen tones.
+    pub contrast: f32,
+    /// Gamma, values above 1 brighten midtones and values below 1 darken them.
+    pub gamma: f32,
+    /// Invert colors after other adjustments, for reading at night.
+    pub invert: bool,
+}
+
+impl Default for Adjustments {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 0.0,
+            gamma: 1.0,
+            invert: false,
+        }
+    }
+}
+
+impl Adjustments {
+    /// Range of brightness.
+    pub const BRIGHTNESS: RangeInclusive<f32> = -0.5..=0.5;
+
+    /// Range of contrast.
+    pub const CONTRAST: RangeInclusive<f32> = -0.9..=1.0;
+
+    /// Range of gamma.
+    pub const GAMMA: RangeInclusive<f32> = 0.25..=4.0;
+
+    /// Check if adjustments leave pages unchanged.
+    pub fn is_neutral(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Get adjusted value of each channel value.
+    fn lookup(&self) -> [u8; 256] {
+        ::core::array::from_fn(|value| {
+            let value = value as f32 / 255.0;
+            let value = ((value - 0.5) * (1.0 + self.contrast) + 0.5 + self.brightness)
+                .clamp(0.0, 1.0)
+                .powf(1.0 / self.gamma.max(f32::EPSILON));
+            let value = if self.invert { 1.0 - value } else { value };
+            (value * 255.0).round() as u8
+        })
+    }
+
+    /// Adjust color channels of rgba pixels in place, alpha is kept.
+    pub(crate) fn apply(&self, pixels: &mut [u8]) {
+        let lookup = self.lookup();
+        for pixel in pixels.chunks_exact_mut(4) {
+            for channel in &mut pixel[..3] {
+                *channel = lookup[usize::from(*channel)];
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Decoding of animated gif and webp pages into frames.
+
+use ::core::time::Duration;
+use ::std::io::Cursor;
+
+use ::bytes::Bytes;
+use ::image::{
+    AnimationDecoder, DynamicImage, Frames, ImageFormat,
+    codecs::{gif::GifDecoder, webp::WebPDecoder},
+};
+
+use crate::{
+    archive::{Page, Result},
+    error::ArchiveError,
+};
+
+/// Max amount of frames decoded of an animation, later frames are dropped.
+const MAX_FRAMES: usize = 256;
+
+/// Max amount of pixel bytes decoded of an animation, later frames are dropped.
+const MAX_BYTES: usize = 256 * 1024 * 1024;
+
+/// Delay used for frames with a delay too short to be meant literally, as browsers do.
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+/// Frames with a delay shorter than this use [DEFAULT_DELAY].
+const MIN_DELAY: Duration = Duration::from_millis(20);
+
+/// A single frame of an animated page.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    /// Image of frame, as rgba pixels.
+    pub page: Page,
+    /// Time frame is shown for.
+    pub delay: Duration,
+}
+
+/// Get frames of a gif or webp image, [None] for other images and still webp images.
+///
+/// # Errors
+/// If the image cannot be decoded.
+fn frames(bytes: &[u8]) -> Result<Option<Frames<'_>>> {
+    let decode = |err| ArchiveError::Decode(Box::new(err));
+    Ok(match ::image::guess_format(bytes).ok() {
+        Some(ImageFormat::Gif) => GifDecoder::new(Cursor::new(bytes))
+            .map_err(decode)?
+            .into_frames()
+            .into(),
+        Some(ImageFormat::WebP) => {
+            let decoder = WebPDecoder::new(Cursor::new(bytes)).map_err(decode)?;
+            decoder.has_animation().then(|| decoder.into_frames())
+        }
+        _ => None,
+    })
+}
+
+/// Decode frames of an animated gif or webp image, downscaled to fit within a square of
+/// the given size if any. Still images and animations of a single frame give [None].
+///
+/// # Errors
+/// If the image cannot be decoded.
+pub fn decode(bytes: &[u8], size: Option<u32>) -> Result<Option<Vec<Frame>>> {
+    let Some(frames) = frames(bytes)? else {
+        return Ok(None);
+    };
+    let mut decoded = Vec::new();
+    let mut total = 0;
+    for frame in frames.take(MAX_FRAMES) {
+        let frame = frame.map_err(|err| ArchiveError::Decode(Box::new(err)))?;
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        let delay = Duration::from_millis(u64::from(numer) / u64::from(denom.max(1)));
+        let delay = if delay < MIN_DELAY {
+            DEFAULT_DELAY
+        } else {
+            delay
+        };
+
+        let image = DynamicImage::from(frame.into_buffer());
+        let image = match size {
+            Some(size) if image.width() > size || image.height() > size => {
+                image.thumbnail(size, size).into_rgba8()
+            }
+            _ => image.into_rgba8(),
+        };
+
+        total += image.as_raw().len();
+        if total > MAX_BYTES {
+            ::log::info!("animation exceeds {MAX_BYTES} bytes, dropping later frames");
+            break;
+        }
+        decoded.push(Frame {
+            page: Page::Rgba {
+                width: image.width(),
+                height: image.height(),
+                pixels: Bytes::from(image.into_raw()),
+            },
+            delay,
+        });
+    }
+    Ok((decoded.len() > 1).then_some(decoded))
+}
@@ -0,0 +1,245 @@
+//! Zip archive backend.
+
+use ::std::{
+    fs::{self, File},
+    io::{BufReader, Read, Write},
+    path::Path,
+};
+
+use ::bytes::Bytes;
+use ::hashbrown::{HashMap, HashSet};
+use ::zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    archive::{
+        COMIC_INFO, ComicInfo, IMAGE_EXTENSIONS, Page, Result, has_extension, preallocation,
+    },
+    error::ArchiveError,
+    temp,
+    text::CaseFolding,
+};
+
+/// Max size of `ComicInfo.xml` read from an archive.
+const MAX_COMIC_INFO_SIZE: u64 = 1024 * 1024;
+
+/// Get indices and names of image entries of zip archive in page order. Names are
+/// compared naturally with a case folding independent of locale, such that pages are in
+/// the same order on every system.
+fn page_entries(zip: &ZipArchive<BufReader<File>>) -> Vec<(usize, &str)> {
+    let mut pages = zip
+        .file_names()
+        .filter(|name| has_extension(Path::new(name), IMAGE_EXTENSIONS))
+        .filter_map(|name| Some((zip.index_for_name(name)?, name)))
+        .collect::<Vec<_>>();
+    pages.sort_unstable_by(|&(_, a), &(_, b)| CaseFolding::Unicode.natural_cmp(a, b));
+    pages
+}
+
+/// Get page index of each page of zip archive at path, in the order pages had when they
+/// were ordered by the bytes of their names, used to migrate saved page indices.
+///
+/// # Errors
+/// If the file cannot be opened or is not a zip archive.
+pub fn natural_page_indices(path: &Path) -> Result<Vec<usize>> {
+    let file = File::open(path).map_err(ArchiveError::Open)?;
+    let zip = ZipArchive::new(BufReader::new(file)).map_err(ArchiveError::Zip)?;
+    let mut pages = page_entries(&zip)
+        .into_iter()
+        .enumerate()
+        .map(|(page, (_, name))| (name, page))
+        .collect::<Vec<_>>();
+    pages.sort_unstable_by_key(|&(name, _)| name);
+    Ok(pages.into_iter().map(|(_, page)| page).collect())
+}
+
+/// Opened zip archive.
+#[derive(Debug)]
+pub struct Cbz {
+    /// Zip archive.
+    zip: ZipArchive<BufReader<File>>,
+    /// Indices of image entries in page order.
+    pages: Vec<usize>,
+}
+
+impl Cbz {
+    /// Open zip archive at path.
+    ///
+    /// # Errors
+    /// If the file cannot be opened or is not a zip archive.
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(ArchiveError::Open)?;
+        let zip = ZipArchive::new(BufReader::new(file)).map_err(ArchiveError::Zip)?;
+        let pages = page_entries(&zip)
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        Ok(Self { zip, pages })
+    }
+
+    /// Get amount of pages in archive.
+    pub const fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Read page at index.
+    ///
+    /// # Errors
+    /// If the page does not exist or cannot be read.
+    pub fn page(&mut self, index: usize) -> Result<Page> {
+        self.read(index).map(|buf| Page::Encoded(Bytes::from(buf)))
+    }
+
+    /// Read content of page at index.
+    ///
+    /// # Errors
+    /// If the page does not exist or cannot be read.
+    fn read(&mut self, index: usize) -> Result<Vec<u8>> {
+        let entry = *self.pages.get(index).ok_or(ArchiveError::NoPage(index))?;
+        let mut file = self
+            .zip
+            .by_index(entry)
+            .map_err(|err| ArchiveError::ReadPage {
+                index,
+                source: Box::new(err),
+            })?;
+        let mut buf = Vec::with_capacity(preallocation(file.size()));
+        file.read_to_end(&mut buf)
+            .map_err(|err| ArchiveError::ReadPage {
+                index,
+                source: Box::new(err),
+            })?;
+        Ok(buf)
+    }
+
+    /// Read `ComicInfo.xml` of archive, [None] if it has none.
+    ///
+    /// # Errors
+    /// If the document cannot be read.
+    fn comic_info_xml(&mut self) -> Result<Option<String>> {
+        let Some(entry) = (0..self.zip.len()).find(|&entry| {
+            self.zip
+                .name_for_index(entry)
+                .is_some_and(|name| name.eq_ignore_ascii_case(COMIC_INFO))
+        }) else {
+            return Ok(None);
+        };
+        let error = |source| ArchiveError::ReadEntry {
+            name: COMIC_INFO.to_owned(),
+            source,
+        };
+        let file = self.zip.by_index(entry).map_err(|err| error(err.into()))?;
+        let mut xml = String::new();
+        file.take(MAX_COMIC_INFO_SIZE)
+            .read_to_string(&mut xml)
+            .map_err(error)?;
+        Ok(Some(xml))
+    }
+}
+
+/// Read `ComicInfo.xml` metadata of zip archive at path, archives without or with an
+/// invalid document have empty metadata.
+///
+/// # Errors
+/// If the archive cannot be opened or its document cannot be read.
+pub fn read_comic_info(path: &Path) -> Result<ComicInfo> {
+    Ok(Cbz::open(path)?
+        .comic_info_xml()?
+        .and_then(|xml| ComicInfo::parse(&xml))
+        .unwrap_or_default())
+}
+
+/// Write `ComicInfo.xml` metadata of zip archive at path, rewriting it through a
+/// temporary file in `temp_dir`. Elements of an existing document which are not fields
+/// of [ComicInfo] are kept, as are all other entries.
+///
+/// # Errors
+/// If the archive cannot be read or written.
+pub fn write_comic_info(path: &Path, temp_dir: &Path, info: &ComicInfo) -> Result<()> {
+    let mut cbz = Cbz::open(path)?;
+    let xml = match cbz.comic_info_xml()? {
+        Some(existing) => info.merge_xml(&existing),
+        None => info.to_xml(cbz.page_count()),
+    };
+
+    let temporary = temp::path(temp_dir, "info.cbz");
+    let written = File::create(&temporary)
+        .map(ZipWriter::new)
+        .and_then(|mut writer| {
+            writer.set_raw_comment(cbz.zip.comment().into());
+            for entry in 0..cbz.zip.len() {
+                let file = cbz.zip.by_index_raw(entry)?;
+                if !file.name().eq_ignore_ascii_case(COMIC_INFO) {
+                    writer.raw_copy_file(file)?;
+                }
+            }
+            writer.start_file(COMIC_INFO, SimpleFileOptions::default())?;
+            writer.write_all(xml.as_bytes())?;
+            writer.finish()?;
+            Ok(())
+        })
+        .and_then(|()| temp::persist(&temporary, path));
+    if let Err(err) = written {
+        _ = fs::remove_file(&temporary);
+        return Err(ArchiveError::Write(err));
+    }
+    Ok(())
+}
+
+/// Remove pages identical to an earlier page from zip archive at path, rewriting it
+/// through a temporary file in `temp_dir`. Returns the amount of pages removed, the
+/// archive is left untouched if there are none.
+///
+/// # Errors
+/// If the archive cannot be read or written.
+pub fn strip_duplicate_pages(path: &Path, temp_dir: &Path) -> Result<usize> {
+    let mut cbz = Cbz::open(path)?;
+
+    // Pages are only compared if their checksums and sizes match.
+    let mut candidates = HashMap::<(u32, u64), Vec<usize>>::new();
+    let mut duplicates = HashSet::new();
+    for index in 0..cbz.page_count() {
+        let entry = cbz.pages[index];
+        let key = cbz
+            .zip
+            .by_index_raw(entry)
+            .map(|file| (file.crc32(), file.size()))
+            .map_err(ArchiveError::Zip)?;
+        let earlier = candidates.entry(key).or_default();
+        if !earlier.is_empty() {
+            let content = cbz.read(index)?;
+            let mut is_duplicate = false;
+            for &earlier in earlier.iter() {
+                if cbz.read(earlier)? == content {
+                    is_duplicate = true;
+                    break;
+                }
+            }
+            if is_duplicate {
+                duplicates.insert(entry);
+                continue;
+            }
+        }
+        earlier.push(index);
+    }
+    if duplicates.is_empty() {
+        return Ok(0);
+    }
+
+    let temporary = temp::path(temp_dir, "strip.cbz");
+    let written = File::create(&temporary)
+        .map(ZipWriter::new)
+        .and_then(|mut writer| {
+            writer.set_raw_comment(cbz.zip.comment().into());
+            for entry in (0..cbz.zip.len()).filter(|entry| !duplicates.contains(entry)) {
+                writer.raw_copy_file(cbz.zip.by_index_raw(entry)?)?;
+            }
+            writer.finish()?;
+            Ok(())
+        })
+        .and_then(|()| temp::persist(&temporary, path));
+    if let Err(err) = written {
+        _ = fs::remove_file(&temporary);
+        return Err(ArchiveError::Write(err));
+    }
+    Ok(duplicates.len())
+}
@@ -0,0 +1,166 @@
+//! `ComicInfo.xml` metadata of zip archives.
+
+use ::roxmltree::Document;
+
+/// Name of metadata document of zip archives.
+pub const COMIC_INFO: &str = "ComicInfo.xml";
+
+/// Field of `ComicInfo.xml` which may be edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComicField {
+    /// Title of archive.
+    Title,
+    /// Series archive belongs to.
+    Series,
+    /// Number of archive within its series.
+    Number,
+    /// Writer of archive.
+    Writer,
+    /// Year archive was published.
+    Year,
+    /// Comma separated tags of archive.
+    Tags,
+}
+
+impl ComicField {
+    /// All fields, in the order they are written and shown.
+    pub const ALL: [Self; 6] = [
+        Self::Title,
+        Self::Series,
+        Self::Number,
+        Self::Writer,
+        Self::Year,
+        Self::Tags,
+    ];
+
+    /// Get name of xml element of field.
+    pub const fn element(self) -> &'static str {
+        match self {
+            ComicField::Title => "Title",
+            ComicField::Series => "Series",
+            ComicField::Number => "Number",
+            ComicField::Writer => "Writer",
+            ComicField::Year => "Year",
+            ComicField::Tags => "Tags",
+        }
+    }
+
+    /// Get field of an xml element name.
+    fn from_element(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|field| field.element() == name)
+    }
+}
+
+/// Metadata of `ComicInfo.xml` of zip archives, empty fields are left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComicInfo {
+    /// Title of archive.
+    pub title: String,
+    /// Series archive belongs to.
+    pub series: String,
+    /// Number of archive within its series.
+    pub number: String,
+    /// Writer of archive.
+    pub writer: String,
+    /// Year archive was published.
+    pub year: String,
+    /// Comma separated tags of archive.
+    pub tags: String,
+}
+
+impl ComicInfo {
+    /// Parse fields of a `ComicInfo.xml` document, [None] if it is not valid xml.
+    pub fn parse(xml: &str) -> Option<Self> {
+        let document = Document::parse(xml).ok()?;
+        let mut info = Self::default();
+        for node in document.root_element().children() {
+            if let Some(field) = ComicField::from_element(node.tag_name().name()) {
+                node.text()
+                    .unwrap_or_default()
+                    .trim()
+                    .clone_into(info.get_mut(field));
+            }
+        }
+        Some(info)
+    }
+
+    /// Get value of a field.
+    pub fn get(&self, field: ComicField) -> &str {
+        match field {
+            ComicField::Title => &self.title,
+            ComicField::Series => &self.series,
+            ComicField::Number => &self.number,
+            ComicField::Writer => &self.writer,
+            ComicField::Year => &self.year,
+            ComicField::Tags => &self.tags,
+        }
+    }
+
+    /// Get value of a field mutably.
+    pub const fn get_mut(&mut self, field: ComicField) -> &mut String {
+        match field {
+            ComicField::Title => &mut self.title,
+            ComicField::Series => &mut self.series,
+            ComicField::Number => &mut self.number,
+            ComicField::Writer => &mut self.writer,
+            ComicField::Year => &mut self.year,
+            ComicField::Tags => &mut self.tags,
+        }
+    }
+
+    /// Check if every field is empty.
+    pub fn is_empty(&self) -> bool {
+        ComicField::ALL
+            .into_iter()
+            .all(|field| self.get(field).trim().is_empty())
+    }
+
+    /// Serialize as xml document of an archive with given amount of pages.
+    pub fn to_xml(&self, page_count: usize) -> String {
+        self.to_xml_with(&[&format!("<PageCount>{page_count}</PageCount>")])
+    }
+
+    /// Serialize as xml document replacing an existing one, elements of the existing
+    /// document which are not fields, such as page lists, are kept as they are. Invalid
+    /// documents are replaced entirely.
+    pub fn merge_xml(&self, existing: &str) -> String {
+        let Ok(document) = Document::parse(existing) else {
+            return self.to_xml_with(&[]);
+        };
+        let kept = document
+            .root_element()
+            .children()
+            .filter(|node| {
+                node.is_element() && ComicField::from_element(node.tag_name().name()).is_none()
+            })
+            .filter_map(|node| existing.get(node.range()))
+            .collect::<Vec<_>>();
+        self.to_xml_with(&kept)
+    }
+
+    /// Serialize as xml document, with given elements after fields.
+    fn to_xml_with(&self, elements: &[&str]) -> String {
+        /// Escape characters with special meaning in xml text.
+        fn escape(text: &str) -> String {
+            text.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+        }
+        let mut xml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+            "<ComicInfo xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" ",
+            "xmlns:xsd=\"http://www.w3.org/2001/XMLSchema\">\n",
+        ));
+        for field in ComicField::ALL {
+            let (name, value) = (field.element(), self.get(field).trim());
+            if !value.is_empty() {
+                xml.push_str(&format!("  <{name}>{}</{name}>\n", escape(value)));
+            }
+        }
+        for element in elements {
+            xml.push_str(&format!("  {element}\n"));
+        }
+        xml.push_str("</ComicInfo>\n");
+        xml
+    }
+}
@@ -0,0 +1,202 @@
+//! Conversion of rar and 7z archives to zip archives, extracted with external tools.
+
+use ::std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use ::hashbrown::HashSet;
+use ::image::codecs::jpeg::JpegEncoder;
+use ::zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    archive::{IMAGE_EXTENSIONS, Result, has_extension},
+    error::ArchiveError,
+    temp,
+};
+
+/// Extensions of archives which may be converted.
+const CONVERTIBLE_EXTENSIONS: &[&str] = &["cbr", "rar", "cb7", "7z"];
+
+/// Check if archive at path may be converted to a zip archive.
+pub fn is_convertible(path: &Path) -> bool {
+    has_extension(path, CONVERTIBLE_EXTENSIONS)
+}
+
+/// Get commands extracting archive into directory, tried in order until one succeeds.
+fn extractors(archive: &Path, dir: &Path) -> [Command; 3] {
+    let mut bsdtar = Command::new("bsdtar");
+    bsdtar.arg("-xf").arg(archive).arg("-C").arg(dir);
+    let mut sevenzip = Command::new("7z");
+    sevenzip
+        .args(["x", "-y"])
+        .arg(format!("-o{}", dir.display()))
+        .arg(archive);
+    let mut unrar = Command::new("unrar");
+    unrar
+        .args(["x", "-o+", "-inul"])
+        .arg(archive)
+        .arg(dir.join(""));
+    [bsdtar, sevenzip, unrar]
+}
+
+/// Check if any program archives are extracted with is installed, searching `PATH`.
+pub fn has_extractor() -> bool {
+    ::std::env::var_os("PATH").is_some_and(|paths| {
+        ::std::env::split_paths(&paths).any(|dir| {
+            ["bsdtar", "7z", "unrar"]
+                .iter()
+                .any(|name| dir.join(name).is_file())
+        })
+    })
+}
+
+/// Extract archive into directory with the first available extractor that succeeds.
+///
+/// # Errors
+/// If no extractor is installed, or every installed extractor fails.
+pub fn extract(archive: &Path, dir: &Path) -> Result<()> {
+    let mut error = io::Error::new(
+        io::ErrorKind::NotFound,
+        "none of bsdtar, 7z or unrar is installed",
+    );
+    for mut extractor in extractors(archive, dir) {
+        let status = extractor
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        match status {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => {
+                error = io::Error::other(format!(
+                    "{} exited with {status}",
+                    extractor.get_program().display()
+                ));
+            }
+            // Extractors which are not installed are skipped.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => error = err,
+        }
+    }
+    Err(ArchiveError::Extract(error))
+}
+
+/// Get files of a directory and its subdirectories, sorted by path.
+///
+/// # Errors
+/// If a directory cannot be read.
+fn files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                dirs.push(entry.path());
+            } else {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort_unstable();
+    Ok(files)
+}
+
+/// Recompress a page image as a jpeg image of given quality, [None] if it cannot be
+/// decoded or the recompressed image is not smaller.
+fn recompress(content: &[u8], quality: u8) -> Option<Vec<u8>> {
+    let image = ::image::load_from_memory(content).ok()?.into_rgb8();
+    let mut recompressed = Vec::new();
+    JpegEncoder::new_with_quality(&mut recompressed, quality)
+        .encode_image(&image)
+        .ok()?;
+    (recompressed.len() < content.len()).then_some(recompressed)
+}
+
+/// Write extracted files to a zip archive at path, recompressing pages if a quality is
+/// given. Recompressed pages are renamed to have a `jpg` extension, unless another file
+/// has that name, in which case the page is kept as it is.
+///
+/// # Errors
+/// If a file cannot be read or the archive cannot be written.
+fn write_zip(path: &Path, dir: &Path, files: &[PathBuf], quality: Option<u8>) -> io::Result<()> {
+    let names = files
+        .iter()
+        .map(|file| {
+            file.strip_prefix(dir)
+                .unwrap_or(file)
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .collect::<Vec<_>>();
+    let mut taken = names.iter().cloned().collect::<HashSet<_>>();
+    let mut writer = ZipWriter::new(File::create(path)?);
+    for (file, mut name) in files.iter().zip(names) {
+        let mut content = fs::read(file)?;
+        let is_page = has_extension(file, IMAGE_EXTENSIONS);
+        let renamed = Path::new(&name)
+            .with_extension("jpg")
+            .to_string_lossy()
+            .into_owned();
+        if is_page
+            && (renamed == name || !taken.contains(&renamed))
+            && let Some(quality) = quality
+            && let Some(recompressed) = recompress(&content, quality)
+        {
+            content = recompressed;
+            taken.remove(&name);
+            taken.insert(renamed.clone());
+            name = renamed;
+        }
+        // Images are compressed already, metadata such as `ComicInfo.xml` is not.
+        let options = SimpleFileOptions::default()
+            .compression_method(if is_page {
+                CompressionMethod::Stored
+            } else {
+                CompressionMethod::Deflated
+            })
+            .large_file(content.len() > u32::MAX as usize);
+        writer.start_file(name, options)?;
+        writer.write_all(&content)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Repack a rar or 7z archive into a zip archive next to it with a `cbz` extension,
+/// returning its path. Every extracted file is kept, page images are recompressed as jpeg
+/// images of given quality if given and smaller. The original archive is left as is.
+///
+/// # Errors
+/// If the archive cannot be extracted, or the zip archive exists or cannot be written.
+pub fn convert_to_cbz(path: &Path, temp_dir: &Path, quality: Option<u8>) -> Result<PathBuf> {
+    if !is_convertible(path) {
+        return Err(ArchiveError::Unsupported);
+    }
+    let target = path.with_extension("cbz");
+    if target.try_exists().map_err(ArchiveError::Write)? {
+        return Err(ArchiveError::Write(io::Error::from(
+            io::ErrorKind::AlreadyExists,
+        )));
+    }
+
+    let dir = temp::path(temp_dir, "convert");
+    fs::create_dir(&dir).map_err(ArchiveError::Write)?;
+    let temporary = temp::path(temp_dir, "convert.cbz");
+    let result = extract(path, &dir).and_then(|()| {
+        files(&dir)
+            .and_then(|files| write_zip(&temporary, &dir, &files, quality))
+            .and_then(|()| temp::persist(&temporary, &target))
+            .map_err(ArchiveError::Write)
+    });
+    _ = fs::remove_dir_all(&dir);
+    if result.is_err() {
+        _ = fs::remove_file(&temporary);
+    }
+    result.map(|()| target)
+}
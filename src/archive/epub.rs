@@ -0,0 +1,191 @@
+//! Epub document backend, reading images of fixed-layout books.
+
+use ::std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+use ::bytes::Bytes;
+use ::roxmltree::{Document, Node};
+use ::zip::ZipArchive;
+
+use crate::{
+    archive::{Metadata, Page, Result, preallocation},
+    error::ArchiveError,
+    isbn::Isbn,
+};
+
+/// Opened epub document.
+#[derive(Debug)]
+pub struct Epub {
+    /// Zip archive of document.
+    zip: ZipArchive<BufReader<File>>,
+    /// Indices of image entries in page order, starting with the cover.
+    pages: Vec<usize>,
+    /// Metadata read from package document.
+    metadata: Metadata,
+}
+
+/// Path of container document in an epub.
+const CONTAINER: &str = "META-INF/container.xml";
+
+/// Max size of xml documents read from an epub.
+const MAX_DOCUMENT_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Read an entry of a zip archive as a string.
+fn read_to_string(zip: &mut ZipArchive<BufReader<File>>, name: &str) -> Result<String> {
+    let mut file = zip
+        .by_name(name)
+        .map_err(|source| ArchiveError::MissingEntry {
+            name: name.to_owned(),
+            source,
+        })?;
+    let mut content = String::new();
+    (&mut file)
+        .take(MAX_DOCUMENT_SIZE)
+        .read_to_string(&mut content)
+        .map_err(|source| ArchiveError::ReadEntry {
+            name: name.to_owned(),
+            source,
+        })?;
+    Ok(content)
+}
+
+/// Resolve a href relative to the directory of base.
+fn resolve(base: &str, href: &str) -> String {
+    let mut components = base.split('/').collect::<Vec<_>>();
+    components.pop();
+    for component in href.split('/') {
+        match component {
+            "." | "" => {}
+            ".." => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+    components.join("/")
+}
+
+/// Get text of first element with given local name below node.
+fn element_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.descendants()
+        .find(|node| node.is_element() && node.tag_name().name() == name)?
+        .text()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+impl Epub {
+    /// Open epub document at path.
+    ///
+    /// # Errors
+    /// If the file cannot be opened or is not a valid epub document.
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(ArchiveError::Open)?;
+        let mut zip = ZipArchive::new(BufReader::new(file)).map_err(ArchiveError::Zip)?;
+
+        let container = read_to_string(&mut zip, CONTAINER)?;
+        let container = Document::parse(&container).map_err(|source| ArchiveError::Xml {
+            name: CONTAINER.to_owned(),
+            source,
+        })?;
+        let package_path = container
+            .descendants()
+            .find(|node| node.is_element() && node.tag_name().name() == "rootfile")
+            .and_then(|node| node.attribute("full-path"))
+            .ok_or(ArchiveError::NoPackage)?
+            .to_owned();
+
+        let package = read_to_string(&mut zip, &package_path)?;
+        let package = Document::parse(&package).map_err(|source| ArchiveError::Xml {
+            name: package_path.clone(),
+            source,
+        })?;
+        let root = package.root_element();
+
+        let metadata = Metadata {
+            title: element_text(root, "title").map(String::from),
+            author: element_text(root, "creator").map(String::from),
+            isbn: root
+                .descendants()
+                .filter(|node| node.is_element() && node.tag_name().name() == "identifier")
+                .find_map(|node| Isbn::parse(node.text()?)),
+        };
+
+        // Epub 2 declares the cover using a meta element, epub 3 using item properties.
+        let cover_id = root
+            .descendants()
+            .find(|node| {
+                node.is_element()
+                    && node.tag_name().name() == "meta"
+                    && node.attribute("name") == Some("cover")
+            })
+            .and_then(|node| node.attribute("content"));
+
+        let mut images = Vec::new();
+        for item in root
+            .descendants()
+            .filter(|node| node.is_element() && node.tag_name().name() == "item")
+        {
+            let (Some(href), Some(media_type)) =
+                (item.attribute("href"), item.attribute("media-type"))
+            else {
+                continue;
+            };
+            if !media_type.starts_with("image/") {
+                continue;
+            }
+            let Some(index) = zip.index_for_name(&resolve(&package_path, href)) else {
+                continue;
+            };
+            let is_cover = item.attribute("properties").is_some_and(|properties| {
+                properties.split_whitespace().any(|p| p == "cover-image")
+            }) || (cover_id.is_some() && item.attribute("id") == cover_id);
+            if is_cover {
+                images.insert(0, index);
+            } else {
+                images.push(index);
+            }
+        }
+
+        Ok(Self {
+            zip,
+            pages: images,
+            metadata,
+        })
+    }
+
+    /// Get metadata of document.
+    pub const fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Get amount of pages in document.
+    pub const fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Read page at index.
+    ///
+    /// # Errors
+    /// If the page does not exist or cannot be read.
+    pub fn page(&mut self, index: usize) -> Result<Page> {
+        let entry = *self.pages.get(index).ok_or(ArchiveError::NoPage(index))?;
+        let mut file = self
+            .zip
+            .by_index(entry)
+            .map_err(|err| ArchiveError::ReadPage {
+                index,
+                source: Box::new(err),
+            })?;
+        let mut buf = Vec::with_capacity(preallocation(file.size()));
+        file.read_to_end(&mut buf)
+            .map_err(|err| ArchiveError::ReadPage {
+                index,
+                source: Box::new(err),
+            })?;
+        Ok(Page::Encoded(Bytes::from(buf)))
+    }
+}
@@ -0,0 +1,114 @@
+//! Extraction of every file of an archive into a directory of its own.
+
+use ::std::{
+    fs::{self, File},
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+};
+
+use ::zip::ZipArchive;
+
+use crate::{
+    archive::{ArchiveKind, Result, convert},
+    error::ArchiveError,
+    inbox,
+    worker::CancelToken,
+};
+
+/// Check if archive at path may be extracted.
+pub fn is_extractable(path: &Path) -> bool {
+    matches!(
+        ArchiveKind::from_path(path),
+        Some(ArchiveKind::Cbz | ArchiveKind::Epub)
+    ) || convert::is_convertible(path)
+}
+
+/// Extract entries of a zip archive into directory, reporting progress after every entry.
+///
+/// # Errors
+/// If the archive cannot be read, an entry cannot be written, or extraction is cancelled.
+fn extract_zip(
+    path: &Path,
+    dir: &Path,
+    cancel: &CancelToken,
+    progress: &mut impl FnMut(usize, usize),
+) -> Result<()> {
+    let file = File::open(path).map_err(ArchiveError::Open)?;
+    let mut zip = ZipArchive::new(BufReader::new(file)).map_err(ArchiveError::Zip)?;
+    let total = zip.len();
+    for index in 0..total {
+        if cancel.is_cancelled() {
+            return Err(ArchiveError::Cancelled);
+        }
+        progress(index, total);
+        let mut entry = zip.by_index(index).map_err(ArchiveError::Zip)?;
+        // Entries with names escaping the directory are skipped.
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        let target = dir.join(name);
+        if entry.is_dir() {
+            fs::create_dir_all(&target).map_err(ArchiveError::Write)?;
+            continue;
+        }
+        let mut file = target
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| File::create(&target))
+            .map_err(ArchiveError::Write)?;
+        io::copy(&mut entry, &mut file).map_err(|source| ArchiveError::ReadEntry {
+            name: entry.name().to_owned(),
+            source,
+        })?;
+    }
+    progress(total, total);
+    Ok(())
+}
+
+/// Extract every file of archive at path into a directory, creating it if it does not
+/// exist. Files of the same name are replaced. Progress is reported as the amount of files
+/// extracted and the amount of files in total, archives extracted with external tools
+/// report no progress until they are done. Directories created for extraction are removed
+/// if it fails.
+///
+/// # Errors
+/// If the archive cannot be extracted, the directory cannot be written, or extraction is
+/// cancelled.
+pub fn extract_into(
+    path: &Path,
+    dir: &Path,
+    cancel: &CancelToken,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<()> {
+    let created = !dir.try_exists().map_err(ArchiveError::Write)?;
+    fs::create_dir_all(dir).map_err(ArchiveError::Write)?;
+    let result = match ArchiveKind::from_path(path) {
+        Some(ArchiveKind::Cbz | ArchiveKind::Epub) => extract_zip(path, dir, cancel, &mut progress),
+        _ if convert::is_convertible(path) => {
+            progress(0, 1);
+            convert::extract(path, dir).inspect(|()| progress(1, 1))
+        }
+        _ => Err(ArchiveError::Unsupported),
+    };
+    if result.is_err() && created {
+        _ = fs::remove_dir_all(dir);
+    }
+    result
+}
+
+/// Extract every file of archive at path into a new directory named after it within
+/// given directory, returning its path. Directories of the same name are never written
+/// to, the new directory is numbered such as `Name (2)` instead.
+///
+/// # Errors
+/// If the archive cannot be extracted, the directory cannot be written, or extraction is
+/// cancelled.
+pub fn extract_to(
+    path: &Path,
+    dir: &Path,
+    cancel: &CancelToken,
+    progress: impl FnMut(usize, usize),
+) -> Result<PathBuf> {
+    let target = inbox::free_path(&dir.join(path.file_stem().unwrap_or_default()));
+    extract_into(path, &target, cancel, progress).map(|()| target)
+}
@@ -0,0 +1,59 @@
+//! Jpeg xl decoding, which the image decoder used by iced does not support.
+
+use ::std::io::Cursor;
+
+use ::bytes::Bytes;
+use ::jxl_oxide::JxlImage;
+
+use crate::{
+    archive::{Page, Result},
+    error::ArchiveError,
+};
+
+/// Signature of a bare jpeg xl codestream.
+const CODESTREAM_SIGNATURE: &[u8] = &[0xFF, 0x0A];
+
+/// Signature of a jpeg xl container.
+const CONTAINER_SIGNATURE: &[u8] = &[
+    0x00, 0x00, 0x00, 0x0C, b'J', b'X', b'L', b' ', 0x0D, 0x0A, 0x87, 0x0A,
+];
+
+/// Check if bytes are a jpeg xl image.
+pub fn is_jxl(bytes: &[u8]) -> bool {
+    bytes.starts_with(CODESTREAM_SIGNATURE) || bytes.starts_with(CONTAINER_SIGNATURE)
+}
+
+/// Decode a jpeg xl image to rgba pixels.
+///
+/// # Errors
+/// If the image cannot be decoded.
+pub fn decode(bytes: &[u8]) -> Result<Page> {
+    let image = JxlImage::builder()
+        .read(Cursor::new(bytes))
+        .map_err(|err| ArchiveError::Decode(Box::new(err)))?;
+    let render = image
+        .render_frame(0)
+        .map_err(|err| ArchiveError::Decode(Box::new(err)))?;
+
+    let mut stream = render.stream();
+    let (width, height, channels) = (stream.width(), stream.height(), stream.channels() as usize);
+    let mut samples = vec![0u8; width as usize * height as usize * channels];
+    stream.write_to_buffer(&mut samples);
+
+    let pixels = samples
+        .chunks_exact(channels)
+        .flat_map(|pixel| match *pixel {
+            [l] => [l, l, l, u8::MAX],
+            [l, a] => [l, l, l, a],
+            [r, g, b] => [r, g, b, u8::MAX],
+            [r, g, b, a, ..] => [r, g, b, a],
+            [] => [0; 4],
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Page::Rgba {
+        width,
+        height,
+        pixels: Bytes::from(pixels),
+    })
+}
@@ -0,0 +1,101 @@
+//! Packing of directories of loose images into zip archives.
+
+use ::std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use ::zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    archive::{COMIC_INFO, ComicInfo, IMAGE_EXTENSIONS, Result, has_extension},
+    error::ArchiveError,
+    temp,
+    text::CaseFolding,
+};
+
+/// Least amount of digits of page names, such that pages sort by name in other readers.
+const PAGE_DIGITS: usize = 3;
+
+/// Get images directly within directory, in natural order of their names.
+///
+/// # Errors
+/// If the directory cannot be read.
+fn images(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut images = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() && has_extension(&entry.path(), IMAGE_EXTENSIONS) {
+            images.push(entry.path());
+        }
+    }
+    images.sort_by(|a, b| {
+        CaseFolding::Unicode.natural_cmp(
+            &a.file_name().unwrap_or_default().to_string_lossy(),
+            &b.file_name().unwrap_or_default().to_string_lossy(),
+        )
+    });
+    Ok(images)
+}
+
+/// Write images as pages of a zip archive at path, named by their position.
+///
+/// # Errors
+/// If an image cannot be read or the archive cannot be written.
+fn write_zip(path: &Path, images: &[PathBuf], info: Option<&ComicInfo>) -> io::Result<()> {
+    let digits = images.len().to_string().len().max(PAGE_DIGITS);
+    let mut writer = ZipWriter::new(File::create(path)?);
+    for (index, image) in images.iter().enumerate() {
+        let extension = image
+            .extension()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_lowercase();
+        let content = fs::read(image)?;
+        // Images are compressed already.
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(content.len() > u32::MAX as usize);
+        writer.start_file(format!("{:0digits$}.{extension}", index + 1), options)?;
+        writer.write_all(&content)?;
+    }
+    if let Some(info) = info.filter(|info| !info.is_empty()) {
+        writer.start_file(COMIC_INFO, SimpleFileOptions::default())?;
+        writer.write_all(info.to_xml(images.len()).as_bytes())?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Pack images directly within a directory into a zip archive next to it, named after the
+/// directory with a `cbz` extension, returning its path. Pages are ordered naturally by
+/// name, and `ComicInfo.xml` is written if metadata is given. The directory is left as
+/// is.
+///
+/// # Errors
+/// If the directory contains no images, or the zip archive exists or cannot be written.
+pub fn pack_dir(dir: &Path, temp_dir: &Path, info: Option<&ComicInfo>) -> Result<PathBuf> {
+    let mut name = OsString::from(dir.file_name().ok_or(ArchiveError::NoImages)?);
+    name.push(".cbz");
+    let target = dir.with_file_name(name);
+    if target.try_exists().map_err(ArchiveError::Write)? {
+        return Err(ArchiveError::Write(io::Error::from(
+            io::ErrorKind::AlreadyExists,
+        )));
+    }
+
+    let images = images(dir).map_err(ArchiveError::Open)?;
+    if images.is_empty() {
+        return Err(ArchiveError::NoImages);
+    }
+    let temporary = temp::path(temp_dir, "pack.cbz");
+    let result = write_zip(&temporary, &images, info)
+        .and_then(|()| temp::persist(&temporary, &target))
+        .map_err(ArchiveError::Write);
+    if result.is_err() {
+        _ = fs::remove_file(&temporary);
+    }
+    result.map(|()| target)
+}
@@ -0,0 +1,82 @@
+//! Pdf document backend, rendered using pdfium.
+
+use ::core::fmt::Debug;
+use ::std::{path::Path, sync::LazyLock};
+
+use ::bytes::Bytes;
+use ::pdfium_render::prelude::{PdfDocument, PdfRenderConfig, Pdfium};
+
+use crate::{
+    archive::{Page, Result},
+    error::ArchiveError,
+};
+
+/// Width pdf pages are rendered at.
+const RENDER_WIDTH: i32 = 1600;
+
+/// Pdfium library, if it could be loaded.
+static PDFIUM: LazyLock<Option<Pdfium>> = LazyLock::new(|| {
+    Pdfium::bind_to_system_library()
+        .map(Pdfium::new)
+        .map_err(|err| ::log::warn!("could not load pdfium, pdf documents will not be read\n{err}"))
+        .ok()
+});
+
+/// Check if pdfium could be loaded, such that pdf documents may be read.
+pub fn is_available() -> bool {
+    PDFIUM.is_some()
+}
+
+/// Opened pdf document.
+pub struct Pdf {
+    /// Loaded document.
+    document: PdfDocument<'static>,
+}
+
+impl Debug for Pdf {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_struct("Pdf").finish_non_exhaustive()
+    }
+}
+
+impl Pdf {
+    /// Open pdf document at path.
+    ///
+    /// # Errors
+    /// If pdfium is not available or the document cannot be loaded.
+    pub fn open(path: &Path) -> Result<Self> {
+        let pdfium = PDFIUM.as_ref().ok_or(ArchiveError::PdfiumUnavailable)?;
+        let document = pdfium
+            .load_pdf_from_file(path, None)
+            .map_err(ArchiveError::Pdf)?;
+        Ok(Self { document })
+    }
+
+    /// Get amount of pages in document.
+    pub fn page_count(&self) -> usize {
+        self.document.pages().len().into()
+    }
+
+    /// Render page at index.
+    ///
+    /// # Errors
+    /// If the page does not exist or cannot be rendered.
+    pub fn page(&mut self, index: usize) -> Result<Page> {
+        let page = u16::try_from(index)
+            .ok()
+            .and_then(|index| self.document.pages().get(index).ok())
+            .ok_or(ArchiveError::NoPage(index))?;
+        let bitmap = page
+            .render_with_config(&PdfRenderConfig::new().set_target_width(RENDER_WIDTH))
+            .map_err(|err| ArchiveError::ReadPage {
+                index,
+                source: Box::new(err),
+            })?;
+
+        Ok(Page::Rgba {
+            width: bitmap.width().unsigned_abs(),
+            height: bitmap.height().unsigned_abs(),
+            pixels: Bytes::from(bitmap.as_rgba_bytes()),
+        })
+    }
+}
@@ -0,0 +1,230 @@
+//! Backups of the state of a profile, a single zip archive of its config file and its
+//! catalogue of read state, progress, tags, collections and reading lists, such that a
+//! profile may be moved between machines or kept in a synced folder. Window geometry and
+//! the journal of organized archives are specific to a machine and are not backed up.
+
+use ::std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use ::rusqlite::{Connection, OpenFlags};
+use ::zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    catalogue::Catalogue,
+    config::{self, CONFIG_FILES},
+    error::{BackupError, Error},
+    temp,
+};
+
+/// Name of directory in data directory of profile backups are written to by default.
+const BACKUP_DIR: &str = "backups";
+
+/// Extension of backups.
+const EXTENSION: &str = "zip";
+
+/// Directory of backups the config file is kept in, under its own name.
+const CONFIG_DIR: &str = "config";
+
+/// Path of catalogue in backups.
+const CATALOGUE_ENTRY: &str = "data/catalogue.sqlite";
+
+/// Get directory backups of profile are written to by default, [None] if there is no
+/// data directory.
+pub fn dir(xdg_dirs: &::xdg::BaseDirectories) -> Option<PathBuf> {
+    xdg_dirs.get_data_home().map(|dir| dir.join(BACKUP_DIR))
+}
+
+/// Get default path of a new backup of profile, named after the profile and the current
+/// time.
+pub fn default_path(xdg_dirs: &::xdg::BaseDirectories, profile: &str) -> Option<PathBuf> {
+    let time = ::jiff::Zoned::now().strftime("%Y-%m-%d-%H%M%S");
+    dir(xdg_dirs).map(|dir| dir.join(format!("{profile}-{time}.{EXTENSION}")))
+}
+
+/// Get names of backups in default directory of profile, newest first.
+#[cfg(feature = "gui")]
+pub fn list(xdg_dirs: &::xdg::BaseDirectories) -> Vec<String> {
+    let Some(entries) = dir(xdg_dirs).and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut backups = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| {
+            Path::new(name)
+                .extension()
+                .is_some_and(|extension| extension == EXTENSION)
+        })
+        .collect::<Vec<_>>();
+    // Names end with the time they were written at.
+    backups.sort_by(|a, b| b.cmp(a));
+    backups
+}
+
+/// Wrap an error of backup at path.
+fn error(path: &Path) -> impl Fn(BackupError) -> Error + use<'_> {
+    move |source| Error::Backup {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// Write zip archive of config file and catalogue snapshot to file.
+///
+/// # Errors
+/// If the config file cannot be read or the archive cannot be written.
+fn write_archive(file: File, config: Option<&Path>, snapshot: &Path) -> Result<(), BackupError> {
+    let mut writer = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().large_file(true);
+    if let Some(config) = config {
+        let name = config.file_name().unwrap_or_default().to_string_lossy();
+        let content = fs::read(config).map_err(BackupError::Read)?;
+        writer.start_file(format!("{CONFIG_DIR}/{name}"), options)?;
+        writer.write_all(&content).map_err(BackupError::Write)?;
+    }
+    writer.start_file(CATALOGUE_ENTRY, options)?;
+    io::copy(
+        &mut File::open(snapshot).map_err(BackupError::Read)?,
+        &mut writer,
+    )
+    .map_err(BackupError::Write)?;
+    writer.finish()?.sync_all().map_err(BackupError::Write)
+}
+
+/// Back up config file and catalogue of profile to target, replacing it if it exists.
+/// The catalogue is copied consistently even if an instance of profile is running.
+///
+/// # Errors
+/// If the catalogue cannot be copied or the backup cannot be written.
+pub fn create(
+    xdg_dirs: &::xdg::BaseDirectories,
+    catalogue: &Catalogue,
+    target: &Path,
+) -> Result<(), Error> {
+    let error = error(target);
+    let temp_dir = temp::dir(xdg_dirs)
+        .map_err(BackupError::Write)
+        .map_err(&error)?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(BackupError::Write)
+            .map_err(&error)?;
+    }
+    let snapshot = temp::path(&temp_dir, "catalogue");
+    catalogue.snapshot(&snapshot)?;
+    let temporary = temp::path(&temp_dir, "backup");
+    let config = config::find(xdg_dirs);
+    let result = File::create(&temporary)
+        .map_err(BackupError::Write)
+        .and_then(|file| write_archive(file, config.as_deref(), &snapshot))
+        .and_then(|()| temp::persist(&temporary, target).map_err(BackupError::Write));
+    _ = fs::remove_file(&snapshot);
+    if result.is_err() {
+        _ = fs::remove_file(&temporary);
+    }
+    result.map_err(error)
+}
+
+/// Check that a restored catalogue is an intact database.
+///
+/// # Errors
+/// If the database cannot be opened or is damaged.
+fn check_catalogue(path: &Path) -> Result<(), BackupError> {
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|_| BackupError::Damaged)?;
+    connection
+        .query_row("PRAGMA quick_check", [], |row| row.get::<_, String>(0))
+        .ok()
+        .filter(|result| result == "ok")
+        .map(|_| ())
+        .ok_or(BackupError::Damaged)
+}
+
+/// Find config file of backup, returning its name and content.
+///
+/// # Errors
+/// If the archive cannot be read.
+fn read_config(
+    archive: &mut ZipArchive<File>,
+) -> Result<Option<(&'static str, String)>, BackupError> {
+    for name in CONFIG_FILES {
+        let mut file = match archive.by_name(&format!("{CONFIG_DIR}/{name}")) {
+            Ok(file) => file,
+            Err(::zip::result::ZipError::FileNotFound) => continue,
+            Err(err) => return Err(err.into()),
+        };
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(BackupError::Read)?;
+        return Ok(Some((name, content)));
+    }
+    Ok(None)
+}
+
+/// Restore config file and catalogue of profile from backup at source. The current
+/// catalogue is kept next to it as `catalogue.sqlite.bak`, and the current config file is
+/// kept as a backup as when settings are saved. The config file of the backup is written
+/// in the format of the current config file. Nothing is replaced unless the backup is
+/// intact.
+///
+/// # Errors
+/// If the backup cannot be read or is damaged, or files of profile cannot be replaced.
+pub fn restore(xdg_dirs: &::xdg::BaseDirectories, source: &Path) -> Result<(), Error> {
+    let error = error(source);
+    let temp_dir = temp::dir(xdg_dirs)
+        .map_err(BackupError::Write)
+        .map_err(&error)?;
+    let mut archive = File::open(source)
+        .map_err(BackupError::Read)
+        .and_then(|file| ZipArchive::new(file).map_err(BackupError::from))
+        .map_err(&error)?;
+    let config = read_config(&mut archive).map_err(&error)?;
+    let config = config
+        .map(|(name, content)| {
+            config::Format::from_path(Path::new(name))
+                .parse(&content)
+                .map_err(BackupError::Config)
+        })
+        .transpose()
+        .map_err(&error)?;
+
+    let temporary = temp::path(&temp_dir, "catalogue");
+    let result = match archive.by_name(CATALOGUE_ENTRY) {
+        Ok(mut entry) => File::create(&temporary)
+            .and_then(|mut file| {
+                io::copy(&mut entry, &mut file)?;
+                file.sync_all()
+            })
+            .map_err(BackupError::Write),
+        Err(::zip::result::ZipError::FileNotFound) => Err(BackupError::MissingCatalogue),
+        Err(err) => Err(err.into()),
+    }
+    .and_then(|()| check_catalogue(&temporary));
+    if let Err(err) = result {
+        _ = fs::remove_file(&temporary);
+        return Err(error(err));
+    }
+
+    let path = Catalogue::open(xdg_dirs)?.path().to_path_buf();
+    let result = temp::copy(&path, &path.with_extension("sqlite.bak"))
+        .and_then(|()| temp::persist(&temporary, &path));
+    if let Err(err) = result {
+        _ = fs::remove_file(&temporary);
+        return Err(error(BackupError::Restore(err)));
+    }
+
+    if let Some(table) = config {
+        let path = config::place(xdg_dirs)?;
+        let content = config::Format::from_path(&path)
+            .serialize(&table)
+            .map_err(|source| Error::Config {
+                path: path.clone(),
+                source,
+            })?;
+        config::write(&path, &content)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,260 @@
+//! Benchmarks of a library run by the bench subcommand, measuring how fast archives are
+//! found, covers extracted, pages decoded and the catalogue queried.
+
+use ::core::time::Duration;
+use ::std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use ::serde::Serialize;
+
+use crate::{
+    archive::{self, Archive},
+    catalogue::Catalogue,
+    error::{ArchiveError, Error, Status},
+};
+
+/// Pages decoded of each sampled archive, from its start.
+const DECODE_PAGES: usize = 4;
+
+/// Timings of repeated measurements, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Timings {
+    /// Amount of measurements.
+    pub count: usize,
+    /// Time of all measurements.
+    pub total_ms: f64,
+    /// Median time of a measurement.
+    pub median_ms: f64,
+    /// 95th percentile time of a measurement.
+    pub p95_ms: f64,
+    /// Longest time of a measurement.
+    pub max_ms: f64,
+}
+
+impl Timings {
+    /// Summarize measured durations.
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let ms = |duration: Option<&Duration>| {
+            duration.map_or(0.0, |duration| duration.as_secs_f64() * 1000.0)
+        };
+        let percentile = |fraction: f64| {
+            let index = (samples.len().saturating_sub(1) as f64 * fraction).round() as usize;
+            ms(samples.get(index))
+        };
+        Self {
+            count: samples.len(),
+            total_ms: ms(Some(&samples.iter().sum())),
+            median_ms: percentile(0.5),
+            p95_ms: percentile(0.95),
+            max_ms: ms(samples.last()),
+        }
+    }
+
+    /// Get measurements per second, 0 if nothing was measured.
+    fn rate(&self) -> f64 {
+        if self.total_ms > 0.0 {
+            self.count as f64 * 1000.0 / self.total_ms
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Throughput of finding archives of a library.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ScanReport {
+    /// Amount of archives found.
+    pub archives: usize,
+    /// Time spent finding archives.
+    pub seconds: f64,
+    /// Archives found per second.
+    pub archives_per_second: f64,
+}
+
+/// Rate of a repeated operation, along with its timings.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct RateReport {
+    /// Operations completed per second.
+    pub per_second: f64,
+    /// Operations which failed, not included in timings.
+    pub failed: usize,
+    /// Timings of completed operations.
+    pub timings: Timings,
+}
+
+impl RateReport {
+    /// Summarize durations of completed operations along with the amount that failed.
+    fn new(samples: Vec<Duration>, failed: usize) -> Self {
+        let timings = Timings::new(samples);
+        Self {
+            per_second: timings.rate(),
+            failed,
+            timings,
+        }
+    }
+}
+
+/// Latency of catalogue queries made for each item listed.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct QueryReport {
+    /// Reading state of an archive.
+    pub state: Timings,
+    /// Chosen cover of an archive.
+    pub cover: Timings,
+}
+
+/// Report of benchmarks of a library.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    /// Library benchmarked.
+    pub directory: PathBuf,
+    /// Amount of archives sampled for covers, decoding and queries.
+    pub sampled: usize,
+    /// Finding archives of library.
+    pub scan: ScanReport,
+    /// Extracting covers downscaled to thumbnails.
+    pub covers: RateReport,
+    /// Reading pages out of archives.
+    pub read: RateReport,
+    /// Decoding read pages into rgba pixels.
+    pub decode: RateReport,
+    /// Querying the catalogue.
+    pub queries: QueryReport,
+}
+
+/// Measure a closure, giving its result along with the time it took.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let started = Instant::now();
+    let value = f();
+    (value, started.elapsed())
+}
+
+/// Pick up to the given amount of archives spread evenly across all archives.
+pub fn sample(archives: &[PathBuf], samples: usize) -> Vec<&Path> {
+    let step = archives.len().div_ceil(samples.max(1)).max(1);
+    archives
+        .iter()
+        .step_by(step)
+        .take(samples)
+        .map(PathBuf::as_path)
+        .collect()
+}
+
+/// Summarize time spent finding the given amount of archives.
+pub fn scan(archives: usize, elapsed: Duration) -> ScanReport {
+    let seconds = elapsed.as_secs_f64();
+    ScanReport {
+        archives,
+        seconds,
+        archives_per_second: if seconds > 0.0 {
+            archives as f64 / seconds
+        } else {
+            0.0
+        },
+    }
+}
+
+/// Log an archive that could not be benchmarked.
+fn failed(path: &Path, source: ArchiveError) {
+    Error::Archive {
+        path: path.to_path_buf(),
+        source,
+    }
+    .log();
+}
+
+/// Extract covers of archives downscaled to fit within a square of the given size.
+/// Failures are logged.
+pub fn covers(archives: &[&Path], size: u32) -> RateReport {
+    let mut samples = Vec::with_capacity(archives.len());
+    let mut errors = 0;
+    for path in archives {
+        match timed(|| archive::preview(path, size)) {
+            (Ok(..), elapsed) => samples.push(elapsed),
+            (Err(source), _) => {
+                failed(path, source);
+                errors += 1;
+            }
+        }
+    }
+    RateReport::new(samples, errors)
+}
+
+/// Read and decode the first pages of archives, giving reports of reading and of
+/// decoding. Failures are logged, and end the benchmark of their archive.
+pub fn pages(archives: &[&Path]) -> (RateReport, RateReport) {
+    let mut read = Vec::new();
+    let mut decode = Vec::new();
+    let mut read_errors = 0;
+    let mut decode_errors = 0;
+    for path in archives {
+        let mut archive = match Archive::open(path) {
+            Ok(archive) => archive,
+            Err(source) => {
+                failed(path, source);
+                read_errors += 1;
+                continue;
+            }
+        };
+        for index in 0..archive.page_count().min(DECODE_PAGES) {
+            let page = match timed(|| archive.page(index)) {
+                (Ok(page), elapsed) => {
+                    read.push(elapsed);
+                    page
+                }
+                (Err(source), _) => {
+                    failed(path, source);
+                    read_errors += 1;
+                    break;
+                }
+            };
+            match timed(|| page.rgba()) {
+                (Ok(..), elapsed) => decode.push(elapsed),
+                (Err(source), _) => {
+                    failed(path, source);
+                    decode_errors += 1;
+                    break;
+                }
+            }
+        }
+    }
+    (
+        RateReport::new(read, read_errors),
+        RateReport::new(decode, decode_errors),
+    )
+}
+
+/// Query the catalogue for each archive as listing it does.
+///
+/// # Errors
+/// If the catalogue cannot be queried.
+pub fn queries(catalogue: &Catalogue, archives: &[&Path]) -> Result<QueryReport, Error> {
+    let mut state = Vec::with_capacity(archives.len());
+    let mut cover = Vec::with_capacity(archives.len());
+    for path in archives {
+        let (result, elapsed) = timed(|| catalogue.get(path));
+        result?;
+        state.push(elapsed);
+        let (result, elapsed) = timed(|| catalogue.cover(path));
+        result?;
+        cover.push(elapsed);
+    }
+    Ok(QueryReport {
+        state: Timings::new(state),
+        cover: Timings::new(cover),
+    })
+}
+
+impl Report {
+    /// Get status of benchmarks, [Status::ErrorsFound] if any archive failed.
+    pub const fn status(&self) -> Status {
+        if self.covers.failed + self.read.failed + self.decode.failed > 0 {
+            Status::ErrorsFound
+        } else {
+            Status::Ok
+        }
+    }
+}
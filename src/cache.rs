@@ -0,0 +1,347 @@
+//! [ThumbnailCache] impl.
+//!
+//! Evicted thumbnails are not tracked by items, instead items with a loaded thumbnail
+//! missing from the cache are requested again when they come into view. Thumbnails of
+//! pages shown in the page strip of readers share the cache and its budget with covers.
+//!
+//! The cache holds any [Thumbnail], such as the [Page] thumbnails of
+//! [archive::preview](crate::archive::preview), the user interface keeps image handles
+//! ready to be drawn.
+
+#[cfg(feature = "gui")]
+use ::std::time::Instant;
+use ::std::{collections::BTreeMap, path::Path, sync::Arc};
+
+use ::hashbrown::HashMap;
+#[cfg(feature = "gui")]
+use ::iced::{Color, widget::image::Handle};
+
+#[cfg(feature = "gui")]
+use crate::animation::Animation;
+use crate::archive::Page;
+
+/// Image held by a [ThumbnailCache].
+pub trait Thumbnail {
+    /// Get approximate memory used by image in bytes.
+    fn size(&self) -> usize;
+}
+
+impl Thumbnail for Page {
+    fn size(&self) -> usize {
+        match self {
+            Page::Encoded(bytes) => bytes.len(),
+            Page::Rgba { pixels, .. } => pixels.len(),
+        }
+    }
+}
+
+/// Convert page into an image handle.
+#[cfg(feature = "gui")]
+pub fn handle(page: Page) -> Handle {
+    match page {
+        Page::Encoded(bytes) => Handle::from_bytes(bytes),
+        Page::Rgba {
+            width,
+            height,
+            pixels,
+        } => Handle::from_rgba(width, height, pixels),
+    }
+}
+
+/// Get approximate memory used by image data of a handle.
+#[cfg(feature = "gui")]
+pub fn handle_size(handle: &Handle) -> usize {
+    match handle {
+        Handle::Path(_, path) => path.as_os_str().len(),
+        Handle::Bytes(_, bytes) => bytes.len(),
+        Handle::Rgba { pixels, .. } => pixels.len(),
+    }
+}
+
+/// Thumbnail shown by the user interface, along with its frames if animated.
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone)]
+pub struct Shown {
+    /// Still image of thumbnail.
+    pub handle: Handle,
+    /// Frames of thumbnail if it is animated, shown instead of its still image.
+    pub animation: Option<Animation>,
+}
+
+#[cfg(feature = "gui")]
+impl Shown {
+    /// Get image to draw, the current frame of animated thumbnails.
+    pub fn image(&self) -> &Handle {
+        self.animation
+            .as_ref()
+            .map_or(&self.handle, Animation::frame)
+    }
+}
+
+#[cfg(feature = "gui")]
+impl From<Handle> for Shown {
+    fn from(handle: Handle) -> Self {
+        Self {
+            handle,
+            animation: None,
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
+impl Thumbnail for Shown {
+    fn size(&self) -> usize {
+        handle_size(&self.handle) + self.animation.as_ref().map_or(0, Animation::size)
+    }
+}
+
+/// Cached thumbnail.
+#[derive(Debug, Clone)]
+struct Entry<T> {
+    /// Thumbnail image.
+    image: T,
+    /// Dominant color of thumbnail, used to tint its card.
+    accent: Option<[u8; 3]>,
+    /// Approximate size of thumbnail in bytes.
+    size: usize,
+    /// Tick thumbnail was last used at.
+    last_used: u64,
+}
+
+/// Key of a cached thumbnail.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    /// Cover of archive at path.
+    Cover(Arc<Path>),
+    /// Page at index of archive at path.
+    Page(Arc<Path>, usize),
+}
+
+/// Memory bounded thumbnail cache, evicting least recently used thumbnails.
+#[derive(Debug, Clone)]
+pub struct ThumbnailCache<T> {
+    /// Cached cover thumbnails.
+    entries: HashMap<Arc<Path>, Entry<T>>,
+    /// Cached page thumbnails by archive path and page index.
+    pages: HashMap<(Arc<Path>, usize), Entry<T>>,
+    /// Keys of cached thumbnails by the tick they were last used at.
+    order: BTreeMap<u64, Key>,
+    /// Current tick, incremented on each use.
+    tick: u64,
+    /// Approximate memory used by thumbnails in bytes.
+    used: usize,
+    /// Memory budget in bytes.
+    budget: usize,
+}
+
+impl<T> Default for ThumbnailCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            pages: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            used: 0,
+            budget: 0,
+        }
+    }
+}
+
+impl<T: Thumbnail> ThumbnailCache<T> {
+    /// Create a new cache with the given memory budget in bytes.
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            ..Self::default()
+        }
+    }
+
+    /// Get amount of cached thumbnails, of covers and pages.
+    pub fn len(&self) -> usize {
+        self.entries.len() + self.pages.len()
+    }
+
+    /// Check if no thumbnails are cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.pages.is_empty()
+    }
+
+    /// Get approximate memory used by thumbnails in bytes.
+    pub const fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Get memory budget in bytes.
+    pub const fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Get thumbnail of path without marking it as used.
+    pub fn get(&self, path: &Path) -> Option<&T> {
+        self.entries.get(path).map(|entry| &entry.image)
+    }
+
+    /// Get thumbnail of page at index of archive at path without marking it as used.
+    pub fn page(&self, path: &Arc<Path>, page: usize) -> Option<&T> {
+        self.pages
+            .get(&(Arc::clone(path), page))
+            .map(|entry| &entry.image)
+    }
+
+    /// Get dominant color of thumbnail of path, if cached and found.
+    pub fn accent(&self, path: &Path) -> Option<[u8; 3]> {
+        self.entries.get(path).and_then(|entry| entry.accent)
+    }
+
+    /// Mark thumbnail of path as used, returns false if it is not cached.
+    pub fn touch(&mut self, path: &Path) -> bool {
+        let Some(entry) = self.entries.get_mut(path) else {
+            return false;
+        };
+        self.tick += 1;
+        if let Some(key) = self.order.remove(&entry.last_used) {
+            self.order.insert(self.tick, key);
+        }
+        entry.last_used = self.tick;
+        true
+    }
+
+    /// Mark thumbnail of page at index of archive at path as used, returns false if it is
+    /// not cached.
+    pub fn touch_page(&mut self, path: &Arc<Path>, page: usize) -> bool {
+        let Some(entry) = self.pages.get_mut(&(Arc::clone(path), page)) else {
+            return false;
+        };
+        self.tick += 1;
+        if let Some(key) = self.order.remove(&entry.last_used) {
+            self.order.insert(self.tick, key);
+        }
+        entry.last_used = self.tick;
+        true
+    }
+
+    /// Insert a thumbnail along with its dominant color, evicting thumbnails to stay
+    /// within budget.
+    pub fn insert(&mut self, path: Arc<Path>, image: T, accent: Option<[u8; 3]>) {
+        self.tick += 1;
+        let size = image.size();
+        let entry = Entry {
+            image,
+            accent,
+            size,
+            last_used: self.tick,
+        };
+        if let Some(prev) = self.entries.insert(Arc::clone(&path), entry) {
+            self.order.remove(&prev.last_used);
+            self.used -= prev.size;
+        }
+        self.order.insert(self.tick, Key::Cover(path));
+        self.used += size;
+
+        self.evict();
+    }
+
+    /// Insert a thumbnail of page at index of archive at path, evicting thumbnails to
+    /// stay within budget.
+    pub fn insert_page(&mut self, path: Arc<Path>, page: usize, image: T) {
+        self.tick += 1;
+        let size = image.size();
+        let entry = Entry {
+            image,
+            accent: None,
+            size,
+            last_used: self.tick,
+        };
+        if let Some(prev) = self.pages.insert((Arc::clone(&path), page), entry) {
+            self.order.remove(&prev.last_used);
+            self.used -= prev.size;
+        }
+        self.order.insert(self.tick, Key::Page(path, page));
+        self.used += size;
+
+        self.evict();
+    }
+
+    /// Remove thumbnails of cover and pages of path, such that they are loaded again
+    /// when next shown.
+    pub fn remove(&mut self, path: &Path) {
+        if let Some(entry) = self.entries.remove(path) {
+            self.order.remove(&entry.last_used);
+            self.used -= entry.size;
+        }
+        self.pages.retain(|(page_path, _), entry| {
+            let keep = **page_path != *path;
+            if !keep {
+                self.order.remove(&entry.last_used);
+                self.used -= entry.size;
+            }
+            keep
+        });
+    }
+
+    /// Remove every thumbnail, such that thumbnails are loaded again when next shown.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.pages.clear();
+        self.order.clear();
+        self.used = 0;
+    }
+
+    /// Set memory budget in bytes, evicting thumbnails to stay within it.
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict();
+    }
+
+    /// Evict least recently used thumbnails until within budget, the most recently used
+    /// thumbnail is always kept.
+    fn evict(&mut self) {
+        while self.used > self.budget && self.order.len() > 1 {
+            let Some((_, key)) = self.order.pop_first() else {
+                break;
+            };
+            let entry = match key {
+                Key::Cover(path) => self.entries.remove(&path),
+                Key::Page(path, page) => self.pages.remove(&(path, page)),
+            };
+            if let Some(entry) = entry {
+                self.used -= entry.size;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
+impl ThumbnailCache<Shown> {
+    /// Get dominant color of thumbnail of path as a color cards are tinted with, if
+    /// cached and found.
+    pub fn accent_color(&self, path: &Path) -> Option<Color> {
+        self.accent(path).map(|[r, g, b]| Color::from_rgb8(r, g, b))
+    }
+
+    /// Check if any cached thumbnail is animated.
+    pub fn is_animating(&self) -> bool {
+        self.entries
+            .values()
+            .any(|entry| entry.image.animation.is_some())
+    }
+
+    /// Advance animated thumbnails to the frame shown at the given time, returns true if
+    /// any shown frame changed.
+    pub fn advance(&mut self, now: Instant) -> bool {
+        self.entries
+            .values_mut()
+            .filter_map(|entry| entry.image.animation.as_mut())
+            .fold(false, |changed, animation| animation.advance(now) | changed)
+    }
+
+    /// Drop frames of animated thumbnails, such that their still images are shown.
+    pub fn stop_animations(&mut self) {
+        for entry in self.entries.values_mut() {
+            if let Some(animation) = entry.image.animation.take() {
+                entry.size -= animation.size();
+                self.used -= animation.size();
+            }
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! [Calendar] impl, a month of dates archives were added to the library and finished.
+
+use ::std::{path::Path, sync::Arc};
+
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+    window,
+};
+use ::jiff::{ToSpan, Zoned, civil::Date, tz::TimeZone};
+use ::tap::Pipe;
+
+use crate::{
+    Message,
+    catalogue::{Catalogue, Event},
+    error::Error,
+    i18n::{tr, tr_args},
+    list_entry_name,
+};
+
+/// Amount of weeks shown, enough for any month.
+const WEEKS: usize = 6;
+
+/// Height of day cells.
+const DAY_HEIGHT: f32 = 56.0;
+
+/// State of a calendar window.
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    /// First day of shown month.
+    month: Date,
+    /// Events of shown month along with the day they occurred, ordered by time.
+    events: Vec<(Date, Arc<Path>, Event)>,
+    /// Day events are listed for.
+    selected: Date,
+}
+
+impl Calendar {
+    /// Load calendar of the current month, errors are logged and leave it empty.
+    pub fn load(catalogue: &Catalogue) -> Self {
+        let today = Zoned::now().date();
+        let mut calendar = Self {
+            month: today.first_of_month(),
+            events: Vec::new(),
+            selected: today,
+        };
+        calendar.reload(catalogue);
+        calendar
+    }
+
+    /// Load events of shown month.
+    fn reload(&mut self, catalogue: &Catalogue) {
+        let tz = TimeZone::system();
+        let second = |date: Date| {
+            date.to_zoned(tz.clone())
+                .map_or(0, |zoned| zoned.timestamp().as_second())
+        };
+        let end = self
+            .month
+            .checked_add(1.month())
+            .unwrap_or(self.month.last_of_month());
+        self.events = catalogue
+            .events(second(self.month), second(end))
+            .inspect_err(Error::log)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(second, path, event)| {
+                let date = ::jiff::Timestamp::from_second(second)
+                    .ok()?
+                    .to_zoned(tz.clone())
+                    .date();
+                Some((date, Arc::from(path), event))
+            })
+            .collect();
+    }
+
+    /// Show the month a number of months from the shown month, selecting its first day.
+    pub fn shift_month(&mut self, months: i32, catalogue: &Catalogue) {
+        let Ok(month) = self.month.checked_add(months.months()) else {
+            return;
+        };
+        self.month = month;
+        self.selected = month;
+        self.reload(catalogue);
+    }
+
+    /// Select a day of the shown month, listing its events.
+    pub const fn select(&mut self, day: Date) {
+        self.selected = day;
+    }
+
+    /// View calendar window.
+    pub fn view(&self, window_id: window::Id) -> Element<'_, Message> {
+        let today = Zoned::now().date();
+        let header = widget::Row::new()
+            .spacing(5)
+            .align_y(Center)
+            .push(
+                widget::button("<")
+                    .padding([2, 8])
+                    .on_press(Message::ShiftCalendarMonth(window_id, -1)),
+            )
+            .push(
+                widget::text(tr_args(
+                    "calendar-month",
+                    &[
+                        ("name", &month_name(self.month)),
+                        ("year", &self.month.year().to_string()),
+                    ],
+                ))
+                .size(20)
+                .width(Fill)
+                .center(),
+            )
+            .push(
+                widget::button(">")
+                    .padding([2, 8])
+                    .on_press(Message::ShiftCalendarMonth(window_id, 1)),
+            );
+
+        let weekdays = widget::Row::new()
+            .spacing(3)
+            .extend((1..=7).map(|weekday: u8| {
+                widget::text(tr_args(
+                    "calendar-weekday-short",
+                    &[("weekday", &weekday.to_string())],
+                ))
+                .style(widget::text::secondary)
+                .width(Fill)
+                .center()
+                .into()
+            }));
+
+        // Weeks start on monday, days before the first of the month are left blank.
+        let lead = i64::from(self.month.weekday().to_monday_zero_offset());
+        let weeks = (0..WEEKS).map(|week| {
+            widget::Row::new()
+                .spacing(3)
+                .extend((0..7).map(|weekday| {
+                    let offset = (week * 7 + weekday) as i64 - lead;
+                    self.month
+                        .checked_add(offset.days())
+                        .ok()
+                        .filter(|day| day.first_of_month() == self.month)
+                        .map_or_else(
+                            || widget::space().width(Fill).height(DAY_HEIGHT).into(),
+                            |day| self.day(window_id, day, day == today),
+                        )
+                }))
+                .into()
+        });
+
+        let listed = self
+            .events
+            .iter()
+            .filter(|(date, ..)| *date == self.selected)
+            .map(|(_, path, event)| {
+                let (text, style): (_, fn(&::iced::Theme) -> widget::text::Style) = match event {
+                    Event::Added => ("calendar-added", widget::text::secondary),
+                    Event::Finished => ("calendar-finished", widget::text::success),
+                };
+                widget::Row::new()
+                    .spacing(5)
+                    .align_y(Center)
+                    .push(widget::text(tr(text)).style(style).width(70))
+                    .push(
+                        widget::button(
+                            widget::text(list_entry_name(path)).wrapping(Wrapping::None),
+                        )
+                        .padding([2, 6])
+                        .width(Fill)
+                        .style(widget::button::text)
+                        .on_press(Message::OpenArchive(Arc::clone(path))),
+                    )
+                    .into()
+            })
+            .collect::<Vec<_>>();
+        let empty = listed.is_empty();
+
+        widget::Column::new()
+            .padding(5)
+            .spacing(5)
+            .push(header)
+            .push(weekdays)
+            .extend(weeks)
+            .push(
+                widget::text(tr_args(
+                    "calendar-day",
+                    &[
+                        (
+                            "weekday",
+                            &tr_args(
+                                "calendar-weekday",
+                                &[(
+                                    "weekday",
+                                    &self.selected.weekday().to_monday_one_offset().to_string(),
+                                )],
+                            ),
+                        ),
+                        ("day", &self.selected.day().to_string()),
+                        ("month", &month_name(self.selected)),
+                    ],
+                ))
+                .size(16),
+            )
+            .push(empty.then(|| widget::text(tr("calendar-empty")).style(widget::text::secondary)))
+            .extend(listed)
+            .pipe(widget::scrollable)
+            .into()
+    }
+
+    /// View a day cell with the amount of archives added and finished on it.
+    fn day<'a>(&self, window_id: window::Id, day: Date, is_today: bool) -> Element<'a, Message> {
+        let count = |kind: Event| {
+            self.events
+                .iter()
+                .filter(|(date, _, event)| *date == day && *event == kind)
+                .count()
+        };
+        let (added, finished) = (count(Event::Added), count(Event::Finished));
+        widget::Column::new()
+            .push(widget::text(day.day().to_string()).style(if is_today {
+                widget::text::primary
+            } else {
+                widget::text::default
+            }))
+            .push((added > 0).then(|| {
+                widget::text(format!("+{added}"))
+                    .size(12)
+                    .style(widget::text::secondary)
+            }))
+            .push((finished > 0).then(|| {
+                widget::text(format!("\u{2713}{finished}"))
+                    .size(12)
+                    .style(widget::text::success)
+            }))
+            .pipe(widget::button)
+            .padding(3)
+            .width(Fill)
+            .height(DAY_HEIGHT)
+            .style(if day == self.selected {
+                widget::button::primary
+            } else {
+                widget::button::secondary
+            })
+            .on_press(Message::SelectCalendarDay(window_id, day))
+            .into()
+    }
+}
+
+/// Get name of month of a date.
+fn month_name(date: Date) -> String {
+    tr_args(
+        "calendar-month-name",
+        &[("month", &date.month().to_string())],
+    )
+}
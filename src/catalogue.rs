@@ -0,0 +1,1881 @@
+//! Catalogue of per-item state such as reading progress, stars and labels, stored in an
+//! sqlite database per profile.
+
+use ::core::{fmt::Display, time::Duration};
+use ::std::{
+    collections::BTreeSet,
+    ffi::OsString,
+    fs::File,
+    io::{self, Read},
+    os::unix::ffi::OsStringExt,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use ::derive_more::IsVariant;
+use ::hashbrown::{HashMap, HashSet};
+use ::rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+use ::serde::Serialize;
+
+use crate::{
+    archive::{self, Adjustments, ArchiveKind, ComicInfo, Cover},
+    error::{CatalogueError, Error},
+    freedesktop,
+    i18n::tr,
+    isbn::Isbn,
+    listing::{Column, Sort, ViewMode},
+    series,
+};
+
+/// Name of database file in data directory.
+const DATABASE_FILE: &str = "catalogue.sqlite";
+
+/// Amount of page turns measured before the measured reading speed is used.
+const MIN_MEASURED_PAGES: u64 = 20;
+
+/// Length of the start of archives hashed by [fingerprint].
+const FINGERPRINT_LEN: u64 = 64 * 1024;
+
+/// Amount of recently opened directories and archives remembered.
+const RECENT_LIMIT: usize = 10;
+
+/// Version of database, stored as its user version. Databases of older versions are
+/// migrated when opened.
+const VERSION: i64 = 1;
+
+/// Schema of database, created if missing.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS items (
+    path BLOB PRIMARY KEY NOT NULL,
+    read_state INTEGER NOT NULL DEFAULT 0,
+    page INTEGER NOT NULL DEFAULT 0,
+    starred INTEGER NOT NULL DEFAULT 0,
+    label INTEGER
+);
+CREATE TABLE IF NOT EXISTS series_order (
+    series BLOB NOT NULL,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    PRIMARY KEY (series, position)
+);
+CREATE TABLE IF NOT EXISTS reading_lists (
+    name TEXT PRIMARY KEY NOT NULL
+);
+CREATE TABLE IF NOT EXISTS reading_list_entries (
+    list TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    path BLOB NOT NULL,
+    PRIMARY KEY (list, position)
+);
+CREATE TABLE IF NOT EXISTS seen (
+    path BLOB PRIMARY KEY NOT NULL,
+    first_seen INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS history (
+    path BLOB PRIMARY KEY NOT NULL,
+    last_read INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS reading_speed (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    pages INTEGER NOT NULL,
+    seconds REAL NOT NULL
+);
+CREATE TABLE IF NOT EXISTS recent (
+    path BLOB PRIMARY KEY NOT NULL,
+    opened INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS bookmarks (
+    path BLOB NOT NULL,
+    page INTEGER NOT NULL,
+    fingerprint TEXT NOT NULL,
+    PRIMARY KEY (path, page)
+);
+CREATE INDEX IF NOT EXISTS bookmarks_fingerprint ON bookmarks (fingerprint);
+CREATE TABLE IF NOT EXISTS reading_activity (
+    day INTEGER PRIMARY KEY NOT NULL,
+    pages INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS view_modes (
+    path BLOB PRIMARY KEY NOT NULL,
+    mode INTEGER NOT NULL,
+    sort_column INTEGER,
+    descending INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS page_counts (
+    path BLOB PRIMARY KEY NOT NULL,
+    modified INTEGER NOT NULL,
+    page_count INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS isbns (
+    path BLOB PRIMARY KEY NOT NULL,
+    isbn TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS isbns_isbn ON isbns (isbn);
+CREATE TABLE IF NOT EXISTS offline (
+    path BLOB PRIMARY KEY NOT NULL,
+    drive TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wishlist (
+    series TEXT NOT NULL,
+    volume REAL NOT NULL,
+    name TEXT NOT NULL,
+    PRIMARY KEY (series, volume)
+);
+CREATE TABLE IF NOT EXISTS followed (
+    series TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS alerts (
+    path BLOB PRIMARY KEY NOT NULL,
+    series TEXT NOT NULL,
+    added INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS item_applications (
+    path BLOB PRIMARY KEY NOT NULL,
+    application TEXT
+);
+CREATE TABLE IF NOT EXISTS kind_applications (
+    kind TEXT PRIMARY KEY NOT NULL,
+    application TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS comic_info (
+    path BLOB PRIMARY KEY NOT NULL,
+    title TEXT NOT NULL,
+    series TEXT NOT NULL,
+    number TEXT NOT NULL,
+    writer TEXT NOT NULL,
+    year TEXT NOT NULL,
+    tags TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS covers (
+    path BLOB PRIMARY KEY NOT NULL,
+    page INTEGER,
+    image BLOB
+);
+CREATE TABLE IF NOT EXISTS visits (
+    path BLOB PRIMARY KEY NOT NULL,
+    visited INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS adjustments (
+    path BLOB PRIMARY KEY NOT NULL,
+    brightness REAL NOT NULL,
+    contrast REAL NOT NULL,
+    gamma REAL NOT NULL,
+    invert INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS trash (
+    path BLOB PRIMARY KEY NOT NULL,
+    trashed BLOB NOT NULL,
+    deleted INTEGER NOT NULL
+);
+";
+
+/// Reading progress of an item.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, IsVariant, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadState {
+    /// Item has not been opened.
+    #[default]
+    Unread,
+    /// Item has been opened but not read to the end.
+    Reading,
+    /// Last page of item has been reached.
+    Finished,
+}
+
+impl ReadState {
+    /// All read states.
+    pub const ALL: [Self; 3] = [Self::Unread, Self::Reading, Self::Finished];
+
+    /// Get read state from its database representation.
+    const fn from_i64(value: i64) -> Self {
+        match value {
+            1 => Self::Reading,
+            2 => Self::Finished,
+            _ => Self::Unread,
+        }
+    }
+
+    /// Get database representation of read state.
+    const fn to_i64(self) -> i64 {
+        match self {
+            Self::Unread => 0,
+            Self::Reading => 1,
+            Self::Finished => 2,
+        }
+    }
+}
+
+impl Display for ReadState {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            ReadState::Unread => "state-unread",
+            ReadState::Reading => "state-reading",
+            ReadState::Finished => "state-finished",
+        }))
+    }
+}
+
+/// Color label of an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Label {
+    /// Red label.
+    Red,
+    /// Orange label.
+    Orange,
+    /// Yellow label.
+    Yellow,
+    /// Green label.
+    Green,
+    /// Blue label.
+    Blue,
+    /// Purple label.
+    Purple,
+}
+
+impl Label {
+    /// All labels.
+    pub const ALL: [Self; 6] = [
+        Self::Red,
+        Self::Orange,
+        Self::Yellow,
+        Self::Green,
+        Self::Blue,
+        Self::Purple,
+    ];
+
+    /// Get label from its database representation.
+    fn from_i64(value: i64) -> Option<Self> {
+        usize::try_from(value)
+            .ok()
+            .and_then(|index| Self::ALL.get(index))
+            .copied()
+    }
+
+    /// Get database representation of label.
+    const fn to_i64(self) -> i64 {
+        self as i64
+    }
+
+    /// Get label following label, cycling through no label.
+    pub fn cycle(label: Option<Self>) -> Option<Self> {
+        match label {
+            None => Some(Self::ALL[0]),
+            Some(label) => Self::ALL.get(label as usize + 1).copied(),
+        }
+    }
+}
+
+/// Catalogue state of an item.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ItemState {
+    /// Reading progress.
+    pub read_state: ReadState,
+    /// Index of last read page.
+    pub page: usize,
+    /// Item is starred.
+    pub starred: bool,
+    /// Color label of item.
+    pub label: Option<Label>,
+}
+
+/// Counts of items in catalogue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Archives found when scanning directories.
+    pub seen: u64,
+    /// Items being read.
+    pub reading: u64,
+    /// Items read to the end.
+    pub finished: u64,
+    /// Starred items.
+    pub starred: u64,
+    /// Pages of archives with a known page count.
+    pub pages: u64,
+}
+
+/// Kinds of dated events of items of the library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, IsVariant)]
+pub enum Event {
+    /// Archive was first found when scanning.
+    Added,
+    /// Item was last read, and has been read to the end.
+    Finished,
+}
+
+/// Volume of a series on the wishlist, not yet owned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wish {
+    /// Name of series.
+    pub series: String,
+    /// Volume number.
+    pub volume: f64,
+}
+
+/// Volume of a followed series found when scanning, not yet dismissed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alert {
+    /// Name of series.
+    pub series: String,
+    /// Path to volume.
+    pub path: PathBuf,
+}
+
+/// Archive moved to trash by application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trashed {
+    /// Path archive was moved from.
+    pub path: PathBuf,
+    /// Path of archive in trash.
+    pub trashed: PathBuf,
+    /// Time archive was moved to trash.
+    pub deleted: SystemTime,
+}
+
+/// Get current time as seconds since the unix epoch, as stored in database.
+fn now() -> i64 {
+    timestamp(SystemTime::now())
+}
+
+/// Get current day as days since the unix epoch, as stored in database.
+pub fn today() -> i64 {
+    now().div_euclid(24 * 60 * 60)
+}
+
+/// Get time as seconds since the unix epoch, as stored in database.
+fn timestamp(time: SystemTime) -> i64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| {
+            i64::try_from(duration.as_secs()).unwrap_or(i64::MAX)
+        })
+}
+
+/// Get fingerprint of the content of archive at path, the md5 digest of its size and
+/// start, used to find bookmarks of renamed archives.
+///
+/// # Errors
+/// If the archive cannot be read.
+pub fn fingerprint(path: &Path) -> io::Result<String> {
+    let file = File::open(path)?;
+    let mut data = file.metadata()?.len().to_le_bytes().to_vec();
+    file.take(FINGERPRINT_LEN).read_to_end(&mut data)?;
+    Ok(freedesktop::md5(&data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Catalogue database.
+#[derive(Debug)]
+pub struct Catalogue {
+    /// Connection to database.
+    connection: Connection,
+    /// Path of database, used in errors.
+    path: PathBuf,
+}
+
+impl Default for Catalogue {
+    fn default() -> Self {
+        let connection =
+            Connection::open_in_memory().expect("in memory database should be creatable");
+        connection
+            .execute_batch(SCHEMA)
+            .expect("schema should be valid");
+        Self {
+            connection,
+            path: PathBuf::from(":memory:"),
+        }
+    }
+}
+
+/// Split comma separated tags of metadata into names.
+fn split_tags(tags: &str) -> impl Iterator<Item = &str> {
+    tags.split(',').map(str::trim).filter(|tag| !tag.is_empty())
+}
+
+/// Get database key of a path.
+fn key(path: &Path) -> &[u8] {
+    path.as_os_str().as_encoded_bytes()
+}
+
+/// Get path from its database key.
+fn path_from_key(key: Vec<u8>) -> PathBuf {
+    PathBuf::from(OsString::from_vec(key))
+}
+
+/// Migrate database of an older version to [VERSION].
+///
+/// # Errors
+/// If the database cannot be queried or updated.
+fn migrate(connection: &mut Connection) -> ::rusqlite::Result<()> {
+    // Version is read within the transaction, such that concurrently opened catalogues
+    // are migrated once.
+    let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let version = transaction.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))?;
+    if version >= VERSION {
+        return Ok(());
+    }
+    if version < 1 {
+        remap_pages(&transaction)?;
+    }
+    transaction.pragma_update(None, "user_version", VERSION)?;
+    transaction.commit()
+}
+
+/// Remap saved pages, bookmarks and covers of zip archives, whose pages were ordered by
+/// the bytes of their names before they were ordered naturally. Archives which cannot be
+/// read are left as they are.
+///
+/// # Errors
+/// If the database cannot be queried or updated.
+fn remap_pages(connection: &Connection) -> ::rusqlite::Result<()> {
+    let paths = connection
+        .prepare(
+            "SELECT path FROM items WHERE page > 0
+             UNION SELECT path FROM bookmarks
+             UNION SELECT path FROM covers WHERE page IS NOT NULL",
+        )?
+        .query_map([], |row| row.get(0).map(path_from_key))?
+        .collect::<Result<Vec<_>, _>>()?;
+    for path in paths {
+        if !ArchiveKind::from_path(&path).is_some_and(|kind| kind.is_cbz()) {
+            continue;
+        }
+        let indices = match archive::natural_page_indices(&path) {
+            Ok(indices) => indices,
+            Err(source) => {
+                Error::Archive { path, source }.log();
+                continue;
+            }
+        };
+        if indices.iter().enumerate().all(|(old, &new)| old == new) {
+            continue;
+        }
+        let remap = |page: usize| indices.get(page).copied().unwrap_or(page);
+
+        let page = connection
+            .query_row(
+                "SELECT page FROM items WHERE path = ?1",
+                params![key(&path)],
+                |row| row.get::<_, usize>(0),
+            )
+            .optional()?;
+        if let Some(page) = page {
+            connection.execute(
+                "UPDATE items SET page = ?2 WHERE path = ?1",
+                params![key(&path), remap(page)],
+            )?;
+        }
+        let cover = connection
+            .query_row(
+                "SELECT page FROM covers WHERE path = ?1 AND page IS NOT NULL",
+                params![key(&path)],
+                |row| row.get::<_, usize>(0),
+            )
+            .optional()?;
+        if let Some(page) = cover {
+            connection.execute(
+                "UPDATE covers SET page = ?2 WHERE path = ?1",
+                params![key(&path), remap(page)],
+            )?;
+        }
+        // Bookmarks are replaced as a whole, remapped pages may collide with pages not yet
+        // remapped.
+        let bookmarks = connection
+            .prepare("SELECT page, fingerprint FROM bookmarks WHERE path = ?1")?
+            .query_map(params![key(&path)], |row| {
+                Ok((row.get::<_, usize>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        connection.execute("DELETE FROM bookmarks WHERE path = ?1", params![key(&path)])?;
+        for (page, fingerprint) in bookmarks {
+            connection.execute(
+                "INSERT OR REPLACE INTO bookmarks (path, page, fingerprint) VALUES (?1, ?2, ?3)",
+                params![key(&path), remap(page), fingerprint],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+impl Catalogue {
+    /// Open catalogue of profile, creating it if it does not exist.
+    ///
+    /// # Errors
+    /// If the database cannot be created or opened.
+    pub fn open(xdg_dirs: &::xdg::BaseDirectories) -> Result<Self, Error> {
+        let path = xdg_dirs
+            .place_data_file(DATABASE_FILE)
+            .map_err(|source| Error::Catalogue {
+                path: PathBuf::from(DATABASE_FILE),
+                source: CatalogueError::Create(source),
+            })?;
+        Connection::open(&path)
+            .and_then(|mut connection| {
+                connection.execute_batch(SCHEMA)?;
+                migrate(&mut connection)?;
+                Ok(connection)
+            })
+            .map(|connection| Self {
+                connection,
+                path: path.clone(),
+            })
+            .map_err(|source| Error::Catalogue {
+                path,
+                source: CatalogueError::Database(source),
+            })
+    }
+
+    /// Wrap a database error.
+    fn error(&self, source: ::rusqlite::Error) -> Error {
+        Error::Catalogue {
+            path: self.path.clone(),
+            source: CatalogueError::Database(source),
+        }
+    }
+
+    /// Get state of item at path, items not in catalogue have the default state.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn get(&self, path: &Path) -> Result<ItemState, Error> {
+        self.connection
+            .query_row(
+                "SELECT read_state, page, starred, label FROM items WHERE path = ?1",
+                params![key(path)],
+                |row| {
+                    Ok(ItemState {
+                        read_state: ReadState::from_i64(row.get(0)?),
+                        page: row.get(1)?,
+                        starred: row.get(2)?,
+                        label: row.get::<_, Option<i64>>(3)?.and_then(Label::from_i64),
+                    })
+                },
+            )
+            .optional()
+            .map(Option::unwrap_or_default)
+            .map_err(|err| self.error(err))
+    }
+
+    /// Set state of item at path.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn set(&self, path: &Path, state: ItemState) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT INTO items (path, read_state, page, starred, label)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT (path) DO UPDATE SET
+                    read_state = excluded.read_state,
+                    page = excluded.page,
+                    starred = excluded.starred,
+                    label = excluded.label",
+                params![
+                    key(path),
+                    state.read_state.to_i64(),
+                    state.page,
+                    state.starred,
+                    state.label.map(Label::to_i64),
+                ],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Modify state of item at path, returning the new state.
+    ///
+    /// # Errors
+    /// If the database cannot be read or written.
+    pub fn update(&self, path: &Path, f: impl FnOnce(&mut ItemState)) -> Result<ItemState, Error> {
+        let mut state = self.get(path)?;
+        f(&mut state);
+        self.set(path, state)?;
+        Ok(state)
+    }
+
+    /// Move state, history and reading list entries of item at path to a new path,
+    /// replacing any recorded for the new path.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn rename(&self, from: &Path, to: &Path) -> Result<(), Error> {
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                for table in [
+                    "items",
+                    "seen",
+                    "history",
+                    "page_counts",
+                    "bookmarks",
+                    "alerts",
+                    "item_applications",
+                    "comic_info",
+                    "covers",
+                    "visits",
+                    "adjustments",
+                ] {
+                    transaction.execute(
+                        &format!("UPDATE OR REPLACE {table} SET path = ?2 WHERE path = ?1"),
+                        params![key(from), key(to)],
+                    )?;
+                }
+                transaction.execute(
+                    "UPDATE reading_list_entries SET path = ?2 WHERE path = ?1",
+                    params![key(from), key(to)],
+                )?;
+                transaction.commit()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get manual reading order of series in directory as entry names, empty if the
+    /// automatic order is used.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn series_order(&self, series: &Path) -> Result<Vec<String>, Error> {
+        self.connection
+            .prepare_cached("SELECT name FROM series_order WHERE series = ?1 ORDER BY position")
+            .and_then(|mut statement| {
+                statement
+                    .query_map(params![key(series)], |row| row.get(0))?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Set manual reading order of series in directory as entry names, an empty order
+    /// restores the automatic order.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn set_series_order(&self, series: &Path, names: &[String]) -> Result<(), Error> {
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                transaction.execute(
+                    "DELETE FROM series_order WHERE series = ?1",
+                    params![key(series)],
+                )?;
+                for (position, name) in names.iter().enumerate() {
+                    transaction.execute(
+                        "INSERT INTO series_order (series, position, name) VALUES (?1, ?2, ?3)",
+                        params![key(series), position, name],
+                    )?;
+                }
+                transaction.commit()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get how items of directory were last presented, [None] if never changed.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn view_mode(&self, dir: &Path) -> Result<Option<(ViewMode, Option<Sort>)>, Error> {
+        self.connection
+            .query_row(
+                "SELECT mode, sort_column, descending FROM view_modes WHERE path = ?1",
+                params![key(dir)],
+                |row| {
+                    let sort = row
+                        .get::<_, Option<i64>>(1)?
+                        .and_then(Column::from_i64)
+                        .map(|column| -> ::rusqlite::Result<Sort> {
+                            Ok(Sort {
+                                column,
+                                descending: row.get(2)?,
+                            })
+                        })
+                        .transpose()?;
+                    Ok((ViewMode::from_i64(row.get(0)?), sort))
+                },
+            )
+            .optional()
+            .map_err(|err| self.error(err))
+    }
+
+    /// Set how items of directory are presented.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn set_view_mode(
+        &self,
+        dir: &Path,
+        mode: ViewMode,
+        sort: Option<Sort>,
+    ) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO view_modes (path, mode, sort_column, descending)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    key(dir),
+                    mode.to_i64(),
+                    sort.map(|sort| sort.column.to_i64()),
+                    sort.is_some_and(|sort| sort.descending),
+                ],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get names of reading lists, sorted by name.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn reading_lists(&self) -> Result<Vec<String>, Error> {
+        self.connection
+            .prepare_cached("SELECT name FROM reading_lists ORDER BY name")
+            .and_then(|mut statement| statement.query_map([], |row| row.get(0))?.collect())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Check integrity of database, returning the problems found, if any.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn integrity_problems(&self) -> Result<Vec<String>, Error> {
+        self.connection
+            .prepare("PRAGMA integrity_check")
+            .and_then(|mut statement| statement.query_map([], |row| row.get(0))?.collect())
+            .map(|mut problems: Vec<String>| {
+                problems.retain(|problem| problem != "ok");
+                problems
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Write a consistent copy of the database to target, which must not exist.
+    ///
+    /// # Errors
+    /// If the copy cannot be written.
+    pub fn snapshot(&self, target: &Path) -> Result<(), Error> {
+        self.connection
+            .execute("VACUUM INTO ?1", params![target.to_string_lossy()])
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get path of database file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get paths of entries of a reading list in order, empty if there is no such list.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn reading_list(&self, name: &str) -> Result<Vec<PathBuf>, Error> {
+        self.connection
+            .prepare_cached(
+                "SELECT path FROM reading_list_entries WHERE list = ?1 ORDER BY position",
+            )
+            .and_then(|mut statement| {
+                statement
+                    .query_map(params![name], |row| row.get(0).map(path_from_key))?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Set entries of a reading list, creating the list if it does not exist. Repeated
+    /// entries are only kept at their first position.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn set_reading_list(&self, name: &str, paths: &[impl AsRef<Path>]) -> Result<(), Error> {
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                transaction.execute(
+                    "INSERT OR IGNORE INTO reading_lists (name) VALUES (?1)",
+                    params![name],
+                )?;
+                transaction.execute(
+                    "DELETE FROM reading_list_entries WHERE list = ?1",
+                    params![name],
+                )?;
+                let mut seen = HashSet::new();
+                let entries = paths
+                    .iter()
+                    .map(AsRef::as_ref)
+                    .filter(|path| seen.insert(*path));
+                for (position, path) in entries.enumerate() {
+                    transaction.execute(
+                        "INSERT INTO reading_list_entries (list, position, path)
+                         VALUES (?1, ?2, ?3)",
+                        params![name, position, key(path)],
+                    )?;
+                }
+                transaction.commit()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Record archives found when scanning, archives already seen keep the time they
+    /// were first seen. Returns the archives not seen before.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn record_seen(
+        &self,
+        paths: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let now = now();
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                let mut added = Vec::new();
+                for path in paths {
+                    let path = path.as_ref();
+                    if transaction.execute(
+                        "INSERT OR IGNORE INTO seen (path, first_seen) VALUES (?1, ?2)",
+                        params![key(path), now],
+                    )? > 0
+                    {
+                        added.push(path.to_path_buf());
+                    }
+                }
+                transaction.commit().map(|()| added)
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get page count of archive at path, if recorded while the archive had the given
+    /// modification time.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn page_count(
+        &self,
+        path: &Path,
+        modified: Option<SystemTime>,
+    ) -> Result<Option<usize>, Error> {
+        self.connection
+            .prepare_cached("SELECT page_count FROM page_counts WHERE path = ?1 AND modified = ?2")
+            .and_then(|mut statement| {
+                statement
+                    .query_row(params![key(path), modified.map_or(0, timestamp)], |row| {
+                        row.get(0)
+                    })
+                    .optional()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Record page counts of archives along with their modification times, replacing
+    /// counts recorded for earlier modifications.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn record_page_counts(
+        &self,
+        counts: &[(impl AsRef<Path>, Option<SystemTime>, usize)],
+    ) -> Result<(), Error> {
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                for (path, modified, page_count) in counts {
+                    transaction.execute(
+                        "INSERT INTO page_counts (path, modified, page_count) VALUES (?1, ?2, ?3)
+                         ON CONFLICT (path) DO UPDATE SET
+                            modified = excluded.modified,
+                            page_count = excluded.page_count",
+                        params![
+                            key(path.as_ref()),
+                            modified.map_or(0, timestamp),
+                            page_count
+                        ],
+                    )?;
+                }
+                transaction.commit()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Record time spent reading a page, measuring reading speed.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn record_page_time(&self, time: Duration) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT INTO reading_speed (id, pages, seconds) VALUES (0, 1, ?1)
+                 ON CONFLICT (id) DO UPDATE SET
+                    pages = pages + 1,
+                    seconds = seconds + excluded.seconds",
+                params![time.as_secs_f64()],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get measured average time spent reading a page, [None] if too few pages have been
+    /// measured.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn page_time(&self) -> Result<Option<Duration>, Error> {
+        self.connection
+            .query_row(
+                "SELECT seconds / pages FROM reading_speed WHERE pages >= ?1",
+                params![MIN_MEASURED_PAGES],
+                |row| row.get::<_, f64>(0),
+            )
+            .optional()
+            .map(|seconds| seconds.and_then(|seconds| Duration::try_from_secs_f64(seconds).ok()))
+            .map_err(|err| self.error(err))
+    }
+
+    /// Record that item at path was read now.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn record_read(&self, path: &Path) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT INTO history (path, last_read) VALUES (?1, ?2)
+                 ON CONFLICT (path) DO UPDATE SET last_read = excluded.last_read",
+                params![key(path), now()],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Record that a page was read today.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn record_page_read(&self) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT INTO reading_activity (day, pages) VALUES (?1, 1)
+                 ON CONFLICT (day) DO UPDATE SET pages = pages + 1",
+                params![today()],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get amount of pages read per day since day, as days since the unix epoch. Days
+    /// without reading are left out.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn reading_activity(&self, since: i64) -> Result<Vec<(i64, u64)>, Error> {
+        self.connection
+            .prepare_cached("SELECT day, pages FROM reading_activity WHERE day >= ?1 ORDER BY day")
+            .and_then(|mut statement| {
+                statement
+                    .query_map(params![since], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get events of items between two times, as seconds since the unix epoch, ordered by
+    /// time.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn events(&self, from: i64, to: i64) -> Result<Vec<(i64, PathBuf, Event)>, Error> {
+        self.connection
+            .prepare_cached(
+                "SELECT first_seen, path, 0 FROM seen WHERE first_seen >= ?1 AND first_seen < ?2
+                 UNION ALL
+                 SELECT history.last_read, history.path, 1 FROM history
+                 JOIN items ON items.path = history.path
+                 WHERE items.read_state = 2
+                    AND history.last_read >= ?1 AND history.last_read < ?2
+                 ORDER BY 1, 2",
+            )
+            .and_then(|mut statement| {
+                statement
+                    .query_map(params![from, to], |row| {
+                        Ok((
+                            row.get(0)?,
+                            row.get(1).map(path_from_key)?,
+                            if row.get::<_, i64>(2)? == 0 {
+                                Event::Added
+                            } else {
+                                Event::Finished
+                            },
+                        ))
+                    })?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get all archives found when scanning.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn seen_paths(&self) -> Result<Vec<PathBuf>, Error> {
+        self.connection
+            .prepare_cached("SELECT path FROM seen")
+            .and_then(|mut statement| {
+                statement
+                    .query_map([], |row| row.get(0).map(path_from_key))?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Record a directory or archive as opened, forgetting all but the most recently
+    /// opened ones.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn record_recent(&self, path: &Path) -> Result<(), Error> {
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                transaction.execute(
+                    // Replacing gives the row a new rowid, ordering it last among
+                    // rows opened the same second.
+                    "INSERT OR REPLACE INTO recent (path, opened) VALUES (?1, ?2)",
+                    params![key(path), now()],
+                )?;
+                transaction.execute(
+                    "DELETE FROM recent WHERE path NOT IN
+                        (SELECT path FROM recent ORDER BY opened DESC, rowid DESC LIMIT ?1)",
+                    params![RECENT_LIMIT],
+                )?;
+                transaction.commit()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get recently opened directories and archives, most recent first.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn recent(&self) -> Result<Vec<PathBuf>, Error> {
+        self.query_paths(
+            "SELECT path FROM recent ORDER BY opened DESC, rowid DESC LIMIT ?1",
+            RECENT_LIMIT,
+        )
+    }
+
+    /// Get bookmarked pages of archive at path, in page order.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn bookmarks(&self, path: &Path) -> Result<Vec<usize>, Error> {
+        self.connection
+            .prepare_cached("SELECT page FROM bookmarks WHERE path = ?1 ORDER BY page")
+            .and_then(|mut statement| {
+                statement
+                    .query_map(params![key(path)], |row| row.get(0))?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get paths bookmarks are recorded for with a content fingerprint.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn bookmarked_paths(&self, fingerprint: &str) -> Result<Vec<PathBuf>, Error> {
+        self.connection
+            .prepare_cached("SELECT DISTINCT path FROM bookmarks WHERE fingerprint = ?1")
+            .and_then(|mut statement| {
+                statement
+                    .query_map(params![fingerprint], |row| row.get(0).map(path_from_key))?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Bookmark page of archive at path, along with the content fingerprint of archive.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn add_bookmark(&self, path: &Path, page: usize, fingerprint: &str) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO bookmarks (path, page, fingerprint) VALUES (?1, ?2, ?3)",
+                params![key(path), page, fingerprint],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Remove bookmark of page of archive at path.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn remove_bookmark(&self, path: &Path, page: usize) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "DELETE FROM bookmarks WHERE path = ?1 AND page = ?2",
+                params![key(path), page],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Query paths of items, the query takes a limit as its only parameter.
+    fn query_paths(&self, query: &str, limit: usize) -> Result<Vec<PathBuf>, Error> {
+        self.connection
+            .prepare_cached(query)
+            .and_then(|mut statement| {
+                statement
+                    .query_map(params![limit], |row| row.get(0).map(path_from_key))?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get items being read, most recently read first.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn continue_reading(&self, limit: usize) -> Result<Vec<PathBuf>, Error> {
+        self.query_paths(
+            "SELECT history.path FROM history
+             JOIN items ON items.path = history.path
+             WHERE items.read_state = 1
+             ORDER BY history.last_read DESC LIMIT ?1",
+            limit,
+        )
+    }
+
+    /// Get archives most recently found when scanning, most recent first.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn recently_added(&self, limit: usize) -> Result<Vec<PathBuf>, Error> {
+        self.query_paths(
+            "SELECT path FROM seen ORDER BY first_seen DESC, path LIMIT ?1",
+            limit,
+        )
+    }
+
+    /// Get time archive at path was first found when scanning, if it has been.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn first_seen(&self, path: &Path) -> Result<Option<i64>, Error> {
+        self.connection
+            .prepare_cached("SELECT first_seen FROM seen WHERE path = ?1")
+            .and_then(|mut statement| {
+                statement
+                    .query_row(params![key(path)], |row| row.get(0))
+                    .optional()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get time directory at path was last viewed, if it has been.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn last_visit(&self, dir: &Path) -> Result<Option<i64>, Error> {
+        self.connection
+            .query_row(
+                "SELECT visited FROM visits WHERE path = ?1",
+                params![key(dir)],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|err| self.error(err))
+    }
+
+    /// Record that directory at path was viewed now, archives first seen later are new
+    /// when it is next viewed.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn record_visit(&self, dir: &Path) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT INTO visits (path, visited) VALUES (?1, ?2)
+                 ON CONFLICT (path) DO UPDATE SET visited = excluded.visited",
+                params![key(dir), now()],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get random unread archives among those found when scanning.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn random_unread(&self, limit: usize) -> Result<Vec<PathBuf>, Error> {
+        self.query_paths(
+            "SELECT seen.path FROM seen
+             LEFT JOIN items ON items.path = seen.path
+             WHERE COALESCE(items.read_state, 0) = 0
+             ORDER BY RANDOM() LIMIT ?1",
+            limit,
+        )
+    }
+
+    /// Count items in catalogue.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn stats(&self) -> Result<Stats, Error> {
+        self.connection
+            .query_row(
+                "SELECT
+                    (SELECT COUNT(*) FROM seen),
+                    (SELECT COUNT(*) FROM items WHERE read_state = 1),
+                    (SELECT COUNT(*) FROM items WHERE read_state = 2),
+                    (SELECT COUNT(*) FROM items WHERE starred),
+                    (SELECT COALESCE(SUM(page_count), 0) FROM page_counts)",
+                [],
+                |row| {
+                    Ok(Stats {
+                        seen: row.get(0)?,
+                        reading: row.get(1)?,
+                        finished: row.get(2)?,
+                        starred: row.get(3)?,
+                        pages: row.get(4)?,
+                    })
+                },
+            )
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get book number given to archive at path.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn isbn(&self, path: &Path) -> Result<Option<Isbn>, Error> {
+        self.connection
+            .prepare_cached("SELECT isbn FROM isbns WHERE path = ?1")
+            .and_then(|mut statement| {
+                statement
+                    .query_row(params![key(path)], |row| row.get::<_, String>(0))
+                    .optional()
+            })
+            .map(|isbn| isbn.as_deref().and_then(Isbn::parse))
+            .map_err(|err| self.error(err))
+    }
+
+    /// Give archive at path a book number, or remove its number if [None].
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn set_isbn(&self, path: &Path, isbn: Option<&Isbn>) -> Result<(), Error> {
+        match isbn {
+            Some(isbn) => self.connection.execute(
+                "INSERT OR REPLACE INTO isbns (path, isbn) VALUES (?1, ?2)",
+                params![key(path), isbn.as_str()],
+            ),
+            None => self
+                .connection
+                .execute("DELETE FROM isbns WHERE path = ?1", params![key(path)]),
+        }
+        .map(|_| ())
+        .map_err(|err| self.error(err))
+    }
+
+    /// Get metadata written to `ComicInfo.xml` of archive at path, as of when it was
+    /// last written by application.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn comic_info(&self, path: &Path) -> Result<Option<ComicInfo>, Error> {
+        self.connection
+            .prepare_cached(
+                "SELECT title, series, number, writer, year, tags FROM comic_info
+                 WHERE path = ?1",
+            )
+            .and_then(|mut statement| {
+                statement
+                    .query_row(params![key(path)], |row| {
+                        Ok(ComicInfo {
+                            title: row.get(0)?,
+                            series: row.get(1)?,
+                            number: row.get(2)?,
+                            writer: row.get(3)?,
+                            year: row.get(4)?,
+                            tags: row.get(5)?,
+                        })
+                    })
+                    .optional()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get tags of archives from metadata written by application, sorted and without
+    /// duplicates.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn tags(&self) -> Result<Vec<String>, Error> {
+        self.connection
+            .prepare_cached("SELECT tags FROM comic_info WHERE tags != ''")
+            .and_then(|mut statement| {
+                statement
+                    .query_map([], |row| row.get::<_, String>(0))?
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map(|tags| {
+                tags.iter()
+                    .flat_map(|tags| split_tags(tags))
+                    .map(String::from)
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get paths of archives tagged with tag in metadata written by application, sorted
+    /// by path.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn tagged(&self, tag: &str) -> Result<Vec<PathBuf>, Error> {
+        self.connection
+            .prepare_cached("SELECT path, tags FROM comic_info WHERE tags != '' ORDER BY path")
+            .and_then(|mut statement| {
+                statement
+                    .query_map([], |row| {
+                        Ok((path_from_key(row.get(0)?), row.get::<_, String>(1)?))
+                    })?
+                    .filter_map(|row| match row {
+                        Ok((path, tags)) => split_tags(&tags)
+                            .any(|name| name == tag)
+                            .then_some(Ok(path)),
+                        Err(err) => Some(Err(err)),
+                    })
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Remember metadata written to `ComicInfo.xml` of archive at path.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn set_comic_info(&self, path: &Path, info: &ComicInfo) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO comic_info
+                 (path, title, series, number, writer, year, tags)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    key(path),
+                    info.title,
+                    info.series,
+                    info.number,
+                    info.writer,
+                    info.year,
+                    info.tags,
+                ],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get cover chosen for archive at path, [None] if its first page is used.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn cover(&self, path: &Path) -> Result<Option<Cover>, Error> {
+        self.connection
+            .prepare_cached("SELECT page, image FROM covers WHERE path = ?1")
+            .and_then(|mut statement| {
+                statement
+                    .query_row(params![key(path)], |row| {
+                        Ok(match (row.get::<_, Option<usize>>(0)?, row.get(1)?) {
+                            (_, Some(image)) => Some(Cover::Image(path_from_key(image))),
+                            (page, None) => page.map(Cover::Page),
+                        })
+                    })
+                    .optional()
+            })
+            .map(Option::flatten)
+            .map_err(|err| self.error(err))
+    }
+
+    /// Choose cover of archive at path, or use its first page if [None].
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn set_cover(&self, path: &Path, cover: Option<&Cover>) -> Result<(), Error> {
+        match cover {
+            Some(Cover::Page(page)) => self.connection.execute(
+                "INSERT OR REPLACE INTO covers (path, page, image) VALUES (?1, ?2, NULL)",
+                params![key(path), page],
+            ),
+            Some(Cover::Image(image)) => self.connection.execute(
+                "INSERT OR REPLACE INTO covers (path, page, image) VALUES (?1, NULL, ?2)",
+                params![key(path), key(image)],
+            ),
+            None => self
+                .connection
+                .execute("DELETE FROM covers WHERE path = ?1", params![key(path)]),
+        }
+        .map(|_| ())
+        .map_err(|err| self.error(err))
+    }
+
+    /// Get page adjustments remembered for archive at path, if any.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn adjustments(&self, path: &Path) -> Result<Option<Adjustments>, Error> {
+        self.connection
+            .prepare_cached(
+                "SELECT brightness, contrast, gamma, invert FROM adjustments WHERE path = ?1",
+            )
+            .and_then(|mut statement| {
+                statement
+                    .query_row(params![key(path)], |row| {
+                        Ok(Adjustments {
+                            brightness: row.get(0)?,
+                            contrast: row.get(1)?,
+                            gamma: row.get(2)?,
+                            invert: row.get(3)?,
+                        })
+                    })
+                    .optional()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Remember page adjustments of archive at path, or forget them if [None].
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn set_adjustments(
+        &self,
+        path: &Path,
+        adjustments: Option<&Adjustments>,
+    ) -> Result<(), Error> {
+        match adjustments {
+            Some(adjustments) => self.connection.execute(
+                "INSERT OR REPLACE INTO adjustments (path, brightness, contrast, gamma, invert)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    key(path),
+                    adjustments.brightness,
+                    adjustments.contrast,
+                    adjustments.gamma,
+                    adjustments.invert,
+                ],
+            ),
+            None => self.connection.execute(
+                "DELETE FROM adjustments WHERE path = ?1",
+                params![key(path)],
+            ),
+        }
+        .map(|_| ())
+        .map_err(|err| self.error(err))
+    }
+
+    /// Get archives given a book number.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn isbn_paths(&self, isbn: &Isbn) -> Result<Vec<PathBuf>, Error> {
+        self.connection
+            .prepare_cached("SELECT path FROM isbns WHERE isbn = ?1 ORDER BY path")
+            .and_then(|mut statement| {
+                statement
+                    .query_map(params![isbn.as_str()], |row| row.get(0).map(path_from_key))?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Register archives as held by a drive, replacing archives registered for it before.
+    /// Archives registered for another drive are moved to this one.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn register_offline(&self, drive: &str, paths: &[impl AsRef<Path>]) -> Result<(), Error> {
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                transaction.execute("DELETE FROM offline WHERE drive = ?1", params![drive])?;
+                for path in paths {
+                    transaction.execute(
+                        "INSERT OR REPLACE INTO offline (path, drive) VALUES (?1, ?2)",
+                        params![key(path.as_ref()), drive],
+                    )?;
+                }
+                transaction.commit()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get archives registered for drives along with the drive holding them, ordered by
+    /// drive and path.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn offline(&self) -> Result<Vec<(String, PathBuf)>, Error> {
+        self.connection
+            .prepare_cached("SELECT drive, path FROM offline ORDER BY drive, path")
+            .and_then(|mut statement| {
+                statement
+                    .query_map([], |row| Ok((row.get(0)?, row.get(1).map(path_from_key)?)))?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Forget archives registered for a drive, returning the amount forgotten.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn forget_offline(&self, drive: &str) -> Result<usize, Error> {
+        self.connection
+            .execute("DELETE FROM offline WHERE drive = ?1", params![drive])
+            .map_err(|err| self.error(err))
+    }
+
+    /// Add volumes of a series to the wishlist, volumes already on it are kept.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn add_wishes(&self, series: &str, volumes: &[f64]) -> Result<(), Error> {
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                for volume in volumes {
+                    transaction.execute(
+                        "INSERT OR IGNORE INTO wishlist (series, volume, name) VALUES (?1, ?2, ?3)",
+                        params![series::series_key(series), volume, series],
+                    )?;
+                }
+                transaction.commit()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Remove volumes of a series from the wishlist, or every volume of it if [None],
+    /// returning the amount removed.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn remove_wishes(&self, series: &str, volumes: Option<&[f64]>) -> Result<usize, Error> {
+        let key = series::series_key(series);
+        match volumes {
+            Some(volumes) => self
+                .connection
+                .unchecked_transaction()
+                .and_then(|transaction| {
+                    let mut removed = 0;
+                    for volume in volumes {
+                        removed += transaction.execute(
+                            "DELETE FROM wishlist WHERE series = ?1 AND volume = ?2",
+                            params![key, volume],
+                        )?;
+                    }
+                    transaction.commit().map(|()| removed)
+                }),
+            None => self
+                .connection
+                .execute("DELETE FROM wishlist WHERE series = ?1", params![key]),
+        }
+        .map_err(|err| self.error(err))
+    }
+
+    /// Get wished for volumes, ordered by series and volume.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn wishlist(&self) -> Result<Vec<Wish>, Error> {
+        self.connection
+            .prepare_cached("SELECT name, volume FROM wishlist ORDER BY series, volume")
+            .and_then(|mut statement| {
+                statement
+                    .query_map([], |row| {
+                        Ok(Wish {
+                            series: row.get(0)?,
+                            volume: row.get(1)?,
+                        })
+                    })?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Follow a series, alerting when new volumes of it are found.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn follow(&self, series: &str) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT OR IGNORE INTO followed (series, name) VALUES (?1, ?2)",
+                params![series::series_key(series), series],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Stop following a series, dismissing its alerts. Returns if the series was followed.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn unfollow(&self, series: &str) -> Result<bool, Error> {
+        let key = series::series_key(series);
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                transaction.execute("DELETE FROM alerts WHERE series = ?1", params![key])?;
+                let removed =
+                    transaction.execute("DELETE FROM followed WHERE series = ?1", params![key])?;
+                transaction.commit().map(|()| removed > 0)
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get names of followed series, ordered by name.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn followed(&self) -> Result<Vec<String>, Error> {
+        self.connection
+            .prepare_cached("SELECT name FROM followed ORDER BY series")
+            .and_then(|mut statement| statement.query_map([], |row| row.get(0))?.collect())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Record alerts for archives not seen before which are volumes of followed series,
+    /// by their name or the name of the directory they are in. Returns the alerts recorded.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn alert_new_volumes(&self, added: &[PathBuf]) -> Result<Vec<Alert>, Error> {
+        if added.is_empty() {
+            return Ok(Vec::new());
+        }
+        let followed = self
+            .followed()?
+            .into_iter()
+            .map(|name| (series::series_key(&name), name))
+            .collect::<HashMap<_, _>>();
+        let now = now();
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                let mut alerts = Vec::new();
+                for path in added {
+                    let names = [path.file_stem(), path.parent().and_then(Path::file_name)];
+                    let Some((series_key, name)) = names.into_iter().flatten().find_map(|name| {
+                        followed.get_key_value(&series::series_key(&name.to_string_lossy()))
+                    }) else {
+                        continue;
+                    };
+                    transaction.execute(
+                        "INSERT OR IGNORE INTO alerts (path, series, added) VALUES (?1, ?2, ?3)",
+                        params![key(path), series_key, now],
+                    )?;
+                    alerts.push(Alert {
+                        series: name.clone(),
+                        path: path.clone(),
+                    });
+                }
+                transaction.commit().map(|()| alerts)
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get alerts not yet dismissed, ordered by series and path.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn alerts(&self) -> Result<Vec<Alert>, Error> {
+        self.connection
+            .prepare_cached(
+                "SELECT followed.name, alerts.path FROM alerts
+                 JOIN followed ON followed.series = alerts.series
+                 ORDER BY alerts.series, alerts.path",
+            )
+            .and_then(|mut statement| {
+                statement
+                    .query_map([], |row| {
+                        Ok(Alert {
+                            series: row.get(0)?,
+                            path: path_from_key(row.get(1)?),
+                        })
+                    })?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Dismiss alerts of a series, or of every series if [None], returning the amount
+    /// dismissed.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn dismiss_alerts(&self, series: Option<&str>) -> Result<usize, Error> {
+        match series {
+            Some(series) => self.connection.execute(
+                "DELETE FROM alerts WHERE series = ?1",
+                params![series::series_key(series)],
+            ),
+            None => self.connection.execute("DELETE FROM alerts", []),
+        }
+        .map_err(|err| self.error(err))
+    }
+
+    /// Get name of the external application archive at path is preferred to be opened
+    /// with, its own preference before the preference of its kind. [None] if it is opened
+    /// by application itself.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn preferred_application(&self, path: &Path) -> Result<Option<String>, Error> {
+        let item = self
+            .connection
+            .prepare_cached("SELECT application FROM item_applications WHERE path = ?1")
+            .and_then(|mut statement| {
+                statement
+                    .query_row(params![key(path)], |row| row.get::<_, Option<String>>(0))
+                    .optional()
+            })
+            .map_err(|err| self.error(err))?;
+        if let Some(application) = item {
+            return Ok(application);
+        }
+        let Some(kind) = ArchiveKind::from_path(path) else {
+            return Ok(None);
+        };
+        self.connection
+            .prepare_cached("SELECT application FROM kind_applications WHERE kind = ?1")
+            .and_then(|mut statement| {
+                statement
+                    .query_row(params![kind.to_string()], |row| row.get(0))
+                    .optional()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Prefer archive at path to be opened with an external application, or by
+    /// application itself if [None], regardless of the preference of its kind.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn set_item_application(
+        &self,
+        path: &Path,
+        application: Option<&str>,
+    ) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO item_applications (path, application) VALUES (?1, ?2)",
+                params![key(path), application],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Prefer archives of a kind to be opened with an external application, or by
+    /// application itself if [None]. The preference of archive at path is forgotten, such
+    /// that it follows its kind.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn set_kind_application(
+        &self,
+        path: &Path,
+        kind: ArchiveKind,
+        application: Option<&str>,
+    ) -> Result<(), Error> {
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                transaction.execute(
+                    "DELETE FROM item_applications WHERE path = ?1",
+                    params![key(path)],
+                )?;
+                match application {
+                    Some(application) => transaction.execute(
+                        "INSERT OR REPLACE INTO kind_applications (kind, application)
+                         VALUES (?1, ?2)",
+                        params![kind.to_string(), application],
+                    )?,
+                    None => transaction.execute(
+                        "DELETE FROM kind_applications WHERE kind = ?1",
+                        params![kind.to_string()],
+                    )?,
+                };
+                transaction.commit()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Record that an archive was moved to trash.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn record_trashed(&self, path: &Path, trashed: &Path) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO trash (path, trashed, deleted) VALUES (?1, ?2, ?3)",
+                params![key(path), key(trashed), now()],
+            )
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Get archives moved to trash, most recently moved first.
+    ///
+    /// # Errors
+    /// If the database cannot be queried.
+    pub fn trashed(&self) -> Result<Vec<Trashed>, Error> {
+        self.connection
+            .prepare_cached("SELECT path, trashed, deleted FROM trash ORDER BY deleted DESC")
+            .and_then(|mut statement| {
+                statement
+                    .query_map([], |row| {
+                        Ok(Trashed {
+                            path: path_from_key(row.get(0)?),
+                            trashed: path_from_key(row.get(1)?),
+                            deleted: SystemTime::UNIX_EPOCH
+                                + Duration::from_secs(row.get::<_, i64>(2)?.max(0).cast_unsigned()),
+                        })
+                    })?
+                    .collect()
+            })
+            .map_err(|err| self.error(err))
+    }
+
+    /// Forget an archive moved to trash, once restored or no longer in trash.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn forget_trashed(&self, path: &Path) -> Result<(), Error> {
+        self.connection
+            .execute("DELETE FROM trash WHERE path = ?1", params![key(path)])
+            .map(|_| ())
+            .map_err(|err| self.error(err))
+    }
+
+    /// Delete a reading list and its entries.
+    ///
+    /// # Errors
+    /// If the database cannot be written to.
+    pub fn delete_reading_list(&self, name: &str) -> Result<(), Error> {
+        self.connection
+            .unchecked_transaction()
+            .and_then(|transaction| {
+                transaction.execute(
+                    "DELETE FROM reading_list_entries WHERE list = ?1",
+                    params![name],
+                )?;
+                transaction.execute("DELETE FROM reading_lists WHERE name = ?1", params![name])?;
+                transaction.commit()
+            })
+            .map_err(|err| self.error(err))
+    }
+}
@@ -0,0 +1,141 @@
+//! [Chooser] impl, choosing the application an archive is opened with, and whether the
+//! choice is remembered for it or for every archive of its kind.
+
+use ::std::{path::Path, sync::Arc};
+
+use ::iced::{
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message, ViewPath,
+    archive::ArchiveKind,
+    i18n::{tr, tr_args},
+    list_entry_name,
+    open_with::Application,
+};
+
+/// Whether the application chosen for an archive is remembered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Remember {
+    /// Archive is opened with application once.
+    Once,
+    /// Archive is always opened with application.
+    Item,
+    /// Archives of the same kind are always opened with application.
+    Kind,
+}
+
+/// Dialog choosing the application an archive is opened with, shown over a pane.
+#[derive(Debug, Clone)]
+pub struct Chooser {
+    /// Archive being opened.
+    pub path: Arc<Path>,
+    /// External applications archive may be opened with.
+    pub applications: Vec<Application>,
+    /// Index of chosen application, [None] if archive is opened by application itself.
+    pub chosen: Option<usize>,
+    /// Whether choice is remembered.
+    pub remember: Remember,
+}
+
+impl Chooser {
+    /// Create a dialog choosing how an archive is opened, starting from the preferred
+    /// application if any.
+    pub fn new(path: Arc<Path>, applications: Vec<Application>, preferred: Option<&str>) -> Self {
+        let chosen = preferred.and_then(|name| {
+            applications
+                .iter()
+                .position(|application| application.name == name)
+        });
+        Self {
+            path,
+            applications,
+            chosen,
+            remember: Remember::Once,
+        }
+    }
+
+    /// Get name of chosen application, [None] if archive is opened by application itself.
+    pub fn chosen_name(&self) -> Option<&str> {
+        self.applications
+            .get(self.chosen?)
+            .map(|application| application.name.as_str())
+    }
+
+    /// View dialog.
+    pub fn view<'a>(&self, view_path: ViewPath) -> Element<'a, Message> {
+        let kind = ArchiveKind::from_path(&self.path);
+        let remember = |label: String, remember: Remember| {
+            widget::radio(label, remember, Some(self.remember), move |remember| {
+                Message::SetChooserRemember(view_path, remember)
+            })
+            .size(14)
+        };
+        widget::Column::new()
+            .spacing(5)
+            .push(
+                widget::text(tr_args(
+                    "dialog-open-with",
+                    &[("name", &list_entry_name(&self.path))],
+                ))
+                .wrapping(Wrapping::WordOrGlyph),
+            )
+            .push(
+                widget::radio("Arkiv Katalog", None, Some(self.chosen), move |chosen| {
+                    Message::ChooseApplication(view_path, chosen)
+                })
+                .size(14),
+            )
+            .extend(
+                self.applications
+                    .iter()
+                    .enumerate()
+                    .map(|(index, application)| {
+                        widget::radio(
+                            application.name.clone(),
+                            Some(index),
+                            Some(self.chosen),
+                            move |chosen| Message::ChooseApplication(view_path, chosen),
+                        )
+                        .size(14)
+                        .into()
+                    }),
+            )
+            .push(widget::rule::horizontal(1))
+            .push(remember(tr("dialog-remember-once"), Remember::Once))
+            .push(remember(tr("dialog-remember-item"), Remember::Item))
+            .push(kind.map(|kind| {
+                remember(
+                    tr_args("dialog-remember-kind", &[("kind", &kind.to_string())]),
+                    Remember::Kind,
+                )
+            }))
+            .push(
+                widget::Row::new()
+                    .spacing(3)
+                    .push(
+                        widget::button(widget::text(tr("button-open")))
+                            .padding(3)
+                            .on_press(Message::ConfirmChooser(view_path)),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("button-cancel")))
+                            .padding(3)
+                            .style(widget::button::secondary)
+                            .on_press(Message::CancelChooser(view_path)),
+                    ),
+            )
+            .pipe(widget::container)
+            .style(widget::container::bordered_box)
+            .padding(10)
+            .max_width(400)
+            .pipe(widget::opaque)
+            .pipe(widget::container)
+            .center(Fill)
+            .into()
+    }
+}
@@ -1,20 +1,84 @@
 //! [Cli] impl.
 
-use ::std::path::PathBuf;
+use ::std::{
+    collections::BTreeSet,
+    ffi::{OsStr, OsString},
+    path::PathBuf,
+};
 
-use ::clap::Parser;
-use ::color_eyre::eyre::eyre;
+use ::clap::{Parser, ValueEnum};
+#[cfg(feature = "gui")]
 use ::iced::daemon;
-use ::katalog_lib::ThemeValueEnum;
+use ::tap::Pipe;
 
-use crate::{Settings, State};
+#[cfg(feature = "gui")]
+use crate::{
+    Settings, State,
+    catalogue::Catalogue,
+    config::{self, Overrides},
+    instance,
+};
+use crate::{
+    ThemeArg,
+    command::Command,
+    error::{Error, Status},
+    logging::{self, LogLevel},
+};
+
+/// Environment variable used by iced to select renderer backends.
+#[cfg(feature = "gui")]
+const BACKEND_VAR: &str = "ICED_BACKEND";
+
+/// Quote an argument for a posix shell, arguments of only safe characters are left as is.
+fn shell_quote(arg: &OsStr) -> String {
+    let arg = arg.to_string_lossy();
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c))
+    {
+        return arg.into_owned();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// Renderer selection.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Renderer {
+    /// Use gpu rendering, falling back to software rendering if it cannot be initialized.
+    #[default]
+    Auto,
+    /// Always use software rendering.
+    Software,
+}
+
+impl Renderer {
+    /// Get iced backends to try in order, [None] if iced should decide.
+    ///
+    /// # Errors
+    /// If software rendering is requested but not compiled in.
+    #[cfg(feature = "gui")]
+    const fn backends(self) -> Result<Option<&'static str>, Error> {
+        match self {
+            Renderer::Auto if cfg!(all(feature = "wgpu", feature = "tiny-skia")) => {
+                Ok(Some("wgpu,tiny-skia"))
+            }
+            Renderer::Auto => Ok(None),
+            Renderer::Software if cfg!(feature = "tiny-skia") => Ok(Some("tiny-skia")),
+            Renderer::Software => Err(Error::RendererUnavailable {
+                renderer: "software",
+                feature: "tiny-skia",
+            }),
+        }
+    }
+}
 
 /// Application to display a comic archive catalogue.
 #[derive(Debug, Default, Clone, Parser)]
 pub struct Cli {
-    /// Theme to use for application.
+    /// Theme to use for application, overrides config file.
     #[arg(long, short, value_enum)]
-    pub theme: Option<ThemeValueEnum>,
+    pub theme: Option<ThemeArg>,
 
     /// Application name used when querying xdg directories.
     #[arg(long, short, default_value = "arkiv-katalog")]
@@ -24,38 +88,254 @@ pub struct Cli {
     #[arg(long, short, default_value = "default")]
     pub profile: String,
 
-    /// Directory to load.
-    pub directory: Option<PathBuf>,
+    /// Directory to keep config, data, cache and state in, instead of the xdg base
+    /// directories, such that nothing is written to the home directory.
+    #[arg(long, value_name = "DIR")]
+    pub portable: Option<PathBuf>,
+
+    /// Config directory to use instead of the xdg config directory.
+    #[arg(long, value_name = "DIR")]
+    pub config_dir: Option<PathBuf>,
+
+    /// Data directory to use instead of the xdg data directory.
+    #[arg(long, value_name = "DIR")]
+    pub data_dir: Option<PathBuf>,
+
+    /// Cache directory to use instead of the xdg cache directory.
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Start without opening a window, waiting for paths to open from later launches or
+    /// D-Bus activation.
+    #[arg(long)]
+    pub service: bool,
+
+    /// Start a separate instance instead of opening directory in a running instance of
+    /// profile.
+    #[arg(long)]
+    pub new_instance: bool,
+
+    /// Renderer to use, auto falls back to software rendering if the gpu renderer fails.
+    #[arg(long, value_enum, default_value_t)]
+    pub renderer: Renderer,
+
+    /// Least severe level of messages logged to the terminal and log files, overrides
+    /// config file. Filters of `RUST_LOG` take precedence.
+    #[arg(long, global = true, value_enum, value_name = "LEVEL")]
+    pub log_level: Option<LogLevel>,
+
+    /// Only print errors, subcommands communicate results through their exit status.
+    #[arg(long, short, global = true)]
+    pub quiet: bool,
+
+    /// Open the most recently opened directory or archive of profile.
+    #[arg(long, conflicts_with = "paths")]
+    pub recent: bool,
+
+    /// Directories and archives to open, each in a window of its own.
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
+    /// Subcommand to run instead of opening a window.
+    #[command(subcommand)]
+    pub command: Option<Command>,
 }
 
 impl Cli {
+    /// Get xdg base directories of profile.
+    pub fn xdg_dirs(&self) -> ::xdg::BaseDirectories {
+        self.profile_dirs(&self.profile)
+    }
+
+    /// Get xdg base directories of given profile.
+    pub fn profile_dirs(&self, profile: &str) -> ::xdg::BaseDirectories {
+        ::xdg::BaseDirectories::with_profile(&self.app_name, profile)
+            .pipe(|xdg_dirs| self.relocate(xdg_dirs))
+    }
+
+    /// Replace base directories of xdg directories by those given on the command line.
+    /// Directories not given fall back to the portable directory if set.
+    fn relocate(&self, mut xdg_dirs: ::xdg::BaseDirectories) -> ::xdg::BaseDirectories {
+        let portable = |name: &str| self.portable.as_ref().map(|dir| dir.join(name));
+        if let Some(dir) = self.config_dir.clone().or_else(|| portable("config")) {
+            xdg_dirs.config_home = Some(dir);
+            // System wide config files would otherwise still be found.
+            xdg_dirs.config_dirs.clear();
+        }
+        if let Some(dir) = self.data_dir.clone().or_else(|| portable("data")) {
+            xdg_dirs.data_home = Some(dir);
+            xdg_dirs.data_dirs.clear();
+        }
+        if let Some(dir) = self.cache_dir.clone().or_else(|| portable("cache")) {
+            xdg_dirs.cache_home = Some(dir);
+        }
+        if let Some(dir) = portable("state") {
+            xdg_dirs.state_home = Some(dir);
+        }
+        xdg_dirs
+    }
+
+    /// Get arguments launching application with given profile, keeping the directories
+    /// and renderer in use.
+    pub fn profile_args(&self, profile: &str) -> Vec<OsString> {
+        let mut args = vec![
+            OsString::from("--app-name"),
+            OsString::from(&self.app_name),
+            OsString::from("--profile"),
+            OsString::from(profile),
+        ];
+        for (flag, dir) in [
+            ("--portable", &self.portable),
+            ("--config-dir", &self.config_dir),
+            ("--data-dir", &self.data_dir),
+            ("--cache-dir", &self.cache_dir),
+        ] {
+            if let Some(dir) = dir {
+                args.extend([OsString::from(flag), dir.clone().into_os_string()]);
+            }
+        }
+        if let Some(renderer) = self.renderer.to_possible_value() {
+            args.extend([
+                OsString::from("--renderer"),
+                OsString::from(renderer.get_name()),
+            ]);
+        }
+        args
+    }
+
+    /// Get arguments launching application with the profile, directories and renderer in
+    /// use, given theme, and opening given directories and archives.
+    pub fn launch_args(&self, theme: ThemeArg, paths: &[PathBuf]) -> Vec<OsString> {
+        let mut args = self.profile_args(&self.profile);
+        if let Some(theme) = theme.to_possible_value() {
+            args.extend([OsString::from("--theme"), OsString::from(theme.get_name())]);
+        }
+        if !paths.is_empty() {
+            args.push(OsString::from("--"));
+            args.extend(paths.iter().map(|path| path.clone().into_os_string()));
+        }
+        args
+    }
+
+    /// Get command line of [Cli::launch_args], quoted for a posix shell.
+    pub fn launch_command(&self, theme: ThemeArg, paths: &[PathBuf]) -> String {
+        let program = ::std::env::current_exe().map_or_else(
+            |_| OsString::from(env!("CARGO_PKG_NAME")),
+            PathBuf::into_os_string,
+        );
+        ::core::iter::once(program)
+            .chain(self.launch_args(theme, paths))
+            .map(|arg| shell_quote(&arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Find names of profiles of application, the directories of application in the xdg
+    /// config, data, cache and state directories.
+    pub fn profiles(&self) -> BTreeSet<String> {
+        let xdg_dirs = self.relocate(::xdg::BaseDirectories::with_prefix(&self.app_name));
+        [
+            xdg_dirs.get_config_home(),
+            xdg_dirs.get_data_home(),
+            xdg_dirs.get_cache_home(),
+            xdg_dirs.get_state_home(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|dir| ::std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            entry
+                .file_type()
+                .ok()?
+                .is_dir()
+                .then(|| entry.file_name().into_string().ok())?
+        })
+        .collect()
+    }
+
+    /// Initialize logging to the terminal and to rotating log files in the state
+    /// directory of profile.
+    pub fn init_logging(&self) {
+        logging::init(self);
+    }
+
     /// Run application.
     ///
     /// # Errors
-    /// On application errors
-    pub fn run(self) -> ::color_eyre::Result<()> {
-        let xdg_dirs = ::xdg::BaseDirectories::with_profile(&self.app_name, &self.profile);
-        let mut settings = xdg_dirs
-            .find_config_file("config.toml")
-            .map(::std::fs::read_to_string)
-            .transpose()
-            .map_err(|err| eyre!(err))?
-            .map(|content| ::toml::from_str::<Settings>(&content))
-            .transpose()
-            .map_err(|err| eyre!(err))?
-            .unwrap_or_default();
-        if let Some(theme) = self.theme {
-            settings.theme = theme;
+    /// If settings cannot be loaded, the requested renderer is not available, the user
+    /// interface fails or a subcommand fails.
+    pub fn run(mut self) -> Result<Status, Error> {
+        match self.command.take() {
+            Some(command) => command.run(&self),
+            None => self.run_gui(),
+        }
+    }
+
+    /// Run user interface, or open paths in a running instance of profile.
+    ///
+    /// # Errors
+    /// If the requested renderer is not available or the user interface fails.
+    #[cfg(feature = "gui")]
+    fn run_gui(mut self) -> Result<Status, Error> {
+        let xdg_dirs = self.xdg_dirs();
+        if !self.new_instance
+            && let Some(socket) = instance::socket_path(&xdg_dirs)
+            && instance::is_running(&socket)
+        {
+            // A new instance opens the most recent path once its catalogue is loaded.
+            if self.recent {
+                self.paths
+                    .extend(Catalogue::open(&xdg_dirs)?.recent()?.into_iter().next());
+            }
+            if instance::forward_all(&socket, &self.paths).is_ok() {
+                ::log::info!("opened in running instance listening on {socket:?}");
+                return Ok(Status::Ok);
+            }
         }
+        let overrides = Overrides::new(&self);
+        // Other settings are read once the daemon runs, such that reading them does not
+        // delay it starting.
+        let font_size = config::font_size(&xdg_dirs, &overrides)
+            .unwrap_or_else(|| Settings::default().font_size);
+        let iced_settings = ::iced::Settings {
+            default_text_size: f32::from(font_size).into(),
+            ..::iced::Settings::default()
+        };
+
+        // An explicitly set backend variable takes precedence for automatic selection.
+        if let Some(backends) = self.renderer.backends()?
+            && (self.renderer == Renderer::Software || ::std::env::var_os(BACKEND_VAR).is_none())
+        {
+            // SAFETY: No other threads have been spawned yet.
+            unsafe { ::std::env::set_var(BACKEND_VAR, backends) };
+        }
+
         daemon(
-            State::init(self, settings, xdg_dirs),
+            State::init(self, overrides, xdg_dirs),
             State::update,
             State::view,
         )
+        .settings(iced_settings)
         .title(State::title)
         .theme(State::theme)
+        .scale_factor(State::scale_factor)
         .subscription(State::subscription)
         .run()
-        .map_err(|err| eyre!(err))
+        .map_err(|err| Error::Gui(Box::new(err)))?;
+        Ok(Status::Ok)
+    }
+
+    /// Report that the user interface is not available without the `gui` feature.
+    ///
+    /// # Errors
+    /// Always, as there is no user interface to run.
+    #[cfg(not(feature = "gui"))]
+    fn run_gui(self) -> Result<Status, Error> {
+        Err(Error::RendererUnavailable {
+            renderer: "graphical",
+            feature: "gui",
+        })
     }
 }
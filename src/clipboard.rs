@@ -0,0 +1,133 @@
+//! Copying paths, names and covers of items to the clipboard, and reading paths pasted
+//! into panes.
+
+use ::std::{
+    env,
+    io::{self, Cursor, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::Arc,
+};
+
+use ::image::{ImageFormat, RgbaImage};
+
+use crate::{
+    archive::{self, Cover, Page},
+    error::{ArchiveError, Error},
+    freedesktop,
+};
+
+/// What is copied of selected items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyKind {
+    /// Full paths of items, one per line.
+    Paths,
+    /// File names of items, one per line.
+    Names,
+    /// Cover of the first selected archive, as an image.
+    Cover,
+}
+
+/// Get text copied for paths, covers are copied as images and their paths are used.
+pub fn text(kind: CopyKind, paths: &[Arc<Path>]) -> String {
+    paths
+        .iter()
+        .map(|path| match kind {
+            CopyKind::Names => path
+                .file_name()
+                .unwrap_or(path.as_os_str())
+                .to_string_lossy(),
+            CopyKind::Paths | CopyKind::Cover => path.to_string_lossy(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Get paths of pasted text, one path or file uri per line. Lines which are neither
+/// absolute paths nor file uris, such as comments of uri lists, are skipped.
+pub fn paths(text: &str) -> Vec<PathBuf> {
+    text.lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            if line.starts_with("file:") {
+                freedesktop::file_path(line)
+            } else {
+                Some(PathBuf::from(line)).filter(|path| path.is_absolute())
+            }
+        })
+        .collect()
+}
+
+/// Get mime type and content of a page as an image other applications may paste. Pages
+/// which had to be decoded are encoded as jpeg.
+///
+/// # Errors
+/// If the page cannot be decoded or encoded.
+fn image_data(page: Page) -> Result<(&'static str, Vec<u8>), ArchiveError> {
+    match page.decoded()? {
+        Page::Encoded(bytes) => {
+            let format =
+                ::image::guess_format(&bytes).map_err(|err| ArchiveError::Decode(Box::new(err)))?;
+            Ok((format.to_mime_type(), bytes.to_vec()))
+        }
+        Page::Rgba {
+            width,
+            height,
+            pixels,
+        } => {
+            let image = RgbaImage::from_raw(width, height, pixels.to_vec())
+                .ok_or(ArchiveError::PixelSize { width, height })?;
+            let mut content = Vec::new();
+            ::image::DynamicImage::from(image)
+                .into_rgb8()
+                .write_to(&mut Cursor::new(&mut content), ImageFormat::Jpeg)
+                .map_err(|err| ArchiveError::Decode(Box::new(err)))?;
+            Ok((ImageFormat::Jpeg.to_mime_type(), content))
+        }
+    }
+}
+
+/// Write content of a mime type to the clipboard with an external program, `wl-copy` on
+/// Wayland and `xclip` otherwise, which keeps serving it after returning.
+///
+/// # Errors
+/// If the program cannot be run or fails.
+fn write_clipboard(mime: &str, content: &[u8]) -> Result<(), Error> {
+    let (program, args): (_, &[&str]) = if env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wl-copy", &["--type", mime])
+    } else {
+        ("xclip", &["-selection", "clipboard", "-t", mime, "-i"])
+    };
+    let error = |source| Error::Clipboard { program, source };
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(error)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(content).map_err(error)?;
+    }
+    let status = child.wait().map_err(error)?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(error(io::Error::other(format!("{program} {status}"))))
+    }
+}
+
+/// Copy cover of archive at path to the clipboard as an image, as the clipboard of the
+/// user interface only holds text.
+///
+/// # Errors
+/// If the cover cannot be read or the clipboard cannot be written.
+pub fn copy_cover(path: &Path, cover: &Cover) -> Result<(), Error> {
+    let (mime, content) = archive::cover_page(path, cover)
+        .and_then(image_data)
+        .map_err(|source| Error::Archive {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    write_clipboard(mime, &content)
+}
@@ -0,0 +1,1729 @@
+//! [Command] impl, subcommands run without opening any windows.
+
+use ::core::cell::LazyCell;
+use ::std::{
+    fs::Metadata,
+    io::{StdoutLock, Write},
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use ::clap::{CommandFactory, Subcommand, builder::PossibleValuesParser};
+use ::clap_complete::Shell;
+use ::serde::Serialize;
+
+use crate::{
+    CardSize, Cli, Settings,
+    archive::{self, Archive, ArchiveKind},
+    backup, bench,
+    catalogue::{Catalogue, ItemState},
+    config::{self, Format, Overrides},
+    doctor::{self, Check, Severity},
+    error::{BackupError, ConfigError, Error, Status},
+    freedesktop::{self, ThumbnailDirs},
+    ignore::IgnoreRules,
+    import::{self, Source},
+    inbox::{self, Conflicts, Disposition, Resolution},
+    instance,
+    isbn::Isbn,
+    job::{Job, Step},
+    manifest::{self, MANIFEST_FILE},
+    organize::{self, Journal, Placement, Template},
+    series, temp,
+    worker::{CancelToken, Throttle},
+};
+
+/// Subcommands of application.
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// List supported archives in a directory and its subdirectories.
+    Scan {
+        /// Directory to scan.
+        directory: PathBuf,
+    },
+    /// Read every page of archives, reporting archives that cannot be read.
+    Verify {
+        /// Archive to verify, or directory to verify all archives in.
+        path: PathBuf,
+    },
+    /// Print catalogue state of archives in a directory and its subdirectories, read
+    /// state, last read page, star and label, as a json object per line.
+    Export {
+        /// Directory to export archives of.
+        directory: PathBuf,
+    },
+    /// Repack rar and 7z archives into zip archives, keeping their read state. Archives
+    /// are extracted with `bsdtar`, `7z` or `unrar`, whichever is installed.
+    Convert {
+        /// Archives to convert, or directories to convert all rar and 7z archives in.
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Recompress page images as jpeg images of given quality, from 1 to 100.
+        #[arg(long, value_parser = ::clap::value_parser!(u8).range(1..=100))]
+        quality: Option<u8>,
+        /// Keep original archives instead of removing them.
+        #[arg(long)]
+        keep: bool,
+    },
+    /// Extract every file of an archive into a directory named after it. Rar and 7z
+    /// archives are extracted with `bsdtar`, `7z` or `unrar`, whichever is installed.
+    Extract {
+        /// Archive to extract.
+        archive: PathBuf,
+        /// Directory the directory named after the archive is created in, the directory of
+        /// the archive if not given.
+        dest: Option<PathBuf>,
+        /// Resolution of extraction onto an existing directory, asked for if not given.
+        /// Overwriting extracts into the existing directory, replacing files.
+        #[arg(long, value_enum)]
+        on_conflict: Option<Resolution>,
+    },
+    /// Inspect and manage configuration.
+    Config {
+        /// Configuration subcommand.
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Check config, catalogue, cache directory, optional dependencies and recently
+    /// opened libraries of profile, printing how found problems may be fixed.
+    Doctor,
+    /// Measure how fast archives of a library are found, covers extracted, pages read and
+    /// decoded and the catalogue queried, printing a json report.
+    Bench {
+        /// Library to measure.
+        directory: PathBuf,
+        /// Amount of archives, spread across the library, covers and pages are measured of.
+        #[arg(long, default_value_t = 100)]
+        samples: usize,
+    },
+    /// Print shell completions, profiles existing when generated are completed by
+    /// `--profile`.
+    Completions {
+        /// Shell to generate completions for.
+        shell: Shell,
+    },
+    /// Manage reading lists spanning several series.
+    List {
+        /// Reading list subcommand.
+        #[command(subcommand)]
+        command: ListCommand,
+    },
+    /// Sort new archives of an inbox, such as a downloads directory, into the series
+    /// directories of a library, reporting archives already in the library.
+    Inbox {
+        /// Directory new archives are found in.
+        inbox: PathBuf,
+        /// Library containing a directory per series.
+        #[arg(long)]
+        library: PathBuf,
+        /// Move new archives into their series directories instead of only reporting
+        /// where they belong.
+        #[arg(long)]
+        apply: bool,
+        /// Resolution of moves onto existing files, asked for each file if not given.
+        #[arg(long, value_enum)]
+        on_conflict: Option<Resolution>,
+    },
+    /// Organize archives of a library into directories given by a path template,
+    /// moving catalogue state along with archives.
+    Organize {
+        /// Library to organize.
+        #[arg(required_unless_present = "undo")]
+        root: Option<PathBuf>,
+        /// Path of archives relative to library, with the fields {series}, {volume},
+        /// {title}, {author} and {name}. The extension of archives is appended.
+        #[arg(long, default_value = organize::DEFAULT_TEMPLATE)]
+        template: String,
+        /// Move archives instead of only reporting where they belong.
+        #[arg(long)]
+        apply: bool,
+        /// Resolution of moves onto existing files, asked for each file if not given.
+        #[arg(long, value_enum)]
+        on_conflict: Option<Resolution>,
+        /// Move archives of the most recent organization back.
+        #[arg(long, conflicts_with_all = ["root", "apply"])]
+        undo: bool,
+    },
+    /// Manage profiles, each with separate config, data and cache.
+    Profile {
+        /// Profile subcommand.
+        #[command(subcommand)]
+        command: ProfileCommand,
+    },
+    /// Export or verify checksum manifests of libraries, to detect damaged archives.
+    Manifest {
+        /// Manifest subcommand.
+        #[command(subcommand)]
+        command: ManifestCommand,
+    },
+    /// Give archives book numbers, such that physical and digital copies may be
+    /// cross-referenced by scanning the barcode of a book.
+    Isbn {
+        /// Book number subcommand.
+        #[command(subcommand)]
+        command: IsbnCommand,
+    },
+    /// Track archives of detached drives, such that searching tells which drive holds an
+    /// archive.
+    Offline {
+        /// Offline subcommand.
+        #[command(subcommand)]
+        command: OfflineCommand,
+    },
+    /// Track volumes of series not yet owned, and export them as a shopping list.
+    Wishlist {
+        /// Wishlist subcommand.
+        #[command(subcommand)]
+        command: WishlistCommand,
+    },
+    /// Follow series, alerting when scans find new volumes of them.
+    Follow {
+        /// Follow subcommand.
+        #[command(subcommand)]
+        command: FollowCommand,
+    },
+    /// Run a batch job file, a toml file of steps such as scanning directories,
+    /// stripping duplicate pages and writing thumbnails.
+    RunJob {
+        /// Job file to run.
+        job: PathBuf,
+    },
+    /// Import series info, read progress and tags from the database of a Komga server or
+    /// Calibre library. Books are matched against archives of a library by path, and by
+    /// size when renamed. Info already in the catalogue is kept and progress is never
+    /// undone.
+    Import {
+        /// Application database is exported from.
+        #[arg(long, value_enum)]
+        from: Source,
+        /// Database to import, `database.sqlite` of Komga or `metadata.db` of Calibre.
+        database: PathBuf,
+        /// Library books are matched against, the current directory by default.
+        #[arg(long)]
+        root: Option<PathBuf>,
+        /// Email of Komga user whose progress is imported, the most recent progress of
+        /// any user is imported if not given.
+        #[arg(long)]
+        user: Option<String>,
+    },
+    /// Back up settings and catalogue of profile, with read state, progress, tags,
+    /// collections and reading lists, into a single archive which may be restored on
+    /// another machine.
+    Backup {
+        /// Path of backup, a file named after the profile and the current time in the
+        /// `backups` directory of the data directory of profile by default.
+        archive: Option<PathBuf>,
+    },
+    /// Replace settings and catalogue of profile with those of a backup. The previous
+    /// catalogue and config file are kept next to them with a `.bak` extension.
+    Restore {
+        /// Backup to restore.
+        archive: PathBuf,
+    },
+}
+
+/// Subcommands of checksum manifests.
+#[derive(Debug, Clone, Subcommand)]
+pub enum ManifestCommand {
+    /// Write sha256 checksums of archives of a library to a manifest in the format of
+    /// `sha256sum`, with paths relative to the library.
+    Export {
+        /// Library to write checksums of.
+        root: PathBuf,
+        /// Path of manifest, `SHA256SUMS` in the library by default.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Compare archives of a library against a manifest, reporting archives that changed,
+    /// are missing or are not listed.
+    Verify {
+        /// Manifest to verify against.
+        manifest: PathBuf,
+        /// Library paths of manifest are relative to, the directory of the manifest by
+        /// default.
+        #[arg(long)]
+        root: Option<PathBuf>,
+    },
+}
+
+/// Subcommands managing book numbers of archives.
+#[derive(Debug, Clone, Subcommand)]
+pub enum IsbnCommand {
+    /// Print book number of an archive, given to it or read from its metadata.
+    Show {
+        /// Archive to print book number of.
+        path: PathBuf,
+    },
+    /// Give an archive a book number, an ISBN-10, ISBN-13 or EAN-13 barcode.
+    Set {
+        /// Archive to give book number.
+        path: PathBuf,
+        /// Book number.
+        isbn: String,
+    },
+    /// Remove book number given to an archive.
+    Clear {
+        /// Archive to remove book number of.
+        path: PathBuf,
+    },
+    /// Print archives given a book number.
+    Find {
+        /// Book number, an ISBN-10, ISBN-13 or EAN-13 barcode.
+        isbn: String,
+    },
+}
+
+/// Subcommands tracking archives of detached drives.
+#[derive(Debug, Clone, Subcommand)]
+pub enum OfflineCommand {
+    /// Register archives of a directory on a mounted drive under a label, replacing
+    /// archives registered for the label before.
+    Register {
+        /// Directory of drive to register archives of.
+        directory: PathBuf,
+        /// Label of drive, such as the name written on it.
+        #[arg(long)]
+        drive: String,
+    },
+    /// Print drives holding archives whose name contains the query, ignoring case.
+    Find {
+        /// Part of the name of archives.
+        query: String,
+    },
+    /// Print labels of drives along with the amount of archives registered for them.
+    Drives,
+    /// Forget archives registered for a drive.
+    Forget {
+        /// Label of drive.
+        drive: String,
+    },
+}
+
+/// Subcommands managing the wishlist.
+#[derive(Debug, Clone, Subcommand)]
+pub enum WishlistCommand {
+    /// Add volumes of a series to the wishlist.
+    Add {
+        /// Name of series.
+        series: String,
+        /// Volume numbers.
+        #[arg(required = true)]
+        volumes: Vec<f64>,
+    },
+    /// Remove volumes of a series from the wishlist, every volume if none are given.
+    Remove {
+        /// Name of series.
+        series: String,
+        /// Volume numbers.
+        volumes: Vec<f64>,
+    },
+    /// Add volumes missing from a series directory to the wishlist, whole volumes up to
+    /// the last one in the directory.
+    Gaps {
+        /// Directory of series.
+        directory: PathBuf,
+    },
+    /// Print the wishlist as a shopping list, one line per series.
+    Export {
+        /// File to write shopping list to instead of printing it.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Subcommands managing followed series.
+#[derive(Debug, Clone, Subcommand)]
+pub enum FollowCommand {
+    /// Follow a series.
+    Add {
+        /// Name of series.
+        series: String,
+    },
+    /// Stop following a series, dismissing its alerts.
+    Remove {
+        /// Name of series.
+        series: String,
+    },
+    /// Print names of followed series.
+    List,
+    /// Print new volumes of followed series found when scanning.
+    Alerts {
+        /// Dismiss printed alerts.
+        #[arg(long)]
+        dismiss: bool,
+    },
+}
+
+/// Subcommands managing profiles.
+#[derive(Debug, Clone, Subcommand)]
+pub enum ProfileCommand {
+    /// Print names of existing profiles.
+    List,
+    /// Create a profile with default settings.
+    New {
+        /// Name of profile.
+        name: String,
+    },
+    /// Remove a profile along with its config, data and cache.
+    Remove {
+        /// Name of profile.
+        name: String,
+    },
+}
+
+/// Subcommands managing reading lists.
+#[derive(Debug, Clone, Subcommand)]
+pub enum ListCommand {
+    /// Print entries of a reading list, or the names of all reading lists if no name is
+    /// given.
+    Show {
+        /// Name of reading list.
+        name: Option<String>,
+    },
+    /// Import a reading list from a file, replacing any list of the same name.
+    ///
+    /// The file is either a json array of strings, or text with one entry per line where
+    /// empty lines and lines starting with `#` are skipped. Entries are paths of
+    /// archives, or titles matched against the names of archives in the root directory.
+    Import {
+        /// Name of reading list.
+        name: String,
+        /// File to import.
+        file: PathBuf,
+        /// Directory titles are searched for in, the current directory by default.
+        #[arg(long)]
+        root: Option<PathBuf>,
+    },
+    /// Delete a reading list.
+    Delete {
+        /// Name of reading list.
+        name: String,
+    },
+}
+
+/// Subcommands inspecting and managing configuration.
+#[derive(Debug, Clone, Subcommand)]
+pub enum ConfigCommand {
+    /// Print path of config file, which may not exist yet.
+    Path,
+    /// Print effective settings, the config file with command line and environment
+    /// overrides applied.
+    Show {
+        /// Format to print settings in.
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Open config file in `$VISUAL` or `$EDITOR`, creating it with default settings if
+    /// it does not exist.
+    Edit,
+    /// Replace config file with default settings, keeping the previous config file as a
+    /// backup.
+    Reset,
+}
+
+/// Editor used if neither `$VISUAL` nor `$EDITOR` is set.
+const DEFAULT_EDITOR: &str = "vi";
+
+/// Catalogue state of an archive, as printed by the export subcommand.
+#[derive(Debug, Serialize)]
+struct Exported<'a> {
+    /// Path of archive.
+    path: &'a Path,
+    /// Catalogue state of archive.
+    #[serde(flatten)]
+    state: ItemState,
+}
+
+/// Standard output of a subcommand, silenced in quiet mode.
+#[derive(Debug)]
+struct Output {
+    /// Locked standard output.
+    stdout: StdoutLock<'static>,
+    /// Nothing should be written.
+    quiet: bool,
+}
+
+impl Output {
+    /// Write a line unless quiet.
+    ///
+    /// # Errors
+    /// If standard output cannot be written to.
+    fn line(&mut self, line: impl ::core::fmt::Display) -> Result<(), Error> {
+        if self.quiet {
+            return Ok(());
+        }
+        writeln!(self.stdout, "{line}").map_err(Error::Output)
+    }
+}
+
+impl Command {
+    /// Run subcommand.
+    ///
+    /// # Errors
+    /// If the given path or settings cannot be read or output cannot be written.
+    pub fn run(self, cli: &Cli) -> Result<Status, Error> {
+        let mut output = Output {
+            stdout: ::std::io::stdout().lock(),
+            quiet: cli.quiet,
+        };
+        // Settings are only read by commands scanning directories.
+        let ignore = LazyCell::new(|| IgnoreRules::of_profile(cli));
+        match self {
+            Command::Scan { directory } => {
+                let (archives, status) = find_archives(&directory, &ignore)?;
+                for path in archives {
+                    output.line(path.display())?;
+                }
+                Ok(status)
+            }
+            Command::Export { directory } => {
+                let directory = archive_path(directory)?;
+                let (archives, status) = find_archives(&directory, &ignore)?;
+                let catalogue = Catalogue::open(&cli.xdg_dirs())?;
+                for path in &archives {
+                    let exported = Exported {
+                        path,
+                        state: catalogue.get(path)?,
+                    };
+                    output.line(
+                        ::serde_json::to_string(&exported)
+                            .map_err(|err| Error::Output(err.into()))?,
+                    )?;
+                }
+                Ok(status)
+            }
+            Command::Verify { path } => {
+                let is_dir = match path.metadata() {
+                    Ok(metadata) => metadata.is_dir(),
+                    Err(source) => return Err(Error::Scan { path, source }),
+                };
+                let (archives, mut status) = if is_dir {
+                    find_archives(&path, &ignore)?
+                } else {
+                    (vec![path], Status::Ok)
+                };
+                let mut failed = 0usize;
+                for path in &archives {
+                    match verify(path) {
+                        Ok(page_count) => {
+                            output
+                                .line(format_args!("ok {} ({page_count} pages)", path.display()))?;
+                        }
+                        Err(source) => {
+                            Error::Archive {
+                                path: path.clone(),
+                                source,
+                            }
+                            .log();
+                            failed += 1;
+                            status = Status::ErrorsFound;
+                        }
+                    }
+                }
+                output.line(format_args!(
+                    "{} archives verified, {failed} failed",
+                    archives.len()
+                ))?;
+                Ok(status)
+            }
+            Command::Convert {
+                paths,
+                quality,
+                keep,
+            } => {
+                let xdg_dirs = cli.xdg_dirs();
+                let catalogue = Catalogue::open(&xdg_dirs)?;
+                let temp_dir = temp::dir(&xdg_dirs).map_err(|source| Error::Config {
+                    path: xdg_dirs.get_cache_home().unwrap_or_default(),
+                    source: ConfigError::Write(source),
+                })?;
+                temp::clean(&temp_dir);
+
+                let mut status = Status::Ok;
+                let mut archives = Vec::new();
+                for path in paths {
+                    let path = archive_path(path)?;
+                    if path.is_dir() {
+                        let (found, scan_status) = find_files(
+                            &path,
+                            archive::is_convertible,
+                            &ignore,
+                            &Throttle::default(),
+                        )?;
+                        status = status.max(scan_status);
+                        archives.extend(found);
+                    } else {
+                        archives.push(path);
+                    }
+                }
+
+                let count = archives.len();
+                let mut failed = 0usize;
+                for (index, path) in archives.iter().enumerate() {
+                    let target = match archive::convert_to_cbz(path, &temp_dir, quality) {
+                        Ok(target) => target,
+                        Err(source) => {
+                            Error::Archive {
+                                path: path.clone(),
+                                source,
+                            }
+                            .log();
+                            failed += 1;
+                            status = Status::ErrorsFound;
+                            continue;
+                        }
+                    };
+                    output.line(format_args!(
+                        "[{}/{count}] converted {}",
+                        index + 1,
+                        path.display()
+                    ))?;
+                    if keep {
+                        continue;
+                    }
+                    catalogue.rename(path, &target)?;
+                    if let Err(source) = ::std::fs::remove_file(path) {
+                        Error::Remove {
+                            path: path.clone(),
+                            source,
+                        }
+                        .log();
+                        status = Status::ErrorsFound;
+                    }
+                }
+                output.line(format_args!(
+                    "{} archives converted, {failed} failed",
+                    count - failed
+                ))?;
+                Ok(status)
+            }
+            Command::Extract {
+                archive,
+                dest,
+                on_conflict,
+            } => {
+                let dest = match dest {
+                    Some(dest) => dest,
+                    None => archive
+                        .parent()
+                        .map_or_else(PathBuf::new, Path::to_path_buf),
+                };
+                let target = dest.join(archive.file_stem().unwrap_or_default());
+                let Some((target, _)) = Conflicts::new(on_conflict).resolve(&archive, &target)?
+                else {
+                    output.line(format_args!("skipped {}", archive.display()))?;
+                    return Ok(Status::Ok);
+                };
+                archive::extract_into(&archive, &target, &CancelToken::default(), |_, _| {})
+                    .map_err(|source| Error::Archive {
+                        path: archive.clone(),
+                        source,
+                    })?;
+                output.line(format_args!(
+                    "extracted {} to {}",
+                    archive.display(),
+                    target.display()
+                ))?;
+                Ok(Status::Ok)
+            }
+            Command::Bench { directory, samples } => {
+                let directory = archive_path(directory)?;
+                let started = Instant::now();
+                let (archives, status) = find_archives(&directory, &ignore)?;
+                let scan = bench::scan(archives.len(), started.elapsed());
+                let sampled = bench::sample(&archives, samples);
+                let catalogue = Catalogue::open(&cli.xdg_dirs())?;
+                let covers = bench::covers(&sampled, CardSize::default().thumbnail_size());
+                let (read, decode) = bench::pages(&sampled);
+                let report = bench::Report {
+                    sampled: sampled.len(),
+                    queries: bench::queries(&catalogue, &sampled)?,
+                    directory,
+                    scan,
+                    covers,
+                    read,
+                    decode,
+                };
+                output.line(
+                    ::serde_json::to_string_pretty(&report)
+                        .map_err(|err| Error::Output(err.into()))?,
+                )?;
+                Ok(status.max(report.status()))
+            }
+            Command::Doctor => {
+                let problems = doctor::diagnose(&cli.xdg_dirs());
+                for check in Check::ALL {
+                    let mut found = problems.iter().filter(|problem| problem.check == check);
+                    let Some(first) = found.next() else {
+                        output.line(format_args!("ok {check}"))?;
+                        continue;
+                    };
+                    for problem in ::core::iter::once(first).chain(found) {
+                        output.line(format_args!(
+                            "{} {check}: {}\n  fix: {}",
+                            problem.severity, problem.message, problem.fix
+                        ))?;
+                    }
+                }
+                Ok(
+                    if problems
+                        .iter()
+                        .any(|problem| problem.severity == Severity::Error)
+                    {
+                        Status::ErrorsFound
+                    } else {
+                        Status::Ok
+                    },
+                )
+            }
+            Command::Completions { shell } => {
+                let mut command = Cli::command();
+                let profiles = cli.profiles();
+                if !profiles.is_empty() {
+                    command = command.mut_arg("profile", |arg| {
+                        arg.value_parser(PossibleValuesParser::new(profiles))
+                    });
+                }
+                let name = command.get_name().to_owned();
+                let mut script = Vec::new();
+                ::clap_complete::generate(shell, &mut command, name, &mut script);
+                output.line(String::from_utf8_lossy(&script).trim_end())?;
+                Ok(Status::Ok)
+            }
+            Command::List {
+                command: ListCommand::Show { name },
+            } => {
+                let catalogue = Catalogue::open(&cli.xdg_dirs())?;
+                match name {
+                    Some(name) => {
+                        for path in catalogue.reading_list(&name)? {
+                            output.line(path.display())?;
+                        }
+                    }
+                    None => {
+                        for name in catalogue.reading_lists()? {
+                            output.line(name)?;
+                        }
+                    }
+                }
+                Ok(Status::Ok)
+            }
+            Command::List {
+                command: ListCommand::Import { name, file, root },
+            } => {
+                let catalogue = Catalogue::open(&cli.xdg_dirs())?;
+                let entries = read_list(&file)?;
+                let (archives, mut status) =
+                    find_archives(root.as_deref().unwrap_or(Path::new(".")), &ignore)?;
+                let mut paths = Vec::with_capacity(entries.len());
+                for entry in &entries {
+                    match resolve_entry(entry, &archives) {
+                        Some(path) => paths.push(path),
+                        None => {
+                            ::log::error!("no archive found for {entry:?}");
+                            status = Status::ErrorsFound;
+                        }
+                    }
+                }
+                catalogue.set_reading_list(&name, &paths)?;
+                output.line(format_args!(
+                    "imported {} of {} entries into {name:?}",
+                    paths.len(),
+                    entries.len()
+                ))?;
+                Ok(status)
+            }
+            Command::List {
+                command: ListCommand::Delete { name },
+            } => {
+                Catalogue::open(&cli.xdg_dirs())?.delete_reading_list(&name)?;
+                Ok(Status::Ok)
+            }
+            Command::Inbox {
+                inbox,
+                library,
+                apply,
+                on_conflict,
+            } => {
+                let mut conflicts = Conflicts::new(on_conflict);
+                let (new, mut status) = find_archives(&inbox, &ignore)?;
+                let (mut archives, library_status) = find_archives(&library, &ignore)?;
+                // An inbox within the library is not part of it.
+                archives.retain(|path| !path.starts_with(&inbox));
+                status = status.max(library_status);
+                for (path, disposition) in inbox::sort(&new, &archives)? {
+                    match disposition {
+                        Disposition::New { target } if apply => {
+                            let Some((target, overwrite)) = conflicts.resolve(&path, &target)?
+                            else {
+                                output.line(format_args!("skipped {}", path.display()))?;
+                                continue;
+                            };
+                            match inbox::move_file(&path, &target, overwrite) {
+                                Ok(()) => output.line(format_args!(
+                                    "moved {} -> {}",
+                                    path.display(),
+                                    target.display()
+                                ))?,
+                                Err(err) => {
+                                    err.log();
+                                    status = Status::ErrorsFound;
+                                }
+                            }
+                        }
+                        Disposition::New { target } => output.line(format_args!(
+                            "new {} -> {}",
+                            path.display(),
+                            target.display()
+                        ))?,
+                        Disposition::Duplicate { of } => output.line(format_args!(
+                            "duplicate {} of {}",
+                            path.display(),
+                            of.display()
+                        ))?,
+                        Disposition::Unsorted => {
+                            output.line(format_args!("unsorted {}", path.display()))?;
+                        }
+                    }
+                }
+                Ok(status)
+            }
+            Command::Organize { undo: true, .. } => {
+                let xdg_dirs = cli.xdg_dirs();
+                let catalogue = Catalogue::open(&xdg_dirs)?;
+                let failed =
+                    Journal::load(&xdg_dirs)?.undo(&xdg_dirs, &catalogue, |from, to| {
+                        output.line(format_args!("moved {} -> {}", from.display(), to.display()))
+                    })?;
+                Ok(if failed == 0 {
+                    Status::Ok
+                } else {
+                    Status::ErrorsFound
+                })
+            }
+            Command::Organize {
+                root,
+                template,
+                apply,
+                on_conflict,
+                ..
+            } => {
+                let template = Template::parse(&template)?;
+                let root = archive_path(root.unwrap_or_else(|| PathBuf::from(".")))?;
+                let (archives, mut status) = find_archives(&root, &ignore)?;
+                let mut moves = Vec::new();
+                for (path, placement) in organize::plan(&root, &archives, &template) {
+                    match placement {
+                        Placement::Move { target } if apply => moves.push((path, target)),
+                        Placement::Move { target } => output.line(format_args!(
+                            "move {} -> {}",
+                            path.display(),
+                            target.display()
+                        ))?,
+                        Placement::InPlace => {}
+                        Placement::Missing { field } => output
+                            .line(format_args!("skipped {}, no {{{field}}}", path.display()))?,
+                        Placement::Outside { target } => {
+                            output.line(format_args!(
+                                "skipped {}, {} is outside of library",
+                                path.display(),
+                                target.display()
+                            ))?;
+                            status = Status::ErrorsFound;
+                        }
+                        Placement::Conflict { target } => {
+                            output.line(format_args!(
+                                "conflict {} -> {}",
+                                path.display(),
+                                target.display()
+                            ))?;
+                            if apply {
+                                moves.push((path, target));
+                            }
+                        }
+                    }
+                }
+                if !moves.is_empty() {
+                    let xdg_dirs = cli.xdg_dirs();
+                    let catalogue = Catalogue::open(&xdg_dirs)?;
+                    let mut conflicts = Conflicts::new(on_conflict);
+                    let failed = Journal::apply(
+                        &xdg_dirs,
+                        &catalogue,
+                        moves,
+                        &mut conflicts,
+                        |from, to| {
+                            output.line(format_args!(
+                                "moved {} -> {}",
+                                from.display(),
+                                to.display()
+                            ))
+                        },
+                    )?;
+                    if failed > 0 {
+                        status = Status::ErrorsFound;
+                    }
+                }
+                Ok(status)
+            }
+            Command::Profile {
+                command: ProfileCommand::List,
+            } => {
+                for profile in cli.profiles() {
+                    output.line(profile)?;
+                }
+                Ok(Status::Ok)
+            }
+            Command::Profile {
+                command: ProfileCommand::New { name },
+            } => {
+                if !is_profile_name(&name) {
+                    ::log::error!("invalid profile name {name:?}");
+                    return Ok(Status::Usage);
+                }
+                if cli.profiles().contains(&name) {
+                    ::log::error!("profile {name:?} already exists");
+                    return Ok(Status::ErrorsFound);
+                }
+                Settings::default().save(&cli.profile_dirs(&name))?;
+                Ok(Status::Ok)
+            }
+            Command::Profile {
+                command: ProfileCommand::Remove { name },
+            } => {
+                if !is_profile_name(&name) {
+                    ::log::error!("invalid profile name {name:?}");
+                    return Ok(Status::Usage);
+                }
+                if !cli.profiles().contains(&name) {
+                    ::log::error!("profile {name:?} does not exist");
+                    return Ok(Status::ErrorsFound);
+                }
+                let xdg_dirs = cli.profile_dirs(&name);
+                for dir in [
+                    xdg_dirs.get_config_home(),
+                    xdg_dirs.get_data_home(),
+                    xdg_dirs.get_cache_home(),
+                    xdg_dirs.get_state_home(),
+                ]
+                .into_iter()
+                .flatten()
+                .filter(|dir| dir.is_dir())
+                {
+                    ::std::fs::remove_dir_all(&dir)
+                        .map_err(|source| Error::RemoveProfile { path: dir, source })?;
+                }
+                Ok(Status::Ok)
+            }
+            Command::Manifest {
+                command: ManifestCommand::Export { root, output: path },
+            } => {
+                let (archives, mut status) = find_archives(&root, &ignore)?;
+                let mut lines = Vec::with_capacity(archives.len());
+                for (index, path) in archives.iter().enumerate() {
+                    let Some(relative) = path.strip_prefix(&root).ok().and_then(Path::to_str)
+                    else {
+                        ::log::error!("{path:?} cannot be listed in a manifest");
+                        status = Status::ErrorsFound;
+                        continue;
+                    };
+                    match manifest::checksum(path) {
+                        Ok(checksum) => {
+                            output.line(format_args!(
+                                "[{}/{}] {relative}",
+                                index + 1,
+                                archives.len()
+                            ))?;
+                            lines.push(manifest::line(&checksum, relative));
+                        }
+                        Err(source) => {
+                            Error::Scan {
+                                path: path.clone(),
+                                source,
+                            }
+                            .log();
+                            status = Status::ErrorsFound;
+                        }
+                    }
+                }
+                let path = path.unwrap_or_else(|| root.join(MANIFEST_FILE));
+                let mut content = lines.join("\n");
+                content.push('\n');
+                temp::write(&path, content.as_bytes()).map_err(|source| Error::Config {
+                    path: path.clone(),
+                    source: ConfigError::Write(source),
+                })?;
+                output.line(format_args!(
+                    "wrote {} checksums to {}",
+                    lines.len(),
+                    path.display()
+                ))?;
+                Ok(status)
+            }
+            Command::Manifest {
+                command: ManifestCommand::Verify { manifest, root },
+            } => verify_manifest(&manifest, root, &ignore, &mut output),
+            Command::Isbn {
+                command: IsbnCommand::Show { path },
+            } => {
+                let path = archive_path(path)?;
+                let isbn = match Catalogue::open(&cli.xdg_dirs())?.isbn(&path)? {
+                    Some(isbn) => Some(isbn),
+                    None => {
+                        Archive::open(&path)
+                            .map_err(|source| Error::Archive {
+                                path: path.clone(),
+                                source,
+                            })?
+                            .metadata()
+                            .isbn
+                    }
+                };
+                match isbn {
+                    Some(isbn) => {
+                        output.line(isbn)?;
+                        Ok(Status::Ok)
+                    }
+                    None => {
+                        ::log::error!("{path:?} has no book number");
+                        Ok(Status::ErrorsFound)
+                    }
+                }
+            }
+            Command::Isbn {
+                command: IsbnCommand::Set { path, isbn },
+            } => {
+                let Some(isbn) = Isbn::parse(&isbn) else {
+                    ::log::error!("{isbn:?} is not a valid book number");
+                    return Ok(Status::Usage);
+                };
+                Catalogue::open(&cli.xdg_dirs())?.set_isbn(&archive_path(path)?, Some(&isbn))?;
+                Ok(Status::Ok)
+            }
+            Command::Isbn {
+                command: IsbnCommand::Clear { path },
+            } => {
+                Catalogue::open(&cli.xdg_dirs())?.set_isbn(&archive_path(path)?, None)?;
+                Ok(Status::Ok)
+            }
+            Command::Isbn {
+                command: IsbnCommand::Find { isbn },
+            } => {
+                let Some(isbn) = Isbn::parse(&isbn) else {
+                    ::log::error!("{isbn:?} is not a valid book number");
+                    return Ok(Status::Usage);
+                };
+                let paths = Catalogue::open(&cli.xdg_dirs())?.isbn_paths(&isbn)?;
+                for path in &paths {
+                    output.line(path.display())?;
+                }
+                Ok(if paths.is_empty() {
+                    Status::ErrorsFound
+                } else {
+                    Status::Ok
+                })
+            }
+            Command::Offline {
+                command: OfflineCommand::Register { directory, drive },
+            } => {
+                // Registered paths are absolute such that they match wherever the drive is
+                // mounted from.
+                let directory = archive_path(directory)?;
+                let (archives, status) = find_archives(&directory, &ignore)?;
+                Catalogue::open(&cli.xdg_dirs())?.register_offline(&drive, &archives)?;
+                output.line(format_args!(
+                    "registered {} archives on {drive}",
+                    archives.len()
+                ))?;
+                Ok(status)
+            }
+            Command::Offline {
+                command: OfflineCommand::Find { query },
+            } => {
+                let query = query.to_lowercase();
+                for (drive, path) in Catalogue::open(&cli.xdg_dirs())?.offline()? {
+                    let matches = path
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().to_lowercase().contains(&query));
+                    if matches {
+                        let online = if path.exists() { " (connected)" } else { "" };
+                        output.line(format_args!("{drive}{online}: {}", path.display()))?;
+                    }
+                }
+                Ok(Status::Ok)
+            }
+            Command::Offline {
+                command: OfflineCommand::Drives,
+            } => {
+                let offline = Catalogue::open(&cli.xdg_dirs())?.offline()?;
+                for drive in offline.chunk_by(|(a, _), (b, _)| a == b) {
+                    output.line(format_args!("{} ({} archives)", drive[0].0, drive.len()))?;
+                }
+                Ok(Status::Ok)
+            }
+            Command::Offline {
+                command: OfflineCommand::Forget { drive },
+            } => {
+                let forgotten = Catalogue::open(&cli.xdg_dirs())?.forget_offline(&drive)?;
+                if forgotten == 0 {
+                    ::log::error!("no archives are registered on {drive:?}");
+                    return Ok(Status::ErrorsFound);
+                }
+                output.line(format_args!("forgot {forgotten} archives on {drive}"))?;
+                Ok(Status::Ok)
+            }
+            Command::Wishlist {
+                command: WishlistCommand::Add { series, volumes },
+            } => {
+                Catalogue::open(&cli.xdg_dirs())?.add_wishes(&series, &volumes)?;
+                Ok(Status::Ok)
+            }
+            Command::Wishlist {
+                command: WishlistCommand::Remove { series, volumes },
+            } => {
+                let volumes = (!volumes.is_empty()).then_some(volumes.as_slice());
+                let removed = Catalogue::open(&cli.xdg_dirs())?.remove_wishes(&series, volumes)?;
+                if removed == 0 {
+                    ::log::error!("no matching volumes of {series:?} are on the wishlist");
+                    return Ok(Status::ErrorsFound);
+                }
+                Ok(Status::Ok)
+            }
+            Command::Wishlist {
+                command: WishlistCommand::Gaps { directory },
+            } => {
+                let (archives, status) = find_archives(&directory, &ignore)?;
+                let owned = archives
+                    .iter()
+                    .filter_map(|path| series::volume(&path.file_stem()?.to_string_lossy()))
+                    .collect::<Vec<_>>();
+                let gaps = series::gaps(&owned);
+                let name = series::entry_name(&directory);
+                Catalogue::open(&cli.xdg_dirs())?.add_wishes(&name, &gaps)?;
+                output.line(format_args!(
+                    "added {} missing volumes of {name}",
+                    gaps.len()
+                ))?;
+                Ok(status)
+            }
+            Command::Wishlist {
+                command: WishlistCommand::Export { output: path },
+            } => {
+                let wishlist = Catalogue::open(&cli.xdg_dirs())?.wishlist()?;
+                let lines = wishlist
+                    .chunk_by(|a, b| series::series_key(&a.series) == series::series_key(&b.series))
+                    .map(|wishes| {
+                        let volumes = wishes
+                            .iter()
+                            .map(|wish| series::volume_text(wish.volume))
+                            .collect::<Vec<_>>();
+                        format!("{}: {}", wishes[0].series, volumes.join(", "))
+                    })
+                    .collect::<Vec<_>>();
+                let Some(path) = path else {
+                    for line in lines {
+                        output.line(line)?;
+                    }
+                    return Ok(Status::Ok);
+                };
+                let mut content = lines.join("\n");
+                content.push('\n');
+                ::std::fs::write(&path, content).map_err(|source| Error::Config {
+                    path: path.clone(),
+                    source: ConfigError::Write(source),
+                })?;
+                output.line(format_args!(
+                    "wrote {} series to {}",
+                    lines.len(),
+                    path.display()
+                ))?;
+                Ok(Status::Ok)
+            }
+            Command::Follow {
+                command: FollowCommand::Add { series },
+            } => {
+                Catalogue::open(&cli.xdg_dirs())?.follow(&series)?;
+                Ok(Status::Ok)
+            }
+            Command::Follow {
+                command: FollowCommand::Remove { series },
+            } => {
+                if !Catalogue::open(&cli.xdg_dirs())?.unfollow(&series)? {
+                    ::log::error!("{series:?} is not followed");
+                    return Ok(Status::ErrorsFound);
+                }
+                Ok(Status::Ok)
+            }
+            Command::Follow {
+                command: FollowCommand::List,
+            } => {
+                for series in Catalogue::open(&cli.xdg_dirs())?.followed()? {
+                    output.line(series)?;
+                }
+                Ok(Status::Ok)
+            }
+            Command::Follow {
+                command: FollowCommand::Alerts { dismiss },
+            } => {
+                let catalogue = Catalogue::open(&cli.xdg_dirs())?;
+                for alert in catalogue.alerts()? {
+                    output.line(format_args!("{}: {}", alert.series, alert.path.display()))?;
+                }
+                if dismiss {
+                    catalogue.dismiss_alerts(None)?;
+                }
+                Ok(Status::Ok)
+            }
+            Command::RunJob { job } => run_job(&Job::load(&job)?, cli, &mut output),
+            Command::Import {
+                from,
+                database,
+                root,
+                user,
+            } => {
+                let records = import::read(from, &database, user.as_deref())?;
+                let root = archive_path(root.unwrap_or_else(|| PathBuf::from(".")))?;
+                let (archives, status) = find_archives(&root, &ignore)?;
+                let catalogue = Catalogue::open(&cli.xdg_dirs())?;
+                let count = records.len();
+                let (matched, unmatched) = import::match_records(records, &archives);
+                for record in &unmatched {
+                    ::log::warn!("no archive found for {:?}", record.path);
+                }
+                for (path, record) in &matched {
+                    let info = import::merge_info(catalogue.comic_info(path)?, &record.info);
+                    catalogue.set_comic_info(path, &info)?;
+                    if let Some(progress) = record.progress {
+                        catalogue.update(path, |state| import::merge_progress(state, progress))?;
+                    }
+                    output.line(path.display())?;
+                }
+                output.line(format_args!("imported {} of {count} books", matched.len()))?;
+                Ok(status)
+            }
+            Command::Backup { archive } => {
+                let xdg_dirs = cli.xdg_dirs();
+                let archive = archive
+                    .or_else(|| backup::default_path(&xdg_dirs, &cli.profile))
+                    .unwrap_or_else(|| PathBuf::from(format!("{}.zip", cli.profile)));
+                backup::create(&xdg_dirs, &Catalogue::open(&xdg_dirs)?, &archive)?;
+                output.line(archive.display())?;
+                Ok(Status::Ok)
+            }
+            Command::Restore { archive } => {
+                let xdg_dirs = cli.xdg_dirs();
+                // A running instance would keep using, and overwrite, the replaced state.
+                if instance::socket_path(&xdg_dirs)
+                    .is_some_and(|socket| instance::is_running(&socket))
+                {
+                    return Err(Error::Backup {
+                        path: archive,
+                        source: BackupError::InUse,
+                    });
+                }
+                backup::restore(&xdg_dirs, &archive)?;
+                output.line(format_args!(
+                    "restored profile {} from {}",
+                    cli.profile,
+                    archive.display()
+                ))?;
+                Ok(Status::Ok)
+            }
+            Command::Config {
+                command: ConfigCommand::Path,
+            } => {
+                let path = config::path(&cli.xdg_dirs())
+                    .unwrap_or_else(|| PathBuf::from(config::CONFIG_FILES[0]));
+                output.line(path.display())?;
+                Ok(Status::Ok)
+            }
+            Command::Config {
+                command: ConfigCommand::Edit,
+            } => edit_config(&cli.xdg_dirs()),
+            Command::Config {
+                command: ConfigCommand::Reset,
+            } => {
+                let xdg_dirs = cli.xdg_dirs();
+                if let Some(path) = config::find(&xdg_dirs) {
+                    let backup = config::backup(&path)?;
+                    output.line(format_args!("previous config kept at {}", backup.display()))?;
+                }
+                Settings::default().save(&xdg_dirs)?;
+                Ok(Status::Ok)
+            }
+            Command::Config {
+                command: ConfigCommand::Show { format },
+            } => {
+                // Config file is read without being upgraded on disk.
+                let settings = config::find(&cli.xdg_dirs())
+                    .map_or_else(|| Ok(Settings::default()), |path| Settings::read(&path))
+                    .and_then(|settings| Overrides::new(cli).apply(settings))?;
+                let content = format
+                    .serialize(&settings)
+                    .map_err(|source| Error::Config {
+                        path: PathBuf::from("stdout"),
+                        source,
+                    })?;
+                output.line(content.trim_end())?;
+                Ok(Status::Ok)
+            }
+        }
+    }
+}
+
+/// Open config file in an editor, creating it first if missing, and check that the edited
+/// file can be read. Invalid files are kept as they are and result in
+/// [Status::ErrorsFound].
+///
+/// # Errors
+/// If the config file cannot be created or the editor cannot be started.
+fn edit_config(xdg_dirs: &::xdg::BaseDirectories) -> Result<Status, Error> {
+    if config::find(xdg_dirs).is_none() {
+        Settings::default().save(xdg_dirs)?;
+    }
+    let path = config::place(xdg_dirs)?;
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|var| ::std::env::var(var).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_owned());
+
+    // Editors may be given with arguments, such as `code --wait`.
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or(DEFAULT_EDITOR);
+    let exit_status = ::std::process::Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .map_err(|source| Error::Editor {
+            editor: editor.clone(),
+            source,
+        })?;
+    if !exit_status.success() {
+        ::log::error!("editor {editor:?} exited with {exit_status}");
+        return Ok(Status::ErrorsFound);
+    }
+
+    match Settings::read(&path) {
+        Ok(_) => Ok(Status::Ok),
+        Err(err) => {
+            err.log();
+            Ok(Status::ErrorsFound)
+        }
+    }
+}
+
+/// Get absolute path of an archive or a directory of archives, as catalogue state is
+/// recorded for absolute paths.
+///
+/// # Errors
+/// If the path cannot be made absolute.
+fn archive_path(path: PathBuf) -> Result<PathBuf, Error> {
+    ::std::path::absolute(&path).map_err(|source| Error::Scan { path, source })
+}
+
+/// Compare archives of a library against a manifest. Archives that changed or are
+/// missing, and invalid lines of the manifest, are logged and result in
+/// [Status::ErrorsFound]. Archives not listed are reported without being errors.
+///
+/// # Errors
+/// If the manifest or library cannot be read, or output cannot be written.
+fn verify_manifest(
+    manifest: &Path,
+    root: Option<PathBuf>,
+    ignore: &IgnoreRules,
+    output: &mut Output,
+) -> Result<Status, Error> {
+    let content = ::std::fs::read_to_string(manifest).map_err(|source| Error::Config {
+        path: manifest.to_path_buf(),
+        source: ConfigError::Read(source),
+    })?;
+    let root = root.unwrap_or_else(|| {
+        manifest
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf()
+    });
+    let (archives, mut status) = find_archives(&root, ignore)?;
+    let mut listed = ::hashbrown::HashSet::new();
+    let (mut changed, mut missing) = (0usize, 0usize);
+
+    for (number, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((expected, relative)) = manifest::parse_line(line) else {
+            ::log::error!("line {} of {manifest:?} is not valid", number + 1);
+            status = Status::ErrorsFound;
+            continue;
+        };
+        let path = root.join(&*relative);
+        match manifest::checksum(&path) {
+            Ok(checksum) if checksum.eq_ignore_ascii_case(expected) => {
+                output.line(format_args!("ok {relative}"))?;
+            }
+            Ok(_) => {
+                ::log::error!("checksum of {path:?} does not match manifest");
+                changed += 1;
+                status = Status::ErrorsFound;
+            }
+            Err(source) => {
+                Error::Scan {
+                    path: path.clone(),
+                    source,
+                }
+                .log();
+                missing += 1;
+                status = Status::ErrorsFound;
+            }
+        }
+        listed.insert(path);
+    }
+
+    let mut unlisted = 0usize;
+    for path in archives.iter().filter(|path| !listed.contains(*path)) {
+        output.line(format_args!("unlisted {}", path.display()))?;
+        unlisted += 1;
+    }
+    output.line(format_args!(
+        "{} archives verified, {changed} changed, {missing} unreadable, {unlisted} unlisted",
+        listed.len()
+    ))?;
+    Ok(status)
+}
+
+/// Run steps of a batch job in order, reporting progress of each archive. Archives that
+/// fail a step are logged and result in [Status::ErrorsFound].
+///
+/// # Errors
+/// If a scanned directory or the catalogue cannot be used, or output cannot be written.
+fn run_job(job: &Job, cli: &Cli, output: &mut Output) -> Result<Status, Error> {
+    let xdg_dirs = cli.xdg_dirs();
+    let catalogue = Catalogue::open(&xdg_dirs)?;
+    let ignore = IgnoreRules::of_profile(cli);
+    let temp_dir = temp::dir(&xdg_dirs)
+        .inspect_err(|err| ::log::error!("could not create directory of temporary files\n{err}"))
+        .ok()
+        .inspect(|dir| temp::clean(dir))
+        .map(Arc::<Path>::from);
+    let mut archives = Vec::<PathBuf>::new();
+    let mut status = Status::Ok;
+
+    for (number, step) in job.steps.iter().enumerate() {
+        output.line(format_args!(
+            "step {}/{}: {}",
+            number + 1,
+            job.steps.len(),
+            step.name()
+        ))?;
+        let count = archives.len();
+        match step {
+            Step::Scan { path } => {
+                let path = archive_path(path.clone())?;
+                let (found, scan_status) = find_archives(&path, &ignore)?;
+                status = status.max(scan_status);
+                let added = catalogue.record_seen(&found)?;
+                output.line(format_args!(
+                    "found {} archives in {}",
+                    found.len(),
+                    path.display()
+                ))?;
+                for alert in catalogue.alert_new_volumes(&added)? {
+                    output.line(format_args!(
+                        "new volume of {}: {}",
+                        alert.series,
+                        alert.path.display()
+                    ))?;
+                }
+                archives.extend(found);
+                archives.sort_unstable();
+                archives.dedup();
+            }
+            Step::Verify => {
+                for (index, path) in archives.iter().enumerate() {
+                    match verify(path) {
+                        Ok(page_count) => output.line(format_args!(
+                            "[{}/{count}] ok {} ({page_count} pages)",
+                            index + 1,
+                            path.display()
+                        ))?,
+                        Err(source) => {
+                            Error::Archive {
+                                path: path.clone(),
+                                source,
+                            }
+                            .log();
+                            status = Status::ErrorsFound;
+                        }
+                    }
+                }
+            }
+            Step::StripDuplicatePages => {
+                let Some(temp_dir) = &temp_dir else {
+                    status = Status::ErrorsFound;
+                    continue;
+                };
+                for (index, path) in archives.iter().enumerate() {
+                    match archive::strip_duplicate_pages(path, temp_dir) {
+                        Ok(0) => {}
+                        Ok(removed) => output.line(format_args!(
+                            "[{}/{count}] removed {removed} duplicate pages from {}",
+                            index + 1,
+                            path.display()
+                        ))?,
+                        Err(source) => {
+                            Error::Archive {
+                                path: path.clone(),
+                                source,
+                            }
+                            .log();
+                            status = Status::ErrorsFound;
+                        }
+                    }
+                }
+            }
+            Step::Thumbnails => {
+                // Freedesktop thumbnails are shared by all applications, they are not per
+                // profile.
+                let Some((cache, temp_dir)) = xdg_dirs.cache_home.as_ref().zip(temp_dir.as_ref())
+                else {
+                    status = Status::ErrorsFound;
+                    continue;
+                };
+                let dirs = ThumbnailDirs {
+                    thumbnails: Arc::from(cache.join("thumbnails")),
+                    temp: Arc::clone(temp_dir),
+                };
+                for (index, path) in archives.iter().enumerate() {
+                    let cover = match archive::preview(path, freedesktop::MAX_SIZE) {
+                        Ok((cover, _)) => cover,
+                        Err(source) => {
+                            Error::Archive {
+                                path: path.clone(),
+                                source,
+                            }
+                            .log();
+                            status = Status::ErrorsFound;
+                            continue;
+                        }
+                    };
+                    match freedesktop::write_thumbnails(&dirs, path, &cover) {
+                        Ok(()) => output.line(format_args!(
+                            "[{}/{count}] thumbnails of {}",
+                            index + 1,
+                            path.display()
+                        ))?,
+                        Err(err) => {
+                            ::log::error!(
+                                "could not write freedesktop thumbnail of {path:?}\n{err}"
+                            );
+                            status = Status::ErrorsFound;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(status)
+}
+
+/// Check that a profile name may be used as the name of a directory.
+fn is_profile_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\'])
+}
+
+/// Read entries of a reading list file, json files are arrays of strings and other files
+/// have one entry per line.
+///
+/// # Errors
+/// If the file cannot be read or is not a valid json array of strings.
+fn read_list(path: &Path) -> Result<Vec<String>, Error> {
+    ::std::fs::read_to_string(path)
+        .map_err(ConfigError::Read)
+        .and_then(|content| {
+            if config::Format::from_path(path) == Format::Json {
+                ::serde_json::from_str(&content).map_err(ConfigError::ParseJson)
+            } else {
+                Ok(content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_owned)
+                    .collect())
+            }
+        })
+        .map_err(|source| Error::Config {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Resolve an entry of a reading list to the path of an archive. Entries naming an
+/// existing file are used as is, other entries are titles matched against the names of
+/// archives, preferring an exact match over one containing the title.
+fn resolve_entry(entry: &str, archives: &[PathBuf]) -> Option<PathBuf> {
+    let path = Path::new(entry);
+    if path.is_file() {
+        return Some(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+    }
+    let title = entry.to_lowercase();
+    let stem = |archive: &PathBuf| {
+        archive
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
+    archives
+        .iter()
+        .find(|archive| stem(archive) == title)
+        .or_else(|| {
+            archives
+                .iter()
+                .find(|archive| stem(archive).contains(&title))
+        })
+        .map(|archive| archive.canonicalize().unwrap_or_else(|_| archive.clone()))
+}
+
+/// Find supported archives in a directory and its subdirectories, sorted by path, skipping
+/// entries ignored by rules. Subdirectories that cannot be read are logged and result in
+/// [Status::ErrorsFound].
+///
+/// # Errors
+/// If the directory itself cannot be read.
+pub(crate) fn find_archives(
+    directory: &Path,
+    ignore: &IgnoreRules,
+) -> Result<(Vec<PathBuf>, Status), Error> {
+    find_archives_throttled(directory, ignore, &Throttle::default())
+}
+
+/// Find archives like [find_archives], pausing between directories while throttled.
+///
+/// # Errors
+/// If the directory itself cannot be read.
+pub(crate) fn find_archives_throttled(
+    directory: &Path,
+    ignore: &IgnoreRules,
+    throttle: &Throttle,
+) -> Result<(Vec<PathBuf>, Status), Error> {
+    find_files(
+        directory,
+        |path| ArchiveKind::from_path(path).is_some(),
+        ignore,
+        throttle,
+    )
+}
+
+/// Find files matching a predicate in a directory and its subdirectories, sorted by
+/// path, skipping entries ignored by rules. Entries reached through several symlinks or
+/// bind mounts are only found once, such that symlink cycles do not loop. Subdirectories
+/// that cannot be read are logged and result in [Status::ErrorsFound].
+///
+/// # Errors
+/// If the directory itself cannot be read.
+fn find_files(
+    directory: &Path,
+    matches: fn(&Path) -> bool,
+    ignore: &IgnoreRules,
+    throttle: &Throttle,
+) -> Result<(Vec<PathBuf>, Status), Error> {
+    /// Files found by a scan, along with directories left to read.
+    #[derive(Debug, Default)]
+    struct Found {
+        /// Matching files found.
+        files: Vec<PathBuf>,
+        /// Directories left to read.
+        dirs: Vec<PathBuf>,
+        /// Symlinks left to follow, once all directories have been read such that files
+        /// are found through their real paths before any symlinks.
+        links: Vec<PathBuf>,
+        /// Device and inode numbers of files and directories found, such that those
+        /// reached through several symlinks or bind mounts are only found once, and
+        /// symlink cycles end.
+        visited: ::hashbrown::HashSet<(u64, u64)>,
+    }
+
+    impl Found {
+        /// Record a file or directory as found, pushing it to files or directories,
+        /// unless it has been found before.
+        fn visit(&mut self, path: PathBuf, metadata: &Metadata) {
+            if !self.visited.insert((metadata.dev(), metadata.ino())) {
+                ::log::info!("skipping {path:?}, it has already been found through another path");
+            } else if metadata.is_dir() {
+                self.dirs.push(path);
+            } else {
+                self.files.push(path);
+            }
+        }
+    }
+
+    /// Read a directory, pushing matching files and subdirectories to found.
+    fn read(
+        directory: &Path,
+        matches: fn(&Path) -> bool,
+        ignore: &IgnoreRules,
+        found: &mut Found,
+    ) -> ::std::io::Result<()> {
+        for entry in ::std::fs::read_dir(directory)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_symlink() {
+                if ignore.follows_symlinks() && !ignore.ignores_name(&path) {
+                    found.links.push(path);
+                }
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            let is_dir = metadata.is_dir();
+            if !ignore.ignores(&path, is_dir) && (is_dir || matches(&path)) {
+                found.visit(path, &metadata);
+            }
+        }
+        Ok(())
+    }
+
+    let mut found = Found::default();
+    let mut status = Status::Ok;
+    ::std::fs::metadata(directory)
+        .map(|metadata| found.visited.insert((metadata.dev(), metadata.ino())))
+        .and_then(|_| read(directory, matches, ignore, &mut found))
+        .map_err(|source| Error::Scan {
+            path: directory.to_path_buf(),
+            source,
+        })?;
+    loop {
+        while let Some(path) = found.dirs.pop() {
+            throttle.pause();
+            if let Err(source) = read(&path, matches, ignore, &mut found) {
+                Error::Scan { path, source }.log();
+                status = Status::ErrorsFound;
+            }
+        }
+        let Some(link) = found.links.pop() else {
+            break;
+        };
+        match ::std::fs::metadata(&link) {
+            Ok(metadata) if metadata.is_dir() || matches(&link) => {
+                if !ignore.ignores(&link, metadata.is_dir()) {
+                    found.visit(link, &metadata);
+                }
+            }
+            Ok(_) => {}
+            Err(err) => ::log::warn!("could not follow symlink {link:?}\n{err}"),
+        }
+    }
+    let mut archives = found.files;
+    archives.sort_unstable();
+    Ok((archives, status))
+}
+
+/// Open archive and decode all of its pages, returning the amount of pages.
+///
+/// # Errors
+/// If the archive cannot be opened or any page cannot be read or decoded.
+fn verify(path: &Path) -> archive::Result<usize> {
+    archive::verify(path, &CancelToken::default(), |_, _| {})
+}
@@ -0,0 +1,286 @@
+//! Config file formats, and overrides of settings from the command line and environment.
+
+use ::core::fmt::Display;
+use ::std::path::{Path, PathBuf};
+
+use ::clap::ValueEnum;
+use ::serde::Serialize;
+use ::toml::{Table, Value};
+
+use crate::{
+    Cli, Settings,
+    error::{ConfigError, Error},
+    temp,
+};
+
+/// Names of config files in order of preference, the first is used when creating one.
+pub const CONFIG_FILES: [&str; 2] = ["config.toml", "config.json"];
+
+/// Prefix of environment variables overriding settings, followed by the name of the
+/// setting in upper case, such as `ARKIV_KATALOG_THEME`.
+const ENV_PREFIX: &str = "ARKIV_KATALOG_";
+
+/// Format of a config file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Toml, used for config files with any extension but `json`.
+    #[default]
+    Toml,
+    /// Json.
+    Json,
+}
+
+impl Format {
+    /// Get format of config file at path, detected by its extension.
+    pub fn from_path(path: &Path) -> Self {
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        {
+            Self::Json
+        } else {
+            Self::Toml
+        }
+    }
+
+    /// Parse content of a config file.
+    ///
+    /// # Errors
+    /// If content is not valid in format.
+    pub fn parse(self, content: &str) -> Result<Table, ConfigError> {
+        match self {
+            Format::Toml => ::toml::from_str(content).map_err(ConfigError::Parse),
+            Format::Json => ::serde_json::from_str(content)
+                .map(strip_nulls)
+                .and_then(::serde_json::from_value)
+                .map_err(ConfigError::ParseJson),
+        }
+    }
+
+    /// Serialize value as content of a config file.
+    ///
+    /// # Errors
+    /// If value cannot be represented in format.
+    pub fn serialize(self, value: &impl Serialize) -> Result<String, ConfigError> {
+        match self {
+            Format::Toml => ::toml::to_string_pretty(value).map_err(ConfigError::Serialize),
+            Format::Json => {
+                ::serde_json::to_string_pretty(value).map_err(ConfigError::SerializeJson)
+            }
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(match self {
+            Format::Toml => "toml",
+            Format::Json => "json",
+        })
+    }
+}
+
+/// Find existing config file of profile, preferring toml if there are several.
+pub fn find(xdg_dirs: &::xdg::BaseDirectories) -> Option<PathBuf> {
+    let mut found = CONFIG_FILES
+        .iter()
+        .filter_map(|name| xdg_dirs.find_config_file(name));
+    let path = found.next()?;
+    if let Some(ignored) = found.next() {
+        ::log::warn!("using config file {path:?}, ignoring {ignored:?}");
+    }
+    Some(path)
+}
+
+/// Read font size of profile from overrides or its config file, without reading other
+/// settings, as it is needed before any window opens. [None] if not set, or if it cannot
+/// be read, in which case loading settings reports why.
+#[cfg(feature = "gui")]
+pub fn font_size(xdg_dirs: &::xdg::BaseDirectories, overrides: &Overrides) -> Option<u16> {
+    /// Name of font size setting.
+    const FONT_SIZE: &str = "font_size";
+    let value = match overrides.table.get(FONT_SIZE) {
+        Some(value) => value.clone(),
+        None => {
+            let path = find(xdg_dirs)?;
+            let content = ::std::fs::read_to_string(&path).ok()?;
+            Format::from_path(&path)
+                .parse(&content)
+                .ok()?
+                .remove(FONT_SIZE)?
+        }
+    };
+    value.try_into().ok()
+}
+
+/// Get path of config file of profile, the existing one or where one would be created.
+pub fn path(xdg_dirs: &::xdg::BaseDirectories) -> Option<PathBuf> {
+    find(xdg_dirs).or_else(|| xdg_dirs.get_config_file(CONFIG_FILES[0]))
+}
+
+/// Get path config file of profile should be written to, creating its directory.
+///
+/// # Errors
+/// If the config directory cannot be created.
+pub fn place(xdg_dirs: &::xdg::BaseDirectories) -> Result<PathBuf, Error> {
+    find(xdg_dirs).map_or_else(
+        || {
+            xdg_dirs
+                .place_config_file(CONFIG_FILES[0])
+                .map_err(|source| Error::Config {
+                    path: PathBuf::from(CONFIG_FILES[0]),
+                    source: ConfigError::Write(source),
+                })
+        },
+        Ok,
+    )
+}
+
+/// Get path of backup of config file at path, such as `config.toml.bak`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    path.with_extension(format!("{}.bak", Format::from_path(path)))
+}
+
+/// Copy config file at path to a backup next to it, such as `config.toml.bak`, returning
+/// the path of the backup.
+///
+/// # Errors
+/// If the backup cannot be written.
+pub fn backup(path: &Path) -> Result<PathBuf, Error> {
+    let backup = backup_path(path);
+    temp::copy(path, &backup)
+        .map(|()| backup.clone())
+        .map_err(|source| Error::Config {
+            path: backup,
+            source: ConfigError::Write(source),
+        })
+}
+
+/// Write content to config file at path such that it is never partially written, keeping
+/// the previous config file as a backup if its content is valid. Backups of valid files
+/// are not replaced by invalid ones, such that there is always a valid file to fall back
+/// to.
+///
+/// # Errors
+/// If the backup or config file cannot be written.
+pub fn write(path: &Path, content: &str) -> Result<(), Error> {
+    let format = Format::from_path(path);
+    if ::std::fs::read_to_string(path).is_ok_and(|previous| format.parse(&previous).is_ok()) {
+        backup(path)?;
+    }
+    temp::write(path, content.as_bytes()).map_err(|source| Error::Config {
+        path: path.to_path_buf(),
+        source: ConfigError::Write(source),
+    })
+}
+
+/// Path used in errors of overrides, as overrides have no file.
+const OVERRIDES: &str = "environment";
+
+/// Settings overridden by the command line and environment, environment variables take
+/// precedence over command line arguments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Overrides {
+    /// Overridden settings, in the same form as in a config file.
+    table: Table,
+}
+
+impl Overrides {
+    /// Collect overrides from command line arguments and environment variables.
+    pub fn new(cli: &Cli) -> Self {
+        let mut table = Table::new();
+        if let Some(theme) = cli.theme.and_then(|theme| Value::try_from(theme).ok()) {
+            table.insert("theme".to_owned(), theme);
+        }
+        if let Some(level) = cli.log_level.and_then(|level| Value::try_from(level).ok()) {
+            table.insert("log_level".to_owned(), level);
+        }
+        for (name, value) in ::std::env::vars_os() {
+            let (Some(name), Some(value)) = (name.to_str(), value.to_str()) else {
+                continue;
+            };
+            if let Some(key) = name.strip_prefix(ENV_PREFIX) {
+                table.insert(key.to_lowercase(), parse_value(value));
+            }
+        }
+        Self { table }
+    }
+
+    /// Apply overrides to settings.
+    ///
+    /// # Errors
+    /// If an overridden setting has an invalid value.
+    pub fn apply(&self, settings: Settings) -> Result<Settings, Error> {
+        if self.table.is_empty() {
+            return Ok(settings);
+        }
+        Table::try_from(settings)
+            .map_err(ConfigError::Serialize)
+            .and_then(|mut table| {
+                table.extend(self.table.clone());
+                Value::Table(table).try_into().map_err(ConfigError::Parse)
+            })
+            .map_err(|source| Error::Config {
+                path: PathBuf::from(OVERRIDES),
+                source,
+            })
+    }
+
+    /// Save settings to config directory, with overridden settings keeping their values
+    /// in the config file, or their defaults if not set there, such that overrides only
+    /// last while they are given.
+    ///
+    /// # Errors
+    /// If the config file cannot be read, or the settings cannot be saved.
+    #[cfg(feature = "gui")]
+    pub fn save(
+        &self,
+        settings: &Settings,
+        xdg_dirs: &::xdg::BaseDirectories,
+    ) -> Result<(), Error> {
+        if self.table.is_empty() {
+            return settings.save(xdg_dirs);
+        }
+        let file = Settings::load(xdg_dirs)?;
+        Table::try_from(file)
+            .and_then(|file| Ok((file, Table::try_from(settings)?)))
+            .map_err(ConfigError::Serialize)
+            .and_then(|(file, mut table)| {
+                for key in self.table.keys() {
+                    match file.get(key) {
+                        Some(value) => table.insert(key.clone(), value.clone()),
+                        None => table.remove(key),
+                    };
+                }
+                Value::Table(table).try_into().map_err(ConfigError::Parse)
+            })
+            .map_err(|source| Error::Config {
+                path: PathBuf::from(OVERRIDES),
+                source,
+            })
+            .and_then(|settings: Settings| settings.save(xdg_dirs))
+    }
+}
+
+/// Remove null values, which have no toml equivalent, from json objects. Settings which
+/// are null are unset, as they would be if they were missing.
+fn strip_nulls(value: ::serde_json::Value) -> ::serde_json::Value {
+    match value {
+        ::serde_json::Value::Object(object) => object
+            .into_iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(key, value)| (key, strip_nulls(value)))
+            .collect(),
+        ::serde_json::Value::Array(array) => array.into_iter().map(strip_nulls).collect(),
+        value => value,
+    }
+}
+
+/// Parse value of an environment variable as a toml value, values which are not valid
+/// toml such as `dark` are used as strings.
+fn parse_value(raw: &str) -> Value {
+    ::toml::from_str::<Table>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_owned()))
+}
@@ -0,0 +1,63 @@
+//! Watching of config file for changes made outside of application.
+
+use ::core::time::Duration;
+use ::std::{path::Path, sync::Arc};
+
+use ::futures::{SinkExt, Stream, channel::mpsc::Sender};
+use ::notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::{Settings, error::Error};
+
+/// Time to wait after a change before reading config file, editors may save files in
+/// several steps.
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// Watch directory of config file at path, sending settings read from it to output
+/// whenever it is changed.
+///
+/// # Errors
+/// If the directory cannot be watched.
+async fn watch(path: &Path, output: &mut Sender<Result<Settings, Error>>) -> ::notify::Result<()> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return Ok(());
+    };
+    let (sender, receiver) = ::flume::unbounded();
+    let mut watcher = ::notify::recommended_watcher(move |event| _ = sender.send(event))?;
+    // The directory is watched as editors may replace the file instead of writing to it.
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+    while let Ok(event) = receiver.recv_async().await {
+        let event = match event {
+            Ok(event) => event,
+            Err(err) => {
+                ::log::warn!("error while watching {path:?}\n{err}");
+                continue;
+            }
+        };
+        if matches!(event.kind, EventKind::Access(..))
+            || !event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == Some(name))
+        {
+            continue;
+        }
+
+        ::smol::Timer::after(SETTLE_TIME).await;
+        receiver.drain();
+        if path.exists() {
+            _ = output.send(Settings::read(path)).await;
+        }
+    }
+    Ok(())
+}
+
+/// Stream of settings read from config file at path whenever it changes on disk.
+pub fn settings_changes(path: &Arc<Path>) -> impl Stream<Item = Result<Settings, Error>> + use<> {
+    let path = Arc::clone(path);
+    ::iced::stream::channel(1, async move |mut output| {
+        if let Err(err) = watch(&path, &mut output).await {
+            ::log::info!("could not watch {path:?} for changes\n{err}");
+        }
+    })
+}
@@ -0,0 +1,203 @@
+//! Desktop integration through the `org.freedesktop.Application` D-Bus interface, used by
+//! desktop environments to launch application and open files in it.
+#![expect(
+    clippy::disallowed_types,
+    reason = "zvariant only implements Type for std HashMap, which interface methods and \
+              portal requests take"
+)]
+
+use ::core::sync::atomic::{AtomicU32, Ordering};
+use ::std::{collections::HashMap, path::PathBuf};
+
+use ::futures::{SinkExt, Stream, StreamExt, channel::mpsc::Sender};
+use ::zbus::{
+    interface,
+    zvariant::{OwnedValue, Value},
+};
+
+use crate::{freedesktop::file_path, i18n::tr};
+
+/// Application id, the well-known bus name of application.
+const APP_ID: &str = "io.github.axel_lord.ArkivKatalog";
+
+/// Object path application is served at, derived from [APP_ID].
+const OBJECT_PATH: &str = "/io/github/axel_lord/ArkivKatalog";
+
+/// Platform data passed along with requests, such as activation tokens.
+type PlatformData = HashMap<String, OwnedValue>;
+
+/// Implementation of `org.freedesktop.Application`, forwarding requests as paths to
+/// open, [None] to open a window without a path.
+struct Application {
+    /// Sender of requests.
+    output: Sender<Option<PathBuf>>,
+}
+
+#[interface(name = "org.freedesktop.Application")]
+impl Application {
+    /// Activate application, opening a window.
+    async fn activate(&mut self, platform_data: PlatformData) {
+        _ = platform_data;
+        _ = self.output.send(None).await;
+    }
+
+    /// Open files given as uris, only local files are supported.
+    async fn open(&mut self, uris: Vec<String>, platform_data: PlatformData) {
+        _ = platform_data;
+        for uri in uris {
+            match file_path(&uri) {
+                Some(path) => _ = self.output.send(Some(path)).await,
+                None => ::log::warn!("cannot open {uri:?}, only local files are supported"),
+            }
+        }
+    }
+
+    /// Activate an action, application has no actions and activates instead.
+    async fn activate_action(
+        &mut self,
+        action_name: String,
+        parameter: Vec<OwnedValue>,
+        platform_data: PlatformData,
+    ) {
+        _ = (parameter, platform_data);
+        ::log::info!("unknown action {action_name:?}, activating instead");
+        _ = self.output.send(None).await;
+    }
+}
+
+/// Serve application on session bus, sending requests to output until the connection
+/// is lost.
+///
+/// # Errors
+/// If the session bus cannot be reached or the name of application is taken.
+async fn serve(output: Sender<Option<PathBuf>>) -> ::zbus::Result<()> {
+    let _connection = ::zbus::connection::Builder::session()?
+        .name(APP_ID)?
+        .serve_at(OBJECT_PATH, Application { output })?
+        .build()
+        .await?;
+    // Requests are handled by the connection for as long as it is kept.
+    ::futures::future::pending::<()>().await;
+    Ok(())
+}
+
+/// Show a desktop notification through `org.freedesktop.Notifications`.
+///
+/// # Errors
+/// If the session bus cannot be reached or no notification server is running.
+pub async fn notify(summary: &str, body: &str) -> ::zbus::Result<()> {
+    ::zbus::Connection::session()
+        .await?
+        .call_method(
+            Some("org.freedesktop.Notifications"),
+            "/org/freedesktop/Notifications",
+            Some("org.freedesktop.Notifications"),
+            "Notify",
+            &(
+                "Arkiv Katalog",
+                0u32,
+                APP_ID,
+                summary,
+                body,
+                Vec::<&str>::new(),
+                PlatformData::new(),
+                -1i32,
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Ask the desktop to pick a directory through the `org.freedesktop.portal.FileChooser`
+/// interface, [None] if the request was cancelled.
+///
+/// # Errors
+/// If the session bus cannot be reached or no portal is running.
+pub async fn pick_directory(title: String) -> ::zbus::Result<Option<PathBuf>> {
+    pick(&title, true).await
+}
+
+/// Ask the desktop to pick an image file through the `org.freedesktop.portal.FileChooser`
+/// interface, [None] if the request was cancelled.
+///
+/// # Errors
+/// If the session bus cannot be reached or no portal is running.
+pub async fn pick_image(title: String) -> ::zbus::Result<Option<PathBuf>> {
+    pick(&title, false).await
+}
+
+/// Ask the desktop to pick a directory, or an image file if not, through the
+/// `org.freedesktop.portal.FileChooser` interface, [None] if the request was cancelled.
+///
+/// # Errors
+/// If the session bus cannot be reached or no portal is running.
+async fn pick(title: &str, directory: bool) -> ::zbus::Result<Option<PathBuf>> {
+    /// Counter making handle tokens of requests unique.
+    static REQUESTS: AtomicU32 = AtomicU32::new(0);
+
+    let connection = ::zbus::Connection::session().await?;
+    let token = format!(
+        "arkiv_katalog_{}_{}",
+        ::std::process::id(),
+        REQUESTS.fetch_add(1, Ordering::Relaxed)
+    );
+    // The response is sent by a request object at a path derived from the unique name
+    // of the connection and the token, listened to before requesting to not miss it.
+    let sender = connection
+        .unique_name()
+        .map(|name| name.trim_start_matches(':').replace('.', "_"))
+        .unwrap_or_default();
+    let request = ::zbus::Proxy::new(
+        &connection,
+        "org.freedesktop.portal.Desktop",
+        format!("/org/freedesktop/portal/desktop/request/{sender}/{token}"),
+        "org.freedesktop.portal.Request",
+    )
+    .await?;
+    let mut responses = request.receive_signal("Response").await?;
+
+    let mut options = HashMap::from([
+        ("handle_token", Value::from(token.as_str())),
+        ("directory", Value::from(directory)),
+    ]);
+    if !directory {
+        let filters = vec![(
+            tr("dialog-filter-images"),
+            vec![(1u32, "image/*".to_owned())],
+        )];
+        options.insert("filters", Value::from(filters));
+    }
+    connection
+        .call_method(
+            Some("org.freedesktop.portal.Desktop"),
+            "/org/freedesktop/portal/desktop",
+            Some("org.freedesktop.portal.FileChooser"),
+            "OpenFile",
+            &("", title, options),
+        )
+        .await?;
+
+    let Some(response) = responses.next().await else {
+        return Ok(None);
+    };
+    let (code, results) = response.body().deserialize::<(u32, PlatformData)>()?;
+    // Any other code means the request was cancelled or failed.
+    if code != 0 {
+        return Ok(None);
+    }
+    let Some(uris) = results.get("uris") else {
+        return Ok(None);
+    };
+    let uris = Vec::<String>::try_from(uris.try_clone()?)?;
+    Ok(uris.first().and_then(|uri| file_path(uri)))
+}
+
+/// Stream of paths requested to be opened through D-Bus, [None] for activation without
+/// a path.
+pub fn requests() -> impl Stream<Item = Option<PathBuf>> {
+    ::iced::stream::channel(1, async |output| {
+        if let Err(err) = serve(output).await {
+            ::log::info!("could not serve {APP_ID} on session bus\n{err}");
+        }
+    })
+}
@@ -0,0 +1,83 @@
+//! Debug overlay showing resource usage.
+
+use ::iced::{
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{Message, SizeUnits, format};
+
+/// Resource usage displayed by debug overlay.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stats {
+    /// Amount of thumbnails in cache.
+    pub cached_thumbnails: usize,
+    /// Approximate memory used by thumbnail cache in bytes.
+    pub cache_used: usize,
+    /// Memory budget of thumbnail cache in bytes.
+    pub cache_budget: usize,
+    /// Amount of items in all views.
+    pub items: usize,
+    /// Amount of thumbnails being loaded.
+    pub loading_thumbnails: usize,
+    /// Amount of page images held by reader windows.
+    pub page_handles: usize,
+    /// Amount of jobs queued or running on the worker pool.
+    pub queued_jobs: usize,
+    /// Resident memory of process in bytes, if known.
+    pub resident_memory: Option<usize>,
+}
+
+/// Get resident memory of process in bytes.
+pub fn resident_memory() -> Option<usize> {
+    ::std::fs::read_to_string("/proc/self/status")
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<usize>()
+        .ok()?
+        .checked_mul(1024)
+}
+
+impl Stats {
+    /// View debug overlay.
+    pub fn view<'a>(self, size_units: SizeUnits) -> Element<'a, Message> {
+        let size = |bytes: usize| format::size(bytes as u64, size_units);
+        [
+            format!(
+                "thumbnail cache: {} / {} ({} thumbnails)",
+                size(self.cache_used),
+                size(self.cache_budget),
+                format::number(self.cached_thumbnails)
+            ),
+            format!(
+                "items: {} ({} loading)",
+                format::number(self.items),
+                format::number(self.loading_thumbnails)
+            ),
+            format!("page handles: {}", format::number(self.page_handles)),
+            format!("worker jobs: {}", format::number(self.queued_jobs)),
+            format!(
+                "resident memory: {}",
+                self.resident_memory
+                    .map_or_else(|| "unknown".to_owned(), size)
+            ),
+        ]
+        .into_iter()
+        .map(|line| widget::text(line).size(12).wrapping(Wrapping::None).into())
+        .pipe(widget::Column::with_children)
+        .pipe(widget::container)
+        .padding(5)
+        .style(widget::container::bordered_box)
+        .pipe(widget::container)
+        .padding(10)
+        .align_right(Fill)
+        .align_top(Fill)
+        .into()
+    }
+}
@@ -0,0 +1,490 @@
+//! [Dialog] impl, modal dialogs shown over a window asking for confirmation or text, or
+//! showing progress of an operation. Dialogs are kept as a stack, the most recent dialog
+//! of a window is shown over it and answered with Enter or cancelled with Escape.
+
+use ::core::{
+    fmt::Debug,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use ::iced::{
+    Alignment::Center,
+    Color, Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+    window,
+};
+use ::tap::Pipe;
+
+use crate::{Message, i18n::tr, worker::CancelToken};
+
+/// Width of dialogs.
+const DIALOG_WIDTH: f32 = 360.0;
+
+/// Counter making identifiers of dialogs unique.
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Identifier of a dialog, used to answer, update or close it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DialogId(u64);
+
+impl DialogId {
+    /// Get a new unique identifier.
+    fn next() -> Self {
+        Self(COUNTER.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Function creating the message sent with the text of a prompt when it is submitted.
+pub struct OnSubmit(Box<dyn Fn(String) -> Message>);
+
+impl Debug for OnSubmit {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str("OnSubmit")
+    }
+}
+
+/// Function checking whether text entered into a prompt may be submitted.
+pub struct Validate(Box<dyn Fn(&str) -> bool>);
+
+impl Debug for Validate {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str("Validate")
+    }
+}
+
+/// Function creating the message sent with the index of the chosen option of a choice,
+/// and whether it applies to all later choices.
+pub struct OnChoose(Box<dyn Fn(usize, bool) -> Message>);
+
+impl Debug for OnChoose {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str("OnChoose")
+    }
+}
+
+/// What a dialog asks for or shows.
+#[derive(Debug)]
+pub enum Kind {
+    /// Ask for confirmation of an action.
+    Confirm {
+        /// Label of button confirming action.
+        action: String,
+        /// Message sent when confirmed, boxed as messages are large.
+        on_confirm: Box<Message>,
+        /// Action cannot be undone, such that its button is styled as dangerous.
+        destructive: bool,
+    },
+    /// Ask for text, such as a name or a password.
+    Prompt {
+        /// Text entered so far.
+        value: String,
+        /// Text shown while nothing has been entered.
+        placeholder: String,
+        /// Label of button submitting text.
+        action: String,
+        /// Get message sent with entered text when submitted.
+        on_submit: OnSubmit,
+        /// Check if entered text may be submitted, any text may be if [None].
+        validate: Option<Validate>,
+        /// Message sent by a button next to the text input letting a value be picked,
+        /// boxed as messages are large.
+        browse: Option<Box<Message>>,
+    },
+    /// Ask to choose one of several options, the first is chosen by Enter.
+    Choose {
+        /// Labels of options.
+        options: Vec<String>,
+        /// Whether the choice applies to all later choices, [None] if it cannot.
+        all: Option<bool>,
+        /// Get message sent with chosen option.
+        on_choose: OnChoose,
+    },
+    /// Show that an operation is in progress, until the operation closes the dialog.
+    Progress {
+        /// Token cancelling operation, the dialog cannot be cancelled without one.
+        cancel: Option<CancelToken>,
+        /// Fraction of operation done, [None] if not known.
+        progress: Option<f32>,
+    },
+}
+
+/// A modal dialog shown over a window.
+#[derive(Debug)]
+pub struct Dialog {
+    /// Identifier of dialog.
+    pub id: DialogId,
+    /// Window dialog is shown over.
+    pub window_id: window::Id,
+    /// Title of dialog.
+    title: String,
+    /// Text explaining what is asked for, may be empty.
+    body: String,
+    /// What dialog asks for or shows.
+    pub kind: Kind,
+    /// Message sent when dialog is cancelled, boxed as messages are large.
+    on_cancel: Option<Box<Message>>,
+}
+
+impl Dialog {
+    /// Create a dialog of a kind.
+    fn new(window_id: window::Id, title: impl Into<String>, kind: Kind) -> Self {
+        Self {
+            id: DialogId::next(),
+            window_id,
+            title: title.into(),
+            body: String::new(),
+            kind,
+            on_cancel: None,
+        }
+    }
+
+    /// Create a dialog asking for confirmation of an action, labelled by action.
+    pub fn confirm(
+        window_id: window::Id,
+        title: impl Into<String>,
+        action: impl Into<String>,
+        on_confirm: Message,
+    ) -> Self {
+        Self::new(
+            window_id,
+            title,
+            Kind::Confirm {
+                action: action.into(),
+                on_confirm: Box::new(on_confirm),
+                destructive: false,
+            },
+        )
+    }
+
+    /// Create a dialog asking for text.
+    pub fn prompt(
+        window_id: window::Id,
+        title: impl Into<String>,
+        placeholder: impl Into<String>,
+        on_submit: impl Fn(String) -> Message + 'static,
+    ) -> Self {
+        Self::new(
+            window_id,
+            title,
+            Kind::Prompt {
+                value: String::new(),
+                placeholder: placeholder.into(),
+                action: tr("button-ok"),
+                on_submit: OnSubmit(Box::new(on_submit)),
+                validate: None,
+                browse: None,
+            },
+        )
+    }
+
+    /// Create a dialog asking to choose one of several options, the first option is the
+    /// default.
+    pub fn choose(
+        window_id: window::Id,
+        title: impl Into<String>,
+        options: Vec<String>,
+        on_choose: impl Fn(usize, bool) -> Message + 'static,
+    ) -> Self {
+        Self::new(
+            window_id,
+            title,
+            Kind::Choose {
+                options,
+                all: None,
+                on_choose: OnChoose(Box::new(on_choose)),
+            },
+        )
+    }
+
+    /// Create a dialog shown while an operation is in progress, cancelled by token if
+    /// given.
+    pub fn progress(
+        window_id: window::Id,
+        title: impl Into<String>,
+        cancel: Option<CancelToken>,
+    ) -> Self {
+        Self::new(
+            window_id,
+            title,
+            Kind::Progress {
+                cancel,
+                progress: None,
+            },
+        )
+    }
+
+    /// Set text explaining what is asked for.
+    #[must_use]
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Mark confirmed action as one which cannot be undone.
+    #[must_use]
+    pub const fn destructive(mut self) -> Self {
+        if let Kind::Confirm { destructive, .. } = &mut self.kind {
+            *destructive = true;
+        }
+        self
+    }
+
+    /// Let a choice apply to all later choices, which it does not by default.
+    #[must_use]
+    pub const fn apply_to_all(mut self) -> Self {
+        if let Kind::Choose { all, .. } = &mut self.kind {
+            *all = Some(false);
+        }
+        self
+    }
+
+    /// Set label of button submitting a prompt.
+    #[must_use]
+    pub fn action(mut self, label: impl Into<String>) -> Self {
+        if let Kind::Prompt { action, .. } = &mut self.kind {
+            *action = label.into();
+        }
+        self
+    }
+
+    /// Only let text accepted by function be submitted from a prompt.
+    #[must_use]
+    pub fn validate(mut self, f: impl Fn(&str) -> bool + 'static) -> Self {
+        if let Kind::Prompt { validate, .. } = &mut self.kind {
+            *validate = Some(Validate(Box::new(f)));
+        }
+        self
+    }
+
+    /// Show a button next to the text input of a prompt sending message when pressed,
+    /// such that a value may be picked and set by [Dialog::set_value].
+    #[must_use]
+    pub fn browse(mut self, message: Message) -> Self {
+        if let Kind::Prompt { browse, .. } = &mut self.kind {
+            *browse = Some(Box::new(message));
+        }
+        self
+    }
+
+    /// Set message sent when dialog is cancelled.
+    #[must_use]
+    pub fn on_cancel(mut self, message: Message) -> Self {
+        self.on_cancel = Some(Box::new(message));
+        self
+    }
+
+    /// Set text initially entered into prompt.
+    #[must_use]
+    pub fn value(mut self, text: impl Into<String>) -> Self {
+        if let Kind::Prompt { value, .. } = &mut self.kind {
+            *value = text.into();
+        }
+        self
+    }
+
+    /// Check if dialog is closed when answered, progress dialogs are closed by their
+    /// operation and prompts may only be answered with valid text.
+    pub fn is_answerable(&self) -> bool {
+        match &self.kind {
+            Kind::Prompt {
+                value,
+                validate: Some(validate),
+                ..
+            } => validate.0(value),
+            Kind::Progress { .. } => false,
+            Kind::Confirm { .. } | Kind::Prompt { .. } | Kind::Choose { .. } => true,
+        }
+    }
+
+    /// Check if dialog may be cancelled.
+    pub const fn is_cancellable(&self) -> bool {
+        !matches!(self.kind, Kind::Progress { cancel: None, .. })
+    }
+
+    /// Get message answering dialog, [None] for progress dialogs.
+    pub fn answer(self) -> Option<Message> {
+        self.answer_with(0)
+    }
+
+    /// Get message answering dialog with option at index of a choice, other dialogs are
+    /// answered as by [Dialog::answer].
+    pub fn answer_with(self, index: usize) -> Option<Message> {
+        match self.kind {
+            Kind::Confirm { on_confirm, .. } => Some(*on_confirm),
+            Kind::Prompt {
+                value, on_submit, ..
+            } => Some(on_submit.0(value)),
+            Kind::Choose { all, on_choose, .. } => Some(on_choose.0(index, all.unwrap_or(false))),
+            Kind::Progress { .. } => None,
+        }
+    }
+
+    /// Cancel dialog, cancelling the operation of a progress dialog. Returns the message
+    /// sent when cancelled, if any.
+    pub fn cancel(self) -> Option<Message> {
+        if let Kind::Progress {
+            cancel: Some(token),
+            ..
+        } = &self.kind
+        {
+            token.cancel();
+        }
+        self.on_cancel.map(|message| *message)
+    }
+
+    /// Set whether a choice applies to all later choices.
+    pub const fn set_all(&mut self, value: bool) {
+        if let Kind::Choose { all: Some(all), .. } = &mut self.kind {
+            *all = value;
+        }
+    }
+
+    /// Set text explaining what is asked for, or how far an operation has come.
+    pub fn set_body(&mut self, body: impl Into<String>) {
+        self.body = body.into();
+    }
+
+    /// Set fraction of the operation of a progress dialog done.
+    pub const fn set_progress(&mut self, fraction: f32) {
+        if let Kind::Progress { progress, .. } = &mut self.kind {
+            *progress = Some(fraction);
+        }
+    }
+
+    /// Set text entered into prompt.
+    pub fn set_value(&mut self, text: String) {
+        if let Kind::Prompt { value, .. } = &mut self.kind {
+            *value = text;
+        }
+    }
+
+    /// View dialog over a window, the window is dimmed and cannot be interacted with.
+    pub fn view(&self) -> Element<'_, Message> {
+        let id = self.id;
+        let answer = self.is_answerable().then_some(Message::AnswerDialog(id));
+        let cancel = self.is_cancellable().then(|| {
+            widget::button(widget::text(tr("button-cancel")))
+                .padding(3)
+                .style(widget::button::secondary)
+                .on_press(Message::CancelDialog(id))
+        });
+        let content: Element<'_, Message> = match &self.kind {
+            Kind::Confirm {
+                action,
+                destructive,
+                ..
+            } => widget::Row::new()
+                .spacing(3)
+                .push(
+                    widget::button(widget::text(action))
+                        .padding(3)
+                        .style(if *destructive {
+                            widget::button::danger
+                        } else {
+                            widget::button::primary
+                        })
+                        .on_press(Message::AnswerDialog(id)),
+                )
+                .push(cancel)
+                .into(),
+            Kind::Prompt {
+                value,
+                placeholder,
+                action,
+                browse,
+                ..
+            } => widget::Column::new()
+                .spacing(5)
+                .push(
+                    widget::Row::new()
+                        .spacing(3)
+                        .align_y(Center)
+                        .push(
+                            widget::text_input(placeholder, value)
+                                .id(input_id(id))
+                                .on_input(move |value| Message::DialogInput(id, value))
+                                .on_submit_maybe(answer.clone())
+                                .padding(3)
+                                .width(Fill),
+                        )
+                        .push(browse.as_ref().map(|message| {
+                            widget::button(widget::text(tr("button-browse")))
+                                .padding(3)
+                                .style(widget::button::secondary)
+                                .on_press(Message::clone(message))
+                        })),
+                )
+                .push(
+                    widget::Row::new()
+                        .spacing(3)
+                        .push(
+                            widget::button(widget::text(action))
+                                .padding(3)
+                                .on_press_maybe(answer),
+                        )
+                        .push(cancel),
+                )
+                .into(),
+            Kind::Choose { options, all, .. } => widget::Column::new()
+                .spacing(5)
+                .push(all.map(|all| {
+                    widget::checkbox(all)
+                        .label(tr("dialog-apply-to-all"))
+                        .on_toggle(move |all| Message::DialogToggleAll(id, all))
+                }))
+                .push(
+                    options
+                        .iter()
+                        .enumerate()
+                        .fold(widget::Row::new().spacing(3), |row, (index, option)| {
+                            row.push(
+                                widget::button(widget::text(option))
+                                    .padding(3)
+                                    .style(if index == 0 {
+                                        widget::button::primary
+                                    } else {
+                                        widget::button::secondary
+                                    })
+                                    .on_press(Message::ChooseDialog(id, index)),
+                            )
+                        })
+                        .push(cancel),
+                )
+                .into(),
+            Kind::Progress { progress, .. } => widget::Column::new()
+                .spacing(5)
+                .push(progress.map(|progress| widget::progress_bar(0.0..=1.0, progress).girth(8)))
+                .push(cancel)
+                .into(),
+        };
+        widget::Column::new()
+            .spacing(5)
+            .push(
+                widget::text(&self.title)
+                    .size(16)
+                    .wrapping(Wrapping::WordOrGlyph),
+            )
+            .push(
+                (!self.body.is_empty())
+                    .then(|| widget::text(&self.body).wrapping(Wrapping::WordOrGlyph)),
+            )
+            .push(content)
+            .pipe(widget::container)
+            .style(widget::container::bordered_box)
+            .padding(10)
+            .max_width(DIALOG_WIDTH)
+            .pipe(widget::container)
+            .center(Fill)
+            .style(|_theme| widget::container::Style {
+                background: Some(Color::BLACK.scale_alpha(0.4).into()),
+                ..widget::container::Style::default()
+            })
+            .pipe(widget::opaque)
+    }
+}
+
+/// Get id of text input of a prompt dialog, such that it may be focused.
+pub fn input_id(id: DialogId) -> widget::Id {
+    widget::Id::from(format!("dialog-{}", id.0))
+}
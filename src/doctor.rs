@@ -0,0 +1,250 @@
+//! Health checks of a profile and the environment it runs in, run by the doctor
+//! subcommand and on startup.
+
+use ::core::fmt::Display;
+use ::std::fs;
+
+use crate::{Settings, archive, catalogue::Catalogue, config, opds, temp};
+
+/// Check of the health of a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// Config file can be read.
+    Config,
+    /// Catalogue database can be opened and is not damaged.
+    Catalogue,
+    /// Cache directory can be written to.
+    Cache,
+    /// Optional programs and libraries are installed.
+    Dependencies,
+    /// Recently opened directories and archives are available.
+    Libraries,
+}
+
+impl Check {
+    /// All checks, in the order they are run.
+    pub const ALL: [Self; 5] = [
+        Self::Config,
+        Self::Catalogue,
+        Self::Cache,
+        Self::Dependencies,
+        Self::Libraries,
+    ];
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(match self {
+            Check::Config => "config",
+            Check::Catalogue => "catalogue",
+            Check::Cache => "cache",
+            Check::Dependencies => "dependencies",
+            Check::Libraries => "libraries",
+        })
+    }
+}
+
+/// Severity of a problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Application works, with some features unavailable.
+    Warning,
+    /// Application does not work as it should.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// Problem found by a check, along with how it may be fixed.
+#[derive(Debug, Clone)]
+pub struct Problem {
+    /// Check problem was found by.
+    pub check: Check,
+    /// Severity of problem.
+    pub severity: Severity,
+    /// Description of problem.
+    pub message: String,
+    /// Action fixing problem.
+    pub fix: String,
+}
+
+impl Problem {
+    /// Create a problem of given severity.
+    fn new(check: Check, severity: Severity, message: impl Display, fix: impl Display) -> Self {
+        Self {
+            check,
+            severity,
+            message: message.to_string(),
+            fix: fix.to_string(),
+        }
+    }
+
+    /// Log problem, along with its fix.
+    #[cfg(feature = "gui")]
+    pub fn log(&self) {
+        let Self {
+            check,
+            message,
+            fix,
+            ..
+        } = self;
+        match self.severity {
+            Severity::Warning => ::log::warn!("{check}: {message}\n{fix}"),
+            Severity::Error => ::log::error!("{check}: {message}\n{fix}"),
+        }
+    }
+}
+
+/// Run every check of profile, returning the problems found in the order of
+/// [Check::ALL].
+pub fn diagnose(xdg_dirs: &::xdg::BaseDirectories) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let settings = check_config(xdg_dirs, &mut problems);
+    let catalogue = check_catalogue(xdg_dirs, &mut problems);
+    check_cache(xdg_dirs, &mut problems);
+    check_dependencies(&settings, &mut problems);
+    if let Some(catalogue) = catalogue {
+        check_libraries(&catalogue, &mut problems);
+    }
+    problems
+}
+
+/// Check that the config file, if any, can be read, returning the settings it holds, or
+/// default settings.
+fn check_config(xdg_dirs: &::xdg::BaseDirectories, problems: &mut Vec<Problem>) -> Settings {
+    let Some(path) = config::find(xdg_dirs) else {
+        return Settings::default();
+    };
+    Settings::read(&path).unwrap_or_else(|err| {
+        problems.push(Problem::new(
+            Check::Config,
+            Severity::Error,
+            err,
+            "fix it with `arkiv-katalog config edit`, or replace it by default settings with \
+             `arkiv-katalog config reset`",
+        ));
+        Settings::default()
+    })
+}
+
+/// Check that the catalogue can be opened and is not damaged, returning it if it could
+/// be opened.
+fn check_catalogue(
+    xdg_dirs: &::xdg::BaseDirectories,
+    problems: &mut Vec<Problem>,
+) -> Option<Catalogue> {
+    let catalogue = match Catalogue::open(xdg_dirs) {
+        Ok(catalogue) => catalogue,
+        Err(err) => {
+            problems.push(Problem::new(
+                Check::Catalogue,
+                Severity::Error,
+                err,
+                "make the data directory writable, or move the catalogue aside such that a \
+                 new one is created",
+            ));
+            return None;
+        }
+    };
+    let fix = format!(
+        "restore {} from a backup, or move it aside such that a new one is created",
+        catalogue.path().display()
+    );
+    match catalogue.integrity_problems() {
+        Ok(found) if found.is_empty() => {}
+        Ok(found) => problems.push(Problem::new(
+            Check::Catalogue,
+            Severity::Error,
+            format!("catalogue is damaged\n{}", found.join("\n")),
+            fix,
+        )),
+        Err(err) => problems.push(Problem::new(Check::Catalogue, Severity::Error, err, fix)),
+    }
+    Some(catalogue)
+}
+
+/// Check that temporary files can be written to the cache directory.
+fn check_cache(xdg_dirs: &::xdg::BaseDirectories, problems: &mut Vec<Problem>) {
+    let fix = "make the cache directory writable, or use another one with `--cache-dir`";
+    let dir = match temp::dir(xdg_dirs) {
+        Ok(dir) => dir,
+        Err(err) => {
+            problems.push(Problem::new(
+                Check::Cache,
+                Severity::Error,
+                format!("could not create directory of temporary files\n{err}"),
+                fix,
+            ));
+            return;
+        }
+    };
+    let probe = temp::path(&dir, "doctor");
+    match fs::write(&probe, []) {
+        Ok(()) => fs::remove_file(&probe).unwrap_or_else(|err| {
+            ::log::warn!("could not remove {probe:?}\n{err}");
+        }),
+        Err(err) => problems.push(Problem::new(
+            Check::Cache,
+            Severity::Error,
+            format!("could not write to {}\n{err}", dir.display()),
+            fix,
+        )),
+    }
+}
+
+/// Check that optional programs and libraries are installed, and that programs needed
+/// by features in use are.
+fn check_dependencies(settings: &Settings, problems: &mut Vec<Problem>) {
+    if !archive::has_extractor() {
+        problems.push(Problem::new(
+            Check::Dependencies,
+            Severity::Warning,
+            "none of bsdtar, 7z or unrar is installed, rar and 7z archives cannot be \
+             converted or extracted",
+            "install libarchive, 7-Zip or unrar",
+        ));
+    }
+    #[cfg(feature = "pdf")]
+    if !archive::has_pdfium() {
+        problems.push(Problem::new(
+            Check::Dependencies,
+            Severity::Warning,
+            "pdfium could not be loaded, pdf documents cannot be read",
+            "install pdfium such that libpdfium is found by the system library loader",
+        ));
+    }
+    if !settings.opds_feeds.is_empty() && !opds::has_curl() {
+        problems.push(Problem::new(
+            Check::Dependencies,
+            Severity::Warning,
+            "curl is not installed, OPDS feeds cannot be browsed",
+            "install curl",
+        ));
+    }
+}
+
+/// Check that recently opened directories and archives are still available.
+fn check_libraries(catalogue: &Catalogue, problems: &mut Vec<Problem>) {
+    let recent = match catalogue.recent() {
+        Ok(recent) => recent,
+        Err(err) => {
+            err.log();
+            return;
+        }
+    };
+    for path in recent.iter().filter(|path| !path.exists()) {
+        problems.push(Problem::new(
+            Check::Libraries,
+            Severity::Warning,
+            format!("{} opened recently is not available", path.display()),
+            "mount the drive holding it, or open it from where it was moved to",
+        ));
+    }
+}
@@ -0,0 +1,453 @@
+//! Error types of application core.
+
+use ::std::{io, path::PathBuf, process::ExitCode};
+
+use crate::i18n::tr_args;
+
+/// Exit status of application, stable such that scripts may branch on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Status {
+    /// Application finished without errors.
+    Ok = 0,
+    /// Errors were found, such as unreadable archives.
+    ErrorsFound = 1,
+    /// Application was used incorrectly, used by clap for invalid arguments.
+    Usage = 2,
+    /// Files could not be read or written.
+    Io = 3,
+}
+
+impl From<Status> for ExitCode {
+    fn from(value: Status) -> Self {
+        ExitCode::from(value as u8)
+    }
+}
+
+/// Errors of application core.
+#[derive(Debug, ::thiserror::Error)]
+pub enum Error {
+    /// A directory or file could not be scanned.
+    #[error("could not scan {path:?}")]
+    Scan {
+        /// Path of directory.
+        path: PathBuf,
+        /// Cause of error.
+        #[source]
+        source: io::Error,
+    },
+    /// An archive could not be read.
+    #[error("could not read archive {path:?}")]
+    Archive {
+        /// Path of archive.
+        path: PathBuf,
+        /// Cause of error.
+        #[source]
+        source: ArchiveError,
+    },
+    /// A configuration or state file could not be loaded or saved.
+    #[error("could not {} {path:?}", if source.is_save() { "save" } else { "load" })]
+    Config {
+        /// Path of file.
+        path: PathBuf,
+        /// Cause of error.
+        #[source]
+        source: ConfigError,
+    },
+    /// The catalogue database could not be opened or used.
+    #[error("could not use catalogue {path:?}")]
+    Catalogue {
+        /// Path of database.
+        path: PathBuf,
+        /// Cause of error.
+        #[source]
+        source: CatalogueError,
+    },
+    /// Requested renderer is not available.
+    #[error("{renderer} rendering requires the {feature} feature to be enabled")]
+    RendererUnavailable {
+        /// Requested renderer.
+        renderer: &'static str,
+        /// Feature required by renderer.
+        feature: &'static str,
+    },
+    /// User interface could not be run.
+    #[error("user interface failed")]
+    Gui(#[source] Box<dyn ::core::error::Error + Send + Sync>),
+    /// Output could not be written.
+    #[error("could not write output")]
+    Output(#[source] io::Error),
+    /// Editor could not be started.
+    #[error("could not run editor {editor:?}")]
+    Editor {
+        /// Command of editor.
+        editor: String,
+        /// Cause of error.
+        #[source]
+        source: io::Error,
+    },
+    /// File could not be moved.
+    #[error("could not move {from:?} to {to:?}")]
+    Move {
+        /// Path of file.
+        from: PathBuf,
+        /// Path file was moved to.
+        to: PathBuf,
+        /// Cause of error.
+        #[source]
+        source: io::Error,
+    },
+    /// File could not be removed.
+    #[error("could not remove {path:?}")]
+    Remove {
+        /// Path of file.
+        path: PathBuf,
+        /// Cause of error.
+        #[source]
+        source: io::Error,
+    },
+    /// File could not be moved to trash.
+    #[error("could not move {path:?} to trash")]
+    Trash {
+        /// Path of file.
+        path: PathBuf,
+        /// Cause of error.
+        #[source]
+        source: io::Error,
+    },
+    /// Path template has a field that does not exist.
+    #[error("unknown field {{{field}}} in template {template:?}")]
+    Template {
+        /// Template given.
+        template: String,
+        /// Unknown field.
+        field: String,
+    },
+    /// External application could not be started.
+    #[error("could not start {application}")]
+    Launch {
+        /// Name of application.
+        application: String,
+        /// Cause of error.
+        #[source]
+        source: io::Error,
+    },
+    /// Directory of profile could not be removed.
+    #[error("could not remove profile directory {path:?}")]
+    RemoveProfile {
+        /// Path of directory.
+        path: PathBuf,
+        /// Cause of error.
+        #[source]
+        source: io::Error,
+    },
+    /// A database exported by another application could not be read.
+    #[error("could not read exported database {path:?}")]
+    Import {
+        /// Path of database.
+        path: PathBuf,
+        /// Cause of error.
+        #[source]
+        source: ::rusqlite::Error,
+    },
+    /// An OPDS feed, or a file linked by it, could not be loaded.
+    #[error("could not load {url}")]
+    Opds {
+        /// Url of feed or file.
+        url: String,
+        /// Cause of error.
+        #[source]
+        source: OpdsError,
+    },
+    /// A backup of a profile could not be written or restored.
+    #[error("could not use backup {path:?}")]
+    Backup {
+        /// Path of backup.
+        path: PathBuf,
+        /// Cause of error.
+        #[source]
+        source: BackupError,
+    },
+    /// An image could not be copied to the clipboard.
+    #[error("could not copy image to clipboard using {program}")]
+    Clipboard {
+        /// Program used to copy image.
+        program: &'static str,
+        /// Cause of error.
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl Error {
+    /// Get exit status of process when exiting due to error.
+    pub const fn status(&self) -> Status {
+        match self {
+            Error::Archive { .. } | Error::Gui(..) => Status::ErrorsFound,
+            Error::RendererUnavailable { .. } | Error::Template { .. } => Status::Usage,
+            Error::Scan { .. }
+            | Error::Config { .. }
+            | Error::Catalogue { .. }
+            | Error::Output(..)
+            | Error::Editor { .. }
+            | Error::Move { .. }
+            | Error::Remove { .. }
+            | Error::Trash { .. }
+            | Error::Launch { .. }
+            | Error::RemoveProfile { .. }
+            | Error::Import { .. }
+            | Error::Opds { .. }
+            | Error::Backup { .. }
+            | Error::Clipboard { .. } => Status::Io,
+        }
+    }
+
+    /// Log error along with the chain of its sources.
+    pub fn log(&self) {
+        let mut message = self.to_string();
+        let mut source = ::core::error::Error::source(self);
+        while let Some(err) = source {
+            message.push_str("\n  caused by: ");
+            message.push_str(&err.to_string());
+            source = err.source();
+        }
+        ::log::error!("{message}");
+    }
+
+    /// Get a short message describing error to users.
+    pub fn user_message(&self) -> String {
+        /// Get name of file at path.
+        fn name(path: &::std::path::Path) -> ::std::borrow::Cow<'_, str> {
+            path.file_name()
+                .unwrap_or(path.as_os_str())
+                .to_string_lossy()
+        }
+        match self {
+            Error::Scan { path, source } => tr_args(
+                match source.kind() {
+                    io::ErrorKind::NotFound => "error-folder-missing",
+                    io::ErrorKind::PermissionDenied => "error-folder-denied",
+                    _ => "error-folder-unreadable",
+                },
+                &[("name", &name(path))],
+            ),
+            Error::Archive { path, source } => tr_args(
+                "error-archive",
+                &[("name", &name(path)), ("reason", &source.to_string())],
+            ),
+            Error::Config { path, source } => tr_args(
+                "error-config",
+                &[
+                    ("action", if source.is_save() { "save" } else { "load" }),
+                    ("name", &name(path)),
+                    ("reason", &source.to_string()),
+                ],
+            ),
+            Error::Catalogue { source, .. } => {
+                tr_args("error-catalogue", &[("reason", &source.to_string())])
+            }
+            Error::Opds { source, .. } => tr_args("error-feed", &[("reason", &source.to_string())]),
+            Error::RendererUnavailable { .. }
+            | Error::Template { .. }
+            | Error::Gui(..)
+            | Error::Output(..)
+            | Error::Editor { .. }
+            | Error::Move { .. }
+            | Error::Remove { .. }
+            | Error::Trash { .. }
+            | Error::Launch { .. }
+            | Error::RemoveProfile { .. }
+            | Error::Import { .. }
+            | Error::Backup { .. }
+            | Error::Clipboard { .. } => {
+                let mut message = self.to_string();
+                if let Some(first) = message.get_mut(..1) {
+                    first.make_ascii_uppercase();
+                }
+                message
+            }
+        }
+    }
+}
+
+/// Errors when loading or saving configuration and state files.
+#[derive(Debug, ::thiserror::Error)]
+pub enum ConfigError {
+    /// File could not be read.
+    #[error("file could not be read")]
+    Read(#[source] io::Error),
+    /// File content is not valid.
+    #[error("file content is not valid")]
+    Parse(#[source] ::toml::de::Error),
+    /// Json file content is not valid.
+    #[error("file content is not valid")]
+    ParseJson(#[source] ::serde_json::Error),
+    /// Content could not be serialized.
+    #[error("content could not be serialized")]
+    Serialize(#[source] ::toml::ser::Error),
+    /// Content could not be serialized as json.
+    #[error("content could not be serialized")]
+    SerializeJson(#[source] ::serde_json::Error),
+    /// File could not be written.
+    #[error("file could not be written")]
+    Write(#[source] io::Error),
+}
+
+impl ConfigError {
+    /// Check if error occurred while saving.
+    pub const fn is_save(&self) -> bool {
+        matches!(
+            self,
+            ConfigError::Serialize(..) | ConfigError::SerializeJson(..) | ConfigError::Write(..)
+        )
+    }
+}
+
+/// Errors when opening or using the catalogue database.
+#[derive(Debug, ::thiserror::Error)]
+pub enum CatalogueError {
+    /// Directory of database could not be created.
+    #[error("directory could not be created")]
+    Create(#[source] io::Error),
+    /// Database could not be opened, queried or written to.
+    #[error("database query failed")]
+    Database(#[source] ::rusqlite::Error),
+}
+
+/// Errors when loading OPDS feeds and the files they link to.
+#[derive(Debug, ::thiserror::Error)]
+pub enum OpdsError {
+    /// Curl, which feeds are fetched with, could not be run.
+    #[error("curl could not be run")]
+    Curl(#[source] io::Error),
+    /// Feed or file could not be fetched.
+    #[error("{0}")]
+    Fetch(String),
+    /// Feed is not valid xml.
+    #[error("feed is not valid xml")]
+    Xml(#[source] ::roxmltree::Error),
+    /// Feed is not an Atom feed.
+    #[error("feed is not an atom feed")]
+    NotAtom,
+    /// Cached file could not be read.
+    #[error("cached file could not be read")]
+    Read(#[source] io::Error),
+    /// Downloaded file could not be written.
+    #[error("file could not be written")]
+    Write(#[source] io::Error),
+}
+
+/// Errors when backing up or restoring a profile.
+#[derive(Debug, ::thiserror::Error)]
+pub enum BackupError {
+    /// Backup or a file of profile could not be read.
+    #[error("file could not be read")]
+    Read(#[source] io::Error),
+    /// Backup could not be written.
+    #[error("file could not be written")]
+    Write(#[source] io::Error),
+    /// Backup is not a valid zip archive.
+    #[error("file is not a valid zip archive")]
+    Zip(#[from] ::zip::result::ZipError),
+    /// Backup contains no catalogue.
+    #[error("backup contains no catalogue")]
+    MissingCatalogue,
+    /// Catalogue of backup is not an intact database.
+    #[error("catalogue of backup is damaged")]
+    Damaged,
+    /// Config file of backup is invalid.
+    #[error("config file of backup is invalid")]
+    Config(#[source] ConfigError),
+    /// Profile is in use by a running instance.
+    #[error("profile is in use by a running instance, close it first")]
+    InUse,
+    /// Files of profile could not be replaced.
+    #[error("files of profile could not be replaced")]
+    Restore(#[source] io::Error),
+}
+
+/// Errors when reading archives.
+#[derive(Debug, ::thiserror::Error)]
+pub enum ArchiveError {
+    /// Archive format is not supported.
+    #[error("format is not supported")]
+    Unsupported,
+    /// Archive file could not be opened.
+    #[error("file could not be opened")]
+    Open(#[source] io::Error),
+    /// Archive is not a valid zip archive.
+    #[error("file is not a valid zip archive")]
+    Zip(#[source] ::zip::result::ZipError),
+    /// An entry of the archive could not be found.
+    #[error("{name:?} is missing")]
+    MissingEntry {
+        /// Name of entry.
+        name: String,
+        /// Cause of error.
+        #[source]
+        source: ::zip::result::ZipError,
+    },
+    /// An entry of the archive could not be read.
+    #[error("{name:?} could not be read")]
+    ReadEntry {
+        /// Name of entry.
+        name: String,
+        /// Cause of error.
+        #[source]
+        source: io::Error,
+    },
+    /// An xml document of the archive is not valid.
+    #[error("{name:?} is not valid xml")]
+    Xml {
+        /// Name of document.
+        name: String,
+        /// Cause of error.
+        #[source]
+        source: ::roxmltree::Error,
+    },
+    /// Epub document declares no package document.
+    #[error("no package document is declared")]
+    NoPackage,
+    /// Requested page does not exist.
+    #[error("page {0} does not exist")]
+    NoPage(usize),
+    /// A page could not be read.
+    #[error("page {index} could not be read")]
+    ReadPage {
+        /// Index of page.
+        index: usize,
+        /// Cause of error.
+        #[source]
+        source: Box<dyn ::core::error::Error + Send + Sync>,
+    },
+    /// Archive could not be written.
+    #[error("file could not be written")]
+    Write(#[source] io::Error),
+    /// Archive could not be extracted by an external tool.
+    #[error("archive could not be extracted")]
+    Extract(#[source] io::Error),
+    /// Directory packed into an archive contains no images.
+    #[error("directory contains no images")]
+    NoImages,
+    /// Extraction of archive was cancelled.
+    #[error("extraction was cancelled")]
+    Cancelled,
+    /// A page image could not be decoded.
+    #[error("image could not be decoded")]
+    Decode(#[source] Box<dyn ::core::error::Error + Send + Sync>),
+    /// Pixel data of a rendered page does not match its size.
+    #[error("pixel data does not match size {width}x{height}")]
+    PixelSize {
+        /// Width of page.
+        width: u32,
+        /// Height of page.
+        height: u32,
+    },
+    /// Pdfium library could not be loaded.
+    #[cfg(feature = "pdf")]
+    #[error("pdfium is not available")]
+    PdfiumUnavailable,
+    /// Pdf document could not be loaded.
+    #[cfg(feature = "pdf")]
+    #[error("file is not a valid pdf document")]
+    Pdf(#[source] ::pdfium_render::prelude::PdfiumError),
+}
@@ -0,0 +1,160 @@
+//! Locale aware formatting of numbers, file sizes, dates and durations.
+
+use ::core::time::Duration;
+use ::std::{sync::LazyLock, time::SystemTime};
+
+use crate::{
+    SizeUnits,
+    i18n::{tr, tr_args},
+};
+
+impl SizeUnits {
+    /// Get base and unit suffixes of units.
+    const fn units(self) -> (f64, [&'static str; 5]) {
+        match self {
+            SizeUnits::Binary => (1024.0, ["B", "KiB", "MiB", "GiB", "TiB"]),
+            SizeUnits::Decimal => (1000.0, ["B", "kB", "MB", "GB", "TB"]),
+        }
+    }
+}
+
+/// Number and date conventions of a locale.
+#[derive(Debug, Clone, Copy)]
+struct Locale {
+    /// Separator between groups of thousands.
+    thousands: &'static str,
+    /// Decimal separator.
+    decimal: char,
+    /// Strftime format of dates with time.
+    date: &'static str,
+}
+
+impl Locale {
+    /// Locale used when the system locale is unknown.
+    const DEFAULT: Self = Self::new(",", '.', "%Y-%m-%d %H:%M");
+
+    /// Create a new locale.
+    const fn new(thousands: &'static str, decimal: char, date: &'static str) -> Self {
+        Self {
+            thousands,
+            decimal,
+            date,
+        }
+    }
+
+    /// Get locale of a language tag such as `en-US` or `sv_SE.UTF-8`.
+    fn from_tag(tag: &str) -> Self {
+        let mut parts = tag.split(['-', '_', '.', '@']);
+        let language = parts.next().unwrap_or_default();
+        let region = parts.next().unwrap_or_default();
+        match language {
+            "en" if matches!(region, "US" | "PH") => Self::new(",", '.', "%m/%d/%Y %I:%M %p"),
+            "en" => Self::new(",", '.', "%d/%m/%Y %H:%M"),
+            "ja" | "zh" | "ko" => Self::new(",", '.', "%Y/%m/%d %H:%M"),
+            "de" | "da" | "tr" => Self::new(".", ',', "%d.%m.%Y %H:%M"),
+            "nl" => Self::new(".", ',', "%d-%m-%Y %H:%M"),
+            "es" | "it" | "pt" | "el" | "id" => Self::new(".", ',', "%d/%m/%Y %H:%M"),
+            "fr" => Self::new("\u{202f}", ',', "%d/%m/%Y %H:%M"),
+            "sv" | "lt" => Self::new("\u{a0}", ',', "%Y-%m-%d %H:%M"),
+            "nb" | "nn" | "no" | "fi" | "ru" | "uk" | "pl" | "cs" | "sk" | "et" | "lv" | "bg" => {
+                Self::new("\u{a0}", ',', "%d.%m.%Y %H:%M")
+            }
+            _ => Self::DEFAULT,
+        }
+    }
+}
+
+/// Locale of system, detected once.
+static LOCALE: LazyLock<Locale> = LazyLock::new(|| {
+    ::sys_locale::get_locale().map_or(Locale::DEFAULT, |tag| Locale::from_tag(&tag))
+});
+
+/// Format a count with locale thousands separators.
+pub fn number(value: usize) -> String {
+    let digits = value.to_string();
+    let mut formatted = String::with_capacity(digits.len() * 2);
+    for (index, digit) in digits.chars().enumerate() {
+        if index != 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push_str(LOCALE.thousands);
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+/// Format a file size in given units, with one decimal for sizes of a kilobyte or more.
+pub fn size(bytes: u64, units: SizeUnits) -> String {
+    let (base, suffixes) = units.units();
+    let mut value = bytes as f64;
+    let mut suffix = suffixes[0];
+    for next in &suffixes[1..] {
+        if value < base {
+            break;
+        }
+        value /= base;
+        suffix = next;
+    }
+    if suffix == suffixes[0] {
+        format!("{bytes} {suffix}")
+    } else {
+        format!("{value:.1} {suffix}").replace('.', LOCALE.decimal.encode_utf8(&mut [0; 4]))
+    }
+}
+
+/// Format a point in time relative to now, such as "3 days ago".
+pub fn relative(time: SystemTime, now: SystemTime) -> String {
+    /// Seconds in a minute.
+    const MINUTE: u64 = 60;
+    /// Seconds in an hour.
+    const HOUR: u64 = 60 * MINUTE;
+    /// Seconds in a day.
+    const DAY: u64 = 24 * HOUR;
+    /// Seconds in a week.
+    const WEEK: u64 = 7 * DAY;
+    /// Seconds in an average month.
+    const MONTH: u64 = 2_629_746;
+    /// Seconds in an average year.
+    const YEAR: u64 = 12 * MONTH;
+
+    // Times in the future, from clock skew or files from other systems, are shown as now.
+    let seconds = now.duration_since(time).unwrap_or_default().as_secs();
+    let (count, unit) = match seconds {
+        ..MINUTE => return tr("format-just-now"),
+        MINUTE..HOUR => (seconds / MINUTE, "format-minutes-ago"),
+        HOUR..DAY => (seconds / HOUR, "format-hours-ago"),
+        DAY..WEEK => (seconds / DAY, "format-days-ago"),
+        WEEK..MONTH => (seconds / WEEK, "format-weeks-ago"),
+        MONTH..YEAR => (seconds / MONTH, "format-months-ago"),
+        _ => (seconds / YEAR, "format-years-ago"),
+    };
+    let count = usize::try_from(count).unwrap_or(usize::MAX);
+    if count == 1 && unit == "format-days-ago" {
+        tr("format-yesterday")
+    } else {
+        tr_args(unit, &[("count", &number(count))])
+    }
+}
+
+/// Format an estimated duration in hours and minutes, such as "1 h 20 min".
+pub fn duration(duration: Duration) -> String {
+    let minutes = duration.as_secs().div_ceil(60);
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => tr("format-under-a-minute"),
+        (0, minutes) => tr_args("format-minutes", &[("minutes", &minutes.to_string())]),
+        (hours, 0) => tr_args("format-hours", &[("hours", &hours.to_string())]),
+        (hours, minutes) => tr_args(
+            "format-hours-minutes",
+            &[
+                ("hours", &hours.to_string()),
+                ("minutes", &minutes.to_string()),
+            ],
+        ),
+    }
+}
+
+/// Format a point in time as a local date and time, [None] if it is out of range.
+pub fn date(time: SystemTime) -> Option<String> {
+    let timestamp = ::jiff::Timestamp::try_from(time).ok()?;
+    let date = timestamp.to_zoned(::jiff::tz::TimeZone::system());
+    Some(date.strftime(LOCALE.date).to_string())
+}
@@ -0,0 +1,165 @@
+//! Thumbnails written according to the freedesktop thumbnail specification, such that
+//! file managers show the same covers as application.
+
+use ::std::{
+    ffi::OsString,
+    fs::{self, DirBuilder},
+    io::{self, Write},
+    os::unix::{
+        ffi::{OsStrExt, OsStringExt},
+        fs::{DirBuilderExt, OpenOptionsExt},
+    },
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use ::md5::{Digest, Md5};
+
+use crate::{archive::Page, temp};
+
+/// Directories of thumbnail sizes written, with the size thumbnails fit within.
+const SIZES: [(&str, u32); 2] = [("large", 256), ("x-large", MAX_SIZE)];
+
+/// Size of square the largest thumbnails written fit within, covers passed to
+/// [write_thumbnails] should be at least this large.
+pub const MAX_SIZE: u32 = 512;
+
+/// Directories thumbnails are written to.
+#[derive(Debug, Clone)]
+pub struct ThumbnailDirs {
+    /// Freedesktop thumbnail directory.
+    pub thumbnails: Arc<Path>,
+    /// Directory of temporary files of profile.
+    pub temp: Arc<Path>,
+}
+
+/// Escape bytes of a path not allowed in uri paths.
+pub fn escape_path(path: &Path) -> String {
+    let mut escaped = String::new();
+    for &byte in path.as_os_str().as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.!~*'()/:@&=+$,".contains(&byte) {
+            escaped.push(char::from(byte));
+        } else {
+            escaped.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    escaped
+}
+
+/// Get file uri of an absolute path, escaping bytes not allowed in uri paths.
+fn file_uri(path: &Path) -> String {
+    format!("file://{}", escape_path(path))
+}
+
+/// Get path of a file uri, decoding percent encoded bytes. Both `file:///path` and the
+/// `file:/path` form written by Java applications are accepted.
+pub fn file_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file:")?.as_bytes();
+    // Uris may name a host, only paths on this host are supported.
+    let start = match encoded.strip_prefix(b"//") {
+        Some(authority) => authority.iter().position(|&byte| byte == b'/')? + 2,
+        None if encoded.starts_with(b"/") => 0,
+        None => return None,
+    };
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut rest = &encoded[start..];
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%'
+            && let Some(hex) = tail.get(..2)
+            && let Ok(hex) = ::core::str::from_utf8(hex)
+            && let Ok(decoded) = u8::from_str_radix(hex, 16)
+        {
+            bytes.push(decoded);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    Some(PathBuf::from(OsString::from_vec(bytes)))
+}
+
+/// Write cover of archive at path as thumbnails in thumbnail directory, thumbnails newer
+/// than the archive are kept.
+///
+/// # Errors
+/// If the archive cannot be found or thumbnails cannot be written.
+pub fn write_thumbnails(dirs: &ThumbnailDirs, path: &Path, cover: &Page) -> io::Result<()> {
+    let Page::Rgba {
+        width,
+        height,
+        pixels,
+    } = cover
+    else {
+        return Ok(());
+    };
+    let path = ::std::path::absolute(path)?;
+    let metadata = fs::metadata(&path)?;
+    let modified = metadata.modified()?;
+    let uri = file_uri(&path);
+    let name = md5(uri.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    let image = ::image::RgbaImage::from_raw(*width, *height, pixels.to_vec())
+        .ok_or_else(|| io::Error::other("cover does not match its size"))?;
+
+    for (dir, size) in SIZES {
+        let dir = dirs.thumbnails.join(dir);
+        let thumbnail = dir.join(format!("{name}.png"));
+        if fs::metadata(&thumbnail)
+            .and_then(|thumbnail| thumbnail.modified())
+            .is_ok_and(|written| written >= modified)
+        {
+            continue;
+        }
+        DirBuilder::new().recursive(true).mode(0o700).create(&dir)?;
+
+        let image = if image.width() > size || image.height() > size {
+            ::image::imageops::thumbnail(&image, size, size)
+        } else {
+            image.clone()
+        };
+        let mut png = Vec::new();
+        let mut encoder = ::png::Encoder::new(&mut png, image.width(), image.height());
+        encoder.set_color(::png::ColorType::Rgba);
+        encoder.set_depth(::png::BitDepth::Eight);
+        let mtime = modified
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        for (key, value) in [
+            ("Thumb::URI", uri.clone()),
+            ("Thumb::MTime", mtime.to_string()),
+            ("Thumb::Size", metadata.len().to_string()),
+            ("Software", "arkiv-katalog".to_owned()),
+        ] {
+            encoder.add_text_chunk(key.to_owned(), value)?;
+        }
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(image.as_raw())?;
+        writer.finish()?;
+
+        // Written to a temporary file first such that readers never see partial files.
+        let temporary = temp::path(&dirs.temp, &format!("{name}.png"));
+        let written = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&temporary)
+            .and_then(|mut file| file.write_all(&png));
+        if let Err(err) = written {
+            _ = fs::remove_file(&temporary);
+            return Err(err);
+        }
+        temp::persist(&temporary, &thumbnail)?;
+    }
+    Ok(())
+}
+
+/// Compute md5 digest of data, as used to name thumbnails.
+pub fn md5(data: &[u8]) -> [u8; 16] {
+    Md5::digest(data).into()
+}
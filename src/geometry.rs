@@ -0,0 +1,136 @@
+//! Persistence of window geometry.
+
+use ::std::{collections::BTreeMap, path::PathBuf};
+
+use ::iced::{Point, Size, window};
+use ::serde::{Deserialize, Serialize};
+
+use crate::{
+    Settings,
+    error::{ConfigError, Error},
+};
+
+/// Name of state file geometry is stored in.
+const STATE_FILE: &str = "windows.toml";
+
+/// Kinds of windows, geometry is remembered per kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WindowKind {
+    /// Main window.
+    Main,
+    /// Settings window.
+    Settings,
+    /// Reader window.
+    Reader,
+    /// Home window.
+    Home,
+    /// Undo history window.
+    History,
+    /// Window listing keyboard shortcuts.
+    Help,
+    /// About window.
+    About,
+    /// Statistics window.
+    Stats,
+    /// Calendar window.
+    Calendar,
+    /// Window of archives moved to trash.
+    Trash,
+    /// Metadata editor window.
+    Metadata,
+}
+
+/// Geometry of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Geometry {
+    /// Width of window.
+    pub width: f32,
+    /// Height of window.
+    pub height: f32,
+    /// Position of window, not known on all platforms.
+    pub position: Option<(f32, f32)>,
+    /// Window is maximized.
+    pub maximized: bool,
+}
+
+impl Geometry {
+    /// Create geometry for a window of given size.
+    pub const fn new(size: Size) -> Self {
+        Self {
+            width: size.width,
+            height: size.height,
+            position: None,
+            maximized: false,
+        }
+    }
+}
+
+/// Remembered geometry of window kinds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Geometries {
+    /// Geometry of most recently closed window of each kind.
+    windows: BTreeMap<WindowKind, Geometry>,
+}
+
+impl Geometries {
+    /// Load remembered geometry from state directory, errors are logged and result in
+    /// nothing being remembered.
+    pub fn load(xdg_dirs: &::xdg::BaseDirectories) -> Self {
+        let Some(path) = xdg_dirs.find_state_file(STATE_FILE) else {
+            return Self::default();
+        };
+        ::std::fs::read_to_string(&path)
+            .map_err(ConfigError::Read)
+            .and_then(|content| ::toml::from_str(&content).map_err(ConfigError::Parse))
+            .unwrap_or_else(|source| {
+                Error::Config { path, source }.log();
+                Self::default()
+            })
+    }
+
+    /// Save remembered geometry to state directory.
+    ///
+    /// # Errors
+    /// If the state file cannot be written.
+    pub fn save(&self, xdg_dirs: &::xdg::BaseDirectories) -> Result<(), Error> {
+        let path = xdg_dirs
+            .place_state_file(STATE_FILE)
+            .map_err(|source| Error::Config {
+                path: PathBuf::from(STATE_FILE),
+                source: ConfigError::Write(source),
+            })?;
+        ::toml::to_string(self)
+            .map_err(ConfigError::Serialize)
+            .and_then(|content| ::std::fs::write(&path, content).map_err(ConfigError::Write))
+            .map_err(|source| Error::Config { path, source })
+    }
+
+    /// Remember geometry of a window kind.
+    pub fn insert(&mut self, kind: WindowKind, geometry: Geometry) {
+        self.windows.insert(kind, geometry);
+    }
+
+    /// Apply remembered geometry of window kind to window settings, as allowed by settings.
+    pub fn apply(
+        &self,
+        kind: WindowKind,
+        settings: &Settings,
+        mut window_settings: window::Settings,
+    ) -> window::Settings {
+        let Some(geometry) = self.windows.get(&kind) else {
+            return window_settings;
+        };
+        if settings.remember_window_size {
+            window_settings.size = Size::new(geometry.width, geometry.height);
+            window_settings.maximized = geometry.maximized;
+        }
+        if settings.remember_window_position
+            && let Some((x, y)) = geometry.position
+        {
+            window_settings.position = window::Position::Specific(Point::new(x, y));
+        }
+        window_settings
+    }
+}
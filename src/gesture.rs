@@ -0,0 +1,440 @@
+//! [Gestures] impl, zooming, panning and turning pages of readers by touch and touchpad.
+
+use ::core::time::Duration;
+use ::std::time::Instant;
+
+use ::derive_more::IsVariant;
+use ::hashbrown::HashMap;
+use ::iced::{
+    Point, Rectangle, Size, Vector,
+    mouse::ScrollDelta,
+    touch::{self, Finger},
+};
+
+/// Largest zoom of a page.
+const MAX_ZOOM: f32 = 8.0;
+
+/// Zoom change per line scrolled by a mouse wheel while Ctrl is held.
+const WHEEL_ZOOM: f32 = 1.25;
+
+/// Pixels scrolled by a touchpad while Ctrl is held doubling zoom.
+const PIXELS_PER_DOUBLING: f32 = 200.0;
+
+/// Pixels panned per line scrolled by a mouse wheel.
+const LINE_HEIGHT: f32 = 40.0;
+
+/// Distance in pixels a finger may travel before a touch is no longer a tap.
+const TAP_SLOP: f32 = 10.0;
+
+/// Rate panning slows down at once fingers are lifted, per second.
+const FRICTION: f32 = 4.0;
+
+/// Speed in pixels per second below which panning stops.
+const MIN_GLIDE_SPEED: f32 = 30.0;
+
+/// Fingers resting longer than this before being lifted do not keep panning.
+const MAX_GLIDE_REST: Duration = Duration::from_millis(100);
+
+/// Pause in touchpad scrolling after which a swipe is taken to have ended, as fingers
+/// lifting from a touchpad are not reported.
+const SWIPE_GAP: Duration = Duration::from_millis(250);
+
+/// Interval panning is advanced at once fingers are lifted.
+pub const TICK: Duration = Duration::from_millis(16);
+
+/// Page turn requested by a gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IsVariant)]
+pub enum Turn {
+    /// Turn to the next page.
+    Forward,
+    /// Turn to the previous page.
+    Back,
+}
+
+/// Horizontal touchpad swipe, turning once per swipe when scrolled far enough sideways.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Swipe {
+    /// Horizontal scroll of the current swipe, reset once it turns.
+    travel: f32,
+    /// The current swipe has turned, further scroll is ignored until it ends.
+    turned: bool,
+    /// Time of the last scroll of the current swipe.
+    scrolled_at: Option<Instant>,
+}
+
+impl Swipe {
+    /// Touchpad scrolled by the given pixels, giving a turn once it has scrolled the
+    /// given distance sideways. A swipe turns at most once, such that jitter as fingers
+    /// lift does not turn back, and ends once scrolling pauses or turns vertical.
+    pub fn scrolled(&mut self, x: f32, y: f32, distance: f32, now: Instant) -> Option<Turn> {
+        if self
+            .scrolled_at
+            .replace(now)
+            .is_none_or(|scrolled_at| now.saturating_duration_since(scrolled_at) > SWIPE_GAP)
+        {
+            self.reset();
+        }
+        if distance == 0.0 || y.abs() > x.abs() {
+            self.reset();
+            return None;
+        }
+        if self.turned {
+            return None;
+        }
+
+        self.travel += x;
+        let turn = if self.travel >= distance {
+            Turn::Back
+        } else if self.travel <= -distance {
+            Turn::Forward
+        } else {
+            return None;
+        };
+        self.travel = 0.0;
+        self.turned = true;
+        Some(turn)
+    }
+
+    /// End the current swipe, such as when the pointer leaves what is swiped.
+    pub const fn reset(&mut self) {
+        self.travel = 0.0;
+        self.turned = false;
+    }
+}
+
+/// Two finger pinch on a touch screen, stepping once per distance the fingers spread or
+/// close.
+#[derive(Debug, Clone, Default)]
+pub struct Pinch {
+    /// Positions of fingers touching the window.
+    fingers: HashMap<Finger, Point>,
+    /// Distance between fingers as of the last step, [None] unless two fingers touch.
+    spread: Option<f32>,
+}
+
+impl Pinch {
+    /// Get distance between fingers if exactly two touch the window.
+    fn spread(&self) -> Option<f32> {
+        let mut fingers = self.fingers.values();
+        match (fingers.next(), fingers.next(), fingers.next()) {
+            (Some(first), Some(second), None) => Some(first.distance(*second)),
+            _ => None,
+        }
+    }
+
+    /// Handle a touch event, giving the amount of steps fingers spread, negative if they
+    /// closed, by the given distance per step.
+    pub fn touched(&mut self, event: touch::Event, distance: f32) -> i32 {
+        match event {
+            touch::Event::FingerPressed { id, position } => {
+                self.fingers.insert(id, position);
+            }
+            touch::Event::FingerMoved { id, position } => {
+                if let Some(finger) = self.fingers.get_mut(&id) {
+                    *finger = position;
+                }
+                if let Some(start) = self.spread
+                    && let Some(spread) = self.spread()
+                    && distance > 0.0
+                {
+                    let steps = ((spread - start) / distance).trunc();
+                    self.spread = Some(start + steps * distance);
+                    return steps as i32;
+                }
+                return 0;
+            }
+            touch::Event::FingerLifted { id, .. } | touch::Event::FingerLost { id, .. } => {
+                self.fingers.remove(&id);
+            }
+        }
+        self.spread = self.spread();
+        0
+    }
+}
+
+/// Zoom and pan of a reader page, along with touches on it.
+#[derive(Debug, Clone)]
+pub struct Gestures {
+    /// Width and height of page in pixels, pages of unknown size are not zoomed.
+    page: Option<Size>,
+    /// Size of area page is shown in as of the last press or scroll on it.
+    area: Size,
+    /// Zoom relative to the page fit within its area.
+    zoom: f32,
+    /// Center of view relative to center of page, in fractions of page size.
+    center: Vector,
+    /// Panning speed kept once fingers are lifted, in pixels per second.
+    velocity: Vector,
+    /// Time of the last pan, or of the last step of panning after fingers were lifted.
+    moved_at: Option<Instant>,
+    /// Positions of fingers touching the window.
+    fingers: HashMap<Finger, Point>,
+    /// Position the only finger touching the window started at, cleared by pinches.
+    origin: Option<Point>,
+    /// Distance between fingers and zoom as the current pinch started.
+    pinch: Option<(f32, f32)>,
+    /// Horizontal touchpad swipe turning pages.
+    swipe: Swipe,
+    /// Current press has moved too far to be a tap, or was a pinch.
+    moved: bool,
+}
+
+impl Default for Gestures {
+    fn default() -> Self {
+        Self {
+            page: None,
+            area: Size::ZERO,
+            zoom: 1.0,
+            center: Vector::ZERO,
+            velocity: Vector::ZERO,
+            moved_at: None,
+            fingers: HashMap::new(),
+            origin: None,
+            pinch: None,
+            swipe: Swipe::default(),
+            moved: false,
+        }
+    }
+}
+
+impl Gestures {
+    /// Show a page of the given width and height, resetting zoom and pan. Fingers
+    /// touching the window are kept.
+    pub fn set_page(&mut self, page: Option<(u32, u32)>) {
+        self.page = page.map(|(width, height)| Size::new(width as f32, height as f32));
+        self.zoom = 1.0;
+        self.center = Vector::ZERO;
+        self.velocity = Vector::ZERO;
+    }
+
+    /// Get region of page shown, [None] if the whole page is shown.
+    pub fn crop(&self) -> Option<Rectangle<u32>> {
+        let page = self.page.filter(|_| self.zoom > 1.0)?;
+        let width = page.width / self.zoom;
+        let height = page.height / self.zoom;
+        Some(Rectangle {
+            x: ((0.5 + self.center.x) * page.width - width / 2.0).max(0.0) as u32,
+            y: ((0.5 + self.center.y) * page.height - height / 2.0).max(0.0) as u32,
+            width: width.ceil() as u32,
+            height: height.ceil() as u32,
+        })
+    }
+
+    /// Panning continues after fingers were lifted.
+    pub fn is_gliding(&self) -> bool {
+        self.velocity != Vector::ZERO
+    }
+
+    /// The current press is a tap, neither moving nor pinching, such that it turns the
+    /// page when released.
+    pub const fn is_tap(&self) -> bool {
+        !self.moved
+    }
+
+    /// Get size of page fit within its area at current zoom.
+    fn zoomed_size(&self) -> Option<Size> {
+        let page = self.page?;
+        let fit = (self.area.width / page.width).min(self.area.height / page.height) * self.zoom;
+        (fit.is_finite() && fit > 0.0).then(|| Size::new(page.width * fit, page.height * fit))
+    }
+
+    /// Move view as if dragging the page the given distance, keeping it within the page.
+    fn pan(&mut self, delta: Vector) {
+        let Some(size) = self.zoomed_size() else {
+            return;
+        };
+        let limit = 0.5 - 0.5 / self.zoom;
+        self.center = Vector::new(
+            (self.center.x - delta.x / size.width).clamp(-limit, limit),
+            (self.center.y - delta.y / size.height).clamp(-limit, limit),
+        );
+    }
+
+    /// Set zoom, keeping the view within the page.
+    fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.clamp(1.0, MAX_ZOOM);
+        let limit = 0.5 - 0.5 / self.zoom;
+        self.center = Vector::new(
+            self.center.x.clamp(-limit, limit),
+            self.center.y.clamp(-limit, limit),
+        );
+    }
+
+    /// Get distance between the first two fingers touching the window and the point
+    /// between them.
+    fn spread(&self) -> Option<(f32, Point)> {
+        let mut fingers = self.fingers.values();
+        let (first, second) = (fingers.next()?, fingers.next()?);
+        Some((
+            first.distance(*second),
+            Point::new((first.x + second.x) / 2.0, (first.y + second.y) / 2.0),
+        ))
+    }
+
+    /// Page shown in an area of the given size was pressed, by the mouse or a finger.
+    pub fn pressed(&mut self, area: Size) {
+        self.area = area;
+        self.velocity = Vector::ZERO;
+        // Further fingers of a pinch press the page as well.
+        if self.fingers.len() <= 1 && self.pinch.is_none() {
+            self.moved = false;
+        }
+    }
+
+    /// A finger touched the window, a second finger starts a pinch.
+    pub fn finger_pressed(&mut self, finger: Finger, position: Point) {
+        self.fingers.insert(finger, position);
+        self.velocity = Vector::ZERO;
+        self.moved_at = None;
+        match self.fingers.len() {
+            1 => self.origin = Some(position),
+            2 => {
+                self.pinch = self.spread().map(|(spread, _)| (spread, self.zoom));
+                self.origin = None;
+                self.moved = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// A finger moved, panning a zoomed page, or zooming and panning it if pinched.
+    pub fn finger_moved(&mut self, finger: Finger, position: Point, now: Instant) {
+        let before = self.spread();
+        let Some(previous) = self
+            .fingers
+            .get_mut(&finger)
+            .map(|current| ::core::mem::replace(current, position))
+        else {
+            return;
+        };
+        if self
+            .origin
+            .is_some_and(|origin| origin.distance(position) > TAP_SLOP)
+        {
+            self.moved = true;
+        }
+
+        if let Some((start_spread, start_zoom)) = self.pinch
+            && let Some((_, before)) = before
+            && let Some((spread, midpoint)) = self.spread()
+        {
+            self.set_zoom(start_zoom * spread / start_spread.max(1.0));
+            self.pan(midpoint - before);
+        } else if self.zoom > 1.0 {
+            let delta = position - previous;
+            self.pan(delta);
+            if let Some(moved_at) = self.moved_at {
+                let elapsed = now.saturating_duration_since(moved_at).as_secs_f32();
+                if elapsed > 0.0 {
+                    // Smoothed, as single moves are uneven.
+                    self.velocity = (self.velocity + delta * (1.0 / elapsed)) * 0.5;
+                }
+            }
+            self.moved_at = Some(now);
+        }
+    }
+
+    /// A finger was lifted, giving a page turn if it ended a swipe of the given distance
+    /// across an unzoomed page. Panning continues if the finger was moving.
+    pub fn finger_lifted(
+        &mut self,
+        finger: Finger,
+        position: Point,
+        swipe_distance: f32,
+        now: Instant,
+    ) -> Option<Turn> {
+        self.fingers.remove(&finger)?;
+        if self.fingers.len() < 2 {
+            self.pinch = None;
+        }
+        if !self.fingers.is_empty() {
+            self.velocity = Vector::ZERO;
+            return None;
+        }
+
+        let resting = self
+            .moved_at
+            .is_none_or(|moved_at| now.saturating_duration_since(moved_at) > MAX_GLIDE_REST);
+        if resting || self.velocity.x.hypot(self.velocity.y) < MIN_GLIDE_SPEED {
+            self.velocity = Vector::ZERO;
+        }
+        self.moved_at = Some(now);
+
+        let travel = position - self.origin.take()?;
+        (self.zoom <= 1.0
+            && swipe_distance > 0.0
+            && travel.x.abs() >= swipe_distance
+            && travel.x.abs() > travel.y.abs())
+        .then_some(if travel.x < 0.0 {
+            Turn::Forward
+        } else {
+            Turn::Back
+        })
+    }
+
+    /// A touch was canceled, such as by the window losing focus.
+    pub fn finger_lost(&mut self, finger: Finger) {
+        self.fingers.remove(&finger);
+        if self.fingers.len() < 2 {
+            self.pinch = None;
+        }
+        self.origin = None;
+        self.velocity = Vector::ZERO;
+    }
+
+    /// Mouse wheel or touchpad scrolled over page shown in an area of the given size.
+    /// Zooms if requested, pans a zoomed page, and otherwise gives a page turn once a
+    /// touchpad has swiped the given distance sideways.
+    pub fn scrolled(
+        &mut self,
+        area: Size,
+        delta: ScrollDelta,
+        zoom: bool,
+        swipe_distance: f32,
+        now: Instant,
+    ) -> Option<Turn> {
+        self.area = area;
+        self.velocity = Vector::ZERO;
+        let (delta, is_wheel) = match delta {
+            ScrollDelta::Lines { x, y } => (Vector::new(x, y) * LINE_HEIGHT, true),
+            ScrollDelta::Pixels { x, y } => (Vector::new(x, y), false),
+        };
+
+        if zoom {
+            let factor = if is_wheel {
+                WHEEL_ZOOM.powf(delta.y / LINE_HEIGHT)
+            } else {
+                2.0_f32.powf(delta.y / PIXELS_PER_DOUBLING)
+            };
+            self.set_zoom(self.zoom * factor);
+            return None;
+        }
+        if self.zoom > 1.0 {
+            self.pan(delta);
+            return None;
+        }
+        if is_wheel {
+            self.swipe.reset();
+            return None;
+        }
+        self.swipe.scrolled(delta.x, delta.y, swipe_distance, now)
+    }
+
+    /// Continue panning after fingers were lifted, slowing down until stopped or an
+    /// edge of the page is reached.
+    pub fn glide(&mut self, now: Instant) {
+        let Some(moved_at) = self.moved_at.replace(now) else {
+            self.velocity = Vector::ZERO;
+            return;
+        };
+        let elapsed = now.saturating_duration_since(moved_at).as_secs_f32();
+        let center = self.center;
+        self.pan(self.velocity * elapsed);
+        self.velocity *= (-FRICTION * elapsed).exp();
+        if self.velocity.x.hypot(self.velocity.y) < MIN_GLIDE_SPEED
+            || (elapsed > 0.0 && self.center == center)
+        {
+            self.velocity = Vector::ZERO;
+        }
+    }
+}
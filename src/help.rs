@@ -0,0 +1,72 @@
+//! Window listing keyboard shortcuts and bindings of function keys and mouse buttons,
+//! such that remapped bindings are listed as they are used.
+
+use ::clap::ValueEnum;
+use ::iced::{
+    Element,
+    Length::{Fill, FillPortion},
+    widget,
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message,
+    i18n::tr,
+    input::{Action, Bindings, FunctionKey, Shortcut, ShortcutContext},
+};
+
+/// View a row of keys and what they do.
+fn row<'a>(keys: String, description: String) -> Element<'a, Message> {
+    widget::Row::new()
+        .spacing(5)
+        .push(widget::text(keys).width(FillPortion(2)))
+        .push(widget::text(description).width(FillPortion(3)))
+        .into()
+}
+
+/// View a group of rows under a heading.
+fn group<'a>(
+    heading: String,
+    rows: impl IntoIterator<Item = Element<'a, Message>>,
+) -> Element<'a, Message> {
+    widget::Column::new()
+        .spacing(3)
+        .push(widget::text(heading).size(16))
+        .extend(rows)
+        .pipe(widget::container)
+        .style(widget::container::bordered_box)
+        .padding(5)
+        .width(Fill)
+        .into()
+}
+
+/// View window listing keyboard shortcuts by the windows they act in, followed by
+/// function keys and mouse buttons bound to actions.
+pub fn view(bindings: &Bindings) -> Element<'_, Message> {
+    let function_keys = FunctionKey::value_variants()
+        .iter()
+        .map(|&key| (key, bindings.key_action(key)))
+        .filter(|(_, action)| *action != Action::None)
+        .map(|(key, action)| row(key.to_string(), action.to_string()));
+    let mouse_buttons = bindings
+        .mouse
+        .iter()
+        .filter(|(_, action)| **action != Action::None)
+        .map(|(button, action)| row(button.to_string(), action.to_string()));
+    widget::Column::new()
+        .padding(5)
+        .spacing(5)
+        .extend(ShortcutContext::ALL.into_iter().map(|context| {
+            group(
+                context.to_string(),
+                Shortcut::ALL
+                    .into_iter()
+                    .filter(move |shortcut| shortcut.context() == context)
+                    .map(|shortcut| row(shortcut.keys(), shortcut.to_string())),
+            )
+        }))
+        .push(group(tr("help-function-keys"), function_keys))
+        .push(group(tr("help-mouse-buttons"), mouse_buttons))
+        .pipe(widget::scrollable)
+        .into()
+}
@@ -0,0 +1,129 @@
+//! Undo history of read state changes, listed in a window where changes may be reverted
+//! in full or for single items.
+
+use ::std::{path::Path, sync::Arc, time::SystemTime};
+
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message,
+    catalogue::{ItemState, ReadState},
+    format,
+    i18n::{tr, tr_args},
+    list_entry_name,
+};
+
+/// Amount of affected items listed for a change before the rest are summarized.
+const LISTED_ITEMS: usize = 5;
+
+/// Change of read state of items which may be undone.
+#[derive(Debug, Clone)]
+pub struct Change {
+    /// Identifier of change, unique within a run of application.
+    pub id: u64,
+    /// Read state items were given.
+    pub read_state: ReadState,
+    /// Time of change.
+    pub time: SystemTime,
+    /// Changed items with their state before the change.
+    pub items: Vec<(Arc<Path>, ItemState)>,
+}
+
+impl Change {
+    /// Describe change.
+    fn summary(&self) -> String {
+        tr_args(
+            "history-marked",
+            &[
+                ("count", &self.items.len().to_string()),
+                ("state", &self.read_state.to_string()),
+            ],
+        )
+    }
+
+    /// View change, with buttons reverting all of it or single items.
+    fn view(&self) -> Element<'_, Message> {
+        let id = self.id;
+        widget::Column::new()
+            .spacing(2)
+            .push(
+                widget::Row::new()
+                    .align_y(Center)
+                    .spacing(5)
+                    .push(widget::text(self.summary()))
+                    .push(
+                        format::date(self.time)
+                            .map(|date| widget::text(date).size(12).style(widget::text::secondary)),
+                    )
+                    .push(widget::space::horizontal())
+                    .push(
+                        widget::button(widget::text(tr("button-revert")))
+                            .padding(3)
+                            .on_press(Message::RevertChange(id)),
+                    ),
+            )
+            .extend(
+                self.items
+                    .iter()
+                    .take(LISTED_ITEMS)
+                    .map(|(path, previous)| {
+                        widget::Row::new()
+                            .align_y(Center)
+                            .spacing(5)
+                            .push(
+                                widget::text(list_entry_name(path))
+                                    .wrapping(Wrapping::None)
+                                    .width(Fill),
+                            )
+                            .push(
+                                widget::text(tr_args(
+                                    "history-was",
+                                    &[("state", &previous.read_state.to_string())],
+                                ))
+                                .size(12)
+                                .style(widget::text::secondary),
+                            )
+                            .push(
+                                widget::button(widget::text(tr("button-revert")).size(12))
+                                    .padding([0, 4])
+                                    .style(widget::button::secondary)
+                                    .on_press(Message::RevertChangedItem(id, Arc::clone(path))),
+                            )
+                            .into()
+                    }),
+            )
+            .push((self.items.len() > LISTED_ITEMS).then(|| {
+                widget::text(tr_args(
+                    "history-more",
+                    &[("count", &(self.items.len() - LISTED_ITEMS).to_string())],
+                ))
+            }))
+            .pipe(widget::container)
+            .style(widget::container::bordered_box)
+            .padding(5)
+            .width(Fill)
+            .into()
+    }
+}
+
+/// View undo history window, most recent change first.
+pub fn view(changes: &[Change]) -> Element<'_, Message> {
+    widget::Column::new()
+        .padding(5)
+        .spacing(5)
+        .push(widget::text(tr("title-undo-history")).size(20))
+        .extend(changes.iter().rev().map(Change::view))
+        .push(
+            changes
+                .is_empty()
+                .then(|| widget::text(tr("history-empty"))),
+        )
+        .pipe(widget::scrollable)
+        .into()
+}
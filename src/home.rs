@@ -0,0 +1,180 @@
+//! [Home] impl, a dashboard of the catalogue shown when no directory is opened.
+
+use ::std::{path::Path, sync::Arc};
+
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+    window,
+};
+use ::tap::Pipe;
+
+use crate::{
+    HomeTile, Message,
+    catalogue::{Catalogue, ItemState, Stats},
+    error::Error,
+    i18n::{tr, tr_args},
+    list_entry_name,
+};
+
+/// Amount of entries shown by tiles listing items.
+const TILE_ENTRIES: usize = 8;
+
+/// State of a home window.
+#[derive(Debug, Clone, Default)]
+pub struct Home {
+    /// Items being read with their state, most recently read first.
+    continue_reading: Vec<(Arc<Path>, ItemState)>,
+    /// Archives most recently found when scanning.
+    recently_added: Vec<Arc<Path>>,
+    /// Random unread archives.
+    random_picks: Vec<Arc<Path>>,
+    /// Counts of items in catalogue.
+    stats: Stats,
+}
+
+/// Get paths of a query, logging errors as tiles are left empty on failure.
+fn paths(result: Result<Vec<::std::path::PathBuf>, Error>) -> Vec<Arc<Path>> {
+    result
+        .inspect_err(Error::log)
+        .unwrap_or_default()
+        .into_iter()
+        .map(Arc::from)
+        .collect()
+}
+
+impl Home {
+    /// Load tiles from catalogue, errors are logged and leave tiles empty.
+    pub fn load(catalogue: &Catalogue) -> Self {
+        Self {
+            continue_reading: paths(catalogue.continue_reading(TILE_ENTRIES))
+                .into_iter()
+                .map(|path| {
+                    let state = catalogue.get(&path).unwrap_or_default();
+                    (path, state)
+                })
+                .collect(),
+            recently_added: paths(catalogue.recently_added(TILE_ENTRIES)),
+            random_picks: paths(catalogue.random_unread(TILE_ENTRIES)),
+            stats: catalogue
+                .stats()
+                .inspect_err(Error::log)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Pick new random unread archives.
+    pub fn shuffle(&mut self, catalogue: &Catalogue) {
+        self.random_picks = paths(catalogue.random_unread(TILE_ENTRIES));
+    }
+
+    /// View home window with given tiles.
+    pub fn view(&self, window_id: window::Id, tiles: &[HomeTile]) -> Element<'_, Message> {
+        widget::Column::new()
+            .padding(5)
+            .spacing(5)
+            .push(
+                widget::Row::new()
+                    .align_y(Center)
+                    .push(widget::text(tr("title-home")).size(20))
+                    .push(widget::space::horizontal())
+                    .push(
+                        widget::button(widget::text(tr("button-browse")))
+                            .padding(3)
+                            .on_press(Message::OpenMainWindow),
+                    ),
+            )
+            .extend(tiles.iter().map(|&tile| self.tile(window_id, tile)))
+            .pipe(widget::scrollable)
+            .into()
+    }
+
+    /// View a single tile.
+    fn tile(&self, window_id: window::Id, tile: HomeTile) -> Element<'_, Message> {
+        /// View an entry opening an archive.
+        fn entry<'a>(path: &Arc<Path>, label: String) -> Element<'a, Message> {
+            widget::button(widget::text(label).wrapping(Wrapping::None))
+                .padding([2, 6])
+                .width(Fill)
+                .style(widget::button::text)
+                .on_press(Message::OpenArchive(Arc::clone(path)))
+                .into()
+        }
+
+        let entries: Vec<Element<'_, Message>> = match tile {
+            HomeTile::ContinueReading => self
+                .continue_reading
+                .iter()
+                .map(|(path, state)| {
+                    entry(
+                        path,
+                        tr_args(
+                            "home-reading-at",
+                            &[
+                                ("name", &list_entry_name(path)),
+                                ("page", &(state.page + 1).to_string()),
+                            ],
+                        ),
+                    )
+                })
+                .collect(),
+            HomeTile::RecentlyAdded => self
+                .recently_added
+                .iter()
+                .map(|path| entry(path, list_entry_name(path)))
+                .collect(),
+            HomeTile::RandomPicks => self
+                .random_picks
+                .iter()
+                .map(|path| entry(path, list_entry_name(path)))
+                .collect(),
+            HomeTile::Stats => {
+                let Stats {
+                    seen,
+                    reading,
+                    finished,
+                    starred,
+                    pages,
+                } = self.stats;
+                vec![
+                    widget::text(tr_args(
+                        "home-stats",
+                        &[
+                            ("seen", &seen.to_string()),
+                            ("reading", &reading.to_string()),
+                            ("finished", &finished.to_string()),
+                            ("starred", &starred.to_string()),
+                            ("pages", &pages.to_string()),
+                        ],
+                    ))
+                    .into(),
+                ]
+            }
+        };
+        let is_empty = entries.is_empty();
+
+        widget::Column::new()
+            .spacing(3)
+            .push(
+                widget::Row::new()
+                    .align_y(Center)
+                    .push(widget::text(tile.to_string()).size(16))
+                    .push(widget::space::horizontal())
+                    .push((tile == HomeTile::RandomPicks).then(|| {
+                        widget::button(widget::text(tr("home-shuffle")))
+                            .padding(3)
+                            .style(widget::button::secondary)
+                            .on_press(Message::ShuffleHome(window_id))
+                    })),
+            )
+            .extend(entries)
+            .push(is_empty.then(|| widget::text(tr("home-empty"))))
+            .pipe(widget::container)
+            .style(widget::container::bordered_box)
+            .padding(5)
+            .width(Fill)
+            .into()
+    }
+}
@@ -0,0 +1,140 @@
+//! [HoverPreview] impl, a popup cycling through the first pages of an archive whose card
+//! the mouse rests on.
+
+use ::core::time::Duration;
+
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, image},
+};
+use ::tap::Pipe;
+
+use crate::{
+    ItemPath, Message,
+    i18n::{tr, tr_args},
+};
+
+/// Amount of pages cycled through, including the cover.
+pub const HOVER_PREVIEW_PAGES: usize = 4;
+
+/// Time each page is shown before the next one.
+pub const PAGE_INTERVAL: Duration = Duration::from_millis(1200);
+
+/// Width and height of the area pages are shown in.
+const PREVIEW_SIZE: f32 = 240.0;
+
+/// Preview of the first pages of an archive, shown once the mouse has rested on its card
+/// for the configured delay. Pages are loaded one at a time as they are needed.
+#[derive(Debug, Clone)]
+pub struct HoverPreview {
+    /// Card of previewed archive.
+    pub item_path: ItemPath,
+    /// Delay has elapsed and preview is shown.
+    pub shown: bool,
+    /// Amount of pages in archive, known once the first page is loaded.
+    pub page_count: Option<usize>,
+    /// Loaded pages, starting with the cover.
+    pub pages: Vec<image::Handle>,
+    /// Index of shown page.
+    pub page: usize,
+    /// A page is being loaded.
+    pub loading: bool,
+}
+
+impl HoverPreview {
+    /// Create a hover preview of a card, not shown until the delay has elapsed.
+    pub const fn new(item_path: ItemPath) -> Self {
+        Self {
+            item_path,
+            shown: false,
+            page_count: None,
+            pages: Vec::new(),
+            page: 0,
+            loading: false,
+        }
+    }
+
+    /// Get amount of pages cycled through, known once the first page is loaded.
+    fn cycled(&self) -> Option<usize> {
+        self.page_count
+            .map(|page_count| page_count.min(HOVER_PREVIEW_PAGES))
+    }
+
+    /// Show preview, its first page is expected to be loading.
+    pub const fn show(&mut self) {
+        self.shown = true;
+        self.loading = true;
+    }
+
+    /// Add a loaded page, showing it.
+    pub fn push_page(&mut self, page_count: usize, page: image::Handle) {
+        self.page_count = Some(page_count);
+        self.pages.push(page);
+        self.page = self.pages.len() - 1;
+        self.loading = false;
+    }
+
+    /// Show the next page, returning its index if it has to be loaded first.
+    pub fn advance(&mut self) -> Option<usize> {
+        if self.loading {
+            return None;
+        }
+        if self.page + 1 < self.pages.len() {
+            self.page += 1;
+            return None;
+        }
+        if self
+            .cycled()
+            .is_some_and(|cycled| self.pages.len() < cycled)
+        {
+            self.loading = true;
+            return Some(self.pages.len());
+        }
+        self.page = 0;
+        None
+    }
+
+    /// View preview, [None] until the delay has elapsed.
+    pub fn view(&self) -> Option<Element<'_, Message>> {
+        if !self.shown {
+            return None;
+        }
+        let page: Element<'_, Message> = match self.pages.get(self.page) {
+            Some(page) => widget::image(page)
+                .width(PREVIEW_SIZE)
+                .height(PREVIEW_SIZE)
+                .into(),
+            None => widget::text(tr("common-loading"))
+                .pipe(widget::container)
+                .center(PREVIEW_SIZE)
+                .into(),
+        };
+        let progress = self.cycled().map(|cycled| {
+            widget::text(tr_args(
+                "common-page-of",
+                &[
+                    ("page", &(self.page + 1).to_string()),
+                    ("count", &cycled.to_string()),
+                ],
+            ))
+            .size(12)
+            .style(widget::text::secondary)
+        });
+        widget::Column::new()
+            .spacing(3)
+            .align_x(Center)
+            .push(page)
+            .push(progress)
+            .pipe(widget::container)
+            .style(widget::container::bordered_box)
+            .padding(5)
+            .pipe(widget::container)
+            .padding(10)
+            .align_right(Fill)
+            .align_bottom(Fill)
+            .pipe(Element::from)
+            .pipe(Some)
+    }
+}
@@ -0,0 +1,265 @@
+//! Translation of user interface text. Messages are kept in Fluent files embedded per
+//! locale, of which simple messages, continued on indented lines, with variables and
+//! select expressions as placeables are supported. Variants of select expressions are
+//! kept on single lines, and picked by value or by the plural category of a number. The locale is detected from the environment unless one is
+//! chosen in settings, messages missing from it fall back to english.
+
+use ::core::{
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use ::std::{env, sync::LazyLock};
+
+use ::hashbrown::HashMap;
+
+/// Embedded messages of locales with their native names, english first as it is the
+/// fallback.
+const BUNDLES: &[(&str, &str, &str)] = &[
+    ("en-US", "English", include_str!("../assets/i18n/en-US.ftl")),
+    ("sv-SE", "Svenska", include_str!("../assets/i18n/sv-SE.ftl")),
+];
+
+/// Messages of locales by id, in the order of [BUNDLES].
+static MESSAGES: LazyLock<Vec<HashMap<&'static str, String>>> =
+    LazyLock::new(|| BUNDLES.iter().map(|(_, _, source)| parse(source)).collect());
+
+/// Index in [BUNDLES] of locale in use.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+/// Language of user interface which may be chosen in settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Language(pub Option<&'static str>);
+
+impl Language {
+    /// Get languages which may be chosen, starting with that of the environment.
+    pub fn options() -> Vec<Self> {
+        [Self(None)]
+            .into_iter()
+            .chain(BUNDLES.iter().map(|(tag, _, _)| Self(Some(tag))))
+            .collect()
+    }
+
+    /// Get language of a locale tag in settings, unknown tags use the environment.
+    pub fn from_setting(setting: Option<&str>) -> Self {
+        Self(
+            setting
+                .and_then(|tag| BUNDLES.iter().find(|(known, _, _)| *known == tag))
+                .map(|(tag, _, _)| *tag),
+        )
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self.0 {
+            None => f.write_str(&tr("settings-language-system")),
+            Some(tag) => f.write_str(
+                BUNDLES
+                    .iter()
+                    .find(|(known, _, _)| *known == tag)
+                    .map_or(tag, |(_, name, _)| name),
+            ),
+        }
+    }
+}
+
+/// Parse messages of a Fluent file. Comments, terms and attributes are skipped.
+pub fn parse(source: &str) -> HashMap<&str, String> {
+    let mut messages = HashMap::<&str, String>::new();
+    let mut current = None;
+    for line in source.lines() {
+        if line.starts_with([' ', '\t']) {
+            let text = line.trim();
+            if let Some(value) = current.and_then(|id| messages.get_mut(id))
+                && !text.is_empty()
+                && !text.starts_with('.')
+            {
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(text);
+            }
+            continue;
+        }
+        current = None;
+        if line.starts_with(['#', '-']) {
+            continue;
+        }
+        if let Some((id, value)) = line.split_once('=') {
+            let id = id.trim();
+            messages.insert(id, value.trim().to_owned());
+            current = Some(id);
+        }
+    }
+    messages
+}
+
+/// Get index of the brace closing a placeable, in text following its opening brace.
+/// String literals may contain braces, and placeables may be nested.
+fn placeable_end(text: &str) -> Option<usize> {
+    let mut depth = 0_usize;
+    let mut literal = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            '"' => literal = !literal,
+            _ if literal => {}
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(index),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Get plural category of a number, shared by the embedded locales, [None] if value is
+/// not a number.
+fn plural_category(value: &str) -> Option<&'static str> {
+    value
+        .parse::<f64>()
+        .ok()
+        .map(|_| if value == "1" { "one" } else { "other" })
+}
+
+/// Pick pattern of the variant of a select expression matching value exactly, then by
+/// plural category, then the default variant marked by `*`.
+fn select<'a>(variants: &'a str, value: &str) -> Option<&'a str> {
+    let variants = variants
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (default, line) = match line.strip_prefix('*') {
+                Some(line) => (true, line),
+                None => (false, line),
+            };
+            let (key, pattern) = line.strip_prefix('[')?.split_once(']')?;
+            Some((key.trim(), default, pattern.trim()))
+        })
+        .collect::<Vec<_>>();
+    let category = plural_category(value);
+    variants
+        .iter()
+        .find(|(key, _, _)| *key == value)
+        .or_else(|| variants.iter().find(|(key, _, _)| Some(*key) == category))
+        .or_else(|| variants.iter().find(|(_, default, _)| *default))
+        .map(|(_, _, pattern)| *pattern)
+}
+
+/// Get value of a placeable, [None] if it is unknown.
+fn resolve(placeable: &str, args: &[(&str, &str)]) -> Option<String> {
+    let arg = |name: &str| {
+        args.iter()
+            .find(|(arg, _)| *arg == name.trim())
+            .map(|(_, value)| *value)
+    };
+    if let Some((selector, variants)) = placeable.split_once("->") {
+        let value = selector
+            .trim()
+            .strip_prefix('$')
+            .and_then(arg)
+            .unwrap_or_default();
+        return select(variants, value).map(|pattern| format(pattern, args));
+    }
+    if let Some(name) = placeable.strip_prefix('$') {
+        return arg(name).map(str::to_owned);
+    }
+    placeable
+        .strip_prefix('"')
+        .and_then(|placeable| placeable.strip_suffix('"'))
+        .map(str::to_owned)
+}
+
+/// Format a message, replacing variable placeables with arguments, select expressions
+/// with their chosen variant and string literal placeables with their content. Unknown
+/// placeables are kept as they are.
+pub fn format(message: &str, args: &[(&str, &str)]) -> String {
+    let mut formatted = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = placeable_end(&rest[start + 1..]).map(|end| start + 1 + end) else {
+            break;
+        };
+        formatted.push_str(&rest[..start]);
+        match resolve(rest[start + 1..end].trim(), args) {
+            Some(value) => formatted.push_str(&value),
+            None => formatted.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    formatted.push_str(rest);
+    formatted
+}
+
+/// Get locale of environment from the variables used by gettext, such as `sv_SE.UTF-8`
+/// in `LANG`.
+fn detect() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .filter(|value| value != "C" && value != "POSIX")
+        .map(|value| {
+            value
+                .split(['.', '@'])
+                .next()
+                .unwrap_or_default()
+                .replace('_', "-")
+        })
+}
+
+/// Use locale chosen in settings, or that of the environment if [None]. Locales of
+/// another region of a known language use that language.
+pub fn set_locale(setting: Option<&str>) {
+    let Some(locale) = setting.map(str::to_owned).or_else(detect) else {
+        CURRENT.store(0, Ordering::Relaxed);
+        return;
+    };
+    let language = locale.split('-').next().unwrap_or_default();
+    let index = BUNDLES
+        .iter()
+        .position(|(tag, _, _)| tag.eq_ignore_ascii_case(&locale))
+        .or_else(|| {
+            BUNDLES.iter().position(|(tag, _, _)| {
+                tag.split('-')
+                    .next()
+                    .is_some_and(|known| known.eq_ignore_ascii_case(language))
+            })
+        })
+        .unwrap_or(0);
+    ::log::debug!("using locale {} for {locale}", BUNDLES[index].0);
+    CURRENT.store(index, Ordering::Relaxed);
+}
+
+/// Get a message in the language in use, with arguments replacing variable placeables.
+/// Messages missing from every locale are shown as their id.
+pub fn tr_args(id: &str, args: &[(&str, &str)]) -> String {
+    let current = CURRENT.load(Ordering::Relaxed);
+    let message = MESSAGES
+        .get(current)
+        .and_then(|messages| messages.get(id))
+        .or_else(|| MESSAGES.first().and_then(|messages| messages.get(id)));
+    match message {
+        Some(message) => format(message, args),
+        None => {
+            ::log::warn!("missing message {id:?}");
+            id.to_owned()
+        }
+    }
+}
+
+/// Get a message in the language in use.
+pub fn tr(id: &str) -> String {
+    tr_args(id, &[])
+}
+
+/// Get ids of messages of every embedded locale, by locale tag.
+pub fn bundles() -> impl Iterator<Item = (&'static str, Vec<&'static str>)> {
+    BUNDLES
+        .iter()
+        .zip(MESSAGES.iter())
+        .map(|((tag, _, _), messages)| {
+            let mut ids = messages.keys().copied().collect::<Vec<_>>();
+            ids.sort_unstable();
+            (*tag, ids)
+        })
+}
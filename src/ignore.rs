@@ -0,0 +1,78 @@
+//! Rules of which files and directories are skipped when scanning directories, such that
+//! system folders, samples and temporary files are not listed.
+
+use ::std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{Cli, Settings, config, text::glob_match};
+
+/// Name of file marking a directory as not containing media, such that it is skipped.
+const NOMEDIA: &str = ".nomedia";
+
+/// Get path of file marking directory as skipped.
+pub fn nomedia_marker(dir: &Path) -> PathBuf {
+    dir.join(NOMEDIA)
+}
+
+/// Rules of which entries are skipped when scanning directories.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IgnoreRules {
+    /// Glob patterns of names of skipped entries.
+    patterns: Arc<[String]>,
+    /// Skip entries with names starting with a dot.
+    skip_hidden: bool,
+    /// Follow symlinks, instead of skipping them.
+    follow_symlinks: bool,
+}
+
+impl IgnoreRules {
+    /// Get ignore rules of settings.
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            patterns: Arc::from(settings.ignore_patterns.as_slice()),
+            skip_hidden: settings.skip_hidden,
+            follow_symlinks: settings.follow_symlinks,
+        }
+    }
+
+    /// Get ignore rules of profile of command line, with overrides applied. Default
+    /// rules are used if the config file cannot be read, which is logged.
+    pub fn of_profile(cli: &Cli) -> Self {
+        config::find(&cli.xdg_dirs())
+            .map_or_else(|| Ok(Settings::default()), |path| Settings::read(&path))
+            .and_then(|settings| config::Overrides::new(cli).apply(settings))
+            .map_or_else(
+                |err| {
+                    err.log();
+                    Self::new(&Settings::default())
+                },
+                |settings| Self::new(&settings),
+            )
+    }
+
+    /// Check if symlinks are followed, symlinks are skipped otherwise.
+    pub const fn follows_symlinks(&self) -> bool {
+        self.follow_symlinks
+    }
+
+    /// Check if entry at path is skipped by its name.
+    pub fn ignores_name(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name() else {
+            return false;
+        };
+        let name = name.to_string_lossy();
+        (self.skip_hidden && name.starts_with('.'))
+            || self
+                .patterns
+                .iter()
+                .any(|pattern| glob_match(pattern, &name))
+    }
+
+    /// Check if entry at path is skipped, directories also being skipped if they contain
+    /// a `.nomedia` file.
+    pub fn ignores(&self, path: &Path, is_dir: bool) -> bool {
+        self.ignores_name(path) || (is_dir && nomedia_marker(path).exists())
+    }
+}
@@ -0,0 +1,370 @@
+//! Import of series info, read progress and tags from the databases of other comic
+//! servers, with their books matched against archives of a local library.
+
+use ::core::hash::Hash;
+use ::std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ::clap::ValueEnum;
+use ::hashbrown::HashMap;
+use ::rusqlite::{Connection, OpenFlags, OptionalExtension, params, types::FromSql};
+
+use crate::{
+    archive::{ArchiveKind, ComicInfo},
+    catalogue::{ItemState, ReadState},
+    error::Error,
+    freedesktop,
+};
+
+/// Application a database is imported from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Source {
+    /// `database.sqlite` of a Komga server.
+    Komga,
+    /// `metadata.db` of a Calibre library, files are found next to it.
+    Calibre,
+}
+
+/// Reading progress of a book in the application it is imported from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// Book is being read, with the index of the last read page.
+    Reading(usize),
+    /// Book has been read.
+    Finished,
+}
+
+/// A book of an imported database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Path of book where it was kept by the application imported from.
+    pub path: PathBuf,
+    /// Size of book in bytes, if known.
+    pub size: Option<u64>,
+    /// Series info, writers and tags of book.
+    pub info: ComicInfo,
+    /// Reading progress of book, if any.
+    pub progress: Option<Progress>,
+}
+
+/// Read books of a database exported by an application. Progress of Komga books is that
+/// of the given user, or the most recent progress of any user.
+///
+/// # Errors
+/// If the database cannot be opened or is not a database of the application.
+pub fn read(source: Source, database: &Path, user: Option<&str>) -> Result<Vec<Record>, Error> {
+    let error = |source| Error::Import {
+        path: database.to_path_buf(),
+        source,
+    };
+    let connection =
+        Connection::open_with_flags(database, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(error)?;
+    match source {
+        Source::Komga => read_komga(&connection, user),
+        Source::Calibre => read_calibre(&connection, database.parent().unwrap_or(Path::new("."))),
+    }
+    .map_err(error)
+}
+
+/// Get year of a date starting with it, empty if not known. Calibre uses the year 101
+/// for unknown dates.
+fn year(date: Option<&str>) -> String {
+    date.and_then(|date| date.get(..4))
+        .filter(|year| !year.starts_with('0') && year.bytes().all(|byte| byte.is_ascii_digit()))
+        .unwrap_or_default()
+        .to_owned()
+}
+
+/// Run a query of pairs of book ids and names, joining the names of each book with
+/// commas.
+///
+/// # Errors
+/// If the query fails.
+fn joined_names<K>(connection: &Connection, query: &str) -> ::rusqlite::Result<HashMap<K, String>>
+where
+    K: FromSql + Eq + Hash,
+{
+    let mut names = HashMap::<K, String>::new();
+    let mut statement = connection.prepare(query)?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let name = row.get::<_, String>(1)?;
+        let joined = names.entry(row.get(0)?).or_default();
+        if !joined.split(", ").any(|joined| joined == name) {
+            if !joined.is_empty() {
+                joined.push_str(", ");
+            }
+            joined.push_str(&name);
+        }
+    }
+    Ok(names)
+}
+
+/// Read books of a Komga database.
+///
+/// # Errors
+/// If the database is not a Komga database.
+fn read_komga(connection: &Connection, user: Option<&str>) -> ::rusqlite::Result<Vec<Record>> {
+    let writers = joined_names::<String>(
+        connection,
+        "SELECT BOOK_ID, NAME FROM BOOK_METADATA_AUTHOR WHERE ROLE = 'writer'",
+    )?;
+    let tags = joined_names::<String>(
+        connection,
+        "SELECT BOOK_ID, TAG FROM BOOK_METADATA_TAG
+         UNION ALL
+         SELECT b.ID, t.TAG FROM BOOK b JOIN SERIES_METADATA_TAG t ON t.SERIES_ID = b.SERIES_ID",
+    )?;
+    // Later progress replaces earlier progress of the same book.
+    let mut progress = HashMap::new();
+    let mut statement = connection.prepare(
+        "SELECT p.BOOK_ID, p.PAGE, p.COMPLETED FROM READ_PROGRESS p
+         JOIN \"USER\" u ON u.ID = p.USER_ID
+         WHERE ?1 IS NULL OR u.EMAIL = ?1
+         ORDER BY p.LAST_MODIFIED_DATE",
+    )?;
+    let mut rows = statement.query(params![user])?;
+    while let Some(row) = rows.next()? {
+        let (id, page, completed) = (row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get(2)?);
+        // Pages of Komga are counted from one.
+        let page = usize::try_from(page.saturating_sub(1)).unwrap_or_default();
+        progress.insert(
+            id,
+            if completed {
+                Progress::Finished
+            } else {
+                Progress::Reading(page)
+            },
+        );
+    }
+
+    let mut statement = connection.prepare(
+        "SELECT b.ID, b.URL, b.FILE_SIZE, m.TITLE, m.NUMBER, m.RELEASE_DATE, s.TITLE
+         FROM BOOK b
+         LEFT JOIN BOOK_METADATA m ON m.BOOK_ID = b.ID
+         LEFT JOIN SERIES_METADATA s ON s.SERIES_ID = b.SERIES_ID",
+    )?;
+    let mut rows = statement.query([])?;
+    let mut records = Vec::new();
+    while let Some(row) = rows.next()? {
+        let id = row.get::<_, String>(0)?;
+        // Books are kept as file urls.
+        let Some(path) = freedesktop::file_path(&row.get::<_, String>(1)?) else {
+            continue;
+        };
+        records.push(Record {
+            path,
+            size: row
+                .get::<_, Option<i64>>(2)?
+                .and_then(|size| u64::try_from(size).ok()),
+            info: ComicInfo {
+                title: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                series: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+                number: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+                writer: writers.get(&id).cloned().unwrap_or_default(),
+                year: year(row.get::<_, Option<String>>(5)?.as_deref()),
+                tags: tags.get(&id).cloned().unwrap_or_default(),
+            },
+            progress: progress.get(&id).copied(),
+        });
+    }
+    Ok(records)
+}
+
+/// Read books of a Calibre library, in which files are kept relative to the directory
+/// of its database. Books are read if a boolean custom column labelled `read` is set.
+///
+/// # Errors
+/// If the database is not a Calibre database.
+fn read_calibre(connection: &Connection, library: &Path) -> ::rusqlite::Result<Vec<Record>> {
+    let writers = joined_names::<i64>(
+        connection,
+        "SELECT l.book, a.name FROM books_authors_link l JOIN authors a ON a.id = l.author
+         ORDER BY l.id",
+    )?;
+    let tags = joined_names::<i64>(
+        connection,
+        "SELECT l.book, t.name FROM books_tags_link l JOIN tags t ON t.id = l.tag
+         ORDER BY l.id",
+    )?;
+    let read_column = connection
+        .query_row(
+            "SELECT id FROM custom_columns WHERE label = 'read' AND datatype = 'bool'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()?;
+    let mut read = HashMap::new();
+    if let Some(column) = read_column {
+        let mut statement =
+            connection.prepare(&format!("SELECT book, value FROM custom_column_{column}"))?;
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            read.insert(row.get::<_, i64>(0)?, row.get::<_, bool>(1)?);
+        }
+    }
+
+    let mut statement = connection.prepare(
+        "SELECT b.id, b.path, d.name, d.format, d.uncompressed_size, b.title, b.series_index,
+         b.pubdate, s.name
+         FROM books b JOIN data d ON d.book = b.id
+         LEFT JOIN books_series_link l ON l.book = b.id
+         LEFT JOIN series s ON s.id = l.series",
+    )?;
+    let mut rows = statement.query([])?;
+    let mut records = Vec::new();
+    while let Some(row) = rows.next()? {
+        let id = row.get::<_, i64>(0)?;
+        let path = library.join(row.get::<_, String>(1)?).join(format!(
+            "{}.{}",
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?.to_lowercase()
+        ));
+        // Formats which cannot be read are not matched against archives.
+        if ArchiveKind::from_path(&path).is_none() {
+            continue;
+        }
+        let series = row.get::<_, Option<String>>(8)?.unwrap_or_default();
+        records.push(Record {
+            path,
+            size: row
+                .get::<_, Option<i64>>(4)?
+                .and_then(|size| u64::try_from(size).ok()),
+            info: ComicInfo {
+                title: row.get(5)?,
+                number: if series.is_empty() {
+                    String::new()
+                } else {
+                    row.get::<_, f64>(6)?.to_string()
+                },
+                series,
+                writer: writers.get(&id).cloned().unwrap_or_default(),
+                year: year(row.get::<_, Option<String>>(7)?.as_deref()),
+                tags: tags.get(&id).cloned().unwrap_or_default(),
+            },
+            progress: read
+                .get(&id)
+                .copied()
+                .unwrap_or_default()
+                .then_some(Progress::Finished),
+        });
+    }
+    Ok(records)
+}
+
+/// Count trailing path components two paths share.
+fn common_suffix(a: &Path, b: &Path) -> usize {
+    a.components()
+        .rev()
+        .zip(b.components().rev())
+        .take_while(|(a, b)| a == b)
+        .count()
+}
+
+/// Match records against archives, returning the matched pairs and the records which
+/// matched no archive.
+///
+/// Records match the archives of the same name sharing most trailing directories with
+/// them, with sizes telling apart archives which are equally close. Records whose name
+/// matches no archive match the only archive of the same size, such that renamed
+/// archives are found.
+pub fn match_records(
+    records: Vec<Record>,
+    archives: &[PathBuf],
+) -> (Vec<(PathBuf, Record)>, Vec<Record>) {
+    let mut by_name = HashMap::<String, Vec<(&PathBuf, Option<u64>)>>::new();
+    let mut by_size = HashMap::<u64, Vec<&PathBuf>>::new();
+    for archive in archives {
+        let size = fs::metadata(archive).ok().map(|metadata| metadata.len());
+        if let Some(size) = size {
+            by_size.entry(size).or_default().push(archive);
+        }
+        if let Some(name) = archive.file_name() {
+            by_name
+                .entry(name.to_string_lossy().to_lowercase())
+                .or_default()
+                .push((archive, size));
+        }
+    }
+
+    let mut matched = Vec::new();
+    let mut unmatched = Vec::new();
+    for record in records {
+        let named = record
+            .path
+            .file_name()
+            .and_then(|name| by_name.get(&name.to_string_lossy().to_lowercase()));
+        let found = match named {
+            Some(candidates) => {
+                let closest = candidates
+                    .iter()
+                    .map(|(path, _)| common_suffix(path, &record.path))
+                    .max()
+                    .unwrap_or_default();
+                let closest = candidates
+                    .iter()
+                    .filter(|(path, _)| common_suffix(path, &record.path) == closest)
+                    .collect::<Vec<_>>();
+                match closest.as_slice() {
+                    [(path, _)] => Some(*path),
+                    closest => match closest
+                        .iter()
+                        .filter(|(_, size)| size.is_some() && *size == record.size)
+                        .collect::<Vec<_>>()
+                        .as_slice()
+                    {
+                        [(path, _)] => Some(*path),
+                        _ => None,
+                    },
+                }
+            }
+            None => record
+                .size
+                .and_then(|size| by_size.get(&size))
+                .and_then(|paths| match paths.as_slice() {
+                    [path] => Some(*path),
+                    _ => None,
+                }),
+        };
+        match found {
+            Some(path) => matched.push((path.clone(), record)),
+            None => unmatched.push(record),
+        }
+    }
+    (matched, unmatched)
+}
+
+/// Merge imported info into info already known of an archive, keeping known fields.
+pub fn merge_info(known: Option<ComicInfo>, imported: &ComicInfo) -> ComicInfo {
+    let mut info = known.unwrap_or_default();
+    for (field, value) in [
+        (&mut info.title, &imported.title),
+        (&mut info.series, &imported.series),
+        (&mut info.number, &imported.number),
+        (&mut info.writer, &imported.writer),
+        (&mut info.year, &imported.year),
+        (&mut info.tags, &imported.tags),
+    ] {
+        if field.is_empty() {
+            value.clone_into(field);
+        }
+    }
+    info
+}
+
+/// Apply imported progress to the state of an archive, progress is never undone.
+pub(crate) fn merge_progress(state: &mut ItemState, progress: Progress) {
+    match (state.read_state, progress) {
+        (ReadState::Finished, _) => {}
+        (_, Progress::Finished) => {
+            state.read_state = ReadState::Finished;
+        }
+        (ReadState::Unread, Progress::Reading(page)) => {
+            state.read_state = ReadState::Reading;
+            state.page = page;
+        }
+        (ReadState::Reading, Progress::Reading(page)) => state.page = state.page.max(page),
+    }
+}
@@ -0,0 +1,235 @@
+//! Sorting of newly downloaded archives into the series directories of a library.
+
+use ::std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, BufRead, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use ::clap::ValueEnum;
+use ::hashbrown::HashMap;
+
+use crate::{error::Error, series, temp};
+
+/// What to do with an archive of the inbox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Disposition {
+    /// Archive is new and belongs in a series directory, at target.
+    New {
+        /// Path archive is moved to.
+        target: PathBuf,
+    },
+    /// Archive has the same content as an archive already in library.
+    Duplicate {
+        /// Archive of library with the same content.
+        of: PathBuf,
+    },
+    /// Archive is new but no series directory of library matches its name.
+    Unsorted,
+}
+
+/// Decide what to do with archives of an inbox, given the archives of a library. Series
+/// directories of the library are the directories containing its archives.
+///
+/// # Errors
+/// If archives cannot be read to compare their content.
+pub fn sort(inbox: &[PathBuf], library: &[PathBuf]) -> Result<Vec<(PathBuf, Disposition)>, Error> {
+    let mut by_size = HashMap::<u64, Vec<&Path>>::new();
+    let mut series_dirs = HashMap::<String, &Path>::new();
+    for path in library {
+        if let Ok(metadata) = fs::metadata(path) {
+            by_size.entry(metadata.len()).or_default().push(path);
+        }
+        if let Some(dir) = path.parent()
+            && let Some(name) = dir.file_name()
+        {
+            let key = series::series_key(&name.to_string_lossy());
+            if !key.is_empty() {
+                series_dirs.entry(key).or_insert(dir);
+            }
+        }
+    }
+
+    let mut sorted = Vec::with_capacity(inbox.len());
+    for path in inbox {
+        let scan_error = |source| Error::Scan {
+            path: path.clone(),
+            source,
+        };
+        let size = fs::metadata(path).map_err(scan_error)?.len();
+        let mut duplicate = None;
+        for candidate in by_size.get(&size).into_iter().flatten() {
+            if same_content(path, candidate).map_err(scan_error)? {
+                duplicate = Some(candidate.to_path_buf());
+                break;
+            }
+        }
+        let disposition = if let Some(of) = duplicate {
+            Disposition::Duplicate { of }
+        } else {
+            let key = path
+                .file_stem()
+                .map(|stem| series::series_key(&stem.to_string_lossy()))
+                .unwrap_or_default();
+            match (series_dirs.get(&key), path.file_name()) {
+                (Some(dir), Some(name)) if !key.is_empty() => Disposition::New {
+                    target: dir.join(name),
+                },
+                _ => Disposition::Unsorted,
+            }
+        };
+        sorted.push((path.clone(), disposition));
+    }
+    Ok(sorted)
+}
+
+/// Check if two files of the same size have the same content.
+///
+/// # Errors
+/// If either file cannot be read.
+fn same_content(a: &Path, b: &Path) -> io::Result<bool> {
+    /// Size of chunks compared at a time.
+    const CHUNK: usize = 64 * 1024;
+
+    let (mut a, mut b) = (File::open(a)?, File::open(b)?);
+    let (mut chunk_a, mut chunk_b) = (vec![0; CHUNK], vec![0; CHUNK]);
+    loop {
+        let len = a.read(&mut chunk_a)?;
+        if len == 0 {
+            return Ok(true);
+        }
+        b.read_exact(&mut chunk_b[..len])?;
+        if chunk_a[..len] != chunk_b[..len] {
+            return Ok(false);
+        }
+    }
+}
+
+/// Move file to target, which must not exist unless overwritten, copying it if target
+/// is on another file system. Copies are never left partially written at target.
+///
+/// # Errors
+/// If target exists and is not overwritten, or the file cannot be moved.
+pub fn move_file(from: &Path, to: &Path, overwrite: bool) -> Result<(), Error> {
+    let move_file = || {
+        if !overwrite && to.try_exists()? {
+            return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+        }
+        match fs::rename(from, to) {
+            Err(err) if err.kind() == io::ErrorKind::CrossesDevices => {
+                temp::copy(from, to)?;
+                fs::remove_file(from)
+            }
+            result => result,
+        }
+    };
+    move_file().map_err(|source| Error::Move {
+        from: from.to_path_buf(),
+        to: to.to_path_buf(),
+        source,
+    })
+}
+
+/// Resolution of a move onto an existing file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Resolution {
+    /// Keep both files where they are.
+    Skip,
+    /// Replace the existing file.
+    Overwrite,
+    /// Move file to a free name next to the existing file, such as `Name (2).cbz`.
+    Rename,
+}
+
+/// Resolves moves onto existing files, asking for each conflict until a resolution is
+/// chosen for all of them.
+#[derive(Debug, Clone, Copy)]
+pub struct Conflicts {
+    /// Resolution used for every conflict, asked for each conflict if [None].
+    all: Option<Resolution>,
+}
+
+impl Conflicts {
+    /// Create conflict resolver using resolution for every conflict, conflicts are
+    /// asked for on the terminal if [None], and skipped without a terminal.
+    pub fn new(all: Option<Resolution>) -> Self {
+        Self {
+            all: all.or_else(|| (!io::stdin().is_terminal()).then_some(Resolution::Skip)),
+        }
+    }
+
+    /// Get path a file should be moved to, along with whether an existing file at it
+    /// is overwritten. [None] if the move is skipped.
+    ///
+    /// # Errors
+    /// If a resolution cannot be asked for.
+    pub fn resolve(&mut self, from: &Path, to: &Path) -> Result<Option<(PathBuf, bool)>, Error> {
+        if !to.try_exists().unwrap_or(true) {
+            return Ok(Some((to.to_path_buf(), false)));
+        }
+        let resolution = match self.all {
+            Some(resolution) => resolution,
+            None => {
+                let (resolution, all) = ask(from, to).map_err(Error::Output)?;
+                if all {
+                    self.all = Some(resolution);
+                }
+                resolution
+            }
+        };
+        Ok(match resolution {
+            Resolution::Skip => None,
+            Resolution::Overwrite => Some((to.to_path_buf(), true)),
+            Resolution::Rename => Some((free_path(to), false)),
+        })
+    }
+}
+
+/// Ask on the terminal how a move onto an existing file is resolved, and if the
+/// resolution applies to all later conflicts. Conflicts are skipped at end of input.
+///
+/// # Errors
+/// If the terminal cannot be written to or read from.
+fn ask(from: &Path, to: &Path) -> io::Result<(Resolution, bool)> {
+    let mut stderr = io::stderr().lock();
+    let mut line = String::new();
+    loop {
+        write!(
+            stderr,
+            "{} exists, moving {}\n[s]kip, [o]verwrite, [r]ename, uppercase for all: ",
+            to.display(),
+            from.display()
+        )?;
+        stderr.flush()?;
+        line.clear();
+        if io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok((Resolution::Skip, true));
+        }
+        let answer = line.trim();
+        let resolution = match answer.to_ascii_lowercase().as_str() {
+            "s" => Resolution::Skip,
+            "o" => Resolution::Overwrite,
+            "r" => Resolution::Rename,
+            _ => continue,
+        };
+        return Ok((resolution, answer.chars().all(|c| c.is_ascii_uppercase())));
+    }
+}
+
+/// Get a path next to path that does not exist, numbered such as `Name (2).cbz`.
+pub fn free_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default();
+    (2usize..)
+        .map(|number| {
+            let mut name = OsString::from(stem);
+            name.push(format!(" ({number})"));
+            if let Some(extension) = path.extension() {
+                name.push(".");
+                name.push(extension);
+            }
+            path.with_file_name(name)
+        })
+        .find(|path| !path.try_exists().unwrap_or(true))
+        .unwrap_or_else(|| path.to_path_buf())
+}
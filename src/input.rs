@@ -0,0 +1,691 @@
+//! Mapping of user input to [Action] values.
+
+use ::core::fmt::Display;
+use ::std::collections::BTreeMap;
+
+use ::clap::ValueEnum;
+#[cfg(feature = "gui")]
+use ::iced_core::keyboard::{Key, Modifiers};
+use ::iced_core::{keyboard::key::Named, mouse};
+use ::serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+
+/// Actions which may be bound to user input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    /// Do nothing.
+    #[default]
+    None,
+    /// Go back in pane history.
+    Back,
+    /// Go forward in pane history.
+    Forward,
+    /// Split pane into a new pane.
+    NewPane,
+    /// Close pane.
+    ClosePane,
+    /// Toggle settings window.
+    ToggleSettings,
+    /// Toggle undo history window.
+    ToggleUndoHistory,
+    /// Toggle statistics window.
+    ToggleStatistics,
+    /// Toggle calendar window.
+    ToggleCalendar,
+    /// Toggle window of archives moved to trash.
+    ToggleTrash,
+    /// Toggle debug overlay.
+    ToggleDebugOverlay,
+    /// Copy command line reproducing profile, theme and open directories and archives.
+    CopyLaunchCommand,
+    /// Mark selected items as read.
+    MarkRead,
+    /// Mark selected items as unread.
+    MarkUnread,
+    /// Rename archive under keyboard cursor.
+    Rename,
+    /// Undo most recent change of read state.
+    Undo,
+    /// Back up settings and catalogue of profile.
+    BackUpProfile,
+    /// Collapse or expand sidebars of main windows.
+    ToggleSidebar,
+    /// Toggle window listing keyboard shortcuts and bindings.
+    ToggleShortcuts,
+    /// Toggle window showing version, build and licenses.
+    ToggleAbout,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            Action::None => "action-none",
+            Action::Back => "action-back",
+            Action::Forward => "action-forward",
+            Action::NewPane => "action-new-pane",
+            Action::ClosePane => "action-close-pane",
+            Action::ToggleSettings => "action-toggle-settings",
+            Action::ToggleUndoHistory => "action-toggle-undo-history",
+            Action::ToggleStatistics => "action-toggle-statistics",
+            Action::ToggleCalendar => "action-toggle-calendar",
+            Action::ToggleTrash => "action-toggle-trash",
+            Action::ToggleDebugOverlay => "action-toggle-debug-overlay",
+            Action::CopyLaunchCommand => "action-copy-launch-command",
+            Action::MarkRead => "action-mark-read",
+            Action::MarkUnread => "action-mark-unread",
+            Action::Rename => "action-rename",
+            Action::Undo => "action-undo",
+            Action::BackUpProfile => "action-back-up-profile",
+            Action::ToggleSidebar => "action-toggle-sidebar",
+            Action::ToggleShortcuts => "action-toggle-shortcuts",
+            Action::ToggleAbout => "action-toggle-about",
+        }))
+    }
+}
+
+/// Mouse buttons which may be bound to actions.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum MouseButton {
+    /// Middle (wheel) button.
+    Middle,
+    /// Back button, usually button 4.
+    Back,
+    /// Forward button, usually button 5.
+    Forward,
+}
+
+impl MouseButton {
+    /// Get bindable button from a mouse button.
+    pub const fn from_button(button: mouse::Button) -> Option<Self> {
+        match button {
+            mouse::Button::Middle => Some(Self::Middle),
+            mouse::Button::Back => Some(Self::Back),
+            mouse::Button::Forward => Some(Self::Forward),
+            mouse::Button::Left | mouse::Button::Right | mouse::Button::Other(..) => None,
+        }
+    }
+}
+
+impl Display for MouseButton {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            MouseButton::Middle => "mouse-middle",
+            MouseButton::Back => "mouse-back",
+            MouseButton::Forward => "mouse-forward",
+        }))
+    }
+}
+
+/// Function keys which may be bound to actions, acting in every window.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum FunctionKey {
+    /// F1 key.
+    F1,
+    /// F2 key.
+    F2,
+    /// F3 key.
+    F3,
+    /// F4 key.
+    F4,
+    /// F6 key, F5 rescans panes.
+    F6,
+    /// F7 key.
+    F7,
+    /// F8 key.
+    F8,
+    /// F9 key.
+    F9,
+    /// F10 key.
+    F10,
+    /// F12 key, F11 toggles fullscreen of readers.
+    F12,
+}
+
+impl FunctionKey {
+    /// Get bindable function key from a named key.
+    pub const fn from_named(key: Named) -> Option<Self> {
+        Some(match key {
+            Named::F1 => Self::F1,
+            Named::F2 => Self::F2,
+            Named::F3 => Self::F3,
+            Named::F4 => Self::F4,
+            Named::F6 => Self::F6,
+            Named::F7 => Self::F7,
+            Named::F8 => Self::F8,
+            Named::F9 => Self::F9,
+            Named::F10 => Self::F10,
+            Named::F12 => Self::F12,
+            _ => return None,
+        })
+    }
+}
+
+impl Display for FunctionKey {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(match self {
+            FunctionKey::F1 => "F1",
+            FunctionKey::F2 => "F2",
+            FunctionKey::F3 => "F3",
+            FunctionKey::F4 => "F4",
+            FunctionKey::F6 => "F6",
+            FunctionKey::F7 => "F7",
+            FunctionKey::F8 => "F8",
+            FunctionKey::F9 => "F9",
+            FunctionKey::F10 => "F10",
+            FunctionKey::F12 => "F12",
+        })
+    }
+}
+
+/// Bindings of user input to actions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bindings {
+    /// Actions bound to mouse buttons.
+    pub mouse: BTreeMap<MouseButton, Action>,
+    /// Actions bound to function keys.
+    pub keys: BTreeMap<FunctionKey, Action>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self {
+            mouse: BTreeMap::from_iter([
+                (MouseButton::Middle, Action::NewPane),
+                (MouseButton::Back, Action::Back),
+                (MouseButton::Forward, Action::Forward),
+            ]),
+            keys: BTreeMap::from_iter([
+                (FunctionKey::F1, Action::ToggleShortcuts),
+                (FunctionKey::F2, Action::Rename),
+                (FunctionKey::F3, Action::ToggleUndoHistory),
+                (FunctionKey::F4, Action::ToggleStatistics),
+                (FunctionKey::F6, Action::ToggleCalendar),
+                (FunctionKey::F7, Action::ToggleTrash),
+                (FunctionKey::F8, Action::ToggleSettings),
+                (FunctionKey::F12, Action::ToggleDebugOverlay),
+            ]),
+        }
+    }
+}
+
+impl Bindings {
+    /// Get action bound to a mouse button.
+    pub fn mouse_action(&self, button: mouse::Button) -> Action {
+        MouseButton::from_button(button)
+            .and_then(|button| self.mouse.get(&button).copied())
+            .unwrap_or_default()
+    }
+
+    /// Get action bound to a function key.
+    pub fn key_action(&self, key: FunctionKey) -> Action {
+        self.keys.get(&key).copied().unwrap_or_default()
+    }
+}
+
+/// Windows keyboard shortcuts act in.
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutContext {
+    /// Every window.
+    Global,
+    /// Main windows, mostly acting on the pane under the mouse or with the keyboard cursor.
+    Main,
+    /// Reader windows.
+    Reader,
+}
+
+#[cfg(feature = "gui")]
+impl ShortcutContext {
+    /// Every context, in the order they are listed.
+    pub const ALL: [Self; 3] = [Self::Global, Self::Main, Self::Reader];
+}
+
+#[cfg(feature = "gui")]
+impl Display for ShortcutContext {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            ShortcutContext::Global => "context-global",
+            ShortcutContext::Main => "context-main",
+            ShortcutContext::Reader => "context-reader",
+        }))
+    }
+}
+
+/// Key of a chord triggering a keyboard shortcut.
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChordKey {
+    /// A named key.
+    Named(Named),
+    /// A character.
+    Character(&'static str),
+    /// Any letter or digit.
+    Alphanumeric,
+}
+
+#[cfg(feature = "gui")]
+impl ChordKey {
+    /// Check if a pressed key is this key.
+    fn matches(self, key: &Key<&str>) -> bool {
+        match (self, key) {
+            (Self::Named(named), Key::Named(key)) => named == *key,
+            (Self::Character(character), Key::Character(key)) => character == *key,
+            (Self::Alphanumeric, Key::Character(key)) => key.chars().all(char::is_alphanumeric),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
+impl Display for ChordKey {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self::Named(named) => f.write_str(match named {
+                Named::ArrowLeft => "Left",
+                Named::ArrowRight => "Right",
+                Named::ArrowUp => "Up",
+                Named::ArrowDown => "Down",
+                Named::PageUp => "Page Up",
+                Named::PageDown => "Page Down",
+                Named::Home => "Home",
+                Named::End => "End",
+                Named::Enter => "Enter",
+                Named::Space => "Space",
+                Named::Delete => "Delete",
+                Named::Backspace => "Backspace",
+                Named::Escape => "Escape",
+                Named::F5 => "F5",
+                Named::F11 => "F11",
+                named => return write!(f, "{named:?}"),
+            }),
+            Self::Character(character) => f.write_str(&character.to_uppercase()),
+            Self::Alphanumeric => f.write_str("Letters, Digits"),
+        }
+    }
+}
+
+/// Modifiers held for a chord triggering a keyboard shortcut.
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Held {
+    /// No modifiers.
+    Nothing,
+    /// Neither the command modifier nor alt, shift may be held.
+    Plain,
+    /// The command modifier, along with any other modifiers.
+    Command,
+    /// The command modifier and shift.
+    CommandShift,
+    /// Any modifiers.
+    Any,
+}
+
+#[cfg(feature = "gui")]
+impl Held {
+    /// Check if modifiers are held as required.
+    fn matches(self, modifiers: Modifiers) -> bool {
+        match self {
+            Self::Nothing => modifiers.is_empty(),
+            Self::Plain => !modifiers.command() && !modifiers.alt(),
+            Self::Command => modifiers.command(),
+            Self::CommandShift => modifiers.command() && modifiers.shift(),
+            Self::Any => true,
+        }
+    }
+
+    /// Get modifiers listed before the key of a chord.
+    const fn prefix(self) -> &'static str {
+        match self {
+            Self::Nothing | Self::Plain | Self::Any => "",
+            Self::Command => "Ctrl+",
+            Self::CommandShift => "Ctrl+Shift+",
+        }
+    }
+}
+
+/// Chords triggering keyboard shortcuts, in the order they are matched such that earlier
+/// chords take precedence. Both dispatch and the listed keys of shortcuts use them.
+#[cfg(feature = "gui")]
+const CHORDS: [(Shortcut, Held, ChordKey); 43] = [
+    (Shortcut::ZoomIn, Held::Command, ChordKey::Character("+")),
+    (Shortcut::ZoomIn, Held::Command, ChordKey::Character("=")),
+    (Shortcut::ZoomOut, Held::Command, ChordKey::Character("-")),
+    (Shortcut::ResetZoom, Held::Command, ChordKey::Character("0")),
+    (Shortcut::Undo, Held::Command, ChordKey::Character("z")),
+    (
+        Shortcut::MoveCursor,
+        Held::Plain,
+        ChordKey::Named(Named::ArrowLeft),
+    ),
+    (
+        Shortcut::MoveCursor,
+        Held::Plain,
+        ChordKey::Named(Named::ArrowRight),
+    ),
+    (
+        Shortcut::MoveCursor,
+        Held::Plain,
+        ChordKey::Named(Named::ArrowUp),
+    ),
+    (
+        Shortcut::MoveCursor,
+        Held::Plain,
+        ChordKey::Named(Named::ArrowDown),
+    ),
+    (
+        Shortcut::MoveCursor,
+        Held::Plain,
+        ChordKey::Named(Named::PageUp),
+    ),
+    (
+        Shortcut::MoveCursor,
+        Held::Plain,
+        ChordKey::Named(Named::PageDown),
+    ),
+    (
+        Shortcut::MoveCursor,
+        Held::Plain,
+        ChordKey::Named(Named::Home),
+    ),
+    (
+        Shortcut::MoveCursor,
+        Held::Plain,
+        ChordKey::Named(Named::End),
+    ),
+    (Shortcut::MarkRead, Held::Nothing, ChordKey::Character("r")),
+    (
+        Shortcut::MarkUnread,
+        Held::Nothing,
+        ChordKey::Character("u"),
+    ),
+    (Shortcut::Filter, Held::Command, ChordKey::Character("f")),
+    (Shortcut::CycleSort, Held::Command, ChordKey::Character("s")),
+    (
+        Shortcut::CycleSort,
+        Held::CommandShift,
+        ChordKey::Character("S"),
+    ),
+    (Shortcut::ClosePane, Held::Command, ChordKey::Character("w")),
+    (
+        Shortcut::CopyNames,
+        Held::CommandShift,
+        ChordKey::Character("c"),
+    ),
+    (
+        Shortcut::CopyNames,
+        Held::CommandShift,
+        ChordKey::Character("C"),
+    ),
+    (Shortcut::CopyPaths, Held::Command, ChordKey::Character("c")),
+    (Shortcut::CopyPaths, Held::Command, ChordKey::Character("C")),
+    (Shortcut::Paste, Held::Command, ChordKey::Character("v")),
+    (Shortcut::Rescan, Held::Nothing, ChordKey::Named(Named::F5)),
+    (Shortcut::TypeAhead, Held::Plain, ChordKey::Alphanumeric),
+    (
+        Shortcut::Activate,
+        Held::Nothing,
+        ChordKey::Named(Named::Enter),
+    ),
+    (
+        Shortcut::QuickLook,
+        Held::Nothing,
+        ChordKey::Named(Named::Space),
+    ),
+    (
+        Shortcut::MoveToTrash,
+        Held::Nothing,
+        ChordKey::Named(Named::Delete),
+    ),
+    (Shortcut::Cancel, Held::Any, ChordKey::Named(Named::Escape)),
+    // Shortcuts of readers are not triggered with modifiers held.
+    (
+        Shortcut::Fullscreen,
+        Held::Nothing,
+        ChordKey::Named(Named::F11),
+    ),
+    (
+        Shortcut::Fullscreen,
+        Held::Nothing,
+        ChordKey::Named(Named::Enter),
+    ),
+    (
+        Shortcut::LeaveFullscreen,
+        Held::Nothing,
+        ChordKey::Named(Named::Escape),
+    ),
+    (Shortcut::Bookmark, Held::Nothing, ChordKey::Character("b")),
+    (Shortcut::PageStrip, Held::Nothing, ChordKey::Character("g")),
+    (
+        Shortcut::Continuous,
+        Held::Nothing,
+        ChordKey::Character("v"),
+    ),
+    (Shortcut::Slideshow, Held::Nothing, ChordKey::Character("s")),
+    (
+        Shortcut::NextPage,
+        Held::Nothing,
+        ChordKey::Named(Named::ArrowRight),
+    ),
+    (
+        Shortcut::NextPage,
+        Held::Nothing,
+        ChordKey::Named(Named::Space),
+    ),
+    (
+        Shortcut::NextPage,
+        Held::Nothing,
+        ChordKey::Named(Named::PageDown),
+    ),
+    (
+        Shortcut::PreviousPage,
+        Held::Nothing,
+        ChordKey::Named(Named::ArrowLeft),
+    ),
+    (
+        Shortcut::PreviousPage,
+        Held::Nothing,
+        ChordKey::Named(Named::Backspace),
+    ),
+    (
+        Shortcut::PreviousPage,
+        Held::Nothing,
+        ChordKey::Named(Named::PageUp),
+    ),
+];
+
+/// Keyboard shortcuts with fixed keys. Keys are resolved to shortcuts through [CHORDS],
+/// such that the keys listed for shortcuts are those which trigger them.
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    /// Scale user interface up.
+    ZoomIn,
+    /// Scale user interface down.
+    ZoomOut,
+    /// Reset scale of user interface.
+    ResetZoom,
+    /// Undo most recent change of read state.
+    Undo,
+    /// Move keyboard cursor.
+    MoveCursor,
+    /// Mark selected items as read.
+    MarkRead,
+    /// Mark selected items as unread.
+    MarkUnread,
+    /// Focus filter of pane.
+    Filter,
+    /// Cycle sort order of pane.
+    CycleSort,
+    /// Close pane.
+    ClosePane,
+    /// Copy paths of selected items.
+    CopyPaths,
+    /// Copy names of selected items.
+    CopyNames,
+    /// Open pasted paths.
+    Paste,
+    /// Scan directory of pane again.
+    Rescan,
+    /// Jump to item starting with typed text.
+    TypeAhead,
+    /// Open item under keyboard cursor.
+    Activate,
+    /// Toggle quick look of item under keyboard cursor.
+    QuickLook,
+    /// Move selected archives to trash.
+    MoveToTrash,
+    /// Close dialogs, menus and quick looks of pane.
+    Cancel,
+    /// Toggle fullscreen.
+    Fullscreen,
+    /// Leave fullscreen.
+    LeaveFullscreen,
+    /// Toggle bookmark of page.
+    Bookmark,
+    /// Go to next page.
+    NextPage,
+    /// Go to previous page.
+    PreviousPage,
+    /// Toggle page strip.
+    PageStrip,
+    /// Toggle continuous layout.
+    Continuous,
+    /// Start or stop slideshow.
+    Slideshow,
+}
+
+#[cfg(feature = "gui")]
+impl Shortcut {
+    /// Every shortcut, in the order they are listed.
+    pub const ALL: [Self; 27] = [
+        Self::ZoomIn,
+        Self::ZoomOut,
+        Self::ResetZoom,
+        Self::Undo,
+        Self::MoveCursor,
+        Self::MarkRead,
+        Self::MarkUnread,
+        Self::Filter,
+        Self::CycleSort,
+        Self::ClosePane,
+        Self::CopyPaths,
+        Self::CopyNames,
+        Self::Paste,
+        Self::Rescan,
+        Self::TypeAhead,
+        Self::Activate,
+        Self::QuickLook,
+        Self::MoveToTrash,
+        Self::Cancel,
+        Self::Fullscreen,
+        Self::LeaveFullscreen,
+        Self::Bookmark,
+        Self::NextPage,
+        Self::PreviousPage,
+        Self::PageStrip,
+        Self::Continuous,
+        Self::Slideshow,
+    ];
+
+    /// Get shortcut of a key pressed in a context, if any.
+    pub fn from_key(
+        context: ShortcutContext,
+        key: Key<&str>,
+        modifiers: Modifiers,
+    ) -> Option<Self> {
+        CHORDS
+            .iter()
+            .find(|(shortcut, held, chord_key)| {
+                shortcut.context() == context && held.matches(modifiers) && chord_key.matches(&key)
+            })
+            .map(|&(shortcut, ..)| shortcut)
+    }
+
+    /// Get context shortcut acts in.
+    pub const fn context(self) -> ShortcutContext {
+        match self {
+            Self::ZoomIn | Self::ZoomOut | Self::ResetZoom => ShortcutContext::Global,
+            Self::Undo
+            | Self::MoveCursor
+            | Self::MarkRead
+            | Self::MarkUnread
+            | Self::Filter
+            | Self::CycleSort
+            | Self::ClosePane
+            | Self::CopyPaths
+            | Self::CopyNames
+            | Self::Paste
+            | Self::Rescan
+            | Self::TypeAhead
+            | Self::Activate
+            | Self::QuickLook
+            | Self::MoveToTrash
+            | Self::Cancel => ShortcutContext::Main,
+            Self::Fullscreen
+            | Self::LeaveFullscreen
+            | Self::Bookmark
+            | Self::NextPage
+            | Self::PreviousPage
+            | Self::PageStrip
+            | Self::Continuous
+            | Self::Slideshow => ShortcutContext::Reader,
+        }
+    }
+
+    /// Get keys triggering shortcut, listed from the chords matched by
+    /// [Shortcut::from_key].
+    pub fn keys(self) -> String {
+        let mut keys = Vec::new();
+        for (_, held, chord_key) in CHORDS.iter().filter(|(shortcut, ..)| *shortcut == self) {
+            let chord = format!("{}{chord_key}", held.prefix());
+            if !keys.contains(&chord) {
+                keys.push(chord);
+            }
+        }
+        keys.join(", ")
+    }
+}
+
+#[cfg(feature = "gui")]
+impl Display for Shortcut {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            Shortcut::ZoomIn => "shortcut-zoom-in",
+            Shortcut::ZoomOut => "shortcut-zoom-out",
+            Shortcut::ResetZoom => "shortcut-reset-zoom",
+            Shortcut::Undo => "shortcut-undo",
+            Shortcut::MoveCursor => "shortcut-move-cursor",
+            Shortcut::MarkRead => "shortcut-mark-read",
+            Shortcut::MarkUnread => "shortcut-mark-unread",
+            Shortcut::Filter => "shortcut-filter",
+            Shortcut::CycleSort => "shortcut-cycle-sort",
+            Shortcut::ClosePane => "shortcut-close-pane",
+            Shortcut::CopyPaths => "shortcut-copy-paths",
+            Shortcut::CopyNames => "shortcut-copy-names",
+            Shortcut::Paste => "shortcut-paste",
+            Shortcut::Rescan => "shortcut-rescan",
+            Shortcut::TypeAhead => "shortcut-type-ahead",
+            Shortcut::Activate => "shortcut-activate",
+            Shortcut::QuickLook => "shortcut-quick-look",
+            Shortcut::MoveToTrash => "shortcut-move-to-trash",
+            Shortcut::Cancel => "shortcut-cancel",
+            Shortcut::Fullscreen => "shortcut-fullscreen",
+            Shortcut::LeaveFullscreen => "shortcut-leave-fullscreen",
+            Shortcut::Bookmark => "shortcut-bookmark",
+            Shortcut::NextPage => "shortcut-next-page",
+            Shortcut::PreviousPage => "shortcut-previous-page",
+            Shortcut::PageStrip => "shortcut-page-strip",
+            Shortcut::Continuous => "shortcut-continuous",
+            Shortcut::Slideshow => "shortcut-slideshow",
+        }))
+    }
+}
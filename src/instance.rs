@@ -0,0 +1,162 @@
+//! Single instance handling, paths given to later launches are forwarded over a unix
+//! socket per profile to the running instance.
+
+#[cfg(feature = "gui")]
+use ::std::{
+    ffi::OsString,
+    io::{self, Write},
+    os::unix::ffi::OsStringExt,
+    process::Command,
+    sync::Arc,
+};
+use ::std::{
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "gui")]
+use ::futures::{SinkExt, Stream, channel::mpsc::Sender};
+#[cfg(feature = "gui")]
+use ::smol::{
+    io::{AsyncBufReadExt, BufReader},
+    net::unix::UnixListener,
+    stream::StreamExt,
+};
+
+#[cfg(feature = "gui")]
+use crate::{Cli, error::Error};
+
+/// Name of socket in runtime directory of profile.
+const SOCKET_FILE: &str = "instance.sock";
+
+/// Get path of socket of profile, [None] if there is no runtime directory.
+pub fn socket_path(xdg_dirs: &::xdg::BaseDirectories) -> Option<PathBuf> {
+    xdg_dirs
+        .place_runtime_file(SOCKET_FILE)
+        .inspect_err(|err| ::log::info!("single instance mode unavailable, {err}"))
+        .ok()
+}
+
+/// Forward directory, or an empty request if [None], to instance listening on socket.
+///
+/// # Errors
+/// If no instance is listening on socket.
+#[cfg(feature = "gui")]
+pub fn forward(socket: &Path, directory: Option<&Path>) -> io::Result<()> {
+    let mut stream = UnixStream::connect(socket)?;
+    let mut request = directory
+        .map(::std::path::absolute)
+        .transpose()?
+        .map(PathBuf::into_os_string)
+        .unwrap_or_default()
+        .into_encoded_bytes();
+    request.push(b'\n');
+    stream.write_all(&request)
+}
+
+/// Forward paths, or an empty request if there are none, to instance listening on
+/// socket.
+///
+/// # Errors
+/// If no instance is listening on socket.
+#[cfg(feature = "gui")]
+pub fn forward_all(socket: &Path, paths: &[PathBuf]) -> io::Result<()> {
+    if paths.is_empty() {
+        return forward(socket, None);
+    }
+    paths
+        .iter()
+        .try_for_each(|path| forward(socket, Some(path)))
+}
+
+/// Check if an instance is listening on socket.
+pub fn is_running(socket: &Path) -> bool {
+    UnixStream::connect(socket).is_ok()
+}
+
+/// Open a window of profile, in the running instance of profile if there is one and
+/// otherwise in a new instance with its own settings and catalogue.
+///
+/// # Errors
+/// If no instance is running and one cannot be started.
+#[cfg(feature = "gui")]
+pub fn open_profile(cli: &Cli, profile: &str) -> Result<(), Error> {
+    let xdg_dirs = cli.profile_dirs(profile);
+    if let Some(socket) = socket_path(&xdg_dirs)
+        && forward(&socket, None).is_ok()
+    {
+        return Ok(());
+    }
+    let error = |source| Error::Launch {
+        application: format!("profile {profile}"),
+        source,
+    };
+    let mut child = ::std::env::current_exe()
+        .and_then(|program| {
+            Command::new(program)
+                .args(cli.profile_args(profile))
+                .spawn()
+        })
+        .map_err(error)?;
+    // Instances are waited on such that they are reaped when they exit.
+    ::std::thread::spawn(move || {
+        if let Err(err) = child.wait() {
+            ::log::warn!("could not wait on instance of profile\n{err}");
+        }
+    });
+    Ok(())
+}
+
+/// Removes socket when dropped.
+#[cfg(feature = "gui")]
+struct SocketGuard<'a>(&'a Path);
+
+#[cfg(feature = "gui")]
+impl Drop for SocketGuard<'_> {
+    fn drop(&mut self) {
+        _ = ::std::fs::remove_file(self.0);
+    }
+}
+
+/// Listen on socket, sending requested paths to output.
+///
+/// # Errors
+/// If socket cannot be bound.
+#[cfg(feature = "gui")]
+async fn listen(socket: &Path, output: &mut Sender<Option<PathBuf>>) -> io::Result<()> {
+    // Socket is left behind by instances that did not exit cleanly.
+    if UnixStream::connect(socket).is_err() {
+        _ = ::std::fs::remove_file(socket);
+    }
+    let listener = UnixListener::bind(socket)?;
+    let _guard = SocketGuard(socket);
+
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                ::log::warn!("could not accept connection on {socket:?}\n{err}");
+                continue;
+            }
+        };
+        // Paths are sent as bytes as they need not be valid utf-8.
+        let mut lines = BufReader::new(stream).split(b'\n');
+        while let Some(Ok(line)) = lines.next().await {
+            let request = (!line.is_empty()).then(|| PathBuf::from(OsString::from_vec(line)));
+            _ = output.send(request).await;
+        }
+    }
+    Ok(())
+}
+
+/// Stream of paths forwarded by later launches, [None] for launches without a path.
+#[cfg(feature = "gui")]
+pub fn requests(socket: &Arc<Path>) -> impl Stream<Item = Option<PathBuf>> + use<> {
+    let socket = Arc::clone(socket);
+    ::iced::stream::channel(1, async move |mut output| {
+        if let Err(err) = listen(&socket, &mut output).await {
+            ::log::info!("could not listen on {socket:?}\n{err}");
+        }
+    })
+}
@@ -0,0 +1,89 @@
+//! IO on file systems which may be slow or unreachable, such as network mounts, with
+//! timeouts and retries such that a dead mount does not hang scans.
+
+use ::core::time::Duration;
+use ::std::{
+    fs::Metadata,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use ::smol::Timer;
+
+/// Time an IO operation may take before it has timed out.
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Attempts made of an IO operation before giving up.
+const ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry, doubled for each following retry.
+const BACKOFF: Duration = Duration::from_millis(250);
+
+/// Check if an error is caused by a file system not responding or being unreachable, such
+/// that the operation may succeed if retried.
+pub fn is_unreachable(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::TimedOut
+            | ErrorKind::Interrupted
+            | ErrorKind::WouldBlock
+            | ErrorKind::StaleNetworkFileHandle
+            | ErrorKind::HostUnreachable
+            | ErrorKind::NetworkUnreachable
+            | ErrorKind::NetworkDown
+            | ErrorKind::NotConnected
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::ConnectionReset
+    )
+}
+
+/// Run an IO operation with a timeout, retrying it with backoff while it fails with
+/// errors of an unreachable file system. Operations which time out are left running on
+/// the blocking thread pool, without blocking the caller.
+///
+/// # Errors
+/// If the last attempt fails or times out, or an attempt fails with another error.
+pub async fn retry<T, F>(mut operation: impl FnMut() -> F) -> io::Result<T>
+where
+    F: Future<Output = io::Result<T>>,
+{
+    let mut backoff = BACKOFF;
+    let mut attempt = 1;
+    loop {
+        let result = ::smol::future::or(operation(), async {
+            Timer::after(IO_TIMEOUT).await;
+            Err(io::Error::new(
+                ErrorKind::TimedOut,
+                format!("no response within {} seconds", IO_TIMEOUT.as_secs()),
+            ))
+        })
+        .await;
+        match result {
+            Err(err) if is_unreachable(&err) && attempt < ATTEMPTS => {
+                ::log::debug!("retrying io operation in {backoff:?}\n{err}");
+                Timer::after(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Read metadata of path with a timeout and retries, see [retry].
+///
+/// # Errors
+/// If the metadata cannot be read or reading it times out.
+pub async fn metadata(path: &Path) -> io::Result<Metadata> {
+    retry(|| ::smol::fs::metadata(path)).await
+}
+
+/// Block until metadata of path has been read, with a timeout and retries, such that
+/// blocking work such as reading archives is not started on an unreachable file system.
+///
+/// # Errors
+/// If the metadata cannot be read or reading it times out.
+#[cfg(feature = "gui")]
+pub fn probe(path: &Path) -> io::Result<Metadata> {
+    ::smol::block_on(metadata(path))
+}
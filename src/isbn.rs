@@ -0,0 +1,86 @@
+//! [Isbn] impl, book numbers identifying the physical and digital editions of a volume.
+
+use ::core::fmt::Display;
+
+/// Prefix of EAN-13 barcodes of books, used when converting ISBN-10 to ISBN-13.
+const BOOKLAND_PREFIX: &[u8] = b"978";
+
+/// International standard book number, normalized to the 13 digits of an ISBN-13 such
+/// that ISBN-10 numbers and the EAN-13 barcodes printed on books compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Isbn(String);
+
+impl Isbn {
+    /// Parse an ISBN-10, ISBN-13 or EAN-13 barcode, ignoring hyphens, spaces and a
+    /// prefix such as `ISBN` or `urn:isbn:`. [None] if the number or its check digit is
+    /// not valid.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = ["urn:isbn:", "isbn-13:", "isbn-10:", "isbn:", "isbn"]
+            .into_iter()
+            .find_map(|prefix| {
+                text.get(..prefix.len())
+                    .filter(|start| start.eq_ignore_ascii_case(prefix))
+                    .map(|_| &text[prefix.len()..])
+            })
+            .unwrap_or(text);
+        let digits = text
+            .bytes()
+            .filter(|byte| !matches!(byte, b'-' | b' '))
+            .collect::<Vec<_>>();
+
+        match digits.len() {
+            10 => {
+                let (body, check) = digits.split_at(9);
+                if !body.iter().all(u8::is_ascii_digit) {
+                    return None;
+                }
+                let sum = body
+                    .iter()
+                    .zip((2..=10).rev())
+                    .map(|(digit, weight)| u32::from(digit - b'0') * weight)
+                    .sum::<u32>();
+                let expected = (11 - sum % 11) % 11;
+                let check = match check[0] {
+                    b'X' | b'x' => 10,
+                    digit @ b'0'..=b'9' => u32::from(digit - b'0'),
+                    _ => return None,
+                };
+                (check == expected).then(|| {
+                    let mut digits = BOOKLAND_PREFIX.to_vec();
+                    digits.extend_from_slice(body);
+                    digits.push(b'0' + ean_check_digit(&digits));
+                    Self(String::from_utf8_lossy(&digits).into_owned())
+                })
+            }
+            13 => {
+                let (body, check) = digits.split_at(12);
+                (digits.iter().all(u8::is_ascii_digit) && check[0] - b'0' == ean_check_digit(body))
+                    .then(|| Self(String::from_utf8_lossy(&digits).into_owned()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the 13 digits of number.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Compute check digit of the first 12 digits of an EAN-13 barcode.
+fn ean_check_digit(body: &[u8]) -> u8 {
+    let sum = body
+        .iter()
+        .zip([1, 3].into_iter().cycle())
+        .map(|(digit, weight)| u32::from(digit - b'0') * weight)
+        .sum::<u32>();
+    // Remainder is below 10.
+    ((10 - sum % 10) % 10) as u8
+}
+
+impl Display for Isbn {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,70 @@
+//! Batch job files, pipelines of steps run without opening any windows by the `run-job`
+//! subcommand.
+
+use ::std::path::{Path, PathBuf};
+
+use ::serde::Deserialize;
+
+use crate::error::{ConfigError, Error};
+
+/// Batch job, steps run in order.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Job {
+    /// Steps of job.
+    pub steps: Vec<Step>,
+}
+
+/// Step of a batch job. Steps other than [Step::Scan] act on every archive found by
+/// earlier scans.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum Step {
+    /// Find archives in a directory and its subdirectories, recording them in the
+    /// catalogue.
+    Scan {
+        /// Directory to scan, relative to the job file.
+        path: PathBuf,
+    },
+    /// Read every page of archives, reporting archives that cannot be read.
+    Verify,
+    /// Remove pages identical to an earlier page of the same archive.
+    StripDuplicatePages,
+    /// Write freedesktop thumbnails of archives missing them or having outdated ones.
+    Thumbnails,
+}
+
+impl Step {
+    /// Get name of step used in job files.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Step::Scan { .. } => "scan",
+            Step::Verify => "verify",
+            Step::StripDuplicatePages => "strip-duplicate-pages",
+            Step::Thumbnails => "thumbnails",
+        }
+    }
+}
+
+impl Job {
+    /// Load job file at path, with paths of steps resolved relative to it.
+    ///
+    /// # Errors
+    /// If the file cannot be read or is not a valid job.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let mut job = ::std::fs::read_to_string(path)
+            .map_err(ConfigError::Read)
+            .and_then(|content| ::toml::from_str::<Self>(&content).map_err(ConfigError::Parse))
+            .map_err(|source| Error::Config {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for step in &mut job.steps {
+            if let Step::Scan { path } = step {
+                *path = dir.join(&*path);
+            }
+        }
+        Ok(job)
+    }
+}
@@ -1,70 +1,326 @@
 #![doc = include_str!("../README.md")]
 
-use ::std::{borrow::Cow, collections::BTreeMap, io::Write, path::Path, sync::Arc};
+#[cfg(feature = "gui")]
+use ::core::time::Duration;
+#[cfg(feature = "gui")]
+use ::std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Instant, SystemTime},
+};
 
-use ::color_eyre::{Report, Section, eyre::eyre};
-use ::derive_more::IsVariant;
+#[cfg(feature = "gui")]
+use ::hashbrown::HashSet;
+#[cfg(feature = "gui")]
 use ::iced::{
-    Element, Size, Subscription, Task, Theme,
-    keyboard::{Key, key::Named},
-    mouse::ScrollDelta,
-    widget::pane_grid,
+    Element, Point, Size, Subscription, Task, Theme,
+    keyboard::{Key, Modifiers, key::Named},
+    mouse::{self, ScrollDelta},
+    touch,
+    widget::{self, pane_grid, scrollable::AbsoluteOffset},
     window,
 };
-use ::katalog_lib::{PartialVariants, ThemeValueEnum, discrete_scroll};
-use ::serde::{Deserialize, Serialize};
+#[cfg(feature = "gui")]
+use ::katalog_lib::discrete_scroll;
+#[cfg(feature = "gui")]
 use ::smol::stream::StreamExt;
+#[cfg(feature = "gui")]
 use ::tap::Pipe;
-use ::unicode_segmentation::UnicodeSegmentation;
 
-use crate::{pane::DirView, window_state::Window};
+#[cfg(feature = "gui")]
+use crate::{
+    about::About,
+    animation::Animation,
+    archive::{Adjustments, Archive, ArchiveKind, Cover},
+    cache::{Shown, ThumbnailCache},
+    calendar::Calendar,
+    catalogue::{Catalogue, ItemState, Label, ReadState, Trashed},
+    chooser::{Chooser, Remember},
+    clipboard::CopyKind,
+    config::Overrides,
+    dialog::{Dialog, DialogId},
+    error::{ArchiveError, Error, OpdsError},
+    freedesktop::ThumbnailDirs,
+    geometry::{Geometries, Geometry, WindowKind},
+    gesture::{Pinch, Swipe, Turn},
+    history::Change,
+    home::Home,
+    hover_preview::{HoverPreview, PAGE_INTERVAL},
+    i18n::{Language, tr, tr_args},
+    ignore::IgnoreRules,
+    inbox::Resolution,
+    input::{Action, FunctionKey, Shortcut, ShortcutContext},
+    listing::{Column, Sort},
+    metadata_editor::{MetadataEditor, Stage},
+    opds::Link,
+    pack::Pack,
+    pane::{CursorTarget, DirView, FeedView, ListChoice, Movement, Pane},
+    quick_look::{QUICK_LOOK_PAGES, QuickLook},
+    reader::{Continuous, LoadedPage, PAGE_THUMBNAIL_SIZE, PageStrip, Prefetched, Reader},
+    rename::{Rename, RenameKind},
+    scan::Scanner,
+    shelf::Shelf,
+    startup::{Loaded, Startup},
+    statistics::Statistics,
+    status_bar::Activity,
+    tasks::{BackgroundTask, TaskKind, TaskOutput, TaskQueue, TaskStatus},
+    transfer::{Drag, Outcome, Transfer, TransferKind},
+    window_state::Window,
+    worker::{CancelToken, Throttle, WorkerPool},
+};
+
+#[cfg(feature = "gui")]
+use self::message::{Message, ShelfSeries};
+pub use self::{
+    cli::Cli,
+    settings::{
+        Activation, CardBadge, CardSize, HomeTile, ReaderTheme, Settings, SizeUnits, ThemeArg,
+    },
+};
+
+/// Size of square pages of hover and quick look previews are downscaled to fit within.
+#[cfg(feature = "gui")]
+const THUMBNAIL_SIZE: u32 = 512;
+
+/// Amount of read state changes which may be undone.
+#[cfg(feature = "gui")]
+const UNDO_LIMIT: usize = 32;
+
+/// Time spent reading a page assumed until reading speed has been measured.
+#[cfg(feature = "gui")]
+const DEFAULT_PAGE_TIME: Duration = Duration::from_secs(20);
 
-pub use self::cli::Cli;
+/// Shorter times on a page are skimming, not counted when measuring reading speed.
+#[cfg(feature = "gui")]
+const MIN_PAGE_TIME: Duration = Duration::from_secs(1);
 
+/// Longer times on a page are breaks, not counted when measuring reading speed.
+#[cfg(feature = "gui")]
+const MAX_PAGE_TIME: Duration = Duration::from_secs(10 * 60);
+
+/// Scans taking longer than this notify when they finish while no window is focused.
+#[cfg(feature = "gui")]
+const LONG_SCAN: Duration = Duration::from_secs(10);
+
+/// Smallest scale factor of the user interface.
+#[cfg(feature = "gui")]
+const MIN_SCALE_FACTOR: f32 = 0.5;
+
+/// Largest scale factor of the user interface.
+#[cfg(feature = "gui")]
+const MAX_SCALE_FACTOR: f32 = 3.0;
+
+/// Change of scale factor of the user interface per keyboard shortcut.
+#[cfg(feature = "gui")]
+const SCALE_FACTOR_STEP: f32 = 0.1;
+
+#[cfg(feature = "gui")]
+mod about;
+#[cfg(feature = "gui")]
+mod animation;
+pub mod archive;
+mod backup;
+mod bench;
+pub mod cache;
+#[cfg(feature = "gui")]
+mod calendar;
+pub mod catalogue;
+#[cfg(feature = "gui")]
+mod chooser;
 mod cli;
+#[cfg(feature = "gui")]
+mod clipboard;
+mod command;
+mod config;
+#[cfg(feature = "gui")]
+mod config_watch;
+#[cfg(feature = "dbus")]
+mod dbus;
+#[cfg(feature = "gui")]
+mod debug;
+#[cfg(feature = "gui")]
+mod dialog;
+mod doctor;
+pub mod error;
+#[cfg(feature = "gui")]
+mod format;
+pub mod freedesktop;
+#[cfg(feature = "gui")]
+mod geometry;
+#[cfg(feature = "gui")]
+pub mod gesture;
+#[cfg(feature = "gui")]
+mod help;
+#[cfg(feature = "gui")]
+mod history;
+#[cfg(feature = "gui")]
+mod home;
+#[cfg(feature = "gui")]
+mod hover_preview;
+pub mod i18n;
+pub mod ignore;
+pub mod import;
+mod inbox;
+mod input;
+mod instance;
+mod io_retry;
+pub mod isbn;
+mod job;
+#[cfg(feature = "gui")]
+mod library;
+#[cfg(feature = "gui")]
+mod list_view;
+pub mod listing;
+mod logging;
+mod manifest;
+#[cfg(feature = "gui")]
+mod message;
+#[cfg(feature = "gui")]
+mod metadata_editor;
+mod migrate;
+pub mod opds;
+#[cfg(feature = "gui")]
+mod open_with;
+mod organize;
+#[cfg(feature = "gui")]
+mod pack;
+#[cfg(feature = "gui")]
 mod pane;
+#[cfg(feature = "upower")]
+mod power;
+#[cfg(feature = "gui")]
+mod quick_look;
+#[cfg(feature = "gui")]
+mod reader;
+#[cfg(feature = "gui")]
+mod rename;
+pub mod scan;
+pub mod series;
+mod settings;
+#[cfg(feature = "gui")]
+mod shelf;
+#[cfg(feature = "gui")]
+mod sidebar;
+#[cfg(feature = "gui")]
+mod startup;
+#[cfg(feature = "gui")]
+mod statistics;
+#[cfg(feature = "gui")]
+mod status_bar;
+#[cfg(feature = "gui")]
+mod tasks;
+mod temp;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod text;
+#[cfg(feature = "gui")]
+mod transfer;
+#[cfg(feature = "gui")]
+mod trash;
+#[cfg(feature = "gui")]
 mod window_state;
+pub mod worker;
 
-/// Shorten text such that it is at most max_len long.
-fn shorten_text(text: &str, max_len: usize) -> Cow<'_, str> {
-    fn inner(text: &str, max_len: usize) -> Option<String> {
-        let mut grapheme_indices = text.grapheme_indices(true);
-        let (end, _) = grapheme_indices.nth(max_len.saturating_sub(3))?;
-        _ = grapheme_indices.nth(3)?;
-        let text = text.get(..end)?;
-        let mut buf = String::with_capacity(end + 3);
-        buf.push_str(text);
-        buf.push_str("...");
-        Some(buf)
-    }
-    inner(text, max_len).map_or(Cow::Borrowed(text), Cow::Owned)
+/// Get archives directly within a directory, such as the volumes of a series, skipping
+/// those ignored by rules.
+///
+/// # Errors
+/// If the directory cannot be read.
+#[cfg(feature = "gui")]
+fn series_archives(directory: &Path, ignore: &IgnoreRules) -> Result<Vec<Arc<Path>>, Error> {
+    ::std::fs::read_dir(directory)
+        .and_then(|entries| {
+            entries
+                .filter(|entry| {
+                    ignore.follows_symlinks()
+                        || entry.as_ref().map_or(true, |entry| {
+                            !entry
+                                .file_type()
+                                .is_ok_and(|file_type| file_type.is_symlink())
+                        })
+                })
+                .map(|entry| entry.map(|entry| entry.path()))
+                .filter(|path| {
+                    path.as_ref().map_or(true, |path| {
+                        ArchiveKind::from_path(path).is_some() && !ignore.ignores_name(path)
+                    })
+                })
+                .map(|path| path.map(Arc::from))
+                .collect()
+        })
+        .map_err(|source| Error::Scan {
+            path: directory.to_path_buf(),
+            source,
+        })
 }
 
-/// Application settings.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct Settings {
-    /// Application theme to use.
-    pub theme: ThemeValueEnum,
-
-    /// Card width to use.
-    pub card_width: u16,
+/// Get title of a window of the application showing name.
+#[cfg(feature = "gui")]
+fn window_title(name: &str) -> String {
+    tr_args("title-window", &[("name", name)])
+}
 
-    /// Max width of card text.
-    pub max_card_text_width: u16,
+/// Get name of an entry of a reading list, which includes the directory of the entry as
+/// lists span several series.
+#[cfg(feature = "gui")]
+fn list_entry_name(path: &Path) -> String {
+    let name = series::entry_name(path);
+    match path.parent().and_then(Path::file_name) {
+        Some(series) => format!("{} / {name}", series.to_string_lossy()),
+        None => name,
+    }
 }
 
-impl Default for Settings {
-    fn default() -> Self {
-        Self {
-            theme: Default::default(),
-            card_width: 150,
-            max_card_text_width: 12,
+/// Show a desktop notification, only logged without D-Bus support.
+#[cfg(feature = "gui")]
+fn notify(summary: String, body: String) -> Task<Message> {
+    #[cfg(feature = "dbus")]
+    return Task::future(async move {
+        if let Err(err) = dbus::notify(&summary, &body).await {
+            ::log::info!("could not show notification\n{err}");
         }
+    })
+    .discard();
+    #[cfg(not(feature = "dbus"))]
+    {
+        ::log::info!("{summary}\n{body}");
+        Task::none()
     }
 }
 
+/// Load a page of the archive of a hover preview. Failures are only logged, as previews
+/// are not asked for.
+#[cfg(feature = "gui")]
+fn load_hover_page(item_path: ItemPath, index: usize) -> Task<Message> {
+    ::smol::unblock({
+        let path = Arc::clone(&item_path.path);
+        move || {
+            let mut archive = Archive::open(&path)?;
+            let page = archive.page(index)?.thumbnail(THUMBNAIL_SIZE)?;
+            Ok::<_, ArchiveError>((archive.page_count(), cache::handle(page)))
+        }
+    })
+    .pipe(Task::future)
+    .then(move |result| match result {
+        Ok((page_count, page)) => Task::done(Message::HoverPageLoaded {
+            item_path: item_path.clone(),
+            page_count,
+            page,
+        }),
+        Err(source) => {
+            Error::Archive {
+                path: item_path.path.to_path_buf(),
+                source,
+            }
+            .log();
+            Task::none()
+        }
+    })
+}
+
 /// Path to a [DirView].
+#[cfg(feature = "gui")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ViewPath {
     /// Window id of item.
@@ -74,6 +330,7 @@ pub struct ViewPath {
 }
 
 /// Path to an item.
+#[cfg(feature = "gui")]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ItemPath {
     /// Path to [DirView] of item.
@@ -82,37 +339,8 @@ pub struct ItemPath {
     pub path: Arc<Path>,
 }
 
-/// Application message.
-#[derive(Debug, Clone, IsVariant)]
-enum Message {
-    /// Add window displaying given directory.
-    AddDirWindow(window::Id, Arc<Path>),
-    /// Add empty window.
-    AddEmptyWindow(window::Id),
-    /// Add settings window.
-    AddSettingsWindow(window::Id),
-    /// Remove a window from application state.
-    RemoveWindow(window::Id),
-    /// Set application theme.
-    SetTheme(ThemeValueEnum),
-    /// Scroll theme.
-    ThemeScroll(ScrollDelta),
-    /// Keyboard event.
-    KeyEvent(::iced::keyboard::Event),
-    /// Add a directory item.
-    AddItem {
-        /// Path to add item at.
-        item_path: ItemPath,
-        /// Item to add.
-        item: pane::Item,
-    },
-    /// Save settings.
-    SaveSettings,
-    /// Reload settings.
-    ReloadSettigns,
-}
-
 /// Application state.
+#[cfg(feature = "gui")]
 #[derive(Debug, Default)]
 struct State {
     /// Application windows.
@@ -127,264 +355,6209 @@ struct State {
     /// Settings used by application.
     settings: Settings,
 
+    /// Settings overridden by command line and environment, applied to settings read
+    /// from config file.
+    overrides: Overrides,
+
     /// Scroll state of theme pick list.
     theme_scroll: f32,
+
+    /// Currently held keyboard modifiers.
+    modifiers: Modifiers,
+
+    /// Most recently focused window.
+    focused_window: Option<window::Id>,
+
+    /// Pane most recently entered by the mouse.
+    hovered_pane: Option<ViewPath>,
+
+    /// Pane most recently clicked, keyboard shortcuts of its window act on it.
+    focused_pane: Option<ViewPath>,
+
+    /// Horizontal touchpad swipe over hovered pane.
+    swipe: Swipe,
+
+    /// Accumulated vertical touchpad scroll of hovered pane while Ctrl is held.
+    card_zoom: f32,
+
+    /// Pinch of fingers on a touch screen over a main window, resizing cards.
+    pinch: Pinch,
+
+    /// Thumbnails shared by all panes.
+    thumbnail_cache: ThumbnailCache<Shown>,
+
+    /// Size of square cached thumbnails were loaded to fit within.
+    thumbnail_size: u32,
+
+    /// Reading progress, stars and labels of items.
+    catalogue: Catalogue,
+
+    /// Estimated time spent reading a page, measured when reading.
+    page_time: Duration,
+
+    /// Socket later launches forward paths to, if single instance mode is available.
+    socket: Option<Arc<Path>>,
+
+    /// Names of profiles of application, including the profile in use.
+    profiles: Vec<String>,
+
+    /// Names of reading lists in catalogue.
+    reading_lists: Vec<String>,
+
+    /// Names of backups in backup directory of profile, newest first.
+    backups: Vec<String>,
+
+    /// Backup chosen to be restored in settings window, if any.
+    selected_backup: Option<String>,
+
+    /// Directory typed into settings window to add as a library root.
+    library_root_input: String,
+
+    /// Tags of archives in catalogue, listed in sidebars.
+    tags: Vec<String>,
+
+    /// Sidebars of main windows are collapsed.
+    sidebar_collapsed: bool,
+
+    /// Entry of a sidebar being dragged, if any.
+    sidebar_drag: Option<sidebar::Entry>,
+
+    /// Dialogs shown over windows, the most recent dialog of a window is shown.
+    dialogs: Vec<Dialog>,
+
+    /// Recently opened directories and archives, most recent first.
+    recent: Vec<Arc<Path>>,
+
+    /// Directory of temporary files of profile, if it could be created.
+    temp_dir: Option<Arc<Path>>,
+
+    /// Archives being read, shown above the panes of main windows.
+    shelf: Shelf,
+
+    /// Preview of the card the mouse rests on, if any.
+    hover_preview: Option<HoverPreview>,
+
+    /// Items being dragged from a pane, if any.
+    drag: Option<Drag>,
+
+    /// Archives dropped onto a pane, awaiting confirmation or being transferred.
+    transfer: Option<Transfer>,
+
+    /// Background tasks, such as conversions and extractions.
+    tasks: TaskQueue,
+
+    /// Task panel is shown in main windows.
+    show_tasks: bool,
+
+    /// Reading list most recently shown, which selected items may be added to.
+    target_list: Option<String>,
+
+    /// Changes of read state which may be undone, most recent change last.
+    undo_history: Vec<Change>,
+
+    /// Identifier given to the next change of read state.
+    next_change_id: u64,
+
+    /// System is running on battery.
+    on_battery: bool,
+
+    /// Switch slowing down scans, library indexing and background tasks, set while
+    /// background work is throttled.
+    throttle: Throttle,
+
+    /// Resource usage shown by debug overlay, if it is shown.
+    debug_stats: Option<debug::Stats>,
+
+    /// Current frame of activity spinner.
+    spinner_frame: usize,
+
+    /// Most recent error reported to user.
+    error: Option<String>,
+
+    /// Settings being edited in settings window, not used until applied.
+    draft: Settings,
+
+    /// Settings window was asked to close while there were unapplied changes.
+    settings_close_prompt: bool,
+
+    /// Settings as they were last loaded from or saved to config file.
+    saved_settings: Settings,
+
+    /// Settings changed on disk while there were unsaved changes, awaiting a decision.
+    disk_settings: Option<Settings>,
+
+    /// Geometry of windows as they were last closed.
+    geometries: Geometries,
+
+    /// Current geometry of open windows.
+    window_geometry: BTreeMap<window::Id, Geometry>,
+
+    /// Pool used for cover extraction.
+    workers: WorkerPool,
+
+    /// Catalogue and other state of profile are still being loaded in the background.
+    loading: bool,
+
+    /// Messages needing the catalogue, handled once it has been loaded.
+    deferred: Vec<Message>,
 }
 
+#[cfg(feature = "gui")]
 impl State {
-    /// Initilize state.
+    /// Initilize state, reading settings in the background before opening any window.
     fn init(
         cli: Cli,
-        settings: Settings,
+        overrides: Overrides,
         xdg_dirs: ::xdg::BaseDirectories,
     ) -> impl Fn() -> (Self, Task<Message>) {
-        let dir_path = cli.directory.as_deref().map(Arc::<Path>::from);
         move || {
-            (
-                Self {
-                    cli: cli.clone(),
-                    xdg_dirs: xdg_dirs.clone(),
-                    settings: settings.clone(),
-                    ..Self::default()
-                },
-                dir_path.as_ref().map_or_else(
-                    || {
-                        let (_, open_window) = window::open(window::Settings::default());
-                        open_window.map(Message::AddEmptyWindow)
-                    },
-                    |path| {
-                        let (_, open_window) = window::open(window::Settings::default());
-                        let path = Arc::clone(path);
+            let load = {
+                let xdg_dirs = xdg_dirs.clone();
+                ::smol::unblock(move || {
+                    Settings::load(&xdg_dirs)
+                        .map(|settings| (settings, Geometries::load(&xdg_dirs)))
+                        .map_err(Arc::new)
+                        .pipe(Message::SettingsLoaded)
+                })
+                .pipe(Task::future)
+            };
+            let state = Self {
+                cli: cli.clone(),
+                overrides: overrides.clone(),
+                xdg_dirs: xdg_dirs.clone(),
+                loading: true,
+                ..Self::default()
+            };
+            (state, load)
+        }
+    }
+
+    /// Replace state with that of profile given by cli arguments once its settings have
+    /// been read, opening its first window. Settings which cannot be read end the
+    /// application, as there is no window to report them in.
+    fn settings_loaded(
+        &mut self,
+        result: Result<(Settings, Geometries), Arc<Error>>,
+    ) -> Task<Message> {
+        let loaded = result.and_then(|(settings, geometries)| {
+            Ok((
+                self.overrides.apply(settings).map_err(Arc::new)?,
+                geometries,
+            ))
+        });
+        match loaded {
+            Ok((settings, geometries)) => {
+                let (state, task) = Self::new(
+                    self.cli.clone(),
+                    settings,
+                    self.overrides.clone(),
+                    self.xdg_dirs.clone(),
+                    geometries,
+                );
+                *self = state;
+                task
+            }
+            Err(err) => {
+                err.log();
+                ::iced::exit()
+            }
+        }
+    }
+
+    /// Create state of profile given by cli arguments, opening its first window.
+    fn new(
+        cli: Cli,
+        settings: Settings,
+        overrides: Overrides,
+        xdg_dirs: ::xdg::BaseDirectories,
+        geometries: Geometries,
+    ) -> (Self, Task<Message>) {
+        i18n::set_locale(settings.language.as_deref());
+        let main_window =
+            || geometries.apply(WindowKind::Main, &settings, window::Settings::default());
+        // Every path is opened in a window of its own.
+        let open_window = match cli.paths.as_slice() {
+            _ if cli.service => Task::none(),
+            // The most recent directory is opened in a main window once loaded.
+            [] if cli.recent || settings.home_tiles.is_empty() => {
+                let (_, open_window) = window::open(main_window());
+                open_window.map(Message::AddEmptyWindow)
+            }
+            [] => {
+                let (_, open_window) = window::open(geometries.apply(
+                    WindowKind::Home,
+                    &settings,
+                    window::Settings::default(),
+                ));
+                open_window.map(Message::AddHomeWindow)
+            }
+            paths => paths
+                .iter()
+                .map(|path| {
+                    let path = Arc::<Path>::from(path.as_path());
+                    if path.is_file() && ArchiveKind::from_path(&path).is_some() {
+                        let (_, open_window) = window::open(geometries.apply(
+                            WindowKind::Reader,
+                            &settings,
+                            window::Settings::default(),
+                        ));
+                        open_window.map(move |id| Message::AddReaderWindow(id, Arc::clone(&path)))
+                    } else {
+                        let (_, open_window) = window::open(main_window());
                         open_window.map(move |id| Message::AddDirWindow(id, Arc::clone(&path)))
-                    },
-                ),
+                    }
+                })
+                .pipe(Task::batch),
+        };
+        // The catalogue is loaded while the first windows are shown, windows needing it
+        // show placeholders until then.
+        let load = {
+            let (cli, xdg_dirs) = (cli.clone(), xdg_dirs.clone());
+            ::smol::unblock(move || {
+                Message::StartupLoaded(Loaded::new(Startup::load(&cli, &xdg_dirs)))
+            })
+            .pipe(Task::future)
+        };
+        let socket = instance::socket_path(&xdg_dirs)
+            .filter(|_| !cli.new_instance)
+            .map(Arc::from);
+        let diagnose = {
+            let xdg_dirs = xdg_dirs.clone();
+            Task::perform(
+                ::smol::unblock(move || doctor::diagnose(&xdg_dirs)),
+                Message::Diagnosed,
             )
+        };
+        let throttle = Throttle::default();
+        let index = Self::index_library(&settings, &throttle);
+        (
+            Self {
+                throttle,
+                thumbnail_cache: ThumbnailCache::new(settings.thumbnail_cache_budget()),
+                thumbnail_size: settings.card_size.thumbnail_size(),
+                saved_settings: settings.clone(),
+                draft: settings.clone(),
+                profiles: vec![cli.profile.clone()],
+                socket,
+                page_time: DEFAULT_PAGE_TIME,
+                cli,
+                xdg_dirs,
+                settings,
+                overrides,
+                geometries,
+                loading: true,
+                ..Self::default()
+            },
+            Task::batch([open_window, load, diagnose, index]),
+        )
+    }
+
+    /// Replace state with that of another profile, closing the windows of the current
+    /// profile once the first window of the other profile has opened.
+    fn switch_profile(&mut self, profile: String) -> Task<Message> {
+        if profile == self.cli.profile {
+            return Task::none();
         }
+        self.replace_state(Cli {
+            profile,
+            recent: false,
+            paths: Vec::new(),
+            ..self.cli.clone()
+        })
     }
 
-    /// Get a mutable reference to a directory view.
-    fn get_dir_view_mut(&mut self, view_path: ViewPath) -> Option<&mut DirView> {
+    /// Replace state with that of profile given by cli, loading its settings and
+    /// catalogue again, closing the current windows once the first new window has opened.
+    fn replace_state(&mut self, cli: Cli) -> Task<Message> {
+        let xdg_dirs = cli.xdg_dirs();
+        let overrides = Overrides::new(&cli);
+        let settings =
+            match Settings::load(&xdg_dirs).and_then(|settings| overrides.apply(settings)) {
+                Ok(settings) => settings,
+                Err(err) => {
+                    self.report_error(&err);
+                    return Task::none();
+                }
+            };
+
+        for (id, window) in &mut self.windows {
+            if let Some(geometry) = self.window_geometry.get(id) {
+                self.geometries.insert(window.kind(), *geometry);
+            }
+            if let Window::Main { panes } = window {
+                panes.iter_mut().for_each(|(_, pane)| pane.cancel_loads());
+            }
+        }
+        self.geometries
+            .save(&self.xdg_dirs)
+            .unwrap_or_else(|err| err.log());
+        let close_windows = self
+            .windows
+            .keys()
+            .map(|&id| window::close(id).map(Message::RemoveWindow))
+            .collect::<Vec<_>>();
+
+        let geometries = Geometries::load(&xdg_dirs);
+        let (state, open_window) = Self::new(cli, settings, overrides, xdg_dirs, geometries);
+        *self = state;
+        open_window.chain(Task::batch(close_windows))
+    }
+
+    /// Get a mutable reference to a pane.
+    fn get_pane_mut(&mut self, view_path: ViewPath) -> Option<&mut Pane> {
         let Window::Main { panes } = self.windows.get_mut(&view_path.window_id)? else {
             return None;
         };
         panes.get_mut(view_path.pane)
     }
 
-    /// Open a directory.
+    /// Get a mutable reference to a directory view.
+    fn get_dir_view_mut(&mut self, view_path: ViewPath) -> Option<&mut DirView> {
+        self.get_pane_mut(view_path).map(|pane| &mut pane.view)
+    }
+
+    /// Get a mutable reference to a directory view, if token is the current token of
+    /// its pane, such that results of stale scans and loads are dropped.
+    fn get_current_view_mut(
+        &mut self,
+        view_path: ViewPath,
+        token: &CancelToken,
+    ) -> Option<&mut DirView> {
+        self.get_pane_mut(view_path)
+            .filter(|pane| pane.is_current(token))
+            .map(|pane| &mut pane.view)
+    }
+
+    /// Open a directory, scan is aborted when the view of the pane is cleared.
     fn open_dir(
-        &self,
+        &mut self,
         path: Arc<Path>,
         prefix: Option<Arc<str>>,
         view_path: ViewPath,
     ) -> Task<Message> {
-        ::smol::fs::read_dir(Arc::clone(&path))
-            .pipe(Task::future)
-            .map({
-                let path = Arc::clone(&path);
-                move |result| {
-                    result
-                        .map_err(|err| ::log::error!("culd not read {path:?}\n{err}"))
-                        .ok()
+        self.record_recent(&path);
+        let ignore = IgnoreRules::new(&self.settings);
+        let view_mode = self.catalogue.view_mode(&path).unwrap_or_else(|err| {
+            err.log();
+            None
+        });
+        let last_visit = self.catalogue.last_visit(&path).unwrap_or_else(|err| {
+            err.log();
+            None
+        });
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            ::log::warn!("could not resolve view path {view_path:?}");
+            return Task::none();
+        };
+        if let Some((mode, sort)) = view_mode {
+            pane.mode = mode;
+            pane.sort = sort;
+        }
+        // Refreshing keeps the visit archives were new since when the directory was opened.
+        pane.last_visit = pane.last_visit.or(last_visit);
+        pane.scanning = true;
+        pane.scan_started = Some(Instant::now());
+        let token = pane.cancel_token();
+        let finished = Message::ScanFinished {
+            view_path,
+            token: token.clone(),
+        };
+        let scanner = Scanner::new(ignore, prefix);
+        let throttle = self.throttle.clone();
+        {
+            let path = Arc::clone(&path);
+            async move { scan::read_dir(&path).await }
+        }
+        .pipe(Task::future)
+        .then(move |result| {
+            let read_dir = match result {
+                Ok(read_dir) => read_dir,
+                Err(source) => {
+                    return Task::done(Message::ReportError(Arc::new(Error::Scan {
+                        path: path.to_path_buf(),
+                        source,
+                    })));
                 }
-            })
-            .and_then(move |read_dir| {
-                let prefix = prefix.clone();
-                read_dir
-                    .filter_map({
-                        let path = Arc::clone(&path);
-                        move |entry| {
-                            entry
-                                .map_err(|err| {
-                                    ::log::warn!("io error while reading directory {path:?}\n{err}")
-                                })
-                                .ok()
-                        }
-                    })
-                    .then(move |entry| {
-                        let prefix = prefix.clone();
-                        async move {
-                            let name = format!(
-                                "{prefix}{name}",
-                                prefix = prefix.as_deref().unwrap_or(""),
-                                name = entry.file_name().display()
-                            );
-                            let path = Arc::from(entry.path());
-                            Message::AddItem {
-                                item_path: ItemPath { view_path, path },
-                                item: pane::Item { name, cover: None },
-                            }
+            };
+            let token = token.clone();
+            let scanner = scanner.clone();
+            let throttle = throttle.clone();
+            read_dir
+                .filter_map({
+                    let path = Arc::clone(&path);
+                    move |entry| {
+                        entry
+                            .map_err(|err| {
+                                ::log::warn!("io error while reading directory {path:?}\n{err}")
+                            })
+                            .ok()
+                    }
+                })
+                .take_while({
+                    let token = token.clone();
+                    move |_| !token.is_cancelled()
+                })
+                .then(move |entry| {
+                    let token = token.clone();
+                    let scanner = scanner.clone();
+                    let throttle = throttle.clone();
+                    async move {
+                        throttle.pause_async().await;
+                        let entry = scanner.entry(entry).await?;
+                        let mut item =
+                            pane::Item::new(entry.name, &entry.path, entry.metadata.as_ref());
+                        if entry.offline {
+                            item.set_offline();
                         }
-                    })
-                    .pipe(Task::stream)
-            })
-    }
-
-    /// Get main application theme.
-    fn main_theme(&self) -> Theme {
-        self.settings.theme.into()
+                        Some(Message::AddItem {
+                            item,
+                            item_path: ItemPath {
+                                view_path,
+                                path: entry.path,
+                            },
+                            token,
+                        })
+                    }
+                })
+                .filter_map(::core::convert::identity)
+                .pipe(Task::stream)
+        })
+        .chain(Task::done(finished))
     }
 
-    /// Get application theme.
-    fn theme(&self, _id: window::Id) -> Theme {
-        self.main_theme()
+    /// Load thumbnails of items near the viewport of a view.
+    fn request_thumbnails(&mut self, view_path: ViewPath) -> Task<Message> {
+        let icon_width = self.settings.card_size.width();
+        let size = self.settings.card_size.thumbnail_size();
+        let throttled = self.is_throttled();
+        let animate = self.settings.animate_covers;
+        let sections = self.settings.section_headers;
+        // Freedesktop thumbnails are shared by all applications, they are not per profile.
+        let thumbnails = self
+            .xdg_dirs
+            .cache_home
+            .as_ref()
+            .filter(|_| self.settings.freedesktop_thumbnails)
+            .zip(self.temp_dir.as_ref())
+            .map(|(cache, temp)| ThumbnailDirs {
+                thumbnails: Arc::from(cache.join("thumbnails")),
+                temp: Arc::clone(temp),
+            });
+        let Some(Window::Main { panes }) = self.windows.get_mut(&view_path.window_id) else {
+            return Task::none();
+        };
+        let Some(pane) = panes.get_mut(view_path.pane) else {
+            return Task::none();
+        };
+        let cancel = pane.cancel_token();
+        let netrc = pane.feed.as_ref().and_then(|_| opds::netrc(&self.xdg_dirs));
+        pane.view
+            .request_thumbnails(
+                &pane.filter,
+                pane.arrangement(sections),
+                icon_width,
+                throttled,
+                &mut self.thumbnail_cache,
+            )
+            .into_iter()
+            .map(|path| {
+                // Feed items which are not downloaded show their remote cover.
+                if let Some(url) = pane.feed.as_ref().and_then(|feed| feed.covers.get(&path))
+                    && !path.is_file()
+                {
+                    return Self::load_remote_cover(
+                        &self.workers,
+                        &cancel,
+                        ItemPath { view_path, path },
+                        url.clone(),
+                        netrc.clone(),
+                        size,
+                    );
+                }
+                let cover = self
+                    .catalogue
+                    .cover(&path)
+                    .inspect_err(Error::log)
+                    .ok()
+                    .flatten();
+                Self::load_thumbnail(
+                    &self.workers,
+                    &cancel,
+                    ItemPath { view_path, path },
+                    cover,
+                    thumbnails.clone(),
+                    size,
+                    animate,
+                )
+            })
+            .pipe(Task::batch)
     }
 
-    /// Get Application title.
-    fn title(&self, id: window::Id) -> String {
-        match self.windows.get(&id) {
-            Some(Window::Settings) => "Arkiv Katalog: Settings".to_owned(),
-            _ => "Arkiv Katalog".to_owned(),
+    /// Choose cover of archive at path, or use its first page if [None], loading its
+    /// thumbnail again wherever it is shown.
+    fn set_cover(&mut self, path: &Path, cover: Option<&Cover>) -> Task<Message> {
+        if let Err(err) = self.catalogue.set_cover(path, cover) {
+            self.report_error(&err);
+            return Task::none();
+        }
+        self.thumbnail_cache.remove(path);
+        for window in self.windows.values_mut() {
+            let Window::Main { panes } = window else {
+                continue;
+            };
+            for (_, pane) in panes.iter_mut() {
+                if let DirView::Dir { items, .. } = &mut pane.view
+                    && let Some(item) = items.get_mut(path)
+                    && !item.thumbnail.is_loading()
+                {
+                    item.thumbnail = pane::Thumbnail::Pending;
+                }
+            }
         }
+        self.request_all_thumbnails()
     }
 
-    /// Get application subscriptions.
-    fn subscription(&self) -> Subscription<Message> {
-        let close_window = window::close_events().map(Message::RemoveWindow);
-        let key_event = ::iced::keyboard::listen().map(Message::KeyEvent);
-
-        Subscription::batch([close_window, key_event])
+    /// Apply thumbnail cache budget from settings, requesting evicted thumbnails of
+    /// visible items again.
+    fn apply_thumbnail_cache_budget(&mut self) -> Task<Message> {
+        self.thumbnail_cache
+            .set_budget(self.settings.thumbnail_cache_budget());
+        self.request_all_thumbnails()
     }
 
-    /// Update application state.
-    fn update(&mut self, message: Message) -> Task<Message> {
-        let report_err = |err: Report| {
-            writeln!(::std::io::stdout().lock(), "{err}").expect("write to stdout should not fail")
+    /// Collect resource usage for debug overlay.
+    fn debug_stats(&self) -> debug::Stats {
+        let mut stats = debug::Stats {
+            cached_thumbnails: self.thumbnail_cache.len(),
+            cache_used: self.thumbnail_cache.used(),
+            cache_budget: self.thumbnail_cache.budget(),
+            queued_jobs: self.workers.queued(),
+            resident_memory: debug::resident_memory(),
+            ..debug::Stats::default()
         };
-        match message {
-            Message::AddDirWindow(window_id, path) => {
-                let (panes, pane) = pane_grid::State::new(pane::DirView::Empty);
-                self.windows.insert(window_id, Window::Main { panes });
-                self.open_dir(path, None, ViewPath { window_id, pane })
-            }
-            Message::AddEmptyWindow(id) => {
-                let (panes, _) = pane_grid::State::new(pane::DirView::Empty);
-                self.windows.insert(id, Window::Main { panes });
-                Task::none()
-            }
-            Message::AddSettingsWindow(id) => {
-                self.windows.insert(id, Window::Settings);
-                Task::none()
-            }
-            Message::RemoveWindow(id) => {
-                self.windows.remove(&id);
-                if self.windows.is_empty() {
-                    ::iced::exit()
-                } else {
-                    Task::none()
-                }
-            }
-            Message::SetTheme(theme_arg) => {
-                self.settings.theme = theme_arg;
-                Task::none()
-            }
-            Message::KeyEvent(event) => match event {
-                ::iced::keyboard::Event::KeyReleased { key, modifiers, .. } => match key.as_ref() {
-                    Key::Named(Named::F2) if modifiers.is_empty() => {
-                        let to_close = self
-                            .windows
-                            .iter()
-                            .filter(|&(_id, ty)| matches!(ty, Window::Settings))
-                            .map(|(id, _ty)| window::close(*id).map(Message::RemoveWindow))
-                            .collect::<Vec<_>>();
-
-                        if to_close.is_empty() {
-                            let (_, task) = window::open(window::Settings {
-                                size: Size {
-                                    width: 400.0,
-                                    height: 400.0,
-                                },
-                                ..window::Settings::default()
-                            });
-                            task.map(Message::AddSettingsWindow)
-                        } else {
-                            Task::batch(to_close)
+        for window in self.windows.values() {
+            match window {
+                Window::Main { panes } => {
+                    for (_, pane) in panes.iter() {
+                        if let DirView::Dir { items, .. } = &pane.view {
+                            stats.items += items.len();
+                            stats.loading_thumbnails += items
+                                .values()
+                                .filter(|item| item.thumbnail.is_loading())
+                                .count();
                         }
                     }
-                    _ => Task::none(),
-                },
-                _ => Task::none(),
-            },
-            Message::SaveSettings => {
-                if let Err(err) = self
-                    .xdg_dirs
-                    .place_config_file("config.toml")
-                    .map_err(|err| eyre!(err))
-                    .and_then(|path| {
-                        let content =
-                            ::toml::to_string_pretty(&self.settings).map_err(|err| eyre!(err))?;
-                        ::std::fs::write(&path, content).map_err(|err| {
-                            eyre!("could not write settings to {path:?}").error(err)
-                        })?;
-                        Ok(())
-                    })
-                {
-                    report_err(err);
                 }
-                Task::none()
+                Window::Reader(reader) => stats.page_handles += usize::from(reader.image.is_some()),
+                Window::Settings
+                | Window::Home(..)
+                | Window::History
+                | Window::Help
+                | Window::About(..)
+                | Window::Stats(..)
+                | Window::Calendar(..)
+                | Window::Trash(..)
+                | Window::Metadata(..) => {}
             }
-            Message::ReloadSettigns => {
-                let settings = self
-                    .xdg_dirs
-                    .find_config_file("config.toml")
-                    .map(|path| -> ::color_eyre::Result<Settings> {
-                        let content = ::std::fs::read_to_string(&path).map_err(|err| {
-                            eyre!("could not read to {path:?} to a utf-8 string").error(err)
-                        })?;
-                        ::toml::from_str(&content).map_err(|err| eyre!(err))
-                    })
-                    .transpose();
+        }
+        stats
+    }
 
-                match settings {
-                    Ok(settings) => {
-                        self.settings = settings.unwrap_or_default();
-                        Task::none()
-                    }
-                    Err(err) => {
-                        report_err(err);
-                        Task::none()
+    /// Check if any directory is being scanned or any job is queued.
+    fn is_busy(&self) -> bool {
+        self.workers.queued() > 0
+            || self.windows.values().any(|window| match window {
+                Window::Main { panes } => panes.iter().any(|(_, pane)| pane.scanning),
+                Window::Settings
+                | Window::Reader(..)
+                | Window::Home(..)
+                | Window::History
+                | Window::Help
+                | Window::About(..)
+                | Window::Stats(..)
+                | Window::Calendar(..)
+                | Window::Trash(..)
+                | Window::Metadata(..) => false,
+            })
+    }
+
+    /// Check if any relative time is shown, which is when an item is selected by itself
+    /// or items are shown as a list.
+    fn shows_relative_time(&self) -> bool {
+        self.windows.values().any(|window| match window {
+            Window::Main { panes } => panes.iter().any(|(_, pane)| {
+                matches!(&pane.view, DirView::Dir { selected, .. }
+                    if selected.len() == 1 || !pane.mode.is_grid())
+            }),
+            Window::Settings
+            | Window::Reader(..)
+            | Window::Home(..)
+            | Window::History
+            | Window::Help
+            | Window::About(..)
+            | Window::Stats(..)
+            | Window::Calendar(..)
+            | Window::Trash(..)
+            | Window::Metadata(..) => false,
+        })
+    }
+
+    /// Check if background work should be throttled.
+    const fn is_throttled(&self) -> bool {
+        self.settings.battery_saver && self.on_battery
+    }
+
+    /// Check if animated covers are played in cards, they are paused while throttled.
+    const fn animates_covers(&self) -> bool {
+        self.settings.animate_covers && !self.is_throttled()
+    }
+
+    /// Load thumbnails of items near the viewports of all views.
+    fn request_all_thumbnails(&mut self) -> Task<Message> {
+        self.windows
+            .iter()
+            .filter_map(|(&window_id, window)| match window {
+                Window::Main { panes } => Some(
+                    panes
+                        .iter()
+                        .map(move |(&pane, _)| ViewPath { window_id, pane }),
+                ),
+                Window::Settings
+                | Window::Reader(..)
+                | Window::Home(..)
+                | Window::History
+                | Window::Help
+                | Window::About(..)
+                | Window::Stats(..)
+                | Window::Calendar(..)
+                | Window::Trash(..)
+                | Window::Metadata(..) => None,
+            })
+            .flatten()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|view_path| self.request_thumbnails(view_path))
+            .pipe(Task::batch)
+    }
+
+    /// Load thumbnail of the given size and metadata of an item on the worker pool, also
+    /// writing it to the freedesktop thumbnail directory if given. Thumbnails are
+    /// converted to rgba pixels on the worker, such that they are uploaded as is.
+    fn load_thumbnail(
+        workers: &WorkerPool,
+        cancel: &CancelToken,
+        item_path: ItemPath,
+        cover: Option<Cover>,
+        thumbnails: Option<ThumbnailDirs>,
+        size: u32,
+        animate: bool,
+    ) -> Task<Message> {
+        let path = Arc::clone(&item_path.path);
+        workers
+            .spawn(cancel, move || {
+                // Archives on unreachable mounts could block the worker indefinitely.
+                io_retry::probe(&path).map_err(ArchiveError::Open)?;
+                // A cover which cannot be animated is still shown as a still image.
+                let animation = animate
+                    .then(|| {
+                        let cover = cover.clone().unwrap_or(Cover::Page(0));
+                        archive::cover_animation(&path, size, &cover)
+                            .inspect_err(|err| {
+                                ::log::warn!("could not animate cover of {path:?}\n{err}");
+                            })
+                            .ok()
+                            .flatten()
+                    })
+                    .flatten();
+                // Covers chosen for a profile are not shared with other applications.
+                let (cover, metadata) = if let Some(cover) = cover {
+                    archive::preview_cover(&path, size, &cover)?
+                } else if let Some(thumbnails) = &thumbnails {
+                    let (cover, metadata) =
+                        archive::preview(&path, size.max(freedesktop::MAX_SIZE))?;
+                    if let Err(err) = freedesktop::write_thumbnails(thumbnails, &path, &cover) {
+                        ::log::warn!("could not write freedesktop thumbnail of {path:?}\n{err}");
                     }
-                }
-            }
-            Message::ThemeScroll(delta) => {
-                match discrete_scroll::Vertical.discrete_scroll(delta, &mut self.theme_scroll) {
-                    discrete_scroll::Direction::Forwards => {
-                        self.settings.theme = *self.settings.theme.partial_cycle_next();
+                    (cover.thumbnail(size)?, metadata)
+                } else {
+                    archive::preview(&path, size)?
+                };
+                let accent = cover.dominant_color();
+                Ok::<_, ArchiveError>((cache::handle(cover), accent, metadata, animation))
+            })
+            .pipe(Task::future)
+            .and_then({
+                let token = cancel.clone();
+                move |result| match result {
+                    Ok((handle, accent, metadata, animation)) => Task::batch([
+                        Task::done(Message::ThumbnailLoaded {
+                            item_path: item_path.clone(),
+                            token: token.clone(),
+                            accent,
+                            handle,
+                            animation: animation
+                                .and_then(|frames| Animation::new(frames, Instant::now())),
+                        }),
+                        Task::done(Message::MetadataLoaded {
+                            item_path: item_path.clone(),
+                            token: token.clone(),
+                            metadata,
+                        }),
+                    ]),
+                    Err(ArchiveError::Open(err)) if io_retry::is_unreachable(&err) => {
+                        ::log::warn!("{:?} is unreachable\n{err}", item_path.path);
+                        Task::done(Message::ItemOffline {
+                            item_path: item_path.clone(),
+                            token: token.clone(),
+                        })
                     }
-                    discrete_scroll::Direction::Backwards => {
-                        self.settings.theme = *self.settings.theme.partial_cycle_prev();
+                    Err(err) => {
+                        ::log::warn!("could not load cover of {:?}\n{err}", item_path.path);
+                        Task::done(Message::ThumbnailFailed {
+                            item_path: item_path.clone(),
+                            token: token.clone(),
+                        })
                     }
-                    discrete_scroll::Direction::Stationary => {}
                 }
-                Task::none()
-            }
-            Message::AddItem {
-                item_path: ItemPath { view_path, path },
-                item,
-            } => {
-                let Some(view) = self.get_dir_view_mut(view_path) else {
-                    ::log::warn!("could not resolve view path {view_path:?}");
-                    return Task::none();
-                };
+            })
+    }
 
-                match view {
-                    DirView::Empty => {
-                        *view = DirView::Dir {
-                            items: BTreeMap::from_iter([(path, item)]),
-                        }
-                    }
-                    DirView::Dir { items, .. } => {
-                        items.insert(path, item);
+    /// Load remote cover of an item of an OPDS feed on the worker pool, fetching it into
+    /// the download cache the item is shown in unless already fetched.
+    fn load_remote_cover(
+        workers: &WorkerPool,
+        cancel: &CancelToken,
+        item_path: ItemPath,
+        url: String,
+        netrc: Option<PathBuf>,
+        size: u32,
+    ) -> Task<Message> {
+        let dir = item_path
+            .path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        workers
+            .spawn(cancel, move || {
+                let path = opds::cover_path(&dir, &url);
+                let content = opds::fetch_cover(&dir, &url, netrc.as_deref())
+                    .map_err(|source| Error::Opds { url, source })?;
+                let cover = archive::Page::Encoded(content.into())
+                    .thumbnail(size)
+                    .map_err(|source| Error::Archive { path, source })?;
+                Ok::<_, Error>((cover.dominant_color(), cache::handle(cover)))
+            })
+            .pipe(Task::future)
+            .and_then({
+                let token = cancel.clone();
+                move |result| match result {
+                    Ok((accent, handle)) => Task::done(Message::ThumbnailLoaded {
+                        item_path: item_path.clone(),
+                        token: token.clone(),
+                        accent,
+                        handle,
+                        animation: None,
+                    }),
+                    Err(err) => {
+                        ::log::warn!("could not load cover of {:?}\n{err}", item_path.path);
+                        Task::done(Message::ThumbnailFailed {
+                            item_path: item_path.clone(),
+                            token: token.clone(),
+                        })
                     }
                 }
+            })
+    }
 
-                Task::none()
-            }
+    /// Replace settings with settings matching config file.
+    fn set_saved_settings(&mut self, settings: Settings) -> Task<Message> {
+        self.saved_settings = settings.clone();
+        self.disk_settings = None;
+        self.draft = settings;
+        self.apply_settings()
+    }
+
+    /// Use edited settings, requesting thumbnails again as budget and throttling may
+    /// have changed. Thumbnails are loaded again when animated covers are enabled, such
+    /// that their frames are decoded.
+    fn apply_settings(&mut self) -> Task<Message> {
+        let roots_changed = self.settings.library_roots != self.draft.library_roots;
+        match (self.settings.animate_covers, self.draft.animate_covers) {
+            (false, true) => self.thumbnail_cache.clear(),
+            (true, false) => self.thumbnail_cache.stop_animations(),
+            _ => {}
+        }
+        self.grow_thumbnails(self.draft.card_size);
+        self.settings = self.draft.clone();
+        i18n::set_locale(self.settings.language.as_deref());
+        self.throttle.set(self.is_throttled());
+        let index = if roots_changed {
+            Self::index_library(&self.settings, &self.throttle)
+        } else {
+            Task::none()
+        };
+        Task::batch([self.apply_thumbnail_cache_budget(), index])
+    }
+
+    /// Find archives of library roots in the background, such that they are recorded as
+    /// seen. Indexing slows down while background work is throttled.
+    fn index_library(settings: &Settings, throttle: &Throttle) -> Task<Message> {
+        if settings.library_roots.is_empty() {
+            return Task::none();
+        }
+        let roots = settings.library_roots.clone();
+        let ignore = IgnoreRules::new(settings);
+        let throttle = throttle.clone();
+        ::smol::unblock(move || Message::LibraryIndexed(library::index(&roots, &ignore, &throttle)))
+            .pipe(Task::future)
+    }
+
+    /// Record archives added to library roots as seen, and scan panes showing changed
+    /// directories again.
+    fn library_changed(&mut self, paths: &[PathBuf]) -> Task<Message> {
+        let ignore = IgnoreRules::new(&self.settings);
+        let paths = paths
+            .iter()
+            .filter(|path| !ignore.ignores_name(path))
+            .collect::<Vec<_>>();
+        let seen = self.record_seen_paths(
+            paths
+                .iter()
+                .filter(|path| ArchiveKind::from_path(path).is_some() && path.is_file()),
+        );
+        paths
+            .iter()
+            .filter_map(|path| path.parent())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|dir| self.rescan_dir(dir))
+            .chain([seen])
+            .collect::<Vec<_>>()
+            .pipe(Task::batch)
+    }
+
+    /// Show an entry of the sidebar of a window in its focused pane, or its first pane if
+    /// none of its panes is focused.
+    fn open_sidebar_entry(
+        &mut self,
+        window_id: window::Id,
+        entry: sidebar::Entry,
+    ) -> Task<Message> {
+        let Some(Window::Main { panes }) = self.windows.get(&window_id) else {
+            return Task::none();
+        };
+        let Some(view_path) = self
+            .focused_pane
+            .filter(|view_path| view_path.window_id == window_id)
+            .or_else(|| {
+                panes
+                    .iter()
+                    .next()
+                    .map(|(&pane, _)| ViewPath { window_id, pane })
+            })
+        else {
+            return Task::none();
+        };
+        self.show_sidebar_entry(view_path, entry)
+    }
+
+    /// Show an entry of a sidebar in a pane, archives are opened in a reader.
+    fn show_sidebar_entry(&mut self, view_path: ViewPath, entry: sidebar::Entry) -> Task<Message> {
+        match entry {
+            sidebar::Entry::Dir(path) => {
+                let Some(pane) = self.get_pane_mut(view_path) else {
+                    return Task::none();
+                };
+                pane.navigate(Arc::clone(&path));
+                self.open_dir(path, None, view_path)
+            }
+            sidebar::Entry::Archive(path) => self.open_reader(path),
+            sidebar::Entry::List(name) => self.open_reading_list(view_path, ListChoice::List(name)),
+            sidebar::Entry::Tag(tag) => self.open_tag(view_path, tag),
+        }
+    }
+
+    /// Check if settings have been edited without being applied.
+    fn has_unapplied_settings(&self) -> bool {
+        self.draft != self.settings
+    }
+
+    /// Close settings windows, resolving a pending close prompt.
+    fn close_settings(&mut self) -> Task<Message> {
+        self.settings_close_prompt = false;
+        self.windows
+            .iter()
+            .filter(|&(_id, ty)| matches!(ty, Window::Settings))
+            .map(|(id, _ty)| window::close(*id).map(Message::RemoveWindow))
+            .pipe(Task::batch)
+    }
+
+    /// Load bookmarks of archive at path. Archives without bookmarks take over the state
+    /// of a missing archive with the same content, such that bookmarks survive renames.
+    fn load_bookmarks(&self, path: &Path) -> BTreeSet<usize> {
+        let load = || {
+            let bookmarks = self.catalogue.bookmarks(path)?;
+            if !bookmarks.is_empty() {
+                return Ok(bookmarks);
+            }
+            let Ok(fingerprint) = catalogue::fingerprint(path) else {
+                return Ok(bookmarks);
+            };
+            let renamed = self
+                .catalogue
+                .bookmarked_paths(&fingerprint)?
+                .into_iter()
+                .find(|bookmarked| !bookmarked.try_exists().unwrap_or(true));
+            match renamed {
+                Some(renamed) => {
+                    ::log::info!("{renamed:?} was renamed to {path:?}, keeping its bookmarks");
+                    self.catalogue.rename(&renamed, path)?;
+                    self.catalogue.bookmarks(path)
+                }
+                None => Ok(bookmarks),
+            }
+        };
+        load().map_or_else(
+            |err: Error| {
+                err.log();
+                BTreeSet::new()
+            },
+            BTreeSet::from_iter,
+        )
+    }
+
+    /// Toggle bookmark of current page of a reader window.
+    fn toggle_bookmark(&mut self, window_id: window::Id) {
+        let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        let (path, page) = (Arc::clone(&reader.path), reader.page);
+        let result = if reader.bookmarks.remove(&page) {
+            self.catalogue.remove_bookmark(&path, page)
+        } else {
+            catalogue::fingerprint(&path)
+                .map_err(|source| Error::Archive {
+                    path: path.to_path_buf(),
+                    source: ArchiveError::Open(source),
+                })
+                .and_then(|fingerprint| self.catalogue.add_bookmark(&path, page, &fingerprint))
+                .inspect(|()| _ = reader.bookmarks.insert(page))
+        };
+        if let Err(err) = result {
+            self.report_error(&err);
+        }
+    }
+
+    /// Record a directory or archive as recently opened.
+    fn record_recent(&mut self, path: &Path) {
+        match self
+            .catalogue
+            .record_recent(path)
+            .and_then(|()| self.catalogue.recent())
+        {
+            Ok(recent) => self.recent = recent.into_iter().map(Arc::from).collect(),
+            Err(err) => err.log(),
+        }
+    }
+
+    /// Log an error and show it to the user.
+    fn report_error(&mut self, err: &Error) {
+        err.log();
+        self.error = Some(err.user_message());
+    }
+
+    /// Update catalogue state of item at path, and of every displayed item with that path.
+    fn update_item_state(&mut self, path: &Path, f: impl FnOnce(&mut ItemState)) {
+        match self.catalogue.update(path, f) {
+            Ok(state) => self.show_item_state(path, state),
+            Err(err) => self.report_error(&err),
+        }
+    }
+
+    /// Set state of every displayed item with path.
+    fn show_item_state(&mut self, path: &Path, state: ItemState) {
+        for window in self.windows.values_mut() {
+            if let Window::Main { panes } = window {
+                for (_, pane) in panes.iter_mut() {
+                    if let DirView::Dir { items, .. } = &mut pane.view
+                        && let Some(item) = items.get_mut(path)
+                    {
+                        item.state = state;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Set read state of selected items of a view, expanding selected directories to the
+    /// archives in them. Previous states are recorded so the change may be undone.
+    fn set_read_state(&mut self, view_path: ViewPath, read_state: ReadState) {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return;
+        };
+        pane.context_menu = None;
+        let DirView::Dir { selected, .. } = &pane.view else {
+            return;
+        };
+
+        let mut paths = Vec::new();
+        for path in selected.clone() {
+            if path.is_dir() {
+                match series_archives(&path, &IgnoreRules::new(&self.settings)) {
+                    Ok(archives) => paths.extend(archives),
+                    Err(err) => self.report_error(&err),
+                }
+            } else {
+                paths.push(path);
+            }
+        }
+
+        let mut previous = Vec::new();
+        for path in paths {
+            let state = match self.catalogue.get(&path) {
+                Ok(state) => state,
+                Err(err) => {
+                    self.report_error(&err);
+                    break;
+                }
+            };
+            if state.read_state != read_state {
+                self.update_item_state(&path, |state| {
+                    state.read_state = read_state;
+                    if read_state.is_unread() {
+                        state.page = 0;
+                    }
+                });
+                previous.push((path, state));
+            }
+        }
+
+        if !previous.is_empty() {
+            if self.undo_history.len() == UNDO_LIMIT {
+                self.undo_history.remove(0);
+            }
+            self.undo_history.push(Change {
+                id: self.next_change_id,
+                read_state,
+                time: SystemTime::now(),
+                items: previous,
+            });
+            self.next_change_id += 1;
+            self.shelf = Shelf::load(&self.catalogue);
+        }
+    }
+
+    /// Restore states of items changed by the most recent change of read state.
+    fn undo_read_state(&mut self) {
+        if let Some(id) = self.undo_history.last().map(|change| change.id) {
+            self.revert_change(id, None);
+        }
+    }
+
+    /// Restore states of items changed by a change of read state, or of a single item of
+    /// it if path is given. Later changes of the same items are overwritten.
+    fn revert_change(&mut self, id: u64, path: Option<&Path>) {
+        let Some(index) = self.undo_history.iter().position(|change| change.id == id) else {
+            return;
+        };
+        let change = &mut self.undo_history[index];
+        let previous = match path {
+            Some(path) => change
+                .items
+                .extract_if(.., |(item, _)| **item == *path)
+                .collect(),
+            None => ::core::mem::take(&mut change.items),
+        };
+        if change.items.is_empty() {
+            self.undo_history.remove(index);
+        }
+        for (path, state) in previous {
+            match self.catalogue.set(&path, state) {
+                Ok(()) => self.show_item_state(&path, state),
+                Err(err) => {
+                    self.report_error(&err);
+                    break;
+                }
+            }
+        }
+        self.shelf = Shelf::load(&self.catalogue);
+    }
+
+    /// Remember how items of the directory of a pane are presented, such that it is
+    /// presented the same way when opened again. Reading lists and feeds are not
+    /// remembered.
+    fn save_view_mode(&mut self, view_path: ViewPath) {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return;
+        };
+        let Some(dir) = pane.path.clone().filter(|_| pane.shows_dir()) else {
+            return;
+        };
+        let (mode, sort) = (pane.mode, pane.sort);
+        if let Err(err) = self.catalogue.set_view_mode(&dir, mode, sort) {
+            self.report_error(&err);
+        }
+    }
+
+    /// Get manual reading order of series in directory, logging errors as a missing
+    /// manual order only changes the order items are shown in.
+    fn series_order(&self, series: &Path) -> Vec<String> {
+        self.catalogue.series_order(series).unwrap_or_else(|err| {
+            err.log();
+            Vec::new()
+        })
+    }
+
+    /// Sort items of a pane into reading order.
+    fn sort_reading_order(&mut self, view_path: ViewPath) {
+        let Some(series) = self
+            .get_pane_mut(view_path)
+            .and_then(|pane| pane.path.clone())
+        else {
+            return;
+        };
+        let manual = self.series_order(&series);
+        if let Some(DirView::Dir { items, order, .. }) = self.get_dir_view_mut(view_path) {
+            let mut sorted = items.keys().cloned().collect::<Vec<_>>();
+            series::reading_order(&mut sorted);
+            series::apply_manual_order(&mut sorted, &manual);
+            *order = Some(sorted);
+        }
+    }
+
+    /// Move item context menu of a view is shown for within reading order, storing the
+    /// new order as the manual order of the series.
+    fn move_in_reading_order(&mut self, view_path: ViewPath, offset: isize) {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return;
+        };
+        let (Some(path), Some(series)) = (pane.context_menu.take(), pane.path.clone()) else {
+            return;
+        };
+        let list = pane.list.clone();
+        let DirView::Dir {
+            order: Some(order), ..
+        } = &mut pane.view
+        else {
+            return;
+        };
+        let Some(from) = order.iter().position(|entry| *entry == path) else {
+            return;
+        };
+        let Some(to) = from
+            .checked_add_signed(offset)
+            .filter(|to| *to < order.len())
+        else {
+            return;
+        };
+        order.swap(from, to);
+        if let Some(list) = list {
+            let order = order.clone();
+            self.set_reading_list(&list, &order);
+            return;
+        }
+        let names = order
+            .iter()
+            .map(|entry| series::entry_name(entry))
+            .collect::<Vec<_>>();
+        if let Err(err) = self.catalogue.set_series_order(&series, &names) {
+            self.report_error(&err);
+        }
+    }
+
+    /// Set entries of a reading list, updating the known reading lists.
+    fn set_reading_list(&mut self, name: &str, paths: &[Arc<Path>]) {
+        match self
+            .catalogue
+            .set_reading_list(name, paths)
+            .and_then(|()| self.catalogue.reading_lists())
+        {
+            Ok(lists) => self.reading_lists = lists,
+            Err(err) => self.report_error(&err),
+        }
+    }
+
+    /// Show a reading list in a pane, creating it if a new list was chosen. Shown lists
+    /// become the target selected items are added to.
+    fn open_reading_list(&mut self, view_path: ViewPath, choice: ListChoice) -> Task<Message> {
+        let name = match choice {
+            ListChoice::List(name) => name,
+            ListChoice::New => {
+                let name = (1..)
+                    .map(|n| tr_args("default-reading-list", &[("number", &n.to_string())]))
+                    .find(|name| !self.reading_lists.contains(name))
+                    .unwrap_or_default();
+                return Dialog::prompt(
+                    view_path.window_id,
+                    tr("dialog-new-reading-list"),
+                    tr("dialog-name"),
+                    move |name| Message::CreateReadingList(view_path, name),
+                )
+                .value(name)
+                .pipe(|dialog| self.show_dialog(dialog));
+            }
+        };
+        let paths = match self.catalogue.reading_list(&name) {
+            Ok(paths) => paths.into_iter().map(Arc::<Path>::from).collect::<Vec<_>>(),
+            Err(err) => {
+                self.report_error(&err);
+                return Task::none();
+            }
+        };
+        let items = self.entry_items(&paths);
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        pane.open_list(name.clone());
+        pane.view = DirView::with_items(items, Some(paths));
+        self.target_list = Some(name);
+        self.request_thumbnails(view_path)
+    }
+
+    /// Show archives tagged with tag in a pane.
+    fn open_tag(&mut self, view_path: ViewPath, tag: String) -> Task<Message> {
+        let paths = match self.catalogue.tagged(&tag) {
+            Ok(paths) => paths.into_iter().map(Arc::<Path>::from).collect::<Vec<_>>(),
+            Err(err) => {
+                self.report_error(&err);
+                return Task::none();
+            }
+        };
+        let items = self.entry_items(&paths);
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        pane.open_tag(tag);
+        pane.view = DirView::with_items(items, None);
+        self.request_thumbnails(view_path)
+    }
+
+    /// Get items of archives of a reading list or tag, named by their series and with
+    /// their state in the catalogue.
+    fn entry_items(&self, paths: &[Arc<Path>]) -> BTreeMap<Arc<Path>, pane::Item> {
+        paths
+            .iter()
+            .map(|path| {
+                let mut item = pane::Item::new(
+                    list_entry_name(path),
+                    path,
+                    ::std::fs::metadata(path).ok().as_ref(),
+                );
+                item.state = self.catalogue.get(path).unwrap_or_else(|err| {
+                    err.log();
+                    ItemState::default()
+                });
+                (Arc::clone(path), item)
+            })
+            .collect()
+    }
+
+    /// Add selected items of a view to the target reading list.
+    fn add_to_reading_list(&mut self, view_path: ViewPath) {
+        let Some(list) = self.target_list.clone() else {
+            return;
+        };
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return;
+        };
+        pane.context_menu = None;
+        let DirView::Dir {
+            selected, order, ..
+        } = &pane.view
+        else {
+            return;
+        };
+        // Selection is added in the order items are shown.
+        let mut selected = match order {
+            Some(order) => order
+                .iter()
+                .filter(|path| selected.contains(*path))
+                .cloned()
+                .collect(),
+            None => selected.iter().cloned().collect::<Vec<_>>(),
+        };
+        let mut entries = match self.catalogue.reading_list(&list) {
+            Ok(paths) => paths.into_iter().map(Arc::<Path>::from).collect::<Vec<_>>(),
+            Err(err) => {
+                self.report_error(&err);
+                return;
+            }
+        };
+        for path in selected.drain(..) {
+            if path.is_dir() {
+                match series_archives(&path, &IgnoreRules::new(&self.settings)) {
+                    Ok(mut archives) => {
+                        series::reading_order(&mut archives);
+                        series::apply_manual_order(&mut archives, &self.series_order(&path));
+                        entries.extend(archives);
+                    }
+                    Err(err) => self.report_error(&err),
+                }
+            } else {
+                entries.push(path);
+            }
+        }
+        self.set_reading_list(&list, &entries);
+    }
+
+    /// Remove selected items of a view showing a reading list from the list.
+    fn remove_from_reading_list(&mut self, view_path: ViewPath) {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return;
+        };
+        pane.context_menu = None;
+        let Some(list) = pane.list.clone() else {
+            return;
+        };
+        let DirView::Dir {
+            items,
+            selected,
+            order: Some(order),
+            ..
+        } = &mut pane.view
+        else {
+            return;
+        };
+        order.retain(|path| !selected.contains(path));
+        items.retain(|path, _| !selected.contains(path));
+        selected.clear();
+        let order = order.clone();
+        self.set_reading_list(&list, &order);
+    }
+
+    /// Record archives of a view as seen, such that they are listed as recently added.
+    fn record_seen(&mut self, view_path: ViewPath) -> Task<Message> {
+        let Some(DirView::Dir { items, .. }) = self.get_dir_view_mut(view_path) else {
+            return Task::none();
+        };
+        let archives = items
+            .keys()
+            .filter(|path| ArchiveKind::from_path(path).is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+        self.record_seen_paths(archives)
+    }
+
+    /// Record archives found when scanning as seen, notifying about those not seen before
+    /// which are new volumes of followed series.
+    fn record_seen_paths(
+        &mut self,
+        paths: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> Task<Message> {
+        let alerts = self
+            .catalogue
+            .record_seen(paths)
+            .and_then(|added| self.catalogue.alert_new_volumes(&added))
+            .unwrap_or_else(|err| {
+                err.log();
+                Vec::new()
+            });
+        let (summary, body) = match alerts.as_slice() {
+            [] => return Task::none(),
+            [alert] => (
+                tr_args("notify-new-volume", &[("series", &alert.series)]),
+                list_entry_name(&alert.path),
+            ),
+            alerts => (
+                tr_args(
+                    "notify-new-volumes",
+                    &[("count", &alerts.len().to_string())],
+                ),
+                alerts
+                    .iter()
+                    .map(|alert| format!("{}: {}", alert.series, list_entry_name(&alert.path)))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+        };
+        notify(summary, body)
+    }
+
+    /// Show a desktop notification if notifications of finished tasks are enabled and no
+    /// window of application is focused, as the user would otherwise miss it.
+    fn notify_unfocused(&self, summary: String, body: String) -> Task<Message> {
+        if self.settings.task_notifications && self.focused_window.is_none() {
+            notify(summary, body)
+        } else {
+            Task::none()
+        }
+    }
+
+    /// Scan subdirectories of a view shown as a shelf for the archives in them.
+    fn scan_shelf(&mut self, view_path: ViewPath, token: CancelToken) -> Task<Message> {
+        let ignore = IgnoreRules::new(&self.settings);
+        let Some(DirView::Dir { items, .. }) = self.get_dir_view_mut(view_path) else {
+            return Task::none();
+        };
+        let paths = items.keys().cloned().collect::<Vec<_>>();
+        ::smol::unblock(move || {
+            paths
+                .into_iter()
+                .filter(|path| path.is_dir())
+                .filter_map(|dir| {
+                    let mut archives = series_archives(&dir, &ignore)
+                        .inspect_err(Error::log)
+                        .ok()?;
+                    series::reading_order(&mut archives);
+                    let volumes = archives
+                        .into_iter()
+                        .map(|path| {
+                            let file = ::std::fs::metadata(&path).ok();
+                            let item =
+                                pane::Item::new(series::entry_name(&path), &path, file.as_ref());
+                            (path, item)
+                        })
+                        .collect::<Vec<_>>();
+                    (!volumes.is_empty()).then_some((dir, volumes))
+                })
+                .collect()
+        })
+        .pipe(Task::future)
+        .map(move |series| Message::ShelfLoaded {
+            view_path,
+            token: token.clone(),
+            series,
+        })
+    }
+
+    /// Fill in page counts of archives in a view from the catalogue, counting pages of
+    /// archives missing from it or modified since they were counted.
+    fn count_pages(&mut self, view_path: ViewPath, token: &CancelToken) -> Task<Message> {
+        let Some(DirView::Dir { items, .. }) = self
+            .windows
+            .get_mut(&view_path.window_id)
+            .and_then(|window| match window {
+                Window::Main { panes } => panes.get_mut(view_path.pane),
+                _ => None,
+            })
+            .filter(|pane| pane.is_current(token))
+            .map(|pane| &mut pane.view)
+        else {
+            return Task::none();
+        };
+        let mut uncounted = Vec::new();
+        for (path, item) in items.iter_mut().filter(|(path, item)| {
+            item.page_count.is_none() && ArchiveKind::from_path(path).is_some()
+        }) {
+            item.page_count = self
+                .catalogue
+                .page_count(path, item.modified)
+                .unwrap_or_else(|err| {
+                    err.log();
+                    None
+                });
+            if item.page_count.is_none() {
+                uncounted.push((Arc::clone(path), item.modified));
+            }
+        }
+        if uncounted.is_empty() {
+            return Task::none();
+        }
+
+        let token = token.clone();
+        self.workers
+            .spawn(&token, {
+                let token = token.clone();
+                move || {
+                    uncounted
+                        .into_iter()
+                        .take_while(|_| !token.is_cancelled())
+                        .filter_map(|(path, modified)| {
+                            // Opening an archive only reads its index, not its pages.
+                            let page_count = Archive::open(&path)
+                                .inspect_err(|err| {
+                                    ::log::warn!("could not count pages of {path:?}\n{err}");
+                                })
+                                .ok()?
+                                .page_count();
+                            Some((path, modified, page_count))
+                        })
+                        .collect()
+                }
+            })
+            .pipe(Task::future)
+            .and_then(move |counts| {
+                Task::done(Message::PagesCounted {
+                    view_path,
+                    token: token.clone(),
+                    counts,
+                })
+            })
+    }
+
+    /// Show scanned series of a view as shelf rows, archives directly in the directory of
+    /// the view form the first row.
+    fn show_shelf(
+        &mut self,
+        view_path: ViewPath,
+        token: &CancelToken,
+        series: ShelfSeries,
+    ) -> Task<Message> {
+        let Some(dir) = self
+            .get_pane_mut(view_path)
+            .filter(|pane| pane.is_current(token))
+            .and_then(|pane| pane.path.clone())
+        else {
+            return Task::none();
+        };
+        let manual_orders = series
+            .iter()
+            .map(|(series, _)| self.series_order(series))
+            .collect::<Vec<_>>();
+        let notify = self.record_seen_paths(
+            series
+                .iter()
+                .flat_map(|(_, volumes)| volumes.iter().map(|(path, _)| path)),
+        );
+        let dir_order = self.series_order(&dir);
+        let wishlist = self.catalogue.wishlist().unwrap_or_else(|err| {
+            err.log();
+            Vec::new()
+        });
+        let followed = self
+            .catalogue
+            .followed()
+            .unwrap_or_else(|err| {
+                err.log();
+                Vec::new()
+            })
+            .iter()
+            .map(|name| series::series_key(name))
+            .collect::<HashSet<_>>();
+        let alerts = self.catalogue.alerts().unwrap_or_else(|err| {
+            err.log();
+            Vec::new()
+        });
+        let series = series
+            .into_iter()
+            .map(|(series, volumes)| {
+                let volumes = volumes
+                    .into_iter()
+                    .map(|(path, mut item)| {
+                        item.state = self.catalogue.get(&path).unwrap_or_else(|err| {
+                            err.log();
+                            ItemState::default()
+                        });
+                        (path, item)
+                    })
+                    .collect::<Vec<_>>();
+                (series, volumes)
+            })
+            .collect::<Vec<_>>();
+
+        let Some(DirView::Dir { items, shelf, .. }) = self.get_current_view_mut(view_path, token)
+        else {
+            return notify;
+        };
+        let mut loose = items
+            .keys()
+            .filter(|path| ArchiveKind::from_path(path).is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+        series::reading_order(&mut loose);
+        series::apply_manual_order(&mut loose, &dir_order);
+
+        let mut rows = Vec::with_capacity(series.len() + 1);
+        let mut acquired = Vec::new();
+        if !loose.is_empty() {
+            let (row, volumes) = pane::ShelfRow::new(series::entry_name(&dir), loose, &wishlist);
+            acquired.push((row.name.clone(), volumes));
+            rows.push(row);
+        }
+        for ((series, volumes), manual) in series.into_iter().zip(&manual_orders) {
+            let mut paths = volumes
+                .iter()
+                .map(|(path, _)| Arc::clone(path))
+                .collect::<Vec<_>>();
+            series::apply_manual_order(&mut paths, manual);
+            items.extend(volumes);
+            let (row, volumes) = pane::ShelfRow::new(series::entry_name(&series), paths, &wishlist);
+            acquired.push((row.name.clone(), volumes));
+            rows.push(row);
+        }
+        for row in &mut rows {
+            let key = series::series_key(&row.name);
+            row.followed = followed.contains(&key);
+            row.alerts = alerts
+                .iter()
+                .filter(|alert| series::series_key(&alert.series) == key)
+                .count();
+        }
+        *shelf = Some(rows);
+
+        // Wished for volumes which have been added to the library are no longer wished.
+        for (name, volumes) in acquired {
+            if !volumes.is_empty()
+                && let Err(err) = self.catalogue.remove_wishes(&name, Some(&volumes))
+            {
+                err.log();
+            }
+        }
+        notify
+    }
+
+    /// Get volume following archive at path in the reading order of its series.
+    fn next_volume(&self, path: &Path) -> Option<Arc<Path>> {
+        let series = path.parent()?;
+        let mut volumes = series_archives(series, &IgnoreRules::new(&self.settings))
+            .inspect_err(|err| err.log())
+            .ok()?;
+        series::reading_order(&mut volumes);
+        series::apply_manual_order(&mut volumes, &self.series_order(series));
+        let index = volumes.iter().position(|volume| **volume == *path)?;
+        volumes.get(index + 1).cloned()
+    }
+
+    /// Load thumbnails of pages near the visible part of the page strip of a reader,
+    /// which are neither cached nor being loaded.
+    fn request_page_thumbnails(&mut self, window_id: window::Id) -> Task<Message> {
+        let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+            return Task::none();
+        };
+        let (Some(page_count), Some(strip)) = (reader.page_count, &mut reader.strip) else {
+            return Task::none();
+        };
+        let path = Arc::clone(&reader.path);
+        let pages = strip
+            .visible(page_count)
+            .filter(|&page| {
+                !strip.loading.contains(&page) && !self.thumbnail_cache.touch_page(&path, page)
+            })
+            .collect::<Vec<_>>();
+        if pages.is_empty() {
+            return Task::none();
+        }
+        strip.loading.extend(pages.iter().copied());
+
+        ::smol::unblock(move || {
+            let thumbnails = Archive::open(&path)
+                .map(|mut archive| {
+                    pages
+                        .iter()
+                        .filter_map(|&page| {
+                            archive
+                                .page(page)
+                                .and_then(|content| content.thumbnail(PAGE_THUMBNAIL_SIZE))
+                                .inspect_err(|err| {
+                                    ::log::warn!("could not load page {page} of {path:?}\n{err}");
+                                })
+                                .ok()
+                                .map(|thumbnail| (page, cache::handle(thumbnail)))
+                        })
+                        .collect()
+                })
+                .unwrap_or_else(|err| {
+                    ::log::warn!("could not open {path:?} for page thumbnails\n{err}");
+                    Vec::new()
+                });
+            Message::PageThumbnailsLoaded {
+                window_id,
+                path,
+                pages,
+                thumbnails,
+            }
+        })
+        .pipe(Task::future)
+    }
+
+    /// Show a page of a reader as current, recording reading progress and reading speed,
+    /// finding the next volume at the last page and revealing the page in the page strip.
+    fn show_page(&mut self, window_id: window::Id, page: usize) -> Task<Message> {
+        let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+            return Task::none();
+        };
+        let Some(page_count) = reader.page_count else {
+            return Task::none();
+        };
+        // Only turning to the following page measures reading speed and counts
+        // as reading a page, skipping pages or going back does not.
+        let turned = reader.page_shown.is_some() && page == reader.page + 1;
+        let page_time = reader
+            .page_shown
+            .filter(|_| turned)
+            .map(|shown| shown.elapsed())
+            .filter(|time| (MIN_PAGE_TIME..=MAX_PAGE_TIME).contains(time));
+        reader.page_shown = Some(Instant::now());
+        reader.page = page;
+        let path = Arc::clone(&reader.path);
+        let adjustments = reader.adjustments;
+        let is_last = page + 1 >= page_count;
+        let prefetch = if is_last && reader.next_volume.is_none() {
+            let next = self.next_volume(&path);
+            if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) {
+                reader.next_volume.clone_from(&next);
+            }
+            next.map_or_else(Task::none, |next| {
+                Self::prefetch_volume(window_id, next, adjustments)
+            })
+        } else {
+            Task::none()
+        };
+        self.catalogue
+            .record_read(&path)
+            .unwrap_or_else(|err| err.log());
+        if turned {
+            self.catalogue
+                .record_page_read()
+                .unwrap_or_else(|err| err.log());
+        }
+        if let Some(page_time) = page_time {
+            self.record_page_time(page_time);
+        }
+        self.update_item_state(&path, |state| {
+            state.page = page;
+            state.read_state = state.read_state.max(if page + 1 >= page_count {
+                ReadState::Finished
+            } else {
+                ReadState::Reading
+            });
+        });
+        self.shelf = Shelf::load(&self.catalogue);
+        Task::batch([prefetch, self.reveal_strip_page(window_id)])
+    }
+
+    /// Get time each page is shown by reader slideshows.
+    fn slideshow_interval(&self) -> Duration {
+        Duration::from_secs(u64::from(self.settings.slideshow_interval.max(1)))
+    }
+
+    /// Delay slideshow of a reader by a full interval, such that it waits while the
+    /// reader is used.
+    fn delay_slideshow(&mut self, window_id: window::Id) {
+        let interval = self.slideshow_interval();
+        if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id)
+            && let Some(at) = &mut reader.slideshow
+        {
+            *at = Instant::now() + interval;
+        }
+    }
+
+    /// Scroll continuous layout of a reader to an offset.
+    fn scroll_continuous(window_id: window::Id, offset: f32) -> Task<Message> {
+        widget::operation::scroll_to(
+            reader::continuous_id(window_id),
+            AbsoluteOffset {
+                x: None,
+                y: Some(offset),
+            },
+        )
+    }
+
+    /// Scroll continuous layout of a reader to the top of a page, loading pages then
+    /// shown. Offsets reported until done are outdated and ignored.
+    fn jump_continuous(&mut self, window_id: window::Id, page: usize) -> Task<Message> {
+        let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+            return Task::none();
+        };
+        let Some(continuous) = &mut reader.continuous else {
+            return Task::none();
+        };
+        continuous.offset = continuous.top(page);
+        continuous.jumping = true;
+        let scroll = Self::scroll_continuous(window_id, continuous.offset)
+            .chain(Task::done(Message::ContinuousJumped(window_id)));
+        Task::batch([scroll, self.request_continuous_pages(window_id)])
+    }
+
+    /// Load pages near the visible part of the continuous layout of a reader which are
+    /// neither loaded nor being loaded, dropping pages further away.
+    fn request_continuous_pages(&mut self, window_id: window::Id) -> Task<Message> {
+        let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+            return Task::none();
+        };
+        let (Some(page_count), Some(continuous)) = (reader.page_count, &mut reader.continuous)
+        else {
+            return Task::none();
+        };
+        let visible = continuous.visible(page_count);
+        continuous.pages.retain(|page, _| visible.contains(page));
+        let pages = visible
+            .filter(|page| {
+                !continuous.pages.contains_key(page) && !continuous.loading.contains(page)
+            })
+            .collect::<Vec<_>>();
+        if pages.is_empty() {
+            return Task::none();
+        }
+        continuous.loading.extend(pages.iter().copied());
+
+        let (path, adjustments) = (Arc::clone(&reader.path), reader.adjustments);
+        ::smol::unblock(move || {
+            let loaded = Archive::open(&path)
+                .map(|mut archive| {
+                    pages
+                        .iter()
+                        .filter_map(|&page| {
+                            archive
+                                .page(page)
+                                .and_then(|content| content.adjusted(&adjustments))
+                                .inspect_err(|err| {
+                                    ::log::warn!("could not load page {page} of {path:?}\n{err}");
+                                })
+                                .ok()
+                                .map(|content| LoadedPage {
+                                    page,
+                                    dimensions: content.dimensions(),
+                                    image: cache::handle(content),
+                                })
+                        })
+                        .collect()
+                })
+                .unwrap_or_else(|err| {
+                    ::log::warn!("could not open {path:?} for continuous layout\n{err}");
+                    Vec::new()
+                });
+            Message::ContinuousPagesLoaded {
+                window_id,
+                path,
+                adjustments,
+                pages,
+                loaded,
+            }
+        })
+        .pipe(Task::future)
+    }
+
+    /// Load current page of a reader again with its adjustments, remembering them for
+    /// its archive if chosen.
+    fn apply_adjustments(&mut self, window_id: window::Id) -> Task<Message> {
+        let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+            return Task::none();
+        };
+        // Prefetched volumes and loaded pages of continuous layout were adjusted with the
+        // previous adjustments.
+        reader.prefetched = None;
+        if let Some(continuous) = &mut reader.continuous {
+            continuous.pages.clear();
+        }
+        if reader.remember_adjustments {
+            self.catalogue
+                .set_adjustments(&reader.path, Some(&reader.adjustments))
+                .unwrap_or_else(|err| err.log());
+        }
+        Self::load_page(
+            window_id,
+            Arc::clone(&reader.path),
+            reader.page,
+            reader.adjustments,
+        )
+    }
+
+    /// Scroll page strip of a reader such that its current page is visible, loading
+    /// thumbnails of the pages then shown.
+    fn reveal_strip_page(&mut self, window_id: window::Id) -> Task<Message> {
+        let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+            return Task::none();
+        };
+        let page = reader.page;
+        let Some(offset) = reader.strip.as_mut().and_then(|strip| {
+            let offset = strip.reveal(page)?;
+            strip.offset = offset;
+            Some(offset)
+        }) else {
+            return self.request_page_thumbnails(window_id);
+        };
+        let scroll = widget::operation::scroll_to(
+            reader::strip_id(window_id),
+            AbsoluteOffset {
+                x: Some(offset),
+                y: None,
+            },
+        );
+        Task::batch([scroll, self.request_page_thumbnails(window_id)])
+    }
+
+    /// Load first page of the next volume of a reader ahead of time, with the given
+    /// adjustments applied.
+    fn prefetch_volume(
+        window_id: window::Id,
+        path: Arc<Path>,
+        adjustments: Adjustments,
+    ) -> Task<Message> {
+        ::smol::unblock({
+            let path = Arc::clone(&path);
+            move || {
+                let mut archive = Archive::open(&path)?;
+                let page = archive.page(0)?;
+                let animation = Animation::of_page(&page, &adjustments);
+                let page = page.adjusted(&adjustments)?;
+                let dimensions = page.dimensions();
+                Ok::<_, ArchiveError>((
+                    archive.page_count(),
+                    cache::handle(page),
+                    dimensions,
+                    animation,
+                ))
+            }
+        })
+        .pipe(Task::future)
+        .then(move |result| match result {
+            Ok((page_count, image, dimensions, animation)) => {
+                Task::done(Message::VolumePrefetched {
+                    window_id,
+                    path: Arc::clone(&path),
+                    page_count,
+                    image,
+                    dimensions,
+                    animation,
+                })
+            }
+            // Volume is loaded again when opened, reporting the error then.
+            Err(err) => {
+                ::log::warn!("could not prefetch {path:?}, {err}");
+                Task::none()
+            }
+        })
+    }
+
+    /// Load a page for a reader window, with the given adjustments applied.
+    fn load_page(
+        window_id: window::Id,
+        path: Arc<Path>,
+        page: usize,
+        adjustments: Adjustments,
+    ) -> Task<Message> {
+        ::smol::unblock({
+            let path = Arc::clone(&path);
+            move || {
+                let mut archive = Archive::open(&path)?;
+                let content = archive.page(page)?;
+                let animation = Animation::of_page(&content, &adjustments);
+                let content = content.adjusted(&adjustments)?;
+                let dimensions = content.dimensions();
+                Ok::<_, ArchiveError>((
+                    archive.page_count(),
+                    cache::handle(content),
+                    dimensions,
+                    animation,
+                ))
+            }
+        })
+        .pipe(Task::future)
+        .map(move |result| match result {
+            Ok((page_count, image, dimensions, animation)) => Message::PageLoaded {
+                window_id,
+                page,
+                page_count,
+                image,
+                dimensions,
+                animation,
+            },
+            Err(source) => Message::ReportError(Arc::new(Error::Archive {
+                path: path.to_path_buf(),
+                source,
+            })),
+        })
+    }
+
+    /// Activate an item, opening directories in the pane and archives in a reader.
+    fn activate(&mut self, ItemPath { view_path, path }: ItemPath) -> Task<Message> {
+        if let Some(link) = self
+            .get_pane_mut(view_path)
+            .and_then(|pane| pane.feed.as_ref())
+            .and_then(|feed| feed.links.get(&path))
+            .cloned()
+        {
+            self.activate_feed_entry(ItemPath { view_path, path }, link)
+        } else if path.is_dir() {
+            let Some(pane) = self.get_pane_mut(view_path) else {
+                ::log::warn!("could not resolve view path {view_path:?}");
+                return Task::none();
+            };
+            pane.navigate(Arc::clone(&path));
+            self.open_dir(path, None, view_path)
+        } else if ArchiveKind::from_path(&path).is_some() {
+            self.open_archive(path)
+        } else {
+            ::log::info!("cannot open {path:?}, it is not a directory or supported archive");
+            Task::none()
+        }
+    }
+
+    /// Select the item the keyboard cursor of a pane moved to, scrolling it into view.
+    /// Shift extends the selection from the anchor.
+    fn select_cursor(
+        pane: &mut Pane,
+        view_path: ViewPath,
+        target: Option<CursorTarget>,
+        modifiers: Modifiers,
+    ) -> Task<Message> {
+        let Some(CursorTarget {
+            path,
+            offset,
+            shelf_offset,
+        }) = target
+        else {
+            return Task::none();
+        };
+        pane.context_menu = None;
+        let modifiers = if modifiers.shift() {
+            Modifiers::SHIFT
+        } else {
+            Modifiers::default()
+        };
+        let sort = pane.list_sort();
+        pane.view.select(&path, &pane.filter, sort, modifiers);
+
+        let vertical = offset.map(|y| {
+            widget::operation::scroll_to(
+                pane::scroll_id(view_path),
+                AbsoluteOffset {
+                    x: None,
+                    y: Some(y),
+                },
+            )
+        });
+        let horizontal = shelf_offset.map(|(row, x)| {
+            widget::operation::scroll_to(
+                pane::shelf_scroll_id(view_path, row),
+                AbsoluteOffset {
+                    x: Some(x),
+                    y: None,
+                },
+            )
+        });
+        Task::batch(vertical.into_iter().chain(horizontal))
+    }
+
+    /// Move keyboard cursor of a pane, selecting the item it moves to.
+    fn move_cursor(
+        &mut self,
+        view_path: ViewPath,
+        movement: Movement,
+        modifiers: Modifiers,
+    ) -> Task<Message> {
+        let icon_width = self.settings.card_size.width();
+        let sections = self.settings.section_headers;
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        let target = pane.view.move_cursor(
+            movement,
+            &pane.filter,
+            pane.arrangement(sections),
+            icon_width,
+        );
+        Self::select_cursor(pane, view_path, target, modifiers)
+    }
+
+    /// Add typed characters to the type-ahead of a pane, moving its keyboard cursor to
+    /// the first item whose title starts with all characters typed in succession.
+    fn type_ahead(&mut self, view_path: ViewPath, text: &str) -> Task<Message> {
+        let icon_width = self.settings.card_size.width();
+        let sections = self.settings.section_headers;
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        let arrangement = pane.arrangement(sections);
+        let prefix = pane.type_ahead.push(text, Instant::now());
+        let target = pane
+            .view
+            .cursor_to_prefix(prefix, &pane.filter, arrangement, icon_width);
+        Self::select_cursor(pane, view_path, target, Modifiers::default())
+    }
+
+    /// Move keyboard cursor of a pane to the first item listed under a letter of the
+    /// alphabetical index, scrolling it to the top of the view.
+    fn jump_to_letter(&mut self, view_path: ViewPath, letter: char) -> Task<Message> {
+        let icon_width = self.settings.card_size.width();
+        let sections = self.settings.section_headers;
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        let arrangement = pane.arrangement(sections);
+        let target = pane
+            .view
+            .cursor_to_letter(letter, &pane.filter, arrangement, icon_width);
+        Task::batch([
+            Self::select_cursor(pane, view_path, target, Modifiers::default()),
+            self.request_thumbnails(view_path),
+        ])
+    }
+
+    /// Get pane keyboard shortcuts of a window act on, the focused pane if it is in the
+    /// window, otherwise the hovered pane.
+    fn shortcut_pane(&self, window_id: window::Id) -> Option<ViewPath> {
+        [self.focused_pane, self.hovered_pane]
+            .into_iter()
+            .flatten()
+            .find(|view_path| view_path.window_id == window_id)
+    }
+
+    /// Scan the directory or reload the reading list shown by a pane again.
+    fn rescan(&mut self, view_path: ViewPath) -> Task<Message> {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        if let Some(name) = pane.list.clone() {
+            return self.open_reading_list(view_path, ListChoice::List(name));
+        }
+        if let Some(tag) = pane.tag.clone() {
+            return self.open_tag(view_path, tag);
+        }
+        if let Some(feed) = &pane.feed {
+            let (url, back) = (feed.url.clone(), feed.back.clone());
+            return self.load_feed(view_path, url, back);
+        }
+        pane.reload()
+            .map_or_else(Task::none, |path| self.open_dir(path, None, view_path))
+    }
+
+    /// Load an OPDS feed into a pane, with the urls of the feeds navigated from to reach
+    /// it. The current view is shown until the feed is loaded.
+    fn load_feed(&mut self, view_path: ViewPath, url: String, back: Vec<String>) -> Task<Message> {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        pane.cancel_loads();
+        pane.scanning = true;
+        let token = pane.cancel_token();
+        let netrc = opds::netrc(&self.xdg_dirs);
+        ::smol::unblock(move || {
+            let result = opds::load(&url, netrc.as_deref())
+                .map_err(|source| Arc::new(Error::Opds { url, source }));
+            Message::FeedLoaded {
+                view_path,
+                token,
+                back,
+                result,
+            }
+        })
+        .pipe(Task::future)
+    }
+
+    /// Show a loaded OPDS feed in a pane, unless the pane has moved on since it was
+    /// requested.
+    fn show_feed(
+        &mut self,
+        view_path: ViewPath,
+        token: &CancelToken,
+        back: Vec<String>,
+        result: Result<opds::Feed, Arc<Error>>,
+    ) -> Task<Message> {
+        let Some(pane) = self
+            .get_pane_mut(view_path)
+            .filter(|pane| pane.is_current(token))
+        else {
+            return Task::none();
+        };
+        pane.scanning = false;
+        let feed = match result {
+            Ok(feed) => feed,
+            Err(err) => {
+                self.report_error(&err);
+                return Task::none();
+            }
+        };
+        let dir = match opds::cache_dir(&self.xdg_dirs) {
+            Ok(dir) => dir,
+            Err(err) => {
+                self.report_error(&Error::Opds {
+                    url: feed.url,
+                    source: OpdsError::Write(err),
+                });
+                return Task::none();
+            }
+        };
+        let (feed, items) = FeedView::new(feed, &dir, back);
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        pane.open_feed(feed, items);
+        self.request_thumbnails(view_path)
+    }
+
+    /// Activate an item of an OPDS feed, loading feeds it leads to and downloading
+    /// archives to the download cache before opening them.
+    fn activate_feed_entry(&mut self, item_path: ItemPath, link: Link) -> Task<Message> {
+        match link {
+            Link::Navigation(url) => {
+                let back = self
+                    .get_pane_mut(item_path.view_path)
+                    .and_then(|pane| pane.feed.as_ref())
+                    .map(|feed| {
+                        let mut back = feed.back.clone();
+                        back.push(feed.url.clone());
+                        back
+                    })
+                    .unwrap_or_default();
+                self.load_feed(item_path.view_path, url, back)
+            }
+            Link::Acquisition { .. } if item_path.path.is_file() => {
+                self.open_archive(item_path.path)
+            }
+            Link::Acquisition { url, .. } => {
+                let Some(temp_dir) = self.temp_dir.clone() else {
+                    ::log::error!(
+                        "cannot download archives without a directory of temporary files"
+                    );
+                    return Task::none();
+                };
+                ::log::info!("downloading {url}");
+                let netrc = opds::netrc(&self.xdg_dirs);
+                ::smol::unblock(move || {
+                    let result = opds::download(&url, netrc.as_deref(), &item_path.path, &temp_dir)
+                        .map_err(|source| Arc::new(Error::Opds { url, source }));
+                    Message::FeedDownloaded { item_path, result }
+                })
+                .pipe(Task::future)
+            }
+        }
+    }
+
+    /// Scan panes showing a directory again, such that files added to it are listed.
+    fn rescan_dir(&mut self, dir: &Path) -> Task<Message> {
+        self.windows
+            .iter()
+            .filter_map(|(&window_id, window)| match window {
+                Window::Main { panes } => Some(
+                    panes
+                        .iter()
+                        .filter(|(_, pane)| pane.shows_dir() && pane.path.as_deref() == Some(dir))
+                        .map(move |(&pane, _)| ViewPath { window_id, pane }),
+                ),
+                _ => None,
+            })
+            .flatten()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|view_path| self.rescan(view_path))
+            .pipe(Task::batch)
+    }
+
+    /// Queue conversion of selected rar and 7z archives of a view to zip archives,
+    /// replacing them once converted.
+    fn convert_to_cbz(&mut self, view_path: ViewPath) -> Task<Message> {
+        let archives = self.selection_where(view_path, archive::is_convertible);
+        let Some(temp_dir) = self.temp_dir.clone() else {
+            ::log::error!("cannot convert archives without a directory of temporary files");
+            return Task::none();
+        };
+        let quality = Some(self.settings.convert_quality).filter(|quality| *quality > 0);
+        for path in archives {
+            let kind = TaskKind::Convert {
+                temp_dir: Arc::clone(&temp_dir),
+                quality,
+            };
+            self.tasks.push(kind, path);
+        }
+        self.run_tasks()
+    }
+
+    /// Start queued background tasks while limits of running tasks allow, reporting
+    /// their progress.
+    fn run_tasks(&mut self) -> Task<Message> {
+        let throttle = &self.throttle;
+        self.tasks
+            .start_next(throttle.is_throttled())
+            .into_iter()
+            .map(|task| {
+                let (id, kind, path, cancel, throttle) = (
+                    task.id,
+                    task.kind.clone(),
+                    Arc::clone(&task.path),
+                    task.cancel.clone(),
+                    throttle.clone(),
+                );
+                let (sender, receiver) = ::flume::unbounded();
+                let progress = receiver
+                    .into_stream()
+                    .map(move |(done, total)| Message::TaskProgress { id, done, total })
+                    .pipe(Task::stream);
+                let finished = Task::perform(
+                    ::smol::unblock(move || {
+                        BackgroundTask::run(&kind, &path, &cancel, |done, total| {
+                            _ = sender.send((done, total));
+                            throttle.pause();
+                        })
+                    }),
+                    move |result| Message::TaskFinished { id, result },
+                );
+                Task::batch([progress, finished])
+            })
+            .collect::<Vec<_>>()
+            .pipe(Task::batch)
+    }
+
+    /// Apply the result of a background task which finished successfully to the
+    /// catalogue and the panes showing its archive.
+    fn task_done(&mut self, path: &Arc<Path>, output: TaskOutput) -> Task<Message> {
+        match output {
+            TaskOutput::Converted(to) => {
+                let to = Arc::<Path>::from(to);
+                if let Err(err) = self.catalogue.rename(path, &to) {
+                    self.report_error(&err);
+                }
+                if let Err(source) = ::std::fs::remove_file(path) {
+                    self.report_error(&Error::Remove {
+                        path: path.to_path_buf(),
+                        source,
+                    });
+                }
+                let mut view_paths = Vec::new();
+                for (&window_id, window) in &mut self.windows {
+                    let Window::Main { panes } = window else {
+                        continue;
+                    };
+                    for (&pane, state) in panes.iter_mut() {
+                        if let DirView::Dir { items, .. } = &state.view
+                            && items.contains_key(path)
+                        {
+                            state.view.rename_item(path, Some(&to));
+                            view_paths.push(ViewPath { window_id, pane });
+                        }
+                    }
+                }
+                view_paths
+                    .into_iter()
+                    .map(|view_path| self.request_thumbnails(view_path))
+                    .pipe(Task::batch)
+            }
+            TaskOutput::Extracted(dir) => dir
+                .parent()
+                .map_or_else(Task::none, |parent| self.rescan_dir(parent)),
+            TaskOutput::Verified(page_count) => {
+                ::log::info!("verified {page_count} pages of {path:?}");
+                Task::none()
+            }
+            TaskOutput::Thumbnails => Task::none(),
+        }
+    }
+
+    /// Show a rename dialog over the window of a pane, focusing its text input.
+    fn show_rename(&mut self, view_path: ViewPath, mut rename: Rename) -> Task<Message> {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        pane.context_menu = None;
+        let dialog = rename.dialog(view_path);
+        rename.dialog = Some(dialog.id);
+        pane.rename = Some(rename);
+        self.show_dialog(dialog)
+    }
+
+    /// Rename the archive under the keyboard cursor of the pane shortcuts act on, [None]
+    /// if there is no such archive.
+    fn rename_cursor(&mut self) -> Option<Task<Message>> {
+        let view_path = self.shortcut_pane(self.focused_window?)?;
+        let pane = self.get_pane_mut(view_path)?;
+        // Keys are released in the dialog as well, which is left open.
+        if pane.rename.is_some() {
+            return Some(Task::none());
+        }
+        let path = pane
+            .view
+            .cursor()
+            .filter(|path| ArchiveKind::from_path(path).is_some())?;
+        Some(Task::done(Message::Rename(ItemPath {
+            view_path,
+            path: Arc::clone(path),
+        })))
+    }
+
+    /// Drop dragged archives onto a pane, asking whether they should be transferred to the
+    /// directory it shows. Holding the command modifier copies them instead of moving them.
+    fn drop_onto(&mut self, view_path: ViewPath) -> Task<Message> {
+        let Some(drag) = self.drag.take() else {
+            return Task::none();
+        };
+        if drag.source == view_path || self.transfer.is_some() {
+            return Task::none();
+        }
+        let kind = if self.modifiers.command() {
+            TransferKind::Copy
+        } else {
+            TransferKind::Move
+        };
+        let Some(dir) = self
+            .get_pane_mut(view_path)
+            .filter(|pane| pane.shows_dir())
+            .and_then(|pane| pane.path.clone())
+        else {
+            return Task::none();
+        };
+        // Archives are already in the directory.
+        if drag.paths.iter().all(|path| path.parent() == Some(&dir)) {
+            return Task::none();
+        }
+        let transfer = Transfer::new(drag, kind, view_path, dir);
+        let dialog = transfer.confirm_dialog();
+        self.transfer = Some(transfer);
+        self.show_dialog(dialog)
+    }
+
+    /// Transfer the next archive of a confirmed transfer, rescanning the panes it was
+    /// transferred between once every archive has been transferred.
+    fn transfer_next(&mut self) -> Task<Message> {
+        let Some(transfer) = &mut self.transfer else {
+            return Task::none();
+        };
+        let Some(from) = transfer.next().cloned() else {
+            let Some(transfer) = self.transfer.take() else {
+                return Task::none();
+            };
+            if let Some(id) = transfer.dialog {
+                self.close_dialog(id);
+            }
+            return Task::batch([self.rescan(transfer.source), self.rescan(transfer.target)]);
+        };
+        let (kind, dir) = (transfer.kind, Arc::clone(&transfer.dir));
+        let resolution = transfer.take_resolution();
+        Task::perform(
+            ::smol::unblock(move || {
+                let size = ::std::fs::metadata(&from).map_or(0, |metadata| metadata.len());
+                let result =
+                    transfer::transfer_file(kind, &from, &dir, resolution).map_err(Arc::new);
+                (from, size, result)
+            }),
+            |(from, size, result)| Message::TransferStepped { from, size, result },
+        )
+    }
+
+    /// Ask how a transferred archive is resolved against an existing file at path, the
+    /// transfer continues once answered.
+    fn ask_transfer_conflict(&mut self, to: &Path) -> Task<Message> {
+        let Some(transfer) = &self.transfer else {
+            return Task::none();
+        };
+        let dir = list_entry_name(&transfer.dir);
+        let dialog = Dialog::choose(
+            transfer.target.window_id,
+            tr_args("dialog-file-exists", &[("name", &list_entry_name(to))]),
+            [
+                "dialog-conflict-skip",
+                "dialog-conflict-overwrite",
+                "dialog-conflict-rename",
+            ]
+            .map(tr)
+            .to_vec(),
+            |index, all| {
+                let resolution = match index {
+                    1 => Resolution::Overwrite,
+                    2 => Resolution::Rename,
+                    _ => Resolution::Skip,
+                };
+                Message::ResolveTransferConflict(resolution, all)
+            },
+        )
+        .body(tr_args("dialog-file-exists-body", &[("dir", &dir)]))
+        .apply_to_all()
+        .on_cancel(Message::StopTransfer);
+        self.show_dialog(dialog)
+    }
+
+    /// Sort items of a pane by the column after the one they are sorted by, or reverse
+    /// the order if reverse is set.
+    fn cycle_sort(&mut self, view_path: ViewPath, reverse: bool) -> Task<Message> {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        let column = match pane.sort {
+            Some(sort) if reverse => sort.column,
+            Some(sort) => Column::ALL
+                .into_iter()
+                .cycle()
+                .skip_while(|column| *column != sort.column)
+                .nth(1)
+                .unwrap_or(Column::Name),
+            None => Column::Name,
+        };
+        Task::done(Message::SortBy(view_path, column))
+    }
+
+    /// Show a quick look of the focused archive of a pane, loading its first pages, or
+    /// close the quick look if one is shown.
+    fn toggle_quick_look(&mut self, view_path: ViewPath) -> Task<Message> {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        if pane.quick_look.take().is_some() {
+            return Task::none();
+        }
+        let Some(path) = pane
+            .view
+            .focused()
+            .filter(|path| ArchiveKind::from_path(path).is_some())
+            .cloned()
+        else {
+            return Task::none();
+        };
+        pane.context_menu = None;
+        pane.quick_look = Some(QuickLook::new(Arc::clone(&path)));
+
+        ::smol::unblock({
+            let path = Arc::clone(&path);
+            move || {
+                let mut archive = Archive::open(&path)?;
+                let page_count = archive.page_count();
+                let mut pages = Vec::with_capacity(QUICK_LOOK_PAGES.min(page_count));
+                for page in 0..QUICK_LOOK_PAGES.min(page_count) {
+                    // Only the cover is shown large enough to need full resolution.
+                    let page = archive.page(page)?;
+                    let page = if pages.is_empty() {
+                        page.decoded()?
+                    } else {
+                        page.thumbnail(THUMBNAIL_SIZE)?
+                    };
+                    pages.push(cache::handle(page));
+                }
+                Ok::<_, ArchiveError>((page_count, pages))
+            }
+        })
+        .pipe(Task::future)
+        .map(move |result| match result {
+            Ok((page_count, pages)) => Message::QuickLookLoaded {
+                view_path,
+                path: Arc::clone(&path),
+                page_count,
+                pages,
+            },
+            Err(source) => Message::ReportError(Arc::new(Error::Archive {
+                path: path.to_path_buf(),
+                source,
+            })),
+        })
+    }
+
+    /// Record time spent reading a page, updating the estimated time spent reading a page
+    /// once enough pages have been measured.
+    fn record_page_time(&mut self, page_time: Duration) {
+        if let Err(err) = self.catalogue.record_page_time(page_time) {
+            err.log();
+            return;
+        }
+        match self.catalogue.page_time() {
+            Ok(Some(page_time)) => self.page_time = page_time,
+            Ok(None) => {}
+            Err(err) => err.log(),
+        }
+    }
+
+    /// Open archive at path with the application preferred for it or its kind, or in a
+    /// reader if there is none or it is not available.
+    fn open_archive(&mut self, path: Arc<Path>) -> Task<Message> {
+        let preferred = self
+            .catalogue
+            .preferred_application(&path)
+            .unwrap_or_else(|err| {
+                err.log();
+                None
+            });
+        let Some(name) = preferred else {
+            return self.open_reader(path);
+        };
+        let application = open_with::applications(&self.settings.open_with, &self.xdg_dirs, &path)
+            .into_iter()
+            .find(|application| application.name == name);
+        match application {
+            Some(application) => {
+                if let Err(err) = application.launch(&path) {
+                    self.report_error(&err);
+                }
+                Task::none()
+            }
+            None => {
+                ::log::warn!("preferred application {name:?} of {path:?} is not available");
+                self.open_reader(path)
+            }
+        }
+    }
+
+    /// Change card size by the given amount of steps, saving it to the config file
+    /// without saving other unsaved changes to settings.
+    fn resize_cards(&mut self, view_path: ViewPath, steps: i32) -> Task<Message> {
+        let card_size = self.settings.card_size.resized(steps);
+        if steps == 0 || card_size == self.settings.card_size {
+            return Task::none();
+        }
+        self.settings.card_size = card_size;
+        self.draft.card_size = card_size;
+        self.saved_settings.card_size = card_size;
+        if let Err(err) = self.overrides.save(&self.saved_settings, &self.xdg_dirs) {
+            self.report_error(&err);
+        }
+        if self.grow_thumbnails(card_size) {
+            return self.request_all_thumbnails();
+        }
+        self.request_thumbnails(view_path)
+    }
+
+    /// Drop cached thumbnails if cards of the given size need larger thumbnails than those
+    /// loaded for the current card size, returns true if dropped. Thumbnails are kept when
+    /// cards shrink, as downscaling them when drawn is cheap.
+    fn grow_thumbnails(&mut self, card_size: CardSize) -> bool {
+        let grown = card_size.thumbnail_size() > self.thumbnail_size;
+        if grown {
+            self.thumbnail_cache.clear();
+        }
+        self.thumbnail_size = card_size.thumbnail_size();
+        grown
+    }
+
+    /// Resize cards of a pane of a main window as fingers pinch on it, the hovered or
+    /// focused pane of the window, or its first pane if neither is in it.
+    fn pinch_cards(&mut self, window_id: window::Id, event: touch::Event) -> Task<Message> {
+        let steps = self
+            .pinch
+            .touched(event, f32::from(self.settings.pinch_distance));
+        if steps == 0 {
+            return Task::none();
+        }
+        let view_path = [self.hovered_pane, self.focused_pane]
+            .into_iter()
+            .flatten()
+            .find(|view_path| view_path.window_id == window_id);
+        let view_path = match (view_path, self.windows.get(&window_id)) {
+            (Some(view_path), _) => view_path,
+            (None, Some(Window::Main { panes })) => match panes.iter().next() {
+                Some((pane, _)) => ViewPath {
+                    window_id,
+                    pane: *pane,
+                },
+                None => return Task::none(),
+            },
+            (None, _) => return Task::none(),
+        };
+        self.resize_cards(view_path, steps)
+    }
+
+    /// Open archive at path in a new reader window.
+    fn open_reader(&self, path: Arc<Path>) -> Task<Message> {
+        let (_, open_window) = self.open_window(WindowKind::Reader, window::Settings::default());
+        open_window.map(move |id| Message::AddReaderWindow(id, Arc::clone(&path)))
+    }
+
+    /// Open a window using remembered geometry of its kind where allowed.
+    fn open_window(
+        &self,
+        kind: WindowKind,
+        window_settings: window::Settings,
+    ) -> (window::Id, Task<window::Id>) {
+        window::open(self.geometries.apply(kind, &self.settings, window_settings))
+    }
+
+    /// Open settings window, or close it if already open, prompting if there are
+    /// unapplied changes.
+    fn toggle_settings(&mut self) -> Task<Message> {
+        let open = self
+            .windows
+            .iter()
+            .find(|(_id, ty)| matches!(ty, Window::Settings))
+            .map(|(id, _ty)| *id);
+
+        if let Some(window_id) = open {
+            self.request_close_settings(window_id)
+        } else {
+            let (_, task) = self.open_window(
+                WindowKind::Settings,
+                window::Settings {
+                    size: Size {
+                        width: 400.0,
+                        height: 400.0,
+                    },
+                    // Closing is handled by application, to prompt for unapplied changes.
+                    exit_on_close_request: false,
+                    ..window::Settings::default()
+                },
+            );
+            task.map(Message::AddSettingsWindow)
+        }
+    }
+
+    /// Close settings windows, asking whether unapplied changes are applied or discarded
+    /// over settings window first if there are any.
+    fn request_close_settings(&mut self, window_id: window::Id) -> Task<Message> {
+        if self.settings_close_prompt {
+            Task::none()
+        } else if self.has_unapplied_settings() {
+            self.settings_close_prompt = true;
+            let dialog = Dialog::choose(
+                window_id,
+                tr("settings-unapplied"),
+                ["button-apply", "button-discard"].map(tr).to_vec(),
+                |index, _all| {
+                    if index == 0 {
+                        Message::ApplySettings
+                    } else {
+                        Message::RevertSettings
+                    }
+                },
+            )
+            .on_cancel(Message::CancelCloseSettings);
+            self.show_dialog(dialog)
+        } else {
+            self.close_settings()
+        }
+    }
+
+    /// Open undo history window, or close it if already open.
+    fn toggle_undo_history(&mut self) -> Task<Message> {
+        let open = self
+            .windows
+            .iter()
+            .find(|(_, window)| matches!(window, Window::History))
+            .map(|(&id, _)| id);
+        if let Some(id) = open {
+            return window::close(id);
+        }
+        let (_, task) = self.open_window(
+            WindowKind::History,
+            window::Settings {
+                size: Size {
+                    width: 500.0,
+                    height: 400.0,
+                },
+                ..window::Settings::default()
+            },
+        );
+        task.map(Message::AddHistoryWindow)
+    }
+
+    /// Open window listing keyboard shortcuts, or close it if already open.
+    fn toggle_help(&mut self) -> Task<Message> {
+        let open = self
+            .windows
+            .iter()
+            .find(|(_, window)| matches!(window, Window::Help))
+            .map(|(&id, _)| id);
+        if let Some(id) = open {
+            return window::close(id);
+        }
+        let (_, task) = self.open_window(
+            WindowKind::Help,
+            window::Settings {
+                size: Size {
+                    width: 500.0,
+                    height: 600.0,
+                },
+                ..window::Settings::default()
+            },
+        );
+        task.map(Message::AddHelpWindow)
+    }
+
+    /// Open about window, or close it if already open.
+    fn toggle_about(&mut self) -> Task<Message> {
+        let open = self
+            .windows
+            .iter()
+            .find(|(_, window)| matches!(window, Window::About(..)))
+            .map(|(&id, _)| id);
+        if let Some(id) = open {
+            return window::close(id);
+        }
+        let (_, task) = self.open_window(
+            WindowKind::About,
+            window::Settings {
+                size: Size {
+                    width: 500.0,
+                    height: 600.0,
+                },
+                ..window::Settings::default()
+            },
+        );
+        task.map(Message::AddAboutWindow)
+    }
+
+    /// Open statistics window, or close it if already open.
+    fn toggle_statistics(&mut self) -> Task<Message> {
+        let open = self
+            .windows
+            .iter()
+            .find(|(_, window)| matches!(window, Window::Stats(..)))
+            .map(|(&id, _)| id);
+        if let Some(id) = open {
+            return window::close(id);
+        }
+        let (_, task) = self.open_window(
+            WindowKind::Stats,
+            window::Settings {
+                size: Size {
+                    width: 600.0,
+                    height: 600.0,
+                },
+                ..window::Settings::default()
+            },
+        );
+        task.map(Message::AddStatsWindow)
+    }
+
+    /// Open calendar window, or close it if already open.
+    fn toggle_calendar(&mut self) -> Task<Message> {
+        let open = self
+            .windows
+            .iter()
+            .find(|(_, window)| matches!(window, Window::Calendar(..)))
+            .map(|(&id, _)| id);
+        if let Some(id) = open {
+            return window::close(id);
+        }
+        let (_, task) = self.open_window(
+            WindowKind::Calendar,
+            window::Settings {
+                size: Size {
+                    width: 600.0,
+                    height: 700.0,
+                },
+                ..window::Settings::default()
+            },
+        );
+        task.map(Message::AddCalendarWindow)
+    }
+
+    /// Open window of archives moved to trash, or close it if already open.
+    fn toggle_trash(&mut self) -> Task<Message> {
+        let open = self
+            .windows
+            .iter()
+            .find(|(_, window)| matches!(window, Window::Trash(..)))
+            .map(|(&id, _)| id);
+        if let Some(id) = open {
+            return window::close(id);
+        }
+        let (_, task) = self.open_window(
+            WindowKind::Trash,
+            window::Settings {
+                size: Size {
+                    width: 600.0,
+                    height: 500.0,
+                },
+                ..window::Settings::default()
+            },
+        );
+        task.map(Message::AddTrashWindow)
+    }
+
+    /// Load archives moved to trash, errors are logged and leave the list empty.
+    fn load_trashed(&self) -> Vec<Trashed> {
+        self.catalogue
+            .trashed()
+            .inspect_err(Error::log)
+            .unwrap_or_default()
+    }
+
+    /// Reload archives listed by open trash windows.
+    fn reload_trash_windows(&mut self) {
+        let trashed = self.load_trashed();
+        for window in self.windows.values_mut() {
+            if let Window::Trash(listed) = window {
+                listed.clone_from(&trashed);
+            }
+        }
+    }
+
+    /// Get selected archives of a view which may be extracted, closing its context menu.
+    fn extractable_selection(&mut self, view_path: ViewPath) -> Vec<Arc<Path>> {
+        self.selection_where(view_path, archive::is_extractable)
+    }
+
+    /// Get selected items of a view matching a predicate, closing its context menu.
+    fn selection_where(
+        &mut self,
+        view_path: ViewPath,
+        matches: fn(&Path) -> bool,
+    ) -> Vec<Arc<Path>> {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Vec::new();
+        };
+        pane.context_menu = None;
+        let DirView::Dir { selected, .. } = &pane.view else {
+            return Vec::new();
+        };
+        selected
+            .iter()
+            .filter(|path| matches(path))
+            .cloned()
+            .collect()
+    }
+
+    /// Get selected zip archives of a view, whose metadata may be edited, closing its
+    /// context menu.
+    fn editable_selection(&mut self, view_path: ViewPath) -> Vec<Arc<Path>> {
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Vec::new();
+        };
+        pane.context_menu = None;
+        let DirView::Dir { selected, .. } = &pane.view else {
+            return Vec::new();
+        };
+        let mut paths = selected
+            .iter()
+            .filter(|path| ArchiveKind::from_path(path) == Some(ArchiveKind::Cbz))
+            .cloned()
+            .collect::<Vec<_>>();
+        paths.sort_unstable();
+        paths
+    }
+
+    /// Get metadata editor of a window.
+    fn metadata_editor_mut(&mut self, id: window::Id) -> Option<&mut MetadataEditor> {
+        match self.windows.get_mut(&id) {
+            Some(Window::Metadata(editor)) => Some(editor),
+            _ => None,
+        }
+    }
+
+    /// Queue archives to be extracted into a directory, or next to themselves if [None].
+    fn extract(&mut self, paths: Vec<Arc<Path>>, dir: Option<&Arc<Path>>) -> Task<Message> {
+        for path in paths {
+            let Some(dir) = dir.cloned().or_else(|| path.parent().map(Arc::from)) else {
+                continue;
+            };
+            self.tasks.push(TaskKind::Extract { dir }, path);
+        }
+        self.run_tasks()
+    }
+
+    /// Move selected archives of a view to trash.
+    fn move_to_trash(&mut self, view_path: ViewPath) -> Task<Message> {
+        let Some(trash_dir) = trash::dir(&self.xdg_dirs) else {
+            ::log::error!("cannot move archives to trash without a data directory");
+            return Task::none();
+        };
+        let Some(pane) = self.get_pane_mut(view_path) else {
+            return Task::none();
+        };
+        pane.context_menu = None;
+        let DirView::Dir { selected, .. } = &pane.view else {
+            return Task::none();
+        };
+        let archives = selected
+            .iter()
+            .filter(|path| ArchiveKind::from_path(path).is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+        Task::batch(archives.into_iter().map(|from| {
+            let trash_dir = trash_dir.clone();
+            Task::perform(
+                ::smol::unblock(move || {
+                    let result = trash::trash(&trash_dir, &from).map_err(Arc::new);
+                    (from, result)
+                }),
+                move |(from, result)| Message::MovedToTrash {
+                    view_path,
+                    from,
+                    result,
+                },
+            )
+        }))
+    }
+
+    /// Copy command line reproducing profile, theme and the directories and archives of
+    /// open windows to clipboard, every pane is opened in a window of its own.
+    fn copy_launch_command(&self) -> Task<Message> {
+        let mut paths = Vec::<PathBuf>::new();
+        for window in self.windows.values() {
+            let window_paths = match window {
+                Window::Main { panes } => panes
+                    .iter()
+                    .filter_map(|(_, pane)| pane.path.clone())
+                    .collect(),
+                Window::Reader(reader) => vec![Arc::clone(&reader.path)],
+                Window::Settings
+                | Window::Home(..)
+                | Window::History
+                | Window::Help
+                | Window::About(..)
+                | Window::Stats(..)
+                | Window::Calendar(..)
+                | Window::Trash(..)
+                | Window::Metadata(..) => Vec::new(),
+            };
+            for path in window_paths {
+                if !paths.iter().any(|listed| **listed == *path) {
+                    paths.push(path.to_path_buf());
+                }
+            }
+        }
+        let command = self.cli.launch_command(self.settings.theme, &paths);
+        ::log::info!("copied launch command {command}");
+        ::iced::clipboard::write(command)
+    }
+
+    /// Copy paths or names of selected items of a view, or the cover of the first selected
+    /// archive, to the clipboard.
+    fn copy_selection(&mut self, view_path: ViewPath, kind: CopyKind) -> Task<Message> {
+        if kind != CopyKind::Cover {
+            let paths = self.selection_where(view_path, |_| true);
+            if paths.is_empty() {
+                return Task::none();
+            }
+            return ::iced::clipboard::write(clipboard::text(kind, &paths));
+        }
+        let Some(path) = self
+            .selection_where(view_path, |path| ArchiveKind::from_path(path).is_some())
+            .into_iter()
+            .next()
+        else {
+            return Task::none();
+        };
+        let cover = self.catalogue.cover(&path).unwrap_or_else(|err| {
+            err.log();
+            None
+        });
+        ::smol::unblock(move || {
+            let result = clipboard::copy_cover(&path, &cover.unwrap_or(Cover::Page(0)));
+            Message::CoverCopied(result.map_err(Arc::new))
+        })
+        .pipe(Task::future)
+    }
+
+    /// Open pasted paths from a pane, the first directory is shown in the pane and archives
+    /// are opened.
+    fn paste(&mut self, view_path: ViewPath, text: &str) -> Task<Message> {
+        let mut shown_dir = false;
+        let mut tasks = Vec::new();
+        for path in clipboard::paths(text) {
+            let path = Arc::<Path>::from(path);
+            if path.is_dir() && !shown_dir {
+                shown_dir = true;
+                tasks.push(self.show_sidebar_entry(view_path, sidebar::Entry::Dir(path)));
+            } else if ArchiveKind::from_path(&path).is_some() && path.is_file() {
+                tasks.push(self.open_archive(path));
+            } else {
+                ::log::warn!("cannot open pasted path {path:?}");
+            }
+        }
+        Task::batch(tasks)
+    }
+
+    /// Back up settings and catalogue of profile to a new file in its backup directory.
+    fn back_up_profile(&self) -> Task<Message> {
+        let Some(target) = backup::default_path(&self.xdg_dirs, &self.cli.profile) else {
+            ::log::warn!("cannot back up profile without a data directory");
+            return Task::none();
+        };
+        let xdg_dirs = self.xdg_dirs.clone();
+        ::smol::unblock(move || {
+            let result = Catalogue::open(&xdg_dirs)
+                .and_then(|catalogue| backup::create(&xdg_dirs, &catalogue, &target))
+                .map(|()| target)
+                .map_err(Arc::new);
+            Message::ProfileBackedUp(result)
+        })
+        .pipe(Task::future)
+    }
+
+    /// Show a dialog over its window, focusing its text input if it has one.
+    fn show_dialog(&mut self, dialog: Dialog) -> Task<Message> {
+        let task = if matches!(dialog.kind, dialog::Kind::Prompt { .. }) {
+            widget::operation::focus(dialog::input_id(dialog.id))
+        } else {
+            Task::none()
+        };
+        self.dialogs.push(dialog);
+        task
+    }
+
+    /// Close a dialog without answering it, returning it if it was shown.
+    fn close_dialog(&mut self, id: DialogId) -> Option<Dialog> {
+        let index = self.dialogs.iter().position(|dialog| dialog.id == id)?;
+        Some(self.dialogs.remove(index))
+    }
+
+    /// Get dialog shown over a window, if any.
+    fn top_dialog(&self, window_id: window::Id) -> Option<&Dialog> {
+        self.dialogs
+            .iter()
+            .rev()
+            .find(|dialog| dialog.window_id == window_id)
+    }
+
+    /// Restore settings and catalogue of profile from backup chosen in settings window,
+    /// loading the profile again once restored. Dialog is shown while restoring.
+    fn restore_backup(&mut self, dialog: DialogId) -> Task<Message> {
+        let Some(source) = self
+            .selected_backup
+            .take()
+            .zip(backup::dir(&self.xdg_dirs))
+            .map(|(name, dir)| dir.join(name))
+        else {
+            return Task::none();
+        };
+        let xdg_dirs = self.xdg_dirs.clone();
+        ::smol::unblock(move || {
+            let result = backup::restore(&xdg_dirs, &source).map_err(Arc::new);
+            Message::ProfileRestored { dialog, result }
+        })
+        .pipe(Task::future)
+    }
+
+    /// Perform an action, pane actions apply to the hovered pane.
+    fn perform(&mut self, action: Action) -> Task<Message> {
+        self.perform_on(action, self.hovered_pane)
+    }
+
+    /// Perform an action, pane scoped actions acting on the given pane.
+    fn perform_on(&mut self, action: Action, target: Option<ViewPath>) -> Task<Message> {
+        match action {
+            Action::None => Task::none(),
+            Action::MarkRead | Action::MarkUnread => {
+                if let Some(view_path) = target {
+                    self.set_read_state(
+                        view_path,
+                        if action == Action::MarkRead {
+                            ReadState::Finished
+                        } else {
+                            ReadState::Unread
+                        },
+                    );
+                }
+                Task::none()
+            }
+            Action::Undo => {
+                self.undo_read_state();
+                Task::none()
+            }
+            Action::Rename => self.rename_cursor().unwrap_or_else(Task::none),
+            Action::ToggleSettings => self.toggle_settings(),
+            Action::ToggleUndoHistory => self.toggle_undo_history(),
+            Action::ToggleShortcuts => self.toggle_help(),
+            Action::ToggleAbout => self.toggle_about(),
+            Action::ToggleStatistics => self.toggle_statistics(),
+            Action::ToggleCalendar => self.toggle_calendar(),
+            Action::ToggleTrash => self.toggle_trash(),
+            Action::ToggleSidebar => {
+                self.sidebar_collapsed = !self.sidebar_collapsed;
+                Task::none()
+            }
+            Action::CopyLaunchCommand => self.copy_launch_command(),
+            Action::BackUpProfile => self.back_up_profile(),
+            Action::ToggleDebugOverlay => {
+                self.debug_stats = if self.debug_stats.is_some() {
+                    None
+                } else {
+                    Some(self.debug_stats())
+                };
+                Task::none()
+            }
+            Action::Back | Action::Forward => {
+                let Some(view_path) = target else {
+                    return Task::none();
+                };
+                let Some(pane) = self.get_pane_mut(view_path) else {
+                    return Task::none();
+                };
+                // Going back from a feed returns to the feed navigated from, and from
+                // the first feed to the directory of the pane.
+                if action == Action::Back
+                    && let Some(feed) = &mut pane.feed
+                    && let Some(url) = feed.back.pop()
+                {
+                    let back = ::core::mem::take(&mut feed.back);
+                    return self.load_feed(view_path, url, back);
+                }
+                // Going back from a reading list returns to the directory of the pane.
+                let path = if action == Action::Back && !pane.shows_dir() {
+                    pane.reload()
+                } else if action == Action::Back {
+                    pane.back()
+                } else {
+                    pane.forward()
+                };
+                path.map_or_else(Task::none, |path| self.open_dir(path, None, view_path))
+            }
+            Action::NewPane => {
+                let Some(ViewPath { window_id, pane }) = target else {
+                    return Task::none();
+                };
+                let Some(Window::Main { panes }) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                let path = panes.get(pane).and_then(|pane| pane.path.clone());
+                let mut new_pane = Pane::default();
+                if let Some(path) = &path {
+                    new_pane.navigate(Arc::clone(path));
+                }
+                let Some((pane, _)) = panes.split(pane_grid::Axis::Vertical, pane, new_pane) else {
+                    return Task::none();
+                };
+                path.map_or_else(Task::none, |path| {
+                    self.open_dir(path, None, ViewPath { window_id, pane })
+                })
+            }
+            Action::ClosePane => {
+                let Some(view_path) = target else {
+                    return Task::none();
+                };
+                for pane in [&mut self.hovered_pane, &mut self.focused_pane] {
+                    if *pane == Some(view_path) {
+                        *pane = None;
+                    }
+                }
+                if let Some(Window::Main { panes }) = self.windows.get_mut(&view_path.window_id)
+                    && let Some((mut pane, _)) = panes.close(view_path.pane)
+                {
+                    pane.cancel_loads();
+                }
+                Task::none()
+            }
+        }
+    }
+
+    /// Get factor a window is scaled by.
+    const fn scale_factor(&self, _id: window::Id) -> f32 {
+        self.settings
+            .scale_factor
+            .clamp(MIN_SCALE_FACTOR, MAX_SCALE_FACTOR)
+    }
+
+    /// Scale the user interface by a step, or reset its scale if [None]. The scale is
+    /// applied at once and saved along with other settings.
+    fn step_scale_factor(&mut self, step: Option<f32>) {
+        let scale_factor = step.map_or(1.0, |step| {
+            // Rounded such that repeated steps do not accumulate errors.
+            ((self.settings.scale_factor + step) * 10.0).round() / 10.0
+        });
+        let scale_factor = scale_factor.clamp(MIN_SCALE_FACTOR, MAX_SCALE_FACTOR);
+        self.settings.scale_factor = scale_factor;
+        self.draft.scale_factor = scale_factor;
+    }
+
+    /// Get theme of a window, [None] if the system color scheme should be matched.
+    fn theme(&self, id: window::Id) -> Option<Theme> {
+        match (self.windows.get(&id), self.settings.reader_theme) {
+            (Some(Window::Reader(..)), Some(theme)) => Some(theme.into()),
+            _ => self.settings.theme.theme(),
+        }
+    }
+
+    /// Get Application title.
+    fn title(&self, id: window::Id) -> String {
+        match self.windows.get(&id) {
+            Some(Window::Settings) => window_title(&tr("title-settings")),
+            Some(Window::Home(..)) => window_title(&tr("title-home")),
+            Some(Window::History) => window_title(&tr("title-undo-history")),
+            Some(Window::Help) => window_title(&tr("title-shortcuts")),
+            Some(Window::About(..)) => window_title(&tr("title-about")),
+            Some(Window::Stats(..)) => window_title(&tr("title-statistics")),
+            Some(Window::Calendar(..)) => window_title(&tr("title-calendar")),
+            Some(Window::Trash(..)) => window_title(&tr("title-recently-deleted")),
+            Some(Window::Metadata(..)) => window_title(&tr("title-edit-metadata")),
+            Some(Window::Reader(reader)) => window_title(
+                &reader
+                    .path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy(),
+            ),
+            _ => tr("title"),
+        }
+    }
+
+    /// Get application subscriptions.
+    fn subscription(&self) -> Subscription<Message> {
+        let close_window = window::close_events().map(Message::RemoveWindow);
+        // Only emitted for settings windows, other windows close on request.
+        let close_request = window::close_requests().map(Message::CloseSettingsRequested);
+        let key_event = ::iced::keyboard::listen().map(Message::KeyEvent);
+        let window_event = window::events().filter_map(|(id, event)| match event {
+            window::Event::Focused => Some(Message::WindowFocused(id)),
+            window::Event::Unfocused => Some(Message::WindowUnfocused(id)),
+            window::Event::Opened { position, size } => Some(Message::WindowOpened {
+                window_id: id,
+                position,
+                size,
+            }),
+            window::Event::Moved(position) => Some(Message::WindowMoved(id, position)),
+            window::Event::Resized(size) => Some(Message::WindowResized(id, size)),
+            _ => None,
+        });
+        let mouse_button = ::iced::event::listen_with(|event, status, id| match event {
+            // Presses handled by widgets, such as middle clicks on cards, are not bound.
+            ::iced::Event::Mouse(mouse::Event::ButtonPressed(button))
+                if status == ::iced::event::Status::Ignored =>
+            {
+                Some(Message::MouseButtonPressed(id, button))
+            }
+            ::iced::Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                Some(Message::MouseScrolled(id, delta))
+            }
+            _ => None,
+        });
+
+        #[cfg(feature = "upower")]
+        let on_battery = if self.settings.battery_saver {
+            Subscription::run(power::on_battery).map(Message::OnBatteryChanged)
+        } else {
+            Subscription::none()
+        };
+        #[cfg(not(feature = "upower"))]
+        let on_battery = Subscription::none();
+
+        let settings_changes =
+            config::path(&self.xdg_dirs).map_or_else(Subscription::none, |path| {
+                Subscription::run_with(Arc::<Path>::from(path), config_watch::settings_changes).map(
+                    |result| match result {
+                        Ok(settings) => Message::SettingsChangedOnDisk(settings),
+                        Err(err) => Message::ReportError(Arc::new(err)),
+                    },
+                )
+            });
+
+        let instance_requests = self
+            .socket
+            .as_ref()
+            .map_or_else(Subscription::none, |socket| {
+                Subscription::run_with(Arc::clone(socket), instance::requests)
+                    .map(Message::InstanceRequest)
+            });
+
+        #[cfg(feature = "dbus")]
+        let dbus_requests = Subscription::run(dbus::requests).map(Message::InstanceRequest);
+        #[cfg(not(feature = "dbus"))]
+        let dbus_requests = Subscription::none();
+
+        let debug_tick = if self.debug_stats.is_some() {
+            ::iced::time::every(Duration::from_secs(1)).map(|_| Message::DebugTick)
+        } else {
+            Subscription::none()
+        };
+
+        // Indicators only matter while they are looked at, they catch up once a window of
+        // application is focused again.
+        let idle = self.focused_window.is_none();
+
+        let spinner_tick = if self.is_busy() && !idle {
+            ::iced::time::every(Duration::from_millis(150)).map(|_| Message::SpinnerTick)
+        } else {
+            Subscription::none()
+        };
+
+        let hover_tick = if self
+            .hover_preview
+            .as_ref()
+            .is_some_and(|preview| preview.shown)
+        {
+            ::iced::time::every(PAGE_INTERVAL).map(|_| Message::HoverTick)
+        } else {
+            Subscription::none()
+        };
+
+        let library_changes = if self.settings.library_roots.is_empty() {
+            Subscription::none()
+        } else {
+            Subscription::run_with(
+                Arc::<[PathBuf]>::from(self.settings.library_roots.as_slice()),
+                library::changes,
+            )
+            .map(Message::LibraryChanged)
+        };
+
+        let animation_tick = if self.animates_covers()
+            && self.thumbnail_cache.is_animating()
+            && !idle
+            || self.windows.values().any(
+                |window| matches!(window, Window::Reader(reader) if reader.animation.is_some()),
+            ) {
+            ::iced::time::every(animation::TICK).map(|_| Message::AnimationTick)
+        } else {
+            Subscription::none()
+        };
+
+        let readers = self
+            .windows
+            .values()
+            .filter_map(|window| match window {
+                Window::Reader(reader) => Some(reader),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let touch = if readers.is_empty() {
+            Subscription::none()
+        } else {
+            ::iced::event::listen_with(|event, _status, id| match event {
+                ::iced::Event::Touch(event) => Some(Message::Touched(id, event)),
+                _ => None,
+            })
+        };
+        let glide_tick = if readers.iter().any(|reader| reader.gestures.is_gliding()) {
+            ::iced::time::every(gesture::TICK).map(|_| Message::GlideTick)
+        } else {
+            Subscription::none()
+        };
+
+        let slideshow_tick = if readers.iter().any(|reader| reader.slideshow.is_some()) {
+            ::iced::time::every(reader::SLIDESHOW_TICK).map(|_| Message::SlideshowTick)
+        } else {
+            Subscription::none()
+        };
+
+        let clock_tick = if self.shows_relative_time() && !idle {
+            ::iced::time::every(Duration::from_secs(60)).map(|_| Message::ClockTick)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([
+            close_window,
+            close_request,
+            key_event,
+            window_event,
+            mouse_button,
+            on_battery,
+            settings_changes,
+            instance_requests,
+            dbus_requests,
+            debug_tick,
+            spinner_tick,
+            hover_tick,
+            animation_tick,
+            touch,
+            glide_tick,
+            slideshow_tick,
+            clock_tick,
+            library_changes,
+        ])
+    }
+
+    /// Hold back a message needing the catalogue while it is being loaded, showing a
+    /// placeholder for the window it adds. Messages which may be handled right away are
+    /// given back.
+    fn defer(&mut self, message: Message) -> Option<Message> {
+        if !self.loading {
+            return Some(message);
+        }
+        match &message {
+            Message::AddDirWindow(id, path) => {
+                let mut root = Pane::default();
+                root.navigate(Arc::clone(path));
+                root.scanning = true;
+                let (panes, _) = pane_grid::State::new(root);
+                self.windows.insert(*id, Window::Main { panes });
+            }
+            Message::AddReaderWindow(id, path) => {
+                let reader = Reader::new(Arc::clone(path));
+                self.windows.insert(*id, Window::Reader(Box::new(reader)));
+            }
+            Message::AddHomeWindow(id) => {
+                self.windows.insert(*id, Window::Home(Home::default()));
+            }
+            Message::OpenArchive(..) | Message::LibraryIndexed(..) => {}
+            _ => return Some(message),
+        }
+        self.deferred.push(message);
+        None
+    }
+
+    /// Use state of profile loaded in the background, handling messages held back until
+    /// then and opening the most recent directory or archive if requested.
+    fn startup_loaded(&mut self, startup: Startup) -> Task<Message> {
+        let Startup {
+            profile,
+            catalogue,
+            reading_lists,
+            recent,
+            shelf,
+            tags,
+            backups,
+            temp_dir,
+            page_time,
+            profiles,
+        } = startup;
+        // State of a profile switched away from before it finished loading.
+        if profile != self.cli.profile || !self.loading {
+            return Task::none();
+        }
+        self.catalogue = catalogue;
+        self.reading_lists = reading_lists;
+        self.recent = recent;
+        self.shelf = shelf;
+        self.tags = tags;
+        self.backups = backups;
+        self.temp_dir = temp_dir;
+        self.page_time = page_time;
+        self.profiles = profiles;
+        self.loading = false;
+
+        let open_recent = if self.cli.recent && self.cli.paths.is_empty() {
+            match self.recent.first().cloned() {
+                Some(path) if ArchiveKind::from_path(&path).is_some() => self.open_reader(path),
+                Some(path) => self
+                    .windows
+                    .iter()
+                    .find_map(|(&window_id, window)| match window {
+                        Window::Main { panes } => panes
+                            .iter()
+                            .next()
+                            .map(|(&pane, _)| ViewPath { window_id, pane }),
+                        _ => None,
+                    })
+                    .map_or_else(Task::none, |view_path| {
+                        Task::done(Message::OpenRecent(view_path, path))
+                    }),
+                None => {
+                    ::log::info!("nothing has been opened recently");
+                    Task::none()
+                }
+            }
+        } else {
+            Task::none()
+        };
+        ::core::mem::take(&mut self.deferred)
+            .into_iter()
+            .map(|message| self.update(message))
+            .chain([open_recent])
+            .collect::<Vec<_>>()
+            .pipe(Task::batch)
+    }
+
+    /// Update application state.
+    fn update(&mut self, message: Message) -> Task<Message> {
+        let Some(message) = self.defer(message) else {
+            return Task::none();
+        };
+        match message {
+            Message::SettingsLoaded(result) => self.settings_loaded(result),
+            Message::StartupLoaded(loaded) => loaded
+                .take()
+                .map_or_else(Task::none, |startup| self.startup_loaded(startup)),
+            Message::AddDirWindow(window_id, path) => {
+                let mut root = Pane::default();
+                root.navigate(Arc::clone(&path));
+                let (panes, pane) = pane_grid::State::new(root);
+                self.windows.insert(window_id, Window::Main { panes });
+                self.open_dir(path, None, ViewPath { window_id, pane })
+            }
+            Message::AddEmptyWindow(id) => {
+                let (panes, _) = pane_grid::State::new(Pane::default());
+                self.windows.insert(id, Window::Main { panes });
+                Task::none()
+            }
+            Message::AddHomeWindow(id) => {
+                self.windows
+                    .insert(id, Window::Home(Home::load(&self.catalogue)));
+                Task::none()
+            }
+            Message::RevertChange(id) => {
+                self.revert_change(id, None);
+                Task::none()
+            }
+            Message::RevertChangedItem(id, path) => {
+                self.revert_change(id, Some(&path));
+                Task::none()
+            }
+            Message::SwitchProfile(profile) => self.switch_profile(profile),
+            Message::CopyLaunchCommand => self.perform(Action::CopyLaunchCommand),
+            Message::OpenProfile(profile) if profile == self.cli.profile => {
+                Task::done(Message::OpenMainWindow)
+            }
+            Message::OpenProfile(profile) => {
+                if let Err(err) = instance::open_profile(&self.cli, &profile) {
+                    self.report_error(&err);
+                }
+                Task::none()
+            }
+            Message::OpenMainWindow => {
+                let (_, open_window) =
+                    self.open_window(WindowKind::Main, window::Settings::default());
+                open_window.map(Message::AddEmptyWindow)
+            }
+            Message::OpenArchive(path) => self.open_archive(path),
+            Message::OpenRecent(view_path, path) => {
+                let Some(pane) = self.get_pane_mut(view_path) else {
+                    return Task::none();
+                };
+                pane.navigate(Arc::clone(&path));
+                self.open_dir(path, None, view_path)
+            }
+            Message::InstanceRequest(path) => match path.map(Arc::<Path>::from) {
+                Some(path) if path.is_dir() => {
+                    let (_, open_window) =
+                        self.open_window(WindowKind::Main, window::Settings::default());
+                    open_window.map(move |id| Message::AddDirWindow(id, Arc::clone(&path)))
+                }
+                Some(path) if ArchiveKind::from_path(&path).is_some() => self.open_reader(path),
+                Some(path) => {
+                    ::log::warn!("cannot open forwarded path {path:?}");
+                    Task::none()
+                }
+                None => Task::done(Message::OpenMainWindow),
+            },
+            Message::PagesCounted {
+                view_path,
+                token,
+                counts,
+            } => {
+                self.catalogue
+                    .record_page_counts(&counts)
+                    .unwrap_or_else(|err| err.log());
+                if let Some(DirView::Dir { items, .. }) =
+                    self.get_current_view_mut(view_path, &token)
+                {
+                    for (path, _, page_count) in counts {
+                        if let Some(item) = items.get_mut(&path) {
+                            item.page_count = Some(page_count);
+                        }
+                    }
+                }
+                Task::none()
+            }
+            Message::ToggleQuickLook(view_path) => self.toggle_quick_look(view_path),
+            Message::CardHovered(item_path) => {
+                let delay = self.settings.hover_preview_delay;
+                if delay == 0 || ArchiveKind::from_path(&item_path.path).is_none() {
+                    self.hover_preview = None;
+                    return Task::none();
+                }
+                self.hover_preview = Some(HoverPreview::new(item_path.clone()));
+                ::smol::Timer::after(Duration::from_millis(delay.into()))
+                    .pipe(Task::future)
+                    .map(move |_| Message::HoverDelayElapsed(item_path.clone()))
+            }
+            Message::CardUnhovered(item_path) => {
+                if self
+                    .hover_preview
+                    .as_ref()
+                    .is_some_and(|preview| preview.item_path == item_path)
+                {
+                    self.hover_preview = None;
+                }
+                Task::none()
+            }
+            Message::HoverDelayElapsed(item_path) => {
+                let Some(preview) = self
+                    .hover_preview
+                    .as_mut()
+                    .filter(|preview| preview.item_path == item_path && !preview.shown)
+                else {
+                    return Task::none();
+                };
+                preview.show();
+                load_hover_page(item_path, 0)
+            }
+            Message::HoverPageLoaded {
+                item_path,
+                page_count,
+                page,
+            } => {
+                if let Some(preview) = self
+                    .hover_preview
+                    .as_mut()
+                    .filter(|preview| preview.item_path == item_path)
+                {
+                    preview.push_page(page_count, page);
+                }
+                Task::none()
+            }
+            Message::HoverTick => {
+                let Some(mut preview) = self.hover_preview.take() else {
+                    return Task::none();
+                };
+                // Cards are left without the mouse leaving them when panes navigate.
+                if !self
+                    .get_dir_view_mut(preview.item_path.view_path)
+                    .is_some_and(|view| view.contains(&preview.item_path.path))
+                {
+                    return Task::none();
+                }
+                let task = match preview.advance() {
+                    Some(index) => load_hover_page(preview.item_path.clone(), index),
+                    None => Task::none(),
+                };
+                self.hover_preview = Some(preview);
+                task
+            }
+            Message::CloseQuickLook(view_path) => {
+                if let Some(pane) = self.get_pane_mut(view_path) {
+                    pane.quick_look = None;
+                }
+                Task::none()
+            }
+            Message::QuickLookLoaded {
+                view_path,
+                path,
+                page_count,
+                pages,
+            } => {
+                if let Some(quick_look) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.quick_look.as_mut())
+                    .filter(|quick_look| quick_look.path == path)
+                {
+                    quick_look.page_count = Some(page_count);
+                    quick_look.pages = pages;
+                }
+                Task::none()
+            }
+            Message::ShuffleHome(id) => {
+                if let Some(Window::Home(home)) = self.windows.get_mut(&id) {
+                    home.shuffle(&self.catalogue);
+                }
+                Task::none()
+            }
+            Message::SetHomeTile(tile, shown) => {
+                self.draft.home_tiles = HomeTile::ALL
+                    .into_iter()
+                    .filter(|&other| {
+                        if other == tile {
+                            shown
+                        } else {
+                            self.draft.home_tiles.contains(&other)
+                        }
+                    })
+                    .collect();
+                Task::none()
+            }
+            Message::SetCardBadge(badge, shown) => {
+                self.draft.card_badges = CardBadge::ALL
+                    .into_iter()
+                    .filter(|&other| {
+                        if other == badge {
+                            shown
+                        } else {
+                            self.draft.card_badges.contains(&other)
+                        }
+                    })
+                    .collect();
+                Task::none()
+            }
+            Message::AddSettingsWindow(id) => {
+                self.windows.insert(id, Window::Settings);
+                Task::none()
+            }
+            Message::AddHistoryWindow(id) => {
+                self.windows.insert(id, Window::History);
+                Task::none()
+            }
+            Message::AddHelpWindow(id) => {
+                self.windows.insert(id, Window::Help);
+                Task::none()
+            }
+            Message::AddAboutWindow(id) => {
+                self.windows.insert(id, Window::About(About::default()));
+                Task::none()
+            }
+            Message::ShowDependencyLicense(id, dependency) => {
+                if let Some(Window::About(about)) = self.windows.get_mut(&id) {
+                    about.show_dependency(dependency);
+                }
+                Task::none()
+            }
+            Message::ToggleAbout => self.perform(Action::ToggleAbout),
+            Message::AddStatsWindow(id) => {
+                self.windows
+                    .insert(id, Window::Stats(Statistics::load(&self.catalogue)));
+                let paths = self.catalogue.seen_paths().unwrap_or_else(|err| {
+                    err.log();
+                    Vec::new()
+                });
+                Task::perform(
+                    ::smol::unblock(move || statistics::disk_usage(paths)),
+                    move |disk_usage| Message::DiskUsageComputed(id, disk_usage),
+                )
+            }
+            Message::AddCalendarWindow(id) => {
+                self.windows
+                    .insert(id, Window::Calendar(Calendar::load(&self.catalogue)));
+                Task::none()
+            }
+            Message::ShiftCalendarMonth(id, months) => {
+                if let Some(Window::Calendar(calendar)) = self.windows.get_mut(&id) {
+                    calendar.shift_month(months, &self.catalogue);
+                }
+                Task::none()
+            }
+            Message::SelectCalendarDay(id, day) => {
+                if let Some(Window::Calendar(calendar)) = self.windows.get_mut(&id) {
+                    calendar.select(day);
+                }
+                Task::none()
+            }
+            Message::DiskUsageComputed(id, disk_usage) => {
+                if let Some(Window::Stats(statistics)) = self.windows.get_mut(&id) {
+                    statistics.set_disk_usage(disk_usage);
+                }
+                Task::none()
+            }
+            Message::AddReaderWindow(id, path) => {
+                self.record_recent(&path);
+                // Archives being read are opened where reading stopped.
+                let page = self
+                    .catalogue
+                    .get(&path)
+                    .ok()
+                    .filter(|state| state.read_state.is_reading())
+                    .map_or(0, |state| state.page);
+                let remembered = self.catalogue.adjustments(&path).unwrap_or_else(|err| {
+                    err.log();
+                    None
+                });
+                let reader = Reader {
+                    bookmarks: self.load_bookmarks(&path),
+                    adjustments: remembered.unwrap_or_default(),
+                    remember_adjustments: remembered.is_some(),
+                    ..Reader::new(Arc::clone(&path))
+                };
+                let adjustments = reader.adjustments;
+                self.windows.insert(id, Window::Reader(Box::new(reader)));
+                Self::load_page(id, path, page, adjustments)
+            }
+            Message::SetRememberWindowSize(remember) => {
+                self.draft.remember_window_size = remember;
+                Task::none()
+            }
+            Message::SetRememberWindowPosition(remember) => {
+                self.draft.remember_window_position = remember;
+                Task::none()
+            }
+            Message::SetContinueReadingShelf(shown) => {
+                self.draft.continue_reading_shelf = shown;
+                Task::none()
+            }
+            Message::SetSidebar(shown) => {
+                self.draft.sidebar = shown;
+                Task::none()
+            }
+            Message::SetLibraryRootInput(input) => {
+                self.library_root_input = input;
+                Task::none()
+            }
+            Message::AddLibraryRoot => {
+                let path = PathBuf::from(self.library_root_input.trim());
+                match path.canonicalize() {
+                    Ok(root) if root.is_dir() => {
+                        if !self.draft.library_roots.contains(&root) {
+                            self.draft.library_roots.push(root);
+                        }
+                        self.library_root_input.clear();
+                    }
+                    Ok(..) => ::log::warn!("library root {path:?} is not a directory"),
+                    Err(source) => self.report_error(&Error::Scan { path, source }),
+                }
+                Task::none()
+            }
+            Message::RemoveLibraryRoot(index) => {
+                if index < self.draft.library_roots.len() {
+                    self.draft.library_roots.remove(index);
+                }
+                Task::none()
+            }
+            Message::LibraryIndexed(archives) => self.record_seen_paths(archives),
+            Message::LibraryChanged(paths) => self.library_changed(&paths),
+            Message::ToggleSidebar => self.perform(Action::ToggleSidebar),
+            Message::SidebarPressed(entry) => {
+                self.sidebar_drag = Some(entry);
+                Task::none()
+            }
+            Message::SidebarReleased(window_id) => {
+                self.sidebar_drag.take().map_or_else(Task::none, |entry| {
+                    self.open_sidebar_entry(window_id, entry)
+                })
+            }
+            Message::WindowOpened {
+                window_id,
+                position,
+                size,
+            } => {
+                self.window_geometry.insert(
+                    window_id,
+                    Geometry {
+                        position: position.map(|Point { x, y }| (x, y)),
+                        ..Geometry::new(size)
+                    },
+                );
+                window::is_maximized(window_id)
+                    .map(move |maximized| Message::WindowMaximized(window_id, maximized))
+            }
+            Message::WindowMoved(window_id, Point { x, y }) => {
+                // Geometry of fullscreen windows should not be remembered.
+                if self
+                    .windows
+                    .get(&window_id)
+                    .is_some_and(Window::is_fullscreen)
+                {
+                    return Task::none();
+                }
+                if let Some(geometry) = self.window_geometry.get_mut(&window_id) {
+                    geometry.position = Some((x, y));
+                }
+                Task::none()
+            }
+            Message::WindowResized(window_id, size) => {
+                if self
+                    .windows
+                    .get(&window_id)
+                    .is_some_and(Window::is_fullscreen)
+                {
+                    return Task::none();
+                }
+                self.window_geometry
+                    .entry(window_id)
+                    .and_modify(|geometry| {
+                        geometry.width = size.width;
+                        geometry.height = size.height;
+                    })
+                    .or_insert_with(|| Geometry::new(size));
+                window::is_maximized(window_id)
+                    .map(move |maximized| Message::WindowMaximized(window_id, maximized))
+            }
+            Message::WindowMaximized(window_id, maximized) => {
+                if let Some(geometry) = self.window_geometry.get_mut(&window_id) {
+                    geometry.maximized = maximized;
+                }
+                Task::none()
+            }
+            Message::WindowUnfocused(id) => {
+                if self.focused_window == Some(id) {
+                    self.focused_window = None;
+                }
+                Task::none()
+            }
+            Message::WindowFocused(id) => {
+                self.focused_window = Some(id);
+                // Home is refreshed as items may have been read in other windows.
+                if let Some(Window::Home(home)) = self.windows.get_mut(&id) {
+                    *home = Home::load(&self.catalogue);
+                }
+                Task::none()
+            }
+            Message::RemoveWindow(id) => {
+                self.dialogs.retain(|dialog| dialog.window_id != id);
+                // Windows closed while loading are not added once loaded.
+                self.deferred.retain(|message| match message {
+                    Message::AddDirWindow(window_id, _)
+                    | Message::AddReaderWindow(window_id, _)
+                    | Message::AddHomeWindow(window_id) => *window_id != id,
+                    _ => true,
+                });
+                let window = self.windows.remove(&id);
+                if let Some(window) = &window
+                    && let Some(geometry) = self.window_geometry.remove(&id)
+                {
+                    self.geometries.insert(window.kind(), geometry);
+                    if let Err(err) = self.geometries.save(&self.xdg_dirs) {
+                        err.log();
+                    }
+                }
+                if let Some(Window::Main { mut panes }) = window {
+                    panes.iter_mut().for_each(|(_, pane)| pane.cancel_loads());
+                }
+                if self.windows.is_empty() {
+                    ::iced::exit()
+                } else {
+                    Task::none()
+                }
+            }
+            Message::SetTheme(theme_arg) => {
+                self.draft.theme = theme_arg;
+                Task::none()
+            }
+            Message::SetSizeUnits(size_units) => {
+                self.draft.size_units = size_units;
+                Task::none()
+            }
+            Message::SetReaderTheme(ReaderTheme(theme)) => {
+                self.draft.reader_theme = theme;
+                Task::none()
+            }
+            Message::SetActivation(activation) => {
+                self.draft.activation = activation;
+                Task::none()
+            }
+            Message::SetCardSize(card_size) => {
+                self.draft.card_size = card_size;
+                Task::none()
+            }
+
+            Message::SetMouseBinding(button, action) => {
+                self.draft.bindings.mouse.insert(button, action);
+                Task::none()
+            }
+            Message::SetKeyBinding(key, action) => {
+                self.draft.bindings.keys.insert(key, action);
+                Task::none()
+            }
+            Message::MouseButtonPressed(window_id, button) => {
+                let action = self.settings.bindings.mouse_action(button);
+                if self
+                    .hovered_pane
+                    .is_some_and(|view_path| view_path.window_id == window_id)
+                    || matches!(
+                        action,
+                        Action::ToggleSettings
+                            | Action::ToggleUndoHistory
+                            | Action::ToggleShortcuts
+                            | Action::ToggleAbout
+                            | Action::ToggleStatistics
+                            | Action::ToggleCalendar
+                            | Action::ToggleTrash
+                            | Action::ToggleDebugOverlay
+                            | Action::CopyLaunchCommand
+                            | Action::BackUpProfile
+                            | Action::ToggleSidebar
+                    )
+                {
+                    self.perform(action)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::PaneScrolled {
+                view_path,
+                offset,
+                viewport: size,
+            } => {
+                if let Some(DirView::Dir {
+                    scroll_offset,
+                    viewport,
+                    ..
+                }) = self.get_dir_view_mut(view_path)
+                {
+                    *scroll_offset = offset;
+                    *viewport = Some(size);
+                }
+                self.request_thumbnails(view_path)
+            }
+            Message::ItemContextMenu(item_path) => {
+                let has_cover = self
+                    .catalogue
+                    .cover(&item_path.path)
+                    .inspect_err(Error::log)
+                    .is_ok_and(|cover| cover.is_some());
+                let open_with = open_with::applications(
+                    &self.settings.open_with,
+                    &self.xdg_dirs,
+                    &item_path.path,
+                );
+                let Some(pane) = self.get_pane_mut(item_path.view_path) else {
+                    return Task::none();
+                };
+                if let DirView::Dir { selected, .. } = &pane.view
+                    && !selected.contains(&item_path.path)
+                {
+                    let (filter, sort) = (pane.filter.clone(), pane.list_sort());
+                    pane.view
+                        .select(&item_path.path, &filter, sort, Modifiers::default());
+                }
+                pane.context_menu = Some(item_path.path);
+                pane.open_with = open_with;
+                pane.has_cover = has_cover;
+                Task::none()
+            }
+            Message::ConvertToCbz(view_path) => self.convert_to_cbz(view_path),
+            Message::ExtractHere(view_path) => {
+                let paths = self.extractable_selection(view_path);
+                self.extract(paths, None)
+            }
+            Message::ExtractTo(view_path) => {
+                let paths = self.extractable_selection(view_path);
+                match Rename::extract_to(paths) {
+                    Some(rename) => self.show_rename(view_path, rename),
+                    None => Task::none(),
+                }
+            }
+            Message::VerifyArchives(view_path) => {
+                for path in
+                    self.selection_where(view_path, |path| ArchiveKind::from_path(path).is_some())
+                {
+                    self.tasks.push(TaskKind::Verify, path);
+                }
+                self.run_tasks()
+            }
+            Message::WriteThumbnails(view_path) => {
+                let paths =
+                    self.selection_where(view_path, |path| ArchiveKind::from_path(path).is_some());
+                // Freedesktop thumbnails are shared by all applications, they are not per
+                // profile.
+                let Some(dirs) = self
+                    .xdg_dirs
+                    .cache_home
+                    .as_ref()
+                    .zip(self.temp_dir.as_ref())
+                    .map(|(cache, temp)| ThumbnailDirs {
+                        thumbnails: Arc::from(cache.join("thumbnails")),
+                        temp: Arc::clone(temp),
+                    })
+                else {
+                    ::log::error!("cannot write thumbnails without a cache directory");
+                    return Task::none();
+                };
+                for path in paths {
+                    let kind = TaskKind::Thumbnails { dirs: dirs.clone() };
+                    self.tasks.push(kind, path);
+                }
+                self.run_tasks()
+            }
+            Message::TaskProgress { id, done, total } => {
+                self.tasks.progress(id, done, total);
+                Task::none()
+            }
+            Message::TaskFinished { id, result } => {
+                let Some(path) = self.tasks.get(id).map(|task| Arc::clone(&task.path)) else {
+                    return Task::none();
+                };
+                let (status, task) = match result {
+                    Ok(output) => (TaskStatus::Done, self.task_done(&path, output)),
+                    Err(err)
+                        if matches!(
+                            *err,
+                            Error::Archive {
+                                source: ArchiveError::Cancelled,
+                                ..
+                            }
+                        ) =>
+                    {
+                        (TaskStatus::Cancelled, Task::none())
+                    }
+                    Err(err) => {
+                        self.report_error(&err);
+                        (TaskStatus::Failed(err.user_message()), Task::none())
+                    }
+                };
+                self.tasks.finish(id, status);
+                let notify = match self.tasks.take_summary() {
+                    Some((summary, body)) => self.notify_unfocused(summary, body),
+                    None => Task::none(),
+                };
+                Task::batch([task.chain(self.run_tasks()), notify])
+            }
+            Message::CancelTask(id) => {
+                self.tasks.cancel(id);
+                Task::none()
+            }
+            Message::CancelAllTasks => {
+                self.tasks.cancel_all();
+                Task::none()
+            }
+            Message::ClearFinishedTasks => {
+                self.tasks.clear_finished();
+                Task::none()
+            }
+            Message::ToggleTaskPanel => {
+                self.show_tasks = !self.show_tasks;
+                Task::none()
+            }
+            Message::PackDir(item_path) => {
+                let Some(pane) = self.get_pane_mut(item_path.view_path) else {
+                    return Task::none();
+                };
+                pane.context_menu = None;
+                pane.pack = Some(Pack::new(item_path.path));
+                Task::none()
+            }
+            Message::SetPackField(view_path, field, text) => {
+                if let Some(pack) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.pack.as_mut())
+                {
+                    pack.set(field, text);
+                }
+                Task::none()
+            }
+            Message::SetPackInfo(view_path, write_info) => {
+                if let Some(pack) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.pack.as_mut())
+                {
+                    pack.write_info = write_info;
+                }
+                Task::none()
+            }
+            Message::SetPackTrash(view_path, trash_dir) => {
+                if let Some(pack) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.pack.as_mut())
+                {
+                    pack.trash_dir = trash_dir;
+                }
+                Task::none()
+            }
+            Message::ConfirmPack(view_path) => {
+                let Some(temp_dir) = self.temp_dir.clone() else {
+                    ::log::error!("cannot pack directories without a directory of temporary files");
+                    return Task::none();
+                };
+                let trash_dir = trash::dir(&self.xdg_dirs);
+                let Some(pack) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.pack.take())
+                else {
+                    return Task::none();
+                };
+                let dir = pack.dir;
+                let info = pack.write_info.then_some(pack.info);
+                let trash_dir = trash_dir.filter(|_| pack.trash_dir);
+                Task::perform(
+                    ::smol::unblock(move || {
+                        let packed = archive::pack_dir(&dir, &temp_dir, info.as_ref())
+                            .map_err(|source| Error::Archive {
+                                path: dir.to_path_buf(),
+                                source,
+                            })
+                            .and_then(|archive| {
+                                // The directory is only trashed once its archive is written.
+                                let trashed = trash_dir
+                                    .map(|trash_dir| trash::trash(&trash_dir, &dir))
+                                    .transpose()?;
+                                Ok((archive, trashed))
+                            })
+                            .map_err(Arc::new);
+                        (dir, packed)
+                    }),
+                    |(dir, result)| Message::Packed { dir, result },
+                )
+            }
+            Message::CancelPack(view_path) => {
+                if let Some(pane) = self.get_pane_mut(view_path) {
+                    pane.pack = None;
+                }
+                Task::none()
+            }
+            Message::Packed { dir, result } => {
+                let trashed = match result {
+                    Ok((_, trashed)) => trashed,
+                    Err(err) => {
+                        self.report_error(&err);
+                        None
+                    }
+                };
+                if let Some(trashed) = trashed {
+                    if let Err(err) = self.catalogue.record_trashed(&dir, &trashed) {
+                        self.report_error(&err);
+                    }
+                    self.reload_trash_windows();
+                }
+                dir.parent()
+                    .map_or_else(Task::none, |parent| self.rescan_dir(parent))
+            }
+            Message::MoveToTrash(view_path) => self.move_to_trash(view_path),
+            Message::MovedToTrash {
+                view_path,
+                from,
+                result,
+            } => {
+                let trashed = match result {
+                    Ok(trashed) => trashed,
+                    Err(err) => {
+                        self.report_error(&err);
+                        return Task::none();
+                    }
+                };
+                if let Err(err) = self.catalogue.record_trashed(&from, &trashed) {
+                    self.report_error(&err);
+                }
+                self.reload_trash_windows();
+                if let Some(pane) = self.get_pane_mut(view_path) {
+                    pane.view.rename_item(&from, None);
+                }
+                Task::none()
+            }
+            Message::EditMetadata(view_path) => {
+                let paths = self.editable_selection(view_path);
+                if paths.is_empty() {
+                    return Task::none();
+                }
+                let (_, task) = self.open_window(
+                    WindowKind::Metadata,
+                    window::Settings {
+                        size: Size {
+                            width: 500.0,
+                            height: 400.0,
+                        },
+                        ..window::Settings::default()
+                    },
+                );
+                task.map(move |id| Message::AddMetadataWindow(id, paths.clone()))
+            }
+            Message::AddMetadataWindow(id, paths) => {
+                let editor = MetadataEditor::new(paths.clone());
+                self.windows.insert(id, Window::Metadata(editor));
+                Task::perform(
+                    ::smol::unblock(move || {
+                        let (mut archives, mut errors) = (Vec::new(), Vec::new());
+                        for path in paths {
+                            match archive::read_comic_info(&path) {
+                                Ok(info) => archives.push((path, info)),
+                                Err(source) => errors.push(Arc::new(Error::Archive {
+                                    path: path.to_path_buf(),
+                                    source,
+                                })),
+                            }
+                        }
+                        (archives, errors)
+                    }),
+                    move |(archives, errors)| Message::MetadataRead {
+                        id,
+                        archives,
+                        errors,
+                    },
+                )
+            }
+            Message::MetadataRead {
+                id,
+                archives,
+                errors,
+            } => {
+                for err in errors {
+                    self.report_error(&err);
+                }
+                if let Some(editor) = self.metadata_editor_mut(id) {
+                    editor.read(archives);
+                }
+                Task::none()
+            }
+            Message::SetMetadataField(id, field, text) => {
+                if let Some(editor) = self.metadata_editor_mut(id) {
+                    editor.set(field, text);
+                }
+                Task::none()
+            }
+            Message::ReviewMetadata(id) => {
+                if let Some(editor) = self.metadata_editor_mut(id)
+                    && editor.stage == Stage::Editing
+                    && !editor.changes().is_empty()
+                {
+                    editor.stage = Stage::Reviewing;
+                }
+                Task::none()
+            }
+            Message::EditMetadataFields(id) => {
+                if let Some(editor) = self.metadata_editor_mut(id)
+                    && editor.stage == Stage::Reviewing
+                {
+                    editor.stage = Stage::Editing;
+                }
+                Task::none()
+            }
+            Message::WriteMetadata(id) => {
+                let Some(temp_dir) = self.temp_dir.clone() else {
+                    ::log::error!("cannot write metadata without a directory of temporary files");
+                    return Task::none();
+                };
+                let Some(editor) = self.metadata_editor_mut(id) else {
+                    return Task::none();
+                };
+                if editor.stage != Stage::Reviewing {
+                    return Task::none();
+                }
+                editor.stage = Stage::Writing;
+                let changes = editor.changes();
+                Task::perform(
+                    ::smol::unblock(move || {
+                        changes
+                            .into_iter()
+                            .map(|change| {
+                                let result =
+                                    archive::write_comic_info(&change.path, &temp_dir, &change.new)
+                                        .map_err(|source| {
+                                            Arc::new(Error::Archive {
+                                                path: change.path.to_path_buf(),
+                                                source,
+                                            })
+                                        });
+                                (change, result)
+                            })
+                            .collect()
+                    }),
+                    move |results| Message::MetadataWritten(id, results),
+                )
+            }
+            Message::MetadataWritten(id, results) => {
+                let mut written = Vec::new();
+                let mut failed = false;
+                for (change, result) in results {
+                    match result.and_then(|()| {
+                        self.catalogue
+                            .set_comic_info(&change.path, &change.new)
+                            .map_err(Arc::new)
+                    }) {
+                        Ok(()) => written.push(change),
+                        Err(err) => {
+                            self.report_error(&err);
+                            failed = true;
+                        }
+                    }
+                }
+                match self.catalogue.tags() {
+                    Ok(tags) => self.tags = tags,
+                    Err(err) => err.log(),
+                }
+                let dirs = written
+                    .iter()
+                    .filter_map(|change| change.path.parent())
+                    .map(Arc::<Path>::from)
+                    .collect::<HashSet<_>>();
+                let rescans = dirs
+                    .iter()
+                    .map(|dir| self.rescan_dir(dir))
+                    .collect::<Vec<_>>();
+                let close = if failed {
+                    if let Some(editor) = self.metadata_editor_mut(id) {
+                        editor.written(&written);
+                    }
+                    Task::none()
+                } else {
+                    window::close(id)
+                };
+                Task::batch(rescans).chain(close)
+            }
+            Message::CloseMetadataEditor(id) => window::close(id),
+            Message::AddTrashWindow(id) => {
+                let trashed = self.load_trashed();
+                self.windows.insert(id, Window::Trash(trashed));
+                Task::none()
+            }
+            Message::RestoreTrashed(path) => {
+                let Some(trashed) = self
+                    .load_trashed()
+                    .into_iter()
+                    .find(|trashed| *trashed.path == *path)
+                else {
+                    return Task::none();
+                };
+                Task::perform(
+                    ::smol::unblock(move || {
+                        let result = trash::restore(&trashed.trashed, &path).map_err(Arc::new);
+                        (path, result)
+                    }),
+                    |(path, result)| Message::Restored { path, result },
+                )
+            }
+            Message::Restored { path, result } => {
+                if let Err(err) = result {
+                    self.report_error(&err);
+                    return Task::none();
+                }
+                if let Err(err) = self.catalogue.forget_trashed(&path) {
+                    self.report_error(&err);
+                }
+                self.reload_trash_windows();
+                path.parent()
+                    .map_or_else(Task::none, |dir| self.rescan_dir(dir))
+            }
+            Message::ForgetTrashed(path) => {
+                if let Err(err) = self.catalogue.forget_trashed(&path) {
+                    self.report_error(&err);
+                }
+                self.reload_trash_windows();
+                Task::none()
+            }
+            Message::Rename(item_path) => {
+                let view_path = item_path.view_path;
+                self.show_rename(view_path, Rename::new(item_path.path))
+            }
+            Message::MoveTo(item_path) => {
+                let view_path = item_path.view_path;
+                self.show_rename(view_path, Rename::move_to(item_path.path))
+            }
+            #[cfg(feature = "dbus")]
+            Message::BrowseMoveTarget(view_path) => {
+                let extract = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.rename.as_ref())
+                    .is_some_and(|rename| rename.kind.is_extract());
+                Task::future(dbus::pick_directory(tr(if extract {
+                    "dialog-extract-to"
+                } else {
+                    "dialog-move-to"
+                })))
+                .then(move |result| match result {
+                    Ok(Some(dir)) => Task::done(Message::MoveTargetPicked(view_path, dir)),
+                    Ok(None) => Task::none(),
+                    Err(err) => {
+                        ::log::warn!("could not pick directory\n{err}");
+                        Task::none()
+                    }
+                })
+            }
+            #[cfg(not(feature = "dbus"))]
+            Message::BrowseMoveTarget(..) => Task::none(),
+            Message::MoveTargetPicked(view_path, dir) => {
+                let Some(id) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.rename.as_ref())
+                    .filter(|rename| !rename.kind.is_rename())
+                    .and_then(|rename| rename.dialog)
+                else {
+                    return Task::none();
+                };
+                if let Some(dialog) = self.dialogs.iter_mut().find(|dialog| dialog.id == id) {
+                    dialog.set_value(dir.to_string_lossy().into_owned());
+                }
+                Task::none()
+            }
+            Message::ConfirmRename(view_path, text) => {
+                let Some(rename) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.rename.take())
+                else {
+                    return Task::none();
+                };
+                let Some(to) = rename.target(&text) else {
+                    return Task::none();
+                };
+                if let RenameKind::Extract(paths) = rename.kind {
+                    return self.extract(paths, Some(&Arc::from(to)));
+                }
+                let from = rename.path;
+                Task::perform(
+                    ::smol::unblock(move || {
+                        let result = inbox::move_file(&from, &to, false)
+                            .map(|()| to)
+                            .map_err(Arc::new);
+                        (from, result)
+                    }),
+                    move |(from, result)| Message::Renamed {
+                        view_path,
+                        from,
+                        result,
+                    },
+                )
+            }
+            Message::CancelRename(view_path) => {
+                if let Some(pane) = self.get_pane_mut(view_path) {
+                    pane.rename = None;
+                }
+                Task::none()
+            }
+            Message::Renamed {
+                view_path,
+                from,
+                result,
+            } => {
+                let to = match result {
+                    Ok(to) => Arc::<Path>::from(to),
+                    Err(err) => {
+                        self.report_error(&err);
+                        return Task::none();
+                    }
+                };
+                if let Err(err) = self.catalogue.rename(&from, &to) {
+                    self.report_error(&err);
+                }
+                let Some(pane) = self.get_pane_mut(view_path) else {
+                    return Task::none();
+                };
+                // Reading lists follow archives wherever they are moved.
+                let shown =
+                    pane.list.is_some() || pane.tag.is_some() || to.parent() == from.parent();
+                pane.view.rename_item(&from, shown.then_some(&to));
+                self.request_thumbnails(view_path)
+            }
+            Message::CloseContextMenu(view_path) => {
+                // Pressing the pane itself rather than an item drags nothing.
+                self.drag = None;
+                self.sidebar_drag = None;
+                if let Some(pane) = self.get_pane_mut(view_path) {
+                    pane.context_menu = None;
+                }
+                Task::none()
+            }
+            Message::SetReadState(view_path, read_state) => {
+                self.set_read_state(view_path, read_state);
+                self.request_thumbnails(view_path)
+            }
+            Message::OpenReadingList(view_path, choice) => {
+                self.open_reading_list(view_path, choice)
+            }
+            Message::OpenFeed(view_path, url) => self.load_feed(view_path, url, Vec::new()),
+            Message::FeedLoaded {
+                view_path,
+                token,
+                back,
+                result,
+            } => self.show_feed(view_path, &token, back, result),
+            Message::BackUpProfile => self.perform(Action::BackUpProfile),
+            Message::ProfileBackedUp(result) => match result {
+                Ok(path) => {
+                    self.backups = backup::list(&self.xdg_dirs);
+                    notify(tr("notify-profile-backed-up"), path.display().to_string())
+                }
+                Err(err) => {
+                    self.report_error(&err);
+                    Task::none()
+                }
+            },
+            Message::SelectBackup(name) => {
+                self.selected_backup = Some(name);
+                Task::none()
+            }
+            Message::RestoreBackup(window_id) => {
+                let Some(name) = self.selected_backup.clone() else {
+                    return Task::none();
+                };
+                Dialog::confirm(
+                    window_id,
+                    tr("dialog-restore-backup"),
+                    tr("settings-restore"),
+                    Message::ConfirmRestoreBackup(window_id),
+                )
+                .body(tr_args("dialog-restore-backup-body", &[("name", &name)]))
+                .destructive()
+                .pipe(|dialog| self.show_dialog(dialog))
+            }
+            Message::ConfirmRestoreBackup(window_id) => {
+                let dialog = Dialog::progress(window_id, tr("dialog-restoring-backup"), None);
+                let id = dialog.id;
+                Task::batch([self.show_dialog(dialog), self.restore_backup(id)])
+            }
+            Message::CopySelection(view_path, kind) => self.copy_selection(view_path, kind),
+            Message::CoverCopied(result) => {
+                if let Err(err) = result {
+                    self.report_error(&err);
+                }
+                Task::none()
+            }
+            Message::Paste(view_path) => {
+                ::iced::clipboard::read().map(move |text| Message::Pasted(view_path, text))
+            }
+            Message::Pasted(view_path, text) => {
+                self.paste(view_path, text.as_deref().unwrap_or_default())
+            }
+            Message::CreateReadingList(view_path, name) => {
+                let name = name.trim().to_owned();
+                if name.is_empty() {
+                    return Task::none();
+                }
+                if !self.reading_lists.contains(&name) {
+                    self.set_reading_list(&name, &[]);
+                }
+                self.open_reading_list(view_path, ListChoice::List(name))
+            }
+            Message::DialogInput(id, value) => {
+                if let Some(dialog) = self.dialogs.iter_mut().find(|dialog| dialog.id == id) {
+                    dialog.set_value(value);
+                }
+                Task::none()
+            }
+            Message::AnswerDialog(id) => {
+                if !self
+                    .dialogs
+                    .iter()
+                    .any(|dialog| dialog.id == id && dialog.is_answerable())
+                {
+                    return Task::none();
+                }
+                self.close_dialog(id)
+                    .and_then(Dialog::answer)
+                    .map_or_else(Task::none, |message| self.update(message))
+            }
+            Message::ChooseDialog(id, index) => self
+                .close_dialog(id)
+                .and_then(|dialog| dialog.answer_with(index))
+                .map_or_else(Task::none, |message| self.update(message)),
+            Message::DialogToggleAll(id, all) => {
+                if let Some(dialog) = self.dialogs.iter_mut().find(|dialog| dialog.id == id) {
+                    dialog.set_all(all);
+                }
+                Task::none()
+            }
+            Message::CancelDialog(id) => {
+                if !self
+                    .dialogs
+                    .iter()
+                    .any(|dialog| dialog.id == id && dialog.is_cancellable())
+                {
+                    return Task::none();
+                }
+                self.close_dialog(id)
+                    .and_then(Dialog::cancel)
+                    .map_or_else(Task::none, |message| self.update(message))
+            }
+            Message::ProfileRestored { dialog, result } => match result {
+                Ok(()) => self.replace_state(Cli {
+                    recent: false,
+                    paths: Vec::new(),
+                    ..self.cli.clone()
+                }),
+                Err(err) => {
+                    self.close_dialog(dialog);
+                    self.report_error(&err);
+                    Task::none()
+                }
+            },
+            Message::FeedDownloaded { item_path, result } => {
+                if let Err(err) = result {
+                    self.report_error(&err);
+                    return Task::none();
+                }
+                // The downloaded archive replaces the remote cover of its item.
+                self.thumbnail_cache.remove(&item_path.path);
+                if let Some(pane) = self.get_pane_mut(item_path.view_path)
+                    && let DirView::Dir { items, .. } = &mut pane.view
+                    && let Some(item) = items.get_mut(&item_path.path)
+                {
+                    let file = ::std::fs::metadata(&item_path.path).ok();
+                    item.size = file.as_ref().map(::std::fs::Metadata::len);
+                    item.modified = file.and_then(|file| file.modified().ok());
+                    item.thumbnail = pane::Thumbnail::Pending;
+                }
+                Task::batch([
+                    self.open_archive(Arc::clone(&item_path.path)),
+                    self.request_thumbnails(item_path.view_path),
+                ])
+            }
+            Message::AddToReadingList(view_path) => {
+                self.add_to_reading_list(view_path);
+                Task::none()
+            }
+            Message::RemoveFromReadingList(view_path) => {
+                self.remove_from_reading_list(view_path);
+                self.request_thumbnails(view_path)
+            }
+            Message::OpenWith(view_path, application) => {
+                if let Some(pane) = self.get_pane_mut(view_path)
+                    && let Some(path) = pane.context_menu.take()
+                    && let Err(err) = application.launch(&path)
+                {
+                    self.report_error(&err);
+                }
+                Task::none()
+            }
+            Message::ShowChooser(item_path) => {
+                let applications = open_with::applications(
+                    &self.settings.open_with,
+                    &self.xdg_dirs,
+                    &item_path.path,
+                );
+                let preferred = self
+                    .catalogue
+                    .preferred_application(&item_path.path)
+                    .unwrap_or_else(|err| {
+                        err.log();
+                        None
+                    });
+                let Some(pane) = self.get_pane_mut(item_path.view_path) else {
+                    return Task::none();
+                };
+                pane.context_menu = None;
+                pane.chooser = Some(Chooser::new(
+                    item_path.path,
+                    applications,
+                    preferred.as_deref(),
+                ));
+                Task::none()
+            }
+            Message::ChooseApplication(view_path, chosen) => {
+                if let Some(chooser) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.chooser.as_mut())
+                {
+                    chooser.chosen = chosen;
+                }
+                Task::none()
+            }
+            Message::SetChooserRemember(view_path, remember) => {
+                if let Some(chooser) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.chooser.as_mut())
+                {
+                    chooser.remember = remember;
+                }
+                Task::none()
+            }
+            Message::ConfirmChooser(view_path) => {
+                let Some(chooser) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.chooser.take())
+                else {
+                    return Task::none();
+                };
+                let name = chooser.chosen_name();
+                let remembered = match (chooser.remember, ArchiveKind::from_path(&chooser.path)) {
+                    (Remember::Once, _) => Ok(()),
+                    (Remember::Item, _) | (Remember::Kind, None) => {
+                        self.catalogue.set_item_application(&chooser.path, name)
+                    }
+                    (Remember::Kind, Some(kind)) => {
+                        self.catalogue
+                            .set_kind_application(&chooser.path, kind, name)
+                    }
+                };
+                if let Err(err) = remembered {
+                    self.report_error(&err);
+                }
+                match chooser
+                    .chosen
+                    .and_then(|index| chooser.applications.get(index))
+                {
+                    Some(application) => {
+                        if let Err(err) = application.launch(&chooser.path) {
+                            self.report_error(&err);
+                        }
+                        Task::none()
+                    }
+                    None => self.open_reader(chooser.path),
+                }
+            }
+            Message::CancelChooser(view_path) => {
+                if let Some(pane) = self.get_pane_mut(view_path) {
+                    pane.chooser = None;
+                }
+                Task::none()
+            }
+            Message::ToggleShelf(view_path) => {
+                let Some(pane) = self.get_pane_mut(view_path) else {
+                    return Task::none();
+                };
+                pane.shelf = !pane.shelf;
+                pane.reload()
+                    .map_or_else(Task::none, |path| self.open_dir(path, None, view_path))
+            }
+            Message::ShelfLoaded {
+                view_path,
+                token,
+                series,
+            } => {
+                let notify = self.show_shelf(view_path, &token, series);
+                Task::batch([
+                    notify,
+                    self.count_pages(view_path, &token),
+                    self.request_thumbnails(view_path),
+                ])
+            }
+            Message::WishMissing(view_path, row) => {
+                let Some(DirView::Dir {
+                    shelf: Some(rows), ..
+                }) = self.get_dir_view_mut(view_path)
+                else {
+                    return Task::none();
+                };
+                let Some(row) = rows.get_mut(row) else {
+                    return Task::none();
+                };
+                let missing = ::core::mem::take(&mut row.missing);
+                row.wished.extend_from_slice(&missing);
+                row.wished.sort_by(f64::total_cmp);
+                let name = row.name.clone();
+                if let Err(err) = self.catalogue.add_wishes(&name, &missing) {
+                    self.report_error(&err);
+                }
+                Task::none()
+            }
+            Message::Unwish(view_path, row, volume) => {
+                let Some(DirView::Dir {
+                    shelf: Some(rows), ..
+                }) = self.get_dir_view_mut(view_path)
+                else {
+                    return Task::none();
+                };
+                let Some(row) = rows.get_mut(row) else {
+                    return Task::none();
+                };
+                row.wished.retain(|wished| *wished != volume);
+                let name = row.name.clone();
+                if let Err(err) = self.catalogue.remove_wishes(&name, Some(&[volume])) {
+                    self.report_error(&err);
+                }
+                Task::none()
+            }
+            Message::ToggleFollow(view_path, row) => {
+                let Some(DirView::Dir {
+                    shelf: Some(rows), ..
+                }) = self.get_dir_view_mut(view_path)
+                else {
+                    return Task::none();
+                };
+                let Some(row) = rows.get_mut(row) else {
+                    return Task::none();
+                };
+                row.followed = !row.followed;
+                row.alerts = 0;
+                let (name, followed) = (row.name.clone(), row.followed);
+                let result = if followed {
+                    self.catalogue.follow(&name)
+                } else {
+                    self.catalogue.unfollow(&name).map(|_| ())
+                };
+                if let Err(err) = result {
+                    self.report_error(&err);
+                }
+                Task::none()
+            }
+            Message::DismissAlerts(view_path, row) => {
+                let Some(DirView::Dir {
+                    shelf: Some(rows), ..
+                }) = self.get_dir_view_mut(view_path)
+                else {
+                    return Task::none();
+                };
+                let Some(row) = rows.get_mut(row) else {
+                    return Task::none();
+                };
+                row.alerts = 0;
+                let name = row.name.clone();
+                if let Err(err) = self.catalogue.dismiss_alerts(Some(&name)) {
+                    self.report_error(&err);
+                }
+                Task::none()
+            }
+            Message::ShelfScrolled {
+                view_path,
+                row,
+                offset,
+            } => {
+                if let Some(DirView::Dir {
+                    shelf: Some(rows), ..
+                }) = self.get_dir_view_mut(view_path)
+                    && let Some(row) = rows.get_mut(row)
+                {
+                    row.offset = offset;
+                }
+                self.request_thumbnails(view_path)
+            }
+            Message::ToggleReadingOrder(view_path) => {
+                let Some(pane) = self.get_pane_mut(view_path) else {
+                    return Task::none();
+                };
+                pane.reading_order = !pane.reading_order;
+                if pane.reading_order {
+                    self.sort_reading_order(view_path);
+                } else if let DirView::Dir { order, .. } = &mut pane.view {
+                    *order = None;
+                }
+                self.request_thumbnails(view_path)
+            }
+            Message::MoveInReadingOrder(view_path, offset) => {
+                self.move_in_reading_order(view_path, offset);
+                self.request_thumbnails(view_path)
+            }
+            Message::SetViewMode(view_path, mode) => {
+                let Some(pane) = self.get_pane_mut(view_path) else {
+                    return Task::none();
+                };
+                pane.mode = mode;
+                if let DirView::Dir { scroll_offset, .. } = &mut pane.view {
+                    *scroll_offset = 0.0;
+                }
+                self.save_view_mode(view_path);
+                self.request_thumbnails(view_path)
+            }
+            Message::SortBy(view_path, column) => {
+                let Some(pane) = self.get_pane_mut(view_path) else {
+                    return Task::none();
+                };
+                pane.sort = Some(Sort::pressed(pane.sort, column));
+                self.save_view_mode(view_path);
+                self.request_thumbnails(view_path)
+            }
+            Message::SetFilterText(view_path, text) => {
+                let Some(pane) = self.get_pane_mut(view_path) else {
+                    return Task::none();
+                };
+                pane.filter.set_text(text);
+                self.request_thumbnails(view_path)
+            }
+            Message::ToggleFilterChip(view_path, chip) => {
+                let Some(pane) = self.get_pane_mut(view_path) else {
+                    return Task::none();
+                };
+                pane.filter.toggle(chip);
+                self.request_thumbnails(view_path)
+            }
+            Message::JumpToLetter(view_path, letter) => self.jump_to_letter(view_path, letter),
+            Message::ToggleStarred(item_path) => {
+                self.update_item_state(&item_path.path, |state| state.starred = !state.starred);
+                self.request_thumbnails(item_path.view_path)
+            }
+            Message::CycleLabel(item_path) => {
+                self.update_item_state(&item_path.path, |state| {
+                    state.label = Label::cycle(state.label);
+                });
+                self.request_thumbnails(item_path.view_path)
+            }
+
+            Message::MouseScrolled(window_id, delta) if self.modifiers.control() => {
+                let Some(view_path) = self
+                    .hovered_pane
+                    .filter(|view_path| view_path.window_id == window_id)
+                else {
+                    return Task::none();
+                };
+                // Wheels resize a step per notch, touchpads per distance scrolled.
+                let (y, distance) = match delta {
+                    ScrollDelta::Lines { y, .. } => (y, 0.0),
+                    ScrollDelta::Pixels { y, .. } => {
+                        self.card_zoom += y;
+                        (self.card_zoom, f32::from(self.settings.pinch_distance))
+                    }
+                };
+                if y.abs() <= distance {
+                    return Task::none();
+                }
+                self.card_zoom = 0.0;
+                self.resize_cards(view_path, if y > 0.0 { 1 } else { -1 })
+            }
+            Message::MouseScrolled(window_id, delta) => {
+                let distance = f32::from(self.settings.swipe_distance);
+                // Touchpads report pixel deltas, wheels report line deltas.
+                let ScrollDelta::Pixels { x, y } = delta else {
+                    return Task::none();
+                };
+                if distance == 0.0
+                    || self
+                        .hovered_pane
+                        .is_none_or(|view_path| view_path.window_id != window_id)
+                {
+                    return Task::none();
+                }
+                match self.swipe.scrolled(x, y, distance, Instant::now()) {
+                    Some(Turn::Back) => self.perform(Action::Back),
+                    Some(Turn::Forward) => self.perform(Action::Forward),
+                    None => Task::none(),
+                }
+            }
+            Message::SetSwipeDistance(distance) => {
+                self.draft.swipe_distance = distance;
+                Task::none()
+            }
+            Message::SetPinchDistance(distance) => {
+                self.draft.pinch_distance = distance;
+                Task::none()
+            }
+            Message::SetSlideshowInterval(interval) => {
+                self.draft.slideshow_interval = interval.max(1);
+                Task::none()
+            }
+            Message::SetSlideshowLoop(slideshow_loop) => {
+                self.draft.slideshow_loop = slideshow_loop;
+                Task::none()
+            }
+            Message::SetHoverPreviewDelay(delay) => {
+                self.draft.hover_preview_delay = delay;
+                Task::none()
+            }
+            Message::SetConvertQuality(quality) => {
+                self.draft.convert_quality = quality.min(100);
+                Task::none()
+            }
+            Message::SetScaleFactor(scale_factor) => {
+                self.draft.scale_factor = scale_factor.clamp(MIN_SCALE_FACTOR, MAX_SCALE_FACTOR);
+                Task::none()
+            }
+            Message::SetFontSize(font_size) => {
+                self.draft.font_size = font_size;
+                Task::none()
+            }
+            Message::SetLanguage(Language(language)) => {
+                self.draft.language = language.map(str::to_owned);
+                Task::none()
+            }
+            Message::SetFreedesktopThumbnails(freedesktop_thumbnails) => {
+                self.draft.freedesktop_thumbnails = freedesktop_thumbnails;
+                Task::none()
+            }
+            Message::SetTaskNotifications(task_notifications) => {
+                self.draft.task_notifications = task_notifications;
+                Task::none()
+            }
+            Message::SetSkipHidden(skip_hidden) => {
+                self.draft.skip_hidden = skip_hidden;
+                Task::none()
+            }
+            Message::SetFollowSymlinks(follow_symlinks) => {
+                self.draft.follow_symlinks = follow_symlinks;
+                Task::none()
+            }
+            Message::SetBatterySaver(battery_saver) => {
+                self.draft.battery_saver = battery_saver;
+                Task::none()
+            }
+            Message::SetAnimateCovers(animate_covers) => {
+                self.draft.animate_covers = animate_covers;
+                Task::none()
+            }
+            Message::SetCoverAccents(cover_accents) => {
+                self.draft.cover_accents = cover_accents;
+                Task::none()
+            }
+            Message::SetSectionHeaders(section_headers) => {
+                self.draft.section_headers = section_headers;
+                Task::none()
+            }
+            Message::ReportError(err) => {
+                self.report_error(&err);
+                Task::none()
+            }
+            Message::DismissError => {
+                self.error = None;
+                Task::none()
+            }
+            Message::Diagnosed(problems) => {
+                problems.iter().for_each(doctor::Problem::log);
+                if !problems.is_empty() {
+                    self.error = Some(tr_args(
+                        "status-problems-found",
+                        &[("count", &problems.len().to_string())],
+                    ));
+                }
+                Task::none()
+            }
+            Message::ClockTick => Task::none(),
+            Message::SpinnerTick => {
+                self.spinner_frame = self.spinner_frame.wrapping_add(1);
+                Task::none()
+            }
+            Message::AnimationTick => {
+                let now = Instant::now();
+                if self.animates_covers() {
+                    self.thumbnail_cache.advance(now);
+                }
+                for window in self.windows.values_mut() {
+                    if let Window::Reader(reader) = window
+                        && let Some(animation) = &mut reader.animation
+                    {
+                        animation.advance(now);
+                    }
+                }
+                Task::none()
+            }
+            Message::ScanFinished { view_path, token } => {
+                if let Some(pane) = self.get_pane_mut(view_path)
+                    && pane.is_current(&token)
+                {
+                    pane.scanning = false;
+                    let long_scan = pane
+                        .scan_started
+                        .take()
+                        .is_some_and(|started| started.elapsed() >= LONG_SCAN);
+                    let scanned = match (&pane.path, &pane.view) {
+                        (Some(path), DirView::Dir { items, .. }) if long_scan => Some((
+                            tr_args("notify-scanned", &[("name", &list_entry_name(path))]),
+                            tr_args("notify-items", &[("count", &format::number(items.len()))]),
+                        )),
+                        _ => None,
+                    };
+                    let shelf = pane.shelf;
+                    let reading_order = pane.reading_order;
+                    let visited = pane.path.clone().filter(|_| pane.shows_dir());
+                    let notify = match scanned {
+                        Some((summary, body)) => self.notify_unfocused(summary, body),
+                        None => Task::none(),
+                    };
+                    let notify = self.record_seen(view_path).chain(notify);
+                    if let Some(visited) = visited {
+                        self.catalogue
+                            .record_visit(&visited)
+                            .unwrap_or_else(|err| err.log());
+                    }
+                    if reading_order {
+                        self.sort_reading_order(view_path);
+                    }
+                    let count_pages = self.count_pages(view_path, &token);
+                    if shelf {
+                        return Task::batch([
+                            notify,
+                            count_pages,
+                            self.scan_shelf(view_path, token),
+                        ]);
+                    }
+                    return Task::batch([notify, count_pages]);
+                }
+                Task::none()
+            }
+            Message::DebugTick => {
+                if self.debug_stats.is_some() {
+                    self.debug_stats = Some(self.debug_stats());
+                }
+                Task::none()
+            }
+            Message::OnBatteryChanged(on_battery) => {
+                self.on_battery = on_battery;
+                self.throttle.set(self.is_throttled());
+                // Tasks held back while throttled start once no longer throttled.
+                Task::batch([self.request_all_thumbnails(), self.run_tasks()])
+            }
+            Message::SetThumbnailCacheSize(size) => {
+                self.draft.thumbnail_cache_size = size;
+                Task::none()
+            }
+            Message::PaneFocused(view_path) => {
+                self.focused_pane = Some(view_path);
+                Task::none()
+            }
+            Message::PaneReleased(view_path) => {
+                if let Some(entry) = self.sidebar_drag.take() {
+                    return self.show_sidebar_entry(view_path, entry);
+                }
+                self.drop_onto(view_path)
+            }
+            Message::ConfirmTransfer => {
+                let Some(transfer) = self
+                    .transfer
+                    .as_mut()
+                    .filter(|transfer| transfer.done.is_none())
+                else {
+                    return Task::none();
+                };
+                transfer.done = Some(0);
+                let dialog = transfer.progress_dialog();
+                transfer.dialog = Some(dialog.id);
+                Task::batch([self.show_dialog(dialog), self.transfer_next()])
+            }
+            Message::ResolveTransferConflict(resolution, all) => {
+                let Some(transfer) = &mut self.transfer else {
+                    return Task::none();
+                };
+                transfer.resolve_next = Some(resolution);
+                if all {
+                    transfer.resolve_all = Some(resolution);
+                }
+                self.transfer_next()
+            }
+            Message::StopTransfer => {
+                let Some(transfer) = &mut self.transfer else {
+                    return Task::none();
+                };
+                transfer.stop();
+                self.transfer_next()
+            }
+            Message::CancelTransfer => {
+                if self
+                    .transfer
+                    .as_ref()
+                    .is_some_and(|transfer| transfer.done.is_none())
+                {
+                    self.transfer = None;
+                }
+                Task::none()
+            }
+            Message::TransferStepped { from, size, result } => {
+                let Some(kind) = self.transfer.as_ref().map(|transfer| transfer.kind) else {
+                    return Task::none();
+                };
+                let failed = match result {
+                    Ok(Outcome::Transferred(to)) => {
+                        if kind.is_move()
+                            && let Err(err) = self.catalogue.rename(&from, &to)
+                        {
+                            self.report_error(&err);
+                        }
+                        false
+                    }
+                    Ok(Outcome::Skipped) => false,
+                    Ok(Outcome::Conflict(to)) => return self.ask_transfer_conflict(&to),
+                    Err(err) => {
+                        self.report_error(&err);
+                        true
+                    }
+                };
+                if let Some(transfer) = &mut self.transfer {
+                    transfer.step(size, failed);
+                    if let Some(dialog) = self
+                        .dialogs
+                        .iter_mut()
+                        .find(|dialog| Some(dialog.id) == transfer.dialog)
+                    {
+                        transfer.update_dialog(dialog);
+                    }
+                }
+                self.transfer_next()
+            }
+            Message::PaneHovered(view_path) => {
+                if self.hovered_pane != Some(view_path) {
+                    self.swipe.reset();
+                }
+                self.hovered_pane = Some(view_path);
+                Task::none()
+            }
+            Message::KeyEvent(event) => match event {
+                ::iced::keyboard::Event::ModifiersChanged(modifiers) => {
+                    self.modifiers = modifiers;
+                    Task::none()
+                }
+                ::iced::keyboard::Event::KeyPressed { key, modifiers, .. } => {
+                    let Some(window_id) = self.focused_window else {
+                        return Task::none();
+                    };
+                    let scale_step = match Shortcut::from_key(
+                        ShortcutContext::Global,
+                        key.as_ref(),
+                        modifiers,
+                    ) {
+                        Some(Shortcut::ZoomIn) => Some(Some(SCALE_FACTOR_STEP)),
+                        Some(Shortcut::ZoomOut) => Some(Some(-SCALE_FACTOR_STEP)),
+                        Some(Shortcut::ResetZoom) => Some(None),
+                        _ => None,
+                    };
+                    if let Some(step) = scale_step {
+                        self.step_scale_factor(step);
+                        return Task::none();
+                    }
+                    // Dialogs are modal, keys only answer or cancel them.
+                    if let Some(dialog) = self.top_dialog(window_id) {
+                        let id = dialog.id;
+                        return match key.as_ref() {
+                            Key::Named(Named::Enter) => self.update(Message::AnswerDialog(id)),
+                            Key::Named(Named::Escape) => self.update(Message::CancelDialog(id)),
+                            _ => Task::none(),
+                        };
+                    }
+                    let reader = match self.windows.get(&window_id) {
+                        Some(Window::Reader(reader)) => reader,
+                        Some(Window::Main { .. }) => {
+                            let shortcut =
+                                Shortcut::from_key(ShortcutContext::Main, key.as_ref(), modifiers);
+                            let Some(view_path) = self.shortcut_pane(window_id) else {
+                                return match shortcut {
+                                    Some(Shortcut::Undo) => self.perform(Action::Undo),
+                                    _ => Task::none(),
+                                };
+                            };
+                            // Shortcut keys continue a type-ahead instead of acting once
+                            // one has been started.
+                            let typing = self
+                                .get_pane_mut(view_path)
+                                .is_some_and(|pane| pane.type_ahead.is_active(Instant::now()));
+                            let shortcut = match shortcut {
+                                Some(Shortcut::MarkRead | Shortcut::MarkUnread) if typing => {
+                                    Some(Shortcut::TypeAhead)
+                                }
+                                shortcut => shortcut,
+                            };
+                            return match shortcut {
+                                Some(Shortcut::Undo) => self.perform(Action::Undo),
+                                Some(Shortcut::MoveCursor) => {
+                                    let movement = match key.as_ref() {
+                                        Key::Named(Named::ArrowLeft) => Movement::Left,
+                                        Key::Named(Named::ArrowRight) => Movement::Right,
+                                        Key::Named(Named::ArrowUp) => Movement::Up,
+                                        Key::Named(Named::ArrowDown) => Movement::Down,
+                                        Key::Named(Named::PageUp) => Movement::PageUp,
+                                        Key::Named(Named::PageDown) => Movement::PageDown,
+                                        Key::Named(Named::Home) => Movement::Home,
+                                        _ => Movement::End,
+                                    };
+                                    self.move_cursor(view_path, movement, modifiers)
+                                }
+                                Some(Shortcut::MarkRead) => {
+                                    self.perform_on(Action::MarkRead, Some(view_path))
+                                }
+                                Some(Shortcut::MarkUnread) => {
+                                    self.perform_on(Action::MarkUnread, Some(view_path))
+                                }
+                                Some(Shortcut::Filter) => {
+                                    widget::operation::focus(pane::filter_id(view_path))
+                                }
+                                Some(Shortcut::CycleSort) => {
+                                    self.cycle_sort(view_path, modifiers.shift())
+                                }
+                                Some(Shortcut::ClosePane) => {
+                                    self.perform_on(Action::ClosePane, Some(view_path))
+                                }
+                                Some(Shortcut::CopyPaths) => {
+                                    self.copy_selection(view_path, CopyKind::Paths)
+                                }
+                                Some(Shortcut::CopyNames) => {
+                                    self.copy_selection(view_path, CopyKind::Names)
+                                }
+                                Some(Shortcut::Paste) => Task::done(Message::Paste(view_path)),
+                                Some(Shortcut::Rescan) => self.rescan(view_path),
+                                Some(Shortcut::TypeAhead) => match key.as_ref() {
+                                    Key::Character(text) => self.type_ahead(view_path, text),
+                                    _ => Task::none(),
+                                },
+                                Some(Shortcut::Activate) => {
+                                    let Some(pane) = self.get_pane_mut(view_path) else {
+                                        return Task::none();
+                                    };
+                                    let Some(path) = pane.view.cursor().cloned() else {
+                                        return Task::none();
+                                    };
+                                    self.activate(ItemPath { view_path, path })
+                                }
+                                Some(Shortcut::QuickLook) => {
+                                    Task::done(Message::ToggleQuickLook(view_path))
+                                }
+                                Some(Shortcut::MoveToTrash) => self.move_to_trash(view_path),
+                                Some(Shortcut::Cancel) => Task::batch([
+                                    Task::done(Message::CancelPack(view_path)),
+                                    Task::done(Message::CancelChooser(view_path)),
+                                    Task::done(Message::CloseQuickLook(view_path)),
+                                    Task::done(Message::CloseContextMenu(view_path)),
+                                ]),
+                                _ => Task::none(),
+                            };
+                        }
+                        _ => return Task::none(),
+                    };
+                    let message = match Shortcut::from_key(
+                        ShortcutContext::Reader,
+                        key.as_ref(),
+                        modifiers,
+                    ) {
+                        Some(Shortcut::Fullscreen) => {
+                            return Task::done(Message::ToggleFullscreen(window_id));
+                        }
+                        Some(Shortcut::LeaveFullscreen) if reader.fullscreen => {
+                            return Task::done(Message::ToggleFullscreen(window_id));
+                        }
+                        Some(Shortcut::Bookmark) => Some(Message::ToggleBookmark(window_id)),
+                        Some(Shortcut::PageStrip) => Some(Message::TogglePageStrip(window_id)),
+                        Some(Shortcut::Continuous) => Some(Message::ToggleContinuous(window_id)),
+                        Some(Shortcut::Slideshow) => Some(Message::ToggleSlideshow(window_id)),
+                        Some(Shortcut::NextPage) => reader.forward(window_id),
+                        Some(Shortcut::PreviousPage) => reader
+                            .prev_page()
+                            .map(|page| Message::GotoPage { window_id, page }),
+                        _ => None,
+                    };
+                    if !message.as_ref().is_some_and(Message::is_toggle_slideshow) {
+                        self.delay_slideshow(window_id);
+                    }
+                    message.map_or_else(Task::none, Task::done)
+                }
+                ::iced::keyboard::Event::KeyReleased { key, modifiers, .. } => {
+                    let function_key = match key.as_ref() {
+                        Key::Named(named) if modifiers.is_empty() => FunctionKey::from_named(named),
+                        _ => None,
+                    };
+                    function_key.map_or_else(Task::none, |function_key| {
+                        self.perform(self.settings.bindings.key_action(function_key))
+                    })
+                }
+            },
+            Message::ApplySettings => {
+                let apply = self.apply_settings();
+                if self.settings_close_prompt {
+                    Task::batch([apply, self.close_settings()])
+                } else {
+                    apply
+                }
+            }
+            Message::RevertSettings => {
+                self.draft = self.settings.clone();
+                if self.settings_close_prompt {
+                    self.close_settings()
+                } else {
+                    Task::none()
+                }
+            }
+            Message::SaveSettings => match self.overrides.save(&self.draft, &self.xdg_dirs) {
+                Ok(()) => {
+                    self.saved_settings = self.draft.clone();
+                    self.disk_settings = None;
+                    self.apply_settings()
+                }
+                Err(err) => {
+                    self.report_error(&err);
+                    Task::none()
+                }
+            },
+            Message::ReloadSettigns => {
+                // Loaded settings are edited like any other change, and used once applied.
+                match Settings::load(&self.xdg_dirs)
+                    .and_then(|settings| self.overrides.apply(settings))
+                {
+                    Ok(settings) => {
+                        self.saved_settings = settings.clone();
+                        self.disk_settings = None;
+                        self.draft = settings;
+                    }
+                    Err(err) => self.report_error(&err),
+                }
+                Task::none()
+            }
+            Message::CloseSettingsRequested(window_id) => {
+                if matches!(self.windows.get(&window_id), Some(Window::Settings)) {
+                    self.request_close_settings(window_id)
+                } else {
+                    window::close(window_id).map(Message::RemoveWindow)
+                }
+            }
+            Message::CancelCloseSettings => {
+                self.settings_close_prompt = false;
+                Task::none()
+            }
+            Message::SettingsChangedOnDisk(settings) => {
+                let settings = match self.overrides.apply(settings) {
+                    Ok(settings) => settings,
+                    Err(err) => {
+                        self.report_error(&err);
+                        return Task::none();
+                    }
+                };
+                if settings == self.saved_settings {
+                    // Written by application, or changed back.
+                    self.disk_settings = None;
+                    Task::none()
+                } else if self.settings == self.saved_settings && !self.has_unapplied_settings() {
+                    self.set_saved_settings(settings)
+                } else {
+                    self.disk_settings = Some(settings);
+                    Task::none()
+                }
+            }
+            Message::LoadSettingsFromDisk => self
+                .disk_settings
+                .take()
+                .map_or_else(Task::none, |settings| self.set_saved_settings(settings)),
+            Message::KeepSettings => {
+                if let Some(settings) = self.disk_settings.take() {
+                    self.saved_settings = settings;
+                }
+                Task::none()
+            }
+            Message::ThemeScroll(delta) => {
+                match discrete_scroll::Vertical.discrete_scroll(delta, &mut self.theme_scroll) {
+                    discrete_scroll::Direction::Forwards => {
+                        self.draft.theme = self.draft.theme.cycle_next();
+                    }
+                    discrete_scroll::Direction::Backwards => {
+                        self.draft.theme = self.draft.theme.cycle_prev();
+                    }
+                    discrete_scroll::Direction::Stationary => {}
+                }
+                Task::none()
+            }
+            Message::AddItem {
+                item_path: ItemPath { view_path, path },
+                token,
+                mut item,
+            } => {
+                item.state = self.catalogue.get(&path).unwrap_or_else(|err| {
+                    err.log();
+                    ItemState::default()
+                });
+                // Archives not yet seen are recorded as seen once the scan finishes.
+                if let Some(last_visit) = self
+                    .get_pane_mut(view_path)
+                    .and_then(|pane| pane.last_visit)
+                    && ArchiveKind::from_path(&path).is_some()
+                {
+                    item.new = self
+                        .catalogue
+                        .first_seen(&path)
+                        .inspect_err(Error::log)
+                        .ok()
+                        .is_some_and(|first_seen| {
+                            first_seen.is_none_or(|first_seen| first_seen > last_visit)
+                        });
+                }
+                let Some(view) = self.get_current_view_mut(view_path, &token) else {
+                    return Task::none();
+                };
+
+                match view {
+                    DirView::Empty => *view = DirView::with_item(path, item),
+                    DirView::Dir { items, order, .. } => {
+                        // Items found after sorting are placed last until sorted again.
+                        if let Some(order) = order
+                            && !items.contains_key(&path)
+                        {
+                            order.push(Arc::clone(&path));
+                        }
+                        items.insert(path, item);
+                    }
+                }
+
+                self.request_thumbnails(view_path)
+            }
+            Message::ItemPressed(item_path) => {
+                let modifiers = self.modifiers;
+                let Some(pane) = self.get_pane_mut(item_path.view_path) else {
+                    ::log::warn!("could not resolve view path {:?}", item_path.view_path);
+                    return Task::none();
+                };
+                pane.context_menu = None;
+                let sort = pane.list_sort();
+                pane.view
+                    .select(&item_path.path, &pane.filter, sort, modifiers);
+                // Only archives are dragged, directories are left where they are.
+                let draggable = pane.shows_dir();
+                self.drag = match &pane.view {
+                    DirView::Dir { selected, .. } if draggable => Some(Drag {
+                        source: item_path.view_path,
+                        paths: selected
+                            .iter()
+                            .filter(|path| ArchiveKind::from_path(path).is_some())
+                            .cloned()
+                            .collect(),
+                    })
+                    .filter(|drag| !drag.paths.is_empty()),
+                    _ => None,
+                };
+
+                if modifiers.is_empty() && self.settings.activation.is_single_click() {
+                    self.activate(item_path)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::ItemMiddleClicked(item_path) => self.open_reader(item_path.path),
+            Message::ItemDoubleClicked(item_path) => {
+                if self.modifiers.is_empty() && self.settings.activation.is_double_click() {
+                    self.activate(item_path)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::ToggleFullscreen(window_id) => {
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                reader.fullscreen = !reader.fullscreen;
+                window::set_mode(
+                    window_id,
+                    if reader.fullscreen {
+                        window::Mode::Fullscreen
+                    } else {
+                        window::Mode::Windowed
+                    },
+                )
+            }
+            Message::GotoPage { window_id, page } => {
+                let Some(Window::Reader(reader)) = self.windows.get(&window_id) else {
+                    return Task::none();
+                };
+                if reader.continuous.is_some() {
+                    return self.jump_continuous(window_id, page);
+                }
+                Self::load_page(
+                    window_id,
+                    Arc::clone(&reader.path),
+                    page,
+                    reader.adjustments,
+                )
+            }
+            Message::PageLoaded {
+                window_id,
+                page,
+                page_count,
+                image,
+                dimensions,
+                animation,
+            } => {
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                reader.page_count = Some(page_count);
+                reader.gestures.set_page(dimensions);
+                reader.animation = animation;
+                let scroll = if let Some(continuous) = &mut reader.continuous
+                    && continuous.insert(page, image.clone(), dimensions)
+                {
+                    Self::scroll_continuous(window_id, continuous.offset)
+                } else {
+                    Task::none()
+                };
+                reader.image = Some(image);
+                Task::batch([
+                    scroll,
+                    self.show_page(window_id, page),
+                    self.request_continuous_pages(window_id),
+                ])
+            }
+            Message::ToggleContinuous(window_id) => {
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                if reader.continuous.take().is_some() {
+                    // Image of the page shown before may be outdated once scrolled.
+                    return Self::load_page(
+                        window_id,
+                        Arc::clone(&reader.path),
+                        reader.page,
+                        reader.adjustments,
+                    );
+                }
+                reader.continuous = Some(Continuous::default());
+                let page = reader.page;
+                self.jump_continuous(window_id, page)
+            }
+            Message::ContinuousScrolled {
+                window_id,
+                offset,
+                size,
+            } => {
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                let Some(continuous) = &mut reader.continuous else {
+                    return Task::none();
+                };
+                if !continuous.jumping {
+                    continuous.offset = offset;
+                }
+                let scroll = if continuous.resize(size) {
+                    Self::scroll_continuous(window_id, continuous.offset)
+                } else {
+                    Task::none()
+                };
+                let current = reader
+                    .page_count
+                    .filter(|_| !continuous.jumping)
+                    .map(|count| continuous.current(count))
+                    .filter(|&page| page != reader.page);
+                let show = current.map_or_else(Task::none, |page| self.show_page(window_id, page));
+                Task::batch([scroll, show, self.request_continuous_pages(window_id)])
+            }
+            Message::ContinuousJumped(window_id) => {
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                let Some(continuous) = &mut reader.continuous else {
+                    return Task::none();
+                };
+                continuous.jumping = false;
+                let current = reader
+                    .page_count
+                    .map(|count| continuous.current(count))
+                    .filter(|&page| page != reader.page);
+                current.map_or_else(Task::none, |page| self.show_page(window_id, page))
+            }
+            Message::ContinuousPagesLoaded {
+                window_id,
+                path,
+                adjustments,
+                pages,
+                loaded,
+            } => {
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                if reader.path != path {
+                    return Task::none();
+                }
+                let Some(continuous) = &mut reader.continuous else {
+                    return Task::none();
+                };
+                for page in &pages {
+                    continuous.loading.remove(page);
+                }
+                // Pages loaded before adjustments changed are loaded again.
+                if adjustments != reader.adjustments {
+                    return self.request_continuous_pages(window_id);
+                }
+                let mut moved = false;
+                for LoadedPage {
+                    page,
+                    image,
+                    dimensions,
+                } in loaded
+                {
+                    moved |= continuous.insert(page, image, dimensions);
+                }
+                let scroll = if moved {
+                    Self::scroll_continuous(window_id, continuous.offset)
+                } else {
+                    Task::none()
+                };
+                Task::batch([scroll, self.request_continuous_pages(window_id)])
+            }
+            Message::ToggleAdjustments(window_id) => {
+                if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) {
+                    reader.adjusting = !reader.adjusting;
+                }
+                Task::none()
+            }
+            Message::SetAdjustments(window_id, adjustments) => {
+                if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) {
+                    reader.adjustments = adjustments;
+                }
+                Task::none()
+            }
+            Message::ApplyAdjustments(window_id) => self.apply_adjustments(window_id),
+            Message::AdjustPages(window_id, adjustments) => {
+                if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) {
+                    reader.adjustments = adjustments;
+                }
+                self.apply_adjustments(window_id)
+            }
+            Message::RememberAdjustments(window_id, remember) => {
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                reader.remember_adjustments = remember;
+                self.catalogue
+                    .set_adjustments(&reader.path, remember.then_some(&reader.adjustments))
+                    .unwrap_or_else(|err| err.log());
+                Task::none()
+            }
+            Message::TogglePageStrip(window_id) => {
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                reader.strip = match reader.strip {
+                    Some(_) => None,
+                    None => Some(PageStrip::default()),
+                };
+                self.reveal_strip_page(window_id)
+            }
+            Message::PageStripScrolled {
+                window_id,
+                offset,
+                width,
+            } => {
+                if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id)
+                    && let Some(strip) = &mut reader.strip
+                {
+                    strip.offset = offset;
+                    strip.width = Some(width);
+                }
+                self.request_page_thumbnails(window_id)
+            }
+            Message::PageThumbnailsLoaded {
+                window_id,
+                path,
+                pages,
+                thumbnails,
+            } => {
+                if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id)
+                    && reader.path == path
+                    && let Some(strip) = &mut reader.strip
+                {
+                    for page in &pages {
+                        strip.loading.remove(page);
+                    }
+                }
+                for (page, handle) in thumbnails {
+                    self.thumbnail_cache
+                        .insert_page(Arc::clone(&path), page, handle.into());
+                }
+                Task::none()
+            }
+            Message::VolumePrefetched {
+                window_id,
+                path,
+                page_count,
+                image,
+                dimensions,
+                animation,
+            } => {
+                if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id)
+                    && reader.next_volume.as_ref() == Some(&path)
+                {
+                    reader.prefetched = Some(Prefetched {
+                        page_count,
+                        image,
+                        dimensions,
+                        animation,
+                    });
+                }
+                Task::none()
+            }
+            Message::ReaderPressed { window_id, area } => {
+                self.delay_slideshow(window_id);
+                if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) {
+                    reader.gestures.pressed(area);
+                }
+                Task::none()
+            }
+            Message::ReaderReleased(window_id) => match self.windows.get(&window_id) {
+                Some(Window::Reader(reader)) if reader.gestures.is_tap() => reader
+                    .forward(window_id)
+                    .map_or_else(Task::none, Task::done),
+                _ => Task::none(),
+            },
+            Message::ReaderScrolled {
+                window_id,
+                delta,
+                area,
+            } => {
+                let (zoom, swipe_distance) = (
+                    self.modifiers.control(),
+                    f32::from(self.settings.swipe_distance),
+                );
+                self.delay_slideshow(window_id);
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                reader
+                    .gestures
+                    .scrolled(area, delta, zoom, swipe_distance, Instant::now())
+                    .and_then(|turn| reader.turn(window_id, turn))
+                    .map_or_else(Task::none, Task::done)
+            }
+            Message::Touched(window_id, event) => {
+                let swipe_distance = f32::from(self.settings.swipe_distance);
+                self.delay_slideshow(window_id);
+                if let Some(Window::Main { .. }) = self.windows.get(&window_id) {
+                    return self.pinch_cards(window_id, event);
+                }
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                let gestures = &mut reader.gestures;
+                let turn = match event {
+                    touch::Event::FingerPressed { id, position } => {
+                        gestures.finger_pressed(id, position);
+                        None
+                    }
+                    touch::Event::FingerMoved { id, position } => {
+                        gestures.finger_moved(id, position, Instant::now());
+                        None
+                    }
+                    touch::Event::FingerLifted { id, position } => {
+                        gestures.finger_lifted(id, position, swipe_distance, Instant::now())
+                    }
+                    touch::Event::FingerLost { id, .. } => {
+                        gestures.finger_lost(id);
+                        None
+                    }
+                };
+                turn.and_then(|turn| reader.turn(window_id, turn))
+                    .map_or_else(Task::none, Task::done)
+            }
+            Message::ToggleSlideshow(window_id) => {
+                let interval = self.slideshow_interval();
+                if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) {
+                    reader.slideshow = match reader.slideshow {
+                        Some(_) => None,
+                        None => Some(Instant::now() + interval),
+                    };
+                }
+                Task::none()
+            }
+            Message::StartSlideshow(path) => {
+                let (_, open_window) =
+                    self.open_window(WindowKind::Reader, window::Settings::default());
+                open_window.then(move |id| {
+                    Task::done(Message::AddReaderWindow(id, Arc::clone(&path)))
+                        .chain(Task::done(Message::ToggleSlideshow(id)))
+                })
+            }
+            Message::StopSlideshows => {
+                for window in self.windows.values_mut() {
+                    if let Window::Reader(reader) = window {
+                        reader.slideshow = None;
+                    }
+                }
+                Task::none()
+            }
+            Message::SlideshowTick => {
+                let (now, interval) = (Instant::now(), self.slideshow_interval());
+                let looping = self.settings.slideshow_loop;
+                let mut advance = Vec::new();
+                for (&window_id, window) in &mut self.windows {
+                    if let Window::Reader(reader) = window
+                        && reader.slideshow.is_some_and(|at| at <= now)
+                    {
+                        // Slideshows stop once there is nothing left to show.
+                        let message = reader.advance_slideshow(window_id, looping);
+                        reader.slideshow = message.as_ref().map(|_| now + interval);
+                        advance.extend(message.map(Task::done));
+                    }
+                }
+                Task::batch(advance)
+            }
+            Message::GlideTick => {
+                let now = Instant::now();
+                for window in self.windows.values_mut() {
+                    if let Window::Reader(reader) = window
+                        && reader.gestures.is_gliding()
+                    {
+                        reader.gestures.glide(now);
+                    }
+                }
+                Task::none()
+            }
+            Message::ToggleBookmark(window_id) => {
+                self.toggle_bookmark(window_id);
+                Task::none()
+            }
+            Message::UseAsCover(window_id) => {
+                let Some(Window::Reader(reader)) = self.windows.get(&window_id) else {
+                    return Task::none();
+                };
+                let (path, cover) = (Arc::clone(&reader.path), Cover::Page(reader.page));
+                self.set_cover(&path, Some(&cover))
+            }
+            #[cfg(feature = "dbus")]
+            Message::PickCover(ItemPath { view_path, path }) => {
+                if let Some(pane) = self.get_pane_mut(view_path) {
+                    pane.context_menu = None;
+                }
+                Task::future(dbus::pick_image(tr("dialog-choose-cover"))).then(move |result| {
+                    match result {
+                        Ok(Some(image)) => {
+                            Task::done(Message::CoverPicked(Arc::clone(&path), image))
+                        }
+                        Ok(None) => Task::none(),
+                        Err(err) => {
+                            ::log::warn!("could not pick cover image\n{err}");
+                            Task::none()
+                        }
+                    }
+                })
+            }
+            #[cfg(not(feature = "dbus"))]
+            Message::PickCover(..) => Task::none(),
+            Message::CoverPicked(path, image) => self.set_cover(&path, Some(&Cover::Image(image))),
+            Message::ResetCover(ItemPath { view_path, path }) => {
+                if let Some(pane) = self.get_pane_mut(view_path) {
+                    pane.context_menu = None;
+                }
+                self.set_cover(&path, None)
+            }
+            Message::NextVolume(window_id) => {
+                let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) else {
+                    return Task::none();
+                };
+                let Some(next) = reader.next_volume.take() else {
+                    return Task::none();
+                };
+                // Adjustments carry over to the next volume unless it has its own.
+                let remembered = self.catalogue.adjustments(&next).unwrap_or_else(|err| {
+                    err.log();
+                    None
+                });
+                let adjustments = remembered.unwrap_or(reader.adjustments);
+                let prefetched = reader
+                    .prefetched
+                    .take()
+                    .filter(|_| adjustments == reader.adjustments);
+                **reader = Reader {
+                    fullscreen: reader.fullscreen,
+                    strip: reader.strip.as_ref().map(|_| PageStrip::default()),
+                    adjustments,
+                    remember_adjustments: remembered.is_some(),
+                    adjusting: reader.adjusting,
+                    continuous: reader.continuous.as_ref().map(|_| Continuous::default()),
+                    ..Reader::new(Arc::clone(&next))
+                };
+                let bookmarks = self.load_bookmarks(&next);
+                if let Some(Window::Reader(reader)) = self.windows.get_mut(&window_id) {
+                    reader.bookmarks = bookmarks;
+                }
+                // Continuous layout keeps its scroll offset from the previous volume.
+                let top = self.jump_continuous(window_id, 0);
+                let load = match prefetched {
+                    Some(Prefetched {
+                        page_count,
+                        image,
+                        dimensions,
+                        animation,
+                    }) => Task::done(Message::PageLoaded {
+                        window_id,
+                        page: 0,
+                        page_count,
+                        image,
+                        dimensions,
+                        animation,
+                    }),
+                    None => Self::load_page(window_id, next, 0, adjustments),
+                };
+                Task::batch([top, load])
+            }
+            Message::ThumbnailLoaded {
+                item_path: ItemPath { view_path, path },
+                token,
+                handle,
+                animation,
+                accent,
+            } => {
+                let Some(view) = self.get_current_view_mut(view_path, &token) else {
+                    return Task::none();
+                };
+                if let DirView::Dir { items, .. } = view
+                    && let Some(item) = items.get_mut(&path)
+                {
+                    item.thumbnail = pane::Thumbnail::Loaded;
+                    self.thumbnail_cache
+                        .insert(path, Shown { handle, animation }, accent);
+                }
+                self.request_thumbnails(view_path)
+            }
+            Message::ThumbnailFailed {
+                item_path: ItemPath { view_path, path },
+                token,
+            } => {
+                let Some(view) = self.get_current_view_mut(view_path, &token) else {
+                    return Task::none();
+                };
+                if let DirView::Dir { items, .. } = view
+                    && let Some(item) = items.get_mut(&path)
+                {
+                    item.thumbnail = pane::Thumbnail::Unavailable;
+                }
+                self.request_thumbnails(view_path)
+            }
+            Message::ItemOffline {
+                item_path: ItemPath { view_path, path },
+                token,
+            } => {
+                let Some(view) = self.get_current_view_mut(view_path, &token) else {
+                    return Task::none();
+                };
+                if let DirView::Dir { items, .. } = view
+                    && let Some(item) = items.get_mut(&path)
+                {
+                    item.set_offline();
+                }
+                self.request_thumbnails(view_path)
+            }
+            Message::MetadataLoaded {
+                item_path: ItemPath { view_path, path },
+                token,
+                metadata,
+            } => {
+                // Numbers given by users take precedence over those of documents.
+                let isbn = self
+                    .catalogue
+                    .isbn(&path)
+                    .inspect_err(Error::log)
+                    .ok()
+                    .flatten();
+                // Zip archives have no metadata of their own other than what was written.
+                let info = self
+                    .catalogue
+                    .comic_info(&path)
+                    .inspect_err(Error::log)
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default();
+                let non_empty = |text: String| (!text.is_empty()).then_some(text);
+                if let Some(DirView::Dir { items, .. }) =
+                    self.get_current_view_mut(view_path, &token)
+                    && let Some(item) = items.get_mut(&path)
+                {
+                    item.metadata = archive::Metadata {
+                        title: metadata.title.or_else(|| non_empty(info.title)),
+                        author: metadata.author.or_else(|| non_empty(info.writer)),
+                        isbn: isbn.or(metadata.isbn),
+                    };
+                }
+                Task::none()
+            }
         }
     }
 
     /// View application
     fn view(&self, id: window::Id) -> Element<'_, Message> {
         let ty = self.windows.get(&id).unwrap_or(&Window::Settings);
-        ty.view(&self.cli, &self.settings).into()
+        ty.view(
+            id,
+            window_state::Context {
+                cli: &self.cli,
+                settings: &self.settings,
+                draft: &self.draft,
+                thumbnail_cache: &self.thumbnail_cache,
+                page_time: self.page_time,
+                recent: &self.recent,
+                shelf: &self.shelf,
+                hover_preview: self.hover_preview.as_ref(),
+                undo_history: &self.undo_history,
+                profiles: &self.profiles,
+                reading_lists: &self.reading_lists,
+                backups: &self.backups,
+                selected_backup: self.selected_backup.as_deref(),
+                library_root_input: &self.library_root_input,
+                tags: &self.tags,
+                sidebar_collapsed: self.sidebar_collapsed,
+                target_list: self.target_list.as_deref(),
+                debug_stats: self.debug_stats,
+                hovered_pane: self.hovered_pane,
+                focused_pane: self.focused_pane,
+                drag: self.drag.as_ref(),
+                error: self.error.as_deref(),
+                settings_conflict: self.disk_settings.is_some(),
+                dialog: self.top_dialog(id),
+                tasks: self.show_tasks.then_some(&self.tasks),
+                activity: Activity {
+                    queued_jobs: self.workers.queued(),
+                    active_tasks: self.tasks.active(),
+                    slideshows: self
+                        .windows
+                        .values()
+                        .filter(|window| {
+                            matches!(window, Window::Reader(reader) if reader.slideshow.is_some())
+                        })
+                        .count(),
+                    frame: self.spinner_frame,
+                },
+            },
+        )
     }
 }
@@ -0,0 +1,78 @@
+//! Library roots registered in settings. Roots are indexed when the application starts
+//! and when they change, recording their archives as seen such that new volumes of
+//! followed series are noticed without opening their directories, and are watched for
+//! changes while the application runs.
+
+use ::core::time::Duration;
+use ::std::{path::PathBuf, sync::Arc};
+
+use ::futures::{SinkExt, Stream, channel::mpsc::Sender};
+use ::notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::{command::find_archives_throttled, ignore::IgnoreRules, worker::Throttle};
+
+/// Time to wait after a change before reporting it, archives are often copied into a
+/// library in several steps.
+const SETTLE_TIME: Duration = Duration::from_secs(1);
+
+/// Find archives of library roots, roots that cannot be read are logged and skipped.
+/// Indexing slows down while throttled.
+pub fn index(roots: &[PathBuf], ignore: &IgnoreRules, throttle: &Throttle) -> Vec<PathBuf> {
+    roots
+        .iter()
+        .flat_map(
+            |root| match find_archives_throttled(root, ignore, throttle) {
+                Ok((archives, _)) => archives,
+                Err(err) => {
+                    err.log();
+                    Vec::new()
+                }
+            },
+        )
+        .collect()
+}
+
+/// Watch library roots and their subdirectories, sending paths changed in them to
+/// output once changes have settled.
+///
+/// # Errors
+/// If no watcher can be created.
+async fn watch(roots: &[PathBuf], output: &mut Sender<Vec<PathBuf>>) -> ::notify::Result<()> {
+    let (sender, receiver) = ::flume::unbounded();
+    let mut watcher = ::notify::recommended_watcher(move |event| _ = sender.send(event))?;
+    for root in roots {
+        if let Err(err) = watcher.watch(root, RecursiveMode::Recursive) {
+            ::log::warn!("could not watch library root {root:?}\n{err}");
+        }
+    }
+
+    while let Ok(event) = receiver.recv_async().await {
+        let mut changed = Vec::new();
+        let mut push = |event: ::notify::Result<::notify::Event>| match event {
+            Ok(event) if !matches!(event.kind, EventKind::Access(..)) => {
+                changed.extend(event.paths);
+            }
+            Ok(..) => {}
+            Err(err) => ::log::warn!("error while watching library roots\n{err}"),
+        };
+        push(event);
+        ::smol::Timer::after(SETTLE_TIME).await;
+        receiver.drain().for_each(&mut push);
+        changed.sort();
+        changed.dedup();
+        if !changed.is_empty() {
+            _ = output.send(changed).await;
+        }
+    }
+    Ok(())
+}
+
+/// Stream of paths changed in library roots, batched by when they change.
+pub fn changes(roots: &Arc<[PathBuf]>) -> impl Stream<Item = Vec<PathBuf>> + use<> {
+    let roots = Arc::clone(roots);
+    ::iced::stream::channel(1, async move |mut output| {
+        if let Err(err) = watch(&roots, &mut output).await {
+            ::log::info!("could not watch library roots for changes\n{err}");
+        }
+    })
+}
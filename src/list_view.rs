@@ -0,0 +1,380 @@
+//! List and detail presentations of directory views, alternatives to the cover grid.
+
+use ::core::{cmp::Ordering, ops::Range};
+use ::std::{collections::BTreeSet, path::Path, sync::Arc, time::SystemTime};
+
+use ::iced::{
+    Alignment::Center,
+    Border, Element,
+    Length::Fill,
+    Padding, Size,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{
+    ItemPath, Message, SizeUnits, ViewPath,
+    cache::{Shown, ThumbnailCache},
+    catalogue::ReadState,
+    format,
+    i18n::{tr, tr_args},
+    listing::{Column, Sort},
+    pane::{
+        CONTEXT_MENU_ENTRY_HEIGHT, CONTEXT_MENU_WIDTH, ContextMenu, Item, PLACEHOLDER, item_menu,
+        scroll_id, visible_range,
+    },
+    series,
+    text::natural_cmp,
+};
+
+/// Height of rows of list views, including the header.
+pub const ROW_HEIGHT: f32 = 26.0;
+
+/// Width of columns of list views other than the name column.
+const COLUMN_WIDTH: f32 = 110.0;
+
+/// Width of panel of detail views.
+const DETAIL_WIDTH: f32 = 300.0;
+
+/// Size of cover of detail views.
+const COVER_SIZE: f32 = 280.0;
+
+/// List or detail presentation of a directory view.
+#[derive(Debug, Clone)]
+pub struct ListView<'a> {
+    /// Path to view.
+    pub view_path: ViewPath,
+    /// Items shown by filter, in the order they are listed.
+    pub listed: Vec<(&'a Arc<Path>, &'a Item)>,
+    /// Selected items.
+    pub selected: &'a BTreeSet<Arc<Path>>,
+    /// Item shown in panel of detail views.
+    pub focused: Option<&'a Arc<Path>>,
+    /// Item the keyboard cursor is on.
+    pub cursor: Option<&'a Arc<Path>>,
+    /// Context menu, if shown.
+    pub context_menu: Option<ContextMenu<'a>>,
+    /// Items may be moved in reading order from context menu.
+    pub reading_order: bool,
+    /// Thumbnails shared by all panes.
+    pub cache: &'a ThumbnailCache<Shown>,
+    /// Vertical scroll offset.
+    pub scroll_offset: f32,
+    /// Order of items, if sorted by a column.
+    pub sort: Option<Sort>,
+    /// A panel with the focused item is shown.
+    pub detail: bool,
+    /// Units file sizes are shown in.
+    pub size_units: SizeUnits,
+}
+
+impl<'a> ListView<'a> {
+    /// View list in an area of given size, creating rows only for visible items.
+    pub fn view(self, size: Size) -> Element<'a, Message> {
+        let Self {
+            view_path,
+            listed,
+            selected,
+            focused,
+            cursor,
+            context_menu,
+            reading_order,
+            cache,
+            scroll_offset,
+            sort,
+            detail,
+            size_units,
+        } = self;
+        let now = SystemTime::now();
+        let list_size = Size {
+            width: if detail {
+                size.width - DETAIL_WIDTH
+            } else {
+                size.width
+            },
+            height: size.height - ROW_HEIGHT,
+        };
+        let visible = visible_items(scroll_offset, list_size.height, listed.len());
+
+        let header = widget::Row::new()
+            .extend(Column::ALL.map(|column| {
+                let arrow = match sort {
+                    Some(sort) if sort.column == column && sort.descending => " \u{25bc}",
+                    Some(sort) if sort.column == column => " \u{25b2}",
+                    _ => "",
+                };
+                widget::button(widget::text(format!("{column}{arrow}")).wrapping(Wrapping::None))
+                    .padding([3, 6])
+                    .width(column_width(column))
+                    .style(widget::button::text)
+                    .on_press(Message::SortBy(view_path, column))
+                    .into()
+            }))
+            .height(ROW_HEIGHT);
+
+        let rows = listed[visible.clone()].iter().map(|&(path, item)| {
+            let item_path = ItemPath {
+                view_path,
+                path: Arc::clone(path),
+            };
+            let is_selected = selected.contains(path);
+            let is_cursor = cursor == Some(path);
+            widget::Row::new()
+                .align_y(Center)
+                .extend(Column::ALL.map(|column| {
+                    let mut text = column_text(column, item, size_units, now);
+                    if column == Column::Name {
+                        text = format!("{}{text}", marks(item));
+                    }
+                    widget::text(text)
+                        .wrapping(Wrapping::None)
+                        .pipe(widget::container)
+                        .padding([0, 6])
+                        .width(column_width(column))
+                        .clip(true)
+                        .into()
+                }))
+                .height(ROW_HEIGHT)
+                .pipe(widget::container)
+                .style(move |theme: &::iced::Theme| {
+                    let style = if is_selected {
+                        widget::container::Style {
+                            background: Some(theme.extended_palette().primary.weak.color.into()),
+                            text_color: Some(theme.extended_palette().primary.weak.text),
+                            ..widget::container::transparent(theme)
+                        }
+                    } else {
+                        widget::container::transparent(theme)
+                    };
+                    if is_cursor {
+                        style.border(Border {
+                            color: theme.extended_palette().primary.strong.color,
+                            width: 1.0,
+                            radius: 2.0.into(),
+                        })
+                    } else {
+                        style
+                    }
+                })
+                .pipe(widget::mouse_area)
+                .on_press(Message::ItemPressed(item_path.clone()))
+                .on_right_press(Message::ItemContextMenu(item_path.clone()))
+                .on_double_click(Message::ItemDoubleClicked(item_path))
+                .into()
+        });
+
+        let list = widget::Column::new()
+            .push(widget::space().height(visible.start as f32 * ROW_HEIGHT))
+            .extend(rows)
+            .push(widget::space().height((listed.len() - visible.end) as f32 * ROW_HEIGHT))
+            .pipe(widget::scrollable)
+            .id(scroll_id(view_path))
+            .on_scroll(move |viewport| Message::PaneScrolled {
+                view_path,
+                offset: viewport.absolute_offset().y,
+                viewport: viewport.bounds().size(),
+            })
+            .pipe(|list| widget::Stack::new().push(list))
+            .extend(
+                context_menu
+                    .and_then(|menu| {
+                        listed
+                            .iter()
+                            .position(|(path, _)| *path == menu.path)
+                            .map(|index| (menu, index))
+                    })
+                    .map(|(menu, index)| {
+                        // Menu is placed below the name of the item, kept within view.
+                        let y = (index as f32 + 1.0) * ROW_HEIGHT - scroll_offset;
+                        let height =
+                            menu.entries(reading_order) as f32 * CONTEXT_MENU_ENTRY_HEIGHT + 6.0;
+                        widget::pin(item_menu(view_path, menu, reading_order))
+                            .x(ROW_HEIGHT
+                                .min(list_size.width - CONTEXT_MENU_WIDTH)
+                                .max(0.0))
+                            .y(y.min(list_size.height - height).max(0.0))
+                            .into()
+                    }),
+            );
+
+        widget::Row::new()
+            .push(widget::Column::new().push(header).push(list).width(Fill))
+            .push(detail.then(|| {
+                detail_panel(
+                    focused.and_then(|path| {
+                        listed
+                            .iter()
+                            .find(|(listed, _)| *listed == path)
+                            .map(|&(path, item)| (path, item))
+                    }),
+                    cache,
+                    size_units,
+                    now,
+                )
+            }))
+            .into()
+    }
+}
+
+/// Get width of a column of list views.
+fn column_width(column: Column) -> ::iced::Length {
+    match column {
+        Column::Name => Fill,
+        Column::Series | Column::Pages | Column::Size | Column::Modified => COLUMN_WIDTH.into(),
+    }
+}
+
+/// Get read state and star marks shown before the name of an item.
+fn marks(item: &Item) -> String {
+    let read_state = match item.state.read_state {
+        ReadState::Unread => "",
+        ReadState::Reading => "\u{25d0} ",
+        ReadState::Finished => "\u{2713} ",
+    };
+    let star = if item.state.starred { "\u{2605} " } else { "" };
+    format!("{read_state}{star}")
+}
+
+/// View panel of detail views, with the cover and metadata of the focused item.
+fn detail_panel<'a>(
+    focused: Option<(&'a Arc<Path>, &'a Item)>,
+    cache: &'a ThumbnailCache<Shown>,
+    size_units: SizeUnits,
+    now: SystemTime,
+) -> Element<'a, Message> {
+    let Some((path, item)) = focused else {
+        return widget::text(tr("pane-nothing-selected"))
+            .pipe(widget::container)
+            .center_x(DETAIL_WIDTH)
+            .padding(5)
+            .into();
+    };
+    let field = |label: String, value: String| {
+        widget::Row::new()
+            .spacing(5)
+            .push(widget::text(label).style(widget::text::secondary).width(70))
+            .push(widget::text(value).width(Fill))
+    };
+    let progress = match item.state.read_state {
+        ReadState::Reading => tr_args(
+            "pane-reading-page",
+            &[("page", &(item.state.page + 1).to_string())],
+        ),
+        read_state => read_state.to_string(),
+    };
+    widget::Column::new()
+        .spacing(5)
+        .padding(Padding {
+            left: 5.0,
+            ..Padding::new(0.0)
+        })
+        .push(match cache.get(path).map(Shown::image) {
+            Some(handle) => widget::image(handle)
+                .width(COVER_SIZE)
+                .height(COVER_SIZE)
+                .into(),
+            None => widget::svg(PLACEHOLDER.clone())
+                .width(COVER_SIZE)
+                .height(COVER_SIZE)
+                .pipe(Element::from),
+        })
+        .push(widget::text(item.title()).size(16))
+        .push(
+            item.metadata
+                .author
+                .clone()
+                .map(|author| field(tr("pane-author"), author)),
+        )
+        .push(
+            item.metadata
+                .isbn
+                .as_ref()
+                .map(|isbn| field(tr("pane-isbn"), isbn.to_string())),
+        )
+        .push(field(
+            Column::Series.to_string(),
+            series::series_name(item.title()),
+        ))
+        .push(field(tr("pane-progress"), progress))
+        .extend(
+            [Column::Pages, Column::Size, Column::Modified].map(|column| {
+                let text = column_text(column, item, size_units, now);
+                (!text.is_empty())
+                    .then(|| field(column.to_string(), text))
+                    .map_or_else(|| widget::space().into(), Element::from)
+            }),
+        )
+        .push(field(tr("pane-file"), item.name.clone()))
+        .pipe(widget::scrollable)
+        .width(DETAIL_WIDTH)
+        .into()
+}
+
+/// Compare items by value of a column, items without a value after those with one.
+fn compare(column: Column, a: &Item, b: &Item) -> Ordering {
+    /// Compare optional values, [None] last.
+    fn known<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (a, b) => a.is_none().cmp(&b.is_none()),
+        }
+    }
+    match column {
+        Column::Name => natural_cmp(a.title(), b.title()),
+        Column::Series => natural_cmp(
+            &series::series_name(a.title()),
+            &series::series_name(b.title()),
+        ),
+        Column::Pages => known(a.page_count, b.page_count),
+        Column::Size => known(a.size, b.size),
+        Column::Modified => known(a.modified, b.modified),
+    }
+}
+
+/// Get text of a column for item.
+fn column_text(column: Column, item: &Item, size_units: SizeUnits, now: SystemTime) -> String {
+    match column {
+        Column::Name if item.offline => tr_args("pane-offline-name", &[("name", item.title())]),
+        Column::Name => item.title().to_owned(),
+        Column::Series => series::series_name(item.title()),
+        Column::Pages => item.page_count.map(format::number).unwrap_or_default(),
+        Column::Size => item
+            .size
+            .map(|size| format::size(size, size_units))
+            .unwrap_or_default(),
+        Column::Modified => item
+            .modified
+            .map(|modified| format::relative(modified, now))
+            .unwrap_or_default(),
+    }
+}
+
+/// Sort items in an order, keeping the order of items with equal values.
+fn sort_items(sort: Sort, items: &mut [(&Arc<Path>, &Item)]) {
+    items.sort_by(|(_, a), (_, b)| {
+        let ordering = compare(sort.column, a, b);
+        if sort.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+/// Get items of a view shown by filter in the order they are listed.
+pub fn listed<'a>(
+    shown: impl Iterator<Item = (&'a Arc<Path>, &'a Item)>,
+    sort: Option<Sort>,
+) -> Vec<(&'a Arc<Path>, &'a Item)> {
+    let mut listed = shown.collect::<Vec<_>>();
+    if let Some(sort) = sort {
+        sort_items(sort, &mut listed);
+    }
+    listed
+}
+
+/// Get range of listed items rows should be created for, given the scroll offset and
+/// height of the list below its header.
+pub fn visible_items(scroll_offset: f32, height: f32, count: usize) -> Range<usize> {
+    visible_range(scroll_offset, height, ROW_HEIGHT, count)
+}
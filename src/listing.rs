@@ -0,0 +1,135 @@
+//! How listings of directories are presented and ordered, remembered per directory by
+//! the catalogue.
+
+use ::core::fmt::Display;
+
+use ::derive_more::IsVariant;
+
+use crate::i18n::tr;
+
+/// How items of a pane are presented.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, IsVariant)]
+pub enum ViewMode {
+    /// Items are shown as covers in a grid.
+    #[default]
+    Grid,
+    /// Items are shown as rows of a table.
+    List,
+    /// Items are shown as rows of a table, with the cover and metadata of the focused
+    /// item in a panel.
+    Detail,
+}
+
+impl ViewMode {
+    /// All view modes.
+    pub const ALL: [Self; 3] = [Self::Grid, Self::List, Self::Detail];
+
+    /// Get view mode from its database representation.
+    pub const fn from_i64(value: i64) -> Self {
+        match value {
+            1 => Self::List,
+            2 => Self::Detail,
+            _ => Self::Grid,
+        }
+    }
+
+    /// Get database representation of view mode.
+    pub const fn to_i64(self) -> i64 {
+        match self {
+            Self::Grid => 0,
+            Self::List => 1,
+            Self::Detail => 2,
+        }
+    }
+}
+
+impl Display for ViewMode {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            ViewMode::Grid => "pane-grid",
+            ViewMode::List => "pane-list",
+            ViewMode::Detail => "pane-detail",
+        }))
+    }
+}
+
+/// Column of list views.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column {
+    /// Title of item.
+    Name,
+    /// Series of item.
+    Series,
+    /// Amount of pages of archive.
+    Pages,
+    /// Size of file.
+    Size,
+    /// Time item was last modified.
+    Modified,
+}
+
+impl Column {
+    /// All columns, in the order they are shown.
+    pub const ALL: [Self; 5] = [
+        Self::Name,
+        Self::Series,
+        Self::Pages,
+        Self::Size,
+        Self::Modified,
+    ];
+
+    /// Get column from its database representation.
+    pub const fn from_i64(value: i64) -> Option<Self> {
+        Some(match value {
+            0 => Self::Name,
+            1 => Self::Series,
+            2 => Self::Pages,
+            3 => Self::Size,
+            4 => Self::Modified,
+            _ => return None,
+        })
+    }
+
+    /// Get database representation of column.
+    pub const fn to_i64(self) -> i64 {
+        match self {
+            Self::Name => 0,
+            Self::Series => 1,
+            Self::Pages => 2,
+            Self::Size => 3,
+            Self::Modified => 4,
+        }
+    }
+}
+
+impl Display for Column {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            Column::Name => "pane-column-name",
+            Column::Series => "pane-column-series",
+            Column::Pages => "pane-column-pages",
+            Column::Size => "pane-column-size",
+            Column::Modified => "pane-column-modified",
+        }))
+    }
+}
+
+/// Order of items of list views.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sort {
+    /// Column items are sorted by.
+    pub column: Column,
+    /// Items are sorted in descending order.
+    pub descending: bool,
+}
+
+impl Sort {
+    /// Get order after the header of column is pressed, reversing the order if items
+    /// are already sorted by column.
+    pub fn pressed(sort: Option<Self>, column: Column) -> Self {
+        Self {
+            column,
+            descending: sort.is_some_and(|sort| sort.column == column && !sort.descending),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! Logging to the terminal and to rotating log files in the state directory of a
+//! profile.
+
+use ::std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use ::clap::ValueEnum;
+use ::env_logger::{Target, WriteStyle};
+use ::log::{LevelFilter, Log, Metadata, Record};
+use ::serde::{Deserialize, Serialize};
+
+use crate::{Cli, Settings, config};
+
+/// Directory in state directory of profile log files are written to.
+const LOG_DIR: &str = "logs";
+
+/// Name of the log file currently written to.
+const LOG_FILE: &str = "arkiv-katalog.log";
+
+/// Size in bytes a log file may reach before it is rotated.
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+
+/// Amount of rotated log files kept, older ones are removed.
+const KEPT_LOGS: usize = 4;
+
+/// Least severe level of messages logged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogLevel {
+    /// Log nothing.
+    Off,
+    /// Log errors.
+    Error,
+    /// Log errors and warnings.
+    Warn,
+    /// Log errors, warnings and information.
+    #[default]
+    Info,
+    /// Log messages useful when debugging.
+    Debug,
+    /// Log everything.
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(value: LogLevel) -> Self {
+        match value {
+            LogLevel::Off => LevelFilter::Off,
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+/// Log file rotated once it grows too large, the current file is renamed with a `.1`
+/// suffix, which in turn is renamed with a `.2` suffix and so on.
+#[derive(Debug)]
+struct RotatingFile {
+    /// Path of file currently written to.
+    path: PathBuf,
+    /// File currently written to.
+    file: File,
+    /// Size of file currently written to.
+    size: u64,
+}
+
+impl RotatingFile {
+    /// Open log file in directory for appending.
+    ///
+    /// # Errors
+    /// If the directory cannot be created or the file cannot be opened.
+    fn open(dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(LOG_FILE);
+        let file = File::options().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, file, size })
+    }
+
+    /// Get path of rotated log file of given age.
+    fn rotated(&self, age: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{age}"));
+        PathBuf::from(path)
+    }
+
+    /// Rotate log files, and start writing to an empty file.
+    ///
+    /// # Errors
+    /// If the new file cannot be opened.
+    fn rotate(&mut self) -> io::Result<()> {
+        for age in (1..KEPT_LOGS).rev() {
+            let from = self.rotated(age);
+            if from.exists() {
+                // A failed rename only leaves an old log in place.
+                _ = fs::rename(&from, self.rotated(age + 1));
+            }
+        }
+        _ = fs::rename(&self.path, self.rotated(1));
+        self.file = File::options().create(true).append(true).open(&self.path)?;
+        self.size = self.file.metadata()?.len();
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > MAX_LOG_SIZE {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Logger writing to the terminal and to a log file, each with a filter of its own.
+#[derive(Debug)]
+struct Logger {
+    /// Logger writing to stderr.
+    terminal: ::env_logger::Logger,
+    /// Logger writing to log file, [None] if it could not be opened.
+    file: Option<::env_logger::Logger>,
+}
+
+impl Logger {
+    /// Get most verbose level of either logger.
+    fn filter(&self) -> LevelFilter {
+        self.file
+            .as_ref()
+            .map_or(LevelFilter::Off, ::env_logger::Logger::filter)
+            .max(self.terminal.filter())
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.terminal.enabled(metadata)
+            || self
+                .file
+                .as_ref()
+                .is_some_and(|file| file.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        self.terminal.log(record);
+        if let Some(file) = &self.file {
+            file.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.terminal.flush();
+        if let Some(file) = &self.file {
+            file.flush();
+        }
+    }
+}
+
+/// Get log level of profile, from the environment, command line or config file in
+/// that order. Settings which cannot be read are reported once logging is initialized.
+fn log_level(cli: &Cli, xdg_dirs: &::xdg::BaseDirectories) -> LogLevel {
+    let settings = config::find(xdg_dirs)
+        .and_then(|path| Settings::read(&path).ok())
+        .unwrap_or_default();
+    config::Overrides::new(cli)
+        .apply(settings)
+        .map_or_else(|_| LogLevel::default(), |settings| settings.log_level)
+}
+
+/// Create a builder of env loggers logging messages of application at level, with
+/// filters of `RUST_LOG` taking precedence.
+fn builder(level: LevelFilter) -> ::env_logger::Builder {
+    let mut builder = ::env_logger::Builder::new();
+    builder
+        .filter_module("arkiv_katalog", level)
+        .parse_default_env();
+    builder
+}
+
+/// Initialize logging to the terminal and to log files of profile.
+pub fn init(cli: &Cli) {
+    let xdg_dirs = cli.xdg_dirs();
+    let level = LevelFilter::from(log_level(cli, &xdg_dirs));
+    let terminal = builder(if cli.quiet {
+        level.min(LevelFilter::Error)
+    } else {
+        level
+    })
+    .build();
+    let (file, err) = match xdg_dirs.get_state_home().map(|dir| dir.join(LOG_DIR)) {
+        Some(dir) => match RotatingFile::open(&dir) {
+            Ok(file) => (Some(file), None),
+            Err(err) => (
+                None,
+                Some(format!("could not open log file in {dir:?}\n{err}")),
+            ),
+        },
+        None => (
+            None,
+            Some("no state directory to write log files to".to_owned()),
+        ),
+    };
+    let file = file.map(|file| {
+        builder(level)
+            .target(Target::Pipe(Box::new(file)))
+            .write_style(WriteStyle::Never)
+            .build()
+    });
+    let logger = Logger { terminal, file };
+    ::log::set_max_level(logger.filter());
+    if ::log::set_boxed_logger(Box::new(logger)).is_ok()
+        && let Some(err) = err
+    {
+        ::log::warn!("{err}");
+    }
+}
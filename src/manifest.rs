@@ -0,0 +1,94 @@
+//! Checksum manifests of libraries in the format of `sha256sum`, used to detect damaged
+//! archives independently of the catalogue.
+
+use ::std::{
+    borrow::Cow,
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+use ::sha2::{Digest, Sha256};
+
+/// Name of manifest written to the root of a library when no other path is given.
+pub const MANIFEST_FILE: &str = "SHA256SUMS";
+
+/// Size of chunks files are read in while hashed.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Compute sha256 checksum of file at path as lowercase hex.
+///
+/// # Errors
+/// If the file cannot be read.
+pub fn checksum(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; CHUNK_SIZE];
+    loop {
+        match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => hasher.update(&buf[..read]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Format a line of a manifest. Paths containing backslashes or line breaks are escaped
+/// like `sha256sum` does, by starting the line with a backslash.
+pub fn line(checksum: &str, path: &str) -> String {
+    if path.contains(['\\', '\n', '\r']) {
+        let path = path
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+        format!("\\{checksum}  {path}")
+    } else {
+        format!("{checksum}  {path}")
+    }
+}
+
+/// Parse a line of a manifest into a checksum and path, lines written by `sha256sum` in
+/// binary mode and escaped lines are accepted. [None] if the line is not valid.
+pub fn parse_line(line: &str) -> Option<(&str, Cow<'_, str>)> {
+    let (escaped, line) = line
+        .strip_prefix('\\')
+        .map_or((false, line), |line| (true, line));
+    let (checksum, path) = line.split_once(' ')?;
+    let path = path.strip_prefix([' ', '*'])?;
+    if checksum.len() != 64
+        || !checksum.bytes().all(|byte| byte.is_ascii_hexdigit())
+        || path.is_empty()
+    {
+        return None;
+    }
+    let path = if escaped {
+        Cow::Owned(unescape(path)?)
+    } else {
+        Cow::Borrowed(path)
+    };
+    Some((checksum, path))
+}
+
+/// Undo escaping of a path of an escaped line, [None] if it has an unknown escape.
+fn unescape(path: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        unescaped.push(match c {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+    Some(unescaped)
+}
@@ -0,0 +1,783 @@
+//! [Message] enum, messages updating application state.
+
+use ::std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use ::derive_more::IsVariant;
+use ::iced::{
+    Point, Size,
+    mouse::{self, ScrollDelta},
+    touch, widget, window,
+};
+
+use crate::{
+    Activation, CardBadge, CardSize, HomeTile, ItemPath, ReaderTheme, Settings, SizeUnits,
+    ThemeArg, ViewPath,
+    animation::Animation,
+    archive::{self, ComicField, ComicInfo},
+    catalogue::ReadState,
+    chooser::Remember,
+    clipboard::CopyKind,
+    dialog::DialogId,
+    doctor,
+    error::Error,
+    geometry::Geometries,
+    i18n::Language,
+    inbox::Resolution,
+    input::{Action, FunctionKey, MouseButton},
+    listing::{Column, ViewMode},
+    metadata_editor::MetadataChange,
+    opds,
+    open_with::Application,
+    pane::{self, Chip, ListChoice},
+    reader::LoadedPage,
+    sidebar,
+    startup::Loaded,
+    statistics::DiskUsage,
+    tasks::{TaskId, TaskOutput},
+    transfer::Outcome,
+    worker::CancelToken,
+};
+
+/// Subdirectories of a directory shown as a shelf, with the items of the archives in
+/// them in reading order.
+pub type ShelfSeries = Vec<(Arc<Path>, Vec<(Arc<Path>, pane::Item)>)>;
+
+/// Counted pages of archives, along with the modification time of archives when counted.
+pub type PageCounts = Vec<(Arc<Path>, Option<SystemTime>, usize)>;
+
+/// Application message.
+#[derive(Debug, Clone, IsVariant)]
+pub enum Message {
+    /// Add window displaying given directory.
+    AddDirWindow(window::Id, Arc<Path>),
+    /// Settings and remembered window geometry of profile were read in the background,
+    /// settings without overrides applied.
+    SettingsLoaded(Result<(Settings, Geometries), Arc<Error>>),
+    /// Catalogue and other state of profile were loaded in the background.
+    StartupLoaded(Loaded),
+    /// Add empty window.
+    AddEmptyWindow(window::Id),
+    /// Add settings window.
+    AddSettingsWindow(window::Id),
+    /// Add home window.
+    AddHomeWindow(window::Id),
+    /// Add an undo history window.
+    AddHistoryWindow(window::Id),
+    /// Add a window listing keyboard shortcuts.
+    AddHelpWindow(window::Id),
+    /// Add an about window.
+    AddAboutWindow(window::Id),
+    /// Show license texts of a dependency in an about window, or the list of
+    /// dependencies if [None].
+    ShowDependencyLicense(window::Id, Option<usize>),
+    /// Open about window, or close it if already open.
+    ToggleAbout,
+    /// Add a statistics window.
+    AddStatsWindow(window::Id),
+    /// Add a calendar window.
+    AddCalendarWindow(window::Id),
+    /// Show the month a number of months from the month shown by a calendar window.
+    ShiftCalendarMonth(window::Id, i32),
+    /// List events of a day of a calendar window.
+    SelectCalendarDay(window::Id, ::jiff::civil::Date),
+    /// Disk usage shown by a statistics window has been computed.
+    DiskUsageComputed(window::Id, DiskUsage),
+    /// A later launch or the desktop environment requested a path to be opened, [None]
+    /// to open a window without a path.
+    InstanceRequest(Option<PathBuf>),
+    /// Show a quick look of the focused item of a pane, or close the shown one.
+    ToggleQuickLook(ViewPath),
+    /// Close quick look of a pane.
+    CloseQuickLook(ViewPath),
+    /// Mouse entered a card.
+    CardHovered(ItemPath),
+    /// Mouse left a card.
+    CardUnhovered(ItemPath),
+    /// Mouse rested on a card for the hover preview delay.
+    HoverDelayElapsed(ItemPath),
+    /// Page of a hover preview has been loaded.
+    HoverPageLoaded {
+        /// Card page was loaded for.
+        item_path: ItemPath,
+        /// Amount of pages in archive.
+        page_count: usize,
+        /// Loaded page.
+        page: widget::image::Handle,
+    },
+    /// Show the next page of the hover preview.
+    HoverTick,
+    /// Pages of archives in a view have been counted.
+    PagesCounted {
+        /// View archives are in.
+        view_path: ViewPath,
+        /// Token of view count was started for.
+        token: CancelToken,
+        /// Counted archives.
+        counts: PageCounts,
+    },
+    /// Pages of a quick look have been loaded.
+    QuickLookLoaded {
+        /// Pane quick look is shown over.
+        view_path: ViewPath,
+        /// Path of previewed archive.
+        path: Arc<Path>,
+        /// Amount of pages in archive.
+        page_count: usize,
+        /// Loaded pages, starting with the cover.
+        pages: Vec<widget::image::Handle>,
+    },
+    /// Revert a change of read state of undo history.
+    RevertChange(u64),
+    /// Revert a change of read state of undo history for a single item.
+    RevertChangedItem(u64, Arc<Path>),
+    /// Replace state with that of another profile.
+    SwitchProfile(String),
+    /// Open a window of a profile alongside the windows of the current profile.
+    OpenProfile(String),
+    /// Copy command line reproducing current profile, theme and open paths to clipboard.
+    CopyLaunchCommand,
+    /// Open an empty main window.
+    OpenMainWindow,
+    /// Open an archive in a reader window.
+    OpenArchive(Arc<Path>),
+    /// Open a recently opened directory in a pane.
+    OpenRecent(ViewPath, Arc<Path>),
+    /// Pick new random items of a home window.
+    ShuffleHome(window::Id),
+    /// Set whether a tile is shown in home window.
+    SetHomeTile(HomeTile, bool),
+    /// Add reader window displaying given archive.
+    AddReaderWindow(window::Id, Arc<Path>),
+    /// A window gained focus.
+    WindowFocused(window::Id),
+    /// A window lost focus.
+    WindowUnfocused(window::Id),
+    /// Remove a window from application state.
+    RemoveWindow(window::Id),
+    /// Set application theme.
+    SetTheme(ThemeArg),
+    /// Set theme of reader windows.
+    SetReaderTheme(ReaderTheme),
+    /// Scroll theme.
+    ThemeScroll(ScrollDelta),
+    /// Set item activation mode.
+    SetActivation(Activation),
+    /// Set card size.
+    SetCardSize(CardSize),
+    /// Bind an action to a mouse button.
+    SetMouseBinding(MouseButton, Action),
+    /// Bind an action to a function key.
+    SetKeyBinding(FunctionKey, Action),
+    /// Mouse button pressed in a window.
+    MouseButtonPressed(window::Id, mouse::Button),
+    /// Mouse wheel or touchpad scrolled in a window.
+    MouseScrolled(window::Id, ScrollDelta),
+    /// Set swipe distance.
+    SetSwipeDistance(u16),
+    /// Set pinch distance.
+    SetPinchDistance(u16),
+    /// Set seconds each page is shown by reader slideshows.
+    SetSlideshowInterval(u16),
+    /// Set whether reader slideshows start over after the last page.
+    SetSlideshowLoop(bool),
+    /// Set memory budget of thumbnail cache in MiB.
+    SetThumbnailCacheSize(u16),
+    /// Set delay in milliseconds before hovered cards are previewed.
+    SetHoverPreviewDelay(u16),
+    /// Set quality page images are recompressed with when converting archives.
+    SetConvertQuality(u8),
+    /// Set factor the user interface is scaled by.
+    SetScaleFactor(f32),
+    /// Set default size of text.
+    SetFontSize(u16),
+    /// Set language of user interface.
+    SetLanguage(Language),
+    /// Set units file sizes are shown in.
+    SetSizeUnits(SizeUnits),
+    /// Set whether background work is throttled on battery.
+    SetBatterySaver(bool),
+    /// Set whether animated covers are played in cards.
+    SetAnimateCovers(bool),
+    /// Set whether cards are tinted with the dominant color of their covers.
+    SetCoverAccents(bool),
+    /// Set whether grids are split into sections by the first letter of titles.
+    SetSectionHeaders(bool),
+    /// Set whether covers are written as freedesktop thumbnails.
+    SetFreedesktopThumbnails(bool),
+    /// Set whether long tasks finishing while no window is focused are notified about.
+    SetTaskNotifications(bool),
+    /// Set whether hidden files are skipped when scanning.
+    SetSkipHidden(bool),
+    /// Set whether symlinks are followed when scanning.
+    SetFollowSymlinks(bool),
+    /// Set whether window size is remembered.
+    SetRememberWindowSize(bool),
+    /// Set whether window position is remembered.
+    SetRememberWindowPosition(bool),
+    /// Set whether the continue reading shelf is shown.
+    SetContinueReadingShelf(bool),
+    /// Set whether the sidebar of main windows is shown.
+    SetSidebar(bool),
+    /// Set directory typed into settings window to add as a library root.
+    SetLibraryRootInput(String),
+    /// Add directory typed into settings window as a library root.
+    AddLibraryRoot,
+    /// Remove a library root by its index.
+    RemoveLibraryRoot(usize),
+    /// Archives of library roots have been found.
+    LibraryIndexed(Vec<PathBuf>),
+    /// Paths in library roots have changed on disk.
+    LibraryChanged(Vec<PathBuf>),
+    /// Collapse or expand sidebars of main windows.
+    ToggleSidebar,
+    /// An entry of a sidebar was pressed, starting to drag it.
+    SidebarPressed(sidebar::Entry),
+    /// Mouse was released over the sidebar of a window, showing the pressed entry in the
+    /// focused pane of window.
+    SidebarReleased(window::Id),
+    /// Set whether a badge is shown on cards.
+    SetCardBadge(CardBadge, bool),
+    /// A window was opened.
+    WindowOpened {
+        /// Id of window.
+        window_id: window::Id,
+        /// Position of window, if known.
+        position: Option<Point>,
+        /// Size of window.
+        size: Size,
+    },
+    /// A window was moved.
+    WindowMoved(window::Id, Point),
+    /// A window was resized.
+    WindowResized(window::Id, Size),
+    /// Maximized state of a window is known.
+    WindowMaximized(window::Id, bool),
+    /// System switched between running on battery and external power.
+    OnBatteryChanged(bool),
+    /// Report an error to the user.
+    ReportError(Arc<Error>),
+    /// Dismiss error shown to the user.
+    DismissError,
+    /// Health checks run on startup have finished.
+    Diagnosed(Vec<doctor::Problem>),
+    /// Refresh debug overlay.
+    DebugTick,
+    /// Advance activity spinner.
+    SpinnerTick,
+    /// Advance animated covers and pages.
+    AnimationTick,
+    /// Refresh relative times.
+    ClockTick,
+    /// Mouse entered a pane.
+    PaneHovered(ViewPath),
+    /// Pane was clicked, making it the pane keyboard shortcuts act on.
+    PaneFocused(ViewPath),
+    /// Mouse was released over a pane, dropping dragged items onto it.
+    PaneReleased(ViewPath),
+    /// Transfer archives dropped onto a pane.
+    ConfirmTransfer,
+    /// Resolve a conflict of a transferred archive with an existing file, also resolving
+    /// later conflicts if set.
+    ResolveTransferConflict(Resolution, bool),
+    /// Stop a transfer, leaving archives not yet transferred where they are.
+    StopTransfer,
+    /// Discard archives dropped onto a pane without transferring them.
+    CancelTransfer,
+    /// An archive of a transfer was moved or copied, or failed to be.
+    TransferStepped {
+        /// Transferred archive.
+        from: Arc<Path>,
+        /// Size of archive in bytes.
+        size: u64,
+        /// Outcome of transferring archive.
+        result: Result<Outcome, Arc<Error>>,
+    },
+    /// Set how items of a pane are presented.
+    SetViewMode(ViewPath, ViewMode),
+    /// Sort items of a pane shown as a list by a column, reversing the order if
+    /// already sorted by it.
+    SortBy(ViewPath, Column),
+    /// Text filter of a pane was edited.
+    SetFilterText(ViewPath, String),
+    /// Quick filter chip of a pane was toggled.
+    ToggleFilterChip(ViewPath, Chip),
+    /// Letter of the alphabetical index of a pane was pressed.
+    JumpToLetter(ViewPath, char),
+    /// Toggle star of an item.
+    ToggleStarred(ItemPath),
+    /// Cycle through labels of an item.
+    CycleLabel(ItemPath),
+    /// Show context menu of an item, selecting it if it is not selected.
+    ItemContextMenu(ItemPath),
+    /// Convert selected rar and 7z archives of a view to zip archives.
+    ConvertToCbz(ViewPath),
+    /// Extract selected archives of a view next to themselves.
+    ExtractHere(ViewPath),
+    /// Show dialog extracting selected archives of a view into another directory.
+    ExtractTo(ViewPath),
+    /// Read every page of selected archives of a view in the background.
+    VerifyArchives(ViewPath),
+    /// Write freedesktop thumbnails of selected archives of a view in the background.
+    WriteThumbnails(ViewPath),
+    /// A background task has made progress.
+    TaskProgress {
+        /// Identifier of task.
+        id: TaskId,
+        /// Amount of steps done.
+        done: usize,
+        /// Amount of steps in total.
+        total: usize,
+    },
+    /// A background task has finished, successfully or not.
+    TaskFinished {
+        /// Identifier of task.
+        id: TaskId,
+        /// Result of task.
+        result: Result<TaskOutput, Arc<Error>>,
+    },
+    /// Cancel a background task.
+    CancelTask(TaskId),
+    /// Cancel every background task which has not finished.
+    CancelAllTasks,
+    /// Remove finished background tasks from task panel.
+    ClearFinishedTasks,
+    /// Show or hide task panel of main windows.
+    ToggleTaskPanel,
+    /// Show dialog packing a directory of images into an archive.
+    PackDir(ItemPath),
+    /// Set a field of the `ComicInfo.xml` form of the pack dialog of a pane.
+    SetPackField(ViewPath, ComicField, String),
+    /// Set whether the pack dialog of a pane writes `ComicInfo.xml`.
+    SetPackInfo(ViewPath, bool),
+    /// Set whether the pack dialog of a pane moves the directory to trash once packed.
+    SetPackTrash(ViewPath, bool),
+    /// Pack directory of the pack dialog of a pane.
+    ConfirmPack(ViewPath),
+    /// Close pack dialog of a pane.
+    CancelPack(ViewPath),
+    /// A directory was packed into an archive, or failed to be.
+    Packed {
+        /// Path of directory.
+        dir: Arc<Path>,
+        /// Path of archive, and path of directory in trash if it was moved there.
+        result: Result<(PathBuf, Option<PathBuf>), Arc<Error>>,
+    },
+    /// Move selected archives of a view to trash.
+    MoveToTrash(ViewPath),
+    /// An archive was moved to trash, or failed to be.
+    MovedToTrash {
+        /// Pane archive was moved from.
+        view_path: ViewPath,
+        /// Path archive was moved from.
+        from: Arc<Path>,
+        /// Path of archive in trash.
+        result: Result<PathBuf, Arc<Error>>,
+    },
+    /// Add a window listing archives moved to trash.
+    AddTrashWindow(window::Id),
+    /// Move an archive in trash back to where it was.
+    RestoreTrashed(Arc<Path>),
+    /// An archive was restored from trash, or failed to be.
+    Restored {
+        /// Path archive was restored to.
+        path: Arc<Path>,
+        /// Result of restoring archive.
+        result: Result<(), Arc<Error>>,
+    },
+    /// Forget an archive which is no longer in trash.
+    ForgetTrashed(Arc<Path>),
+    /// Open a window editing metadata of selected zip archives of a view.
+    EditMetadata(ViewPath),
+    /// Add a window editing metadata of archives.
+    AddMetadataWindow(window::Id, Vec<Arc<Path>>),
+    /// Metadata of archives edited by a window was read.
+    MetadataRead {
+        /// Window of metadata editor.
+        id: window::Id,
+        /// Archives whose metadata was read, along with their metadata.
+        archives: Vec<(Arc<Path>, ComicInfo)>,
+        /// Errors of archives whose metadata could not be read.
+        errors: Vec<Arc<Error>>,
+    },
+    /// Set a field of a metadata editor.
+    SetMetadataField(window::Id, ComicField, String),
+    /// Show changes of a metadata editor before they are written.
+    ReviewMetadata(window::Id),
+    /// Return from reviewing changes of a metadata editor to editing them.
+    EditMetadataFields(window::Id),
+    /// Write changes of a metadata editor.
+    WriteMetadata(window::Id),
+    /// Changes of a metadata editor were written, or failed to be.
+    MetadataWritten(window::Id, Vec<(MetadataChange, Result<(), Arc<Error>>)>),
+    /// Close a metadata editor.
+    CloseMetadataEditor(window::Id),
+    /// Show dialog renaming an archive.
+    Rename(ItemPath),
+    /// Show dialog moving an archive to another directory.
+    MoveTo(ItemPath),
+    /// Pick directory an archive is moved to with the file chooser of the desktop.
+    BrowseMoveTarget(ViewPath),
+    /// Directory was picked for the rename dialog of a pane.
+    MoveTargetPicked(ViewPath, PathBuf),
+    /// Rename or move archive of the rename dialog of a pane, by entered text.
+    ConfirmRename(ViewPath, String),
+    /// Close rename dialog of a pane.
+    CancelRename(ViewPath),
+    /// Archive was renamed or moved, or failed to be.
+    Renamed {
+        /// Pane archive was renamed from.
+        view_path: ViewPath,
+        /// Previous path of archive.
+        from: Arc<Path>,
+        /// Path archive was renamed to.
+        result: Result<PathBuf, Arc<Error>>,
+    },
+    /// Close context menu of a view.
+    CloseContextMenu(ViewPath),
+    /// Set read state of selected items of a view, selected directories are treated as
+    /// a series with every archive in them being set.
+    SetReadState(ViewPath, ReadState),
+    /// Show a reading list in a pane, creating it first if a new list was chosen.
+    OpenReadingList(ViewPath, ListChoice),
+    /// Add selected items of a view to the target reading list, selected directories
+    /// are added as the archives in them in reading order.
+    AddToReadingList(ViewPath),
+    /// Remove selected items of a view showing a reading list from it.
+    RemoveFromReadingList(ViewPath),
+    /// Show an OPDS feed in a pane.
+    OpenFeed(ViewPath, String),
+    /// An OPDS feed has been loaded for a pane, or failed to be.
+    FeedLoaded {
+        /// View feed was loaded for.
+        view_path: ViewPath,
+        /// Token of view load was started for.
+        token: CancelToken,
+        /// Urls of feeds navigated from to reach feed.
+        back: Vec<String>,
+        /// Loaded feed.
+        result: Result<opds::Feed, Arc<Error>>,
+    },
+    /// Back up settings and catalogue of profile.
+    BackUpProfile,
+    /// Profile has been backed up to a file, or failed to be.
+    ProfileBackedUp(Result<PathBuf, Arc<Error>>),
+    /// Choose a backup of profile to restore.
+    SelectBackup(String),
+    /// Ask for confirmation before restoring the chosen backup of profile.
+    RestoreBackup(window::Id),
+    /// Replace settings and catalogue of profile with those of the chosen backup, showing
+    /// progress over a window.
+    ConfirmRestoreBackup(window::Id),
+    /// Profile has been restored from a backup, or failed to be.
+    ProfileRestored {
+        /// Dialog shown while restoring.
+        dialog: DialogId,
+        /// Result of restoring.
+        result: Result<(), Arc<Error>>,
+    },
+    /// Text of a prompt dialog has been edited.
+    DialogInput(DialogId, String),
+    /// Answer a dialog, confirming it or submitting its text.
+    AnswerDialog(DialogId),
+    /// Cancel a dialog.
+    CancelDialog(DialogId),
+    /// Option at index of a choice dialog was chosen.
+    ChooseDialog(DialogId, usize),
+    /// Set whether a choice dialog applies to all later choices.
+    DialogToggleAll(DialogId, bool),
+    /// Create a reading list with a name and show it in a pane.
+    CreateReadingList(ViewPath, String),
+    /// Copy selected items of a view to the clipboard.
+    CopySelection(ViewPath, CopyKind),
+    /// Cover of an archive has been copied to the clipboard, or failed to be.
+    CoverCopied(Result<(), Arc<Error>>),
+    /// Open paths of the clipboard from a pane.
+    Paste(ViewPath),
+    /// Clipboard has been read for pasting into a pane.
+    Pasted(ViewPath, Option<String>),
+    /// An archive of an OPDS feed has been downloaded, or failed to be.
+    FeedDownloaded {
+        /// Item of archive.
+        item_path: ItemPath,
+        /// Result of download.
+        result: Result<(), Arc<Error>>,
+    },
+    /// Open item context menu of a view is shown for in an external application.
+    OpenWith(ViewPath, Application),
+    /// Show dialog choosing the application an archive is opened with.
+    ShowChooser(ItemPath),
+    /// Choose application of the chooser of a pane, [None] for the built-in reader.
+    ChooseApplication(ViewPath, Option<usize>),
+    /// Set whether the chooser of a pane remembers its choice.
+    SetChooserRemember(ViewPath, Remember),
+    /// Open archive of the chooser of a pane with the chosen application.
+    ConfirmChooser(ViewPath),
+    /// Close chooser of a pane.
+    CancelChooser(ViewPath),
+    /// Toggle showing a pane as a shelf.
+    ToggleShelf(ViewPath),
+    /// Series of a directory shown as a shelf have been scanned.
+    ShelfLoaded {
+        /// View directory was scanned for.
+        view_path: ViewPath,
+        /// Token of view scan was started for.
+        token: CancelToken,
+        /// Subdirectories containing archives, with their archives in reading order.
+        series: ShelfSeries,
+    },
+    /// A row of a shelf was scrolled horizontally.
+    ShelfScrolled {
+        /// Path to scrolled view.
+        view_path: ViewPath,
+        /// Index of row.
+        row: usize,
+        /// New horizontal scroll offset.
+        offset: f32,
+    },
+    /// Add the missing volumes of a shelf row to the wishlist.
+    WishMissing(ViewPath, usize),
+    /// Remove a volume of a shelf row from the wishlist.
+    Unwish(ViewPath, usize, f64),
+    /// Follow the series of a shelf row, or stop following it.
+    ToggleFollow(ViewPath, usize),
+    /// Dismiss new volumes of the series of a shelf row.
+    DismissAlerts(ViewPath, usize),
+    /// Toggle showing items of a pane in reading order.
+    ToggleReadingOrder(ViewPath),
+    /// Move item context menu of a view is shown for by an offset in reading order,
+    /// storing the result as the manual order of the series.
+    MoveInReadingOrder(ViewPath, isize),
+    /// Directory view of a pane was scrolled.
+    PaneScrolled {
+        /// Path to scrolled view.
+        view_path: ViewPath,
+        /// New vertical scroll offset.
+        offset: f32,
+        /// Size of scrollable viewport.
+        viewport: Size,
+    },
+    /// Keyboard event.
+    KeyEvent(::iced::keyboard::Event),
+    /// Add a directory item.
+    AddItem {
+        /// Path to add item at.
+        item_path: ItemPath,
+        /// Token of view scan was started for.
+        token: CancelToken,
+        /// Item to add.
+        item: pane::Item,
+    },
+    /// Scan of a directory has finished.
+    ScanFinished {
+        /// View directory was scanned for.
+        view_path: ViewPath,
+        /// Token of view scan was started for.
+        token: CancelToken,
+    },
+    /// Thumbnail of an item has been loaded.
+    ThumbnailLoaded {
+        /// Path of item.
+        item_path: ItemPath,
+        /// Token of view load was started for.
+        token: CancelToken,
+        /// Loaded thumbnail.
+        handle: widget::image::Handle,
+        /// Frames of thumbnail if animated and animated covers are enabled.
+        animation: Option<Animation>,
+        /// Dominant color of thumbnail, if found.
+        accent: Option<[u8; 3]>,
+    },
+    /// Thumbnail of an item could not be loaded.
+    ThumbnailFailed {
+        /// Path of item.
+        item_path: ItemPath,
+        /// Token of view load was started for.
+        token: CancelToken,
+    },
+    /// File of an item could not be reached.
+    ItemOffline {
+        /// Path of item.
+        item_path: ItemPath,
+        /// Token of view load was started for.
+        token: CancelToken,
+    },
+    /// Metadata of an item has been loaded.
+    MetadataLoaded {
+        /// Path of item.
+        item_path: ItemPath,
+        /// Token of view load was started for.
+        token: CancelToken,
+        /// Loaded metadata.
+        metadata: archive::Metadata,
+    },
+    /// An item was pressed.
+    ItemPressed(ItemPath),
+    /// An item was double clicked.
+    ItemDoubleClicked(ItemPath),
+    /// An item was clicked with the middle mouse button.
+    ItemMiddleClicked(ItemPath),
+    /// Toggle fullscreen of a reader window.
+    ToggleFullscreen(window::Id),
+    /// Use current page of a reader as cover of its archive.
+    UseAsCover(window::Id),
+    /// Pick an image file used as cover of an archive with the file chooser of the
+    /// desktop.
+    PickCover(ItemPath),
+    /// Image file was picked as cover of an archive.
+    CoverPicked(Arc<Path>, PathBuf),
+    /// Use first page of an archive as its cover.
+    ResetCover(ItemPath),
+    /// Toggle bookmark of current page of a reader window.
+    ToggleBookmark(window::Id),
+    /// Go to a page in a reader window.
+    GotoPage {
+        /// Window of reader.
+        window_id: window::Id,
+        /// Page to go to.
+        page: usize,
+    },
+    /// A page has been loaded for a reader window.
+    PageLoaded {
+        /// Window of reader.
+        window_id: window::Id,
+        /// Index of loaded page.
+        page: usize,
+        /// Amount of pages in archive.
+        page_count: usize,
+        /// Loaded page.
+        image: widget::image::Handle,
+        /// Width and height of page in pixels, if known.
+        dimensions: Option<(u32, u32)>,
+        /// Frames of page if animated.
+        animation: Option<Animation>,
+    },
+    /// Page of a reader window was pressed by the mouse or a finger.
+    ReaderPressed {
+        /// Window of reader.
+        window_id: window::Id,
+        /// Size of area page is shown in.
+        area: Size,
+    },
+    /// Show or hide controls adjusting pages of a reader window.
+    ToggleAdjustments(window::Id),
+    /// Change adjustments of a reader window without applying them, such as while a
+    /// slider is dragged.
+    SetAdjustments(window::Id, archive::Adjustments),
+    /// Load current page of a reader window again with its adjustments.
+    ApplyAdjustments(window::Id),
+    /// Change adjustments of a reader window and apply them.
+    AdjustPages(window::Id, archive::Adjustments),
+    /// Set whether adjustments of a reader window are remembered for its archive.
+    RememberAdjustments(window::Id, bool),
+    /// Show or hide page strip of a reader window.
+    TogglePageStrip(window::Id),
+    /// Page strip of a reader window was scrolled.
+    PageStripScrolled {
+        /// Window of reader.
+        window_id: window::Id,
+        /// Horizontal scroll offset of strip.
+        offset: f32,
+        /// Width of strip.
+        width: f32,
+    },
+    /// Thumbnails of pages shown in the page strip of a reader window were loaded.
+    PageThumbnailsLoaded {
+        /// Window of reader.
+        window_id: window::Id,
+        /// Path of archive.
+        path: Arc<Path>,
+        /// Pages thumbnails were requested for.
+        pages: Vec<usize>,
+        /// Thumbnails of pages which could be loaded.
+        thumbnails: Vec<(usize, widget::image::Handle)>,
+    },
+    /// Start or stop slideshow of a reader window.
+    ToggleSlideshow(window::Id),
+    /// Open archive in a new reader window running a slideshow.
+    StartSlideshow(Arc<Path>),
+    /// Stop slideshows of every reader window.
+    StopSlideshows,
+    /// Advance slideshows of reader windows which are due.
+    SlideshowTick,
+    /// Switch a reader window between showing single pages and continuous layout.
+    ToggleContinuous(window::Id),
+    /// Continuous layout of a reader window was scrolled or resized.
+    ContinuousScrolled {
+        /// Window of reader.
+        window_id: window::Id,
+        /// Vertical scroll offset.
+        offset: f32,
+        /// Size of visible area.
+        size: Size,
+    },
+    /// Continuous layout of a reader window finished scrolling to a page.
+    ContinuousJumped(window::Id),
+    /// Pages near the visible area of the continuous layout of a reader window were
+    /// loaded.
+    ContinuousPagesLoaded {
+        /// Window of reader.
+        window_id: window::Id,
+        /// Path of archive.
+        path: Arc<Path>,
+        /// Adjustments pages were loaded with.
+        adjustments: archive::Adjustments,
+        /// Pages requested.
+        pages: Vec<usize>,
+        /// Pages which could be loaded.
+        loaded: Vec<LoadedPage>,
+    },
+    /// Press on page of a reader window was released.
+    ReaderReleased(window::Id),
+    /// Mouse wheel or touchpad scrolled over page of a reader window.
+    ReaderScrolled {
+        /// Window of reader.
+        window_id: window::Id,
+        /// Scrolled distance.
+        delta: ScrollDelta,
+        /// Size of area page is shown in.
+        area: Size,
+    },
+    /// A finger touched, moved on or was lifted from a window.
+    Touched(window::Id, touch::Event),
+    /// Continue panning pages of readers after fingers were lifted.
+    GlideTick,
+    /// First page of the next volume of a reader has been loaded ahead of time.
+    VolumePrefetched {
+        /// Window of reader.
+        window_id: window::Id,
+        /// Path of next volume.
+        path: Arc<Path>,
+        /// Amount of pages in next volume.
+        page_count: usize,
+        /// Loaded first page.
+        image: widget::image::Handle,
+        /// Width and height of first page in pixels, if known.
+        dimensions: Option<(u32, u32)>,
+        /// Frames of first page if animated.
+        animation: Option<Animation>,
+    },
+    /// Replace archive of a reader with the next volume of its series.
+    NextVolume(window::Id),
+    /// Apply edited settings.
+    ApplySettings,
+    /// Discard edited settings.
+    RevertSettings,
+    /// Apply edited settings and save them.
+    SaveSettings,
+    /// Load settings from config file for editing.
+    ReloadSettigns,
+    /// Settings window was asked to close.
+    CloseSettingsRequested(window::Id),
+    /// Keep settings window with unapplied changes open.
+    CancelCloseSettings,
+    /// Config file was changed outside of application.
+    SettingsChangedOnDisk(Settings),
+    /// Use settings changed on disk, discarding unsaved changes.
+    LoadSettingsFromDisk,
+    /// Keep unsaved changes, ignoring settings changed on disk.
+    KeepSettings,
+}
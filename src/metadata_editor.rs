@@ -0,0 +1,289 @@
+//! [MetadataEditor] impl, editing `ComicInfo.xml` metadata of one or many zip archives,
+//! with changes reviewed before they are written.
+
+use ::std::{path::Path, sync::Arc};
+
+use ::hashbrown::HashSet;
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+    window,
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message,
+    archive::{ComicField, ComicInfo},
+    i18n::{tr, tr_args},
+    list_entry_name,
+};
+
+/// Stage of a metadata editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// Metadata of archives is being read.
+    Reading,
+    /// Fields are being edited.
+    Editing,
+    /// Changes are shown before they are written.
+    Reviewing,
+    /// Changes are being written.
+    Writing,
+}
+
+/// Change of the metadata of an archive.
+#[derive(Debug, Clone)]
+pub struct MetadataChange {
+    /// Path of archive.
+    pub path: Arc<Path>,
+    /// Metadata of archive before change.
+    pub old: ComicInfo,
+    /// Metadata of archive after change.
+    pub new: ComicInfo,
+}
+
+/// Editor of the metadata of one or many archives, fields differing between archives are
+/// left as they are unless edited.
+#[derive(Debug, Clone)]
+pub struct MetadataEditor {
+    /// Archives being edited, along with their metadata once read.
+    archives: Vec<(Arc<Path>, ComicInfo)>,
+    /// Values of fields, empty for fields differing between archives.
+    values: ComicInfo,
+    /// Fields which have been edited, and are written to every archive.
+    edited: HashSet<ComicField>,
+    /// Stage of editor.
+    pub stage: Stage,
+}
+
+impl MetadataEditor {
+    /// Create an editor of archives whose metadata is being read.
+    pub fn new(paths: Vec<Arc<Path>>) -> Self {
+        Self {
+            archives: paths
+                .into_iter()
+                .map(|path| (path, ComicInfo::default()))
+                .collect(),
+            values: ComicInfo::default(),
+            edited: HashSet::new(),
+            stage: Stage::Reading,
+        }
+    }
+
+    /// Start editing archives whose metadata has been read, archives which could not be
+    /// read are not edited.
+    pub fn read(&mut self, archives: Vec<(Arc<Path>, ComicInfo)>) {
+        self.values = ComicInfo::default();
+        for field in ComicField::ALL {
+            let mut values = archives.iter().map(|(_, info)| info.get(field));
+            if let Some(first) = values.next()
+                && values.all(|value| value == first)
+            {
+                first.clone_into(self.values.get_mut(field));
+            }
+        }
+        self.archives = archives;
+        self.edited.clear();
+        self.stage = Stage::Editing;
+    }
+
+    /// Set value of a field, which is then written to every archive.
+    pub fn set(&mut self, field: ComicField, text: String) {
+        *self.values.get_mut(field) = text;
+        self.edited.insert(field);
+    }
+
+    /// Check if a field differs between archives and has not been edited.
+    fn is_mixed(&self, field: ComicField) -> bool {
+        !self.edited.contains(&field)
+            && self
+                .archives
+                .iter()
+                .any(|(_, info)| info.get(field) != self.values.get(field))
+    }
+
+    /// Get changes of archives whose metadata would change.
+    pub fn changes(&self) -> Vec<MetadataChange> {
+        self.archives
+            .iter()
+            .filter_map(|(path, old)| {
+                let mut new = old.clone();
+                for &field in &self.edited {
+                    self.values.get(field).trim().clone_into(new.get_mut(field));
+                }
+                (new != *old).then(|| MetadataChange {
+                    path: Arc::clone(path),
+                    old: old.clone(),
+                    new,
+                })
+            })
+            .collect()
+    }
+
+    /// Record changes which were written, and continue editing such that the remaining
+    /// changes may be written again.
+    pub fn written(&mut self, changes: &[MetadataChange]) {
+        for change in changes {
+            if let Some((_, info)) = self
+                .archives
+                .iter_mut()
+                .find(|(path, _)| *path == change.path)
+            {
+                info.clone_from(&change.new);
+            }
+        }
+        self.stage = Stage::Editing;
+    }
+
+    /// View editor.
+    pub fn view(&self, window_id: window::Id) -> Element<'_, Message> {
+        let title = match self.archives.as_slice() {
+            [(path, _)] => list_entry_name(path),
+            archives => tr_args(
+                "metadata-archives",
+                &[("count", &archives.len().to_string())],
+            ),
+        };
+        let content = match self.stage {
+            Stage::Reading => widget::text(tr_args("metadata-reading", &[("title", &title)]))
+                .style(widget::text::secondary)
+                .pipe(widget::container)
+                .center(Fill)
+                .into(),
+            Stage::Editing => self.view_fields(window_id, title),
+            Stage::Reviewing | Stage::Writing => self.view_changes(window_id),
+        };
+        widget::container(content).padding(10).into()
+    }
+
+    /// View fields being edited.
+    fn view_fields(&self, window_id: window::Id, title: String) -> Element<'_, Message> {
+        let has_changes = !self.changes().is_empty();
+        widget::Column::new()
+            .spacing(5)
+            .push(
+                widget::text(tr_args("metadata-edit", &[("title", &title)]))
+                    .wrapping(Wrapping::WordOrGlyph),
+            )
+            .extend(ComicField::ALL.map(|field| {
+                let label = field_label(field);
+                let placeholder = if self.is_mixed(field) {
+                    tr("metadata-multiple-values")
+                } else {
+                    label.clone()
+                };
+                widget::Row::new()
+                    .spacing(5)
+                    .align_y(Center)
+                    .push(widget::text(label).width(60))
+                    .push(
+                        widget::text_input(&placeholder, self.values.get(field))
+                            .on_input(move |text| Message::SetMetadataField(window_id, field, text))
+                            .on_submit_maybe(
+                                has_changes.then_some(Message::ReviewMetadata(window_id)),
+                            )
+                            .padding(3)
+                            .width(Fill),
+                    )
+                    .into()
+            }))
+            .push(
+                widget::Row::new()
+                    .spacing(3)
+                    .push(
+                        widget::button(widget::text(tr("metadata-review")))
+                            .padding(3)
+                            .on_press_maybe(
+                                has_changes.then_some(Message::ReviewMetadata(window_id)),
+                            ),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("button-cancel")))
+                            .padding(3)
+                            .style(widget::button::secondary)
+                            .on_press(Message::CloseMetadataEditor(window_id)),
+                    ),
+            )
+            .into()
+    }
+
+    /// View changes to be written, as removed and added lines per archive.
+    fn view_changes(&self, window_id: window::Id) -> Element<'_, Message> {
+        let changes = self.changes();
+        let writing = self.stage == Stage::Writing;
+        widget::Column::new()
+            .spacing(5)
+            .push(widget::text(tr_args(
+                "metadata-will-be-written",
+                &[("count", &changes.len().to_string())],
+            )))
+            .push(
+                widget::Column::new()
+                    .spacing(8)
+                    .extend(changes.iter().map(view_change))
+                    .pipe(widget::scrollable)
+                    .height(Fill),
+            )
+            .push(
+                widget::Row::new()
+                    .spacing(3)
+                    .push(
+                        widget::button(widget::text(tr(if writing {
+                            "metadata-writing"
+                        } else {
+                            "metadata-write"
+                        })))
+                        .padding(3)
+                        .on_press_maybe((!writing).then_some(Message::WriteMetadata(window_id))),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("button-back")))
+                            .padding(3)
+                            .style(widget::button::secondary)
+                            .on_press_maybe(
+                                (!writing).then_some(Message::EditMetadataFields(window_id)),
+                            ),
+                    ),
+            )
+            .into()
+    }
+}
+
+/// Get label of a field in the language in use.
+pub fn field_label(field: ComicField) -> String {
+    tr_args("metadata-field", &[("field", field.element())])
+}
+
+/// View change of an archive, changed fields are shown as their removed value followed
+/// by their added value.
+fn view_change<'a>(change: &MetadataChange) -> Element<'a, Message> {
+    let line = |sign: char,
+                field: ComicField,
+                value: &str,
+                style: fn(&::iced::Theme) -> widget::text::Style| {
+        (!value.trim().is_empty()).then(|| {
+            widget::text(format!("{sign} {}: {}", field_label(field), value.trim()))
+                .size(13)
+                .font(::iced::Font::MONOSPACE)
+                .style(style)
+        })
+    };
+    widget::Column::new()
+        .push(widget::text(list_entry_name(&change.path)).wrapping(Wrapping::None))
+        .extend(
+            ComicField::ALL
+                .into_iter()
+                .filter(|&field| change.old.get(field) != change.new.get(field))
+                .flat_map(|field| {
+                    [
+                        line('-', field, change.old.get(field), widget::text::danger),
+                        line('+', field, change.new.get(field), widget::text::success),
+                    ]
+                })
+                .flatten()
+                .map(Element::from),
+        )
+        .into()
+}
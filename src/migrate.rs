@@ -0,0 +1,88 @@
+//! Upgrading of config files written by older versions of application.
+
+use ::toml::{Table, Value};
+
+/// Current version of settings schema, increased whenever a migration is added.
+pub const SETTINGS_VERSION: u32 = 2;
+
+/// Migration upgrading settings from the version preceding it.
+type Migration = fn(&mut Table);
+
+/// Migrations of settings, the migration at index `n` upgrades version `n` to `n + 1`.
+const MIGRATIONS: [Migration; SETTINGS_VERSION as usize] = [card_width_to_card_size, f2_to_rename];
+
+/// Key of schema version in config file, config files without it are version 0.
+const VERSION_KEY: &str = "version";
+
+/// Upgrade settings table to the current version, returning true if it was changed.
+///
+/// Tables written by a newer version are left as they are, settings unknown to this
+/// version are ignored when deserialized.
+pub fn settings(table: &mut Table) -> bool {
+    let version = match table.get(VERSION_KEY) {
+        None => 0,
+        Some(Value::Integer(version)) => u32::try_from(*version).unwrap_or(u32::MAX),
+        Some(value) => {
+            ::log::warn!("config file has invalid version {value}, assuming it is current");
+            SETTINGS_VERSION
+        }
+    };
+    if version > SETTINGS_VERSION {
+        ::log::warn!(
+            "config file has version {version}, newer than supported version {SETTINGS_VERSION}"
+        );
+        return false;
+    }
+    if version == SETTINGS_VERSION {
+        return false;
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        ::log::info!("upgrading config file from version {from} to {}", from + 1);
+        migration(table);
+    }
+    table.insert(
+        VERSION_KEY.to_owned(),
+        Value::Integer(SETTINGS_VERSION.into()),
+    );
+    true
+}
+
+/// Version 0 to 1, card width in pixels was replaced by a card size in millimeters.
+fn card_width_to_card_size(table: &mut Table) {
+    /// Logical pixels per millimeter at a scale factor of 1.
+    const PIXELS_PER_MM: f64 = 96.0 / 25.4;
+
+    let Some(Value::Integer(width)) = table.remove("card_width") else {
+        return;
+    };
+    if table.contains_key("card_size") {
+        return;
+    }
+    let millimeters = (width as f64 / PIXELS_PER_MM).round() as i64;
+    table.insert(
+        "card_size".to_owned(),
+        Value::Table(Table::from_iter([(
+            "millimeters".to_owned(),
+            Value::Integer(millimeters.clamp(1, u16::MAX.into())),
+        )])),
+    );
+}
+
+/// Version 1 to 2, renaming the archive under the keyboard cursor became an action bound
+/// to F2, instead of being done by F2 before the action bound to it. Settings bound to F2
+/// are moved to F8, if it is free.
+fn f2_to_rename(table: &mut Table) {
+    let Some(Value::Table(keys)) = table
+        .get_mut("bindings")
+        .and_then(|bindings| bindings.get_mut("keys"))
+    else {
+        return;
+    };
+    if keys.get("f2").and_then(Value::as_str) != Some("toggle-settings") {
+        return;
+    }
+    keys.insert("f2".to_owned(), Value::String("rename".to_owned()));
+    keys.entry("f8")
+        .or_insert_with(|| Value::String("toggle-settings".to_owned()));
+}
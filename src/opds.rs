@@ -0,0 +1,330 @@
+//! Browsing of OPDS catalogues, such as those served by Komga, Kavita and Calibre-web.
+//! Feeds and the files they link to are fetched with curl, downloaded archives are kept
+//! in a cache directory of profile.
+
+use ::std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use ::derive_more::IsVariant;
+use ::roxmltree::{Document, Node};
+use ::sha2::{Digest, Sha256};
+
+use crate::{archive::ArchiveKind, error::OpdsError, i18n::tr, temp};
+
+/// Name of directory in cache directory of profile downloads are kept in.
+const CACHE_DIR: &str = "opds";
+
+/// Name of directory in download cache remote covers are kept in.
+const COVER_DIR: &str = "covers";
+
+/// Name of netrc file in config directory of profile credentials of feeds are read from.
+const NETRC: &str = "opds.netrc";
+
+/// Namespace of Atom documents.
+const ATOM: &str = "http://www.w3.org/2005/Atom";
+
+/// Seconds a feed or cover may take to be fetched.
+const FETCH_TIMEOUT: &str = "60";
+
+/// Seconds connecting to a server may take.
+const CONNECT_TIMEOUT: &str = "10";
+
+/// A loaded OPDS feed.
+#[derive(Debug, Clone, Default)]
+pub struct Feed {
+    /// Url feed was loaded from.
+    pub url: String,
+    /// Title of feed.
+    pub title: String,
+    /// Entries of feed, in the order they are listed.
+    pub entries: Vec<Entry>,
+}
+
+/// What an entry of a feed leads to.
+#[derive(Debug, Clone, PartialEq, Eq, IsVariant)]
+pub enum Link {
+    /// Another feed.
+    Navigation(String),
+    /// An archive which may be downloaded.
+    Acquisition {
+        /// Url of archive.
+        url: String,
+        /// Extension archive is saved with.
+        extension: &'static str,
+    },
+}
+
+/// An entry of a feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Title of entry.
+    pub title: String,
+    /// Author of entry, if given.
+    pub author: Option<String>,
+    /// What entry leads to.
+    pub link: Link,
+    /// Url of cover image of entry, preferring thumbnails.
+    pub cover: Option<String>,
+}
+
+impl Entry {
+    /// Get path entry is shown as in download cache, archives are downloaded to it.
+    pub fn path(&self, dir: &Path) -> PathBuf {
+        match &self.link {
+            Link::Navigation(url) => dir.join(hash(url)),
+            Link::Acquisition { url, extension } => dir.join(format!("{}.{extension}", hash(url))),
+        }
+    }
+}
+
+/// Get download cache of profile, creating it if missing.
+///
+/// # Errors
+/// If the directory cannot be created.
+pub fn cache_dir(xdg_dirs: &::xdg::BaseDirectories) -> io::Result<PathBuf> {
+    xdg_dirs.create_cache_directory(CACHE_DIR)
+}
+
+/// Find netrc file of profile holding credentials of feeds, if any.
+pub fn netrc(xdg_dirs: &::xdg::BaseDirectories) -> Option<PathBuf> {
+    xdg_dirs.find_config_file(NETRC)
+}
+
+/// Check if curl is installed, searching `PATH`.
+pub fn has_curl() -> bool {
+    ::std::env::var_os("PATH")
+        .is_some_and(|paths| ::std::env::split_paths(&paths).any(|dir| dir.join("curl").is_file()))
+}
+
+/// Get path remote cover at url is kept at in download cache.
+pub fn cover_path(dir: &Path, url: &str) -> PathBuf {
+    dir.join(COVER_DIR).join(hash(url))
+}
+
+/// Get a file name unique to url.
+fn hash(url: &str) -> String {
+    Sha256::digest(url.as_bytes())[..16]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Get extension archives of a media type are saved with, [None] if not supported.
+fn extension(media_type: &str) -> Option<&'static str> {
+    let extension = match media_type.split(';').next()?.trim() {
+        "application/zip"
+        | "application/x-zip-compressed"
+        | "application/x-cbz"
+        | "application/vnd.comicbook+zip" => "cbz",
+        "application/epub+zip" => "epub",
+        "application/pdf" => "pdf",
+        _ => return None,
+    };
+    ArchiveKind::from_path(Path::new(&format!("archive.{extension}"))).map(|_| extension)
+}
+
+/// Resolve a possibly relative reference against the url of the document it is found in.
+pub fn resolve(base: &str, href: &str) -> String {
+    let scheme_end = base.find("://").map_or(0, |end| end + 3);
+    let origin_end = base[scheme_end..]
+        .find(['/', '?', '#'])
+        .map_or(base.len(), |end| end + scheme_end);
+    let path_end = base.find(['?', '#']).unwrap_or(base.len()).max(origin_end);
+    if href
+        .split_once("://")
+        .is_some_and(|(scheme, _)| !scheme.contains(['/', '?', '#']))
+    {
+        href.to_owned()
+    } else if let Some(href) = href.strip_prefix("//") {
+        format!("{}//{href}", &base[..scheme_end.saturating_sub(2)])
+    } else if href.starts_with('/') {
+        format!("{}{href}", &base[..origin_end])
+    } else if href.starts_with(['?', '#']) {
+        format!("{}{href}", &base[..path_end])
+    } else {
+        let dir_end = base[origin_end..path_end]
+            .rfind('/')
+            .map_or(origin_end, |end| end + origin_end);
+        format!("{}/{href}", &base[..dir_end])
+    }
+}
+
+/// Get attribute of link, or an empty string.
+fn attribute<'a>(link: Node<'a, '_>, name: &str) -> &'a str {
+    link.attribute(name).unwrap_or_default()
+}
+
+/// Get text of first child element of node with given name.
+fn child_text(node: Node<'_, '_>, name: &str) -> Option<String> {
+    node.children()
+        .find(|child| child.has_tag_name((ATOM, name)))
+        .and_then(|child| child.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(String::from)
+}
+
+/// Parse an entry of a feed at url, [None] if it leads to nothing which can be shown.
+fn parse_entry(url: &str, entry: Node<'_, '_>) -> Option<Entry> {
+    let links = entry
+        .children()
+        .filter(|child| child.has_tag_name((ATOM, "link")))
+        .collect::<Vec<_>>();
+    let href = |link: Node<'_, '_>| resolve(url, attribute(link, "href"));
+    let link = links
+        .iter()
+        .find_map(|&link| {
+            attribute(link, "rel")
+                .starts_with("http://opds-spec.org/acquisition")
+                .then(|| extension(attribute(link, "type")))
+                .flatten()
+                .map(|extension| Link::Acquisition {
+                    url: href(link),
+                    extension,
+                })
+        })
+        .or_else(|| {
+            links
+                .iter()
+                .find(|&&link| attribute(link, "type").starts_with("application/atom+xml"))
+                .map(|&link| Link::Navigation(href(link)))
+        })?;
+    let cover = [
+        "http://opds-spec.org/image/thumbnail",
+        "http://opds-spec.org/thumbnail",
+        "http://opds-spec.org/image",
+        "http://opds-spec.org/cover",
+    ]
+    .into_iter()
+    .find_map(|rel| links.iter().find(|&&link| attribute(link, "rel") == rel))
+    .map(|&link| href(link));
+    Some(Entry {
+        title: child_text(entry, "title").unwrap_or_else(|| tr("opds-untitled")),
+        author: entry
+            .children()
+            .find(|child| child.has_tag_name((ATOM, "author")))
+            .and_then(|author| child_text(author, "name")),
+        link,
+        cover,
+    })
+}
+
+/// Parse an Atom feed loaded from url. The next page of a paged feed is added as a
+/// navigation entry.
+///
+/// # Errors
+/// If the document is not a valid Atom feed.
+pub fn parse(url: &str, content: &str) -> Result<Feed, OpdsError> {
+    let document = Document::parse(content).map_err(OpdsError::Xml)?;
+    let root = document.root_element();
+    if !root.has_tag_name((ATOM, "feed")) {
+        return Err(OpdsError::NotAtom);
+    }
+    let mut entries = root
+        .children()
+        .filter(|child| child.has_tag_name((ATOM, "entry")))
+        .filter_map(|entry| parse_entry(url, entry))
+        .collect::<Vec<_>>();
+    if let Some(next) = root
+        .children()
+        .find(|child| child.has_tag_name((ATOM, "link")) && attribute(*child, "rel") == "next")
+    {
+        entries.push(Entry {
+            title: tr("opds-next-page"),
+            author: None,
+            link: Link::Navigation(resolve(url, attribute(next, "href"))),
+            cover: None,
+        });
+    }
+    Ok(Feed {
+        url: url.to_owned(),
+        title: child_text(root, "title").unwrap_or_else(|| url.to_owned()),
+        entries,
+    })
+}
+
+/// Get a curl command fetching url, failing on http errors. Credentials are read from
+/// netrc if given and not part of url.
+fn curl(url: &str, netrc: Option<&Path>) -> Command {
+    let mut curl = Command::new("curl");
+    curl.args(["--fail", "--silent", "--show-error", "--location"])
+        .args(["--proto", "=http,https"])
+        .args(["--connect-timeout", CONNECT_TIMEOUT]);
+    if let Some(netrc) = netrc {
+        curl.arg("--netrc-optional").arg("--netrc-file").arg(netrc);
+    }
+    curl.arg("--url").arg(url).stdin(Stdio::null());
+    curl
+}
+
+/// Run a curl command, returning what it wrote to stdout.
+///
+/// # Errors
+/// If curl cannot be run or fails.
+fn run(mut curl: Command) -> Result<Vec<u8>, OpdsError> {
+    let output = curl.output().map_err(OpdsError::Curl)?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        let message = String::from_utf8_lossy(&output.stderr);
+        Err(OpdsError::Fetch(
+            message.trim().trim_start_matches("curl: ").to_owned(),
+        ))
+    }
+}
+
+/// Fetch and parse feed at url.
+///
+/// # Errors
+/// If the feed cannot be fetched or is not a valid Atom feed.
+pub fn load(url: &str, netrc: Option<&Path>) -> Result<Feed, OpdsError> {
+    let mut curl = curl(url, netrc);
+    curl.args(["--max-time", FETCH_TIMEOUT]);
+    let content = run(curl)?;
+    parse(url, &String::from_utf8_lossy(&content))
+}
+
+/// Fetch cover at url into download cache, unless already fetched, returning its
+/// content.
+///
+/// # Errors
+/// If the cover cannot be fetched or written.
+pub fn fetch_cover(dir: &Path, url: &str, netrc: Option<&Path>) -> Result<Vec<u8>, OpdsError> {
+    let target = cover_path(dir, url);
+    match fs::read(&target) {
+        Ok(content) => return Ok(content),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(OpdsError::Read(err)),
+    }
+    let mut curl = curl(url, netrc);
+    curl.args(["--max-time", FETCH_TIMEOUT]);
+    let content = run(curl)?;
+    fs::create_dir_all(dir.join(COVER_DIR)).map_err(OpdsError::Write)?;
+    temp::write(&target, &content).map_err(OpdsError::Write)?;
+    Ok(content)
+}
+
+/// Download file at url to target, through a temporary file in directory of temporary
+/// files such that an interrupted download is never mistaken for an archive.
+///
+/// # Errors
+/// If the file cannot be fetched or moved into place.
+pub fn download(
+    url: &str,
+    netrc: Option<&Path>,
+    target: &Path,
+    temp_dir: &Path,
+) -> Result<(), OpdsError> {
+    let temporary = temp::path(temp_dir, "download");
+    let mut curl = curl(url, netrc);
+    curl.arg("--output").arg(&temporary);
+    let result =
+        run(curl).and_then(|_| temp::persist(&temporary, target).map_err(OpdsError::Write));
+    if result.is_err() {
+        _ = fs::remove_file(&temporary);
+    }
+    result
+}
@@ -0,0 +1,248 @@
+//! Opening archives in external applications, found in the desktop mime database or
+//! configured as command templates in settings.
+
+use ::std::{
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Arc,
+};
+
+use crate::{archive::ArchiveKind, error::Error};
+
+/// Placeholder of commands replaced by the path of the opened archive.
+pub const PATH_PLACEHOLDER: &str = "{path}";
+
+/// Desktop file of application, which is not listed as an external application.
+const OWN_DESKTOP_FILE: &str = "io.github.axel_lord.ArkivKatalog.desktop";
+
+/// Max amount of applications of the desktop mime database listed for an archive.
+const MAX_DESKTOP_APPLICATIONS: usize = 5;
+
+/// External application archives may be opened with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Application {
+    /// Name of application shown to user.
+    pub name: String,
+    /// Program and arguments, with [PATH_PLACEHOLDER] where the path is passed.
+    command: Arc<[String]>,
+}
+
+impl Application {
+    /// Create an application from a command template of whitespace separated arguments,
+    /// the path is appended if the template has no [PATH_PLACEHOLDER]. [None] for empty
+    /// templates.
+    pub fn from_template(template: &str) -> Option<Self> {
+        let mut command = template
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let program = command.first()?;
+        let name = Path::new(program).file_name().map_or_else(
+            || program.clone(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        if !command.iter().any(|arg| arg.contains(PATH_PLACEHOLDER)) {
+            command.push(PATH_PLACEHOLDER.to_owned());
+        }
+        Some(Self {
+            name,
+            command: command.into(),
+        })
+    }
+
+    /// Parse a desktop entry, [None] if it is hidden or does not take files.
+    fn from_desktop_entry(content: &str) -> Option<Self> {
+        let (mut name, mut exec) = (None, None);
+        for (key, value) in section(content, "[Desktop Entry]") {
+            match key {
+                "Name" => name = Some(value),
+                "Exec" => exec = Some(value),
+                "Hidden" if value == "true" => return None,
+                "Type" if value != "Application" => return None,
+                _ => {}
+            }
+        }
+        Some(Self {
+            name: name?.to_owned(),
+            command: exec_command(exec?)?.into(),
+        })
+    }
+
+    /// Launch application with archive at path, application is not waited on.
+    ///
+    /// # Errors
+    /// If the program of application cannot be started.
+    pub fn launch(&self, path: &Path) -> Result<(), Error> {
+        let mut args = self.command.iter().map(|arg| {
+            if arg == PATH_PLACEHOLDER {
+                path.as_os_str().to_owned()
+            } else {
+                OsString::from(arg.replace(PATH_PLACEHOLDER, &path.to_string_lossy()))
+            }
+        });
+        let program = args.next().unwrap_or_default();
+        let mut child =
+            Command::new(program)
+                .args(args)
+                .spawn()
+                .map_err(|source| Error::Launch {
+                    application: self.name.clone(),
+                    source,
+                })?;
+        // Children are waited on such that they are reaped when they exit.
+        ::std::thread::spawn(move || {
+            if let Err(err) = child.wait() {
+                ::log::warn!("could not wait on external application\n{err}");
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Get key value pairs of a section of a desktop entry or mime association file.
+fn section<'a>(content: &'a str, header: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+    let mut in_section = false;
+    content.lines().map(str::trim).filter_map(move |line| {
+        if line.starts_with('[') {
+            in_section = line == header;
+            return None;
+        }
+        let (key, value) = line.split_once('=').filter(|_| in_section)?;
+        Some((key.trim(), value.trim()))
+    })
+}
+
+/// Split the exec key of a desktop entry into arguments, replacing file and url field
+/// codes with [PATH_PLACEHOLDER] and dropping other field codes. [None] if there is no
+/// field code for files.
+fn exec_command(exec: &str) -> Option<Vec<String>> {
+    let mut command = Vec::new();
+    let mut arg = String::new();
+    let (mut in_arg, mut quoted) = (false, false);
+    let mut chars = exec.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_arg = true;
+            }
+            '\\' if quoted => arg.extend(chars.next()),
+            c if c.is_whitespace() && !quoted => {
+                if in_arg {
+                    command.push(::core::mem::take(&mut arg));
+                    in_arg = false;
+                }
+            }
+            '%' => match chars.next() {
+                Some('%') => {
+                    arg.push('%');
+                    in_arg = true;
+                }
+                // Local files may be passed as paths to url field codes.
+                Some('f' | 'F' | 'u' | 'U') => {
+                    arg.push_str(PATH_PLACEHOLDER);
+                    in_arg = true;
+                }
+                _ => {}
+            },
+            c => {
+                arg.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        command.push(arg);
+    }
+    command
+        .iter()
+        .any(|arg| arg.contains(PATH_PLACEHOLDER))
+        .then_some(command)
+}
+
+/// Get mime types of an archive kind, as named by the desktop mime database.
+const fn mime_types(kind: ArchiveKind) -> &'static [&'static str] {
+    match kind {
+        ArchiveKind::Cbz => &[
+            "application/vnd.comicbook+zip",
+            "application/x-cbz",
+            "application/zip",
+        ],
+        ArchiveKind::Epub => &["application/epub+zip"],
+        #[cfg(feature = "pdf")]
+        ArchiveKind::Pdf => &["application/pdf"],
+    }
+}
+
+/// Find applications of the desktop mime database able to open archive at path, with
+/// default applications first.
+fn desktop_applications(xdg_dirs: &::xdg::BaseDirectories, path: &Path) -> Vec<Application> {
+    let Some(kind) = ArchiveKind::from_path(path) else {
+        return Vec::new();
+    };
+    let mime_types = mime_types(kind);
+    let application_dirs = xdg_dirs
+        .data_home
+        .iter()
+        .chain(&xdg_dirs.data_dirs)
+        .map(|dir| dir.join("applications"))
+        .collect::<Vec<_>>();
+    let association_files = xdg_dirs
+        .config_home
+        .iter()
+        .chain(&xdg_dirs.config_dirs)
+        .map(|dir| (dir.join("mimeapps.list"), "[Default Applications]"))
+        .chain(
+            application_dirs
+                .iter()
+                .map(|dir| (dir.join("mimeinfo.cache"), "[MIME Cache]")),
+        );
+
+    let mut desktop_files = Vec::<String>::new();
+    for (file, header) in association_files {
+        let Ok(content) = fs::read_to_string(&file) else {
+            continue;
+        };
+        for (mime_type, ids) in section(&content, header) {
+            if !mime_types.contains(&mime_type) {
+                continue;
+            }
+            for id in ids.split(';').filter(|id| !id.is_empty()) {
+                if id != OWN_DESKTOP_FILE && !desktop_files.iter().any(|known| known == id) {
+                    desktop_files.push(id.to_owned());
+                }
+            }
+        }
+    }
+
+    desktop_files
+        .iter()
+        .filter_map(|id| {
+            application_dirs
+                .iter()
+                .map(|dir| dir.join(id))
+                .find_map(|path: PathBuf| fs::read_to_string(path).ok())
+                .and_then(|content| Application::from_desktop_entry(&content))
+        })
+        .take(MAX_DESKTOP_APPLICATIONS)
+        .collect()
+}
+
+/// Get applications archive at path may be opened with, configured command templates
+/// first. Directories have no applications.
+pub fn applications(
+    templates: &[String],
+    xdg_dirs: &::xdg::BaseDirectories,
+    path: &Path,
+) -> Vec<Application> {
+    if ArchiveKind::from_path(path).is_none() {
+        return Vec::new();
+    }
+    templates
+        .iter()
+        .filter_map(|template| Application::from_template(template))
+        .chain(desktop_applications(xdg_dirs, path))
+        .collect()
+}
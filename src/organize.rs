@@ -0,0 +1,410 @@
+//! Organization of a library into directories given by a path template, with moves
+//! journaled such that they may be undone.
+
+use ::std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use ::hashbrown::HashSet;
+use ::serde::{Deserialize, Serialize};
+
+use crate::{
+    archive::Archive,
+    catalogue::Catalogue,
+    error::{ConfigError, Error},
+    inbox::{self, Conflicts},
+    series,
+};
+
+/// Template used when none is given.
+pub const DEFAULT_TEMPLATE: &str = "{series}/{series} v{volume}";
+
+/// Name of state file moves are journaled in.
+const JOURNAL_FILE: &str = "organize.toml";
+
+/// Field of a path template, replaced by a value of the archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    /// Series name, from the title or file name.
+    Series,
+    /// Volume number, padded to two digits.
+    Volume,
+    /// Title, from metadata or file name.
+    Title,
+    /// Author, from metadata.
+    Author,
+    /// File name without extension.
+    Name,
+}
+
+impl Field {
+    /// Get field of a name used in templates.
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "series" => Self::Series,
+            "volume" => Self::Volume,
+            "title" => Self::Title,
+            "author" => Self::Author,
+            "name" => Self::Name,
+            _ => return None,
+        })
+    }
+
+    /// Get name of field used in templates.
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Series => "series",
+            Self::Volume => "volume",
+            Self::Title => "title",
+            Self::Author => "author",
+            Self::Name => "name",
+        }
+    }
+}
+
+/// Part of a path template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    /// Text used as is.
+    Text(String),
+    /// Field replaced by a value of the archive.
+    Field(Field),
+}
+
+/// Template of paths of archives relative to library root, such as
+/// `{series}/{series} v{volume}`. The extension of archives is appended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template(Vec<Part>);
+
+impl Template {
+    /// Parse a template, fields are names within braces.
+    ///
+    /// # Errors
+    /// If the template names an unknown field.
+    pub fn parse(template: &str) -> Result<Self, Error> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            let name = &rest[start + 1..start + len];
+            let field = Field::from_name(name).ok_or_else(|| Error::Template {
+                template: template.to_owned(),
+                field: name.to_owned(),
+            })?;
+            if start > 0 {
+                parts.push(Part::Text(rest[..start].to_owned()));
+            }
+            parts.push(Part::Field(field));
+            rest = &rest[start + len + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Text(rest.to_owned()));
+        }
+        Ok(Self(parts))
+    }
+
+    /// Get path of archive relative to library root, or the name of a field archive
+    /// has no value for.
+    fn render(&self, path: &Path) -> Result<PathBuf, &'static str> {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let metadata = Archive::open(path)
+            .map(|archive| archive.metadata())
+            .unwrap_or_default();
+        let title = metadata.title.unwrap_or_else(|| name.clone());
+
+        let mut rendered = String::new();
+        for part in &self.0 {
+            let field = match part {
+                Part::Text(text) => {
+                    rendered.push_str(text);
+                    continue;
+                }
+                Part::Field(field) => *field,
+            };
+            let value = match field {
+                Field::Series => Some(series::series_name(&title)),
+                Field::Volume => series::volume(&name)
+                    .or_else(|| series::volume(&title))
+                    .map(format_volume),
+                Field::Title => Some(title.clone()),
+                Field::Author => metadata.author.clone(),
+                Field::Name => Some(name.clone()),
+            }
+            .filter(|value| !value.trim().is_empty())
+            .ok_or(field.name())?;
+            rendered.push_str(&component(&value));
+        }
+        let mut rendered = PathBuf::from(rendered);
+        if let Some(extension) = path.extension() {
+            rendered.as_mut_os_string().push(".");
+            rendered.as_mut_os_string().push(extension);
+        }
+        Ok(rendered)
+    }
+}
+
+/// Make value of a field part of a single path component, replacing separators and
+/// leading dots by underscores, such that values from archive metadata such as `..`
+/// cannot lead out of the library.
+fn component(value: &str) -> String {
+    let value = value.trim().replace('/', "_");
+    let rest = value.trim_start_matches('.');
+    let mut component = "_".repeat(value.len() - rest.len());
+    component.push_str(rest);
+    component
+}
+
+/// Format a volume number, with the whole part padded to two digits such that volumes
+/// sort by name.
+fn format_volume(volume: f64) -> String {
+    let text = volume.to_string();
+    if text.find('.').unwrap_or(text.len()) < 2 {
+        format!("0{text}")
+    } else {
+        text
+    }
+}
+
+/// Where an archive of the library belongs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Placement {
+    /// Archive belongs at target.
+    Move {
+        /// Path archive is moved to.
+        target: PathBuf,
+    },
+    /// Archive is already where it belongs.
+    InPlace,
+    /// Archive has no value for a field of template.
+    Missing {
+        /// Name of field.
+        field: &'static str,
+    },
+    /// Template leads out of the library root, such as by `..`.
+    Outside {
+        /// Path archive would be moved to.
+        target: PathBuf,
+    },
+    /// Target exists, or another archive belongs at target. Conflicts are resolved
+    /// when moving.
+    Conflict {
+        /// Path archive belongs at.
+        target: PathBuf,
+    },
+}
+
+/// Decide where archives of a library at root belong according to template.
+pub fn plan(root: &Path, archives: &[PathBuf], template: &Template) -> Vec<(PathBuf, Placement)> {
+    let mut targets = HashSet::new();
+    archives
+        .iter()
+        .map(|path| {
+            let placement = match template.render(path) {
+                Ok(target)
+                    if !target
+                        .components()
+                        .all(|component| matches!(component, Component::Normal(_))) =>
+                {
+                    Placement::Outside {
+                        target: root.join(target),
+                    }
+                }
+                Ok(target) => {
+                    let target = root.join(target);
+                    if target == *path {
+                        Placement::InPlace
+                    } else if !targets.insert(target.clone()) || target.try_exists().unwrap_or(true)
+                    {
+                        Placement::Conflict { target }
+                    } else {
+                        Placement::Move { target }
+                    }
+                }
+                Err(field) => Placement::Missing { field },
+            };
+            (path.clone(), placement)
+        })
+        .collect()
+}
+
+/// Move of an archive recorded in journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    /// Path archive was moved from.
+    from: PathBuf,
+    /// Path archive was moved to.
+    to: PathBuf,
+    /// Archive has been moved.
+    done: bool,
+}
+
+/// Journal of the most recent organization, saved before and after every move such
+/// that interrupted or unwanted organizations may be undone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Journal {
+    /// Moves of organization.
+    moves: Vec<Entry>,
+}
+
+impl Journal {
+    /// Load journal from state directory, empty if there is none.
+    ///
+    /// # Errors
+    /// If the journal cannot be read.
+    pub fn load(xdg_dirs: &::xdg::BaseDirectories) -> Result<Self, Error> {
+        let Some(path) = xdg_dirs.find_state_file(JOURNAL_FILE) else {
+            return Ok(Self::default());
+        };
+        fs::read_to_string(&path)
+            .map_err(ConfigError::Read)
+            .and_then(|content| ::toml::from_str(&content).map_err(ConfigError::Parse))
+            .map_err(|source| Error::Config { path, source })
+    }
+
+    /// Save journal to state directory.
+    ///
+    /// # Errors
+    /// If the journal cannot be written.
+    fn save(&self, xdg_dirs: &::xdg::BaseDirectories) -> Result<(), Error> {
+        let path = xdg_dirs
+            .place_state_file(JOURNAL_FILE)
+            .map_err(|source| Error::Config {
+                path: PathBuf::from(JOURNAL_FILE),
+                source: ConfigError::Write(source),
+            })?;
+        ::toml::to_string(self)
+            .map_err(ConfigError::Serialize)
+            .and_then(|content| fs::write(&path, content).map_err(ConfigError::Write))
+            .map_err(|source| Error::Config { path, source })
+    }
+
+    /// Move archives, journaling every move and moving catalogue state along with
+    /// archives. Directories left empty are removed. Calls `moved` for every archive
+    /// moved, failed moves are logged and skipped, as are moves skipped when resolving
+    /// conflicts. Archives that are overwritten cannot be restored by [Journal::undo].
+    /// Returns the amount of archives that could not be moved, or whose catalogue state
+    /// could not be moved.
+    ///
+    /// # Errors
+    /// If the journal cannot be saved or conflicts cannot be resolved, no further
+    /// archives are moved.
+    pub fn apply(
+        xdg_dirs: &::xdg::BaseDirectories,
+        catalogue: &Catalogue,
+        moves: Vec<(PathBuf, PathBuf)>,
+        conflicts: &mut Conflicts,
+        mut moved: impl FnMut(&Path, &Path) -> Result<(), Error>,
+    ) -> Result<usize, Error> {
+        let mut journal = Self {
+            moves: moves
+                .into_iter()
+                .map(|(from, to)| Entry {
+                    from,
+                    to,
+                    done: false,
+                })
+                .collect(),
+        };
+        journal.save(xdg_dirs)?;
+        let mut failed = 0;
+        for index in 0..journal.moves.len() {
+            let Entry { from, to, .. } = &journal.moves[index];
+            // Conflicts are resolved when moving, as earlier moves may cause them.
+            let Some((to, overwrite)) = conflicts.resolve(from, to)? else {
+                continue;
+            };
+            journal.moves[index].to = to;
+            let Entry { from, to, .. } = &journal.moves[index];
+            if let Err(err) = move_archive(from, to, overwrite) {
+                err.log();
+                failed += 1;
+                continue;
+            }
+            // Archive is journaled as moved before its state, such that it is moved back
+            // by undo even if its state cannot be moved.
+            journal.moves[index].done = true;
+            journal.save(xdg_dirs)?;
+            let Entry { from, to, .. } = &journal.moves[index];
+            if !move_state(catalogue, from, to) {
+                failed += 1;
+            }
+            moved(from, to)?;
+        }
+        Ok(failed)
+    }
+
+    /// Move archives of journal back, most recent first, along with their catalogue
+    /// state. Calls `moved` for every archive moved back, failed moves are logged and
+    /// kept in journal. Returns the amount of archives that could not be moved back, or
+    /// whose catalogue state could not be moved back.
+    ///
+    /// # Errors
+    /// If the journal cannot be saved, no further archives are moved.
+    pub fn undo(
+        mut self,
+        xdg_dirs: &::xdg::BaseDirectories,
+        catalogue: &Catalogue,
+        mut moved: impl FnMut(&Path, &Path) -> Result<(), Error>,
+    ) -> Result<usize, Error> {
+        let mut failed = 0;
+        for index in (0..self.moves.len()).rev() {
+            let Entry { from, to, done } = &self.moves[index];
+            if !done {
+                continue;
+            }
+            if let Err(err) = move_archive(to, from, false) {
+                err.log();
+                failed += 1;
+                continue;
+            }
+            self.moves[index].done = false;
+            self.save(xdg_dirs)?;
+            let Entry { from, to, .. } = &self.moves[index];
+            if !move_state(catalogue, to, from) {
+                failed += 1;
+            }
+            moved(to, from)?;
+        }
+        Ok(failed)
+    }
+}
+
+/// Move archive, creating the directory it is moved to and removing the directory it is
+/// moved from if left empty.
+///
+/// # Errors
+/// If the archive cannot be moved.
+fn move_archive(from: &Path, to: &Path, overwrite: bool) -> Result<(), Error> {
+    if let Some(dir) = to.parent() {
+        fs::create_dir_all(dir).map_err(|source| Error::Move {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+            source,
+        })?;
+    }
+    inbox::move_file(from, to, overwrite)?;
+    if let Some(dir) = from.parent() {
+        // Fails for directories that are not empty, which are kept.
+        _ = fs::remove_dir(dir);
+    }
+    Ok(())
+}
+
+/// Move catalogue state of an archive moved from one path to another, returning false
+/// and logging why if it could not be moved.
+fn move_state(catalogue: &Catalogue, from: &Path, to: &Path) -> bool {
+    catalogue
+        .rename(from, to)
+        .inspect_err(|err| {
+            err.log();
+            ::log::warn!("{from:?} was moved to {to:?}, but its catalogue state was not");
+        })
+        .is_ok()
+}
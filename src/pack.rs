@@ -0,0 +1,121 @@
+//! [Pack] impl, packing a directory of loose images into a zip archive.
+
+use ::std::{path::Path, sync::Arc};
+
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message, ViewPath,
+    archive::{ComicField, ComicInfo},
+    i18n::{tr, tr_args},
+    list_entry_name, metadata_editor, series,
+};
+
+/// Dialog packing a directory of images into a zip archive, shown over a pane.
+#[derive(Debug, Clone)]
+pub struct Pack {
+    /// Directory being packed.
+    pub dir: Arc<Path>,
+    /// Metadata written as `ComicInfo.xml`.
+    pub info: ComicInfo,
+    /// `ComicInfo.xml` is written.
+    pub write_info: bool,
+    /// Directory is moved to trash once packed.
+    pub trash_dir: bool,
+}
+
+impl Pack {
+    /// Create a dialog packing a directory, with metadata guessed from its name.
+    pub fn new(dir: Arc<Path>) -> Self {
+        let name = dir.file_name().unwrap_or_default().to_string_lossy();
+        let info = ComicInfo {
+            title: name.clone().into_owned(),
+            series: series::series_name(&name),
+            number: series::volume(&name)
+                .map(series::volume_text)
+                .unwrap_or_default(),
+            ..ComicInfo::default()
+        };
+        Self {
+            dir,
+            info,
+            write_info: true,
+            trash_dir: false,
+        }
+    }
+
+    /// Set a field of the `ComicInfo.xml` form.
+    pub fn set(&mut self, field: ComicField, text: String) {
+        *self.info.get_mut(field) = text;
+    }
+
+    /// View dialog.
+    pub fn view<'a>(&self, view_path: ViewPath) -> Element<'a, Message> {
+        let field =
+            |field: ComicField| {
+                let label = metadata_editor::field_label(field);
+                widget::Row::new()
+                    .spacing(5)
+                    .align_y(Center)
+                    .push(widget::text(label.clone()).width(60))
+                    .push(
+                        widget::text_input(&label, self.info.get(field))
+                            .on_input_maybe(self.write_info.then_some(move |text| {
+                                Message::SetPackField(view_path, field, text)
+                            }))
+                            .on_submit(Message::ConfirmPack(view_path))
+                            .padding(3)
+                            .width(Fill),
+                    )
+            };
+        widget::Column::new()
+            .spacing(5)
+            .push(
+                widget::text(tr_args(
+                    "dialog-pack",
+                    &[("name", &list_entry_name(&self.dir))],
+                ))
+                .wrapping(Wrapping::WordOrGlyph),
+            )
+            .push(
+                widget::checkbox(self.write_info)
+                    .label(tr("dialog-pack-write-info"))
+                    .on_toggle(move |write| Message::SetPackInfo(view_path, write)),
+            )
+            .extend(ComicField::ALL.map(field).map(Element::from))
+            .push(
+                widget::checkbox(self.trash_dir)
+                    .label(tr("dialog-pack-trash"))
+                    .on_toggle(move |trash| Message::SetPackTrash(view_path, trash)),
+            )
+            .push(
+                widget::Row::new()
+                    .spacing(3)
+                    .push(
+                        widget::button(widget::text(tr("button-pack")))
+                            .padding(3)
+                            .on_press(Message::ConfirmPack(view_path)),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("button-cancel")))
+                            .padding(3)
+                            .style(widget::button::secondary)
+                            .on_press(Message::CancelPack(view_path)),
+                    ),
+            )
+            .pipe(widget::container)
+            .style(widget::container::bordered_box)
+            .padding(10)
+            .max_width(400)
+            .pipe(widget::opaque)
+            .pipe(widget::container)
+            .center(Fill)
+            .into()
+    }
+}
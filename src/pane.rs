@@ -1,24 +1,1279 @@
 //! [Pane] impl.
 
+use ::core::{fmt::Display, ops::Range, time::Duration};
 use ::std::{
     borrow::Cow,
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
+    fs,
     path::Path,
     sync::{Arc, LazyLock},
+    time::{Instant, SystemTime},
 };
 
 use ::derive_more::IsVariant;
 use ::iced::{
-    Element,
+    Alignment::Center,
+    Border, Color, Element,
     Length::Fill,
-    Padding,
+    Padding, Size,
+    keyboard::Modifiers,
     widget::{self, text::Wrapping},
 };
 use ::tap::Pipe;
 
-use crate::{Message, shorten_text};
+use crate::{
+    CardBadge, ItemPath, Message, SizeUnits, ViewPath,
+    archive::{self, ArchiveKind, Metadata},
+    cache::{Shown, ThumbnailCache},
+    catalogue::{ItemState, Label, ReadState, Wish},
+    chooser::Chooser,
+    clipboard::CopyKind,
+    format,
+    i18n::{tr, tr_args},
+    isbn::Isbn,
+    list_entry_name,
+    list_view::{self, ListView},
+    listing::{Sort, ViewMode},
+    opds::{self, Link},
+    open_with::Application,
+    pack::Pack,
+    quick_look::QuickLook,
+    rename::Rename,
+    series,
+    text::{Ellipsis, index_letter, natural_cmp, shorten_text},
+    worker::CancelToken,
+};
+
+/// Opacity of the dominant color of a cover tinting the background of its card.
+const ACCENT_TINT_ALPHA: f32 = 0.15;
+
+/// Opacity of the dominant color of a cover used as the border of its card.
+const ACCENT_BORDER_ALPHA: f32 = 0.6;
+
+/// Height of headers of sections of a grid.
+const SECTION_HEADER_HEIGHT: f32 = 24.0;
+
+/// Width of the letter index beside a grid.
+const LETTER_INDEX_WIDTH: f32 = 20.0;
+
+/// Spacing between cards in grid.
+const GRID_SPACING: f32 = 3.0;
+
+/// Amount of rows outside of the visible area for which cards are created.
+const ROW_MARGIN: usize = 2;
+
+/// Height of series title above each row of a shelf.
+const SHELF_TITLE_HEIGHT: f32 = 24.0;
+
+/// Viewport size assumed for views which have not yet been scrolled.
+const ASSUMED_VIEWPORT: Size = Size::new(1280.0, 1024.0);
+
+/// Max amount of thumbnails loading at the same time for a single view.
+const MAX_LOADING: usize = 8;
+
+/// Width of item context menu.
+pub const CONTEXT_MENU_WIDTH: f32 = 150.0;
+
+/// Height reserved for each entry of item context menu when positioning it.
+pub const CONTEXT_MENU_ENTRY_HEIGHT: f32 = 33.0;
+
+/// Max width of recently opened list of empty views.
+const RECENT_WIDTH: f32 = 300.0;
+
+/// Max length of open with entries of context menu, such that they fit on one line.
+const OPEN_WITH_TEXT_LEN: usize = 18;
+
+/// Max amount of thumbnails loading at the same time for a single view when throttled.
+const MAX_LOADING_THROTTLED: usize = 1;
+
+/// Time after the last typed character at which type-ahead starts over.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Layout of cards in a grid.
+#[derive(Debug, Clone, Copy)]
+struct GridLayout {
+    /// Amount of columns.
+    columns: usize,
+    /// Width of grid.
+    width: f32,
+    /// Height of a row including spacing.
+    row_height: f32,
+    /// Amount of rows.
+    rows: usize,
+}
+
+impl GridLayout {
+    /// Compute layout of item_count cards of given width in an area of given width.
+    fn new(item_count: usize, area_width: f32, icon_width: f32) -> Self {
+        let max_columns = (area_width.div_euclid(icon_width) as usize).max(1);
+        let columns = item_count.clamp(1, max_columns);
+        let width = if item_count < max_columns {
+            (item_count as f32 + 1.0) * icon_width
+        } else {
+            area_width
+        };
+
+        // Cells are square, so rows are as tall as cells are wide.
+        let row_height =
+            (width - GRID_SPACING * (columns - 1) as f32) / columns as f32 + GRID_SPACING;
+
+        Self {
+            columns,
+            width,
+            row_height,
+            rows: item_count.div_ceil(columns),
+        }
+    }
+
+    /// Get range of rows cards should be created for.
+    fn visible_rows(&self, scroll_offset: f32, height: f32) -> Range<usize> {
+        let first = ((scroll_offset / self.row_height) as usize)
+            .saturating_sub(ROW_MARGIN)
+            .min(self.rows);
+        let last = (((scroll_offset + height) / self.row_height).ceil() as usize + ROW_MARGIN)
+            .clamp(first, self.rows);
+        first..last
+    }
+
+    /// Get range of items cards should be created for.
+    fn visible_items(&self, scroll_offset: f32, height: f32) -> Range<usize> {
+        let rows = self.visible_rows(scroll_offset, height);
+        rows.start * self.columns..rows.end * self.columns
+    }
+}
+
+/// Layout of cards in a grid split into sections by the index letter of titles, each
+/// section starting on a new row below its header.
+#[derive(Debug, Clone)]
+struct SectionLayout {
+    /// Range of shown items of each row, with the letter of its section if it is the
+    /// first row of one.
+    rows: Vec<(Range<usize>, Option<char>)>,
+    /// Vertical offset of each row, including its header.
+    offsets: Vec<f32>,
+    /// Height of a row of cards including spacing.
+    row_height: f32,
+    /// Height of all rows.
+    height: f32,
+}
+
+impl SectionLayout {
+    /// Compute layout of cards with given index letters in a grid of given layout.
+    fn new(letters: impl IntoIterator<Item = char>, layout: &GridLayout) -> Self {
+        let mut rows = Vec::<(Range<usize>, Option<char>)>::new();
+        let mut offsets = Vec::new();
+        let mut height = 0.0;
+        let mut current = None;
+        for (index, letter) in letters.into_iter().enumerate() {
+            match rows.last_mut() {
+                Some((range, _)) if current == Some(letter) && range.len() < layout.columns => {
+                    range.end = index + 1;
+                }
+                _ => {
+                    let header = (current != Some(letter)).then_some(letter);
+                    offsets.push(height);
+                    height += layout.row_height + header.map_or(0.0, |_| SECTION_HEADER_HEIGHT);
+                    rows.push((index..index + 1, header));
+                    current = Some(letter);
+                }
+            }
+        }
+        Self {
+            rows,
+            offsets,
+            row_height: layout.row_height,
+            height,
+        }
+    }
+
+    /// Get height of a row, including its header.
+    fn row_height(&self, row: usize) -> f32 {
+        match self.rows.get(row) {
+            Some((_, Some(_))) => self.row_height + SECTION_HEADER_HEIGHT,
+            _ => self.row_height,
+        }
+    }
+
+    /// Get range of rows cards should be created for.
+    fn visible_rows(&self, scroll_offset: f32, height: f32) -> Range<usize> {
+        let first = self
+            .offsets
+            .partition_point(|offset| {
+                *offset + self.row_height + SECTION_HEADER_HEIGHT < scroll_offset
+            })
+            .saturating_sub(ROW_MARGIN);
+        let last = (self
+            .offsets
+            .partition_point(|offset| *offset < scroll_offset + height)
+            + ROW_MARGIN)
+            .clamp(first, self.rows.len());
+        first..last
+    }
+
+    /// Get range of items cards should be created for.
+    fn visible_items(&self, scroll_offset: f32, height: f32) -> Range<usize> {
+        let rows = self.visible_rows(scroll_offset, height);
+        let start = self
+            .rows
+            .get(rows.start)
+            .map_or(0, |(range, _)| range.start);
+        let end = rows
+            .end
+            .checked_sub(1)
+            .and_then(|last| self.rows.get(last))
+            .map_or(start, |(range, _)| range.end);
+        start..end.max(start)
+    }
+
+    /// Get index of row containing shown item at index.
+    fn row_of(&self, index: usize) -> usize {
+        self.rows.partition_point(|(range, _)| range.end <= index)
+    }
+}
+
+/// Get range of a sequence of cells that cards should be created for, given the scroll
+/// offset and visible length along it and the size of a cell including spacing.
+pub fn visible_range(offset: f32, length: f32, cell: f32, count: usize) -> Range<usize> {
+    let first = ((offset / cell) as usize)
+        .saturating_sub(ROW_MARGIN)
+        .min(count);
+    let last = (((offset + length) / cell).ceil() as usize + ROW_MARGIN).clamp(first, count);
+    first..last
+}
+
+/// View placeholder cards filling a pane while its directory is scanned and no items
+/// have been found yet.
+fn skeleton<'a>(icon_width: f32) -> widget::Container<'a, Message> {
+    widget::responsive(move |size| {
+        let cell = icon_width + GRID_SPACING;
+        let columns = (size.width.div_euclid(cell) as usize).max(1);
+        let rows = (size.height / cell).ceil() as usize;
+        widget::Column::new()
+            .spacing(GRID_SPACING)
+            .extend((0..rows).map(|_| {
+                widget::Row::new()
+                    .spacing(GRID_SPACING)
+                    .extend((0..columns).map(|_| {
+                        widget::container(widget::space())
+                            .width(icon_width)
+                            .height(icon_width)
+                            .style(|theme: &::iced::Theme| widget::container::Style {
+                                background: Some(
+                                    theme.extended_palette().background.weak.color.into(),
+                                ),
+                                border: ::iced::border::rounded(4),
+                                ..widget::container::Style::default()
+                            })
+                            .into()
+                    }))
+                    .into()
+            }))
+            .pipe(widget::container)
+            .center_x(Fill)
+            .height(Fill)
+            .clip(true)
+            .into()
+    })
+    .pipe(widget::container)
+}
+
+/// Get height of a shelf row, including its title and spacing.
+const fn shelf_row_height(icon_width: f32) -> f32 {
+    icon_width + SHELF_TITLE_HEIGHT + GRID_SPACING
+}
+
+/// Get color of label.
+const fn label_color(label: Label) -> ::iced::Color {
+    match label {
+        Label::Red => ::iced::color!(0xe5484d),
+        Label::Orange => ::iced::color!(0xf76b15),
+        Label::Yellow => ::iced::color!(0xffc53d),
+        Label::Green => ::iced::color!(0x30a46c),
+        Label::Blue => ::iced::color!(0x0090ff),
+        Label::Purple => ::iced::color!(0x8e4ec6),
+    }
+}
+
+/// View a color swatch of a label.
+fn label_dot<'a>(label: Label, size: f32) -> Element<'a, Message> {
+    widget::space()
+        .width(size)
+        .height(size)
+        .pipe(widget::container)
+        .style(move |_theme: &::iced::Theme| widget::container::Style {
+            background: Some(label_color(label).into()),
+            border: Border {
+                radius: (size / 2.0).into(),
+                ..Border::default()
+            },
+            ..widget::container::Style::default()
+        })
+        .into()
+}
+
+/// View new mark, star and label of an item, selected cards show star and label as
+/// controls even if unset.
+fn card_marks<'a>(item_path: &ItemPath, item: &Item, is_selected: bool) -> Element<'a, Message> {
+    let state = item.state;
+    let mut row = widget::Row::new().spacing(3).align_y(Center);
+    if item.new {
+        row = row.push(
+            widget::text(tr("pane-new"))
+                .size(10)
+                .pipe(widget::container)
+                .padding([0, 3])
+                .style(widget::container::primary),
+        );
+    }
+    match state.read_state {
+        ReadState::Unread => {}
+        ReadState::Reading => row = row.push(widget::text("\u{25d0}").style(widget::text::primary)),
+        ReadState::Finished => {
+            row = row.push(widget::text("\u{2713}").style(widget::text::success))
+        }
+    }
+    if state.starred || is_selected {
+        row = row.push(
+            widget::button(widget::text(if state.starred {
+                "\u{2605}"
+            } else {
+                "\u{2606}"
+            }))
+            .padding([0, 4])
+            .style(widget::button::text)
+            .on_press_maybe(is_selected.then(|| Message::ToggleStarred(item_path.clone()))),
+        );
+    }
+    if let Some(label) = state.label {
+        row = row.push(
+            widget::button(label_dot(label, 10.0))
+                .padding(3)
+                .style(widget::button::text)
+                .on_press_maybe(is_selected.then(|| Message::CycleLabel(item_path.clone()))),
+        );
+    } else if is_selected {
+        row = row.push(
+            widget::button(widget::text("\u{25cb}"))
+                .padding([0, 4])
+                .style(widget::button::text)
+                .on_press(Message::CycleLabel(item_path.clone())),
+        );
+    }
+    row.pipe(widget::container).padding(3).into()
+}
+
+/// View badges of an item in the top right corner of its card, [None] if it has none.
+fn card_badges<'a>(path: &Path, item: &Item, badges: &[CardBadge]) -> Option<Element<'a, Message>> {
+    let badge = |text: String, style: fn(&::iced::Theme) -> widget::container::Style| {
+        widget::text(text)
+            .size(12)
+            .pipe(widget::container)
+            .style(style)
+            .padding([0, 3])
+            .into()
+    };
+    // Unreachable items are marked whatever badges are shown.
+    let offline = item
+        .offline
+        .then(|| badge(tr("pane-offline"), widget::container::danger));
+    let Some(kind) = ArchiveKind::from_path(path) else {
+        return offline.map(|offline| {
+            widget::container(offline)
+                .padding(3)
+                .align_right(Fill)
+                .into()
+        });
+    };
+    let row = widget::Row::new().spacing(3).push(offline).extend(
+        CardBadge::ALL
+            .into_iter()
+            .filter(|badge| badges.contains(badge))
+            .filter_map(|shown| match shown {
+                CardBadge::Unread => item
+                    .state
+                    .read_state
+                    .is_unread()
+                    .then(|| badge(tr("pane-new-badge"), widget::container::primary)),
+                CardBadge::Format => Some(badge(kind.to_string(), widget::container::bordered_box)),
+                CardBadge::PageCount => item.page_count.map(|page_count| {
+                    badge(
+                        tr_args("pane-page-count", &[("count", &page_count.to_string())]),
+                        widget::container::bordered_box,
+                    )
+                }),
+            }),
+    );
+    row.pipe(widget::container)
+        .padding(3)
+        .align_right(Fill)
+        .pipe(Element::from)
+        .pipe(Some)
+}
 
-/// A Single main window pain.
+/// Cover shown for items without a thumbnail.
+pub static PLACEHOLDER: LazyLock<widget::svg::Handle> = LazyLock::new(|| {
+    include_bytes!("./question.svg")
+        .as_slice()
+        .pipe(Cow::Borrowed)
+        .pipe(widget::svg::Handle::from_memory)
+});
+
+/// View a single item card, tinted by the dominant color of its thumbnail if given.
+fn card<'a>(
+    item_path: ItemPath,
+    item: &'a Item,
+    (thumbnail, accent): (Option<&'a widget::image::Handle>, Option<Color>),
+    (is_selected, is_cursor): (bool, bool),
+    max_text_len: u16,
+    badges: &[CardBadge],
+) -> Element<'a, Message> {
+    if let Some(handle) = thumbnail {
+        widget::Stack::new().push(widget::image(handle).width(Fill).height(Fill))
+    } else {
+        widget::Stack::new().push(widget::svg(PLACEHOLDER.clone()).width(Fill).height(Fill))
+    }
+    .push(
+        widget::text(shorten_text(
+            item.title(),
+            max_text_len.into(),
+            Ellipsis::Middle,
+        ))
+        .wrapping(Wrapping::None)
+        .pipe(widget::container)
+        .style(widget::container::bordered_box)
+        .center_x(Fill)
+        .padding(3)
+        .pipe(widget::container)
+        .padding(Padding {
+            left: 5.0,
+            right: 5.0,
+            ..Padding::new(0.0)
+        })
+        .center_x(Fill)
+        .align_bottom(Fill),
+    )
+    .push(card_marks(&item_path, item, is_selected))
+    .extend(card_badges(&item_path.path, item, badges))
+    .pipe(widget::container)
+    .padding(2)
+    .style(move |theme: &::iced::Theme| {
+        let (color, width) = match (is_selected, is_cursor, accent) {
+            (true, true, _) => (theme.extended_palette().primary.strong.color, 3.0),
+            (true, false, _) => (theme.palette().primary, 2.0),
+            (false, true, _) => (theme.extended_palette().background.strong.color, 3.0),
+            (false, false, Some(accent)) => (accent.scale_alpha(ACCENT_BORDER_ALPHA), 1.0),
+            (false, false, None) => return widget::container::transparent(theme),
+        };
+        widget::container::Style {
+            border: Border {
+                color,
+                width,
+                radius: 3.0.into(),
+            },
+            background: accent.map(|accent| accent.scale_alpha(ACCENT_TINT_ALPHA).into()),
+            ..widget::container::transparent(theme)
+        }
+    })
+    .pipe(widget::mouse_area)
+    .on_press(Message::ItemPressed(item_path.clone()))
+    .on_right_press(Message::ItemContextMenu(item_path.clone()))
+    .on_enter(Message::CardHovered(item_path.clone()))
+    .on_exit(Message::CardUnhovered(item_path.clone()))
+    .pipe(|area| {
+        // Directories are left to the middle button binding, such as opening a pane.
+        if ArchiveKind::from_path(&item_path.path).is_some() {
+            area.on_middle_press(Message::ItemMiddleClicked(item_path.clone()))
+        } else {
+            area
+        }
+    })
+    .on_double_click(Message::ItemDoubleClicked(item_path))
+    .into()
+}
+
+/// Context menu of an item in a view.
+#[derive(Debug, Clone, Copy)]
+pub struct ContextMenu<'a> {
+    /// Item menu is shown for.
+    pub path: &'a Arc<Path>,
+    /// Reading list selected items may be added to, if any.
+    pub target_list: Option<&'a str>,
+    /// View shows a reading list, which selected items may be removed from.
+    pub in_list: bool,
+    /// External applications item may be opened with.
+    pub open_with: &'a [Application],
+    /// Item is a directory, which may be packed into an archive.
+    pub is_dir: bool,
+    /// Item has a cover chosen for it, which may be reset to its first page.
+    pub has_cover: bool,
+}
+
+impl ContextMenu<'_> {
+    /// Get amount of entries of menu.
+    pub fn entries(&self, reading_order: bool) -> usize {
+        4 + 2 * usize::from(reading_order)
+            + (7 + usize::from(cfg!(feature = "dbus")))
+                * usize::from(ArchiveKind::from_path(self.path).is_some())
+            + usize::from(self.has_cover)
+            + usize::from(archive::is_convertible(self.path))
+            + 2 * usize::from(archive::is_extractable(self.path))
+            + usize::from(self.is_dir)
+            + usize::from(ArchiveKind::from_path(self.path) == Some(ArchiveKind::Cbz))
+            + usize::from(self.target_list.is_some())
+            + usize::from(self.in_list)
+            + self.open_with.len()
+    }
+}
+
+/// View context menu of selected items in a view, views in reading order may also move
+/// the item the menu is shown for.
+pub fn item_menu<'a>(
+    view_path: ViewPath,
+    menu: ContextMenu<'a>,
+    reading_order: bool,
+) -> Element<'a, Message> {
+    let entry = |label: widget::Text<'a>, message| {
+        widget::button(label)
+            .padding([2, 6])
+            .width(Fill)
+            .style(widget::button::text)
+            .on_press(message)
+    };
+    widget::Column::new()
+        .push(entry(
+            widget::text(tr("menu-mark-read")),
+            Message::SetReadState(view_path, ReadState::Finished),
+        ))
+        .push(entry(
+            widget::text(tr("menu-mark-unread")),
+            Message::SetReadState(view_path, ReadState::Unread),
+        ))
+        .push(entry(
+            widget::text(tr("menu-copy-path")),
+            Message::CopySelection(view_path, CopyKind::Paths),
+        ))
+        .push(entry(
+            widget::text(tr("menu-copy-name")),
+            Message::CopySelection(view_path, CopyKind::Names),
+        ))
+        .push(ArchiveKind::from_path(menu.path).map(|_| {
+            entry(
+                widget::text(tr("menu-copy-cover")),
+                Message::CopySelection(view_path, CopyKind::Cover),
+            )
+        }))
+        .push(reading_order.then(|| {
+            entry(
+                widget::text(tr("menu-move-earlier")),
+                Message::MoveInReadingOrder(view_path, -1),
+            )
+        }))
+        .push(reading_order.then(|| {
+            entry(
+                widget::text(tr("menu-move-later")),
+                Message::MoveInReadingOrder(view_path, 1),
+            )
+        }))
+        .push(ArchiveKind::from_path(menu.path).map(|_| {
+            entry(
+                widget::text(tr("menu-rename")),
+                Message::Rename(ItemPath {
+                    view_path,
+                    path: Arc::clone(menu.path),
+                }),
+            )
+        }))
+        .push(ArchiveKind::from_path(menu.path).map(|_| {
+            entry(
+                widget::text(tr("menu-move-to")),
+                Message::MoveTo(ItemPath {
+                    view_path,
+                    path: Arc::clone(menu.path),
+                }),
+            )
+        }))
+        .push(archive::is_extractable(menu.path).then(|| {
+            entry(
+                widget::text(tr("menu-extract-here")),
+                Message::ExtractHere(view_path),
+            )
+        }))
+        .push(archive::is_extractable(menu.path).then(|| {
+            entry(
+                widget::text(tr("menu-extract-to")),
+                Message::ExtractTo(view_path),
+            )
+        }))
+        .push(menu.is_dir.then(|| {
+            entry(
+                widget::text(tr("menu-pack")),
+                Message::PackDir(ItemPath {
+                    view_path,
+                    path: Arc::clone(menu.path),
+                }),
+            )
+        }))
+        .push(
+            ArchiveKind::from_path(menu.path)
+                .filter(|kind| kind.is_cbz())
+                .map(|_| {
+                    entry(
+                        widget::text(tr("menu-edit-metadata")),
+                        Message::EditMetadata(view_path),
+                    )
+                }),
+        )
+        .push(ArchiveKind::from_path(menu.path).map(|_| {
+            entry(
+                widget::text(tr("menu-move-to-trash")),
+                Message::MoveToTrash(view_path),
+            )
+        }))
+        .push(archive::is_convertible(menu.path).then(|| {
+            entry(
+                widget::text(tr("menu-convert")),
+                Message::ConvertToCbz(view_path),
+            )
+        }))
+        .push(ArchiveKind::from_path(menu.path).map(|_| {
+            entry(
+                widget::text(tr("menu-verify")),
+                Message::VerifyArchives(view_path),
+            )
+        }))
+        .push(ArchiveKind::from_path(menu.path).map(|_| {
+            entry(
+                widget::text(tr("menu-write-thumbnails")),
+                Message::WriteThumbnails(view_path),
+            )
+        }))
+        .push(menu.target_list.map(|list| {
+            entry(
+                widget::text(tr_args("menu-add-to-list", &[("list", list)])),
+                Message::AddToReadingList(view_path),
+            )
+        }))
+        .push(menu.in_list.then(|| {
+            entry(
+                widget::text(tr("menu-remove-from-list")),
+                Message::RemoveFromReadingList(view_path),
+            )
+        }))
+        .push(
+            ArchiveKind::from_path(menu.path)
+                .filter(|_| cfg!(feature = "dbus"))
+                .map(|_| {
+                    entry(
+                        widget::text(tr("menu-choose-cover")),
+                        Message::PickCover(ItemPath {
+                            view_path,
+                            path: Arc::clone(menu.path),
+                        }),
+                    )
+                }),
+        )
+        .push(menu.has_cover.then(|| {
+            entry(
+                widget::text(tr("menu-reset-cover")),
+                Message::ResetCover(ItemPath {
+                    view_path,
+                    path: Arc::clone(menu.path),
+                }),
+            )
+        }))
+        .push(ArchiveKind::from_path(menu.path).map(|_| {
+            entry(
+                widget::text(tr("menu-open-with")),
+                Message::ShowChooser(ItemPath {
+                    view_path,
+                    path: Arc::clone(menu.path),
+                }),
+            )
+        }))
+        .extend(menu.open_with.iter().map(|application| {
+            entry(
+                widget::text(
+                    shorten_text(
+                        &tr_args(
+                            "menu-open-with-application",
+                            &[("application", &application.name)],
+                        ),
+                        OPEN_WITH_TEXT_LEN,
+                        Ellipsis::End,
+                    )
+                    .into_owned(),
+                )
+                .wrapping(Wrapping::None),
+                Message::OpenWith(view_path, application.clone()),
+            )
+            .into()
+        }))
+        .pipe(widget::container)
+        .padding(3)
+        .width(CONTEXT_MENU_WIDTH)
+        .style(widget::container::bordered_box)
+        .pipe(widget::opaque)
+}
+
+/// Choice of reading list picker of a pane header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListChoice {
+    /// Show an existing reading list.
+    List(String),
+    /// Create a new empty reading list and show it.
+    New,
+}
+
+impl Display for ListChoice {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            ListChoice::List(name) => f.write_str(name),
+            ListChoice::New => f.write_str(&tr("pane-new-list")),
+        }
+    }
+}
+
+/// Quick filter chip of a pane header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chip {
+    /// Show items in a read state.
+    Read(ReadState),
+    /// Show starred items.
+    Starred,
+    /// Show items with a label.
+    Label(Label),
+    /// Show archives added since the directory was last viewed.
+    New,
+}
+
+/// Filter of items shown by a pane.
+///
+/// Items are shown if they match the text and every chip group, where a group matches
+/// if any of its chips match or none of them are active.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    /// Text titles should contain.
+    text: String,
+    /// Lowercase text, used for case insensitive matching.
+    lowercase: String,
+    /// Read states of shown items.
+    read_states: BTreeSet<ReadState>,
+    /// Only starred items are shown.
+    starred: bool,
+    /// Labels of shown items.
+    labels: BTreeSet<Label>,
+    /// Only archives added since the directory was last viewed are shown.
+    new: bool,
+    /// Book number text is, such that a scanned barcode shows the matching item.
+    isbn: Option<Isbn>,
+}
+
+impl Filter {
+    /// Get text titles should contain.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Set text titles should contain.
+    pub fn set_text(&mut self, text: String) {
+        self.lowercase = text.to_lowercase();
+        self.isbn = Isbn::parse(&text);
+        self.text = text;
+    }
+
+    /// Check if filter shows all items.
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+            && self.read_states.is_empty()
+            && !self.starred
+            && self.labels.is_empty()
+            && !self.new
+    }
+
+    /// Check if a chip is active.
+    pub fn is_active(&self, chip: Chip) -> bool {
+        match chip {
+            Chip::Read(read_state) => self.read_states.contains(&read_state),
+            Chip::Starred => self.starred,
+            Chip::Label(label) => self.labels.contains(&label),
+            Chip::New => self.new,
+        }
+    }
+
+    /// Toggle a chip.
+    pub fn toggle(&mut self, chip: Chip) {
+        /// Insert value if missing, otherwise remove it.
+        fn toggle<T: Ord>(set: &mut BTreeSet<T>, value: T) {
+            if !set.remove(&value) {
+                set.insert(value);
+            }
+        }
+        match chip {
+            Chip::Read(read_state) => toggle(&mut self.read_states, read_state),
+            Chip::Starred => self.starred = !self.starred,
+            Chip::Label(label) => toggle(&mut self.labels, label),
+            Chip::New => self.new = !self.new,
+        }
+    }
+
+    /// Check if an item is shown by filter.
+    pub fn matches(&self, item: &Item) -> bool {
+        let ItemState {
+            read_state,
+            starred,
+            label,
+            ..
+        } = item.state;
+        (self.lowercase.is_empty()
+            || item.title().to_lowercase().contains(&self.lowercase)
+            || self.isbn.is_some() && item.metadata.isbn == self.isbn)
+            && (self.read_states.is_empty() || self.read_states.contains(&read_state))
+            && (!self.starred || starred)
+            && (self.labels.is_empty() || label.is_some_and(|label| self.labels.contains(&label)))
+            && (!self.new || item.new)
+    }
+}
+
+/// A single main window pane.
+#[derive(Debug, Clone, Default)]
+pub struct Pane {
+    /// Directory view of pane.
+    pub view: DirView,
+    /// Filter of shown items.
+    pub filter: Filter,
+    /// Item context menu is shown for.
+    pub context_menu: Option<Arc<Path>>,
+    /// External applications item context menu is shown for may be opened with.
+    pub open_with: Vec<Application>,
+    /// Item context menu is shown for has a cover chosen for it.
+    pub has_cover: bool,
+    /// Items are shown in the reading order of the series in the directory.
+    pub reading_order: bool,
+    /// Archives of the directory and its subdirectories are shown as a shelf, in rows
+    /// grouped by series.
+    pub shelf: bool,
+    /// How items of pane are presented, shelves are shown as such in any mode.
+    pub mode: ViewMode,
+    /// Order of items in list and detail modes, if sorted by a column.
+    pub sort: Option<Sort>,
+    /// Reading list displayed by pane instead of its directory.
+    pub list: Option<String>,
+    /// OPDS feed displayed by pane instead of its directory.
+    pub feed: Option<FeedView>,
+    /// Tag whose archives are displayed by pane instead of its directory.
+    pub tag: Option<String>,
+    /// Quick look shown over pane.
+    pub quick_look: Option<QuickLook>,
+    /// Directory displayed by pane.
+    pub path: Option<Arc<Path>>,
+    /// Directory of pane is being scanned.
+    pub scanning: bool,
+    /// Time scan of directory of pane started, if it is being scanned.
+    pub scan_started: Option<Instant>,
+    /// Time directory of pane was last viewed before it was opened, archives first seen
+    /// later are new.
+    pub last_visit: Option<i64>,
+    /// Previously displayed directories.
+    back: Vec<Arc<Path>>,
+    /// Directories navigated back from.
+    forward: Vec<Arc<Path>>,
+    /// Token cancelling scans and loads for the current view.
+    loads: CancelToken,
+    /// Characters typed to jump to an item.
+    pub type_ahead: TypeAhead,
+    /// Dialog renaming or moving an archive, shown over pane.
+    pub rename: Option<Rename>,
+    /// Dialog packing a directory into an archive, shown over pane.
+    pub pack: Option<Pack>,
+    /// Dialog choosing the application an archive is opened with, shown over pane.
+    pub chooser: Option<Chooser>,
+}
+
+/// OPDS feed shown by a pane, with the feeds navigated from to reach it.
+#[derive(Debug, Clone, Default)]
+pub struct FeedView {
+    /// Url of feed.
+    pub url: String,
+    /// Title of feed.
+    pub title: String,
+    /// What items of feed lead to, by the paths they are shown as.
+    pub links: BTreeMap<Arc<Path>, Link>,
+    /// Remote covers of items of feed, by the paths they are shown as.
+    pub covers: BTreeMap<Arc<Path>, String>,
+    /// Urls of feeds navigated from, the most recent last.
+    pub back: Vec<String>,
+}
+
+impl FeedView {
+    /// Create a view of a loaded feed along with its items, archives are shown as paths
+    /// in the download cache at dir.
+    pub fn new(feed: opds::Feed, dir: &Path, back: Vec<String>) -> (Self, Vec<(Arc<Path>, Item)>) {
+        let mut links = BTreeMap::new();
+        let mut covers = BTreeMap::new();
+        let items = feed
+            .entries
+            .into_iter()
+            .filter_map(|entry| {
+                let path = Arc::<Path>::from(entry.path(dir));
+                // Entries listed more than once are shown once.
+                if links.contains_key(&path) {
+                    return None;
+                }
+                let file = fs::metadata(&path).ok();
+                let mut item = Item::new(entry.title, &path, file.as_ref());
+                item.metadata.author = entry.author;
+                // Archives which are not downloaded show their remote cover, if any.
+                if let Some(cover) = entry.cover {
+                    covers.insert(Arc::clone(&path), cover);
+                    item.thumbnail = Thumbnail::Pending;
+                } else if file.is_none() {
+                    item.thumbnail = Thumbnail::Unavailable;
+                }
+                links.insert(Arc::clone(&path), entry.link);
+                Some((path, item))
+            })
+            .collect();
+        let view = Self {
+            url: feed.url,
+            title: feed.title,
+            links,
+            covers,
+            back,
+        };
+        (view, items)
+    }
+}
+
+/// Characters typed in quick succession, used to jump to the first item whose title
+/// starts with them.
+#[derive(Debug, Clone, Default)]
+pub struct TypeAhead {
+    /// Typed characters.
+    text: String,
+    /// When the last character was typed.
+    typed_at: Option<Instant>,
+}
+
+impl TypeAhead {
+    /// Check if a character was typed recently enough for the next one to be appended.
+    pub fn is_active(&self, now: Instant) -> bool {
+        self.typed_at
+            .is_some_and(|typed_at| now.duration_since(typed_at) < TYPE_AHEAD_TIMEOUT)
+    }
+
+    /// Append typed characters, starting over if the timeout has passed, returning all
+    /// characters typed in succession.
+    pub fn push(&mut self, text: &str, now: Instant) -> &str {
+        if !self.is_active(now) {
+            self.text.clear();
+        }
+        self.text.push_str(text);
+        self.typed_at = Some(now);
+        &self.text
+    }
+}
+
+/// Movement of the keyboard cursor of a view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    /// Previous item of row, or last item of previous row.
+    Left,
+    /// Next item of row, or first item of next row.
+    Right,
+    /// Item of previous row.
+    Up,
+    /// Item of next row.
+    Down,
+    /// Item one page of rows up.
+    PageUp,
+    /// Item one page of rows down.
+    PageDown,
+    /// First item.
+    Home,
+    /// Last item.
+    End,
+}
+
+/// Item the keyboard cursor moved to, with the scroll offsets keeping it visible.
+#[derive(Debug, Clone)]
+pub struct CursorTarget {
+    /// Item cursor is on.
+    pub path: Arc<Path>,
+    /// Vertical scroll offset of view, if it has to scroll.
+    pub offset: Option<f32>,
+    /// Shelf row of item and its horizontal scroll offset, if it has to scroll.
+    pub shelf_offset: Option<(usize, f32)>,
+}
+
+/// Items shown by a view, arranged in the rows the keyboard cursor moves over.
+struct CursorRows {
+    /// Shown items of each row.
+    rows: Vec<Vec<Arc<Path>>>,
+    /// Index of each row among the rows of the shelf, if shown as a shelf.
+    shelf_rows: Option<Vec<usize>>,
+    /// Height of a row including spacing.
+    row_height: f32,
+    /// Vertical offset and height of each row, if rows differ in height.
+    spans: Option<Vec<(f32, f32)>>,
+}
+
+impl CursorRows {
+    /// Get vertical offset and height of a row.
+    fn span(&self, row: usize) -> (f32, f32) {
+        self.spans
+            .as_ref()
+            .and_then(|spans| spans.get(row).copied())
+            .unwrap_or((row as f32 * self.row_height, self.row_height))
+    }
+}
+
+/// Get id of the scrollable of a view.
+pub fn scroll_id(view_path: ViewPath) -> widget::Id {
+    widget::Id::from(format!("view-{view_path:?}"))
+}
+
+/// Get id of the filter text input of a pane.
+pub fn filter_id(view_path: ViewPath) -> widget::Id {
+    widget::Id::from(format!("filter-{view_path:?}"))
+}
+
+/// Get id of the horizontal scrollable of a shelf row.
+pub fn shelf_scroll_id(view_path: ViewPath, row: usize) -> widget::Id {
+    widget::Id::from(format!("shelf-{view_path:?}-{row}"))
+}
+
+/// Get offset scrolling the least from offset such that an element spanning start and
+/// start + size is visible in a viewport of given length, [None] if it already is.
+fn reveal(offset: f32, viewport: f32, start: f32, size: f32) -> Option<f32> {
+    if start < offset {
+        Some(start)
+    } else if start + size > offset + viewport {
+        Some((start + size - viewport).max(0.0))
+    } else {
+        None
+    }
+}
+
+impl Pane {
+    /// Get token cancelled when view of pane is cleared or pane is closed.
+    pub fn cancel_token(&self) -> CancelToken {
+        self.loads.clone()
+    }
+
+    /// Check if token is the token of the current view.
+    pub fn is_current(&self, token: &CancelToken) -> bool {
+        self.loads.is_same(token)
+    }
+
+    /// Cancel scans and loads started for the current view.
+    pub fn cancel_loads(&mut self) {
+        ::core::mem::take(&mut self.loads).cancel();
+    }
+
+    /// Clear view, cancelling scans and loads.
+    fn clear(&mut self) {
+        self.cancel_loads();
+        self.scanning = false;
+        self.context_menu = None;
+        self.quick_look = None;
+        self.list = None;
+        self.feed = None;
+        self.tag = None;
+        self.last_visit = None;
+        self.view = DirView::Empty;
+    }
+
+    /// Check if pane displays its directory, rather than a reading list, feed or tag.
+    pub const fn shows_dir(&self) -> bool {
+        self.list.is_none() && self.feed.is_none() && self.tag.is_none()
+    }
+
+    /// Show archives tagged with tag in pane, clearing the view. The directory of pane is
+    /// kept such that navigating back returns to it.
+    pub fn open_tag(&mut self, tag: String) {
+        self.clear();
+        self.tag = Some(tag);
+    }
+
+    /// Show a reading list in pane, clearing the view. The directory of pane is kept
+    /// such that navigating back returns to it.
+    pub fn open_list(&mut self, name: String) {
+        self.clear();
+        self.list = Some(name);
+    }
+
+    /// Show an OPDS feed in pane, clearing the view. The directory of pane is kept such
+    /// that navigating back from the first feed returns to it.
+    pub fn open_feed(&mut self, feed: FeedView, items: Vec<(Arc<Path>, Item)>) {
+        self.clear();
+        let order = items.iter().map(|(path, _)| Arc::clone(path)).collect();
+        self.view = DirView::with_items(items.into_iter().collect(), Some(order));
+        self.feed = Some(feed);
+    }
+
+    /// Navigate pane to a directory, clearing the view.
+    pub fn navigate(&mut self, path: Arc<Path>) {
+        if let Some(prev) = self.path.replace(path) {
+            self.back.push(prev);
+        }
+        self.forward.clear();
+        self.clear();
+    }
+
+    /// Navigate pane back in history, returning the directory to load.
+    pub fn back(&mut self) -> Option<Arc<Path>> {
+        let prev = self.back.pop()?;
+        if let Some(current) = self.path.replace(Arc::clone(&prev)) {
+            self.forward.push(current);
+        }
+        self.clear();
+        Some(prev)
+    }
+
+    /// Clear view, also closing any shown reading list, returning the directory of pane
+    /// to load again.
+    pub fn reload(&mut self) -> Option<Arc<Path>> {
+        self.clear();
+        self.path.clone()
+    }
+
+    /// View header of pane, with text filter, quick filter chips, reading lists and OPDS
+    /// feeds.
+    pub fn header<'a>(
+        &'a self,
+        view_path: ViewPath,
+        lists: &[String],
+        feeds: &[String],
+    ) -> Element<'a, Message> {
+        let shows_dir = self.shows_dir();
+        let chip = |content: Element<'static, Message>, chip: Chip| {
+            widget::button(content)
+                .padding([2, 6])
+                .style(if self.filter.is_active(chip) {
+                    widget::button::primary
+                } else {
+                    widget::button::secondary
+                })
+                .on_press(Message::ToggleFilterChip(view_path, chip))
+        };
+        widget::Row::new()
+            .spacing(3)
+            .align_y(Center)
+            .push(self.feed.as_ref().map(|feed| {
+                widget::text(shorten_text(&feed.title, 32, Ellipsis::Middle))
+                    .wrapping(Wrapping::None)
+            }))
+            .push(self.tag.as_ref().map(|tag| {
+                widget::text(shorten_text(tag, 32, Ellipsis::Middle)).wrapping(Wrapping::None)
+            }))
+            .push(
+                widget::text_input(&tr("pane-filter"), self.filter.text())
+                    .id(filter_id(view_path))
+                    .on_input(move |text| Message::SetFilterText(view_path, text))
+                    .padding(3)
+                    .width(Fill),
+            )
+            .extend(ReadState::ALL.map(|read_state| {
+                chip(
+                    widget::text(read_state.to_string()).into(),
+                    Chip::Read(read_state),
+                )
+                .into()
+            }))
+            .push(chip(widget::text("\u{2605}").into(), Chip::Starred))
+            .extend(Label::ALL.map(|label| chip(label_dot(label, 10.0), Chip::Label(label)).into()))
+            .push(
+                self.last_visit
+                    .is_some()
+                    .then(|| chip(widget::text(tr("pane-new")).into(), Chip::New)),
+            )
+            .push(shows_dir.then(|| {
+                widget::button(widget::text(tr("pane-shelf")))
+                    .padding([2, 6])
+                    .style(if self.shelf {
+                        widget::button::primary
+                    } else {
+                        widget::button::secondary
+                    })
+                    .on_press(Message::ToggleShelf(view_path))
+            }))
+            .push(shows_dir.then(|| {
+                widget::button(widget::text(tr("pane-reading-order")))
+                    .padding([2, 6])
+                    .style(if self.reading_order {
+                        widget::button::primary
+                    } else {
+                        widget::button::secondary
+                    })
+                    .on_press(Message::ToggleReadingOrder(view_path))
+            }))
+            .push(
+                widget::pick_list(ViewMode::ALL, Some(self.mode), move |mode| {
+                    Message::SetViewMode(view_path, mode)
+                })
+                .padding([2, 6]),
+            )
+            .push(
+                widget::pick_list(
+                    lists
+                        .iter()
+                        .cloned()
+                        .map(ListChoice::List)
+                        .chain([ListChoice::New])
+                        .collect::<Vec<_>>(),
+                    self.list.clone().map(ListChoice::List),
+                    move |choice| Message::OpenReadingList(view_path, choice),
+                )
+                .placeholder(tr("pane-reading-lists"))
+                .padding([2, 6]),
+            )
+            .push((!feeds.is_empty()).then(|| {
+                widget::pick_list(
+                    feeds.to_vec(),
+                    self.feed
+                        .as_ref()
+                        .and_then(|feed| feed.back.first().or(Some(&feed.url)))
+                        .filter(|url| feeds.contains(url))
+                        .cloned(),
+                    move |url| Message::OpenFeed(view_path, url),
+                )
+                .placeholder(tr("pane-opds-feeds"))
+                .padding([2, 6])
+            }))
+            .pipe(widget::container)
+            .padding(Padding {
+                bottom: 0.0,
+                ..Padding::new(5.0)
+            })
+            .into()
+    }
+
+    /// Get how items of pane are arranged, grids split into sections if sections is set.
+    pub fn arrangement(&self, sections: bool) -> Arrangement {
+        Arrangement {
+            mode: self.mode,
+            sort: self.list_sort(),
+            sections,
+        }
+    }
+
+    /// Get order of listed items, if sorted by a column and shown as a list.
+    pub fn list_sort(&self) -> Option<Sort> {
+        self.sort.filter(|_| !self.mode.is_grid())
+    }
+
+    /// Navigate pane forward in history, returning the directory to load.
+    pub fn forward(&mut self) -> Option<Arc<Path>> {
+        let next = self.forward.pop()?;
+        if let Some(current) = self.path.replace(Arc::clone(&next)) {
+            self.back.push(current);
+        }
+        self.clear();
+        Some(next)
+    }
+}
+
+/// Directory contents displayed by a pane.
 #[derive(Debug, Clone, Default, IsVariant)]
 pub enum DirView {
     /// Empty pane.
@@ -28,77 +1283,1300 @@ pub enum DirView {
     Dir {
         /// View Items.
         items: BTreeMap<Arc<Path>, Item>,
+        /// Selected items.
+        selected: BTreeSet<Arc<Path>>,
+        /// Item range selections extend from.
+        anchor: Option<Arc<Path>>,
+        /// Item the keyboard cursor is on, moved by selecting items.
+        cursor: Option<Arc<Path>>,
+        /// Vertical scroll offset of view.
+        scroll_offset: f32,
+        /// Size of scrollable viewport, if known.
+        viewport: Option<Size>,
+        /// Order items are shown in, by path if [None].
+        order: Option<Vec<Arc<Path>>>,
+        /// Rows of a shelf items are shown in, as a grid if [None].
+        shelf: Option<Vec<ShelfRow>>,
     },
 }
 
+/// Row of a shelf, the volumes of a series.
+#[derive(Debug, Clone)]
+pub struct ShelfRow {
+    /// Name of series.
+    pub name: String,
+    /// Volumes of series in reading order.
+    pub volumes: Vec<Arc<Path>>,
+    /// Horizontal scroll offset of row.
+    pub offset: f32,
+    /// Volumes of series on the wishlist.
+    pub wished: Vec<f64>,
+    /// Volumes missing from series which are not on the wishlist.
+    pub missing: Vec<f64>,
+    /// Series is followed.
+    pub followed: bool,
+    /// Amount of new volumes of a followed series not yet dismissed.
+    pub alerts: usize,
+}
+
+impl ShelfRow {
+    /// Create a row of the volumes of a series, along with the volumes of the wishlist
+    /// which are among them and thus have been acquired.
+    pub fn new(name: String, volumes: Vec<Arc<Path>>, wishlist: &[Wish]) -> (Self, Vec<f64>) {
+        let key = series::series_key(&name);
+        let owned = volumes
+            .iter()
+            .filter_map(|path| series::volume(&path.file_stem()?.to_string_lossy()))
+            .collect::<Vec<_>>();
+        let (acquired, wished) = wishlist
+            .iter()
+            .filter(|wish| series::series_key(&wish.series) == key)
+            .map(|wish| wish.volume)
+            .partition::<Vec<_>, _>(|volume| owned.contains(volume));
+        let missing = series::gaps(&owned)
+            .into_iter()
+            .filter(|volume| !wished.contains(volume))
+            .collect();
+        let row = Self {
+            name,
+            volumes,
+            offset: 0.0,
+            wished,
+            missing,
+            followed: false,
+            alerts: 0,
+        };
+        (row, acquired)
+    }
+
+    /// View title of row, with the new volumes of a followed series and the wished for
+    /// volumes of the series.
+    fn title<'a>(&self, view_path: ViewPath, index: usize, text: String) -> Element<'a, Message> {
+        widget::Row::new()
+            .spacing(3)
+            .align_y(Center)
+            .push(widget::text(text).wrapping(Wrapping::None))
+            .push((self.alerts > 0).then(|| {
+                widget::button(
+                    widget::text(tr_args(
+                        "pane-new-volumes",
+                        &[("count", &self.alerts.to_string())],
+                    ))
+                    .size(12),
+                )
+                .padding([0, 4])
+                .style(widget::button::success)
+                .on_press(Message::DismissAlerts(view_path, index))
+            }))
+            .push(
+                widget::button(
+                    widget::text(tr(if self.followed {
+                        "pane-following"
+                    } else {
+                        "pane-follow"
+                    }))
+                    .size(12),
+                )
+                .padding([0, 4])
+                .style(if self.followed {
+                    widget::button::secondary
+                } else {
+                    widget::button::text
+                })
+                .on_press(Message::ToggleFollow(view_path, index)),
+            )
+            .push((!self.wished.is_empty()).then(|| {
+                widget::text(tr("pane-wishlist"))
+                    .size(12)
+                    .style(widget::text::secondary)
+            }))
+            .extend(self.wished.iter().map(|&volume| {
+                widget::button(widget::text(series::volume_text(volume)).size(12))
+                    .padding([0, 4])
+                    .style(widget::button::secondary)
+                    .on_press(Message::Unwish(view_path, index, volume))
+                    .into()
+            }))
+            .push((!self.missing.is_empty()).then(|| {
+                widget::button(
+                    widget::text(tr_args(
+                        "pane-wish-missing",
+                        &[("count", &self.missing.len().to_string())],
+                    ))
+                    .size(12),
+                )
+                .padding([0, 4])
+                .style(widget::button::text)
+                .on_press(Message::WishMissing(view_path, index))
+            }))
+            .height(SHELF_TITLE_HEIGHT)
+            .into()
+    }
+
+    /// Iterate over volumes of row shown by filter.
+    fn shown<'a>(
+        &'a self,
+        items: &'a BTreeMap<Arc<Path>, Item>,
+        filter: &'a Filter,
+    ) -> impl Iterator<Item = (&'a Arc<Path>, &'a Item)> + 'a {
+        self.volumes
+            .iter()
+            .filter_map(|path| items.get_key_value(path))
+            .filter(|(_, item)| filter.matches(item))
+    }
+}
+
+/// Shelf presentation of a directory view.
+#[derive(Debug, Clone, Copy)]
+struct ShelfView<'a> {
+    /// Path to view.
+    view_path: ViewPath,
+    /// Rows of shelf.
+    rows: &'a [ShelfRow],
+    /// Items of view.
+    items: &'a BTreeMap<Arc<Path>, Item>,
+    /// Selected items.
+    selected: &'a BTreeSet<Arc<Path>>,
+    /// Item the keyboard cursor is on.
+    cursor: Option<&'a Arc<Path>>,
+    /// Filter of shown items.
+    filter: &'a Filter,
+    /// Context menu, if shown.
+    context_menu: Option<ContextMenu<'a>>,
+    /// Thumbnails shared by all panes.
+    cache: &'a ThumbnailCache<Shown>,
+    /// Vertical scroll offset.
+    scroll_offset: f32,
+    /// Width and height of cards.
+    icon_width: f32,
+    /// Max length of card titles.
+    max_text_len: u16,
+    /// Badges shown on cards.
+    badges: &'a [CardBadge],
+    /// Estimated time spent reading a page.
+    page_time: Duration,
+    /// Cards are tinted with the dominant color of their covers.
+    accents: bool,
+}
+
+impl<'a> ShelfView<'a> {
+    /// View shelf in an area of given size, creating cards only for visible volumes.
+    fn view(self, size: Size) -> Element<'a, Message> {
+        let Self {
+            view_path,
+            rows,
+            items,
+            selected,
+            cursor,
+            filter,
+            context_menu,
+            cache,
+            scroll_offset,
+            icon_width,
+            max_text_len,
+            badges,
+            page_time,
+            accents,
+        } = self;
+        let row_height = shelf_row_height(icon_width);
+        let cell = icon_width + GRID_SPACING;
+        let shown = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.shown(items, filter).next().is_some())
+            .collect::<Vec<_>>();
+        let visible = visible_range(scroll_offset, size.height, row_height, shown.len());
+
+        let shelf_rows = shown[visible.clone()].iter().map(|&(index, row)| {
+            let count = row.shown(items, filter).count();
+            let columns = visible_range(row.offset, size.width, cell, count);
+            let cards = row
+                .shown(items, filter)
+                .skip(columns.start)
+                .take(columns.len())
+                .map(|(path, item)| {
+                    card(
+                        ItemPath {
+                            view_path,
+                            path: Arc::clone(path),
+                        },
+                        item,
+                        (
+                            cache.get(path).map(Shown::image),
+                            cache.accent_color(path).filter(|_| accents),
+                        ),
+                        (selected.contains(path), cursor == Some(path)),
+                        max_text_len,
+                        badges,
+                    )
+                    .pipe(widget::container)
+                    .padding(Padding {
+                        right: GRID_SPACING,
+                        ..Padding::new(0.0)
+                    })
+                    .width(cell)
+                    .height(icon_width)
+                    .into()
+                });
+            let remaining = row
+                .volumes
+                .iter()
+                .filter_map(|path| items.get(path)?.remaining_pages())
+                .sum::<usize>();
+            let title = if remaining == 0 {
+                tr_args(
+                    "pane-series",
+                    &[("name", &row.name), ("count", &count.to_string())],
+                )
+            } else {
+                tr_args(
+                    "pane-series-left",
+                    &[
+                        ("name", &row.name),
+                        ("count", &count.to_string()),
+                        (
+                            "left",
+                            &format::duration(reading_time(page_time, remaining)),
+                        ),
+                    ],
+                )
+            };
+            let row_element = widget::Column::new()
+                .push(row.title(view_path, index, title))
+                .push(
+                    widget::Row::new()
+                        .push(widget::space().width(columns.start as f32 * cell))
+                        .extend(cards)
+                        .push(widget::space().width((count - columns.end) as f32 * cell))
+                        .pipe(widget::scrollable)
+                        .horizontal()
+                        .id(shelf_scroll_id(view_path, index))
+                        .on_scroll(move |viewport| Message::ShelfScrolled {
+                            view_path,
+                            row: index,
+                            offset: viewport.absolute_offset().x,
+                        }),
+                )
+                .height(row_height)
+                .into();
+            (index, row_element)
+        });
+
+        widget::Column::new()
+            .push(widget::space().height(visible.start as f32 * row_height))
+            // Keyed such that rows keep their horizontal scroll state when scrolled.
+            .push(widget::keyed_column(shelf_rows))
+            .push(widget::space().height((shown.len() - visible.end) as f32 * row_height))
+            .pipe(widget::scrollable)
+            .id(scroll_id(view_path))
+            .on_scroll(move |viewport| Message::PaneScrolled {
+                view_path,
+                offset: viewport.absolute_offset().y,
+                viewport: viewport.bounds().size(),
+            })
+            .pipe(|shelf| widget::Stack::new().push(shelf))
+            .extend(context_menu.and_then(|menu| {
+                shown
+                    .iter()
+                    .enumerate()
+                    .find_map(|(shown_index, (_, row))| {
+                        let column = row
+                            .shown(items, filter)
+                            .position(|(path, _)| path == menu.path)?;
+                        // Menu is placed at center of card, kept within view.
+                        let x = column as f32 * cell + icon_width / 2.0 - row.offset;
+                        let y =
+                            shown_index as f32 * row_height + SHELF_TITLE_HEIGHT + icon_width / 2.0
+                                - scroll_offset;
+                        let height = menu.entries(false) as f32 * CONTEXT_MENU_ENTRY_HEIGHT + 6.0;
+                        widget::pin(item_menu(view_path, menu, false))
+                            .x(x.min(size.width - CONTEXT_MENU_WIDTH).max(0.0))
+                            .y(y.min(size.height - height).max(0.0))
+                            .pipe(Element::from)
+                            .pipe(Some)
+                    })
+            }))
+            .into()
+    }
+}
+
+/// Iterate over items in the order they are shown, in natural order of paths if there
+/// is no order.
+fn ordered<'a>(
+    items: &'a BTreeMap<Arc<Path>, Item>,
+    order: Option<&'a [Arc<Path>]>,
+) -> Box<dyn Iterator<Item = (&'a Arc<Path>, &'a Item)> + 'a> {
+    match order {
+        Some(order) => Box::new(order.iter().filter_map(|path| items.get_key_value(path))),
+        None => {
+            let mut sorted = items.iter().collect::<Vec<_>>();
+            sorted
+                .sort_by(|(a, _), (b, _)| natural_cmp(&a.to_string_lossy(), &b.to_string_lossy()));
+            Box::new(sorted.into_iter())
+        }
+    }
+}
+
+/// Thumbnail state of an item.
+#[derive(Debug, Clone, Default, IsVariant)]
+pub enum Thumbnail {
+    /// Item has no thumbnail.
+    #[default]
+    Unavailable,
+    /// Thumbnail has not been requested.
+    Pending,
+    /// Thumbnail is being loaded.
+    Loading,
+    /// Thumbnail has been loaded into the thumbnail cache.
+    Loaded,
+}
+
 /// Displayed item.
 #[derive(Debug, Clone)]
 pub struct Item {
     /// Name of item.
     pub name: String,
     /// Thumbnail of item.
-    pub cover: Option<widget::image::Handle>,
+    pub thumbnail: Thumbnail,
+    /// Metadata of item.
+    pub metadata: Metadata,
+    /// Size of item in bytes, if it is a file.
+    pub size: Option<u64>,
+    /// Time item was last modified, if known.
+    pub modified: Option<SystemTime>,
+    /// Catalogue state of item.
+    pub state: ItemState,
+    /// Amount of pages of archive, if counted.
+    pub page_count: Option<usize>,
+    /// File of item could not be reached, such as on an unreachable network mount.
+    pub offline: bool,
+    /// Archive was added since the directory was last viewed.
+    pub new: bool,
+}
+
+impl Item {
+    /// Create a new item for path, archives get a pending thumbnail.
+    pub fn new(name: String, path: &Path, file: Option<&fs::Metadata>) -> Self {
+        Self {
+            name,
+            thumbnail: if ArchiveKind::from_path(path).is_some() {
+                Thumbnail::Pending
+            } else {
+                Thumbnail::Unavailable
+            },
+            metadata: Metadata::default(),
+            size: file.filter(|file| file.is_file()).map(fs::Metadata::len),
+            modified: file.and_then(|file| file.modified().ok()),
+            state: ItemState::default(),
+            page_count: None,
+            offline: false,
+            new: false,
+        }
+    }
+
+    /// Mark file of item as unreachable, such that its thumbnail is not loaded.
+    pub const fn set_offline(&mut self) {
+        self.offline = true;
+        self.thumbnail = Thumbnail::Unavailable;
+    }
+
+    /// Get displayed title of item, preferring title from metadata.
+    pub fn title(&self) -> &str {
+        self.metadata.title.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Get amount of pages left to read of archive, if its pages have been counted.
+    pub fn remaining_pages(&self) -> Option<usize> {
+        let page_count = self.page_count?;
+        Some(match self.state.read_state {
+            ReadState::Unread => page_count,
+            ReadState::Reading => page_count.saturating_sub(self.state.page),
+            ReadState::Finished => 0,
+        })
+    }
+}
+
+/// Options of how items of a view are presented.
+#[derive(Debug, Clone, Copy)]
+pub struct ViewOptions<'a> {
+    /// Width and height of cards.
+    pub icon_width: f32,
+    /// Max length of card titles.
+    pub max_text_len: u16,
+    /// Badges shown on cards.
+    pub badges: &'a [CardBadge],
+    /// Estimated time spent reading a page.
+    pub page_time: Duration,
+    /// Recently opened directories and archives, shown by empty views.
+    pub recent: &'a [Arc<Path>],
+    /// How items are presented.
+    pub mode: ViewMode,
+    /// Order of listed items, if sorted by a column.
+    pub sort: Option<Sort>,
+    /// Units file sizes are shown in.
+    pub size_units: SizeUnits,
+    /// Cards are tinted with the dominant color of their covers.
+    pub accents: bool,
+    /// Grids are split into sections by the index letter of titles.
+    pub sections: bool,
+    /// Directory of view is being scanned, empty views show placeholder cards.
+    pub scanning: bool,
+}
+
+/// How items of a view are arranged, deciding which items cards are created for and
+/// the rows the keyboard cursor moves over.
+#[derive(Debug, Clone, Copy)]
+pub struct Arrangement {
+    /// How items are presented.
+    pub mode: ViewMode,
+    /// Order of listed items, if sorted by a column.
+    pub sort: Option<Sort>,
+    /// Grids are split into sections by the index letter of titles.
+    pub sections: bool,
+}
+
+/// Estimate time spent reading an amount of pages.
+pub fn reading_time(page_time: Duration, pages: usize) -> Duration {
+    page_time.saturating_mul(u32::try_from(pages).unwrap_or(u32::MAX))
 }
 
 impl DirView {
+    /// Create a directory view containing a single item.
+    pub fn with_item(path: Arc<Path>, item: Item) -> Self {
+        Self::Dir {
+            items: BTreeMap::from_iter([(path, item)]),
+            selected: BTreeSet::new(),
+            anchor: None,
+            cursor: None,
+            scroll_offset: 0.0,
+            viewport: None,
+            order: None,
+            shelf: None,
+        }
+    }
+
+    /// Create a directory view of items, shown in order if given.
+    pub const fn with_items(
+        items: BTreeMap<Arc<Path>, Item>,
+        order: Option<Vec<Arc<Path>>>,
+    ) -> Self {
+        Self::Dir {
+            items,
+            selected: BTreeSet::new(),
+            anchor: None,
+            cursor: None,
+            scroll_offset: 0.0,
+            viewport: None,
+            order,
+            shelf: None,
+        }
+    }
+
+    /// Check if view contains an item.
+    pub fn contains(&self, path: &Path) -> bool {
+        matches!(self, DirView::Dir { items, .. } if items.contains_key(path))
+    }
+
+    /// Check if an item of view is a directory, directories have no size.
+    pub fn item_is_dir(&self, path: &Path) -> bool {
+        matches!(self, DirView::Dir { items, .. }
+            if items.get(path).is_some_and(|item| item.size.is_none()))
+    }
+
+    /// Replace an item by the item it was renamed to, keeping its place and selection, or
+    /// remove it if it was moved out of view.
+    pub fn rename_item(&mut self, from: &Path, to: Option<&Arc<Path>>) {
+        let DirView::Dir {
+            items,
+            selected,
+            anchor,
+            cursor,
+            order,
+            shelf,
+            ..
+        } = self
+        else {
+            return;
+        };
+        let Some(mut item) = items.remove(from) else {
+            return;
+        };
+        let was_selected = selected.remove(from);
+        let rows = shelf.iter_mut().flatten().map(|row| &mut row.volumes);
+        let Some(to) = to else {
+            for path in [anchor, cursor] {
+                if path.as_deref() == Some(from) {
+                    *path = None;
+                }
+            }
+            for paths in order.iter_mut().chain(rows) {
+                paths.retain(|path| **path != *from);
+            }
+            return;
+        };
+
+        // Names of items may have a prefix, such as the directory of items of a
+        // reading list, only the file name is replaced.
+        let (old, new) = (
+            from.file_name().unwrap_or_default().display().to_string(),
+            to.file_name().unwrap_or_default().display().to_string(),
+        );
+        if let Some(prefix) = item.name.strip_suffix(&old) {
+            item.name = format!("{prefix}{new}");
+        }
+        // Thumbnails are cached by path, and the kind of archive may have changed.
+        item.thumbnail = if ArchiveKind::from_path(to).is_some() {
+            Thumbnail::Pending
+        } else {
+            Thumbnail::Unavailable
+        };
+        items.insert(Arc::clone(to), item);
+        if was_selected {
+            selected.insert(Arc::clone(to));
+        }
+        for path in [anchor, cursor].into_iter().flatten() {
+            if **path == *from {
+                *path = Arc::clone(to);
+            }
+        }
+        for path in order.iter_mut().chain(rows).flatten() {
+            if **path == *from {
+                *path = Arc::clone(to);
+            }
+        }
+    }
+
+    /// Get the selected item most recently pressed, or any selected item if it has been
+    /// deselected.
+    pub fn focused(&self) -> Option<&Arc<Path>> {
+        let DirView::Dir {
+            selected, anchor, ..
+        } = self
+        else {
+            return None;
+        };
+        anchor
+            .as_ref()
+            .filter(|anchor| selected.contains(*anchor))
+            .or_else(|| selected.first())
+    }
+
+    /// Get item the keyboard cursor is on, the focused item if it has not been moved.
+    pub fn cursor(&self) -> Option<&Arc<Path>> {
+        match self {
+            DirView::Dir {
+                cursor: Some(cursor),
+                items,
+                ..
+            } if items.contains_key(cursor) => Some(cursor),
+            _ => self.focused(),
+        }
+    }
+
+    /// Arrange items shown by filter in the rows the keyboard cursor moves over.
+    fn cursor_rows(
+        &self,
+        filter: &Filter,
+        Arrangement {
+            mode,
+            sort,
+            sections,
+        }: Arrangement,
+        icon_width: f32,
+    ) -> CursorRows {
+        let DirView::Dir {
+            items,
+            viewport,
+            order,
+            shelf,
+            ..
+        } = self
+        else {
+            return CursorRows {
+                rows: Vec::new(),
+                shelf_rows: None,
+                row_height: 0.0,
+                spans: None,
+            };
+        };
+        let viewport = viewport.unwrap_or(ASSUMED_VIEWPORT);
+
+        if let Some(shelf) = shelf {
+            let (shelf_rows, rows) = shelf
+                .iter()
+                .enumerate()
+                .map(|(index, row)| {
+                    let volumes = row
+                        .shown(items, filter)
+                        .map(|(path, _)| Arc::clone(path))
+                        .collect::<Vec<_>>();
+                    (index, volumes)
+                })
+                .filter(|(_, volumes)| !volumes.is_empty())
+                .unzip();
+            CursorRows {
+                rows,
+                shelf_rows: Some(shelf_rows),
+                row_height: shelf_row_height(icon_width),
+                spans: None,
+            }
+        } else if !mode.is_grid() {
+            CursorRows {
+                rows: list_view::listed(
+                    ordered(items, order.as_deref()).filter(|(_, item)| filter.matches(item)),
+                    sort,
+                )
+                .into_iter()
+                .map(|(path, _)| vec![Arc::clone(path)])
+                .collect(),
+                shelf_rows: None,
+                row_height: list_view::ROW_HEIGHT,
+                spans: None,
+            }
+        } else if sections {
+            let shown = ordered(items, order.as_deref())
+                .filter(|(_, item)| filter.matches(item))
+                .collect::<Vec<_>>();
+            let layout = GridLayout::new(shown.len(), viewport.width, icon_width);
+            let sections = SectionLayout::new(
+                shown.iter().map(|(_, item)| index_letter(item.title())),
+                &layout,
+            );
+            CursorRows {
+                rows: sections
+                    .rows
+                    .iter()
+                    .map(|(range, _)| {
+                        shown[range.clone()]
+                            .iter()
+                            .map(|(path, _)| Arc::clone(path))
+                            .collect()
+                    })
+                    .collect(),
+                shelf_rows: None,
+                row_height: layout.row_height,
+                spans: (0..sections.rows.len())
+                    .map(|row| (sections.offsets[row], sections.row_height(row)))
+                    .collect::<Vec<_>>()
+                    .pipe(Some),
+            }
+        } else {
+            let shown = ordered(items, order.as_deref())
+                .filter(|(_, item)| filter.matches(item))
+                .map(|(path, _)| Arc::clone(path))
+                .collect::<Vec<_>>();
+            let layout = GridLayout::new(shown.len(), viewport.width, icon_width);
+            CursorRows {
+                rows: shown.chunks(layout.columns).map(<[_]>::to_vec).collect(),
+                shelf_rows: None,
+                row_height: layout.row_height,
+                spans: None,
+            }
+        }
+    }
+
+    /// Put keyboard cursor on an item of rows, scrolling such that it is visible.
+    fn place_cursor(
+        &mut self,
+        rows: &CursorRows,
+        (row, column): (usize, usize),
+        icon_width: f32,
+    ) -> Option<CursorTarget> {
+        let DirView::Dir {
+            cursor,
+            scroll_offset,
+            viewport,
+            shelf,
+            ..
+        } = self
+        else {
+            return None;
+        };
+        let path = Arc::clone(rows.rows.get(row)?.get(column)?);
+        let viewport = viewport.unwrap_or(ASSUMED_VIEWPORT);
+        *cursor = Some(Arc::clone(&path));
+
+        let (start, height) = rows.span(row);
+        let offset = reveal(*scroll_offset, viewport.height, start, height);
+        if let Some(offset) = offset {
+            *scroll_offset = offset;
+        }
+        let shelf_offset = rows
+            .shelf_rows
+            .as_ref()
+            .and_then(|shelf_rows| shelf_rows.get(row).copied())
+            .and_then(|index| {
+                let shelf_row = shelf.as_mut()?.get_mut(index)?;
+                let cell = icon_width + GRID_SPACING;
+                let offset = reveal(shelf_row.offset, viewport.width, column as f32 * cell, cell)?;
+                shelf_row.offset = offset;
+                Some((index, offset))
+            });
+
+        Some(CursorTarget {
+            path,
+            offset,
+            shelf_offset,
+        })
+    }
+
+    /// Move keyboard cursor over items shown by filter, scrolling such that the item it
+    /// moves to is visible. Moves to the first item if cursor is not on a shown item.
+    pub fn move_cursor(
+        &mut self,
+        movement: Movement,
+        filter: &Filter,
+        arrangement: Arrangement,
+        icon_width: f32,
+    ) -> Option<CursorTarget> {
+        let rows = self.cursor_rows(filter, arrangement, icon_width);
+        let last_row = rows.rows.len().checked_sub(1)?;
+        let current = self.cursor().and_then(|cursor| {
+            rows.rows.iter().enumerate().find_map(|(row, paths)| {
+                paths
+                    .iter()
+                    .position(|path| path == cursor)
+                    .map(|column| (row, column))
+            })
+        });
+        let Some((row, column)) = current else {
+            return self.place_cursor(&rows, (0, 0), icon_width);
+        };
+        let viewport = match self {
+            DirView::Dir { viewport, .. } => viewport.unwrap_or(ASSUMED_VIEWPORT),
+            DirView::Empty => ASSUMED_VIEWPORT,
+        };
+        let page = ((viewport.height / rows.row_height) as usize).max(1);
+        let row_len = |row: usize| rows.rows[row].len();
+
+        let target = match movement {
+            Movement::Left if column > 0 => (row, column - 1),
+            Movement::Left if row > 0 => (row - 1, row_len(row - 1) - 1),
+            Movement::Right if column + 1 < row_len(row) => (row, column + 1),
+            Movement::Right if row < last_row => (row + 1, 0),
+            Movement::Left | Movement::Right => (row, column),
+            Movement::Up | Movement::Down | Movement::PageUp | Movement::PageDown => {
+                let row = match movement {
+                    Movement::Up => row.saturating_sub(1),
+                    Movement::Down => (row + 1).min(last_row),
+                    Movement::PageUp => row.saturating_sub(page),
+                    _ => (row + page).min(last_row),
+                };
+                (row, column.min(row_len(row) - 1))
+            }
+            Movement::Home => (0, 0),
+            Movement::End => (last_row, row_len(last_row) - 1),
+        };
+        self.place_cursor(&rows, target, icon_width)
+    }
+
+    /// Move keyboard cursor to the first item shown by filter whose title starts with
+    /// prefix, ignoring case, scrolling such that it is visible.
+    pub fn cursor_to_prefix(
+        &mut self,
+        prefix: &str,
+        filter: &Filter,
+        arrangement: Arrangement,
+        icon_width: f32,
+    ) -> Option<CursorTarget> {
+        let prefix = prefix.to_lowercase();
+        let rows = self.cursor_rows(filter, arrangement, icon_width);
+        let target = self.find_first(&rows, |item| {
+            item.title().to_lowercase().starts_with(&prefix)
+        })?;
+        self.place_cursor(&rows, target, icon_width)
+    }
+
+    /// Move keyboard cursor to the first item shown by filter whose title is listed
+    /// under letter of the alphabetical index, scrolling its row to the top of the view.
+    pub fn cursor_to_letter(
+        &mut self,
+        letter: char,
+        filter: &Filter,
+        arrangement: Arrangement,
+        icon_width: f32,
+    ) -> Option<CursorTarget> {
+        let rows = self.cursor_rows(filter, arrangement, icon_width);
+        let target = self.find_first(&rows, |item| index_letter(item.title()) == letter)?;
+        let mut cursor = self.place_cursor(&rows, target, icon_width)?;
+        if let DirView::Dir { scroll_offset, .. } = self {
+            let (start, _) = rows.span(target.0);
+            *scroll_offset = start;
+            cursor.offset = Some(start);
+        }
+        Some(cursor)
+    }
+
+    /// Find row and column of the first item of rows matching predicate.
+    fn find_first(
+        &self,
+        rows: &CursorRows,
+        matches: impl Fn(&Item) -> bool,
+    ) -> Option<(usize, usize)> {
+        let DirView::Dir { items, .. } = self else {
+            return None;
+        };
+        rows.rows.iter().enumerate().find_map(|(row, paths)| {
+            paths
+                .iter()
+                .position(|path| items.get(path).is_some_and(&matches))
+                .map(|column| (row, column))
+        })
+    }
+
+    /// Mark pending thumbnails of items shown by filter near the viewport as loading,
+    /// returning their paths.
+    /// At most [MAX_LOADING], or [MAX_LOADING_THROTTLED] if throttled, thumbnails are
+    /// loading at the same time.
+    ///
+    /// Loaded thumbnails near the viewport are marked as used in the cache, and are
+    /// requested again if they have been evicted.
+    pub fn request_thumbnails(
+        &mut self,
+        filter: &Filter,
+        Arrangement {
+            mode,
+            sort,
+            sections,
+        }: Arrangement,
+        icon_width: f32,
+        throttled: bool,
+        cache: &mut ThumbnailCache<Shown>,
+    ) -> Vec<Arc<Path>> {
+        let DirView::Dir {
+            items,
+            selected,
+            anchor,
+            scroll_offset,
+            viewport,
+            order,
+            shelf,
+            ..
+        } = self
+        else {
+            return Vec::new();
+        };
+        let viewport = viewport.unwrap_or(ASSUMED_VIEWPORT);
+        let loading = items
+            .values()
+            .filter(|item| item.thumbnail.is_loading())
+            .count();
+
+        let visible = if let Some(rows) = shelf {
+            let shown = rows
+                .iter()
+                .filter(|row| row.shown(items, filter).next().is_some())
+                .collect::<Vec<_>>();
+            let range = visible_range(
+                *scroll_offset,
+                viewport.height,
+                shelf_row_height(icon_width),
+                shown.len(),
+            );
+            shown[range]
+                .iter()
+                .flat_map(|row| {
+                    let count = row.shown(items, filter).count();
+                    let columns =
+                        visible_range(row.offset, viewport.width, icon_width + GRID_SPACING, count);
+                    row.shown(items, filter)
+                        .skip(columns.start)
+                        .take(columns.len())
+                        .map(|(path, _)| Arc::clone(path))
+                })
+                .collect::<Vec<_>>()
+        } else if !mode.is_grid() {
+            let listed = list_view::listed(
+                ordered(items, order.as_deref()).filter(|(_, item)| filter.matches(item)),
+                sort,
+            );
+            let visible = list_view::visible_items(*scroll_offset, viewport.height, listed.len());
+            // Detail views show the cover of the focused item.
+            let focused = mode
+                .is_detail()
+                .then(|| anchor.clone())
+                .flatten()
+                .filter(|anchor| selected.contains(anchor));
+            listed[visible]
+                .iter()
+                .map(|(path, _)| Arc::clone(path))
+                .chain(focused)
+                .collect()
+        } else {
+            let shown = items.values().filter(|item| filter.matches(item)).count();
+            let layout = GridLayout::new(shown, viewport.width, icon_width);
+            let visible = if sections {
+                SectionLayout::new(
+                    ordered(items, order.as_deref())
+                        .filter(|(_, item)| filter.matches(item))
+                        .map(|(_, item)| index_letter(item.title())),
+                    &layout,
+                )
+                .visible_items(*scroll_offset, viewport.height)
+            } else {
+                layout.visible_items(*scroll_offset, viewport.height)
+            };
+            ordered(items, order.as_deref())
+                .filter(|(_, item)| filter.matches(item))
+                .skip(visible.start)
+                .take(visible.len())
+                .map(|(path, _)| Arc::clone(path))
+                .collect()
+        };
+
+        let requested = visible
+            .into_iter()
+            .filter(|path| match items.get(path).map(|item| &item.thumbnail) {
+                Some(Thumbnail::Pending) => true,
+                Some(Thumbnail::Loaded) => !cache.touch(path),
+                Some(Thumbnail::Unavailable | Thumbnail::Loading) | None => false,
+            })
+            // Collected so every visible loaded thumbnail is touched.
+            .collect::<Vec<_>>()
+            .into_iter()
+            .take(
+                if throttled {
+                    MAX_LOADING_THROTTLED
+                } else {
+                    MAX_LOADING
+                }
+                .saturating_sub(loading),
+            )
+            .collect::<Vec<_>>();
+        for path in &requested {
+            if let Some(item) = items.get_mut(path) {
+                item.thumbnail = Thumbnail::Loading;
+            }
+        }
+        requested
+    }
+
+    /// Update selection in response to an item being pressed with the given modifiers.
+    ///
+    /// Shift extends the selection from the anchor over items shown by filter, command
+    /// toggles the item, and without modifiers the item becomes the only selected item.
+    pub fn select(
+        &mut self,
+        path: &Arc<Path>,
+        filter: &Filter,
+        sort: Option<Sort>,
+        modifiers: Modifiers,
+    ) {
+        let DirView::Dir {
+            items,
+            selected,
+            anchor,
+            cursor,
+            order,
+            shelf,
+            ..
+        } = self
+        else {
+            return;
+        };
+        *cursor = Some(Arc::clone(path));
+        let shelf_order = shelf.as_ref().map(|rows| {
+            rows.iter()
+                .flat_map(|row| row.volumes.iter().cloned())
+                .collect::<Vec<_>>()
+        });
+        let sorted_order = sort.filter(|_| shelf.is_none()).map(|sort| {
+            list_view::listed(ordered(items, order.as_deref()), Some(sort))
+                .into_iter()
+                .map(|(path, _)| Arc::clone(path))
+                .collect::<Vec<_>>()
+        });
+
+        if modifiers.shift()
+            && let Some(from) = anchor.as_ref()
+        {
+            if !modifiers.command() {
+                selected.clear();
+            }
+            // Range is taken in the order items are shown, from whichever end comes first.
+            let mut in_range = false;
+            let shown_order = shelf_order
+                .as_deref()
+                .or(sorted_order.as_deref())
+                .or(order.as_deref());
+            for (item_path, item) in ordered(items, shown_order) {
+                let is_end = item_path == from || item_path == path;
+                if is_end || in_range {
+                    if filter.matches(item) {
+                        selected.insert(Arc::clone(item_path));
+                    }
+                    if is_end && (in_range || from == path) {
+                        break;
+                    }
+                    in_range = true;
+                }
+            }
+        } else if modifiers.command() {
+            if !selected.remove(path) {
+                selected.insert(Arc::clone(path));
+            }
+            *anchor = Some(Arc::clone(path));
+        } else {
+            selected.clear();
+            selected.insert(Arc::clone(path));
+            *anchor = Some(Arc::clone(path));
+        }
+    }
+
     /// View pane.
     pub fn view<'this>(
         &'this self,
-        icon_width: f32,
-        max_text_len: u16,
+        view_path: ViewPath,
+        filter: &'this Filter,
+        context_menu: Option<ContextMenu<'this>>,
+        cache: &'this ThumbnailCache<Shown>,
+        options: ViewOptions<'this>,
     ) -> impl Into<Element<'this, Message>> {
-        static PLACEHOLDER: LazyLock<widget::svg::Handle> = LazyLock::new(|| {
-            include_bytes!("./question.svg")
-                .as_slice()
-                .pipe(Cow::Borrowed)
-                .pipe(widget::svg::Handle::from_memory)
-        });
+        let ViewOptions {
+            icon_width,
+            max_text_len,
+            badges,
+            page_time,
+            recent,
+            mode,
+            sort,
+            size_units,
+            accents,
+            sections,
+            scanning,
+        } = options;
         match self {
-            DirView::Empty => widget::button("Open...")
+            DirView::Empty if scanning => skeleton(icon_width),
+            DirView::Empty => widget::Column::new()
+                .spacing(5)
+                .push(widget::button(widget::text(tr("pane-open"))))
+                .push((!recent.is_empty()).then(|| widget::text(tr("pane-recent")).size(14)))
+                .extend(recent.iter().map(|path| {
+                    let message = if ArchiveKind::from_path(path).is_some() {
+                        Message::OpenArchive(Arc::clone(path))
+                    } else {
+                        Message::OpenRecent(view_path, Arc::clone(path))
+                    };
+                    widget::button(widget::text(list_entry_name(path)).wrapping(Wrapping::None))
+                        .padding([2, 6])
+                        .width(Fill)
+                        .style(widget::button::text)
+                        .on_press(message)
+                        .into()
+                }))
                 .pipe(widget::container)
                 .padding(5)
+                .max_width(RECENT_WIDTH)
                 .style(widget::container::bordered_box)
                 .pipe(widget::container)
                 .padding(5)
                 .center(Fill),
-            DirView::Dir { items } => widget::responsive(move |size| {
-                let width = icon_width;
-                let columns = size.width.div_euclid(width);
-                widget::Grid::with_children(items.iter().map(|(_, Item { name, cover })| {
-                    if let Some(handle) = cover {
-                        widget::Stack::new().push(widget::image(handle).width(Fill).height(Fill))
-                    } else {
-                        widget::Stack::new()
-                            .push(widget::svg(PLACEHOLDER.clone()).width(Fill).height(Fill))
+            DirView::Dir {
+                items,
+                selected,
+                scroll_offset,
+                order,
+                shelf,
+                ..
+            } => widget::responsive(move |size| {
+                if let Some(rows) = shelf {
+                    return ShelfView {
+                        view_path,
+                        rows,
+                        items,
+                        selected,
+                        cursor: self.cursor(),
+                        filter,
+                        context_menu,
+                        cache,
+                        scroll_offset: *scroll_offset,
+                        icon_width,
+                        max_text_len,
+                        badges,
+                        page_time,
+                        accents,
                     }
-                    .push(
-                        widget::text(shorten_text(name, max_text_len.into()))
-                            .wrapping(Wrapping::None)
-                            .pipe(widget::container)
-                            .style(widget::container::bordered_box)
-                            .center_x(Fill)
-                            .padding(3)
-                            .pipe(widget::container)
-                            .padding(Padding {
-                                left: 5.0,
-                                right: 5.0,
-                                ..Padding::new(0.0)
-                            })
-                            .center_x(Fill)
-                            .align_bottom(Fill),
+                    .view(size);
+                }
+                if !mode.is_grid() {
+                    return ListView {
+                        view_path,
+                        listed: list_view::listed(
+                            ordered(items, order.as_deref())
+                                .filter(|(_, item)| filter.matches(item)),
+                            sort,
+                        ),
+                        selected,
+                        focused: self.focused(),
+                        cursor: self.cursor(),
+                        context_menu,
+                        reading_order: order.is_some() && sort.is_none(),
+                        cache,
+                        scroll_offset: *scroll_offset,
+                        sort,
+                        detail: mode.is_detail(),
+                        size_units,
+                    }
+                    .view(size);
+                }
+                let shown = ordered(items, order.as_deref())
+                    .filter(|(_, item)| filter.matches(item))
+                    .collect::<Vec<_>>();
+                let letters = shown
+                    .iter()
+                    .map(|(_, item)| index_letter(item.title()))
+                    .collect::<Vec<_>>();
+                // Letters are indexed in order of appearance, such that views in reading
+                // order also jump to the first item of a letter.
+                let index = letters.iter().fold(Vec::new(), |mut index, letter| {
+                    if !index.contains(letter) {
+                        index.push(*letter);
+                    }
+                    index
+                });
+                let index_width = if index.len() > 1 {
+                    LETTER_INDEX_WIDTH
+                } else {
+                    0.0
+                };
+                let layout = GridLayout::new(shown.len(), size.width - index_width, icon_width);
+                let section_layout =
+                    sections.then(|| SectionLayout::new(letters.iter().copied(), &layout));
+                let GridLayout {
+                    columns,
+                    width,
+                    row_height,
+                    rows,
+                } = layout;
+                let create_card = |&(path, item): &(&Arc<Path>, &'this Item)| {
+                    card(
+                        ItemPath {
+                            view_path,
+                            path: Arc::clone(path),
+                        },
+                        item,
+                        (
+                            cache.get(path).map(Shown::image),
+                            cache.accent_color(path).filter(|_| accents),
+                        ),
+                        (selected.contains(path), self.cursor() == Some(path)),
+                        max_text_len,
+                        badges,
                     )
-                    .pipe(Element::from)
-                }))
-                .spacing(3)
-                .columns(items.len().min(columns as usize))
-                .width(if items.len() < columns as usize {
-                    (items.len() as f32 + 1.0) * width
+                };
+
+                let grid = if let Some(section_layout) = &section_layout {
+                    let visible = section_layout.visible_rows(*scroll_offset, size.height);
+                    let top = section_layout
+                        .offsets
+                        .get(visible.start)
+                        .copied()
+                        .unwrap_or(section_layout.height);
+                    let bottom = visible.end.checked_sub(1).map_or(top, |last| {
+                        section_layout.offsets[last] + section_layout.row_height(last)
+                    });
+                    widget::Column::new()
+                        .push(widget::space().height(top))
+                        .extend(section_layout.rows[visible].iter().map(|(range, header)| {
+                            widget::Column::new()
+                                .push(header.map(|letter| {
+                                    widget::text(letter.to_string())
+                                        .size(16)
+                                        .pipe(widget::container)
+                                        .center_y(SECTION_HEADER_HEIGHT)
+                                        .padding([0, 3])
+                                }))
+                                .push(
+                                    widget::Grid::with_children(
+                                        shown[range.clone()].iter().map(create_card),
+                                    )
+                                    .spacing(GRID_SPACING)
+                                    .columns(columns)
+                                    .width(width),
+                                )
+                                .push(widget::space().height(GRID_SPACING))
+                                .into()
+                        }))
+                        .push(widget::space().height(section_layout.height - bottom))
                 } else {
-                    size.width
-                })
-                .into()
+                    let Range {
+                        start: first_row,
+                        end: last_row,
+                    } = layout.visible_rows(*scroll_offset, size.height);
+                    let cards = first_row * columns..(last_row * columns).min(shown.len());
+                    widget::Column::new()
+                        .push(widget::space().height(first_row as f32 * row_height))
+                        .push(
+                            widget::Grid::with_children(shown[cards].iter().map(create_card))
+                                .spacing(GRID_SPACING)
+                                .columns(columns)
+                                .width(width),
+                        )
+                        .push(widget::space().height((rows - last_row) as f32 * row_height))
+                };
+
+                let letter_index = (index.len() > 1).then(|| {
+                    widget::Column::new()
+                        .width(LETTER_INDEX_WIDTH)
+                        .align_x(Center)
+                        .extend(index.iter().map(|&letter| {
+                            widget::button(widget::text(letter.to_string()).size(11).center())
+                                .padding(0)
+                                .width(Fill)
+                                .style(widget::button::text)
+                                .on_press(Message::JumpToLetter(view_path, letter))
+                                .into()
+                        }))
+                        .pipe(widget::container)
+                        .center_y(Fill)
+                        .clip(true)
+                });
+
+                grid.pipe(widget::scrollable)
+                    .id(scroll_id(view_path))
+                    .on_scroll(move |viewport| Message::PaneScrolled {
+                        view_path,
+                        offset: viewport.absolute_offset().y,
+                        viewport: viewport.bounds().size(),
+                    })
+                    .pipe(|grid| widget::Stack::new().push(grid))
+                    .extend(
+                        context_menu
+                            .and_then(|menu| {
+                                shown
+                                    .iter()
+                                    .position(|(shown, _)| *shown == menu.path)
+                                    .map(|index| (menu, index))
+                            })
+                            .map(|(menu, index)| {
+                                let (column, top) = match &section_layout {
+                                    Some(section_layout) => {
+                                        let row = section_layout.row_of(index);
+                                        let (range, header) = &section_layout.rows[row];
+                                        (
+                                            index - range.start,
+                                            section_layout.offsets[row]
+                                                + header.map_or(0.0, |_| SECTION_HEADER_HEIGHT),
+                                        )
+                                    }
+                                    None => {
+                                        (index % columns, (index / columns) as f32 * row_height)
+                                    }
+                                };
+                                // Menu is placed at center of card, kept within view.
+                                let x = column as f32 * row_height + row_height / 2.0;
+                                let y = top + row_height / 2.0 - *scroll_offset;
+                                let entries = menu.entries(order.is_some()) as f32;
+                                let height = entries * CONTEXT_MENU_ENTRY_HEIGHT + 6.0;
+                                widget::pin(item_menu(view_path, menu, order.is_some()))
+                                    .x(x.min(size.width - CONTEXT_MENU_WIDTH).max(0.0))
+                                    .y(y.min(size.height - height).max(0.0))
+                                    .into()
+                            }),
+                    )
+                    .pipe(|grid| widget::Row::new().push(grid).push(letter_index))
+                    .into()
             })
-            .pipe(widget::scrollable)
             .pipe(widget::container)
             .padding(Padding {
                 bottom: 0.0,
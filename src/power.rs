@@ -0,0 +1,37 @@
+//! Power source monitoring through UPower.
+
+use ::futures::{SinkExt, Stream, StreamExt, channel::mpsc::Sender};
+use ::zbus::{Connection, Proxy};
+
+/// Watch UPower for changes to whether the system is running on battery, sending the
+/// current state and every change to output.
+///
+/// # Errors
+/// If the system bus or UPower cannot be reached.
+async fn watch(output: &mut Sender<bool>) -> ::zbus::Result<()> {
+    let connection = Connection::system().await?;
+    let proxy = Proxy::new(
+        &connection,
+        "org.freedesktop.UPower",
+        "/org/freedesktop/UPower",
+        "org.freedesktop.UPower",
+    )
+    .await?;
+
+    let mut changes = proxy.receive_property_changed::<bool>("OnBattery").await;
+    _ = output.send(proxy.get_property("OnBattery").await?).await;
+    while let Some(change) = changes.next().await {
+        _ = output.send(change.get().await?).await;
+    }
+    Ok(())
+}
+
+/// Stream of whether the system is running on battery, if UPower is unavailable the
+/// system is assumed to not be running on battery.
+pub fn on_battery() -> impl Stream<Item = bool> {
+    ::iced::stream::channel(1, async |mut output| {
+        if let Err(err) = watch(&mut output).await {
+            ::log::info!("could not watch power source through upower\n{err}");
+        }
+    })
+}
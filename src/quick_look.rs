@@ -0,0 +1,103 @@
+//! [QuickLook] impl, a popup previewing an archive without opening a reader.
+
+use ::std::{path::Path, sync::Arc};
+
+use ::iced::{
+    Alignment::Center,
+    Color, Element,
+    Length::{Fill, FillPortion},
+    widget::{self, image},
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message, ViewPath,
+    i18n::{tr, tr_args},
+    list_entry_name,
+};
+
+/// Amount of pages loaded for a quick look, including the cover.
+pub const QUICK_LOOK_PAGES: usize = 5;
+
+/// Preview of the cover and first pages of an archive, shown over a pane.
+#[derive(Debug, Clone)]
+pub struct QuickLook {
+    /// Path of previewed archive.
+    pub path: Arc<Path>,
+    /// Amount of pages in archive, if loaded.
+    pub page_count: Option<usize>,
+    /// Loaded pages, starting with the cover.
+    pub pages: Vec<image::Handle>,
+}
+
+impl QuickLook {
+    /// Create a quick look of archive at path, with pages not yet loaded.
+    pub const fn new(path: Arc<Path>) -> Self {
+        Self {
+            path,
+            page_count: None,
+            pages: Vec::new(),
+        }
+    }
+
+    /// View quick look over a pane, pressing outside of it closes it.
+    pub fn view(&self, view_path: ViewPath) -> Element<'_, Message> {
+        let mut pages = self.pages.iter();
+        let cover: Element<'_, Message> = match pages.next() {
+            Some(cover) => widget::image(cover).width(Fill).height(Fill).into(),
+            None => widget::text(tr("common-loading"))
+                .pipe(widget::container)
+                .center(Fill)
+                .into(),
+        };
+        let title = match self.page_count {
+            Some(page_count) => tr_args(
+                "pane-quick-look-title",
+                &[
+                    ("name", &list_entry_name(&self.path)),
+                    ("count", &page_count.to_string()),
+                ],
+            ),
+            None => list_entry_name(&self.path),
+        };
+
+        widget::Column::new()
+            .spacing(5)
+            .push(
+                widget::Row::new()
+                    .spacing(5)
+                    .align_y(Center)
+                    .push(widget::text(title).width(Fill))
+                    .push(
+                        widget::button(widget::text(tr("pane-read")))
+                            .padding([2, 6])
+                            .on_press(Message::OpenArchive(Arc::clone(&self.path))),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("pane-close")))
+                            .padding([2, 6])
+                            .style(widget::button::secondary)
+                            .on_press(Message::CloseQuickLook(view_path)),
+                    ),
+            )
+            .push(widget::container(cover).height(FillPortion(3)))
+            .push(
+                widget::Row::new()
+                    .spacing(5)
+                    .height(FillPortion(1))
+                    .extend(pages.map(|page| widget::image(page).width(Fill).height(Fill).into())),
+            )
+            .padding(10)
+            .pipe(widget::container)
+            .style(widget::container::bordered_box)
+            .max_width(720)
+            .pipe(widget::opaque)
+            .pipe(widget::container)
+            .padding(20)
+            .center(Fill)
+            .style(|_| widget::container::background(Color::BLACK.scale_alpha(0.5)))
+            .pipe(widget::mouse_area)
+            .on_press(Message::CloseQuickLook(view_path))
+            .pipe(widget::opaque)
+    }
+}
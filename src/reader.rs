@@ -0,0 +1,694 @@
+//! [Reader] impl.
+
+use ::core::{ops::Range, time::Duration};
+use ::std::{collections::BTreeSet, path::Path, sync::Arc, time::Instant};
+
+use ::hashbrown::HashMap;
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    Size,
+    widget::{self, image},
+    window,
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message,
+    animation::Animation,
+    archive::Adjustments,
+    cache::{Shown, ThumbnailCache},
+    gesture::{Gestures, Turn},
+    i18n::{tr, tr_args},
+};
+
+/// Width of bookmark sidebar.
+const BOOKMARKS_WIDTH: f32 = 120.0;
+
+/// Width of a page in the page strip.
+const STRIP_CELL: f32 = 76.0;
+
+/// Height of the page strip, without its scrollbar.
+const STRIP_HEIGHT: f32 = 112.0;
+
+/// Pages on either side of those visible in the page strip whose thumbnails are loaded
+/// ahead of scrolling.
+const STRIP_MARGIN: usize = 4;
+
+/// Width assumed for the page strip until it reports its width when scrolled.
+const STRIP_DEFAULT_WIDTH: f32 = 800.0;
+
+/// Size of square page thumbnails of the page strip are downscaled to fit within.
+pub const PAGE_THUMBNAIL_SIZE: u32 = 160;
+
+/// Interval slideshows are checked for pages due to be turned at.
+pub const SLIDESHOW_TICK: Duration = Duration::from_millis(250);
+
+/// Size assumed for continuous layout until it reports its size when scrolled.
+const CONTINUOUS_DEFAULT_SIZE: Size = Size::new(800.0, 600.0);
+
+/// Height relative to width assumed for pages in continuous layout until loaded.
+const DEFAULT_ASPECT: f32 = 1.5;
+
+/// Pages on either side of those visible in continuous layout which are loaded ahead of
+/// scrolling, pages further away are dropped.
+const CONTINUOUS_MARGIN: usize = 2;
+
+/// Get id of the scrollable of the page strip of a reader.
+pub fn strip_id(window_id: window::Id) -> widget::Id {
+    widget::Id::from(format!("page-strip-{window_id:?}"))
+}
+
+/// Get id of the scrollable of the continuous layout of a reader.
+pub fn continuous_id(window_id: window::Id) -> widget::Id {
+    widget::Id::from(format!("continuous-{window_id:?}"))
+}
+
+/// Page thumbnails shown below the page of a reader, for jumping between pages.
+#[derive(Debug, Clone, Default)]
+pub struct PageStrip {
+    /// Horizontal scroll offset of strip.
+    pub offset: f32,
+    /// Width of strip, known once it has been scrolled.
+    pub width: Option<f32>,
+    /// Pages whose thumbnails are being loaded.
+    pub loading: BTreeSet<usize>,
+}
+
+impl PageStrip {
+    /// Get pages visible in strip of an archive with the given amount of pages, along
+    /// with [STRIP_MARGIN] pages on either side.
+    pub fn visible(&self, page_count: usize) -> Range<usize> {
+        let width = self.width.unwrap_or(STRIP_DEFAULT_WIDTH);
+        let first = (self.offset / STRIP_CELL).floor() as usize;
+        let last = ((self.offset + width) / STRIP_CELL).ceil() as usize;
+        first.saturating_sub(STRIP_MARGIN).min(page_count)
+            ..last.saturating_add(STRIP_MARGIN).min(page_count)
+    }
+
+    /// Get offset scrolling the strip such that page is centered, [None] if the page is
+    /// already fully visible.
+    pub fn reveal(&self, page: usize) -> Option<f32> {
+        let width = self.width.unwrap_or(STRIP_DEFAULT_WIDTH);
+        let start = page as f32 * STRIP_CELL;
+        (start < self.offset || start + STRIP_CELL > self.offset + width)
+            .then(|| (start + STRIP_CELL / 2.0 - width / 2.0).max(0.0))
+    }
+}
+
+/// Pages of a reader stitched into one vertical scroll at the width of the reader, as is
+/// common for webtoons.
+#[derive(Debug, Clone, Default)]
+pub struct Continuous {
+    /// Vertical scroll offset.
+    pub offset: f32,
+    /// Size of visible area, known once it has been scrolled.
+    pub size: Option<Size>,
+    /// Scrolling to a page, such that reported offsets are outdated until done.
+    pub jumping: bool,
+    /// Images of pages near the visible area.
+    pub pages: HashMap<usize, image::Handle>,
+    /// Width and height of pages in pixels, kept after their images are dropped.
+    pub dimensions: HashMap<usize, (u32, u32)>,
+    /// Pages being loaded.
+    pub loading: BTreeSet<usize>,
+}
+
+impl Continuous {
+    /// Get size of visible area, or the assumed size if not yet known.
+    fn size(&self) -> Size {
+        self.size.unwrap_or(CONTINUOUS_DEFAULT_SIZE)
+    }
+
+    /// Get height of a page, pages of unknown size use [DEFAULT_ASPECT].
+    pub fn height(&self, page: usize) -> f32 {
+        let width = self.size().width;
+        match self.dimensions.get(&page) {
+            Some(&(page_width, page_height)) if page_width > 0 => {
+                width * page_height as f32 / page_width as f32
+            }
+            _ => width * DEFAULT_ASPECT,
+        }
+    }
+
+    /// Get offset of top of a page.
+    pub fn top(&self, page: usize) -> f32 {
+        (0..page).map(|page| self.height(page)).sum()
+    }
+
+    /// Get pages visible in an archive with the given amount of pages, along with
+    /// [CONTINUOUS_MARGIN] pages on either side.
+    pub fn visible(&self, page_count: usize) -> Range<usize> {
+        let bottom = self.offset + self.size().height;
+        let (mut first, mut last, mut top) = (page_count, page_count, 0.0);
+        for page in 0..page_count {
+            let height = self.height(page);
+            if first == page_count && top + height > self.offset {
+                first = page;
+            }
+            if top >= bottom {
+                last = page;
+                break;
+            }
+            top += height;
+        }
+        first.saturating_sub(CONTINUOUS_MARGIN)
+            ..last.saturating_add(CONTINUOUS_MARGIN).min(page_count)
+    }
+
+    /// Get page at top of visible area, or the last page once scrolled to the end.
+    pub fn current(&self, page_count: usize) -> usize {
+        let last = page_count.saturating_sub(1);
+        if self.offset + self.size().height >= self.top(page_count) - 1.0 {
+            return last;
+        }
+        let mut top = 0.0;
+        (0..page_count)
+            .find(|&page| {
+                top += self.height(page);
+                top > self.offset + 1.0
+            })
+            .unwrap_or(last)
+    }
+
+    /// Insert a loaded page of the given width and height in pixels, pages above the
+    /// visible area changing height move the offset along with them. Returns true if
+    /// the offset changed.
+    pub fn insert(
+        &mut self,
+        page: usize,
+        image: image::Handle,
+        dimensions: Option<(u32, u32)>,
+    ) -> bool {
+        self.pages.insert(page, image);
+        let Some(dimensions) = dimensions else {
+            return false;
+        };
+        let (top, before) = (self.top(page), self.height(page));
+        self.dimensions.insert(page, dimensions);
+        let change = self.height(page) - before;
+        if top < self.offset && change != 0.0 {
+            self.offset += change;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set size of visible area, page heights follow its width such that the offset is
+    /// scaled along with it. Returns true if the offset changed.
+    pub fn resize(&mut self, size: Size) -> bool {
+        let previous = self.size.replace(size);
+        match previous {
+            Some(previous) if previous.width > 0.0 && previous.width != size.width => {
+                self.offset *= size.width / previous.width;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Page loaded for continuous layout.
+#[derive(Debug, Clone)]
+pub struct LoadedPage {
+    /// Index of page.
+    pub page: usize,
+    /// Image of page.
+    pub image: image::Handle,
+    /// Width and height of page in pixels, if known.
+    pub dimensions: Option<(u32, u32)>,
+}
+
+/// First page of the next volume of a reader, loaded ahead of time.
+#[derive(Debug, Clone)]
+pub struct Prefetched {
+    /// Amount of pages in volume.
+    pub page_count: usize,
+    /// Image of first page.
+    pub image: image::Handle,
+    /// Width and height of first page in pixels, if known.
+    pub dimensions: Option<(u32, u32)>,
+    /// Frames of first page if animated.
+    pub animation: Option<Animation>,
+}
+
+/// State of a reader window.
+#[derive(Debug, Clone)]
+pub struct Reader {
+    /// Path of archive being read.
+    pub path: Arc<Path>,
+    /// Index of current page.
+    pub page: usize,
+    /// Amount of pages in archive, if known.
+    pub page_count: Option<usize>,
+    /// Image of current page, if loaded.
+    pub image: Option<image::Handle>,
+    /// Frames of current page if animated, shown instead of its still image.
+    pub animation: Option<Animation>,
+    /// Window is fullscreen, hiding page navigation.
+    pub fullscreen: bool,
+    /// Volume following archive in reading order of its series, found when the last
+    /// page is reached.
+    pub next_volume: Option<Arc<Path>>,
+    /// First page of next volume, if prefetched.
+    pub prefetched: Option<Prefetched>,
+    /// Time current page was shown, used to measure reading speed.
+    pub page_shown: Option<Instant>,
+    /// Bookmarked pages, listed in a sidebar.
+    pub bookmarks: BTreeSet<usize>,
+    /// Zoom and pan of current page, along with touches on it.
+    pub gestures: Gestures,
+    /// Page strip, if shown.
+    pub strip: Option<PageStrip>,
+    /// Adjustments applied to pages as they are loaded.
+    pub adjustments: Adjustments,
+    /// Adjustments are remembered for archive.
+    pub remember_adjustments: bool,
+    /// Adjustment controls are shown.
+    pub adjusting: bool,
+    /// Continuous layout, if pages are stitched into one vertical scroll.
+    pub continuous: Option<Continuous>,
+    /// Time slideshow turns to the next page at, if running.
+    pub slideshow: Option<Instant>,
+}
+
+impl Reader {
+    /// Create a new reader for archive at path.
+    pub fn new(path: Arc<Path>) -> Self {
+        Self {
+            path,
+            page: 0,
+            page_count: None,
+            image: None,
+            animation: None,
+            fullscreen: false,
+            next_volume: None,
+            prefetched: None,
+            page_shown: None,
+            bookmarks: BTreeSet::new(),
+            gestures: Gestures::default(),
+            strip: None,
+            adjustments: Adjustments::default(),
+            remember_adjustments: false,
+            adjusting: false,
+            continuous: None,
+            slideshow: None,
+        }
+    }
+
+    /// Get index of next page, if any.
+    pub fn next_page(&self) -> Option<usize> {
+        let next = self.page.checked_add(1)?;
+        self.page_count
+            .is_none_or(|count| next < count)
+            .then_some(next)
+    }
+
+    /// Get message going forward from current page, to the next page or at the last page
+    /// to the next volume, if any.
+    pub fn forward(&self, window_id: window::Id) -> Option<Message> {
+        self.next_page()
+            .map(|page| Message::GotoPage { window_id, page })
+            .or_else(|| {
+                self.next_volume
+                    .as_ref()
+                    .map(|_| Message::NextVolume(window_id))
+            })
+    }
+
+    /// Get index of previous page, if any.
+    pub const fn prev_page(&self) -> Option<usize> {
+        self.page.checked_sub(1)
+    }
+
+    /// Get message advancing a slideshow from current page, going back to the first page
+    /// after the last one if looping and otherwise going forward, [None] if there is
+    /// nothing left to show.
+    pub fn advance_slideshow(&self, window_id: window::Id, looping: bool) -> Option<Message> {
+        if !looping {
+            return self.forward(window_id);
+        }
+        self.next_page()
+            .or_else(|| (self.page > 0).then_some(0))
+            .map(|page| Message::GotoPage { window_id, page })
+    }
+
+    /// Get message turning page as requested by a gesture, if there is a page to turn to.
+    pub fn turn(&self, window_id: window::Id, turn: Turn) -> Option<Message> {
+        match turn {
+            Turn::Forward => self.forward(window_id),
+            Turn::Back => self
+                .prev_page()
+                .map(|page| Message::GotoPage { window_id, page }),
+        }
+    }
+
+    /// View page strip, pages without a loaded thumbnail show their number.
+    fn view_strip<'a>(
+        &'a self,
+        window_id: window::Id,
+        strip: &PageStrip,
+        cache: &'a ThumbnailCache<Shown>,
+    ) -> Element<'a, Message> {
+        let count = self.page_count.unwrap_or(0);
+        let visible = strip.visible(count);
+        widget::Row::new()
+            .push(widget::space().width(visible.start as f32 * STRIP_CELL))
+            .extend(visible.clone().map(|page| {
+                let thumbnail = match cache.page(&self.path, page).map(Shown::image) {
+                    Some(handle) => widget::image(handle)
+                        .width(Fill)
+                        .height(Fill)
+                        .pipe(Element::from),
+                    None => widget::space().width(Fill).height(Fill).into(),
+                };
+                widget::Column::new()
+                    .align_x(Center)
+                    .push(thumbnail)
+                    .push(widget::text((page + 1).to_string()).size(10))
+                    .pipe(widget::button)
+                    .padding(3)
+                    .width(STRIP_CELL)
+                    .height(STRIP_HEIGHT)
+                    .style(if page == self.page {
+                        widget::button::secondary
+                    } else {
+                        widget::button::text
+                    })
+                    .on_press(Message::GotoPage { window_id, page })
+                    .into()
+            }))
+            .push(widget::space().width((count - visible.end) as f32 * STRIP_CELL))
+            .pipe(widget::scrollable)
+            .horizontal()
+            .id(strip_id(window_id))
+            .on_scroll(move |viewport| Message::PageStripScrolled {
+                window_id,
+                offset: viewport.absolute_offset().x,
+                width: viewport.bounds().width,
+            })
+            .into()
+    }
+
+    /// View pages in continuous layout, pages near the visible area are shown once
+    /// loaded and others are left as space.
+    fn view_continuous(
+        &self,
+        window_id: window::Id,
+        continuous: &Continuous,
+    ) -> Element<'_, Message> {
+        let count = self.page_count.unwrap_or(0);
+        let visible = continuous.visible(count);
+        let below = (visible.end..count)
+            .map(|page| continuous.height(page))
+            .sum::<f32>();
+        widget::Column::new()
+            .push(widget::space().height(continuous.top(visible.start)))
+            .extend(visible.map(|page| {
+                let height = continuous.height(page);
+                match continuous.pages.get(&page) {
+                    Some(handle) => widget::image(handle)
+                        .width(Fill)
+                        .height(height)
+                        .pipe(Element::from),
+                    None => widget::text(tr("common-loading"))
+                        .pipe(widget::container)
+                        .center_x(Fill)
+                        .center_y(height)
+                        .into(),
+                }
+            }))
+            .push(widget::space().height(below))
+            .pipe(widget::scrollable)
+            .id(continuous_id(window_id))
+            .width(Fill)
+            .height(Fill)
+            .on_scroll(move |viewport| Message::ContinuousScrolled {
+                window_id,
+                offset: viewport.absolute_offset().y,
+                size: viewport.bounds().size(),
+            })
+            .into()
+    }
+
+    /// View controls adjusting pages, sliders apply adjustments once released.
+    fn view_adjustments(&self, window_id: window::Id) -> Element<'_, Message> {
+        let adjustments = self.adjustments;
+        let slider = |label, range, value, set: fn(Adjustments, f32) -> Adjustments| {
+            widget::Row::new()
+                .spacing(3)
+                .align_y(Center)
+                .push(widget::text(label))
+                .push(
+                    widget::slider(range, value, move |value| {
+                        Message::SetAdjustments(window_id, set(adjustments, value))
+                    })
+                    .step(0.01)
+                    .on_release(Message::ApplyAdjustments(window_id))
+                    .width(120),
+                )
+        };
+        widget::Row::new()
+            .spacing(10)
+            .padding(5)
+            .align_y(Center)
+            .push(slider(
+                tr("reader-brightness"),
+                Adjustments::BRIGHTNESS,
+                adjustments.brightness,
+                |adjustments, brightness| Adjustments {
+                    brightness,
+                    ..adjustments
+                },
+            ))
+            .push(slider(
+                tr("reader-contrast"),
+                Adjustments::CONTRAST,
+                adjustments.contrast,
+                |adjustments, contrast| Adjustments {
+                    contrast,
+                    ..adjustments
+                },
+            ))
+            .push(slider(
+                tr("reader-gamma"),
+                Adjustments::GAMMA,
+                adjustments.gamma,
+                |adjustments, gamma| Adjustments {
+                    gamma,
+                    ..adjustments
+                },
+            ))
+            .push(
+                widget::checkbox(adjustments.invert)
+                    .label(tr("reader-invert"))
+                    .on_toggle(move |invert| {
+                        Message::AdjustPages(
+                            window_id,
+                            Adjustments {
+                                invert,
+                                ..adjustments
+                            },
+                        )
+                    }),
+            )
+            .push(
+                widget::checkbox(self.remember_adjustments)
+                    .label(tr("reader-remember-adjustments"))
+                    .on_toggle(move |remember| Message::RememberAdjustments(window_id, remember)),
+            )
+            .push(widget::space::horizontal())
+            .push(
+                widget::button(widget::text(tr("reader-reset")))
+                    .padding(3)
+                    .style(widget::button::secondary)
+                    .on_press_maybe(
+                        (!adjustments.is_neutral())
+                            .then(|| Message::AdjustPages(window_id, Adjustments::default())),
+                    ),
+            )
+            .into()
+    }
+
+    /// View reader, with page thumbnails of the page strip from cache.
+    pub fn view<'a>(
+        &'a self,
+        window_id: window::Id,
+        cache: &'a ThumbnailCache<Shown>,
+    ) -> Element<'a, Message> {
+        let frame = self.animation.as_ref().map(Animation::frame);
+        let goto =
+            move |page: Option<usize>| page.map(|page| Message::GotoPage { window_id, page });
+
+        // Size of the page area is needed to pan by the distance fingers moved.
+        let page = widget::responsive(move |area| {
+            let page = match frame.or(self.image.as_ref()) {
+                Some(handle) => {
+                    let image = widget::image(handle).width(Fill).height(Fill);
+                    match self.gestures.crop() {
+                        Some(crop) => image.crop(crop),
+                        None => image,
+                    }
+                    .pipe(Element::from)
+                }
+                None => widget::text(tr("common-loading"))
+                    .pipe(widget::container)
+                    .center(Fill)
+                    .into(),
+            };
+
+            // Pages are turned on release, such that swipes and pinches do not turn them.
+            let mut page = widget::mouse_area(page)
+                .on_press(Message::ReaderPressed { window_id, area })
+                .on_release(Message::ReaderReleased(window_id))
+                .on_scroll(move |delta| Message::ReaderScrolled {
+                    window_id,
+                    delta,
+                    area,
+                });
+            if let Some(message) = goto(self.prev_page()) {
+                page = page.on_right_press(message);
+            }
+            Element::from(page)
+        });
+
+        let page = match &self.continuous {
+            Some(continuous) => self.view_continuous(window_id, continuous),
+            None => Element::from(page),
+        };
+
+        if self.fullscreen {
+            return page;
+        }
+
+        let bookmarks = (!self.bookmarks.is_empty()).then(|| {
+            widget::Column::new()
+                .spacing(3)
+                .padding(5)
+                .push(widget::text(tr("reader-bookmarks")).size(14))
+                .extend(self.bookmarks.iter().map(|&page| {
+                    widget::button(widget::text(tr_args(
+                        "common-page",
+                        &[("page", &(page + 1).to_string())],
+                    )))
+                    .padding([2, 6])
+                    .width(Fill)
+                    .style(if page == self.page {
+                        widget::button::secondary
+                    } else {
+                        widget::button::text
+                    })
+                    .on_press(Message::GotoPage { window_id, page })
+                    .into()
+                }))
+                .width(BOOKMARKS_WIDTH)
+                .pipe(widget::scrollable)
+                .height(Fill)
+        });
+
+        let strip = self
+            .strip
+            .as_ref()
+            .map(|strip| self.view_strip(window_id, strip, cache));
+
+        let adjustments = self.adjusting.then(|| self.view_adjustments(window_id));
+
+        widget::Column::new()
+            .push(widget::Row::new().push(page).push(bookmarks).height(Fill))
+            .push(strip)
+            .push(adjustments)
+            .push(
+                widget::Row::new()
+                    .spacing(3)
+                    .padding(5)
+                    .align_y(Center)
+                    .push(
+                        widget::button("<")
+                            .padding(3)
+                            .on_press_maybe(goto(self.prev_page())),
+                    )
+                    .push(widget::space::horizontal())
+                    .push(widget::text(tr_args(
+                        "common-page-of",
+                        &[
+                            ("page", &(self.page + 1).to_string()),
+                            (
+                                "count",
+                                &self
+                                    .page_count
+                                    .map_or_else(|| "?".to_owned(), |count| count.to_string()),
+                            ),
+                        ],
+                    )))
+                    .push(widget::space::horizontal())
+                    .push(
+                        widget::button(widget::text(tr(if self.bookmarks.contains(&self.page) {
+                            "reader-remove-bookmark"
+                        } else {
+                            "reader-bookmark"
+                        })))
+                        .padding(3)
+                        .style(widget::button::secondary)
+                        .on_press(Message::ToggleBookmark(window_id)),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("reader-adjust")))
+                            .padding(3)
+                            .style(if self.adjusting {
+                                widget::button::primary
+                            } else {
+                                widget::button::secondary
+                            })
+                            .on_press(Message::ToggleAdjustments(window_id)),
+                    )
+                    .push(
+                        widget::button(widget::text(tr(if self.slideshow.is_some() {
+                            "reader-stop-slideshow"
+                        } else {
+                            "reader-slideshow"
+                        })))
+                        .padding(3)
+                        .style(if self.slideshow.is_some() {
+                            widget::button::primary
+                        } else {
+                            widget::button::secondary
+                        })
+                        .on_press(Message::ToggleSlideshow(window_id)),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("reader-scroll")))
+                            .padding(3)
+                            .style(if self.continuous.is_some() {
+                                widget::button::primary
+                            } else {
+                                widget::button::secondary
+                            })
+                            .on_press(Message::ToggleContinuous(window_id)),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("reader-pages")))
+                            .padding(3)
+                            .style(if self.strip.is_some() {
+                                widget::button::primary
+                            } else {
+                                widget::button::secondary
+                            })
+                            .on_press(Message::TogglePageStrip(window_id)),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("reader-use-as-cover")))
+                            .padding(3)
+                            .style(widget::button::secondary)
+                            .on_press(Message::UseAsCover(window_id)),
+                    )
+                    .push(
+                        widget::button(">")
+                            .padding(3)
+                            .on_press_maybe(self.forward(window_id)),
+                    ),
+            )
+            .into()
+    }
+}
@@ -0,0 +1,148 @@
+//! [Rename] impl, renaming archives, moving them to another directory or extracting them
+//! into one.
+
+use ::std::{
+    path::{MAIN_SEPARATOR, Path, PathBuf},
+    sync::Arc,
+};
+
+use ::derive_more::IsVariant;
+
+use crate::{
+    Message, ViewPath,
+    dialog::{Dialog, DialogId},
+    i18n::{tr, tr_args},
+    list_entry_name,
+};
+
+/// What the rename dialog does with its archive.
+#[derive(Debug, Clone, IsVariant)]
+pub enum RenameKind {
+    /// Archive is renamed within its directory.
+    Rename,
+    /// Archive is moved to another directory.
+    Move,
+    /// Archives are extracted into another directory.
+    Extract(Vec<Arc<Path>>),
+}
+
+/// Renaming of an archive, moving it to another directory or extracting archives into
+/// one, asked for by a prompt dialog over the window of a pane.
+#[derive(Debug, Clone)]
+pub struct Rename {
+    /// Archive being renamed or moved, or first archive extracted.
+    pub path: Arc<Path>,
+    /// What is done with archive.
+    pub kind: RenameKind,
+    /// New file name, or directory archive is moved or extracted to, initially entered.
+    pub text: String,
+    /// Dialog asking for new name or directory, [None] until shown.
+    pub dialog: Option<DialogId>,
+}
+
+impl Rename {
+    /// Create a dialog renaming an archive, starting from its current name.
+    pub fn new(path: Arc<Path>) -> Self {
+        let text = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        Self {
+            path,
+            kind: RenameKind::Rename,
+            text,
+            dialog: None,
+        }
+    }
+
+    /// Create a dialog moving an archive, starting from the directory it is in.
+    pub fn move_to(path: Arc<Path>) -> Self {
+        Self::with_dir(path, RenameKind::Move)
+    }
+
+    /// Create a dialog extracting archives, starting from the directory the first one is
+    /// in. [None] if there are no archives.
+    pub fn extract_to(paths: Vec<Arc<Path>>) -> Option<Self> {
+        let path = Arc::clone(paths.first()?);
+        Some(Self::with_dir(path, RenameKind::Extract(paths)))
+    }
+
+    /// Create a dialog of given kind, starting from the directory archive is in.
+    fn with_dir(path: Arc<Path>, kind: RenameKind) -> Self {
+        let text = path
+            .parent()
+            .unwrap_or(Path::new(""))
+            .to_string_lossy()
+            .into_owned();
+        Self {
+            path,
+            kind,
+            text,
+            dialog: None,
+        }
+    }
+
+    /// Get path archive is renamed or moved to, or directory archives are extracted to,
+    /// from entered text, [None] if nothing has been entered.
+    pub fn target(&self, text: &str) -> Option<PathBuf> {
+        let text = text.trim();
+        // Names are kept within the directory of the archive.
+        if text.is_empty() || (self.kind.is_rename() && text.contains(MAIN_SEPARATOR)) {
+            return None;
+        }
+        Some(match self.kind {
+            RenameKind::Rename => self.path.with_file_name(text),
+            RenameKind::Move => Path::new(text).join(self.path.file_name()?),
+            RenameKind::Extract(..) => PathBuf::from(text),
+        })
+    }
+
+    /// Check if entered text names a target other than the archive itself, archives may
+    /// be extracted next to themselves.
+    pub fn accepts(&self, text: &str) -> bool {
+        self.target(text)
+            .is_some_and(|target| self.kind.is_extract() || *target != *self.path)
+    }
+
+    /// Create prompt dialog asking for new name or directory over the window of a pane.
+    pub fn dialog(&self, view_path: ViewPath) -> Dialog {
+        let name = list_entry_name(&self.path);
+        let (title, action) = match &self.kind {
+            RenameKind::Rename => (
+                tr_args("dialog-rename", &[("name", &name)]),
+                "button-rename",
+            ),
+            RenameKind::Move => (tr_args("dialog-move", &[("name", &name)]), "button-move"),
+            RenameKind::Extract(paths) if paths.len() > 1 => (
+                tr_args(
+                    "dialog-extract-archives",
+                    &[("count", &paths.len().to_string())],
+                ),
+                "button-extract",
+            ),
+            RenameKind::Extract(..) => (
+                tr_args("dialog-extract", &[("name", &name)]),
+                "button-extract",
+            ),
+        };
+        let placeholder = if self.kind.is_rename() {
+            "dialog-name"
+        } else {
+            "dialog-directory"
+        };
+        let rename = self.clone();
+        let dialog = Dialog::prompt(view_path.window_id, title, tr(placeholder), move |text| {
+            Message::ConfirmRename(view_path, text)
+        })
+        .value(&self.text)
+        .action(tr(action))
+        .validate(move |text| rename.accepts(text))
+        .on_cancel(Message::CancelRename(view_path));
+        if !self.kind.is_rename() && cfg!(feature = "dbus") {
+            dialog.browse(Message::BrowseMoveTarget(view_path))
+        } else {
+            dialog
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Scanning of directories for the entries listed in panes. Once an entry cannot be
+//! reached the rest are not waited for, as the file system is likely unreachable as a
+//! whole, such that they are reported as offline instead.
+
+use ::core::sync::atomic::{AtomicBool, Ordering};
+use ::std::{fs::Metadata, io, path::Path, sync::Arc};
+
+use ::smol::{
+    fs::{DirEntry, ReadDir},
+    stream::StreamExt,
+};
+
+use crate::{
+    ignore::{self, IgnoreRules},
+    io_retry,
+};
+
+/// Entry found when scanning a directory.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// Path of entry.
+    pub path: Arc<Path>,
+    /// Name entry is listed by, its file name following the prefix of the scan.
+    pub name: String,
+    /// Metadata of entry, [None] if it could not be read.
+    pub metadata: Option<Metadata>,
+    /// File system of entry became unreachable while scanning.
+    pub offline: bool,
+}
+
+/// Scan of a directory, reading its entries.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    /// Rules of which entries are skipped.
+    ignore: IgnoreRules,
+    /// Prefix of names of entries, such as the name of a parent directory.
+    prefix: Option<Arc<str>>,
+    /// An entry could not be reached.
+    unreachable: Arc<AtomicBool>,
+}
+
+impl Scanner {
+    /// Create a scan skipping entries by ignore rules, prefixing the names of entries if
+    /// given a prefix.
+    pub fn new(ignore: IgnoreRules, prefix: Option<Arc<str>>) -> Self {
+        Self {
+            ignore,
+            prefix,
+            unreachable: Arc::default(),
+        }
+    }
+
+    /// Read an entry of the scanned directory, [None] if it is skipped.
+    pub async fn entry(&self, entry: DirEntry) -> Option<Entry> {
+        let path = Arc::<Path>::from(entry.path());
+        let is_symlink = entry
+            .file_type()
+            .await
+            .is_ok_and(|file_type| file_type.is_symlink());
+        if self.ignore.ignores_name(&path) || (is_symlink && !self.ignore.follows_symlinks()) {
+            return None;
+        }
+        let metadata = if self.unreachable.load(Ordering::Relaxed) {
+            None
+        } else {
+            io_retry::retry(|| entry.metadata())
+                .await
+                .inspect_err(|err| {
+                    if io_retry::is_unreachable(err) {
+                        ::log::warn!("{path:?} is unreachable\n{err}");
+                        self.unreachable.store(true, Ordering::Relaxed);
+                    }
+                })
+                .ok()
+        };
+        let offline = self.unreachable.load(Ordering::Relaxed) && metadata.is_none();
+        if metadata.as_ref().is_some_and(Metadata::is_dir)
+            && io_retry::metadata(&ignore::nomedia_marker(&path))
+                .await
+                .is_ok()
+        {
+            return None;
+        }
+        let name = format!(
+            "{prefix}{name}",
+            prefix = self.prefix.as_deref().unwrap_or(""),
+            name = entry.file_name().display()
+        );
+        Some(Entry {
+            path,
+            name,
+            metadata,
+            offline,
+        })
+    }
+}
+
+/// Open a directory for scanning, retrying transient errors.
+///
+/// # Errors
+/// If the directory cannot be read.
+pub async fn read_dir(path: &Path) -> io::Result<ReadDir> {
+    io_retry::retry(|| ::smol::fs::read_dir(path)).await
+}
+
+/// Scan a directory, blocking until every entry has been read. Entries which cannot be
+/// read are logged and skipped.
+///
+/// # Errors
+/// If the directory cannot be read.
+pub fn scan(path: &Path, ignore: IgnoreRules) -> io::Result<Vec<Entry>> {
+    ::smol::block_on(async {
+        let scanner = Scanner::new(ignore, None);
+        let mut read_dir = read_dir(path).await?;
+        let mut entries = Vec::new();
+        while let Some(entry) = read_dir.next().await {
+            match entry {
+                Ok(entry) => entries.extend(scanner.entry(entry).await),
+                Err(err) => ::log::warn!("io error while reading directory {path:?}\n{err}"),
+            }
+        }
+        Ok(entries)
+    })
+}
@@ -0,0 +1,252 @@
+//! Reading order of volumes and chapters within a series.
+
+use ::core::cmp::Ordering;
+use ::std::{path::Path, sync::Arc};
+
+use crate::text::NaturalKey;
+
+/// Words preceding a volume number.
+const VOLUME_MARKERS: [&str; 5] = ["v", "vol", "volume", "book", "tome"];
+
+/// Words preceding a chapter number.
+const CHAPTER_MARKERS: [&str; 5] = ["c", "ch", "chap", "chapter", "#"];
+
+/// Words marking an entry as a special, read after the numbered entries.
+const SPECIAL_MARKERS: [&str; 6] = ["special", "specials", "extra", "extras", "omake", "oneshot"];
+
+/// Token of an entry name.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    /// Lowercase word.
+    Word(&'a str),
+    /// Number, which may have a fractional part such as chapter `12.5`.
+    Number(f64),
+}
+
+/// Split name into words and numbers, skipping anything within brackets such as years
+/// and release groups.
+fn tokenize(name: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut rest = name;
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '(' | '[' | '{' => {
+                depth += 1;
+                1
+            }
+            ')' | ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                1
+            }
+            '#' => {
+                if depth == 0 {
+                    tokens.push(Token::Word("#"));
+                }
+                1
+            }
+            c if c.is_ascii_digit() => {
+                let mut len = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                // Include fractional part, but not a trailing dot such as in `3.cbz`.
+                if rest[len..].starts_with('.')
+                    && rest[len + 1..].starts_with(|c: char| c.is_ascii_digit())
+                {
+                    len += 1 + rest[len + 1..]
+                        .find(|c: char| !c.is_ascii_digit())
+                        .unwrap_or(rest.len() - len - 1);
+                }
+                if depth == 0
+                    && let Ok(number) = rest[..len].parse()
+                {
+                    tokens.push(Token::Number(number));
+                }
+                len
+            }
+            c if c.is_alphabetic() => {
+                let len = rest
+                    .find(|c: char| !c.is_alphabetic())
+                    .unwrap_or(rest.len());
+                if depth == 0 {
+                    tokens.push(Token::Word(&rest[..len]));
+                }
+                len
+            }
+            c => c.len_utf8(),
+        };
+        rest = &rest[len..];
+    }
+    tokens
+}
+
+/// Position of an entry within a series, parsed from its name.
+///
+/// Positions are ordered with specials last, and entries without a volume read after
+/// entries with one.
+#[derive(Debug, Clone, Copy, Default)]
+struct Position {
+    /// Entry is a special, or has no number.
+    special: bool,
+    /// Volume number of entry.
+    volume: Option<f64>,
+    /// Chapter number of entry.
+    chapter: Option<f64>,
+}
+
+impl Position {
+    /// Parse position from name of entry, without extension.
+    fn parse(name: &str) -> Self {
+        let lowercase = name.to_lowercase();
+        let tokens = tokenize(&lowercase);
+        let mut position = Self::default();
+        let mut last_number = None;
+        for (index, token) in tokens.iter().enumerate() {
+            match *token {
+                Token::Word(word) => {
+                    position.special |= SPECIAL_MARKERS.contains(&word);
+                    let Some(&Token::Number(number)) = tokens.get(index + 1) else {
+                        continue;
+                    };
+                    if VOLUME_MARKERS.contains(&word) && position.volume.is_none() {
+                        position.volume = Some(number);
+                    } else if CHAPTER_MARKERS.contains(&word) && position.chapter.is_none() {
+                        position.chapter = Some(number);
+                    }
+                }
+                Token::Number(number) => last_number = Some(number),
+            }
+        }
+        // Names without markers, such as `Series 03`, are numbered by their last number.
+        if position.volume.is_none() && position.chapter.is_none() {
+            position.volume = last_number;
+        }
+        position.special |= position.volume.is_none() && position.chapter.is_none();
+        position
+    }
+}
+
+impl PartialEq for Position {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Position {}
+
+impl PartialOrd for Position {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Position {
+    fn cmp(&self, other: &Self) -> Ordering {
+        /// Compare optional numbers, with missing numbers last.
+        fn cmp_number(a: Option<f64>, b: Option<f64>) -> Ordering {
+            a.unwrap_or(f64::INFINITY)
+                .total_cmp(&b.unwrap_or(f64::INFINITY))
+        }
+        self.special
+            .cmp(&other.special)
+            .then_with(|| cmp_number(self.volume, other.volume))
+            .then_with(|| {
+                // Volumes without chapters are read before the loose chapters of it.
+                cmp_number(self.chapter.or(Some(-1.0)), other.chapter.or(Some(-1.0)))
+            })
+    }
+}
+
+/// Get name of the series an entry name belongs to, the words of the name preceding its
+/// first number, volume or chapter, such that `Series_Name v03 (2004)` belongs to
+/// `Series Name`.
+pub fn series_name(name: &str) -> String {
+    /// Check if a word marks a following number as a volume or chapter.
+    fn is_marker(word: &str) -> bool {
+        let word = word.to_lowercase();
+        VOLUME_MARKERS.contains(&word.as_str()) || CHAPTER_MARKERS.contains(&word.as_str())
+    }
+    let tokens = tokenize(name);
+    let mut words = Vec::new();
+    for (index, token) in tokens.iter().enumerate() {
+        match *token {
+            Token::Number(..) => break,
+            Token::Word(word)
+                if matches!(tokens.get(index + 1), Some(Token::Number(..))) && is_marker(word) =>
+            {
+                break;
+            }
+            Token::Word("#") => break,
+            Token::Word(word) => words.push(word),
+        }
+    }
+    words.join(" ")
+}
+
+/// Get key of the series an entry name belongs to, the lowercase [series_name] of it.
+/// Names of series directories map to the same key, such that `Series_Name v03
+/// (2004).cbz` belongs to `Series Name`.
+pub fn series_key(name: &str) -> String {
+    series_name(name).to_lowercase()
+}
+
+/// Get volume number of an entry name, without extension. Names without a volume
+/// marker are numbered by their last number.
+pub fn volume(name: &str) -> Option<f64> {
+    Position::parse(name).volume
+}
+
+/// Get whole volume numbers missing from owned volumes, from the first volume up to the
+/// last owned volume.
+pub fn gaps(owned: &[f64]) -> Vec<f64> {
+    let last = owned.iter().copied().fold(0.0, f64::max).floor() as u32;
+    (1..=last)
+        .map(f64::from)
+        .filter(|volume| !owned.contains(volume))
+        .collect()
+}
+
+/// Format a volume number, without a fractional part for whole volumes.
+pub fn volume_text(volume: f64) -> String {
+    if volume.fract() == 0.0 {
+        format!("{volume:.0}")
+    } else {
+        volume.to_string()
+    }
+}
+
+/// Get name of entry used to order it, and to store manual orders.
+pub fn entry_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Sort entries of a series into reading order, by volume and chapter numbers in their
+/// names with specials at the end. Entries at the same position are sorted by name.
+pub fn reading_order(entries: &mut [Arc<Path>]) {
+    entries.sort_by_cached_key(|path| {
+        let stem = path
+            .file_stem()
+            .unwrap_or(path.as_os_str())
+            .to_string_lossy()
+            .into_owned();
+        (Position::parse(&stem), NaturalKey(stem))
+    });
+}
+
+/// Sort entries into a manual order given by entry names, entries missing from it keep
+/// their relative order and are placed after the manually ordered entries.
+pub fn apply_manual_order(entries: &mut [Arc<Path>], names: &[String]) {
+    if names.is_empty() {
+        return;
+    }
+    entries.sort_by_cached_key(|path| {
+        let name = entry_name(path);
+        names
+            .iter()
+            .position(|ordered| *ordered == name)
+            .unwrap_or(names.len())
+    });
+}
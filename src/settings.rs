@@ -0,0 +1,613 @@
+//! Settings of application, read from and written to config files.
+
+use ::core::fmt::Display;
+use ::std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use ::clap::ValueEnum;
+use ::derive_more::IsVariant;
+use ::katalog_lib::ThemeValueEnum;
+use ::serde::{Deserialize, Serialize};
+use ::tap::Pipe;
+
+use crate::{
+    config,
+    error::{ConfigError, Error},
+    i18n::{tr, tr_args},
+    input::Bindings,
+    logging::LogLevel,
+    migrate,
+};
+
+/// Units file sizes are formatted in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SizeUnits {
+    /// Powers of 1024, KiB, MiB, GiB.
+    #[default]
+    Binary,
+    /// Powers of 1000, kB, MB, GB.
+    Decimal,
+}
+
+impl Display for SizeUnits {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            SizeUnits::Binary => "format-binary",
+            SizeUnits::Decimal => "format-decimal",
+        }))
+    }
+}
+
+/// Badge shown on cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CardBadge {
+    /// Format of archive.
+    Format,
+    /// Amount of pages of archive, if counted.
+    PageCount,
+    /// Archive has not been read.
+    Unread,
+}
+
+impl CardBadge {
+    /// All badges, in the order they are shown.
+    pub const ALL: [Self; 3] = [Self::Unread, Self::Format, Self::PageCount];
+}
+
+impl Display for CardBadge {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            CardBadge::Format => "pane-badge-format",
+            CardBadge::PageCount => "pane-badge-page-count",
+            CardBadge::Unread => "pane-badge-unread",
+        }))
+    }
+}
+
+/// Tile of home window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HomeTile {
+    /// Items being read, most recently read first.
+    ContinueReading,
+    /// Archives most recently found when scanning.
+    RecentlyAdded,
+    /// Random unread archives.
+    RandomPicks,
+    /// Counts of items in catalogue.
+    Stats,
+}
+
+impl HomeTile {
+    /// All tiles, in the order they are shown.
+    pub const ALL: [Self; 4] = [
+        Self::ContinueReading,
+        Self::RecentlyAdded,
+        Self::RandomPicks,
+        Self::Stats,
+    ];
+}
+
+impl Display for HomeTile {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            HomeTile::ContinueReading => "home-continue-reading",
+            HomeTile::RecentlyAdded => "home-recently-added",
+            HomeTile::RandomPicks => "home-random-picks",
+            HomeTile::Stats => "home-library",
+        }))
+    }
+}
+
+/// How items are activated (opened) by mouse clicks.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize, IsVariant,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Activation {
+    /// Items are activated by a single click.
+    SingleClick,
+    /// Items are activated by a double click.
+    #[default]
+    DoubleClick,
+}
+
+impl Display for Activation {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&tr(match self {
+            Activation::SingleClick => "activation-single-click",
+            Activation::DoubleClick => "activation-double-click",
+        }))
+    }
+}
+
+/// Size of cards in a directory view.
+///
+/// Sizes are physical, converted to logical pixels which iced scales by the
+/// scale factor of the monitor a window is on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CardSize {
+    /// Small cards.
+    Compact,
+    /// Medium cards.
+    #[default]
+    Comfortable,
+    /// Large cards.
+    Spacious,
+    /// Cards of a custom width in millimeters.
+    Millimeters(u16),
+}
+
+impl CardSize {
+    /// Preset card sizes.
+    pub const PRESETS: [Self; 3] = [Self::Compact, Self::Comfortable, Self::Spacious];
+
+    /// Logical pixels per inch at a scale factor of 1.
+    const LOGICAL_DPI: f32 = 96.0;
+
+    /// Millimeters per inch.
+    const MM_PER_INCH: f32 = 25.4;
+
+    /// Smallest custom card width in millimeters.
+    pub const MIN_MILLIMETERS: u16 = 20;
+
+    /// Largest custom card width in millimeters.
+    pub const MAX_MILLIMETERS: u16 = 100;
+
+    /// Change of card width in millimeters per step of Ctrl+scroll or pinch.
+    pub const STEP_MILLIMETERS: u16 = 5;
+
+    /// Get card width in millimeters.
+    pub const fn millimeters(self) -> u16 {
+        match self {
+            CardSize::Compact => 30,
+            CardSize::Comfortable => 40,
+            CardSize::Spacious => 55,
+            CardSize::Millimeters(mm) => mm,
+        }
+    }
+
+    /// Get card width in logical pixels.
+    pub fn width(self) -> f32 {
+        f32::from(self.millimeters()) * Self::LOGICAL_DPI / Self::MM_PER_INCH
+    }
+
+    /// Get size of square covers of cards are downscaled to fit within, twice the card
+    /// width such that covers stay sharp on high density displays.
+    pub fn thumbnail_size(self) -> u32 {
+        (self.width() * 2.0).ceil() as u32
+    }
+
+    /// Get a custom size the given amount of steps larger, or smaller if negative,
+    /// limited to the range of custom sizes.
+    pub fn resized(self, steps: i32) -> Self {
+        let change = steps.saturating_mul(Self::STEP_MILLIMETERS.into());
+        let mm = i32::from(self.millimeters())
+            .saturating_add(change)
+            .clamp(Self::MIN_MILLIMETERS.into(), Self::MAX_MILLIMETERS.into());
+        Self::Millimeters(u16::try_from(mm).unwrap_or(Self::MAX_MILLIMETERS))
+    }
+}
+
+impl Display for CardSize {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&match self {
+            CardSize::Compact => tr("card-size-compact"),
+            CardSize::Comfortable => tr("card-size-comfortable"),
+            CardSize::Spacious => tr("card-size-spacious"),
+            CardSize::Millimeters(mm) => tr_args("card-size-custom", &[("mm", &mm.to_string())]),
+        })
+    }
+}
+
+/// Application theme, either following the light or dark preference of the desktop or
+/// a specific theme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ThemeArg {
+    /// Follow desktop preference, switching when it changes.
+    #[default]
+    Auto,
+    /// Use a specific theme.
+    Theme(ThemeValueEnum),
+}
+
+impl ThemeArg {
+    /// Name of [ThemeArg::Auto] when parsed and serialized.
+    const AUTO: &str = "auto";
+
+    /// Get theme to use, [None] if iced should match the system color scheme.
+    #[cfg(feature = "gui")]
+    pub fn theme(self) -> Option<::iced::Theme> {
+        match self {
+            ThemeArg::Auto => None,
+            ThemeArg::Theme(theme) => Some(theme.into()),
+        }
+    }
+
+    /// Get theme after this one, wrapping around.
+    pub fn cycle_next(self) -> Self {
+        let variants = Self::value_variants();
+        let index = variants.iter().position(|theme| *theme == self);
+        index.map_or(Self::Auto, |index| variants[(index + 1) % variants.len()])
+    }
+
+    /// Get theme before this one, wrapping around.
+    pub fn cycle_prev(self) -> Self {
+        let variants = Self::value_variants();
+        let index = variants.iter().position(|theme| *theme == self);
+        index.map_or(Self::Auto, |index| {
+            variants[(index + variants.len() - 1) % variants.len()]
+        })
+    }
+}
+
+impl ValueEnum for ThemeArg {
+    fn value_variants<'a>() -> &'a [Self] {
+        /// All variants, auto first.
+        static VARIANTS: LazyLock<Vec<ThemeArg>> = LazyLock::new(|| {
+            ::core::iter::once(ThemeArg::Auto)
+                .chain(
+                    ThemeValueEnum::value_variants()
+                        .iter()
+                        .copied()
+                        .map(ThemeArg::Theme),
+                )
+                .collect()
+        });
+        &VARIANTS
+    }
+
+    fn to_possible_value(&self) -> Option<::clap::builder::PossibleValue> {
+        match self {
+            ThemeArg::Auto => ::clap::builder::PossibleValue::new(Self::AUTO)
+                .help("Follow light or dark preference of desktop")
+                .pipe(Some),
+            ThemeArg::Theme(theme) => theme.to_possible_value(),
+        }
+    }
+}
+
+impl Display for ThemeArg {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            ThemeArg::Auto => f.write_str(&tr("theme-auto")),
+            ThemeArg::Theme(theme) => Display::fmt(theme, f),
+        }
+    }
+}
+
+impl Serialize for ThemeArg {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        match self {
+            ThemeArg::Auto => serializer.serialize_str(Self::AUTO),
+            ThemeArg::Theme(theme) => theme.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeArg {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: ::serde::Deserializer<'de>,
+    {
+        use ::serde::de::IntoDeserializer;
+
+        let name = String::deserialize(deserializer)?;
+        if name == Self::AUTO {
+            return Ok(ThemeArg::Auto);
+        }
+        ThemeValueEnum::deserialize(IntoDeserializer::<D::Error>::into_deserializer(name))
+            .map(ThemeArg::Theme)
+    }
+}
+
+/// Theme of reader windows, [None] if the application theme is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReaderTheme(pub Option<ThemeValueEnum>);
+
+impl ReaderTheme {
+    /// Get all reader themes that may be selected.
+    pub fn options() -> Vec<Self> {
+        ::core::iter::once(None)
+            .chain(ThemeValueEnum::value_variants().iter().copied().map(Some))
+            .map(Self)
+            .collect()
+    }
+}
+
+impl Display for ReaderTheme {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self.0 {
+            Some(theme) => Display::fmt(&theme, f),
+            None => f.write_str(&tr("theme-same-as-theme")),
+        }
+    }
+}
+
+/// Application settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Version of settings schema, used to upgrade config files of older versions.
+    pub version: u32,
+
+    /// Application theme to use.
+    pub theme: ThemeArg,
+
+    /// Theme to use for reader windows, application theme is used if [None].
+    pub reader_theme: Option<ThemeValueEnum>,
+
+    /// Card size to use.
+    pub card_size: CardSize,
+
+    /// Max width of card text.
+    pub max_card_text_width: u16,
+
+    /// Time in milliseconds the mouse rests on a card before its first pages are
+    /// previewed. Hover previews are disabled if 0.
+    pub hover_preview_delay: u16,
+
+    /// Badges shown on cards.
+    pub card_badges: Vec<CardBadge>,
+
+    /// How items are activated.
+    pub activation: Activation,
+
+    /// Bindings of user input to actions.
+    pub bindings: Bindings,
+
+    /// Horizontal touchpad scroll distance, in pixels, of a swipe navigating pane history
+    /// or turning reader pages, also used by touch swipes in readers. Swipe navigation is
+    /// disabled if 0.
+    pub swipe_distance: u16,
+
+    /// Distance, in pixels, touchpads scroll while Ctrl is held and fingers pinch on a
+    /// touch screen per step of resizing cards. Pinch resizing is disabled if 0.
+    pub pinch_distance: u16,
+
+    /// Seconds each page is shown by reader slideshows.
+    pub slideshow_interval: u16,
+
+    /// Reader slideshows start over at the first page after the last one, instead of
+    /// going on to the next volume.
+    pub slideshow_loop: bool,
+
+    /// Memory budget of thumbnail cache in MiB.
+    pub thumbnail_cache_size: u16,
+
+    /// Units file sizes are shown in.
+    pub size_units: SizeUnits,
+
+    /// Throttle background work while running on battery.
+    pub battery_saver: bool,
+
+    /// Play animated covers in cards, paused while background work is throttled.
+    pub animate_covers: bool,
+
+    /// Tint cards with the dominant color of their covers.
+    pub cover_accents: bool,
+
+    /// Split grids into sections by the first letter of titles, each below a header.
+    pub section_headers: bool,
+
+    /// Also write covers to the thumbnail directory of the freedesktop thumbnail
+    /// specification, such that file managers show the same covers.
+    pub freedesktop_thumbnails: bool,
+
+    /// Show a desktop notification when background tasks or long scans finish while no
+    /// window of application is focused.
+    pub task_notifications: bool,
+
+    /// Least severe level of messages logged to the terminal and to log files in the
+    /// state directory of profile, read on startup.
+    pub log_level: LogLevel,
+
+    /// Glob patterns of names of files and directories skipped when scanning directories,
+    /// where `*` matches any text and `?` any character, ignoring case. Directories
+    /// containing a `.nomedia` file are also skipped.
+    pub ignore_patterns: Vec<String>,
+
+    /// Skip files and directories with names starting with a dot when scanning
+    /// directories.
+    pub skip_hidden: bool,
+
+    /// Follow symbolic links when scanning directories, instead of skipping them.
+    /// Directories reached through several links or mounts are only scanned once.
+    pub follow_symlinks: bool,
+
+    /// Commands of external applications archives may be opened with, arguments are
+    /// separated by whitespace and `{path}` is replaced by the path of the archive.
+    pub open_with: Vec<String>,
+
+    /// Restore size and maximized state of windows from when a window of the same kind
+    /// was last closed.
+    pub remember_window_size: bool,
+
+    /// Restore position of windows from when a window of the same kind was last closed.
+    pub remember_window_position: bool,
+
+    /// Tiles of home window, shown when started without a directory. The home window is
+    /// not shown if there are no tiles.
+    pub home_tiles: Vec<HomeTile>,
+
+    /// Show archives being read on a shelf above the panes of main windows.
+    pub continue_reading_shelf: bool,
+
+    /// Quality of jpeg images page images are recompressed as when converting archives
+    /// to zip archives, from 1 to 100. Pages are kept as they are if 0.
+    pub convert_quality: u8,
+
+    /// Urls of OPDS feeds which may be browsed in panes, such as those of Komga, Kavita
+    /// or Calibre-web. Credentials are read from `opds.netrc` in the config directory of
+    /// profile, or may be given as part of the url.
+    pub opds_feeds: Vec<String>,
+
+    /// Directories of the library, indexed on startup and watched for archives added to
+    /// them while the application runs, listed in the sidebar of main windows.
+    pub library_roots: Vec<PathBuf>,
+
+    /// Show a sidebar of library roots and reading lists in main windows.
+    pub sidebar: bool,
+
+    /// Factor the user interface is scaled by, on top of the scale of the display.
+    pub scale_factor: f32,
+
+    /// Default size of text in pixels before scaling, read on startup.
+    pub font_size: u16,
+
+    /// Locale of the user interface, such as `sv-SE`, detected from the environment if
+    /// [None].
+    pub language: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: migrate::SETTINGS_VERSION,
+            theme: Default::default(),
+            reader_theme: None,
+            card_size: CardSize::default(),
+            max_card_text_width: 12,
+            hover_preview_delay: 800,
+            card_badges: CardBadge::ALL.to_vec(),
+            activation: Activation::default(),
+            bindings: Bindings::default(),
+            swipe_distance: 150,
+            pinch_distance: 40,
+            slideshow_interval: 5,
+            slideshow_loop: false,
+            thumbnail_cache_size: 256,
+            size_units: SizeUnits::default(),
+            battery_saver: true,
+            animate_covers: false,
+            cover_accents: true,
+            section_headers: false,
+            freedesktop_thumbnails: false,
+            task_notifications: true,
+            log_level: LogLevel::default(),
+            ignore_patterns: [
+                "__MACOSX",
+                "@eaDir",
+                "$RECYCLE.BIN",
+                "System Volume Information",
+                "lost+found",
+                "*.part",
+            ]
+            .map(String::from)
+            .to_vec(),
+            skip_hidden: true,
+            follow_symlinks: true,
+            open_with: Vec::new(),
+            remember_window_size: true,
+            remember_window_position: true,
+            home_tiles: HomeTile::ALL.to_vec(),
+            continue_reading_shelf: true,
+            convert_quality: 0,
+            opds_feeds: Vec::new(),
+            library_roots: Vec::new(),
+            sidebar: true,
+            scale_factor: 1.0,
+            font_size: 16,
+            language: None,
+        }
+    }
+}
+
+impl Settings {
+    /// Load settings from config directory, default settings are used if there is no
+    /// config file.
+    ///
+    /// # Errors
+    /// If the config file cannot be read, or cannot be parsed and neither can its backup.
+    ///
+    /// Config files of older versions are upgraded, with the original kept as a backup.
+    /// If the config file is not valid, the backup kept when it was last saved is used
+    /// instead, leaving the config file as is such that it may be fixed.
+    pub fn load(xdg_dirs: &::xdg::BaseDirectories) -> Result<Self, Error> {
+        let Some(path) = config::find(xdg_dirs) else {
+            return Ok(Self::default());
+        };
+        let (settings, upgraded) = match Self::read_upgraded(&path) {
+            Ok(read) => read,
+            Err(
+                err @ Error::Config {
+                    source: ConfigError::Parse(..) | ConfigError::ParseJson(..),
+                    ..
+                },
+            ) => {
+                let backup = config::backup_path(&path);
+                let Ok((settings, _)) = Self::read_upgraded(&backup) else {
+                    return Err(err);
+                };
+                err.log();
+                ::log::warn!("using settings of backup {backup:?}");
+                return Ok(settings);
+            }
+            Err(err) => return Err(err),
+        };
+        if upgraded {
+            settings.save(xdg_dirs).unwrap_or_else(|err| err.log());
+        }
+        Ok(settings)
+    }
+
+    /// Read settings from config file at path, upgrading them if they were written by
+    /// an older version.
+    ///
+    /// # Errors
+    /// If the file cannot be read or parsed.
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        Self::read_upgraded(path).map(|(settings, _)| settings)
+    }
+
+    /// Read settings from config file at path, also returning true if they were upgraded.
+    /// The format of the file is detected by its extension.
+    ///
+    /// # Errors
+    /// If the file cannot be read or parsed.
+    fn read_upgraded(path: &Path) -> Result<(Self, bool), Error> {
+        ::std::fs::read_to_string(path)
+            .map_err(ConfigError::Read)
+            .and_then(|content| {
+                let mut table = config::Format::from_path(path).parse(&content)?;
+                let upgraded = migrate::settings(&mut table);
+                ::toml::Value::Table(table)
+                    .try_into()
+                    .map(|settings| (settings, upgraded))
+                    .map_err(ConfigError::Parse)
+            })
+            .map_err(|source| Error::Config {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
+
+    /// Save settings to config directory, in the format of the existing config file. The
+    /// config file is replaced atomically, with the previous one kept as a backup.
+    ///
+    /// # Errors
+    /// If the settings cannot be serialized or the config file cannot be written.
+    pub fn save(&self, xdg_dirs: &::xdg::BaseDirectories) -> Result<(), Error> {
+        let path = config::place(xdg_dirs)?;
+        let content = config::Format::from_path(&path)
+            .serialize(self)
+            .map_err(|source| Error::Config {
+                path: path.clone(),
+                source,
+            })?;
+        config::write(&path, &content)
+    }
+
+    /// Get memory budget of thumbnail cache in bytes.
+    pub fn thumbnail_cache_budget(&self) -> usize {
+        usize::from(self.thumbnail_cache_size).saturating_mul(1024 * 1024)
+    }
+}
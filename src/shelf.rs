@@ -0,0 +1,135 @@
+//! [Shelf] impl, a strip of archives being read shown above the panes of main windows.
+
+use ::std::{path::Path, sync::Arc};
+
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message,
+    catalogue::Catalogue,
+    error::Error,
+    i18n::{tr, tr_args},
+    series,
+    text::{Ellipsis, shorten_text},
+};
+
+/// Amount of archives shown on shelf.
+const SHELF_ENTRIES: usize = 8;
+
+/// Width of entries of shelf.
+const ENTRY_WIDTH: f32 = 160.0;
+
+/// Max length of names of entries of shelf.
+const ENTRY_TEXT_LEN: usize = 20;
+
+/// Archive being read, shown on shelf.
+#[derive(Debug, Clone)]
+struct Entry {
+    /// Path of archive.
+    path: Arc<Path>,
+    /// Page reading was left at.
+    page: usize,
+    /// Amount of pages of archive, if known.
+    page_count: Option<usize>,
+}
+
+/// Archives being read, most recently read first.
+#[derive(Debug, Clone, Default)]
+pub struct Shelf {
+    /// Entries of shelf.
+    entries: Vec<Entry>,
+}
+
+impl Shelf {
+    /// Load shelf from catalogue, errors are logged and leave shelf empty.
+    pub fn load(catalogue: &Catalogue) -> Self {
+        let paths = catalogue
+            .continue_reading(SHELF_ENTRIES)
+            .inspect_err(Error::log)
+            .unwrap_or_default();
+        Self {
+            entries: paths
+                .into_iter()
+                .map(|path| {
+                    let page = catalogue.get(&path).unwrap_or_default().page;
+                    let modified = path
+                        .metadata()
+                        .and_then(|metadata| metadata.modified())
+                        .ok();
+                    let page_count = catalogue
+                        .page_count(&path, modified)
+                        .inspect_err(Error::log)
+                        .ok()
+                        .flatten();
+                    Entry {
+                        path: Arc::from(path),
+                        page,
+                        page_count,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// View shelf, [None] if nothing is being read.
+    pub fn view(&self) -> Option<Element<'_, Message>> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        widget::Row::new()
+            .spacing(5)
+            .align_y(Center)
+            .push(widget::text(tr("home-continue-reading")).size(16))
+            .extend(self.entries.iter().map(Entry::view))
+            .pipe(widget::scrollable)
+            .horizontal()
+            .width(Fill)
+            .pipe(widget::container)
+            .padding(5)
+            .width(Fill)
+            .pipe(Element::from)
+            .pipe(Some)
+    }
+}
+
+impl Entry {
+    /// View entry, opening archive at the saved page when pressed.
+    fn view(&self) -> Element<'_, Message> {
+        let name = series::entry_name(&self.path);
+        let page = self.page + 1;
+        let progress = self.page_count.map_or_else(
+            || tr_args("common-page", &[("page", &page.to_string())]),
+            |page_count| {
+                tr_args(
+                    "common-page-of",
+                    &[
+                        ("page", &page.to_string()),
+                        ("count", &page_count.to_string()),
+                    ],
+                )
+            },
+        );
+        widget::Column::new()
+            .spacing(2)
+            .push(
+                widget::text(shorten_text(&name, ENTRY_TEXT_LEN, Ellipsis::Middle).into_owned())
+                    .wrapping(Wrapping::None),
+            )
+            .push(self.page_count.map(|page_count| {
+                widget::progress_bar(0.0..=page_count as f32, page as f32).girth(4)
+            }))
+            .push(widget::text(progress).size(12))
+            .pipe(widget::button)
+            .padding([3, 6])
+            .width(ENTRY_WIDTH)
+            .style(widget::button::secondary)
+            .on_press(Message::OpenArchive(Arc::clone(&self.path)))
+            .into()
+    }
+}
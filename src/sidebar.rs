@@ -0,0 +1,177 @@
+//! [Sidebar] impl, the navigation panel of main windows listing library roots, reading
+//! lists, tags and recently opened locations. Entries are shown in the focused pane when
+//! pressed, or in the pane they are dragged onto.
+
+use ::std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    mouse::Interaction,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message,
+    archive::ArchiveKind,
+    i18n::tr,
+    text::{Ellipsis, shorten_text},
+};
+
+/// Width of sidebar.
+const SIDEBAR_WIDTH: f32 = 180.0;
+
+/// Max length of names of entries of sidebar.
+const ENTRY_TEXT_LEN: usize = 24;
+
+/// Entry of sidebar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    /// A directory, a library root or a recently opened directory.
+    Dir(Arc<Path>),
+    /// A recently opened archive, opened in a reader.
+    Archive(Arc<Path>),
+    /// A reading list.
+    List(String),
+    /// A tag, showing the archives tagged with it.
+    Tag(String),
+}
+
+/// Sidebar of a main window.
+#[derive(Debug, Clone, Copy)]
+pub struct Sidebar<'a> {
+    /// Window sidebar is shown in.
+    pub window_id: ::iced::window::Id,
+    /// Library roots.
+    pub roots: &'a [PathBuf],
+    /// Names of reading lists.
+    pub reading_lists: &'a [String],
+    /// Tags of archives.
+    pub tags: &'a [String],
+    /// Recently opened directories and archives, most recent first.
+    pub recent: &'a [Arc<Path>],
+    /// Sidebar is collapsed to a strip with a button expanding it.
+    pub collapsed: bool,
+}
+
+/// Get name an entry with a path is listed as.
+fn path_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// View an entry of the sidebar, pressing it starts dragging it and releasing it over
+/// the sidebar shows it in the focused pane.
+fn entry<'a>(window_id: ::iced::window::Id, name: &str, entry: Entry) -> Element<'a, Message> {
+    widget::text(shorten_text(name, ENTRY_TEXT_LEN, Ellipsis::Middle).into_owned())
+        .wrapping(Wrapping::None)
+        .pipe(widget::container)
+        .padding([3, 6])
+        .width(Fill)
+        .pipe(widget::mouse_area)
+        .interaction(Interaction::Pointer)
+        .on_press(Message::SidebarPressed(entry))
+        .on_release(Message::SidebarReleased(window_id))
+        .into()
+}
+
+/// View a section of the sidebar with a heading, [None] if it has no entries.
+fn section<'a>(
+    heading: String,
+    entries: impl IntoIterator<Item = Element<'a, Message>>,
+) -> Option<Element<'a, Message>> {
+    let entries = entries.into_iter().collect::<Vec<_>>();
+    if entries.is_empty() {
+        return None;
+    }
+    widget::Column::new()
+        .spacing(2)
+        .push(widget::text(heading).size(16))
+        .extend(entries)
+        .pipe(Element::from)
+        .pipe(Some)
+}
+
+impl<'a> Sidebar<'a> {
+    /// View sidebar.
+    pub fn view(self) -> Element<'a, Message> {
+        let Self {
+            window_id,
+            roots,
+            reading_lists,
+            tags,
+            recent,
+            collapsed,
+        } = self;
+        if collapsed {
+            return widget::button("\u{203a}")
+                .padding([3, 6])
+                .style(widget::button::text)
+                .on_press(Message::ToggleSidebar)
+                .pipe(widget::container)
+                .padding(2)
+                .height(Fill)
+                .style(widget::container::bordered_box)
+                .into();
+        }
+        widget::Column::new()
+            .spacing(8)
+            .push(
+                widget::Row::new()
+                    .align_y(Center)
+                    .push(widget::space::horizontal())
+                    .push(
+                        widget::button("\u{2039}")
+                            .padding([3, 6])
+                            .style(widget::button::text)
+                            .on_press(Message::ToggleSidebar),
+                    ),
+            )
+            .push(section(
+                tr("sidebar-library"),
+                roots.iter().map(|root| {
+                    entry(
+                        window_id,
+                        &path_name(root),
+                        Entry::Dir(Arc::from(root.as_path())),
+                    )
+                }),
+            ))
+            .push(section(
+                tr("sidebar-reading-lists"),
+                reading_lists
+                    .iter()
+                    .map(|name| entry(window_id, name, Entry::List(name.clone()))),
+            ))
+            .push(section(
+                tr("sidebar-tags"),
+                tags.iter()
+                    .map(|tag| entry(window_id, tag, Entry::Tag(tag.clone()))),
+            ))
+            .push(section(
+                tr("sidebar-recent"),
+                recent.iter().map(|path| {
+                    let target = if ArchiveKind::from_path(path).is_some() {
+                        Entry::Archive(Arc::clone(path))
+                    } else {
+                        Entry::Dir(Arc::clone(path))
+                    };
+                    entry(window_id, &path_name(path), target)
+                }),
+            ))
+            .pipe(widget::scrollable)
+            .height(Fill)
+            .pipe(widget::container)
+            .padding(5)
+            .width(SIDEBAR_WIDTH)
+            .style(widget::container::bordered_box)
+            .into()
+    }
+}
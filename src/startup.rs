@@ -0,0 +1,111 @@
+//! [Startup] impl, state of a profile loaded in the background while its first windows
+//! are shown.
+
+use ::core::time::Duration;
+use ::std::{
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+    time::Instant,
+};
+
+use crate::{Cli, DEFAULT_PAGE_TIME, backup, catalogue::Catalogue, shelf::Shelf, temp};
+
+/// State of a profile read from disk, errors are logged and leave parts of it empty.
+#[derive(Debug)]
+pub struct Startup {
+    /// Profile state was loaded for.
+    pub profile: String,
+    /// Reading progress, stars and labels of items.
+    pub catalogue: Catalogue,
+    /// Names of reading lists in catalogue.
+    pub reading_lists: Vec<String>,
+    /// Recently opened directories and archives, most recent first.
+    pub recent: Vec<Arc<Path>>,
+    /// Archives being read.
+    pub shelf: Shelf,
+    /// Tags of archives in catalogue.
+    pub tags: Vec<String>,
+    /// Names of backups of profile, newest first.
+    pub backups: Vec<String>,
+    /// Directory of temporary files of profile, cleaned of stale files.
+    pub temp_dir: Option<Arc<Path>>,
+    /// Estimated time spent reading a page.
+    pub page_time: Duration,
+    /// Names of profiles of application, including the profile in use.
+    pub profiles: Vec<String>,
+}
+
+impl Startup {
+    /// Load state of profile given by cli arguments.
+    pub fn load(cli: &Cli, xdg_dirs: &::xdg::BaseDirectories) -> Self {
+        let started = Instant::now();
+        let catalogue = Catalogue::open(xdg_dirs).unwrap_or_else(|err| {
+            err.log();
+            Catalogue::default()
+        });
+        let reading_lists = catalogue.reading_lists().unwrap_or_else(|err| {
+            err.log();
+            Vec::new()
+        });
+        let recent = catalogue
+            .recent()
+            .unwrap_or_else(|err| {
+                err.log();
+                Vec::new()
+            })
+            .into_iter()
+            .map(Arc::from)
+            .collect();
+        let shelf = Shelf::load(&catalogue);
+        let tags = catalogue.tags().unwrap_or_else(|err| {
+            err.log();
+            Vec::new()
+        });
+        let page_time = catalogue
+            .page_time()
+            .unwrap_or_else(|err| {
+                err.log();
+                None
+            })
+            .unwrap_or(DEFAULT_PAGE_TIME);
+        let temp_dir = temp::dir(xdg_dirs)
+            .inspect_err(|err| ::log::warn!("could not create directory of temporary files\n{err}"))
+            .ok()
+            .inspect(|dir| temp::clean(dir))
+            .map(Arc::from);
+        let mut profiles = cli.profiles();
+        profiles.insert(cli.profile.clone());
+
+        let startup = Self {
+            profile: cli.profile.clone(),
+            catalogue,
+            reading_lists,
+            recent,
+            shelf,
+            tags,
+            backups: backup::list(xdg_dirs),
+            temp_dir,
+            page_time,
+            profiles: profiles.into_iter().collect(),
+        };
+        ::log::info!("loaded profile {} in {:?}", cli.profile, started.elapsed());
+        startup
+    }
+}
+
+/// Loaded [Startup] passed along in a message, taken by whichever receives it first as
+/// the catalogue cannot be cloned.
+#[derive(Debug, Clone)]
+pub struct Loaded(Arc<Mutex<Option<Startup>>>);
+
+impl Loaded {
+    /// Wrap loaded state.
+    pub fn new(startup: Startup) -> Self {
+        Self(Arc::new(Mutex::new(Some(startup))))
+    }
+
+    /// Take loaded state, [None] if already taken.
+    pub fn take(&self) -> Option<Startup> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).take()
+    }
+}
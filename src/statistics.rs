@@ -0,0 +1,199 @@
+//! [Statistics] impl, a summary of the library computed from the catalogue.
+
+use ::std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ::hashbrown::HashMap;
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message, SizeUnits,
+    catalogue::{self, Catalogue, Stats},
+    error::Error,
+    format,
+    i18n::{tr, tr_args},
+};
+
+/// Amount of weeks reading activity is shown for.
+const ACTIVITY_WEEKS: usize = 12;
+
+/// Amount of directories disk usage is shown for.
+const DISK_USAGE_DIRS: usize = 10;
+
+/// Width of labels of rows of bars.
+const LABEL_WIDTH: f32 = 180.0;
+
+/// Width of values of rows of bars.
+const VALUE_WIDTH: f32 = 90.0;
+
+/// Disk usage of directories, largest first.
+pub type DiskUsage = Vec<(Arc<Path>, u64)>;
+
+/// State of a statistics window.
+#[derive(Debug, Clone, Default)]
+pub struct Statistics {
+    /// Counts of items in catalogue.
+    totals: Stats,
+    /// Pages read per week, the current week last.
+    weeks: [u64; ACTIVITY_WEEKS],
+    /// Disk usage of directories with the most archives by size, [None] while computed.
+    disk_usage: Option<DiskUsage>,
+}
+
+impl Statistics {
+    /// Load statistics from catalogue, errors are logged and leave statistics empty.
+    /// Disk usage is computed separately by [disk_usage].
+    pub fn load(catalogue: &Catalogue) -> Self {
+        let today = catalogue::today();
+        let days = i64::try_from(ACTIVITY_WEEKS * 7).unwrap_or(i64::MAX);
+        let mut weeks = [0; ACTIVITY_WEEKS];
+        for (day, pages) in catalogue
+            .reading_activity(today - days + 1)
+            .inspect_err(Error::log)
+            .unwrap_or_default()
+        {
+            let weeks_ago = usize::try_from((today - day) / 7).unwrap_or(usize::MAX);
+            if let Some(week) = weeks_ago
+                .checked_add(1)
+                .and_then(|weeks_ago| ACTIVITY_WEEKS.checked_sub(weeks_ago))
+            {
+                weeks[week] += pages;
+            }
+        }
+        Self {
+            totals: catalogue
+                .stats()
+                .inspect_err(Error::log)
+                .unwrap_or_default(),
+            weeks,
+            disk_usage: None,
+        }
+    }
+
+    /// Set computed disk usage.
+    pub fn set_disk_usage(&mut self, disk_usage: DiskUsage) {
+        self.disk_usage = Some(disk_usage);
+    }
+
+    /// View statistics window.
+    pub fn view(&self, size_units: SizeUnits) -> Element<'_, Message> {
+        let Stats {
+            seen,
+            reading,
+            finished,
+            starred,
+            pages,
+        } = self.totals;
+        let unread = seen.saturating_sub(reading + finished);
+        let count = |count: u64| format::number(usize::try_from(count).unwrap_or(usize::MAX));
+
+        let totals = [
+            ("stats-archives", seen),
+            ("stats-pages", pages),
+            ("state-unread", unread),
+            ("state-reading", reading),
+            ("state-finished", finished),
+            ("stats-starred", starred),
+        ]
+        .into_iter()
+        .map(|(label, value)| {
+            widget::Column::new()
+                .align_x(Center)
+                .push(widget::text(count(value)).size(20))
+                .push(widget::text(tr(label)).style(widget::text::secondary))
+                .pipe(widget::container)
+                .style(widget::container::bordered_box)
+                .padding(5)
+                .width(Fill)
+                .into()
+        })
+        .pipe(|totals| widget::Row::new().spacing(5).extend(totals));
+
+        let max_pages = self.weeks.iter().copied().max().unwrap_or_default();
+        let activity = self.weeks.iter().enumerate().rev().map(|(week, &pages)| {
+            let label = tr_args(
+                "stats-weeks-ago",
+                &[("weeks", &(ACTIVITY_WEEKS - 1 - week).to_string())],
+            );
+            bar(label, pages, max_pages, count(pages))
+        });
+
+        let disk_usage: Vec<Element<'_, Message>> = match &self.disk_usage {
+            None => vec![widget::text(tr("stats-computing")).into()],
+            Some(disk_usage) if disk_usage.is_empty() => {
+                vec![widget::text(tr("stats-no-archives")).into()]
+            }
+            Some(disk_usage) => {
+                let max_size = disk_usage.first().map_or(0, |(_, size)| *size);
+                disk_usage
+                    .iter()
+                    .map(|(dir, size)| {
+                        bar(
+                            dir.display().to_string(),
+                            *size,
+                            max_size,
+                            format::size(*size, size_units),
+                        )
+                    })
+                    .collect()
+            }
+        };
+
+        widget::Column::new()
+            .padding(5)
+            .spacing(5)
+            .push(widget::text(tr("title-statistics")).size(20))
+            .push(totals)
+            .push(widget::text(tr("stats-pages-read")).size(16))
+            .extend(activity)
+            .push(widget::text(tr("stats-disk-usage")).size(16))
+            .extend(disk_usage)
+            .pipe(widget::scrollable)
+            .into()
+    }
+}
+
+/// View a labeled bar of a value relative to max.
+fn bar<'a>(label: String, value: u64, max: u64, text: String) -> Element<'a, Message> {
+    widget::Row::new()
+        .align_y(Center)
+        .spacing(5)
+        .push(
+            widget::text(label)
+                .wrapping(Wrapping::None)
+                .width(LABEL_WIDTH),
+        )
+        .push(widget::progress_bar(0.0..=max.max(1) as f32, value as f32).girth(10))
+        .push(widget::text(text).width(VALUE_WIDTH))
+        .into()
+}
+
+/// Compute disk usage of directories containing archives, blocking while archives are
+/// inspected. Archives that are missing are skipped.
+pub fn disk_usage(paths: Vec<PathBuf>) -> DiskUsage {
+    let mut usage = HashMap::<&Path, u64>::new();
+    for path in &paths {
+        let (Some(dir), Ok(metadata)) = (path.parent(), fs::metadata(path)) else {
+            continue;
+        };
+        *usage.entry(dir).or_default() += metadata.len();
+    }
+    let mut usage = usage
+        .into_iter()
+        .map(|(dir, size)| (Arc::from(dir), size))
+        .collect::<DiskUsage>();
+    usage.sort_unstable_by(|(a_dir, a_size), (b_dir, b_size)| {
+        b_size.cmp(a_size).then_with(|| a_dir.cmp(b_dir))
+    });
+    usage.truncate(DISK_USAGE_DIRS);
+    usage
+}
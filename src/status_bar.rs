@@ -0,0 +1,321 @@
+//! [StatusBar] impl.
+
+use ::core::time::Duration;
+use ::std::time::SystemTime;
+
+use ::iced::{
+    Alignment::Center,
+    Background, Element,
+    Length::Fill,
+    Padding,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message, SizeUnits,
+    archive::ArchiveKind,
+    format,
+    i18n::{tr, tr_args},
+    pane::{self, DirView, Pane},
+};
+
+/// Frames of activity spinner.
+const SPINNER: &[&str] = &["|", "/", "-", "\\"];
+
+/// Background activity shown in status bar.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Activity {
+    /// Amount of jobs queued or running on the worker pool.
+    pub queued_jobs: usize,
+    /// Amount of background tasks queued or running.
+    pub active_tasks: usize,
+    /// Amount of reader windows running a slideshow.
+    pub slideshows: usize,
+    /// Current frame of activity spinner.
+    pub frame: usize,
+}
+
+/// Prompt asking whether settings changed on disk should replace unsaved changes.
+pub fn settings_conflict_prompt<'a>() -> Element<'a, Message> {
+    widget::Row::new()
+        .align_y(Center)
+        .spacing(3)
+        .push(widget::text(tr("settings-conflict")).style(widget::text::warning))
+        .push(
+            widget::button(widget::text(tr("button-load")))
+                .padding(3)
+                .on_press(Message::LoadSettingsFromDisk),
+        )
+        .push(
+            widget::button(widget::text(tr("button-keep-mine")))
+                .padding(3)
+                .style(widget::button::secondary)
+                .on_press(Message::KeepSettings),
+        )
+        .into()
+}
+
+/// Status bar of a main window.
+#[derive(Debug, Clone, Copy)]
+pub struct StatusBar<'a> {
+    /// Profile in use.
+    pub profile: &'a str,
+    /// Profiles which may be switched to.
+    pub profiles: &'a [String],
+    /// Pane status is shown for.
+    pub pane: Option<&'a Pane>,
+    /// Background activity.
+    pub activity: Activity,
+    /// Error to show, if any.
+    pub error: Option<&'a str>,
+    /// Config file changed on disk while there were unsaved changes.
+    pub settings_conflict: bool,
+    /// Units file sizes are shown in.
+    pub size_units: SizeUnits,
+    /// Estimated time spent reading a page.
+    pub page_time: Duration,
+}
+
+impl<'a> StatusBar<'a> {
+    /// View status bar.
+    pub fn view(self) -> Element<'a, Message> {
+        let Self {
+            profile,
+            profiles,
+            pane,
+            activity,
+            error,
+            settings_conflict,
+            size_units,
+            page_time,
+        } = self;
+        let scanning = pane.is_some_and(|pane| pane.scanning);
+        // A single selected archive may be shown as a slideshow.
+        let mut slideshow = None;
+
+        let mut row = widget::Row::new().align_y(Center).spacing(10);
+        if let Some(pane) = pane {
+            if let Some(path) = &pane.path {
+                row = row.push(
+                    widget::text(path.display().to_string())
+                        .wrapping(Wrapping::None)
+                        .width(Fill),
+                );
+            } else {
+                row = row.push(widget::space::horizontal());
+            }
+            if let DirView::Dir {
+                items, selected, ..
+            } = &pane.view
+            {
+                let count = format::number(items.len());
+                row = row.push(widget::text(if pane.filter.is_empty() {
+                    tr_args("status-items", &[("count", &count)])
+                } else {
+                    let shown = items
+                        .values()
+                        .filter(|item| pane.filter.matches(item))
+                        .count();
+                    tr_args(
+                        "status-filtered-items",
+                        &[("shown", &format::number(shown)), ("count", &count)],
+                    )
+                }));
+                let selected_items = || selected.iter().filter_map(|path| items.get(path));
+                match selected.len() {
+                    0 => {}
+                    1 => {
+                        row = row.push(widget::text(tr_args("status-selected", &[("count", "1")])));
+                        slideshow = selected
+                            .first()
+                            .filter(|path| ArchiveKind::from_path(path).is_some())
+                            .cloned();
+                        let item = selected_items().next();
+                        if let Some(size) = item.and_then(|item| item.size) {
+                            row = row.push(widget::text(format::size(size, size_units)));
+                        }
+                        if let Some(item) = item
+                            && let Some(page_count) = item.page_count
+                            && let Some(remaining) = item.remaining_pages()
+                        {
+                            let count = format::number(page_count);
+                            row = row.push(widget::text(if remaining == 0 {
+                                tr_args("status-pages", &[("count", &count)])
+                            } else {
+                                tr_args(
+                                    "status-pages-left",
+                                    &[
+                                        ("count", &count),
+                                        (
+                                            "left",
+                                            &format::duration(pane::reading_time(
+                                                page_time, remaining,
+                                            )),
+                                        ),
+                                    ],
+                                )
+                            }));
+                        }
+                        if let Some(modified) = item.and_then(|item| item.modified) {
+                            let relative = widget::text(tr_args(
+                                "status-modified",
+                                &[("time", &format::relative(modified, SystemTime::now()))],
+                            ));
+                            row = row.push(match format::date(modified) {
+                                Some(date) => widget::tooltip(
+                                    relative,
+                                    widget::container(widget::text(date))
+                                        .padding(3)
+                                        .style(widget::container::bordered_box),
+                                    widget::tooltip::Position::Top,
+                                )
+                                .pipe(Element::from),
+                                None => relative.into(),
+                            });
+                        }
+                    }
+                    count => {
+                        let size = selected_items().filter_map(|item| item.size).sum();
+                        let remaining = selected_items()
+                            .filter_map(pane::Item::remaining_pages)
+                            .sum();
+                        let (count, size) = (format::number(count), format::size(size, size_units));
+                        row = row.push(widget::text(if remaining == 0 {
+                            tr_args(
+                                "status-selected-size",
+                                &[("count", &count), ("size", &size)],
+                            )
+                        } else {
+                            tr_args(
+                                "status-selected-size-left",
+                                &[
+                                    ("count", &count),
+                                    ("size", &size),
+                                    (
+                                        "left",
+                                        &format::duration(pane::reading_time(page_time, remaining)),
+                                    ),
+                                ],
+                            )
+                        }));
+                    }
+                }
+            }
+        } else {
+            row = row.push(widget::space::horizontal());
+        }
+
+        let spinner = SPINNER[activity.frame % SPINNER.len()];
+        if scanning || activity.queued_jobs > 0 {
+            row = row.push(widget::text(if scanning {
+                format!("{spinner} {}", tr("status-scanning"))
+            } else {
+                format!(
+                    "{spinner} {}",
+                    tr_args(
+                        "status-loading",
+                        &[("count", &activity.queued_jobs.to_string())]
+                    )
+                )
+            }));
+        }
+
+        if activity.active_tasks > 0 {
+            row = row.push(
+                widget::button(widget::text(format!(
+                    "{spinner} {}",
+                    tr_args(
+                        "status-active-tasks",
+                        &[("count", &activity.active_tasks.to_string())]
+                    )
+                )))
+                .padding(0)
+                .style(widget::button::text)
+                .on_press(Message::ToggleTaskPanel),
+            );
+        }
+
+        if activity.slideshows > 0 {
+            row = row.push(
+                widget::button(widget::text(tr("status-stop-slideshow")))
+                    .padding([0, 4])
+                    .style(widget::button::secondary)
+                    .on_press(Message::StopSlideshows),
+            );
+        } else if let Some(path) = slideshow {
+            row = row.push(
+                widget::button(widget::text(tr("status-slideshow")))
+                    .padding([0, 4])
+                    .style(widget::button::secondary)
+                    .on_press(Message::StartSlideshow(path)),
+            );
+        }
+
+        if settings_conflict {
+            row = row.push(settings_conflict_prompt());
+        }
+
+        if let Some(error) = error {
+            row = row.push(
+                widget::button(
+                    widget::text(error)
+                        .wrapping(Wrapping::None)
+                        .style(widget::text::danger),
+                )
+                .padding(0)
+                .style(widget::button::text)
+                .on_press(Message::DismissError),
+            );
+        }
+
+        widget::Column::new()
+            .spacing(3)
+            .padding(Padding {
+                top: 0.0,
+                ..Padding::new(5.0)
+            })
+            .width(Fill)
+            .push(widget::rule::horizontal(2))
+            .push(
+                row.push(widget::text(tr("status-profile")))
+                    .push(
+                        widget::pick_list(
+                            profiles,
+                            Some(profile.to_owned()),
+                            Message::SwitchProfile,
+                        )
+                        .padding([0, 4]),
+                    )
+                    .push(
+                        widget::pick_list(profiles, None::<String>, Message::OpenProfile)
+                            .placeholder(tr("status-new-window"))
+                            .padding([0, 4]),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("status-tasks")).size(12))
+                            .padding([0, 4])
+                            .style(widget::button::text)
+                            .on_press(Message::ToggleTaskPanel),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("status-copy-launch-command")).size(12))
+                            .padding([0, 4])
+                            .style(widget::button::text)
+                            .on_press(Message::CopyLaunchCommand),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("status-about")).size(12))
+                            .padding([0, 4])
+                            .style(widget::button::text)
+                            .on_press(Message::ToggleAbout),
+                    ),
+            )
+            .pipe(widget::container)
+            .style(|theme: &::iced::Theme| widget::container::Style {
+                background: Some(Background::Color(theme.palette().background)),
+                ..widget::container::transparent(theme)
+            })
+            .into()
+    }
+}
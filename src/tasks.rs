@@ -0,0 +1,457 @@
+//! [TaskQueue] impl, long running background tasks such as conversions, extractions,
+//! verifications and thumbnail generation, queued and run a few at a time with their
+//! progress shown in a task panel.
+
+use ::std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ::derive_more::IsVariant;
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message, archive,
+    error::{ArchiveError, Error},
+    freedesktop::{self, ThumbnailDirs},
+    i18n::{tr, tr_args},
+    list_entry_name,
+    worker::CancelToken,
+};
+
+/// Max amount of tasks running at once.
+const MAX_RUNNING: usize = 3;
+
+/// Max height of task panel.
+const PANEL_HEIGHT: f32 = 180.0;
+
+/// Kind of background task, along with what it needs to run.
+#[derive(Debug, Clone)]
+pub enum TaskKind {
+    /// Convert a rar or 7z archive to a zip archive, recompressing pages as jpeg images
+    /// of given quality if any.
+    Convert {
+        /// Directory of temporary files.
+        temp_dir: Arc<Path>,
+        /// Jpeg quality of recompressed pages.
+        quality: Option<u8>,
+    },
+    /// Extract an archive into a new directory within a directory.
+    Extract {
+        /// Directory the archive is extracted into.
+        dir: Arc<Path>,
+    },
+    /// Read and decode every page of an archive.
+    Verify,
+    /// Write freedesktop thumbnails of an archive.
+    Thumbnails {
+        /// Directories thumbnails are written to.
+        dirs: ThumbnailDirs,
+    },
+}
+
+impl TaskKind {
+    /// Get max amount of tasks of kind running at once.
+    const fn limit(&self) -> usize {
+        match self {
+            // Extractions write a lot, running them one at a time keeps the disk usable.
+            TaskKind::Extract { .. } => 1,
+            TaskKind::Convert { .. } | TaskKind::Verify | TaskKind::Thumbnails { .. } => 2,
+        }
+    }
+
+    /// Check if running tasks of kind stop when cancelled, tasks of other kinds may only
+    /// be cancelled while queued.
+    const fn is_cancellable(&self) -> bool {
+        matches!(self, TaskKind::Extract { .. } | TaskKind::Verify)
+    }
+
+    /// Get name of kind, selecting how tasks of kind are described.
+    const fn name(&self) -> &'static str {
+        match self {
+            TaskKind::Convert { .. } => "convert",
+            TaskKind::Extract { .. } => "extract",
+            TaskKind::Verify => "verify",
+            TaskKind::Thumbnails { .. } => "thumbnails",
+        }
+    }
+
+    /// Describe what a task of kind does with archive at path.
+    fn describe(&self, path: &Path) -> String {
+        tr_args(
+            "task-title",
+            &[("kind", self.name()), ("name", &list_entry_name(path))],
+        )
+    }
+
+    /// Check if kind is the same kind as other, ignoring what tasks need to run.
+    fn is_same_kind(&self, other: &Self) -> bool {
+        ::core::mem::discriminant(self) == ::core::mem::discriminant(other)
+    }
+}
+
+/// Identifier of a background task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+/// Status of a background task.
+#[derive(Debug, Clone, PartialEq, Eq, IsVariant)]
+pub enum TaskStatus {
+    /// Task is waiting to be run.
+    Queued,
+    /// Task is running.
+    Running,
+    /// Task finished successfully.
+    Done,
+    /// Task failed, with a message describing why.
+    Failed(String),
+    /// Task was cancelled.
+    Cancelled,
+}
+
+impl TaskStatus {
+    /// Check if task has finished, successfully or not.
+    pub const fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            TaskStatus::Done | TaskStatus::Failed(..) | TaskStatus::Cancelled
+        )
+    }
+}
+
+/// Result of a background task which finished successfully.
+#[derive(Debug, Clone)]
+pub enum TaskOutput {
+    /// Archive was converted to zip archive at path.
+    Converted(PathBuf),
+    /// Archive was extracted into directory at path.
+    Extracted(PathBuf),
+    /// Every page of archive could be read, the amount of pages is given.
+    Verified(usize),
+    /// Thumbnails of archive were written.
+    Thumbnails,
+}
+
+/// Background task.
+#[derive(Debug)]
+pub struct BackgroundTask {
+    /// Identifier of task.
+    pub id: TaskId,
+    /// Kind of task.
+    pub kind: TaskKind,
+    /// Archive task works on.
+    pub path: Arc<Path>,
+    /// Amount of steps done, and amount of steps in total, 0 if unknown.
+    pub progress: (usize, usize),
+    /// Status of task.
+    pub status: TaskStatus,
+    /// Token cancelling task.
+    pub cancel: CancelToken,
+    /// Task has been included in a summary of finished tasks.
+    summarized: bool,
+}
+
+impl BackgroundTask {
+    /// Run task to completion, reporting progress of it.
+    ///
+    /// # Errors
+    /// If the task fails or is cancelled.
+    pub fn run(
+        kind: &TaskKind,
+        path: &Path,
+        cancel: &CancelToken,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<TaskOutput, Arc<Error>> {
+        let archive_error = |source| {
+            Arc::new(Error::Archive {
+                path: path.to_path_buf(),
+                source,
+            })
+        };
+        match kind {
+            TaskKind::Convert { temp_dir, quality } => {
+                archive::convert_to_cbz(path, temp_dir, *quality).map(TaskOutput::Converted)
+            }
+            TaskKind::Extract { dir } => {
+                archive::extract_to(path, dir, cancel, progress).map(TaskOutput::Extracted)
+            }
+            TaskKind::Verify => archive::verify(path, cancel, progress).map(TaskOutput::Verified),
+            TaskKind::Thumbnails { dirs } => archive::preview(path, freedesktop::MAX_SIZE)
+                .and_then(|(cover, _)| {
+                    progress(0, 1);
+                    freedesktop::write_thumbnails(dirs, path, &cover)
+                        .map_err(ArchiveError::Write)?;
+                    progress(1, 1);
+                    Ok(TaskOutput::Thumbnails)
+                }),
+        }
+        .map_err(archive_error)
+    }
+
+    /// View task as a row of the task panel.
+    fn view<'a>(&self) -> Element<'a, Message> {
+        let status: Element<'a, Message> = match &self.status {
+            TaskStatus::Queued => widget::text(tr("task-queued"))
+                .style(widget::text::secondary)
+                .into(),
+            TaskStatus::Running => {
+                let (done, total) = self.progress;
+                widget::progress_bar(0.0..=total.max(1) as f32, done as f32)
+                    .length(120)
+                    .girth(8)
+                    .into()
+            }
+            TaskStatus::Done => widget::text(tr("task-done"))
+                .style(widget::text::success)
+                .into(),
+            TaskStatus::Failed(message) => widget::text(message.clone())
+                .wrapping(Wrapping::None)
+                .style(widget::text::danger)
+                .into(),
+            TaskStatus::Cancelled => widget::text(tr("task-cancelled"))
+                .style(widget::text::secondary)
+                .into(),
+        };
+        let cancellable = self.status.is_queued()
+            || (self.status.is_running()
+                && self.kind.is_cancellable()
+                && !self.cancel.is_cancelled());
+        widget::Row::new()
+            .spacing(8)
+            .align_y(Center)
+            .push(
+                widget::text(self.kind.describe(&self.path))
+                    .wrapping(Wrapping::None)
+                    .width(Fill),
+            )
+            .push(status)
+            .push(
+                widget::button(widget::text(tr("button-cancel")).size(12))
+                    .padding([0, 4])
+                    .style(widget::button::secondary)
+                    .on_press_maybe(cancellable.then_some(Message::CancelTask(self.id))),
+            )
+            .into()
+    }
+}
+
+/// Queue of background tasks, along with finished tasks until they are cleared.
+#[derive(Debug, Default)]
+pub struct TaskQueue {
+    /// Tasks in the order they were queued.
+    tasks: Vec<BackgroundTask>,
+    /// Identifier of next queued task.
+    next_id: u64,
+}
+
+impl TaskQueue {
+    /// Queue a task of kind working on archive at path.
+    pub fn push(&mut self, kind: TaskKind, path: Arc<Path>) {
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+        self.tasks.push(BackgroundTask {
+            id,
+            kind,
+            path,
+            progress: (0, 0),
+            status: TaskStatus::Queued,
+            cancel: CancelToken::default(),
+            summarized: false,
+        });
+    }
+
+    /// Start queued tasks in order while limits of running tasks allow, returning the
+    /// started tasks. Only one task runs at a time while throttled.
+    pub fn start_next(&mut self, throttled: bool) -> Vec<&BackgroundTask> {
+        let max_running = if throttled { 1 } else { MAX_RUNNING };
+        let mut running = self
+            .tasks
+            .iter()
+            .filter(|task| task.status.is_running())
+            .count();
+        let mut started = Vec::new();
+        for index in 0..self.tasks.len() {
+            if running >= max_running {
+                break;
+            }
+            let task = &self.tasks[index];
+            let of_kind = self
+                .tasks
+                .iter()
+                .filter(|other| other.status.is_running() && other.kind.is_same_kind(&task.kind))
+                .count();
+            if task.status.is_queued() && of_kind < task.kind.limit() {
+                self.tasks[index].status = TaskStatus::Running;
+                running += 1;
+                started.push(index);
+            }
+        }
+        started
+            .into_iter()
+            .map(|index| &self.tasks[index])
+            .collect()
+    }
+
+    /// Get a task.
+    pub fn get(&self, id: TaskId) -> Option<&BackgroundTask> {
+        self.tasks.iter().find(|task| task.id == id)
+    }
+
+    /// Get a task mutably.
+    fn get_mut(&mut self, id: TaskId) -> Option<&mut BackgroundTask> {
+        self.tasks.iter_mut().find(|task| task.id == id)
+    }
+
+    /// Record progress of a running task.
+    pub fn progress(&mut self, id: TaskId, done: usize, total: usize) {
+        if let Some(task) = self.get_mut(id)
+            && task.status.is_running()
+        {
+            task.progress = (done, total);
+        }
+    }
+
+    /// Record that a running task has finished with given status.
+    pub fn finish(&mut self, id: TaskId, status: TaskStatus) {
+        if let Some(task) = self.get_mut(id) {
+            task.status = status;
+        }
+    }
+
+    /// Cancel a task, queued tasks are cancelled at once and running tasks once they
+    /// notice.
+    pub fn cancel(&mut self, id: TaskId) {
+        let Some(task) = self.get_mut(id) else {
+            return;
+        };
+        task.cancel.cancel();
+        if task.status.is_queued() {
+            task.status = TaskStatus::Cancelled;
+        }
+    }
+
+    /// Cancel every task which has not finished.
+    pub fn cancel_all(&mut self) {
+        let ids = self
+            .tasks
+            .iter()
+            .filter(|task| !task.status.is_finished())
+            .map(|task| task.id)
+            .collect::<Vec<_>>();
+        ids.into_iter().for_each(|id| self.cancel(id));
+    }
+
+    /// Summarize tasks finished since the last summary once no task is queued or
+    /// running, as a summary line and a body listing failures. [None] if tasks are still
+    /// active or none have finished.
+    pub fn take_summary(&mut self) -> Option<(String, String)> {
+        if self.active() > 0 {
+            return None;
+        }
+        let finished = self
+            .tasks
+            .iter_mut()
+            .filter(|task| !task.summarized)
+            .map(|task| {
+                task.summarized = true;
+                &*task
+            })
+            .collect::<Vec<_>>();
+        if finished.is_empty() {
+            return None;
+        }
+        let count = |status: fn(&TaskStatus) -> bool| {
+            finished.iter().filter(|task| status(&task.status)).count()
+        };
+        let summary = match finished.as_slice() {
+            [task] => tr_args(
+                "task-finished",
+                &[("title", &task.kind.describe(&task.path))],
+            ),
+            tasks => tr_args("task-all-finished", &[("count", &tasks.len().to_string())]),
+        };
+        let mut body = vec![tr_args(
+            "task-summary",
+            &[
+                ("done", &count(TaskStatus::is_done).to_string()),
+                ("failed", &count(TaskStatus::is_failed).to_string()),
+                ("cancelled", &count(TaskStatus::is_cancelled).to_string()),
+            ],
+        )];
+        body.extend(finished.iter().filter_map(|task| match &task.status {
+            TaskStatus::Failed(message) => Some(message.clone()),
+            _ => None,
+        }));
+        Some((summary, body.join("\n")))
+    }
+
+    /// Remove finished tasks.
+    pub fn clear_finished(&mut self) {
+        self.tasks.retain(|task| !task.status.is_finished());
+    }
+
+    /// Get amount of tasks queued or running.
+    pub fn active(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|task| !task.status.is_finished())
+            .count()
+    }
+
+    /// Check if there are no tasks, not even finished ones.
+    pub const fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// View task panel, listing tasks with their progress.
+    pub fn view<'a>(&self) -> Element<'a, Message> {
+        let finished = self.tasks.len() - self.active();
+        widget::Column::new()
+            .spacing(3)
+            .padding([3, 5])
+            .push(
+                widget::Row::new()
+                    .spacing(3)
+                    .align_y(Center)
+                    .push(widget::text(tr("task-panel")).width(Fill))
+                    .push(
+                        widget::button(widget::text(tr("task-cancel-all")).size(12))
+                            .padding([0, 4])
+                            .style(widget::button::secondary)
+                            .on_press_maybe((self.active() > 0).then_some(Message::CancelAllTasks)),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("task-clear-finished")).size(12))
+                            .padding([0, 4])
+                            .style(widget::button::secondary)
+                            .on_press_maybe((finished > 0).then_some(Message::ClearFinishedTasks)),
+                    )
+                    .push(
+                        widget::button(widget::text(tr("task-hide")).size(12))
+                            .padding([0, 4])
+                            .style(widget::button::text)
+                            .on_press(Message::ToggleTaskPanel),
+                    ),
+            )
+            .push(
+                widget::Column::new()
+                    .spacing(3)
+                    .extend(self.tasks.iter().map(BackgroundTask::view))
+                    .push(
+                        self.is_empty()
+                            .then(|| widget::text(tr("task-none")).style(widget::text::secondary)),
+                    )
+                    .pipe(widget::scrollable)
+                    .width(Fill),
+            )
+            .pipe(widget::container)
+            .max_height(PANEL_HEIGHT)
+            .into()
+    }
+}
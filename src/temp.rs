@@ -0,0 +1,145 @@
+//! Temporary files, kept in a directory in the cache of profile such that files left
+//! behind by crashes are removed on the next start instead of littering the library.
+
+use ::core::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use ::std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Name of directory of temporary files in cache directory.
+const TEMP_DIR: &str = "tmp";
+
+/// Extension of temporary files, never that of an archive such that they are not scanned.
+const EXTENSION: &str = "part";
+
+/// Temporary files older than this are stale even if the process owning them may still
+/// be running.
+const STALE_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Counter making names of temporary files of a process unique.
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Get directory of temporary files of profile, creating it if missing.
+///
+/// # Errors
+/// If the directory cannot be created.
+pub fn dir(xdg_dirs: &::xdg::BaseDirectories) -> io::Result<PathBuf> {
+    xdg_dirs.create_cache_directory(TEMP_DIR)
+}
+
+/// Get a unique path in directory for a temporary file, named after the process owning
+/// it and name.
+pub fn path(dir: &Path, name: &str) -> PathBuf {
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    dir.join(format!(
+        "{}-{count}-{name}.{EXTENSION}",
+        ::std::process::id()
+    ))
+}
+
+/// Get a hidden temporary path next to target, used when a file cannot be renamed into
+/// place from the directory of temporary files.
+fn sibling(target: &Path) -> PathBuf {
+    let name = target.file_name().unwrap_or_default().to_string_lossy();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    target.with_file_name(format!(
+        ".{name}.{}-{count}.{EXTENSION}",
+        ::std::process::id()
+    ))
+}
+
+/// Check if a temporary file in directory of temporary files is stale, it being owned by
+/// a process no longer running or being older than [STALE_AGE].
+fn is_stale(path: &Path, modified: SystemTime) -> bool {
+    if modified.elapsed().is_ok_and(|age| age > STALE_AGE) {
+        return true;
+    }
+    let Some(pid) = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split_once('-'))
+        .and_then(|(pid, _)| pid.parse::<u32>().ok())
+    else {
+        return true;
+    };
+    // Without procfs only the age of files is known.
+    let proc = Path::new("/proc");
+    pid != ::std::process::id() && proc.is_dir() && !proc.join(pid.to_string()).exists()
+}
+
+/// Remove stale temporary files from directory of temporary files, errors are logged.
+pub fn clean(dir: &Path) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            ::log::warn!("could not read temporary files of {dir:?}\n{err}");
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified())
+            && is_stale(&path, modified)
+        {
+            match fs::remove_file(&path) {
+                Ok(()) => ::log::info!("removed stale temporary file {path:?}"),
+                Err(err) => ::log::warn!("could not remove temporary file {path:?}\n{err}"),
+            }
+        }
+    }
+}
+
+/// Copy file to target through a hidden temporary file next to it, such that target is
+/// never partially written. Target is replaced if it exists.
+///
+/// # Errors
+/// If the file cannot be copied.
+pub fn copy(from: &Path, target: &Path) -> io::Result<()> {
+    let temporary = sibling(target);
+    let result = fs::copy(from, &temporary).and_then(|_| fs::rename(&temporary, target));
+    if result.is_err() {
+        _ = fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// Write contents to target through a hidden temporary file next to it, which is synced
+/// to disk before replacing target, such that target is never partially written even if
+/// the system crashes.
+///
+/// # Errors
+/// If the file cannot be written.
+pub fn write(target: &Path, contents: &[u8]) -> io::Result<()> {
+    let temporary = sibling(target);
+    let result = fs::File::create(&temporary)
+        .and_then(|mut file| {
+            io::Write::write_all(&mut file, contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temporary, target));
+    if result.is_err() {
+        _ = fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// Move a written temporary file to target, replacing it if it exists. Temporary files
+/// on another file system than target are copied.
+///
+/// # Errors
+/// If the file cannot be moved.
+pub fn persist(temporary: &Path, target: &Path) -> io::Result<()> {
+    match fs::rename(temporary, target) {
+        Err(err) if err.kind() == io::ErrorKind::CrossesDevices => {
+            let result = copy(temporary, target);
+            _ = fs::remove_file(temporary);
+            result
+        }
+        result => result,
+    }
+}
@@ -0,0 +1,87 @@
+//! Driving application state without a running daemon, such that integration tests may
+//! feed messages to a [Harness] and inspect the resulting state. Tasks returned when
+//! updating are dropped rather than run, such that tests feed the messages background
+//! work would produce themselves.
+
+use ::iced::{Task, window};
+
+use crate::State;
+pub use crate::{
+    ItemPath, Settings, ViewPath,
+    message::Message,
+    pane::{DirView, Pane},
+    window_state::Window,
+};
+
+/// Application state driven by messages fed by tests.
+#[derive(Debug, Default)]
+pub struct Harness {
+    /// Driven state, with an in memory catalogue.
+    state: State,
+}
+
+impl Harness {
+    /// Create state using settings, with no windows open.
+    pub fn new(settings: Settings) -> Self {
+        Self {
+            state: State {
+                saved_settings: settings.clone(),
+                draft: settings.clone(),
+                settings,
+                ..State::default()
+            },
+        }
+    }
+
+    /// Update state with a message, dropping the returned task.
+    pub fn update(&mut self, message: Message) {
+        drop::<Task<Message>>(self.state.update(message));
+    }
+
+    /// Open a window with a message adding it to state, such as [Message::AddDirWindow],
+    /// as the daemon would once the window has opened.
+    pub fn open_window(&mut self, add: impl FnOnce(window::Id) -> Message) -> window::Id {
+        let id = window::Id::unique();
+        self.update(add(id));
+        id
+    }
+
+    /// Get settings in use.
+    pub const fn settings(&self) -> &Settings {
+        &self.state.settings
+    }
+
+    /// Get settings as changed in the settings window, before they are applied.
+    pub const fn draft(&self) -> &Settings {
+        &self.state.draft
+    }
+
+    /// Get a window.
+    pub fn window(&self, id: window::Id) -> Option<&Window> {
+        self.state.windows.get(&id)
+    }
+
+    /// Get every window, ordered by id.
+    pub fn windows(&self) -> impl Iterator<Item = (window::Id, &Window)> {
+        self.state.windows.iter().map(|(&id, window)| (id, window))
+    }
+
+    /// Get paths to the panes of a main window.
+    pub fn panes(&self, window_id: window::Id) -> Vec<ViewPath> {
+        match self.window(window_id) {
+            Some(Window::Main { panes }) => panes
+                .iter()
+                .map(|(&pane, _)| ViewPath { window_id, pane })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get a pane of a main window.
+    pub fn pane(&self, view_path: ViewPath) -> Option<&Pane> {
+        match self.window(view_path.window_id)? {
+            Window::Main { panes } => panes.get(view_path.pane),
+            _ => None,
+        }
+    }
+}
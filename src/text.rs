@@ -0,0 +1,222 @@
+//! Shortening of text to fit a display width, and natural ordering of names.
+
+use ::core::cmp::Ordering;
+use ::std::{borrow::Cow, sync::LazyLock};
+
+use ::unicode_segmentation::UnicodeSegmentation;
+use ::unicode_width::UnicodeWidthStr;
+
+/// Text replacing the removed part of shortened text.
+const ELLIPSIS: &str = "...";
+
+/// Languages in which dotted and dotless i are separate letters.
+const DOTLESS_I_LANGUAGES: [&str; 2] = ["tr", "az"];
+
+/// Case folding of system locale, detected once.
+static CASE_FOLDING: LazyLock<CaseFolding> = LazyLock::new(|| {
+    ::sys_locale::get_locale().map_or(CaseFolding::Unicode, |tag| CaseFolding::of_locale(&tag))
+});
+
+/// Letter of alphabetical index used for titles starting with something other than a
+/// letter.
+pub const OTHER_LETTER: char = '#';
+
+/// Where text is shortened.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Ellipsis {
+    /// Keep the start of text, removing the end.
+    #[default]
+    End,
+    /// Keep the start and end of text, removing the middle, such that the distinguishing
+    /// tail of names such as volume numbers stays visible.
+    Middle,
+}
+
+/// Take graphemes from iterator while their total display width fits in budget,
+/// returning the byte length and display width of the graphemes taken.
+fn take_width<'a>(graphemes: impl Iterator<Item = &'a str>, budget: usize) -> (usize, usize) {
+    let mut len = 0;
+    let mut width = 0;
+    for grapheme in graphemes {
+        let grapheme_width = grapheme.width();
+        if width + grapheme_width > budget {
+            break;
+        }
+        len += grapheme.len();
+        width += grapheme_width;
+    }
+    (len, width)
+}
+
+/// Shorten text such that its display width is at most max_width, where wide characters
+/// such as CJK ideographs take up two columns. Text is only cut between grapheme
+/// clusters, such that combining marks and emoji sequences are kept whole.
+pub fn shorten_text(text: &str, max_width: usize, ellipsis: Ellipsis) -> Cow<'_, str> {
+    if text.width() <= max_width {
+        return Cow::Borrowed(text);
+    }
+    let budget = max_width.saturating_sub(ELLIPSIS.len());
+    let (head, tail) = match ellipsis {
+        Ellipsis::End => (take_width(text.graphemes(true), budget).0, 0),
+        Ellipsis::Middle => {
+            let (head, head_width) = take_width(text.graphemes(true), budget.div_ceil(2));
+            let (tail, _) = take_width(text.graphemes(true).rev(), budget - head_width);
+            (head, tail)
+        }
+    };
+    let mut buf = String::with_capacity(head + ELLIPSIS.len() + tail);
+    buf.push_str(&text[..head]);
+    buf.push_str(ELLIPSIS);
+    buf.push_str(&text[text.len() - tail..]);
+    Cow::Owned(buf)
+}
+
+/// Rules of a locale for ignoring case when comparing text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CaseFolding {
+    /// Default lowercase mapping of unicode.
+    #[default]
+    Unicode,
+    /// Turkic lowercase mapping, where `I` is the uppercase of dotless `ı` and `İ` the
+    /// uppercase of `i`.
+    Turkic,
+}
+
+impl CaseFolding {
+    /// Get case folding of a language tag such as `tr-TR` or `sv_SE.UTF-8`.
+    pub fn of_locale(tag: &str) -> Self {
+        let language = tag.split(['-', '_', '.', '@']).next().unwrap_or_default();
+        if DOTLESS_I_LANGUAGES.contains(&language) {
+            Self::Turkic
+        } else {
+            Self::Unicode
+        }
+    }
+
+    /// Get case folding of system locale.
+    pub fn system() -> Self {
+        *CASE_FOLDING
+    }
+
+    /// Fold case of a character.
+    fn fold(self, c: char) -> ::core::char::ToLowercase {
+        match (self, c) {
+            (CaseFolding::Turkic, 'I') => 'ı',
+            (CaseFolding::Turkic, 'İ') => 'i',
+            (_, c) => c,
+        }
+        .to_lowercase()
+    }
+
+    /// Compare names such that runs of digits are compared by their value, and other
+    /// text ignoring case, such that `Chapter 2` is ordered before `chapter 10`. Names
+    /// equal but for case and leading zeros are ordered by their bytes, such that only
+    /// equal names compare equal.
+    pub fn natural_cmp(self, a: &str, b: &str) -> Ordering {
+        let (mut a_runs, mut b_runs) = (digit_runs(a), digit_runs(b));
+        loop {
+            let ordering = match (a_runs.next(), b_runs.next()) {
+                (None, None) => return a.cmp(b),
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (Some(a), Some(b))
+                    if a.starts_with(|c: char| c.is_ascii_digit())
+                        && b.starts_with(|c: char| c.is_ascii_digit()) =>
+                {
+                    let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+                    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+                }
+                (Some(a), Some(b)) => a
+                    .chars()
+                    .flat_map(|c| self.fold(c))
+                    .cmp(b.chars().flat_map(|c| self.fold(c))),
+            };
+            if ordering.is_ne() {
+                return ordering;
+            }
+        }
+    }
+}
+
+/// Split text into runs of ascii digits and runs of other characters.
+fn digit_runs(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    ::core::iter::from_fn(move || {
+        let is_digit = rest.chars().next()?.is_ascii_digit();
+        let len = rest
+            .find(|c: char| c.is_ascii_digit() != is_digit)
+            .unwrap_or(rest.len());
+        let (run, tail) = rest.split_at(len);
+        rest = tail;
+        Some(run)
+    })
+}
+
+/// Compare names naturally, with the case folding of system locale, see
+/// [CaseFolding::natural_cmp].
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    CaseFolding::system().natural_cmp(a, b)
+}
+
+/// Get letter of alphabetical index a title is listed under, its first letter in upper
+/// case or [OTHER_LETTER] if it starts with something else, such as a digit.
+pub fn index_letter(title: &str) -> char {
+    match title.trim_start().chars().next() {
+        Some(c) if c.is_alphabetic() => c.to_uppercase().next().unwrap_or(c),
+        _ => OTHER_LETTER,
+    }
+}
+
+/// Name ordered naturally, for use as a sort key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NaturalKey(pub String);
+
+impl PartialOrd for NaturalKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NaturalKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        natural_cmp(&self.0, &other.0)
+    }
+}
+
+/// Check if name matches a glob pattern ignoring case, where `*` matches any text and `?`
+/// any single character.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    /// Check if characters are equal ignoring case.
+    fn eq(a: char, b: char) -> bool {
+        a == b || a.to_lowercase().eq(b.to_lowercase())
+    }
+    let (pattern, name) = (
+        pattern.chars().collect::<Vec<_>>(),
+        name.chars().collect::<Vec<_>>(),
+    );
+    let (mut p, mut n) = (0, 0);
+    // Position of last star in pattern, and of name when it was reached.
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || eq(c, name[n]) => {
+                p += 1;
+                n += 1;
+            }
+            _ => {
+                // Let the last star match one more character.
+                let Some((star, matched)) = backtrack else {
+                    return false;
+                };
+                backtrack = Some((star, matched + 1));
+                p = star + 1;
+                n = matched + 1;
+            }
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,214 @@
+//! [Transfer] impl, moving or copying archives dragged from one pane to another.
+
+use ::std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ::derive_more::IsVariant;
+
+use crate::{
+    Message, ViewPath,
+    dialog::{Dialog, DialogId},
+    error::Error,
+    i18n::{tr, tr_args},
+    inbox::{self, Resolution},
+    list_entry_name, temp,
+};
+
+/// Items dragged from a pane, dropped onto another pane when the mouse is released over
+/// it.
+#[derive(Debug, Clone)]
+pub struct Drag {
+    /// Pane items are dragged from.
+    pub source: ViewPath,
+    /// Dragged items.
+    pub paths: Vec<Arc<Path>>,
+}
+
+/// Whether transferred archives are moved or copied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IsVariant)]
+pub enum TransferKind {
+    /// Archives are moved, along with their catalogue state.
+    Move,
+    /// Archives are copied, originals are kept.
+    Copy,
+}
+
+/// Outcome of transferring an archive.
+#[derive(Debug, Clone, IsVariant)]
+pub enum Outcome {
+    /// Archive was transferred to path.
+    Transferred(PathBuf),
+    /// Archive was kept where it is, as a file of the same name exists.
+    Skipped,
+    /// A file of the same name exists at path, and how to resolve it was not given.
+    Conflict(PathBuf),
+}
+
+/// Archives dropped onto a pane, transferred to its directory once confirmed.
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    /// Whether archives are moved or copied.
+    pub kind: TransferKind,
+    /// Pane archives are transferred from.
+    pub source: ViewPath,
+    /// Pane archives are dropped onto.
+    pub target: ViewPath,
+    /// Directory archives are transferred to.
+    pub dir: Arc<Path>,
+    /// Transferred archives.
+    pub paths: Vec<Arc<Path>>,
+    /// Amount of archives transferred or failed, [None] until confirmed.
+    pub done: Option<usize>,
+    /// Amount of archives which could not be transferred.
+    pub failed: usize,
+    /// Size of transferred archives in bytes.
+    pub bytes_done: u64,
+    /// Size of all archives in bytes.
+    pub bytes_total: u64,
+    /// Resolution of conflicts with existing files chosen for all archives.
+    pub resolve_all: Option<Resolution>,
+    /// Resolution of a conflict chosen for the next archive only.
+    pub resolve_next: Option<Resolution>,
+    /// Dialog showing progress once confirmed.
+    pub dialog: Option<DialogId>,
+}
+
+impl Transfer {
+    /// Create a transfer of dragged items to the directory of the pane they were dropped
+    /// onto, awaiting confirmation.
+    pub fn new(drag: Drag, kind: TransferKind, target: ViewPath, dir: Arc<Path>) -> Self {
+        let bytes_total = drag
+            .paths
+            .iter()
+            .filter_map(|path| fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        Self {
+            kind,
+            source: drag.source,
+            target,
+            dir,
+            paths: drag.paths,
+            done: None,
+            failed: 0,
+            bytes_done: 0,
+            bytes_total,
+            resolve_all: None,
+            resolve_next: None,
+            dialog: None,
+        }
+    }
+
+    /// Get archive transferred next, if any remain.
+    pub fn next(&self) -> Option<&Arc<Path>> {
+        self.paths.get(self.done?)
+    }
+
+    /// Get resolution of a conflict of the next archive, taking one chosen for it alone.
+    pub fn take_resolution(&mut self) -> Option<Resolution> {
+        self.resolve_next.take().or(self.resolve_all)
+    }
+
+    /// Leave archives not yet transferred where they are.
+    pub fn stop(&mut self) {
+        if let Some(done) = self.done {
+            self.paths.truncate(done);
+        }
+    }
+
+    /// Create dialog asking for confirmation over the window of the target pane.
+    pub fn confirm_dialog(&self) -> Dialog {
+        let (title, action) = match self.kind {
+            TransferKind::Move => ("dialog-transfer-move", "button-move"),
+            TransferKind::Copy => ("dialog-transfer-copy", "button-copy"),
+        };
+        let name = match &*self.paths {
+            [path] => list_entry_name(path),
+            paths => tr_args("dialog-archives", &[("count", &paths.len().to_string())]),
+        };
+        Dialog::confirm(
+            self.target.window_id,
+            tr_args(
+                title,
+                &[("name", &name), ("dir", &list_entry_name(&self.dir))],
+            ),
+            tr(action),
+            Message::ConfirmTransfer,
+        )
+        .on_cancel(Message::CancelTransfer)
+    }
+
+    /// Create dialog showing progress over the window of the target pane.
+    pub fn progress_dialog(&self) -> Dialog {
+        let mut dialog = Dialog::progress(
+            self.target.window_id,
+            tr(match self.kind {
+                TransferKind::Move => "dialog-moving",
+                TransferKind::Copy => "dialog-copying",
+            }),
+            None,
+        );
+        self.update_dialog(&mut dialog);
+        dialog
+    }
+
+    /// Show how far transfer has come in its progress dialog.
+    pub fn update_dialog(&self, dialog: &mut Dialog) {
+        dialog.set_body(tr_args(
+            "dialog-transfer-progress",
+            &[
+                ("done", &self.done.unwrap_or(0).to_string()),
+                ("count", &self.paths.len().to_string()),
+            ],
+        ));
+        dialog.set_progress(self.bytes_done as f32 / self.bytes_total.max(1) as f32);
+    }
+
+    /// Record that an archive of given size has been transferred, or failed to.
+    pub fn step(&mut self, size: u64, failed: bool) {
+        self.done = Some(self.done.map_or(1, |done| done + 1));
+        self.failed += usize::from(failed);
+        self.bytes_done += size;
+    }
+}
+
+/// Move or copy an archive into a directory, blocking while it is transferred. A file of
+/// the same name is resolved as given, and left for the caller to resolve otherwise.
+///
+/// # Errors
+/// If the archive cannot be transferred.
+pub fn transfer_file(
+    kind: TransferKind,
+    from: &Path,
+    dir: &Path,
+    resolution: Option<Resolution>,
+) -> Result<Outcome, Error> {
+    let to = dir.join(from.file_name().unwrap_or_default());
+    let (to, overwrite) = match resolution {
+        _ if !to.try_exists().unwrap_or(true) => (to, false),
+        None => return Ok(Outcome::Conflict(to)),
+        Some(Resolution::Skip) => return Ok(Outcome::Skipped),
+        Some(Resolution::Overwrite) => (to, true),
+        Some(Resolution::Rename) => (inbox::free_path(&to), false),
+    };
+    match kind {
+        TransferKind::Move => inbox::move_file(from, &to, overwrite)?,
+        TransferKind::Copy => {
+            let copy = || {
+                if !overwrite && to.try_exists()? {
+                    return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+                }
+                temp::copy(from, &to)
+            };
+            copy().map_err(|source| Error::Move {
+                from: from.to_path_buf(),
+                to: to.clone(),
+                source,
+            })?;
+        }
+    }
+    Ok(Outcome::Transferred(to))
+}
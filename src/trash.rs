@@ -0,0 +1,182 @@
+//! Archives moved to trash according to the freedesktop trash specification, such that
+//! file managers may restore them as well, listed in a window where they may be restored.
+
+use ::std::{
+    ffi::OsString,
+    fs::{self, DirBuilder},
+    io::{self, Write},
+    os::unix::fs::{DirBuilderExt, OpenOptionsExt},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ::iced::{
+    Alignment::Center,
+    Element,
+    Length::Fill,
+    widget::{self, text::Wrapping},
+};
+use ::tap::Pipe;
+
+use crate::{
+    Message,
+    catalogue::Trashed,
+    error::Error,
+    format, freedesktop,
+    i18n::{tr, tr_args},
+    inbox, list_entry_name,
+};
+
+/// Get home trash directory, shared by all profiles and applications.
+pub fn dir(xdg_dirs: &::xdg::BaseDirectories) -> Option<PathBuf> {
+    xdg_dirs.data_home.as_ref().map(|data| data.join("Trash"))
+}
+
+/// Get path of the info file of a file in trash.
+fn info_path(trashed: &Path) -> Option<PathBuf> {
+    let mut name = OsString::from(trashed.file_name()?);
+    name.push(".trashinfo");
+    Some(trashed.parent()?.parent()?.join("info").join(name))
+}
+
+/// Move file to trash directory, returning the path it was moved to.
+///
+/// # Errors
+/// If the trash directory cannot be written to, or the file cannot be moved.
+pub fn trash(trash_dir: &Path, path: &Path) -> Result<PathBuf, Error> {
+    let error = |source| Error::Trash {
+        path: path.to_path_buf(),
+        source,
+    };
+    if path.file_name().is_none() {
+        return Err(error(io::Error::from(io::ErrorKind::InvalidInput)));
+    }
+    let files = trash_dir.join("files");
+    for dir in [&files, &trash_dir.join("info")] {
+        DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)
+            .map_err(error)?;
+    }
+
+    // Names are reserved by creating their info file, as required by the specification.
+    let stem = path.file_stem().unwrap_or_default();
+    let (trashed, info) = (1usize..)
+        .map(|number| {
+            let mut name = OsString::from(stem);
+            if number > 1 {
+                name.push(format!(" ({number})"));
+            }
+            if let Some(extension) = path.extension() {
+                name.push(".");
+                name.push(extension);
+            }
+            files.join(name)
+        })
+        .find_map(|trashed| {
+            let info = info_path(&trashed)?;
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&info)
+            {
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => None,
+                result => Some(result.map(|file| (trashed, info, file))),
+            }
+        })
+        .unwrap_or_else(|| Err(io::Error::from(io::ErrorKind::AlreadyExists)))
+        .and_then(|(trashed, info, mut file)| {
+            let deleted = ::jiff::Zoned::now().strftime("%Y-%m-%dT%H:%M:%S");
+            write!(
+                file,
+                "[Trash Info]\nPath={}\nDeletionDate={deleted}\n",
+                freedesktop::escape_path(path)
+            )
+            .inspect_err(|_| _ = fs::remove_file(&info))?;
+            Ok((trashed, info))
+        })
+        .map_err(error)?;
+
+    inbox::move_file(path, &trashed, false).inspect_err(|_| _ = fs::remove_file(&info))?;
+    Ok(trashed)
+}
+
+/// Move file in trash back to where it was, removing its info file.
+///
+/// # Errors
+/// If the file cannot be moved, or a file exists where it was.
+pub fn restore(trashed: &Path, original: &Path) -> Result<(), Error> {
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent).map_err(|source| Error::Move {
+            from: trashed.to_path_buf(),
+            to: original.to_path_buf(),
+            source,
+        })?;
+    }
+    inbox::move_file(trashed, original, false)?;
+    if let Some(info) = info_path(trashed) {
+        _ = fs::remove_file(info);
+    }
+    Ok(())
+}
+
+/// View archives moved to trash, with buttons restoring them or forgetting those no
+/// longer in trash.
+pub fn view(trashed: &[Trashed]) -> Element<'_, Message> {
+    if trashed.is_empty() {
+        return widget::text(tr("trash-empty"))
+            .style(widget::text::secondary)
+            .pipe(widget::container)
+            .center(Fill)
+            .into();
+    }
+    widget::Column::new()
+        .padding(5)
+        .spacing(5)
+        .extend(trashed.iter().map(|item| {
+            let in_trash = item.trashed.exists();
+            let path = Arc::<Path>::from(item.path.as_path());
+            widget::Row::new()
+                .align_y(Center)
+                .spacing(5)
+                .push(
+                    widget::Column::new()
+                        .width(Fill)
+                        .push(widget::text(list_entry_name(&path)).wrapping(Wrapping::None))
+                        .push(
+                            widget::text({
+                                let dir = item
+                                    .path
+                                    .parent()
+                                    .unwrap_or(Path::new(""))
+                                    .display()
+                                    .to_string();
+                                match format::date(item.deleted) {
+                                    Some(date) => {
+                                        tr_args("trash-deleted", &[("dir", &dir), ("date", &date)])
+                                    }
+                                    None => dir,
+                                }
+                            })
+                            .size(12)
+                            .style(widget::text::secondary)
+                            .wrapping(Wrapping::None),
+                        ),
+                )
+                .push(if in_trash {
+                    widget::button(widget::text(tr("trash-restore")))
+                        .padding(3)
+                        .on_press(Message::RestoreTrashed(path))
+                } else {
+                    widget::button(widget::text(tr("trash-forget")))
+                        .padding(3)
+                        .style(widget::button::secondary)
+                        .on_press(Message::ForgetTrashed(path))
+                })
+                .into()
+        }))
+        .pipe(widget::scrollable)
+        .into()
+}
@@ -1,17 +1,45 @@
 //! [Window] impl.
 
+use ::core::time::Duration;
+use ::std::{path::Path, sync::Arc};
+
 use ::clap::ValueEnum;
 use ::iced::{
     Alignment::{self, Center},
-    Background, Element,
+    Border, Element,
     Length::Fill,
-    Padding,
     widget::{self, pane_grid},
+    window,
 };
-use ::katalog_lib::ThemeValueEnum;
 use ::tap::Pipe;
 
-use crate::{Cli, Message, Settings, pane::DirView};
+use crate::{
+    Activation, CardBadge, CardSize, Cli, HomeTile, MAX_SCALE_FACTOR, MIN_SCALE_FACTOR, Message,
+    ReaderTheme, Settings, SizeUnits, ThemeArg, ViewPath,
+    about::About,
+    cache::{Shown, ThumbnailCache},
+    calendar::Calendar,
+    catalogue::Trashed,
+    debug,
+    dialog::Dialog,
+    geometry::WindowKind,
+    help,
+    history::{self, Change},
+    home::Home,
+    hover_preview::HoverPreview,
+    i18n::{Language, tr, tr_args},
+    input::{Action, FunctionKey, MouseButton},
+    metadata_editor::MetadataEditor,
+    pane::{ContextMenu, Pane, ViewOptions},
+    reader::Reader,
+    shelf::Shelf,
+    sidebar::Sidebar,
+    statistics::Statistics,
+    status_bar::{self, Activity, StatusBar},
+    tasks::TaskQueue,
+    transfer::Drag,
+    trash,
+};
 
 /// Window kinds.
 #[derive(Debug, Clone)]
@@ -19,53 +47,297 @@ pub enum Window {
     /// Window is a main window.
     Main {
         /// Panes of window.
-        panes: pane_grid::State<DirView>,
+        panes: pane_grid::State<Pane>,
     },
     /// Window is a settings window.
     Settings,
+    /// Window is a reader window, boxed as readers are much larger than other windows.
+    Reader(Box<Reader>),
+    /// Window is a home window.
+    Home(Home),
+    /// Window is an undo history window.
+    History,
+    /// Window lists keyboard shortcuts.
+    Help,
+    /// Window shows version, build and licenses.
+    About(About),
+    /// Window is a statistics window.
+    Stats(Statistics),
+    /// Window is a calendar window.
+    Calendar(Calendar),
+    /// Window lists archives moved to trash.
+    Trash(Vec<Trashed>),
+    /// Window edits metadata of archives.
+    Metadata(MetadataEditor),
+}
+
+/// Application state shared by window views.
+#[derive(Debug, Clone, Copy)]
+pub struct Context<'a> {
+    /// Cli arguments of application.
+    pub cli: &'a Cli,
+    /// Settings used by application.
+    pub settings: &'a Settings,
+    /// Settings being edited in settings window.
+    pub draft: &'a Settings,
+    /// Thumbnails shared by all panes.
+    pub thumbnail_cache: &'a ThumbnailCache<Shown>,
+    /// Estimated time spent reading a page.
+    pub page_time: Duration,
+    /// Recently opened directories and archives.
+    pub recent: &'a [Arc<Path>],
+    /// Archives being read.
+    pub shelf: &'a Shelf,
+    /// Preview of the card the mouse rests on, if any.
+    pub hover_preview: Option<&'a HoverPreview>,
+    /// Changes of read state which may be undone, most recent change last.
+    pub undo_history: &'a [Change],
+    /// Names of profiles.
+    pub profiles: &'a [String],
+    /// Names of reading lists.
+    pub reading_lists: &'a [String],
+    /// Names of backups of profile, newest first.
+    pub backups: &'a [String],
+    /// Backup chosen to be restored, if any.
+    pub selected_backup: Option<&'a str>,
+    /// Directory typed to add as a library root.
+    pub library_root_input: &'a str,
+    /// Tags of archives.
+    pub tags: &'a [String],
+    /// Sidebars are collapsed.
+    pub sidebar_collapsed: bool,
+    /// Reading list selected items may be added to.
+    pub target_list: Option<&'a str>,
+    /// Resource usage shown by debug overlay, if it is shown.
+    pub debug_stats: Option<debug::Stats>,
+    /// Pane most recently entered by the mouse.
+    pub hovered_pane: Option<ViewPath>,
+    /// Pane keyboard shortcuts act on, if any.
+    pub focused_pane: Option<ViewPath>,
+    /// Items being dragged from a pane, if any.
+    pub drag: Option<&'a Drag>,
+    /// Background activity.
+    pub activity: Activity,
+    /// Background tasks, if the task panel is shown.
+    pub tasks: Option<&'a TaskQueue>,
+    /// Most recent error reported to user.
+    pub error: Option<&'a str>,
+    /// Config file changed on disk while there were unsaved changes.
+    pub settings_conflict: bool,
+    /// Dialog shown over window, if any.
+    pub dialog: Option<&'a Dialog>,
 }
 
 impl Window {
+    /// Get kind of window.
+    pub const fn kind(&self) -> WindowKind {
+        match self {
+            Window::Main { .. } => WindowKind::Main,
+            Window::Settings => WindowKind::Settings,
+            Window::Reader(..) => WindowKind::Reader,
+            Window::Home(..) => WindowKind::Home,
+            Window::History => WindowKind::History,
+            Window::Help => WindowKind::Help,
+            Window::About(..) => WindowKind::About,
+            Window::Stats(..) => WindowKind::Stats,
+            Window::Calendar(..) => WindowKind::Calendar,
+            Window::Trash(..) => WindowKind::Trash,
+            Window::Metadata(..) => WindowKind::Metadata,
+        }
+    }
+
+    /// Check if window is fullscreen.
+    pub const fn is_fullscreen(&self) -> bool {
+        matches!(self, Window::Reader(reader) if reader.fullscreen)
+    }
+
     /// View window state.
     pub fn view<'this>(
         &'this self,
-        cli: &'this Cli,
-        settings: &'this Settings,
-    ) -> impl Into<Element<'this, Message>> {
-        match self {
+        window_id: window::Id,
+        Context {
+            cli,
+            settings,
+            draft,
+            thumbnail_cache,
+            page_time,
+            recent,
+            shelf,
+            hover_preview,
+            undo_history,
+            profiles,
+            reading_lists,
+            backups,
+            selected_backup,
+            library_root_input,
+            tags,
+            sidebar_collapsed,
+            target_list,
+            debug_stats,
+            hovered_pane,
+            focused_pane,
+            drag,
+            activity,
+            tasks,
+            error,
+            settings_conflict,
+            dialog,
+        }: Context<'this>,
+    ) -> Element<'this, Message> {
+        let is_dirty = draft != settings;
+        let content = match self {
             Window::Main { panes } => widget::Column::new()
-                .push(widget::PaneGrid::new(panes, |pane, state, is_maximized| {
-                    _ = (pane, is_maximized);
-                    pane_grid::Content::new(
-                        state.view(settings.card_width.into(), settings.max_card_text_width),
-                    )
-                }))
                 .push(
-                    widget::Column::new()
-                        .spacing(3)
-                        .padding(Padding {
-                            top: 0.0,
-                            ..Padding::new(5.0)
-                        })
-                        .width(Fill)
-                        .push(widget::rule::horizontal(2))
+                    settings
+                        .continue_reading_shelf
+                        .then(|| shelf.view())
+                        .flatten(),
+                )
+                .push(
+                    widget::Row::new()
+                        .push(settings.sidebar.then(|| {
+                            Sidebar {
+                                window_id,
+                                roots: &settings.library_roots,
+                                reading_lists,
+                                tags,
+                                recent,
+                                collapsed: sidebar_collapsed,
+                            }
+                            .view()
+                        }))
                         .push(
-                            widget::Row::new()
-                                .align_y(Center)
-                                .spacing(0)
-                                .push(widget::space::horizontal())
-                                .push(widget::text(format!("profile: {}", cli.profile))),
-                        )
-                        .pipe(widget::container)
-                        .style(|theme: &::iced::Theme| widget::container::Style {
-                            background: Some(Background::Color(theme.palette().background)),
-                            ..widget::container::transparent(theme)
-                        }),
-                ),
+                            widget::PaneGrid::new(panes, move |pane, state, is_maximized| {
+                                _ = is_maximized;
+                                let view_path = ViewPath { window_id, pane };
+                                // A single pane needs no highlight to tell it apart.
+                                let is_focused = focused_pane == Some(view_path) && panes.len() > 1;
+                                state
+                                    .view
+                                    .view(
+                                        view_path,
+                                        &state.filter,
+                                        // Feed items are not files of the library until downloaded.
+                                        state
+                                            .context_menu
+                                            .as_ref()
+                                            .filter(|_| state.feed.is_none())
+                                            .map(|path| ContextMenu {
+                                                path,
+                                                // Items cannot be added to the list they are shown in.
+                                                target_list: target_list.filter(|list| {
+                                                    state.list.as_deref() != Some(*list)
+                                                }),
+                                                in_list: state.list.is_some(),
+                                                open_with: &state.open_with,
+                                                is_dir: state.view.item_is_dir(path),
+                                                has_cover: state.has_cover,
+                                            }),
+                                        thumbnail_cache,
+                                        ViewOptions {
+                                            icon_width: settings.card_size.width(),
+                                            max_text_len: settings.max_card_text_width,
+                                            badges: &settings.card_badges,
+                                            page_time,
+                                            recent,
+                                            mode: state.mode,
+                                            sort: state.list_sort(),
+                                            size_units: settings.size_units,
+                                            accents: settings.cover_accents,
+                                            scanning: state.scanning,
+                                            sections: settings.section_headers,
+                                        },
+                                    )
+                                    .pipe(widget::mouse_area)
+                                    .on_enter(Message::PaneHovered(view_path))
+                                    .on_press(Message::CloseContextMenu(view_path))
+                                    .on_release(Message::PaneReleased(view_path))
+                                    .pipe(|content| widget::Stack::new().push(content))
+                                    .extend(
+                                        state
+                                            .quick_look
+                                            .as_ref()
+                                            .map(|quick_look| quick_look.view(view_path)),
+                                    )
+                                    .extend(
+                                        hover_preview
+                                            .filter(|preview| {
+                                                preview.item_path.view_path == view_path
+                                                    && state.quick_look.is_none()
+                                                    && state.view.contains(&preview.item_path.path)
+                                            })
+                                            .and_then(HoverPreview::view),
+                                    )
+                                    .extend(
+                                        drag.filter(|drag| {
+                                            drag.source != view_path
+                                                && hovered_pane == Some(view_path)
+                                                && state.path.is_some()
+                                                && state.shows_dir()
+                                        })
+                                        .map(|_| drop_hint()),
+                                    )
+                                    .extend(state.pack.as_ref().map(|pack| pack.view(view_path)))
+                                    .extend(
+                                        state
+                                            .chooser
+                                            .as_ref()
+                                            .map(|chooser| chooser.view(view_path)),
+                                    )
+                                    .pipe(pane_grid::Content::new)
+                                    .title_bar(pane_grid::TitleBar::new(state.header(
+                                        view_path,
+                                        reading_lists,
+                                        &settings.opds_feeds,
+                                    )))
+                                    .style(move |theme: &::iced::Theme| {
+                                        if is_focused {
+                                            widget::container::Style {
+                                                border: Border {
+                                                    color: theme.palette().primary,
+                                                    width: 1.0,
+                                                    radius: 0.0.into(),
+                                                },
+                                                ..widget::container::transparent(theme)
+                                            }
+                                        } else {
+                                            widget::container::transparent(theme)
+                                        }
+                                    })
+                            })
+                            .on_click(move |pane| {
+                                Message::PaneFocused(ViewPath { window_id, pane })
+                            }),
+                        ),
+                )
+                .push(tasks.map(TaskQueue::view))
+                .push(
+                    StatusBar {
+                        profile: &cli.profile,
+                        profiles,
+                        pane: hovered_pane
+                            .filter(|view_path| view_path.window_id == window_id)
+                            .and_then(|view_path| panes.get(view_path.pane))
+                            .or_else(|| panes.iter().next().map(|(_, pane)| pane)),
+                        activity,
+                        error,
+                        settings_conflict,
+                        size_units: settings.size_units,
+                        page_time,
+                    }
+                    .view(),
+                )
+                .pipe(|main| {
+                    widget::Stack::new()
+                        .push(main)
+                        .extend(debug_stats.map(|stats| stats.view(settings.size_units)))
+                })
+                .into(),
             Window::Settings => widget::Column::new()
                 .padding(5)
                 .spacing(3)
                 .align_x(Center)
+                .push(settings_conflict.then(status_bar::settings_conflict_prompt))
                 .push(widget::space::vertical())
                 .push(
                     widget::Column::new()
@@ -74,12 +346,26 @@ impl Window {
                             widget::Row::new()
                                 .align_y(Center)
                                 .spacing(3)
-                                .push("Theme")
+                                .push(widget::text(tr("settings-language")))
+                                .push(
+                                    widget::pick_list(
+                                        Language::options(),
+                                        Some(Language::from_setting(draft.language.as_deref())),
+                                        Message::SetLanguage,
+                                    )
+                                    .padding(3),
+                                ),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-theme")))
                                 .push(
                                     widget::mouse_area(
                                         widget::pick_list(
-                                            ThemeValueEnum::value_variants(),
-                                            Some(settings.theme),
+                                            ThemeArg::value_variants(),
+                                            Some(draft.theme),
                                             Message::SetTheme,
                                         )
                                         .padding(3),
@@ -87,6 +373,356 @@ impl Window {
                                     .on_scroll(Message::ThemeScroll),
                                 ),
                         )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-reader-theme")))
+                                .push(
+                                    widget::pick_list(
+                                        ReaderTheme::options(),
+                                        Some(ReaderTheme(draft.reader_theme)),
+                                        Message::SetReaderTheme,
+                                    )
+                                    .padding(3),
+                                ),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-activation")))
+                                .push(
+                                    widget::pick_list(
+                                        Activation::value_variants(),
+                                        Some(draft.activation),
+                                        Message::SetActivation,
+                                    )
+                                    .padding(3),
+                                ),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-card-size")))
+                                .push(
+                                    widget::pick_list(
+                                        CardSize::PRESETS
+                                            .into_iter()
+                                            .chain([CardSize::Millimeters(
+                                                draft.card_size.millimeters(),
+                                            )])
+                                            .collect::<Vec<_>>(),
+                                        Some(draft.card_size),
+                                        Message::SetCardSize,
+                                    )
+                                    .padding(3),
+                                )
+                                .push(widget::slider(
+                                    CardSize::MIN_MILLIMETERS..=CardSize::MAX_MILLIMETERS,
+                                    draft.card_size.millimeters(),
+                                    |mm| Message::SetCardSize(CardSize::Millimeters(mm)),
+                                )),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-swipe-distance")))
+                                .push(widget::slider(
+                                    0..=400,
+                                    draft.swipe_distance,
+                                    Message::SetSwipeDistance,
+                                ))
+                                .push(widget::text(if draft.swipe_distance == 0 {
+                                    tr("settings-off")
+                                } else {
+                                    format!("{}px", draft.swipe_distance)
+                                })),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-pinch-distance")))
+                                .push(widget::slider(
+                                    0..=200,
+                                    draft.pinch_distance,
+                                    Message::SetPinchDistance,
+                                ))
+                                .push(widget::text(if draft.pinch_distance == 0 {
+                                    tr("settings-off")
+                                } else {
+                                    format!("{}px", draft.pinch_distance)
+                                })),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-slideshow-interval")))
+                                .push(widget::slider(
+                                    1..=60,
+                                    draft.slideshow_interval,
+                                    Message::SetSlideshowInterval,
+                                ))
+                                .push(widget::text(format!("{}s", draft.slideshow_interval))),
+                        )
+                        .push(
+                            widget::checkbox(draft.slideshow_loop)
+                                .label(tr("settings-slideshow-loop"))
+                                .on_toggle(Message::SetSlideshowLoop),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-thumbnail-cache")))
+                                .push(widget::slider(
+                                    16..=2048,
+                                    draft.thumbnail_cache_size,
+                                    Message::SetThumbnailCacheSize,
+                                ))
+                                .push(widget::text(format!("{} MiB", draft.thumbnail_cache_size))),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-hover-preview")))
+                                .push(
+                                    widget::slider(
+                                        0..=3000,
+                                        draft.hover_preview_delay,
+                                        Message::SetHoverPreviewDelay,
+                                    )
+                                    .step(100u16),
+                                )
+                                .push(widget::text(if draft.hover_preview_delay == 0 {
+                                    tr("settings-off")
+                                } else {
+                                    format!("{}ms", draft.hover_preview_delay)
+                                })),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-file-sizes")))
+                                .push(
+                                    widget::pick_list(
+                                        SizeUnits::value_variants(),
+                                        Some(draft.size_units),
+                                        Message::SetSizeUnits,
+                                    )
+                                    .padding(3),
+                                ),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-scale")))
+                                .push(
+                                    widget::slider(
+                                        MIN_SCALE_FACTOR..=MAX_SCALE_FACTOR,
+                                        draft.scale_factor,
+                                        Message::SetScaleFactor,
+                                    )
+                                    .step(0.1),
+                                )
+                                .push(widget::text(format!("{:.0}%", draft.scale_factor * 100.0))),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-font-size")))
+                                .push(widget::slider(
+                                    10..=32,
+                                    draft.font_size,
+                                    Message::SetFontSize,
+                                ))
+                                .push(widget::text(if draft.font_size == settings.font_size {
+                                    format!("{}px", draft.font_size)
+                                } else {
+                                    tr_args(
+                                        "settings-font-size-restart",
+                                        &[("size", &draft.font_size.to_string())],
+                                    )
+                                })),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-convert-quality")))
+                                .push(widget::slider(
+                                    0..=100,
+                                    draft.convert_quality,
+                                    Message::SetConvertQuality,
+                                ))
+                                .push(widget::text(if draft.convert_quality == 0 {
+                                    tr("settings-keep-pages")
+                                } else {
+                                    draft.convert_quality.to_string()
+                                })),
+                        )
+                        .push(
+                            widget::checkbox(draft.battery_saver)
+                                .label(tr("settings-battery-saver"))
+                                .on_toggle(Message::SetBatterySaver),
+                        )
+                        .push(
+                            widget::checkbox(draft.animate_covers)
+                                .label(tr("settings-animate-covers"))
+                                .on_toggle(Message::SetAnimateCovers),
+                        )
+                        .push(
+                            widget::checkbox(draft.cover_accents)
+                                .label(tr("settings-cover-accents"))
+                                .on_toggle(Message::SetCoverAccents),
+                        )
+                        .push(
+                            widget::checkbox(draft.section_headers)
+                                .label(tr("settings-section-headers"))
+                                .on_toggle(Message::SetSectionHeaders),
+                        )
+                        .push(
+                            widget::checkbox(draft.freedesktop_thumbnails)
+                                .label(tr("settings-freedesktop-thumbnails"))
+                                .on_toggle(Message::SetFreedesktopThumbnails),
+                        )
+                        .push(
+                            widget::checkbox(draft.task_notifications)
+                                .label(tr("settings-task-notifications"))
+                                .on_toggle(Message::SetTaskNotifications),
+                        )
+                        .push(
+                            widget::checkbox(draft.skip_hidden)
+                                .label(tr("settings-skip-hidden"))
+                                .on_toggle(Message::SetSkipHidden),
+                        )
+                        .push(
+                            widget::checkbox(draft.follow_symlinks)
+                                .label(tr("settings-follow-symlinks"))
+                                .on_toggle(Message::SetFollowSymlinks),
+                        )
+                        .push(
+                            widget::checkbox(draft.remember_window_size)
+                                .label(tr("settings-remember-window-size"))
+                                .on_toggle(Message::SetRememberWindowSize),
+                        )
+                        .push(
+                            widget::checkbox(draft.remember_window_position)
+                                .label(tr("settings-remember-window-position"))
+                                .on_toggle(Message::SetRememberWindowPosition),
+                        )
+                        .push(
+                            widget::checkbox(draft.continue_reading_shelf)
+                                .label(tr("settings-continue-reading-shelf"))
+                                .on_toggle(Message::SetContinueReadingShelf),
+                        )
+                        .push(
+                            widget::checkbox(draft.sidebar)
+                                .label(tr("settings-sidebar"))
+                                .on_toggle(Message::SetSidebar),
+                        )
+                        .push(widget::text(tr("settings-library-roots")))
+                        .extend(draft.library_roots.iter().enumerate().map(|(index, root)| {
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(root.display().to_string()).width(Fill))
+                                .push(
+                                    widget::button(widget::text(tr("button-remove")))
+                                        .padding(3)
+                                        .style(widget::button::secondary)
+                                        .on_press(Message::RemoveLibraryRoot(index)),
+                                )
+                                .into()
+                        }))
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(
+                                    widget::text_input(
+                                        &tr("settings-directory"),
+                                        library_root_input,
+                                    )
+                                    .on_input(Message::SetLibraryRootInput)
+                                    .on_submit(Message::AddLibraryRoot)
+                                    .padding(3),
+                                )
+                                .push(
+                                    widget::button(widget::text(tr("button-add")))
+                                        .padding(3)
+                                        .on_press(Message::AddLibraryRoot),
+                                ),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-card-badges")))
+                                .extend(CardBadge::ALL.map(|badge| {
+                                    widget::checkbox(draft.card_badges.contains(&badge))
+                                        .label(badge.to_string())
+                                        .on_toggle(move |shown| Message::SetCardBadge(badge, shown))
+                                        .into()
+                                })),
+                        )
+                        .push(
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(tr("settings-home")))
+                                .extend(HomeTile::ALL.map(|tile| {
+                                    widget::checkbox(draft.home_tiles.contains(&tile))
+                                        .label(tile.to_string())
+                                        .on_toggle(move |shown| Message::SetHomeTile(tile, shown))
+                                        .into()
+                                })),
+                        )
+                        .extend(MouseButton::value_variants().iter().map(|&button| {
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(button.to_string()))
+                                .push(
+                                    widget::pick_list(
+                                        Action::value_variants(),
+                                        Some(
+                                            draft
+                                                .bindings
+                                                .mouse
+                                                .get(&button)
+                                                .copied()
+                                                .unwrap_or_default(),
+                                        ),
+                                        move |action| Message::SetMouseBinding(button, action),
+                                    )
+                                    .padding(3),
+                                )
+                                .into()
+                        }))
+                        .extend(FunctionKey::value_variants().iter().map(|&key| {
+                            widget::Row::new()
+                                .align_y(Center)
+                                .spacing(3)
+                                .push(widget::text(key.to_string()))
+                                .push(
+                                    widget::pick_list(
+                                        Action::value_variants(),
+                                        Some(draft.bindings.key_action(key)),
+                                        move |action| Message::SetKeyBinding(key, action),
+                                    )
+                                    .padding(3),
+                                )
+                                .into()
+                        }))
                         .pipe(widget::container)
                         .style(widget::container::bordered_box)
                         .padding(5),
@@ -94,15 +730,58 @@ impl Window {
                 .push(widget::space::vertical())
                 .push(
                     widget::Row::new()
+                        .align_y(Center)
                         .spacing(3)
                         .push(
-                            widget::button("Save")
+                            widget::button(widget::text(tr("settings-back-up")))
+                                .padding(3)
+                                .on_press(Message::BackUpProfile),
+                        )
+                        .push(
+                            widget::pick_list(
+                                backups,
+                                selected_backup.map(String::from),
+                                Message::SelectBackup,
+                            )
+                            .placeholder(tr("settings-backups"))
+                            .padding(3),
+                        )
+                        .push(
+                            widget::button(widget::text(tr("settings-restore")))
+                                .padding(3)
+                                .style(widget::button::danger)
+                                .on_press_maybe(
+                                    selected_backup
+                                        .is_some()
+                                        .then_some(Message::RestoreBackup(window_id)),
+                                ),
+                        )
+                        .pipe(widget::container)
+                        .style(widget::container::bordered_box)
+                        .padding(5),
+                )
+                .push(
+                    widget::Row::new()
+                        .spacing(3)
+                        .push(
+                            widget::button(widget::text(tr("button-apply")))
+                                .padding(3)
+                                .on_press_maybe(is_dirty.then_some(Message::ApplySettings)),
+                        )
+                        .push(
+                            widget::button(widget::text(tr("button-revert")))
+                                .padding(3)
+                                .style(widget::button::secondary)
+                                .on_press_maybe(is_dirty.then_some(Message::RevertSettings)),
+                        )
+                        .push(
+                            widget::button(widget::text(tr("button-save")))
                                 .padding(3)
                                 .on_press(Message::SaveSettings)
                                 .style(widget::button::success),
                         )
                         .push(
-                            widget::button("Load")
+                            widget::button(widget::text(tr("button-load")))
                                 .padding(3)
                                 .on_press(Message::ReloadSettigns),
                         )
@@ -112,7 +791,37 @@ impl Window {
                         .pipe(widget::container)
                         .width(Fill)
                         .align_x(Alignment::End),
-                ),
+                )
+                .into(),
+            Window::Reader(reader) => reader.view(window_id, thumbnail_cache),
+            Window::Home(home) => home.view(window_id, &settings.home_tiles),
+            Window::History => history::view(undo_history),
+            Window::Help => help::view(&settings.bindings),
+            Window::About(about) => about.view(window_id),
+            Window::Stats(statistics) => statistics.view(settings.size_units),
+            Window::Calendar(calendar) => calendar.view(window_id),
+            Window::Trash(trashed) => trash::view(trashed),
+            Window::Metadata(editor) => editor.view(window_id),
+        };
+        match dialog {
+            Some(dialog) => widget::Stack::new()
+                .push(content)
+                .push(dialog.view())
+                .into(),
+            None => content,
         }
     }
 }
+
+/// Hint shown over the pane dragged items would be dropped onto.
+fn drop_hint<'a>() -> Element<'a, Message> {
+    widget::text(tr("pane-drop-hint"))
+        .pipe(widget::container)
+        .style(widget::container::bordered_box)
+        .padding(5)
+        .pipe(widget::container)
+        .center_x(Fill)
+        .align_bottom(Fill)
+        .padding(10)
+        .into()
+}
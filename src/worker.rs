@@ -0,0 +1,129 @@
+//! [WorkerPool] impl.
+
+use ::core::{
+    num::NonZero,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+use ::std::{sync::Arc, thread};
+
+use ::rayon::{ThreadPool, ThreadPoolBuilder};
+
+/// Max amount of worker threads, archive loading is mostly bound by disk access.
+const MAX_THREADS: usize = 4;
+
+/// Pause between steps of background work while it is throttled.
+const THROTTLE_PAUSE: Duration = Duration::from_millis(5);
+
+/// Token used to cancel jobs which have not yet started.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Cancel jobs spawned with this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Check if two tokens are clones of the same token.
+    pub fn is_same(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Switch shared with background work slowing it down while set, such as scans and
+/// indexing while running on battery. Work already running slows down when it is set.
+#[derive(Debug, Clone, Default)]
+pub struct Throttle(Arc<AtomicBool>);
+
+impl Throttle {
+    /// Set whether background work is throttled.
+    pub fn set(&self, throttled: bool) {
+        self.0.store(throttled, Ordering::Relaxed);
+    }
+
+    /// Check if background work is throttled.
+    pub fn is_throttled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Block the current thread before the next step of background work, if throttled.
+    pub fn pause(&self) {
+        if self.is_throttled() {
+            thread::sleep(THROTTLE_PAUSE);
+        }
+    }
+
+    /// Wait before the next step of background work, if throttled.
+    pub async fn pause_async(&self) {
+        if self.is_throttled() {
+            ::smol::Timer::after(THROTTLE_PAUSE).await;
+        }
+    }
+}
+
+/// Bounded pool of threads for blocking work such as cover extraction.
+#[derive(Debug)]
+pub struct WorkerPool {
+    /// Thread pool, if it could be created, otherwise the global pool is used.
+    pool: Option<ThreadPool>,
+    /// Amount of jobs queued or running.
+    queued: Arc<AtomicUsize>,
+}
+
+impl Default for WorkerPool {
+    fn default() -> Self {
+        let threads = thread::available_parallelism()
+            .map_or(1, NonZero::get)
+            .min(MAX_THREADS);
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("worker-{index}"))
+            .build()
+            .map_err(|err| ::log::warn!("could not create worker pool, using global pool\n{err}"))
+            .ok();
+        Self {
+            pool,
+            queued: Arc::default(),
+        }
+    }
+}
+
+impl WorkerPool {
+    /// Get amount of jobs queued or running.
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    /// Spawn a job on the pool, resolving to [None] if it was cancelled before starting.
+    pub fn spawn<T, F>(
+        &self,
+        cancel: &CancelToken,
+        job: F,
+    ) -> impl Future<Output = Option<T>> + use<T, F>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (tx, rx) = ::flume::bounded(1);
+        let cancel = cancel.clone();
+        let queued = Arc::clone(&self.queued);
+        queued.fetch_add(1, Ordering::Relaxed);
+        let job = move || {
+            if !cancel.is_cancelled() {
+                _ = tx.send(job());
+            }
+            queued.fetch_sub(1, Ordering::Relaxed);
+        };
+        match &self.pool {
+            Some(pool) => pool.spawn(job),
+            None => ::rayon::spawn(job),
+        }
+        async move { rx.recv_async().await.ok() }
+    }
+}
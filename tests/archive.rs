@@ -0,0 +1,399 @@
+//! Tests of archive reading and writing, archives may come from anywhere and reading them
+//! must fail gracefully instead of panicking.
+
+use ::std::{
+    io::{Cursor, Write},
+    path::{Path, PathBuf},
+};
+
+use ::arkiv_katalog::{
+    archive::{
+        Adjustments, Archive, ComicInfo, Cover, Page, cover_animation, extract_to,
+        natural_page_indices, pack_dir, preview, preview_cover, read_comic_info, write_comic_info,
+    },
+    text::CaseFolding,
+};
+use ::bytes::Bytes;
+use ::proptest::prelude::*;
+use ::tempfile::TempDir;
+use ::zip::{ZipWriter, write::SimpleFileOptions};
+
+/// Write file with given name and content to a new temporary directory.
+fn write_file(name: &str, content: &[u8]) -> (TempDir, PathBuf) {
+    let dir = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    let path = dir.path().join(name);
+    ::std::fs::write(&path, content).expect("temporary file should be writable");
+    (dir, path)
+}
+
+/// Create a zip archive containing the given entries.
+fn zip<'a>(entries: impl IntoIterator<Item = (&'a str, &'a [u8])>) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in entries {
+        zip.start_file(name, SimpleFileOptions::default())
+            .expect("entry should be writable");
+        zip.write_all(content).expect("entry should be writable");
+    }
+    zip.finish()
+        .expect("zip archive should be writable")
+        .into_inner()
+}
+
+/// Open archive and read all of its pages and metadata, ignoring errors.
+fn read_all(name: &str, content: &[u8]) {
+    let (_dir, path) = write_file(name, content);
+    if let Ok(mut archive) = Archive::open(&path) {
+        _ = archive.metadata();
+        for index in 0..=archive.page_count() {
+            _ = archive.page(index);
+        }
+    }
+    _ = preview(&path, 64);
+}
+
+/// Get files within directory and its subdirectories.
+fn files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for entry in ::std::fs::read_dir(dir).expect("directory should be readable") {
+        let path = entry.expect("directory should be readable").path();
+        if path.is_dir() {
+            files.extend(self::files(&path));
+        } else {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Strategy for names of zip entries.
+fn entry_name() -> impl Strategy<Value = String> {
+    (
+        "[a-z0-9/._-]{1,12}",
+        prop::sample::select(&["jpg", "png", "webp", "txt", "xml", ""][..]),
+    )
+        .prop_map(|(stem, ext)| format!("{stem}.{ext}"))
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_bytes_do_not_panic(
+        content in prop::collection::vec(any::<u8>(), 0..2048),
+        ext in prop::sample::select(&["cbz", "zip", "epub"][..]),
+    ) {
+        read_all(&format!("archive.{ext}"), &content);
+    }
+
+    #[test]
+    fn corrupted_zip_does_not_panic(
+        entries in prop::collection::btree_map(entry_name(), prop::collection::vec(any::<u8>(), 0..64), 0..8),
+        flips in prop::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..16),
+    ) {
+        let mut content = zip(entries.iter().map(|(name, content)| (name.as_str(), content.as_slice())));
+        for (index, byte) in flips {
+            let index = index.index(content.len());
+            content[index] ^= byte;
+        }
+        read_all("archive.cbz", &content);
+    }
+
+    #[test]
+    fn cbz_pages_are_images_in_natural_order(
+        entries in prop::collection::btree_map(entry_name(), prop::collection::vec(any::<u8>(), 0..64), 0..8),
+    ) {
+        let (_dir, path) = write_file(
+            "archive.cbz",
+            &zip(entries.iter().map(|(name, content)| (name.as_str(), content.as_slice()))),
+        );
+        let mut images = entries
+            .iter()
+            .filter(|(name, _)| {
+                Path::new(name)
+                    .extension()
+                    .is_some_and(|ext| ["jpg", "png", "webp"].iter().any(|e| ext == *e))
+            })
+            .collect::<Vec<_>>();
+        images.sort_by(|(a, _), (b, _)| CaseFolding::Unicode.natural_cmp(a, b));
+
+        let mut archive = Archive::open(&path).expect("valid zip archive should open");
+        prop_assert_eq!(archive.page_count(), images.len());
+        for (index, (_, content)) in images.into_iter().enumerate() {
+            let Page::Encoded(page) = archive.page(index).expect("page should be readable") else {
+                panic!("zip pages should be encoded images");
+            };
+            prop_assert_eq!(page, Bytes::from(content.clone()));
+        }
+        prop_assert!(archive.page(archive.page_count()).is_err());
+    }
+
+    #[test]
+    fn arbitrary_epub_package_does_not_panic(
+        package in "(<[a-z:]{0,8}( [a-z-]{1,8}=\"[a-z0-9./_-]{0,12}\"){0,3}/?>|</[a-z]{0,8}>|[a-z ]{0,8}){0,24}",
+        images in prop::collection::btree_map(entry_name(), prop::collection::vec(any::<u8>(), 0..16), 0..4),
+    ) {
+        let container = r#"<?xml version="1.0"?>
+<container xmlns="urn:oasis:names:tc:opendocument:xmlns:container" version="1.0">
+  <rootfiles><rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/></rootfiles>
+</container>"#;
+        let content = zip(
+            [("META-INF/container.xml", container.as_bytes()), ("OEBPS/content.opf", package.as_bytes())]
+                .into_iter()
+                .chain(images.iter().map(|(name, content)| (name.as_str(), content.as_slice()))),
+        );
+        read_all("book.epub", &content);
+    }
+
+    #[test]
+    fn extracted_files_stay_within_directory(
+        entries in prop::collection::btree_map("(\\.\\./|/|[a-z]{1,3}/){0,3}[a-z]{1,3}\\.jpg", prop::collection::vec(any::<u8>(), 0..16), 1..6),
+    ) {
+        let content = zip(entries.iter().map(|(name, content)| (name.as_str(), content.as_slice())));
+        let (dir, path) = write_file("archive.cbz", &content);
+        let target = dir.path().join("library").join("extracted");
+        ::std::fs::create_dir_all(&target).expect("temporary directory should be writable");
+        let extracted = extract_to(&path, &target, &Default::default(), |_, _| {});
+        for file in files(dir.path()).into_iter().filter(|file| *file != path) {
+            let extracted = extracted.as_ref().expect("files are only left by successful extraction");
+            prop_assert!(file.starts_with(extracted), "{file:?} is outside {extracted:?}");
+        }
+    }
+
+    #[test]
+    fn arbitrary_images_do_not_panic(content in prop::collection::vec(any::<u8>(), 0..4096)) {
+        _ = Page::Encoded(Bytes::from(content)).thumbnail(64);
+    }
+
+    #[test]
+    fn mismatched_pixels_do_not_panic(
+        width in 0u32..64,
+        height in 0u32..64,
+        pixels in prop::collection::vec(any::<u8>(), 0..1024),
+    ) {
+        _ = Page::Rgba { width, height, pixels: Bytes::from(pixels) }.thumbnail(16);
+    }
+}
+
+#[test]
+fn packed_pages_are_ordered_naturally() {
+    let dir = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    let images = dir.path().join("Series v01");
+    let temp = dir.path().join("temp");
+    for dir in [&images, &temp] {
+        ::std::fs::create_dir(dir).expect("temporary directory should be writable");
+    }
+    for (name, content) in [
+        ("page 10.png", "ten"),
+        ("page 2.png", "two"),
+        ("Page 1.jpg", "one"),
+        ("notes.txt", "notes"),
+    ] {
+        ::std::fs::write(images.join(name), content).expect("temporary file should be writable");
+    }
+
+    let path = pack_dir(&images, &temp, None).expect("directory should be packed");
+    assert_eq!(path, dir.path().join("Series v01.cbz"));
+    let mut archive = Archive::open(&path).expect("packed archive should be readable");
+    let pages = (0..archive.page_count())
+        .map(|index| match archive.page(index) {
+            Ok(Page::Encoded(bytes)) => bytes,
+            _ => panic!("page {index} should be readable"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(pages, ["one", "two", "ten"]);
+}
+
+#[test]
+fn pages_ordered_by_bytes_map_to_natural_order() {
+    let (_dir, path) = write_file(
+        "archive.cbz",
+        &zip([
+            ("p10.jpg", b"ten".as_slice()),
+            ("p2.jpg", b"two"),
+            ("p1.jpg", b"one"),
+            ("Cover.png", b"cover"),
+            ("notes.txt", b"notes"),
+        ]),
+    );
+    assert_eq!(
+        natural_page_indices(&path).expect("zip archive should be readable"),
+        [0, 1, 3, 2]
+    );
+}
+
+#[test]
+fn written_comic_info_keeps_pages_and_other_elements() {
+    let existing = concat!(
+        "<?xml version=\"1.0\"?>\n<ComicInfo>\n  <Title>Old</Title>\n",
+        "  <Summary>Kept &amp; unchanged</Summary>\n</ComicInfo>\n",
+    );
+    let content = zip([
+        ("01.png", b"one".as_slice()),
+        ("comicinfo.xml", existing.as_bytes()),
+    ]);
+    let (dir, path) = write_file("Series v01.cbz", &content);
+    let info = ComicInfo {
+        title: "New <Title>".to_owned(),
+        series: "Series".to_owned(),
+        year: "2024".to_owned(),
+        ..ComicInfo::default()
+    };
+
+    write_comic_info(&path, dir.path(), &info).expect("metadata should be written");
+    assert_eq!(
+        read_comic_info(&path).expect("metadata should be readable"),
+        info
+    );
+    let mut zip = ::zip::ZipArchive::new(::std::fs::File::open(&path).expect("archive exists"))
+        .expect("archive should be a zip archive");
+    assert_eq!(zip.len(), 2);
+    let mut xml = String::new();
+    ::std::io::Read::read_to_string(
+        &mut zip.by_name("ComicInfo.xml").expect("metadata should exist"),
+        &mut xml,
+    )
+    .expect("metadata should be readable");
+    assert!(xml.contains("<Summary>Kept &amp; unchanged</Summary>"));
+    assert!(!xml.contains("Old"));
+    let mut archive = Archive::open(&path).expect("archive should be readable");
+    assert!(matches!(archive.page(0), Ok(Page::Encoded(bytes)) if bytes == "one"));
+}
+
+/// Encode a jpeg image of given size.
+fn jpeg(width: u32, height: u32) -> Vec<u8> {
+    let mut content = Cursor::new(Vec::new());
+    ::image::RgbImage::new(width, height)
+        .write_to(&mut content, ::image::ImageFormat::Jpeg)
+        .expect("image should be encodable");
+    content.into_inner()
+}
+
+#[test]
+fn chosen_covers_fall_back_to_first_page() {
+    let (first, second, external) = (jpeg(4, 2), jpeg(2, 6), jpeg(3, 3));
+    let (dir, path) = write_file(
+        "Series v01.cbz",
+        &zip([("01.jpg", first.as_slice()), ("02.jpg", second.as_slice())]),
+    );
+    let image = dir.path().join("cover.jpg");
+    ::std::fs::write(&image, external).expect("temporary file should be writable");
+
+    let size = |cover: Cover| match preview_cover(&path, 64, &cover) {
+        Ok((Page::Rgba { width, height, .. }, _)) => (width, height),
+        _ => panic!("cover {cover:?} should be readable"),
+    };
+    assert_eq!(size(Cover::Page(1)), (2, 6));
+    assert_eq!(size(Cover::Page(5)), (4, 2));
+    assert_eq!(size(Cover::Image(image)), (3, 3));
+    assert_eq!(size(Cover::Image(dir.path().join("missing.jpg"))), (4, 2));
+}
+
+/// Encode an animated gif of given size with a frame for each delay in milliseconds.
+fn gif(width: u32, height: u32, delays: &[u32]) -> Vec<u8> {
+    let mut content = Vec::new();
+    ::image::codecs::gif::GifEncoder::new(&mut content)
+        .encode_frames(delays.iter().map(|&delay| {
+            ::image::Frame::from_parts(
+                ::image::RgbaImage::new(width, height),
+                0,
+                0,
+                ::image::Delay::from_numer_denom_ms(delay, 1),
+            )
+        }))
+        .expect("animation should be encodable");
+    content
+}
+
+#[test]
+fn animated_covers_are_decoded_into_downscaled_frames() {
+    let (animated, still) = (gif(8, 4, &[50, 200, 0]), gif(8, 4, &[50]));
+    let (_dir, path) = write_file(
+        "Series v01.cbz",
+        &zip([
+            ("01.gif", animated.as_slice()),
+            ("02.gif", still.as_slice()),
+            ("03.jpg", jpeg(8, 4).as_slice()),
+        ]),
+    );
+
+    let frames = cover_animation(&path, 4, &Cover::Page(0))
+        .expect("cover should be readable")
+        .expect("cover should be animated");
+    let delays = frames
+        .iter()
+        .map(|frame| frame.delay.as_millis())
+        .collect::<Vec<_>>();
+    // Delays too short to be meant literally use a default delay.
+    assert_eq!(delays, [50, 200, 100]);
+    for frame in &frames {
+        assert!(matches!(
+            frame.page,
+            Page::Rgba {
+                width: 4,
+                height: 2,
+                ..
+            }
+        ));
+    }
+
+    for cover in [Cover::Page(1), Cover::Page(2)] {
+        let frames = cover_animation(&path, 4, &cover).expect("cover should be readable");
+        assert!(frames.is_none(), "cover {cover:?} should not be animated");
+    }
+}
+
+#[test]
+fn dominant_color_prefers_saturated_colors_and_ignores_transparency() {
+    let page = |pixels: &[[u8; 4]]| Page::Rgba {
+        width: u32::try_from(pixels.len()).expect("page should be small"),
+        height: 1,
+        pixels: Bytes::from(pixels.concat()),
+    };
+    let (gray, red, clear) = ([128, 128, 128, 255], [200, 20, 30, 255], [0, 0, 255, 0]);
+
+    assert_eq!(
+        page(&[gray, gray, red]).dominant_color(),
+        Some([200, 20, 30])
+    );
+    assert_eq!(page(&[gray; 8]).dominant_color(), Some([128, 128, 128]));
+    assert_eq!(
+        page(&[clear, clear, gray]).dominant_color(),
+        Some([128, 128, 128])
+    );
+    assert_eq!(page(&[clear]).dominant_color(), None);
+    assert_eq!(Page::Encoded(Bytes::new()).dominant_color(), None);
+}
+
+#[test]
+fn adjusted_pages_change_colors_but_not_alpha() {
+    let page = || Page::Rgba {
+        width: 2,
+        height: 1,
+        pixels: Bytes::from_static(&[0, 128, 255, 10, 200, 100, 50, 255]),
+    };
+    let pixels = |page: Page| match page {
+        Page::Rgba { pixels, .. } => pixels.to_vec(),
+        Page::Encoded(_) => panic!("adjusted page should be decoded"),
+    };
+
+    let neutral = page()
+        .adjusted(&Adjustments::default())
+        .expect("page should be adjustable");
+    assert_eq!(pixels(neutral), pixels(page()));
+
+    let inverted = Adjustments {
+        invert: true,
+        ..Adjustments::default()
+    };
+    let inverted = page()
+        .adjusted(&inverted)
+        .expect("page should be adjustable");
+    assert_eq!(pixels(inverted), [255, 127, 0, 10, 55, 155, 205, 255]);
+
+    let brightened = Adjustments {
+        brightness: 0.2,
+        ..Adjustments::default()
+    };
+    let brightened = page()
+        .adjusted(&brightened)
+        .expect("page should be adjustable");
+    assert_eq!(pixels(brightened), [51, 179, 255, 10, 251, 151, 101, 255]);
+}
@@ -0,0 +1,68 @@
+//! Tests of backing up and restoring profiles.
+
+use ::std::fs;
+
+use ::arkiv_katalog::Cli;
+use ::clap::Parser;
+use ::rusqlite::Connection;
+
+#[test]
+fn restored_backup_replaces_catalogue_and_config() {
+    let dir = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    let portable = dir.path().join("profile");
+    let backup = dir.path().join("backup.zip");
+    let run = |args: &[&str]| {
+        Cli::parse_from(
+            ["arkiv-katalog", "--quiet", "--portable"]
+                .into_iter()
+                .chain([portable.to_str().expect("path should be UTF-8")])
+                .chain(args.iter().copied()),
+        )
+        .run()
+        .expect("command should succeed")
+    };
+    let archive = dir.path().join("Series v01.cbz");
+    fs::write(&archive, b"").expect("file should be writable");
+    let list = dir.path().join("list.txt");
+    fs::write(&list, archive.to_str().expect("path should be UTF-8"))
+        .expect("file should be writable");
+
+    run(&[
+        "list",
+        "import",
+        "Reading",
+        list.to_str().expect("path should be UTF-8"),
+    ]);
+    let xdg_dirs = Cli::parse_from([
+        "arkiv-katalog",
+        "--portable",
+        portable.to_str().expect("path should be UTF-8"),
+    ])
+    .xdg_dirs();
+    let config = xdg_dirs
+        .place_config_file("config.toml")
+        .expect("config directory should be creatable");
+    fs::write(&config, "continue_reading_shelf = false\n").expect("config should be writable");
+    run(&["backup", backup.to_str().expect("path should be UTF-8")]);
+
+    run(&["list", "delete", "Reading"]);
+    fs::write(&config, "").expect("config should be writable");
+    run(&["restore", backup.to_str().expect("path should be UTF-8")]);
+
+    let catalogue = xdg_dirs
+        .find_data_file("catalogue.sqlite")
+        .expect("catalogue should exist");
+    let lists = Connection::open(&catalogue)
+        .expect("catalogue should be openable")
+        .query_row("SELECT COUNT(*) FROM reading_lists", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .expect("reading lists should be countable");
+    assert_eq!(lists, 1);
+    assert!(catalogue.with_extension("sqlite.bak").exists());
+    assert!(
+        fs::read_to_string(&config)
+            .expect("config should be readable")
+            .contains("continue_reading_shelf = false")
+    );
+}
@@ -0,0 +1,34 @@
+//! Tests of command lines produced by application.
+
+use ::std::path::PathBuf;
+
+use ::arkiv_katalog::{Cli, ThemeArg};
+use ::clap::{Parser, ValueEnum};
+
+#[test]
+fn launch_args_parse_back_to_session() {
+    let cli = Cli::parse_from([
+        "arkiv-katalog",
+        "--profile",
+        "work",
+        "--portable",
+        "/media/usb/katalog",
+    ]);
+    let theme = ThemeArg::value_variants()
+        .last()
+        .copied()
+        .expect("themes should exist");
+    let paths = [
+        PathBuf::from("/comics/-dashed series"),
+        PathBuf::from("/comics/Series v01.cbz"),
+    ];
+
+    let parsed = Cli::parse_from(
+        ::core::iter::once("arkiv-katalog".into()).chain(cli.launch_args(theme, &paths)),
+    );
+    assert_eq!(parsed.profile, "work");
+    assert_eq!(parsed.portable, cli.portable);
+    assert_eq!(parsed.theme, Some(theme));
+    assert_eq!(parsed.paths, paths);
+    assert!(parsed.command.is_none());
+}
@@ -0,0 +1,44 @@
+//! Tests of touchpad swipes.
+
+use ::core::time::Duration;
+use ::std::time::Instant;
+
+use ::arkiv_katalog::gesture::{Swipe, Turn};
+
+#[test]
+fn swipe_turns_once_despite_reverse_jitter() {
+    let mut swipe = Swipe::default();
+    let start = Instant::now();
+    let at = |ms| start + Duration::from_millis(ms);
+
+    assert_eq!(swipe.scrolled(-60.0, 0.0, 100.0, at(0)), None);
+    assert_eq!(
+        swipe.scrolled(-60.0, 0.0, 100.0, at(10)),
+        Some(Turn::Forward)
+    );
+    // Fingers lifting move slightly back, which must not undo the turn.
+    assert_eq!(swipe.scrolled(5.0, 0.0, 100.0, at(20)), None);
+    assert_eq!(swipe.scrolled(-200.0, 0.0, 100.0, at(30)), None);
+    assert_eq!(swipe.scrolled(200.0, 0.0, 100.0, at(40)), None);
+
+    // A new swipe after a pause turns again, starting from no travel.
+    assert_eq!(swipe.scrolled(90.0, 0.0, 100.0, at(400)), None);
+    assert_eq!(swipe.scrolled(20.0, 0.0, 100.0, at(410)), Some(Turn::Back));
+}
+
+#[test]
+fn swipe_travel_is_dropped_between_swipes() {
+    let mut swipe = Swipe::default();
+    let start = Instant::now();
+
+    assert_eq!(swipe.scrolled(-90.0, 0.0, 100.0, start), None);
+    let later = start + Duration::from_secs(1);
+    assert_eq!(swipe.scrolled(-20.0, 0.0, 100.0, later), None);
+    // Vertical scroll ends a swipe as well.
+    assert_eq!(
+        swipe.scrolled(-90.0, 0.0, 100.0, later),
+        Some(Turn::Forward)
+    );
+    assert_eq!(swipe.scrolled(0.0, 30.0, 100.0, later), None);
+    assert_eq!(swipe.scrolled(-90.0, 0.0, 100.0, later), None);
+}
@@ -0,0 +1,49 @@
+//! Tests of translations of user interface text.
+
+use ::arkiv_katalog::i18n;
+
+#[test]
+fn every_locale_has_every_message() {
+    let mut bundles = i18n::bundles();
+    let (_, english) = bundles.next().unwrap();
+    for (tag, ids) in bundles {
+        assert_eq!(ids, english, "messages of {tag} differ from english");
+    }
+}
+
+#[test]
+fn messages_are_parsed_and_formatted() {
+    let messages = i18n::parse(
+        "# comment\n\
+         -term = Term\n\
+         greeting = Hello { $name }, { \"{\" }braces{ \"}\" }\n\
+         multiline =\n    First\n    Second\n",
+    );
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        i18n::format(&messages["greeting"], &[("name", "Reader")]),
+        "Hello Reader, {braces}"
+    );
+    assert_eq!(messages["multiline"], "First\nSecond");
+    assert_eq!(i18n::format("Missing { $name }", &[]), "Missing { $name }");
+}
+
+#[test]
+fn select_expressions_pick_variants() {
+    let messages = i18n::parse(
+        "archives =\n    { $count ->\n        [0] No archives\n        [one] One archive\n       \
+         *[other] { $count } archives\n    }\n\
+         kind = { $kind ->\n    [cbz] Zip\n   *[other] Other { \"{\" }{ $kind }{ \"}\" }\n    }\n",
+    );
+    let archives = |count: &str| i18n::format(&messages["archives"], &[("count", count)]);
+    assert_eq!(archives("0"), "No archives");
+    assert_eq!(archives("1"), "One archive");
+    assert_eq!(archives("12"), "12 archives");
+    assert_eq!(archives("1.5"), "1.5 archives");
+    assert_eq!(i18n::format(&messages["kind"], &[("kind", "cbz")]), "Zip");
+    assert_eq!(
+        i18n::format(&messages["kind"], &[("kind", "rar")]),
+        "Other {rar}"
+    );
+    assert_eq!(i18n::format(&messages["kind"], &[]), "Other {{ $kind }}");
+}
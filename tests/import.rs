@@ -0,0 +1,150 @@
+//! Tests of importing books of Komga and Calibre databases.
+
+use ::std::{fs, path::PathBuf};
+
+use ::arkiv_katalog::import::{self, Progress, Source};
+use ::rusqlite::Connection;
+
+#[test]
+fn calibre_books_are_read_with_series_authors_and_tags() {
+    let library = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    let database = library.path().join("metadata.db");
+    Connection::open(&database)
+        .expect("database should be creatable")
+        .execute_batch(
+            "CREATE TABLE books (id INTEGER PRIMARY KEY, title TEXT, series_index REAL,
+                 pubdate TEXT, path TEXT);
+             CREATE TABLE data (id INTEGER PRIMARY KEY, book INTEGER, format TEXT,
+                 uncompressed_size INTEGER, name TEXT);
+             CREATE TABLE series (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE books_series_link (id INTEGER PRIMARY KEY, book INTEGER, series INTEGER);
+             CREATE TABLE authors (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE books_authors_link (id INTEGER PRIMARY KEY, book INTEGER, author INTEGER);
+             CREATE TABLE tags (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE books_tags_link (id INTEGER PRIMARY KEY, book INTEGER, tag INTEGER);
+             CREATE TABLE custom_columns (id INTEGER PRIMARY KEY, label TEXT, datatype TEXT);
+             CREATE TABLE custom_column_1 (id INTEGER PRIMARY KEY, book INTEGER, value BOOL);
+             INSERT INTO books VALUES
+                 (1, 'First', 1.0, '2019-05-01 00:00:00+00:00', 'Author/First (1)'),
+                 (2, 'Novel', 1.0, '0101-01-01 00:00:00+00:00', 'Author/Novel (2)');
+             INSERT INTO data VALUES (1, 1, 'CBZ', 10, 'First - Author'),
+                 (2, 2, 'MOBI', 10, 'Novel - Author');
+             INSERT INTO series VALUES (1, 'Series');
+             INSERT INTO books_series_link VALUES (1, 1, 1);
+             INSERT INTO authors VALUES (1, 'Author'), (2, 'Artist');
+             INSERT INTO books_authors_link VALUES (1, 1, 1), (2, 1, 2);
+             INSERT INTO tags VALUES (1, 'Action');
+             INSERT INTO books_tags_link VALUES (1, 1, 1);
+             INSERT INTO custom_columns VALUES (1, 'read', 'bool');
+             INSERT INTO custom_column_1 VALUES (1, 1, 1);",
+        )
+        .expect("database should be populatable");
+
+    let records =
+        import::read(Source::Calibre, &database, None).expect("database should be readable");
+    let [record] = records.as_slice() else {
+        panic!("unexpected records {records:?}");
+    };
+    assert_eq!(
+        record.path,
+        library.path().join("Author/First (1)/First - Author.cbz")
+    );
+    assert_eq!(record.info.title, "First");
+    assert_eq!(record.info.series, "Series");
+    assert_eq!(record.info.number, "1");
+    assert_eq!(record.info.writer, "Author, Artist");
+    assert_eq!(record.info.year, "2019");
+    assert_eq!(record.info.tags, "Action");
+    assert_eq!(record.progress, Some(Progress::Finished));
+}
+
+#[test]
+fn komga_books_are_read_with_progress_of_user() {
+    let dir = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    let database = dir.path().join("database.sqlite");
+    Connection::open(&database)
+        .expect("database should be creatable")
+        .execute_batch(
+            "CREATE TABLE BOOK (ID TEXT, URL TEXT, FILE_SIZE INTEGER, SERIES_ID TEXT);
+             CREATE TABLE BOOK_METADATA (BOOK_ID TEXT, TITLE TEXT, NUMBER TEXT,
+                 RELEASE_DATE TEXT);
+             CREATE TABLE SERIES_METADATA (SERIES_ID TEXT, TITLE TEXT);
+             CREATE TABLE BOOK_METADATA_AUTHOR (BOOK_ID TEXT, NAME TEXT, ROLE TEXT);
+             CREATE TABLE BOOK_METADATA_TAG (BOOK_ID TEXT, TAG TEXT);
+             CREATE TABLE SERIES_METADATA_TAG (SERIES_ID TEXT, TAG TEXT);
+             CREATE TABLE \"USER\" (ID TEXT, EMAIL TEXT);
+             CREATE TABLE READ_PROGRESS (BOOK_ID TEXT, USER_ID TEXT, PAGE INTEGER,
+                 COMPLETED BOOLEAN, LAST_MODIFIED_DATE TEXT);
+             INSERT INTO BOOK VALUES ('b1', 'file:/comics/Series/Series%20v01.cbz', 10, 's1');
+             INSERT INTO BOOK_METADATA VALUES ('b1', 'Volume 1', '1', NULL);
+             INSERT INTO SERIES_METADATA VALUES ('s1', 'Series');
+             INSERT INTO BOOK_METADATA_AUTHOR VALUES ('b1', 'Writer', 'writer'),
+                 ('b1', 'Penciller', 'penciller');
+             INSERT INTO BOOK_METADATA_TAG VALUES ('b1', 'Action');
+             INSERT INTO SERIES_METADATA_TAG VALUES ('s1', 'Drama');
+             INSERT INTO \"USER\" VALUES ('u1', 'a@example.com'), ('u2', 'b@example.com');
+             INSERT INTO READ_PROGRESS VALUES ('b1', 'u1', 5, 0, '2024-01-01'),
+                 ('b1', 'u2', 20, 1, '2024-02-01');",
+        )
+        .expect("database should be populatable");
+
+    let records = import::read(Source::Komga, &database, Some("a@example.com"))
+        .expect("database should be readable");
+    let [record] = records.as_slice() else {
+        panic!("unexpected records {records:?}");
+    };
+    assert_eq!(record.path, PathBuf::from("/comics/Series/Series v01.cbz"));
+    assert_eq!(record.size, Some(10));
+    assert_eq!(record.info.series, "Series");
+    assert_eq!(record.info.number, "1");
+    assert_eq!(record.info.writer, "Writer");
+    assert_eq!(record.info.tags, "Action, Drama");
+    assert_eq!(record.progress, Some(Progress::Reading(4)));
+
+    let records =
+        import::read(Source::Komga, &database, None).expect("database should be readable");
+    assert_eq!(records[0].progress, Some(Progress::Finished));
+}
+
+#[test]
+fn records_match_closest_archive_or_only_archive_of_same_size() {
+    let library = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    let archive = |path: &str, size: usize| {
+        let path = library.path().join(path);
+        fs::create_dir_all(path.parent().expect("path should have a parent"))
+            .expect("directory should be creatable");
+        fs::write(&path, vec![0; size]).expect("file should be writable");
+        path
+    };
+    let archives = [
+        archive("A/Series/v01.cbz", 1),
+        archive("B/Other/v01.cbz", 2),
+        archive("A/Renamed.cbz", 3),
+    ];
+    let record = |path: &str, size| import::Record {
+        path: PathBuf::from(path),
+        size: Some(size),
+        info: Default::default(),
+        progress: None,
+    };
+    let (matched, unmatched) = import::match_records(
+        vec![
+            record("/server/Series/v01.cbz", 5),
+            record("/server/Original.cbz", 3),
+            record("/server/Missing.cbz", 4),
+        ],
+        &archives,
+    );
+    let matched = matched
+        .into_iter()
+        .map(|(path, record)| (path, record.path))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        matched,
+        [
+            (archives[0].clone(), PathBuf::from("/server/Series/v01.cbz")),
+            (archives[2].clone(), PathBuf::from("/server/Original.cbz")),
+        ]
+    );
+    assert_eq!(unmatched.len(), 1);
+}
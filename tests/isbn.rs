@@ -0,0 +1,35 @@
+//! Tests of parsing book numbers.
+
+use ::arkiv_katalog::isbn::Isbn;
+
+/// Parse text, returning the normalized digits.
+fn parse(text: &str) -> Option<String> {
+    Isbn::parse(text).map(|isbn| isbn.to_string())
+}
+
+#[test]
+fn isbn_10_and_barcodes_compare_equal() {
+    assert_eq!(parse("0-306-40615-2").as_deref(), Some("9780306406157"));
+    assert_eq!(
+        parse("ISBN 978-0-306-40615-7").as_deref(),
+        Some("9780306406157")
+    );
+    assert_eq!(
+        parse("urn:isbn:9780306406157").as_deref(),
+        Some("9780306406157")
+    );
+    assert_eq!(parse("080442957x").as_deref(), Some("9780804429573"));
+}
+
+#[test]
+fn invalid_numbers_are_rejected() {
+    for text in [
+        "0-306-40615-3",
+        "9780306406158",
+        "97803064061",
+        "978030640615X",
+        "",
+    ] {
+        assert_eq!(parse(text), None, "{text:?}");
+    }
+}
@@ -0,0 +1,71 @@
+//! Tests of parsing OPDS feeds and resolving the links in them.
+
+use ::arkiv_katalog::opds::{self, Link};
+
+/// Feed with a navigation entry, an archive and a link to its next page.
+const FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Library</title>
+  <link rel="next" href="?page=2" type="application/atom+xml;profile=opds-catalog"/>
+  <entry>
+    <title>Series</title>
+    <link rel="subsection" href="series/1" type="application/atom+xml;profile=opds-catalog;kind=acquisition"/>
+  </entry>
+  <entry>
+    <title>Series v01</title>
+    <author><name>Author</name></author>
+    <link rel="http://opds-spec.org/image/thumbnail" href="/covers/1.jpg" type="image/jpeg"/>
+    <link rel="http://opds-spec.org/acquisition" href="/books/1/file" type="application/vnd.comicbook+zip"/>
+  </entry>
+  <entry>
+    <title>Unsupported</title>
+    <link rel="http://opds-spec.org/acquisition" href="/books/2/file" type="application/x-cbr"/>
+  </entry>
+</feed>"#;
+
+#[test]
+fn feed_entries_are_parsed() {
+    let feed = opds::parse("https://host/opds/v1.2/catalog", FEED).unwrap();
+    assert_eq!(feed.title, "Library");
+    let [series, volume, next] = feed.entries.as_slice() else {
+        panic!("unexpected entries {:?}", feed.entries);
+    };
+    assert_eq!(
+        series.link,
+        Link::Navigation("https://host/opds/v1.2/series/1".to_owned())
+    );
+    assert_eq!(volume.title, "Series v01");
+    assert_eq!(volume.author.as_deref(), Some("Author"));
+    assert_eq!(
+        volume.link,
+        Link::Acquisition {
+            url: "https://host/books/1/file".to_owned(),
+            extension: "cbz",
+        }
+    );
+    assert_eq!(volume.cover.as_deref(), Some("https://host/covers/1.jpg"));
+    assert_eq!(
+        next.link,
+        Link::Navigation("https://host/opds/v1.2/catalog?page=2".to_owned())
+    );
+}
+
+#[test]
+fn links_are_resolved_against_feed() {
+    let base = "http://host:8080/opds/feed?page=1";
+    assert_eq!(opds::resolve(base, "https://other/x"), "https://other/x");
+    assert_eq!(opds::resolve(base, "//other/x"), "http://other/x");
+    assert_eq!(opds::resolve(base, "/x"), "http://host:8080/x");
+    assert_eq!(opds::resolve(base, "x"), "http://host:8080/opds/x");
+    assert_eq!(
+        opds::resolve(base, "?page=2"),
+        "http://host:8080/opds/feed?page=2"
+    );
+    assert_eq!(opds::resolve("http://host", "x"), "http://host/x");
+}
+
+#[test]
+fn documents_other_than_atom_feeds_are_rejected() {
+    assert!(opds::parse("http://host", "<html></html>").is_err());
+    assert!(opds::parse("http://host", "not xml").is_err());
+}
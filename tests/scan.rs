@@ -0,0 +1,40 @@
+//! Tests of scanning directories.
+
+use ::std::fs;
+
+use ::arkiv_katalog::{Settings, ignore::IgnoreRules, scan};
+
+#[test]
+fn scan_skips_ignored_and_nomedia_entries() {
+    let dir = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    for name in ["Vol 1.cbz", ".hidden.cbz", "Vol 2.cbz.part"] {
+        fs::write(dir.path().join(name), b"").expect("file should be writable");
+    }
+    fs::create_dir(dir.path().join("Extras")).expect("directory should be creatable");
+    fs::create_dir(dir.path().join("Samples")).expect("directory should be creatable");
+    fs::write(dir.path().join("Samples/.nomedia"), b"").expect("file should be writable");
+
+    let settings = Settings {
+        skip_hidden: true,
+        ignore_patterns: vec!["*.part".to_owned()],
+        ..Settings::default()
+    };
+    let mut entries =
+        scan::scan(dir.path(), IgnoreRules::new(&settings)).expect("directory should be scannable");
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    assert_eq!(
+        entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect::<Vec<_>>(),
+        ["Extras", "Vol 1.cbz"]
+    );
+    assert!(entries.iter().all(|entry| !entry.offline));
+    assert!(
+        entries[0]
+            .metadata
+            .as_ref()
+            .is_some_and(::std::fs::Metadata::is_dir)
+    );
+}
@@ -0,0 +1,66 @@
+//! Tests of reading order of series.
+
+use ::std::{path::Path, sync::Arc};
+
+use ::arkiv_katalog::series;
+
+/// Sort names into reading order, applying a manual order.
+fn order(names: &[&str], manual: &[&str]) -> Vec<String> {
+    let mut entries = names
+        .iter()
+        .map(|name| Arc::from(Path::new(name)))
+        .collect::<Vec<Arc<Path>>>();
+    series::reading_order(&mut entries);
+    series::apply_manual_order(
+        &mut entries,
+        &manual
+            .iter()
+            .map(|name| (*name).to_owned())
+            .collect::<Vec<_>>(),
+    );
+    entries
+        .iter()
+        .map(|entry| series::entry_name(entry))
+        .collect()
+}
+
+#[test]
+fn volumes_are_ordered_numerically_with_specials_last() {
+    assert_eq!(
+        order(
+            &[
+                "Series Special.cbz",
+                "Series Vol 10.cbz",
+                "Series Vol 2 Ch 12.5.cbz",
+                "Series Vol 2.cbz",
+                "Series (2004) Vol 1.cbz",
+            ],
+            &[]
+        ),
+        [
+            "Series (2004) Vol 1.cbz",
+            "Series Vol 2.cbz",
+            "Series Vol 2 Ch 12.5.cbz",
+            "Series Vol 10.cbz",
+            "Series Special.cbz",
+        ],
+    );
+}
+
+#[test]
+fn manual_order_takes_precedence() {
+    assert_eq!(
+        order(
+            &["Series 01.cbz", "Series 02.cbz", "Series 03.cbz"],
+            &["Series 03.cbz", "Series 01.cbz"]
+        ),
+        ["Series 03.cbz", "Series 01.cbz", "Series 02.cbz"],
+    );
+}
+
+#[test]
+fn gaps_are_whole_volumes_up_to_the_last_owned() {
+    assert_eq!(series::gaps(&[1.0, 2.0, 2.5, 4.0, 6.0]), [3.0, 5.0]);
+    assert_eq!(series::gaps(&[3.0]), [1.0, 2.0]);
+    assert!(series::gaps(&[]).is_empty());
+}
@@ -0,0 +1,92 @@
+//! Tests of loading config files written by older versions, and of saving them.
+
+use ::arkiv_katalog::{CardSize, Cli, Settings};
+use ::clap::Parser;
+
+/// Write config file with given content to a new temporary directory and read it.
+fn read(content: &str) -> Settings {
+    let dir = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    let path = dir.path().join("config.toml");
+    ::std::fs::write(&path, content).expect("temporary file should be writable");
+    Settings::read(&path).expect("config file should be readable")
+}
+
+#[test]
+fn card_width_is_upgraded_to_card_size() {
+    let settings = read("card_width = 151\nswipe_distance = 80\n");
+    assert_eq!(settings.card_size, CardSize::Millimeters(40));
+    assert_eq!(settings.swipe_distance, 80);
+    assert_eq!(settings.version, Settings::default().version);
+}
+
+#[test]
+fn settings_bound_to_f2_move_to_f8_for_renaming() {
+    let settings = read("version = 1\n[bindings.keys]\nf2 = \"toggle-settings\"\n");
+    let keys = ::toml::Value::try_from(&settings.bindings)
+        .expect("bindings should be serializable")["keys"]
+        .clone();
+    assert_eq!(keys["f2"].as_str(), Some("rename"));
+    assert_eq!(keys["f8"].as_str(), Some("toggle-settings"));
+}
+
+#[test]
+fn current_config_is_read_unchanged() {
+    let settings = Settings {
+        card_size: CardSize::Spacious,
+        ..Settings::default()
+    };
+    let content = ::toml::to_string(&settings).expect("settings should be serializable");
+    assert_eq!(read(&content), settings);
+}
+
+#[test]
+fn newer_config_is_read() {
+    let settings = read("version = 1000\nunknown_setting = true\nswipe_distance = 20\n");
+    assert_eq!(settings.swipe_distance, 20);
+}
+
+#[test]
+fn invalid_config_falls_back_to_backup() {
+    let dir = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    let cli = Cli::parse_from(["arkiv-katalog".as_ref(), "--portable".as_ref(), dir.path()]);
+    let xdg_dirs = cli.xdg_dirs();
+    let with_swipe = |swipe_distance| Settings {
+        swipe_distance,
+        ..Settings::default()
+    };
+    for swipe_distance in [10, 20] {
+        with_swipe(swipe_distance)
+            .save(&xdg_dirs)
+            .expect("settings should be saved");
+    }
+    let path = xdg_dirs
+        .find_config_file("config.toml")
+        .expect("config file should exist");
+    assert_eq!(Settings::load(&xdg_dirs).ok(), Some(with_swipe(20)));
+
+    ::std::fs::write(&path, "swipe_distance = [").expect("config file should be writable");
+    assert_eq!(Settings::load(&xdg_dirs).ok(), Some(with_swipe(10)));
+
+    // The valid backup is kept when the invalid file is replaced.
+    with_swipe(30)
+        .save(&xdg_dirs)
+        .expect("settings should be saved");
+    ::std::fs::write(&path, "swipe_distance = [").expect("config file should be writable");
+    assert_eq!(Settings::load(&xdg_dirs).ok(), Some(with_swipe(10)));
+}
+
+#[test]
+fn resized_card_size_stays_within_custom_range() {
+    assert_eq!(
+        CardSize::Comfortable.resized(1),
+        CardSize::Millimeters(40 + CardSize::STEP_MILLIMETERS)
+    );
+    assert_eq!(
+        CardSize::Compact.resized(-100),
+        CardSize::Millimeters(CardSize::MIN_MILLIMETERS)
+    );
+    assert_eq!(
+        CardSize::Spacious.resized(i32::MAX),
+        CardSize::Millimeters(CardSize::MAX_MILLIMETERS)
+    );
+}
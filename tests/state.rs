@@ -0,0 +1,58 @@
+//! Tests of application state driven by messages, without a running daemon.
+
+use ::std::{path::Path, sync::Arc};
+
+use ::arkiv_katalog::{
+    CardSize, Settings,
+    testing::{Harness, Message, Window},
+};
+
+#[test]
+fn dir_window_shows_directory_in_one_pane() {
+    let dir = ::tempfile::tempdir().expect("temporary directory should be creatable");
+    let path = Arc::<Path>::from(dir.path());
+    let mut harness = Harness::new(Settings::default());
+
+    let window_id = harness.open_window(|id| Message::AddDirWindow(id, Arc::clone(&path)));
+
+    assert!(matches!(
+        harness.window(window_id),
+        Some(Window::Main { .. })
+    ));
+    let panes = harness.panes(window_id);
+    assert_eq!(panes.len(), 1);
+    let pane = harness.pane(panes[0]).expect("pane should exist");
+    assert_eq!(pane.path.as_deref(), Some(dir.path()));
+    assert!(pane.scanning);
+}
+
+#[test]
+fn settings_change_when_applied() {
+    let mut harness = Harness::new(Settings::default());
+
+    harness.update(Message::SetCardSize(CardSize::Spacious));
+    assert_eq!(harness.draft().card_size, CardSize::Spacious);
+    assert_eq!(harness.settings().card_size, CardSize::default());
+
+    harness.update(Message::ApplySettings);
+    assert_eq!(harness.settings().card_size, CardSize::Spacious);
+
+    harness.update(Message::SetCardSize(CardSize::Compact));
+    harness.update(Message::RevertSettings);
+    assert_eq!(harness.draft().card_size, CardSize::Spacious);
+}
+
+#[test]
+fn removed_window_is_forgotten() {
+    let mut harness = Harness::new(Settings::default());
+    let first = harness.open_window(Message::AddEmptyWindow);
+    let second = harness.open_window(Message::AddEmptyWindow);
+
+    harness.update(Message::RemoveWindow(first));
+
+    assert!(harness.window(first).is_none());
+    assert_eq!(
+        harness.windows().map(|(id, _)| id).collect::<Vec<_>>(),
+        [second]
+    );
+}
@@ -0,0 +1,112 @@
+//! Tests of shortening text to fit a display width, and of natural ordering.
+
+use ::core::cmp::Ordering;
+
+use ::arkiv_katalog::text::{CaseFolding, Ellipsis, OTHER_LETTER, index_letter, shorten_text};
+
+#[test]
+fn short_text_is_kept() {
+    assert_eq!(shorten_text("Series v01", 10, Ellipsis::End), "Series v01");
+    assert_eq!(
+        shorten_text("Series v01", 10, Ellipsis::Middle),
+        "Series v01"
+    );
+}
+
+#[test]
+fn long_text_is_shortened_at_end_or_middle() {
+    assert_eq!(
+        shorten_text("Series Name v03", 10, Ellipsis::End),
+        "Series ..."
+    );
+    assert_eq!(
+        shorten_text("Series Name v03", 10, Ellipsis::Middle),
+        "Seri...v03"
+    );
+}
+
+#[test]
+fn wide_characters_take_two_columns() {
+    let title = "進撃の巨人 第01巻";
+    assert_eq!(shorten_text(title, 10, Ellipsis::End), "進撃の...");
+    assert_eq!(shorten_text(title, 10, Ellipsis::Middle), "進撃...1巻");
+}
+
+#[test]
+fn emoji_sequences_are_kept_whole() {
+    let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    let title = format!("{family}{family}{family} Family");
+    let shortened = shorten_text(&title, 8, Ellipsis::End);
+    assert_eq!(shortened, format!("{family}{family}..."));
+}
+
+#[test]
+fn combining_marks_stay_with_their_base() {
+    let title = "e\u{301}".repeat(10);
+    assert_eq!(
+        shorten_text(&title, 8, Ellipsis::End),
+        format!("{}...", "e\u{301}".repeat(5))
+    );
+    assert_eq!(
+        shorten_text(&title, 8, Ellipsis::Middle),
+        format!("{}...{}", "e\u{301}".repeat(3), "e\u{301}".repeat(2))
+    );
+}
+
+#[test]
+fn numbers_are_ordered_by_value() {
+    let mut names = [
+        "Chapter 10",
+        "chapter 2",
+        "Chapter 02",
+        "Chapter 1",
+        "Appendix",
+    ];
+    names.sort_by(|a, b| CaseFolding::Unicode.natural_cmp(a, b));
+    assert_eq!(
+        names,
+        [
+            "Appendix",
+            "Chapter 1",
+            "Chapter 02",
+            "chapter 2",
+            "Chapter 10"
+        ]
+    );
+}
+
+#[test]
+fn turkic_case_folding_keeps_dotless_i() {
+    assert_eq!(CaseFolding::of_locale("tr_TR.UTF-8"), CaseFolding::Turkic);
+    assert_eq!(CaseFolding::of_locale("sv-SE"), CaseFolding::Unicode);
+    assert_eq!(
+        CaseFolding::Unicode.natural_cmp("Iz", "\u{131}a"),
+        Ordering::Less
+    );
+    assert_eq!(
+        CaseFolding::Turkic.natural_cmp("Iz", "\u{131}a"),
+        Ordering::Greater
+    );
+}
+
+#[test]
+fn glob_patterns_match_names_ignoring_case() {
+    use ::arkiv_katalog::text::glob_match;
+    assert!(glob_match("__MACOSX", "__macosx"));
+    assert!(glob_match("*.part", "Series v01.cbz.part"));
+    assert!(glob_match("*sample*", "Series v01 SAMPLE.cbz"));
+    assert!(glob_match("v?? *", "v01 Title"));
+    assert!(!glob_match("*.part", "Series v01.cbz"));
+    assert!(!glob_match("v??", "v1"));
+    assert!(glob_match("*a*b", "aXbab"));
+}
+
+#[test]
+fn titles_are_indexed_by_first_letter() {
+    assert_eq!(index_letter("batman"), 'B');
+    assert_eq!(index_letter("  Ärlighet"), 'Ä');
+    assert_eq!(index_letter("ßtraße"), 'S');
+    assert_eq!(index_letter("20th Century Boys"), OTHER_LETTER);
+    assert_eq!(index_letter("(Untitled)"), OTHER_LETTER);
+    assert_eq!(index_letter(""), OTHER_LETTER);
+}